@@ -11,7 +11,7 @@ use xnn::{Context, Tensor};
 
 fn configure<'a>(c: &'a mut Criterion, name: &str) -> BenchmarkGroup<'a, WallTime> {
     let mut group = c.benchmark_group(name);
-    group.warm_up_time(Duration::from_millis(1000));
+    group.warm_up_time(Duration::from_secs(1));
     group.measurement_time(Duration::from_secs(5));
     group.sample_size(50);
     group
@@ -141,3 +141,45 @@ pub(crate) fn bench_matmul_batched(c: &mut Criterion) {
 
     group.finish();
 }
+
+pub(crate) fn bench_matmul_structured(c: &mut Criterion) {
+    let ctx = Context::try_default().unwrap();
+    let mut group = configure(c, "tensor/matmul_structured");
+
+    let n = 1024;
+    let b = Tensor::<f32>::random_well_conditioned(&ctx, &[n, n], 1).unwrap();
+
+    #[allow(clippy::type_complexity)]
+    let cases: &[(&str, fn(&Context) -> Tensor<f32>)] = &[
+        ("well_conditioned", |ctx| {
+            Tensor::random_well_conditioned(ctx, &[1024, 1024], 2).unwrap()
+        }),
+        ("orthogonal", |ctx| {
+            Tensor::random_orthogonal(ctx, &[1024, 1024], 2).unwrap()
+        }),
+        ("tridiagonal", |ctx| {
+            Tensor::random_banded(ctx, &[1024, 1024], 1, 1, 2).unwrap()
+        }),
+        ("sparse_1pct", |ctx| {
+            Tensor::random_sparse(ctx, &[1024, 1024], 0.01, 2).unwrap()
+        }),
+    ];
+
+    group.throughput(Throughput::ElementsAndBytes {
+        elements: (2 * n * n * n) as u64,
+        bytes: (3 * n * n * size_of::<f32>()) as u64,
+    });
+
+    for &(name, generate) in cases {
+        let a = generate(&ctx);
+
+        group.bench_with_input(BenchmarkId::from_parameter(name), &a, |bencher, a| {
+            bencher.iter(|| {
+                let _ = a.matmul(&b, false, false).unwrap();
+                ctx.poll().unwrap();
+            });
+        });
+    }
+
+    group.finish();
+}
@@ -23,7 +23,7 @@ const SIZES: &[(&str, &[usize])] = &[
 
 fn configure<'a>(c: &'a mut Criterion, name: &str) -> BenchmarkGroup<'a, WallTime> {
     let mut group = c.benchmark_group(name);
-    group.warm_up_time(Duration::from_millis(1000));
+    group.warm_up_time(Duration::from_secs(1));
     group.measurement_time(Duration::from_secs(5));
     group.sample_size(100);
     group
@@ -89,6 +89,7 @@ criterion::criterion_group!(
     linalg::matmul::bench_matmul,
     linalg::matmul::bench_matmul_transpose,
     linalg::matmul::bench_matmul_batched,
+    linalg::matmul::bench_matmul_structured,
     // Math: clamp, select
     math::clamp::bench_clamp,
     math::select::bench_select,
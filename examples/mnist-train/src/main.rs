@@ -280,25 +280,14 @@ fn softmax(x: &Tensor<f32>) -> Result<Tensor<f32>, Error> {
     exp_vals.div(&sum_exp)
 }
 
-fn compute_accuracy(probs: &[f32], labels: &[u8]) -> f32 {
-    let batch_size = labels.len();
-    let mut correct = 0;
-
-    for i in 0..batch_size {
-        let start = i * OUTPUT_SIZE;
-        let pred = probs[start..start + OUTPUT_SIZE]
-            .iter()
-            .enumerate()
-            .max_by(|a, b| a.1.partial_cmp(b.1).unwrap())
-            .map(|(idx, _)| idx)
-            .unwrap();
-
-        if pred == labels[i] as usize {
-            correct += 1;
-        }
-    }
-
-    correct as f32 / batch_size as f32
+fn compute_accuracy(predictions: &[u32], labels: &[u8]) -> f32 {
+    let correct = predictions
+        .iter()
+        .zip(labels)
+        .filter(|&(&pred, &label)| pred == u32::from(label))
+        .count();
+
+    correct as f32 / labels.len() as f32
 }
 
 fn download_mnist(data_dir: &Path) -> std::io::Result<()> {
@@ -397,8 +386,8 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         let (test_images, _) = test.get_batch(&test_indices);
         let x_test = Tensor::from_shape_slice(&ctx, &[test_batch, INPUT_SIZE], &test_images)?;
         let (_, test_probs) = model.forward(&x_test)?;
-        let test_probs_vec = test_probs.to_vec()?;
-        let accuracy = compute_accuracy(&test_probs_vec, &test.labels[..test_batch]);
+        let predictions = test_probs.argmax(1)?.to_vec()?;
+        let accuracy = compute_accuracy(&predictions, &test.labels[..test_batch]);
 
         let avg_loss = total_loss / batches_per_epoch as f32;
         println!(
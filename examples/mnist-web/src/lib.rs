@@ -18,6 +18,7 @@ macro_rules! log {
 thread_local! {
     static CTX: RefCell<Option<Context>> = const { RefCell::new(None) };
     static MODEL: RefCell<Option<MnistModel>> = const { RefCell::new(None) };
+    static LOADER: RefCell<Option<WeightLoader>> = const { RefCell::new(None) };
 }
 
 /// Weight sizes.
@@ -26,6 +27,9 @@ const B1_SIZE: usize = 128;
 const W2_SIZE: usize = 128 * 10;
 const B2_SIZE: usize = 10;
 
+/// Total number of weights.
+const TOTAL_SIZE: usize = W1_SIZE + B1_SIZE + W2_SIZE + B2_SIZE;
+
 /// MNIST neural network model (784 -> 128 -> 10).
 struct MnistModel {
     ctx: Context,
@@ -38,10 +42,9 @@ struct MnistModel {
 impl MnistModel {
     /// Creates a new model from weight data.
     fn from_weights(ctx: Context, weights: &[f32]) -> Result<Self, JsValue> {
-        let expected = W1_SIZE + B1_SIZE + W2_SIZE + B2_SIZE;
-        if weights.len() != expected {
+        if weights.len() != TOTAL_SIZE {
             return Err(JsValue::from_str(&format!(
-                "Invalid weights size: {} (expected {expected})",
+                "Invalid weights size: {} (expected {TOTAL_SIZE})",
                 weights.len(),
             )));
         }
@@ -69,6 +72,37 @@ impl MnistModel {
         })
     }
 
+    /// Creates a model with zeroed weights, to be filled by [`Self::write_weights`].
+    fn zeroed(ctx: Context) -> Result<Self, xnn::Error> {
+        Ok(Self {
+            w1: Tensor::constant(&ctx, &[784, 128], &[0.0])?,
+            b1: Tensor::constant(&ctx, &[1, 128], &[0.0])?,
+            w2: Tensor::constant(&ctx, &[128, 10], &[0.0])?,
+            b2: Tensor::constant(&ctx, &[1, 10], &[0.0])?,
+            ctx,
+        })
+    }
+
+    /// Writes weights starting at `offset` of the flat weight file, spanning tensors as needed.
+    fn write_weights(&mut self, mut offset: usize, mut weights: &[f32]) -> Result<(), xnn::Error> {
+        let mut start = 0;
+        for tensor in [&mut self.w1, &mut self.b1, &mut self.w2, &mut self.b2] {
+            let size = tensor.dimensions().iter().product::<usize>();
+            let end = start + size;
+
+            if offset < end && !weights.is_empty() {
+                let len = weights.len().min(end - offset);
+                tensor.write_slice(offset - start, &weights[..len])?;
+                offset += len;
+                weights = &weights[len..];
+            }
+
+            start = end;
+        }
+
+        Ok(())
+    }
+
     /// Runs forward pass, returns output tensor.
     fn forward(&self, pixels: &[f32]) -> Result<Tensor<f32>, xnn::Error> {
         let x = Tensor::from_shape_slice(&self.ctx, &[1, 784], pixels)?;
@@ -84,6 +118,48 @@ impl MnistModel {
     }
 }
 
+/// Progressive weight upload state.
+struct WeightLoader {
+    /// Model being filled.
+    model: MnistModel,
+    /// Number of weights written so far.
+    written: usize,
+    /// Trailing bytes of the last chunk that did not form a whole `f32`.
+    pending: Vec<u8>,
+}
+
+impl WeightLoader {
+    /// Uploads the whole `f32` values in `data`, keeping any partial value for the next chunk.
+    fn push(&mut self, data: &[u8]) -> Result<(), JsValue> {
+        self.pending.extend_from_slice(data);
+        let whole = self.pending.len() / 4 * 4;
+
+        let weights: Vec<f32> = self.pending[..whole]
+            .chunks_exact(4)
+            .map(|chunk| f32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]))
+            .collect();
+        self.pending.drain(..whole);
+
+        if self.written + weights.len() > TOTAL_SIZE {
+            return Err(JsValue::from_str(&format!(
+                "Invalid weights size: more than {TOTAL_SIZE} weights"
+            )));
+        }
+
+        self.model
+            .write_weights(self.written, &weights)
+            .map_err(|e| JsValue::from_str(&e.to_string()))?;
+        self.written += weights.len();
+
+        Ok(())
+    }
+
+    /// Returns true once every weight has been written.
+    fn is_complete(&self) -> bool {
+        self.written == TOTAL_SIZE && self.pending.is_empty()
+    }
+}
+
 /// Computes softmax: exp(x - max(x)) / sum(exp(x - max(x))).
 fn softmax(x: &Tensor<f32>) -> Result<Tensor<f32>, xnn::Error> {
     let max_val = x.max_reduce(&[1])?;
@@ -147,6 +223,66 @@ pub fn load_weights(data: &[u8]) -> Result<(), JsValue> {
     })
 }
 
+/// Starts a progressive weight upload, discarding any unfinished one.
+///
+/// # Errors
+///
+/// Returns error if WebGPU is not initialized or buffer allocation fails.
+#[wasm_bindgen]
+pub fn begin_weights() -> Result<(), JsValue> {
+    CTX.with(|c| {
+        let ctx = c.borrow();
+        let ctx = ctx
+            .as_ref()
+            .ok_or_else(|| JsValue::from_str("WebGPU not initialized"))?;
+
+        let model =
+            MnistModel::zeroed(ctx.clone()).map_err(|e| JsValue::from_str(&e.to_string()))?;
+        LOADER.with(|l| {
+            *l.borrow_mut() = Some(WeightLoader {
+                model,
+                written: 0,
+                pending: Vec::new(),
+            });
+        });
+
+        Ok(())
+    })
+}
+
+/// Uploads the next chunk of model weights (little-endian f32) to the GPU.
+///
+/// Chunks may split values at any byte. Returns true when the last weight has arrived and the
+/// model is ready.
+///
+/// # Errors
+///
+/// Returns error if no upload was started with `begin_weights`, or the data exceeds the
+/// model size.
+#[wasm_bindgen]
+pub fn load_weights_chunk(data: &[u8]) -> Result<bool, JsValue> {
+    LOADER.with(|l| {
+        let mut loader = l.borrow_mut();
+        let state = loader
+            .as_mut()
+            .ok_or_else(|| JsValue::from_str("Weight upload not started"))?;
+
+        if let Err(e) = state.push(data) {
+            *loader = None;
+            return Err(e);
+        }
+
+        if !state.is_complete() {
+            return Ok(false);
+        }
+
+        let state = loader.take().expect("loader is present");
+        MODEL.with(|m| *m.borrow_mut() = Some(state.model));
+        log!("Model loaded successfully!");
+        Ok(true)
+    })
+}
+
 /// Returns true if model is loaded and ready.
 #[must_use]
 #[wasm_bindgen]
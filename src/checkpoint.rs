@@ -0,0 +1,660 @@
+//! Checkpoint import from `PyTorch` state dicts.
+//!
+//! - [`Checkpoint`] — named tensors of a safetensors file, loaded on demand.
+//! - [`Dtype`] — element type of a stored tensor.
+//! - [`WeightLayout`] — axis order of a `PyTorch` parameter relative to its xnn counterpart.
+//!
+//! `PyTorch` `.pt` files are pickled Python objects and cannot be read without a Python
+//! unpickler, which may execute arbitrary code. Export the state dict to safetensors instead,
+//! which stores raw little-endian tensor data behind a JSON header:
+//!
+//! `save_file({k: v.contiguous() for k, v in model.state_dict().items()}, "model.safetensors")`
+//!
+//! with `save_file` from `safetensors.torch`. Parameters are then loaded by their state dict
+//! names into the layouts xnn expects, with the axis order converted and the dimensions
+//! validated, e.g. a `torch.nn.Conv1d` weight `[c_out, c_in, k]` into the `[k, c_in, c_out]`
+//! filter of a [`DilatedLayer`](crate::wavenet::DilatedLayer).
+//!
+//! Available with the `checkpoint` feature, which links `std` for file access.
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::ops::Range;
+use std::path::Path;
+
+use crate::error::{Error, TensorError};
+use crate::{Context, Tensor};
+
+/// Element type of a stored tensor, as named in the safetensors header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Dtype {
+    /// `F64`, narrowed to `f32` on load.
+    F64,
+    /// `F32`.
+    F32,
+    /// `F16`, widened to `f32` on load.
+    F16,
+    /// `BF16`, widened to `f32` on load.
+    BF16,
+    /// `I64`, not loadable.
+    I64,
+    /// `I32`, not loadable.
+    I32,
+    /// `I16`, not loadable.
+    I16,
+    /// `I8`, not loadable.
+    I8,
+    /// `U8`, not loadable.
+    U8,
+    /// `BOOL`, not loadable.
+    Bool,
+}
+
+impl Dtype {
+    /// Parses the header name of a dtype.
+    fn parse(name: &str) -> Option<Self> {
+        Some(match name {
+            "F64" => Self::F64,
+            "F32" => Self::F32,
+            "F16" => Self::F16,
+            "BF16" => Self::BF16,
+            "I64" => Self::I64,
+            "I32" => Self::I32,
+            "I16" => Self::I16,
+            "I8" => Self::I8,
+            "U8" => Self::U8,
+            "BOOL" => Self::Bool,
+            _ => return None,
+        })
+    }
+
+    /// Size of one element in bytes.
+    #[must_use]
+    pub fn size(self) -> usize {
+        match self {
+            Self::F64 | Self::I64 => 8,
+            Self::F32 | Self::I32 => 4,
+            Self::F16 | Self::BF16 | Self::I16 => 2,
+            Self::I8 | Self::U8 | Self::Bool => 1,
+        }
+    }
+}
+
+/// Axis order of a `PyTorch` parameter, converted on load to the order xnn operations take.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WeightLayout {
+    /// Loaded as stored, e.g. biases and normalization parameters.
+    Same,
+    /// `torch.nn.Linear` weight `[out, in]`, loaded as `[in, out]` for `x.matmul(w)`.
+    Linear,
+    /// `torch.nn.Conv1d` weight `[c_out, c_in, k]`, loaded as `[k, c_in, c_out]` for
+    /// [`Tensor::conv1d`].
+    Conv1d,
+    /// Depthwise `torch.nn.Conv2d` weight `[c, 1, kh, kw]`, loaded as `[kh, kw, c]` for
+    /// [`Tensor::depthwise_conv2d`].
+    Depthwise2d,
+    /// 1×1 `torch.nn.Conv2d` weight `[c_out, c_in, 1, 1]`, loaded as `[c_in, c_out]` for
+    /// [`Tensor::pointwise_conv2d`].
+    Pointwise2d,
+}
+
+impl WeightLayout {
+    /// Returns the stored dimensions with unit axes dropped, and the axis order that converts
+    /// them to xnn's, or `None` if `stored` does not fit the layout.
+    fn permutation(self, stored: &[usize]) -> Option<(Vec<usize>, &'static [usize])> {
+        match (self, stored) {
+            (Self::Same, _) => Some((stored.to_vec(), &[])),
+            (Self::Linear, &[out, input]) | (Self::Pointwise2d, &[out, input, 1, 1]) => {
+                Some((Vec::from([out, input]), &[1, 0]))
+            }
+            (Self::Conv1d, &[out, input, k]) => Some((Vec::from([out, input, k]), &[2, 1, 0])),
+            (Self::Depthwise2d, &[c, 1, kh, kw]) => Some((Vec::from([c, kh, kw]), &[1, 2, 0])),
+            _ => None,
+        }
+    }
+}
+
+/// Stored tensor of a checkpoint.
+struct Entry {
+    /// State dict name.
+    name: String,
+    /// Element type.
+    dtype: Dtype,
+    /// Dimensions as stored.
+    dimensions: Vec<usize>,
+    /// Byte range of the row-major data within the file.
+    range: Range<usize>,
+}
+
+/// Named tensors of a safetensors file.
+///
+/// The file is read into host memory once; tensors are decoded and uploaded individually by
+/// [`Self::load`], so a model only pays for the parameters it uses.
+pub struct Checkpoint {
+    /// File contents.
+    bytes: Vec<u8>,
+    /// Stored tensors, in header order.
+    entries: Vec<Entry>,
+    /// String metadata from the header.
+    metadata: Vec<(String, String)>,
+}
+
+impl Checkpoint {
+    /// Reads the safetensors file at `path`.
+    ///
+    /// # Errors
+    ///
+    /// - [`Error::Data`] if the file cannot be read or parsed, see [`Self::parse`].
+    pub fn read(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let path = path.as_ref();
+        let bytes = std::fs::read(path)
+            .map_err(|e| Error::Data(format!("cannot read {}: {e}", path.display())))?;
+        Self::parse(bytes)
+    }
+
+    /// Parses the contents of a safetensors file: an 8-byte little-endian header length, a
+    /// JSON header describing each tensor, then the tensor data.
+    ///
+    /// # Errors
+    ///
+    /// - [`Error::Data`] if the header is truncated, not valid JSON or nested more than 128
+    ///   levels deep, or a tensor has an unknown dtype or a data range that does not match
+    ///   its dimensions or the file.
+    pub fn parse(bytes: Vec<u8>) -> Result<Self, Error> {
+        let header_len = bytes
+            .get(..8)
+            .and_then(|len| usize::try_from(u64::from_le_bytes(len.try_into().ok()?)).ok())
+            .ok_or_else(|| Error::Data("missing safetensors header length".into()))?;
+        let start = header_len
+            .checked_add(8)
+            .filter(|&start| start <= bytes.len())
+            .ok_or_else(|| Error::Data(format!("header of {header_len} bytes exceeds file")))?;
+
+        let header = core::str::from_utf8(&bytes[8..start])
+            .map_err(|e| Error::Data(format!("header is not UTF-8: {e}")))?;
+        let Json::Object(fields) = Parser::parse(header)? else {
+            return Err(Error::Data("header is not a JSON object".into()));
+        };
+
+        let mut entries = Vec::new();
+        let mut metadata = Vec::new();
+        for (name, value) in fields {
+            if name == "__metadata__" {
+                let Json::Object(pairs) = value else {
+                    return Err(Error::Data("metadata is not a JSON object".into()));
+                };
+                for (key, value) in pairs {
+                    let Json::String(value) = value else {
+                        return Err(Error::Data(format!("metadata {key:?} is not a string")));
+                    };
+                    metadata.push((key, value));
+                }
+                continue;
+            }
+
+            let entry = Entry::parse(name, &value, bytes.len() - start)?;
+            entries.push(Entry {
+                range: entry.range.start + start..entry.range.end + start,
+                ..entry
+            });
+        }
+
+        Ok(Self {
+            bytes,
+            entries,
+            metadata,
+        })
+    }
+
+    /// State dict names of the stored tensors, in header order.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.entries.iter().map(|entry| entry.name.as_str())
+    }
+
+    /// Element type of the tensor named `name`.
+    #[must_use]
+    pub fn dtype(&self, name: &str) -> Option<Dtype> {
+        self.entry(name).map(|entry| entry.dtype)
+    }
+
+    /// Stored dimensions of the tensor named `name`, in `PyTorch`'s axis order.
+    #[must_use]
+    pub fn dimensions(&self, name: &str) -> Option<&[usize]> {
+        self.entry(name).map(|entry| entry.dimensions.as_slice())
+    }
+
+    /// Header metadata stored under `key`.
+    #[must_use]
+    pub fn metadata(&self, key: &str) -> Option<&str> {
+        self.metadata
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Loads the floating-point tensor named `name` as `f32`, converted from the `PyTorch`
+    /// `layout` to xnn's axis order and validated against the expected xnn `dimensions`.
+    ///
+    /// # Errors
+    ///
+    /// - [`Error::Data`] if no tensor is named `name` or its dtype is not floating-point.
+    /// - [`TensorError::InvalidShape`] if the stored dimensions do not fit `layout`, or the
+    ///   converted dimensions differ from `dimensions`.
+    /// - [`Error::Device`] if buffer allocation fails.
+    pub fn load(
+        &self,
+        ctx: &Context,
+        name: &str,
+        layout: WeightLayout,
+        dimensions: &[usize],
+    ) -> Result<Tensor<f32>, Error> {
+        let entry = self
+            .entry(name)
+            .ok_or_else(|| Error::Data(format!("no tensor named {name:?}")))?;
+
+        let (stored, axes) = layout.permutation(&entry.dimensions).ok_or_else(|| {
+            TensorError::InvalidShape(format!(
+                "{name:?} with dimensions {:?} does not fit {layout:?} layout",
+                entry.dimensions
+            ))
+        })?;
+        let converted: Vec<usize> = if axes.is_empty() {
+            stored.clone()
+        } else {
+            axes.iter().map(|&axis| stored[axis]).collect()
+        };
+
+        if converted != dimensions {
+            return Err(TensorError::InvalidShape(format!(
+                "{name:?} loads as {converted:?}, expected {dimensions:?}"
+            ))
+            .into());
+        }
+
+        let values = entry.decode(&self.bytes[entry.range.clone()])?;
+        let values = if axes.is_empty() {
+            values
+        } else {
+            permute(&values, &stored, axes)
+        };
+
+        Tensor::from_shape_slice(ctx, dimensions, &values)
+    }
+
+    /// Stored tensor named `name`.
+    fn entry(&self, name: &str) -> Option<&Entry> {
+        self.entries.iter().find(|entry| entry.name == name)
+    }
+}
+
+impl Entry {
+    /// Parses the header entry of the tensor `name`, with the data range relative to the start
+    /// of the `data_len` data bytes.
+    fn parse(name: String, value: &Json, data_len: usize) -> Result<Self, Error> {
+        let invalid = |what: &str| Error::Data(format!("tensor {name:?} has {what}"));
+
+        let dtype = match value.field("dtype") {
+            Some(Json::String(dtype)) => Dtype::parse(dtype),
+            _ => None,
+        }
+        .ok_or_else(|| invalid("a missing or unknown dtype"))?;
+
+        let dimensions = value
+            .field("shape")
+            .and_then(Json::integers)
+            .ok_or_else(|| invalid("a missing or invalid shape"))?;
+
+        let range = match value.field("data_offsets").and_then(Json::integers) {
+            Some(offsets) if offsets.len() == 2 && offsets[0] <= offsets[1] => {
+                offsets[0]..offsets[1]
+            }
+            _ => return Err(invalid("missing or invalid data offsets")),
+        };
+
+        let size = dimensions
+            .iter()
+            .try_fold(dtype.size(), |acc, &d| acc.checked_mul(d));
+        if range.end > data_len || size != Some(range.len()) {
+            return Err(invalid(
+                "data offsets that do not match its shape or the file",
+            ));
+        }
+
+        Ok(Self {
+            name,
+            dtype,
+            dimensions,
+            range,
+        })
+    }
+
+    /// Decodes the little-endian `bytes` of a floating-point tensor as `f32`.
+    fn decode(&self, bytes: &[u8]) -> Result<Vec<f32>, Error> {
+        let values = match self.dtype {
+            Dtype::F32 => bytes
+                .chunks_exact(4)
+                .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+                .collect(),
+            #[allow(clippy::cast_possible_truncation)]
+            Dtype::F64 => bytes
+                .chunks_exact(8)
+                .map(|b| {
+                    f64::from_le_bytes([b[0], b[1], b[2], b[3], b[4], b[5], b[6], b[7]]) as f32
+                })
+                .collect(),
+            Dtype::F16 => bytes
+                .chunks_exact(2)
+                .map(|b| f16_to_f32(u16::from_le_bytes([b[0], b[1]])))
+                .collect(),
+            Dtype::BF16 => bytes
+                .chunks_exact(2)
+                .map(|b| f32::from_bits(u32::from(u16::from_le_bytes([b[0], b[1]])) << 16))
+                .collect(),
+            dtype => {
+                return Err(Error::Data(format!(
+                    "tensor {:?} has non-float dtype {dtype:?}",
+                    self.name
+                )));
+            }
+        };
+
+        Ok(values)
+    }
+}
+
+/// Widens IEEE half-precision `bits` to `f32`.
+fn f16_to_f32(bits: u16) -> f32 {
+    let sign = u32::from(bits >> 15) << 31;
+    let exponent = u32::from((bits >> 10) & 0x1f);
+    let mantissa = u32::from(bits & 0x3ff);
+
+    let bits = match (exponent, mantissa) {
+        (0, 0) => sign,
+        (0, _) => {
+            // Subnormal: shift the mantissa up to an implicit leading one.
+            let shift = mantissa.leading_zeros() - 21;
+            let mantissa = (mantissa << shift) & 0x3ff;
+            sign | ((113 - shift) << 23) | (mantissa << 13)
+        }
+        (0x1f, _) => sign | 0x7f80_0000 | (mantissa << 13),
+        _ => sign | ((exponent + 112) << 23) | (mantissa << 13),
+    };
+
+    f32::from_bits(bits)
+}
+
+/// Reorders row-major `values` of `dimensions` so output axis `i` is input axis `axes[i]`.
+fn permute(values: &[f32], dimensions: &[usize], axes: &[usize]) -> Vec<f32> {
+    let rank = dimensions.len();
+    let mut strides = alloc::vec![1; rank];
+    for i in (0..rank.saturating_sub(1)).rev() {
+        strides[i] = strides[i + 1] * dimensions[i + 1];
+    }
+
+    let out_dimensions: Vec<usize> = axes.iter().map(|&axis| dimensions[axis]).collect();
+    let mut index = alloc::vec![0; rank];
+    let mut out = Vec::with_capacity(values.len());
+    for _ in 0..values.len() {
+        let offset: usize = (0..rank).map(|i| index[i] * strides[axes[i]]).sum();
+        out.push(values[offset]);
+
+        for i in (0..rank).rev() {
+            index[i] += 1;
+            if index[i] < out_dimensions[i] {
+                break;
+            }
+            index[i] = 0;
+        }
+    }
+    out
+}
+
+/// JSON value of a safetensors header.
+enum Json {
+    /// `null`, `true` or `false`.
+    Literal,
+    /// Number, as written.
+    Number(String),
+    /// String.
+    String(String),
+    /// Array.
+    Array(Vec<Json>),
+    /// Object fields, in order.
+    Object(Vec<(String, Json)>),
+}
+
+impl Json {
+    /// Field `key` of an object.
+    fn field(&self, key: &str) -> Option<&Json> {
+        match self {
+            Self::Object(fields) => fields.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    /// Elements of an array of non-negative integers.
+    fn integers(&self) -> Option<Vec<usize>> {
+        match self {
+            Self::Array(items) => items
+                .iter()
+                .map(|item| match item {
+                    Self::Number(number) => number.parse().ok(),
+                    _ => None,
+                })
+                .collect(),
+            _ => None,
+        }
+    }
+}
+
+/// Deepest nesting of objects and arrays accepted in a header.
+const MAX_DEPTH: usize = 128;
+
+/// Recursive descent parser of a JSON document.
+struct Parser<'a> {
+    /// Document text.
+    text: &'a str,
+    /// Byte position of the next token.
+    pos: usize,
+    /// Number of objects and arrays enclosing the current position.
+    depth: usize,
+}
+
+impl Parser<'_> {
+    /// Parses `text` as a single JSON value.
+    fn parse(text: &str) -> Result<Json, Error> {
+        let mut parser = Parser {
+            text,
+            pos: 0,
+            depth: 0,
+        };
+        let value = parser.value()?;
+        parser.skip_whitespace();
+        if parser.pos != text.len() {
+            return Err(parser.error("trailing characters"));
+        }
+        Ok(value)
+    }
+
+    /// Parses the value at the current position.
+    ///
+    /// Objects and arrays nested deeper than [`MAX_DEPTH`] are rejected, so a hostile header
+    /// cannot exhaust the stack.
+    fn value(&mut self) -> Result<Json, Error> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some(open @ ('{' | '[')) => {
+                if self.depth == MAX_DEPTH {
+                    return Err(self.error("nesting too deep"));
+                }
+                self.depth += 1;
+                let value = if open == '{' {
+                    self.object()
+                } else {
+                    self.array()
+                };
+                self.depth -= 1;
+                value
+            }
+            Some('"') => self.string().map(Json::String),
+            Some('-' | '0'..='9') => {
+                let start = self.pos;
+                while matches!(self.peek(), Some('-' | '+' | '.' | 'e' | 'E' | '0'..='9')) {
+                    self.pos += 1;
+                }
+                Ok(Json::Number(self.text[start..self.pos].into()))
+            }
+            _ => {
+                for literal in ["null", "true", "false"] {
+                    if self.text[self.pos..].starts_with(literal) {
+                        self.pos += literal.len();
+                        return Ok(Json::Literal);
+                    }
+                }
+                Err(self.error("expected a value"))
+            }
+        }
+    }
+
+    /// Parses an object starting at `{`.
+    fn object(&mut self) -> Result<Json, Error> {
+        let mut fields = Vec::new();
+        self.pos += 1;
+        self.skip_whitespace();
+        if self.eat('}') {
+            return Ok(Json::Object(fields));
+        }
+
+        loop {
+            self.skip_whitespace();
+            let key = self.string()?;
+            self.skip_whitespace();
+            if !self.eat(':') {
+                return Err(self.error("expected ':'"));
+            }
+            fields.push((key, self.value()?));
+
+            self.skip_whitespace();
+            if self.eat('}') {
+                return Ok(Json::Object(fields));
+            }
+            if !self.eat(',') {
+                return Err(self.error("expected ',' or '}'"));
+            }
+        }
+    }
+
+    /// Parses an array starting at `[`.
+    fn array(&mut self) -> Result<Json, Error> {
+        let mut items = Vec::new();
+        self.pos += 1;
+        self.skip_whitespace();
+        if self.eat(']') {
+            return Ok(Json::Array(items));
+        }
+
+        loop {
+            items.push(self.value()?);
+            self.skip_whitespace();
+            if self.eat(']') {
+                return Ok(Json::Array(items));
+            }
+            if !self.eat(',') {
+                return Err(self.error("expected ',' or ']'"));
+            }
+        }
+    }
+
+    /// Parses a string starting at `"`, resolving escapes.
+    fn string(&mut self) -> Result<String, Error> {
+        if !self.eat('"') {
+            return Err(self.error("expected a string"));
+        }
+
+        let mut out = String::new();
+        loop {
+            let c = self
+                .next()
+                .ok_or_else(|| self.error("unterminated string"))?;
+            match c {
+                '"' => return Ok(out),
+                '\\' => {
+                    let escape = self
+                        .next()
+                        .ok_or_else(|| self.error("unterminated string"))?;
+                    out.push(match escape {
+                        '"' | '\\' | '/' => escape,
+                        'b' => '\u{8}',
+                        'f' => '\u{c}',
+                        'n' => '\n',
+                        'r' => '\r',
+                        't' => '\t',
+                        'u' => self.unicode_escape()?,
+                        _ => return Err(self.error("invalid escape")),
+                    });
+                }
+                c => out.push(c),
+            }
+        }
+    }
+
+    /// Parses the hex digits of a `\u` escape, combining surrogate pairs.
+    fn unicode_escape(&mut self) -> Result<char, Error> {
+        let high = self.hex4()?;
+        let code = if (0xd800..0xdc00).contains(&high) && self.text[self.pos..].starts_with("\\u") {
+            self.pos += 2;
+            let low = self.hex4()?;
+            0x10000 + ((high - 0xd800) << 10) + (low.wrapping_sub(0xdc00) & 0x3ff)
+        } else {
+            high
+        };
+        char::from_u32(code).ok_or_else(|| self.error("invalid unicode escape"))
+    }
+
+    /// Parses four hex digits.
+    fn hex4(&mut self) -> Result<u32, Error> {
+        let digits = self
+            .text
+            .get(self.pos..self.pos + 4)
+            .ok_or_else(|| self.error("truncated unicode escape"))?;
+        self.pos += 4;
+        u32::from_str_radix(digits, 16).map_err(|_| self.error("invalid unicode escape"))
+    }
+
+    /// Skips whitespace between tokens.
+    fn skip_whitespace(&mut self) {
+        while matches!(self.peek(), Some(' ' | '\t' | '\n' | '\r')) {
+            self.pos += 1;
+        }
+    }
+
+    /// Consumes `c` if it is next.
+    fn eat(&mut self, c: char) -> bool {
+        let found = self.peek() == Some(c);
+        if found {
+            self.pos += c.len_utf8();
+        }
+        found
+    }
+
+    /// Next character, without consuming it.
+    fn peek(&self) -> Option<char> {
+        self.text[self.pos..].chars().next()
+    }
+
+    /// Consumes the next character.
+    fn next(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += c.len_utf8();
+        Some(c)
+    }
+
+    /// Parse error at the current position.
+    fn error(&self, message: &str) -> Error {
+        Error::Data(format!(
+            "invalid header JSON at byte {}: {message}",
+            self.pos
+        ))
+    }
+}
@@ -0,0 +1,309 @@
+//! Low-level compute on raw buffers, without tensor shapes or strides.
+//!
+//! - [`fill`] — sets every element of a buffer.
+//! - [`copy`] — copies one buffer into another.
+//! - [`gemm`] — multiplies row-major matrices.
+//! - [`reduce`] — reduces a buffer to one element.
+//! - [`Reduction`] — combining operation of [`reduce`].
+//!
+//! For element-wise work beyond these, [`Context::map`] and [`Context::zip`] compile a WGSL
+//! expression into a kernel, cached by the generated source, [`Context::stencil`] does the
+//! same over the neighborhood of each element of an array, and [`Context::reduce`] reduces
+//! the result:
+//!
+//! ```no_run
+//! # use xnn::{Context, compute::Reduction};
+//! # let ctx = Context::try_default()?;
+//! let x = ctx.create_buffer_from_slice(&[1.0f32, 2.0, 3.0])?;
+//! let squares: xnn::Buffer<f32> = ctx.map(&x, "x * x")?;
+//! let total = ctx.reduce(&squares, Reduction::Sum)?;
+//! # Ok::<(), xnn::Error>(())
+//! ```
+//!
+//! Buffers are created, written and read through [`Context::create_buffer`],
+//! [`Context::create_buffer_from_slice`], [`Context::write_buffer`] and
+//! [`Context::read_buffer`]. Every call here submits its work to the queue right away;
+//! reads wait for all earlier submissions, so no explicit synchronization is needed.
+
+use alloc::format;
+
+use crate::element::{FloatElement, NumericElement};
+use crate::error::{Error, TensorError};
+use crate::kernel::pad::{MODE_CONSTANT, MODE_REFLECT, MODE_REPLICATE};
+use crate::kernel::reduction::full::{self, FullReduction};
+use crate::kernel::{expr, ops, stencil};
+use crate::{Buffer, Context, Element, PadMode};
+
+/// Combining operation of [`reduce`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Reduction {
+    /// Sum of the elements, zero for an empty buffer.
+    Sum,
+    /// Largest element.
+    Max,
+    /// Smallest element.
+    Min,
+    /// Product of the elements.
+    Prod,
+}
+
+impl Context {
+    /// Evaluates the WGSL expression `expr` for each element of `x` into a new buffer.
+    ///
+    /// The expression sees the element as `x` and its `u32` index as `i`, and its value is
+    /// converted to the output element type, e.g. `"x * x"` or `"select(0.0, x, x > 0.0)"`.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidArgument`] if `expr` is not a valid WGSL expression.
+    /// - [`Error::Device`] if buffer allocation fails.
+    pub fn map<T: NumericElement, U: NumericElement>(
+        &self,
+        x: &Buffer<T>,
+        expr: &str,
+    ) -> Result<Buffer<U>, Error> {
+        let y = self.create_buffer(x.len())?;
+        expr::execute(self, &[("x", x)], &y, expr, "map")?;
+        Ok(y)
+    }
+
+    /// Evaluates the WGSL expression `expr` for each pair of elements of `a` and `b` into a
+    /// new buffer.
+    ///
+    /// The expression sees the elements as `a` and `b` and their `u32` index as `i`, e.g.
+    /// `"a * b + 1.0"`.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidShape`] if `a` and `b` differ in length.
+    /// - [`TensorError::InvalidArgument`] if `expr` is not a valid WGSL expression.
+    /// - [`Error::Device`] if buffer allocation fails.
+    pub fn zip<T: NumericElement, U: NumericElement>(
+        &self,
+        a: &Buffer<T>,
+        b: &Buffer<T>,
+        expr: &str,
+    ) -> Result<Buffer<U>, Error> {
+        if a.len() != b.len() {
+            return Err(TensorError::InvalidShape(format!(
+                "cannot zip buffers of {} and {} elements",
+                a.len(),
+                b.len()
+            ))
+            .into());
+        }
+
+        let y = self.create_buffer(a.len())?;
+        expr::execute(self, &[("a", a), ("b", b)], &y, expr, "zip")?;
+        Ok(y)
+    }
+
+    /// Evaluates the WGSL stencil expression `expr` at each element of `x`, an array of
+    /// `dimensions` in row-major order, into a new buffer.
+    ///
+    /// The expression sees the element as `x`, its `u32` flat index as `i`, and its
+    /// neighbors through `at`, which takes one `i32` offset per axis, e.g. the 2D Laplacian
+    /// `"at(-1, 0) + at(1, 0) + at(0, -1) + at(0, 1) - 4.0 * x"`. Offsets are clamped to
+    /// `radius`. Neighbors outside the array are resolved by `boundary` as in
+    /// [`Tensor::pad`](crate::Tensor::pad).
+    ///
+    /// Arrays of rank 1 to 3 are supported, with `radius` up to 4. Each workgroup stages a
+    /// tile of the output plus its halo in workgroup memory, so every element is read from
+    /// global memory about once per tile.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidShape`] if `dimensions` is not of rank 1 to 3, does not match
+    ///   the length of `x`, or is too large to tile.
+    /// - [`TensorError::InvalidArgument`] if `radius` exceeds 4, reflect `boundary` is not
+    ///   smaller than an axis, or `expr` is not a valid WGSL expression.
+    /// - [`Error::Device`] if buffer allocation fails.
+    pub fn stencil<T: NumericElement>(
+        &self,
+        x: &Buffer<T>,
+        dimensions: &[usize],
+        radius: usize,
+        boundary: PadMode<T>,
+        expr: &str,
+    ) -> Result<Buffer<T>, Error> {
+        if !(1..=stencil::MAX_RANK).contains(&dimensions.len())
+            || dimensions.iter().product::<usize>() != x.len()
+        {
+            return Err(TensorError::InvalidShape(format!(
+                "stencil needs rank 1 to {} dimensions of {} elements, got {dimensions:?}",
+                stencil::MAX_RANK,
+                x.len()
+            ))
+            .into());
+        }
+
+        if radius > stencil::MAX_RADIUS {
+            return Err(TensorError::InvalidArgument(format!(
+                "stencil radius {radius} exceeds {}",
+                stencil::MAX_RADIUS
+            ))
+            .into());
+        }
+
+        if matches!(boundary, PadMode::Reflect) && dimensions.iter().any(|&size| size <= radius) {
+            return Err(TensorError::InvalidArgument(format!(
+                "reflect boundary needs axes longer than radius {radius}, got {dimensions:?}"
+            ))
+            .into());
+        }
+
+        let (mode, fill) = match boundary {
+            PadMode::Constant(value) => (MODE_CONSTANT, value),
+            PadMode::Reflect => (MODE_REFLECT, T::zeroed()),
+            PadMode::Replicate => (MODE_REPLICATE, T::zeroed()),
+        };
+
+        let y = self.create_buffer(x.len())?;
+        stencil::execute(self, x, &y, dimensions, radius, mode, fill, expr)?;
+        Ok(y)
+    }
+
+    /// Reduces all elements of `x` by `op` into a new one-element buffer, as [`reduce`].
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidShape`] if `x` is empty and `op` is not [`Reduction::Sum`].
+    /// - [`Error::Device`] if buffer allocation fails.
+    pub fn reduce<T: NumericElement>(
+        &self,
+        x: &Buffer<T>,
+        op: Reduction,
+    ) -> Result<Buffer<T>, Error> {
+        reduce(self, x, op)
+    }
+}
+
+/// Sets every element of `buffer` to `value`.
+pub fn fill<T: Element>(ctx: &Context, buffer: &Buffer<T>, value: T) {
+    ops::constant(ctx, buffer, value);
+}
+
+/// Copies all elements of `src` to the start of `dst`.
+///
+/// # Errors
+///
+/// - [`TensorError::InvalidShape`] if `dst` is shorter than `src`.
+pub fn copy<T: Element>(ctx: &Context, src: &Buffer<T>, dst: &Buffer<T>) -> Result<(), Error> {
+    if dst.len() < src.len() {
+        return Err(TensorError::InvalidShape(format!(
+            "destination of {} elements is shorter than source of {}",
+            dst.len(),
+            src.len()
+        ))
+        .into());
+    }
+
+    ops::copy(ctx, src, dst);
+    Ok(())
+}
+
+/// General matrix multiplication `C = op(A) × op(B)` of row-major matrices, with `C` of
+/// `[m, n]` overwritten.
+///
+/// `A` holds `[m, k]`, or `[k, m]` if `transpose_a`; `B` holds `[k, n]`, or `[n, k]` if
+/// `transpose_b`. Runs the same tiled kernel as [`Tensor::matmul`](crate::Tensor::matmul).
+///
+/// # Errors
+///
+/// - [`TensorError::InvalidShape`] if a buffer length does not match its matrix.
+pub fn gemm<T: FloatElement>(
+    ctx: &Context,
+    a: &Buffer<T>,
+    b: &Buffer<T>,
+    c: &Buffer<T>,
+    [m, k, n]: [usize; 3],
+    transpose_a: bool,
+    transpose_b: bool,
+) -> Result<(), Error> {
+    if a.len() != m * k || b.len() != k * n || c.len() != m * n {
+        return Err(TensorError::InvalidShape(format!(
+            "buffers of {}, {} and {} elements do not hold [{m}, {k}] × [{k}, {n}] = [{m}, {n}]",
+            a.len(),
+            b.len(),
+            c.len()
+        ))
+        .into());
+    }
+
+    let a_dims = if transpose_a { [k, m] } else { [m, k] };
+    let b_dims = if transpose_b { [n, k] } else { [k, n] };
+    ops::matmul(
+        ctx,
+        a,
+        b,
+        c,
+        &a_dims,
+        &b_dims,
+        &[m, n],
+        transpose_a,
+        transpose_b,
+    );
+    Ok(())
+}
+
+/// Reduces all elements of `x` by `op` into a new one-element buffer.
+///
+/// Runs as a multi-pass tree: each pass reduces chunks of consecutive elements with one
+/// workgroup each, until one element remains.
+///
+/// # Errors
+///
+/// - [`TensorError::InvalidShape`] if `x` is empty and `op` is not [`Reduction::Sum`].
+/// - [`Error::Device`] if buffer allocation fails.
+pub fn reduce<T: NumericElement>(
+    ctx: &Context,
+    x: &Buffer<T>,
+    op: Reduction,
+) -> Result<Buffer<T>, Error> {
+    if x.is_empty() {
+        return match op {
+            Reduction::Sum => ctx.create_buffer_from_slice(&[T::zeroed()]),
+            _ => Err(
+                TensorError::InvalidShape(format!("cannot reduce empty buffer by {op:?}")).into(),
+            ),
+        };
+    }
+
+    let op = match op {
+        Reduction::Sum => FullReduction::Sum,
+        Reduction::Max => FullReduction::Max,
+        Reduction::Min => FullReduction::Min,
+        Reduction::Prod => FullReduction::Prod,
+    };
+    reduce_rows(ctx, x, 1, x.len(), op, None)
+}
+
+/// Reduces each row of contiguous `[rows, row_len]` `x` by `op` to one element, dividing
+/// the results by `divisor` if given.
+///
+/// Each pass reduces chunks of [`full::CHUNK`] elements to partial results, one workgroup
+/// per chunk, and passes chain until one result per row remains.
+pub(crate) fn reduce_rows<T: NumericElement>(
+    ctx: &Context,
+    x: &Buffer<T>,
+    rows: usize,
+    mut row_len: usize,
+    op: FullReduction,
+    divisor: Option<usize>,
+) -> Result<Buffer<T>, Error> {
+    let mut partial = None;
+    loop {
+        let chunks = row_len.div_ceil(full::CHUNK);
+        let y = ctx.create_buffer(rows * chunks)?;
+        let last = chunks == 1;
+        let x = partial.as_ref().unwrap_or(x);
+        ops::full_reduce(ctx, x, &y, rows, row_len, op, divisor.filter(|_| last));
+
+        if last {
+            return Ok(y);
+        }
+
+        partial = Some(y);
+        row_len = chunks;
+    }
+}
@@ -0,0 +1,346 @@
+//! Tabular data ingestion.
+//!
+//! - [`Schema`] — names and element types of the columns to load.
+//! - [`Table`] — loaded columns, one GPU tensor per column.
+//! - [`read_csv`] — loads a CSV file into a [`Table`].
+//! - [`parse_csv`] — loads CSV text into a [`Table`].
+//!
+//! Available with the `data` feature, which links `std` for file access.
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use alloc::{format, vec};
+use std::path::Path;
+
+use crate::error::Error;
+use crate::{Context, Tensor};
+
+/// Element type of a loaded column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColumnType {
+    /// Parsed as `f32`.
+    F32,
+    /// Parsed as `i32`.
+    I32,
+    /// Parsed as `u32`.
+    U32,
+    /// Parsed from `true`/`false` or `1`/`0`, case-insensitive.
+    Bool,
+}
+
+/// Columns to load from a table with a header row.
+///
+/// Columns are looked up by header name, in any order; columns of the file not listed are
+/// skipped.
+#[derive(Debug, Clone)]
+pub struct Schema {
+    /// Column names and types, in output order.
+    columns: Vec<(String, ColumnType)>,
+    /// Field separator.
+    delimiter: char,
+}
+
+impl Default for Schema {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Schema {
+    /// Creates an empty comma-separated schema.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            columns: Vec::new(),
+            delimiter: ',',
+        }
+    }
+
+    /// Adds a column named `name` parsed as `ty`.
+    #[must_use]
+    pub fn column(mut self, name: &str, ty: ColumnType) -> Self {
+        self.columns.push((name.to_string(), ty));
+        self
+    }
+
+    /// Sets the field separator, e.g. `'\t'` for TSV files.
+    #[must_use]
+    pub fn delimiter(mut self, delimiter: char) -> Self {
+        self.delimiter = delimiter;
+        self
+    }
+}
+
+/// A loaded column.
+pub enum Column {
+    /// `f32` column.
+    F32(Tensor<f32>),
+    /// `i32` column.
+    I32(Tensor<i32>),
+    /// `u32` column.
+    U32(Tensor<u32>),
+    /// `bool` column.
+    Bool(Tensor<bool>),
+}
+
+/// Columns loaded according to a [`Schema`], each a `[rows]` tensor.
+pub struct Table {
+    /// Column names and tensors, in schema order.
+    columns: Vec<(String, Column)>,
+    /// Number of rows.
+    rows: usize,
+}
+
+impl Table {
+    /// Number of rows.
+    #[must_use]
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    /// Column named `name`.
+    #[must_use]
+    pub fn column(&self, name: &str) -> Option<&Column> {
+        self.columns
+            .iter()
+            .find(|(column, _)| column == name)
+            .map(|(_, column)| column)
+    }
+
+    /// Column names and tensors, in schema order.
+    pub fn columns(&self) -> impl Iterator<Item = (&str, &Column)> {
+        self.columns
+            .iter()
+            .map(|(name, column)| (name.as_str(), column))
+    }
+
+    /// `f32` column named `name`.
+    #[must_use]
+    pub fn f32(&self, name: &str) -> Option<&Tensor<f32>> {
+        match self.column(name)? {
+            Column::F32(tensor) => Some(tensor),
+            _ => None,
+        }
+    }
+
+    /// `i32` column named `name`.
+    #[must_use]
+    pub fn i32(&self, name: &str) -> Option<&Tensor<i32>> {
+        match self.column(name)? {
+            Column::I32(tensor) => Some(tensor),
+            _ => None,
+        }
+    }
+
+    /// `u32` column named `name`.
+    #[must_use]
+    pub fn u32(&self, name: &str) -> Option<&Tensor<u32>> {
+        match self.column(name)? {
+            Column::U32(tensor) => Some(tensor),
+            _ => None,
+        }
+    }
+
+    /// `bool` column named `name`.
+    #[must_use]
+    pub fn bool(&self, name: &str) -> Option<&Tensor<bool>> {
+        match self.column(name)? {
+            Column::Bool(tensor) => Some(tensor),
+            _ => None,
+        }
+    }
+
+    /// Stacks the `f32` columns `names` into a `[rows, names.len()]` feature matrix.
+    ///
+    /// # Errors
+    ///
+    /// - [`Error::Data`] if a column is missing or not `f32`.
+    /// - [`Error::Device`] if buffer allocation fails.
+    pub fn features(&self, names: &[&str]) -> Result<Tensor<f32>, Error> {
+        let columns = names
+            .iter()
+            .map(|name| {
+                self.f32(name)
+                    .ok_or_else(|| Error::Data(format!("no f32 column named {name:?}")))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if columns.is_empty() {
+            return Err(Error::Data("no feature columns given".into()));
+        }
+
+        Tensor::stack(&columns, 1)
+    }
+}
+
+/// Reads the CSV file at `path`, whose first line is a header, into one tensor per column of
+/// `schema`.
+///
+/// # Errors
+///
+/// - [`Error::Data`] if the file cannot be read or parsed, see [`parse_csv`].
+/// - [`Error::Device`] if buffer allocation fails.
+pub fn read_csv(ctx: &Context, path: impl AsRef<Path>, schema: &Schema) -> Result<Table, Error> {
+    let path = path.as_ref();
+    let text = std::fs::read_to_string(path)
+        .map_err(|e| Error::Data(format!("cannot read {}: {e}", path.display())))?;
+    parse_csv(ctx, &text, schema)
+}
+
+/// Parses CSV `text`, whose first line is a header, into one tensor per column of `schema`.
+///
+/// Fields may be quoted with `"`, with `""` for a literal quote; surrounding whitespace of
+/// unquoted fields is ignored. Blank lines are skipped.
+///
+/// # Errors
+///
+/// - [`Error::Data`] if the schema is empty, a schema column is not in the header, a row has
+///   the wrong number of fields, a field does not parse as its type, or there are no rows.
+/// - [`Error::Device`] if buffer allocation fails.
+pub fn parse_csv(ctx: &Context, text: &str, schema: &Schema) -> Result<Table, Error> {
+    if schema.columns.is_empty() {
+        return Err(Error::Data("schema has no columns".into()));
+    }
+
+    let mut lines = text
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty());
+
+    let (_, header) = lines
+        .next()
+        .ok_or_else(|| Error::Data("missing header row".into()))?;
+    let header = split_fields(header, schema.delimiter, 1)?;
+
+    let positions = schema
+        .columns
+        .iter()
+        .map(|(name, _)| {
+            header
+                .iter()
+                .position(|field| field == name)
+                .ok_or_else(|| Error::Data(format!("column {name:?} not in header")))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut values: Vec<Vec<String>> = vec![Vec::new(); schema.columns.len()];
+    for (index, line) in lines {
+        let fields = split_fields(line, schema.delimiter, index + 1)?;
+        if fields.len() != header.len() {
+            return Err(Error::Data(format!(
+                "line {}: expected {} fields, got {}",
+                index + 1,
+                header.len(),
+                fields.len()
+            )));
+        }
+
+        for (column, &position) in values.iter_mut().zip(&positions) {
+            column.push(fields[position].clone());
+        }
+    }
+
+    let rows = values[0].len();
+    if rows == 0 {
+        return Err(Error::Data("no data rows".into()));
+    }
+
+    let columns = schema
+        .columns
+        .iter()
+        .zip(&values)
+        .map(|((name, ty), fields)| {
+            let column = match ty {
+                ColumnType::F32 => Column::F32(Tensor::from_slice(ctx, &parse(name, fields)?)?),
+                ColumnType::I32 => Column::I32(Tensor::from_slice(ctx, &parse(name, fields)?)?),
+                ColumnType::U32 => Column::U32(Tensor::from_slice(ctx, &parse(name, fields)?)?),
+                ColumnType::Bool => {
+                    Column::Bool(Tensor::from_slice(ctx, &parse_bool(name, fields)?)?)
+                }
+            };
+            Ok((name.clone(), column))
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+
+    Ok(Table { columns, rows })
+}
+
+/// Splits one CSV line into fields, unquoting quoted fields.
+fn split_fields(line: &str, delimiter: char, line_number: usize) -> Result<Vec<String>, Error> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut in_quotes = false;
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    field.push('"');
+                    chars.next();
+                }
+                '"' => in_quotes = false,
+                _ => field.push(c),
+            }
+        } else if c == '"' && field.trim().is_empty() && !quoted {
+            field.clear();
+            quoted = true;
+            in_quotes = true;
+        } else if c == delimiter {
+            fields.push(finish_field(&field, quoted));
+            field.clear();
+            quoted = false;
+        } else {
+            field.push(c);
+        }
+    }
+
+    if in_quotes {
+        return Err(Error::Data(format!(
+            "line {line_number}: unterminated quote"
+        )));
+    }
+    fields.push(finish_field(&field, quoted));
+
+    Ok(fields)
+}
+
+/// Returns a field's value, trimming unquoted fields.
+fn finish_field(field: &str, quoted: bool) -> String {
+    if quoted {
+        field.to_string()
+    } else {
+        field.trim().to_string()
+    }
+}
+
+/// Parses every field of column `name` as `T`.
+fn parse<T: core::str::FromStr>(name: &str, fields: &[String]) -> Result<Vec<T>, Error> {
+    fields
+        .iter()
+        .enumerate()
+        .map(|(row, field)| {
+            field.parse().map_err(|_| {
+                Error::Data(format!(
+                    "row {row}: cannot parse {field:?} in column {name:?}"
+                ))
+            })
+        })
+        .collect()
+}
+
+/// Parses every field of column `name` as a boolean.
+fn parse_bool(name: &str, fields: &[String]) -> Result<Vec<bool>, Error> {
+    fields
+        .iter()
+        .enumerate()
+        .map(|(row, field)| match field.to_ascii_lowercase().as_str() {
+            "true" | "1" => Ok(true),
+            "false" | "0" => Ok(false),
+            _ => Err(Error::Data(format!(
+                "row {row}: cannot parse {field:?} in column {name:?} as bool"
+            ))),
+        })
+        .collect()
+}
@@ -24,23 +24,28 @@ impl<T: Element> Buffer<T> {
         }
     }
 
-    /// Returns the buffer size in bytes.
-    pub(crate) fn byte_size(&self) -> u64 {
+    /// Returns the buffer size in bytes, including padding to a multiple of 4 elements.
+    #[must_use]
+    pub fn byte_size(&self) -> u64 {
         self.inner.size()
     }
 
     /// Returns the number of elements.
-    pub(crate) fn len(&self) -> usize {
+    #[must_use]
+    pub fn len(&self) -> usize {
         self.len
     }
 
     /// Returns `true` if empty.
-    pub(crate) fn is_empty(&self) -> bool {
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
         self.len == 0
     }
 
-    /// Returns the underlying wgpu buffer.
-    pub(crate) fn inner(&self) -> &wgpu::Buffer {
+    /// Returns the underlying wgpu buffer, e.g. to bind it in a custom pipeline on the
+    /// context's device.
+    #[must_use]
+    pub fn inner(&self) -> &wgpu::Buffer {
         &self.inner
     }
 }
@@ -95,7 +100,7 @@ mod tests {
         let ctx = Context::try_default().unwrap();
 
         let buf = ctx.create_buffer::<f32>(0).unwrap();
-        assert_eq!(buf.byte_size(), 0);
+        assert_eq!(buf.byte_size(), 16);
         assert!(buf.is_empty());
 
         let buf = ctx.create_buffer::<f32>(4).unwrap();
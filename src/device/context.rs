@@ -9,25 +9,55 @@ use alloc::sync::Arc;
 use alloc::vec::Vec;
 
 use spin::RwLock;
-use wgpu::naga::FastHashMap;
+use wgpu::naga::{self, FastHashMap};
 use wgpu::util::DeviceExt as _;
 
+use crate::error::TensorError;
 use crate::{Buffer, Element, Error};
 
-/// Default `max_storage_buffer_binding_size` (128 MiB).
-const MAX_STORAGE_BUFFER_SIZE: u64 = 128 * 1024 * 1024;
-
 /// Cache for compute pipelines keyed by type.
 type PipelineCache = RwLock<FastHashMap<TypeId, Arc<wgpu::ComputePipeline>>>;
 
+/// Cache for compute pipelines of WGSL built at run time, keyed by source.
+type SourceCache = RwLock<FastHashMap<String, Arc<wgpu::ComputePipeline>>>;
+
+/// Queue submission record, shared with the device's uncaptured error handler.
+#[derive(Default)]
+struct Submissions {
+    /// Id and op label of the latest submission; ids count from 1.
+    last: RwLock<(u64, &'static str)>,
+    /// First uncaptured device error not yet reported, tagged with its submission.
+    error: RwLock<Option<String>>,
+}
+
+impl Submissions {
+    /// Describes the latest submission, if any, for an error message.
+    fn describe(&self) -> Option<String> {
+        match *self.last.read() {
+            (0, _) => None,
+            (id, label) => Some(format!("submission {id} ({label})")),
+        }
+    }
+}
+
 /// Shared inner state for [`Context`].
 struct ContextInner {
     device: wgpu::Device,
     queue: wgpu::Queue,
     cache: PipelineCache,
+    sources: SourceCache,
+    max_binding_size: u64,
+    stop_flag: RwLock<Option<wgpu::Buffer>>,
+    submissions: Arc<Submissions>,
 }
 
 /// GPU device context for buffer and pipeline management.
+///
+/// Every op encodes and submits its own command buffer to the queue in call order, so the
+/// GPU runs ops in program order. Each submission gets the next id of a monotonically
+/// increasing counter, recorded with the op label; [`Error::Device`] messages name the latest
+/// submission, so an asynchronous GPU failure in a long training step can be traced to the op
+/// that caused it.
 pub struct Context {
     inner: Arc<ContextInner>,
 }
@@ -69,16 +99,41 @@ impl Context {
 
     /// Asynchronously creates a GPU context from a wgpu adapter.
     ///
+    /// Requests the largest storage buffer and binding sizes the adapter supports, so tensors
+    /// are not held to the 128 MiB default where the hardware allows more. Uncaptured device
+    /// errors are held with the submission they followed and returned by the next
+    /// [`Self::poll`] or read instead of panicking.
+    ///
     /// # Errors
     ///
     /// Returns [`Error::Device`] if device creation fails.
     pub async fn from_adapter_async(adapter: &wgpu::Adapter) -> Result<Self, Error> {
+        let supported = adapter.limits();
+        let descriptor = wgpu::DeviceDescriptor {
+            required_limits: wgpu::Limits {
+                max_storage_buffer_binding_size: supported.max_storage_buffer_binding_size,
+                max_buffer_size: supported.max_buffer_size,
+                ..wgpu::Limits::default()
+            },
+            ..wgpu::DeviceDescriptor::default()
+        };
+
         let (device, queue) = adapter
-            .request_device(&wgpu::DeviceDescriptor::default())
+            .request_device(&descriptor)
             .await
             .map_err(|e| Error::Device(format!("failed to create device: {e}")))?;
 
-        Ok(Self::from_device_queue(&device, &queue))
+        let ctx = Self::from_device_queue(&device, &queue);
+        let submissions = Arc::clone(&ctx.inner.submissions);
+        device.on_uncaptured_error(Arc::new(move |e| {
+            let mut error = submissions.error.write();
+            if error.is_none() {
+                let after = submissions.describe().unwrap_or_else(|| "setup".to_owned());
+                *error = Some(format!("{after} failed: {e}"));
+            }
+        }));
+
+        Ok(ctx)
     }
 
     /// Creates a GPU context from a wgpu adapter.
@@ -122,12 +177,22 @@ impl Context {
     }
 
     /// Creates a GPU context from existing wgpu device and queue.
+    ///
+    /// Buffer sizes are bounded by the limits the device was created with. The device's
+    /// uncaptured error handler is left to the caller; [`Self::last_submission`] identifies
+    /// the op an error followed.
     #[must_use]
     pub fn from_device_queue(device: &wgpu::Device, queue: &wgpu::Queue) -> Self {
+        let limits = device.limits();
         let inner = ContextInner {
             device: device.clone(),
             queue: queue.clone(),
             cache: RwLock::new(FastHashMap::default()),
+            sources: RwLock::new(FastHashMap::default()),
+            max_binding_size: u64::from(limits.max_storage_buffer_binding_size)
+                .min(limits.max_buffer_size),
+            stop_flag: RwLock::new(None),
+            submissions: Arc::default(),
         };
 
         Self {
@@ -135,37 +200,81 @@ impl Context {
         }
     }
 
+    /// Largest buffer in bytes that can be bound to a single storage binding.
+    ///
+    /// Tensor buffers must fit within this size.
+    #[must_use]
+    pub fn max_binding_size(&self) -> u64 {
+        self.inner.max_binding_size
+    }
+
     /// Blocks until all submitted GPU work completes.
     ///
     /// # Errors
     ///
-    /// Returns [`Error::Device`] if device poll fails.
+    /// Returns [`Error::Device`] if device poll fails or a submission raised a device error.
     pub fn poll(&self) -> Result<(), Error> {
         self.inner
             .device
             .poll(wgpu::PollType::wait_indefinitely())
-            .map_err(|e| Error::Device(format!("device poll failed: {e}")))?;
+            .map_err(|e| self.device_error(format_args!("device poll failed: {e}")))?;
 
-        Ok(())
+        self.take_device_error()
+    }
+
+    /// Id and op label of the latest queue submission, or `None` before the first.
+    ///
+    /// Ids increase by one per submission from 1, in the order ops were called, so they
+    /// line up across runs of the same program.
+    #[must_use]
+    pub fn last_submission(&self) -> Option<(u64, &'static str)> {
+        match *self.inner.submissions.last.read() {
+            (0, _) => None,
+            last => Some(last),
+        }
+    }
+
+    /// Records a queue submission for the op `label`, returning its id.
+    pub(crate) fn begin_submission(&self, label: &'static str) -> u64 {
+        let mut last = self.inner.submissions.last.write();
+        *last = (last.0 + 1, label);
+        last.0
+    }
+
+    /// Builds an [`Error::Device`] from `message`, naming the latest submission.
+    pub(crate) fn device_error(&self, message: impl core::fmt::Display) -> Error {
+        match self.inner.submissions.describe() {
+            Some(after) => Error::Device(format!("{message} (after {after})")),
+            None => Error::Device(format!("{message}")),
+        }
+    }
+
+    /// Returns the pending uncaptured device error, if any, clearing it.
+    fn take_device_error(&self) -> Result<(), Error> {
+        match self.inner.submissions.error.write().take() {
+            Some(message) => Err(Error::Device(message)),
+            None => Ok(()),
+        }
     }
 
     /// Creates an uninitialized GPU buffer with the given number of elements.
     ///
-    /// The buffer is padded to a multiple of 4 elements.
+    /// The buffer is padded to a non-zero multiple of 4 elements, so empty buffers stay bindable.
     ///
     /// # Errors
     ///
     /// Returns [`Error::Device`] if buffer size exceeds max storage buffer binding size.
-    pub(crate) fn create_buffer<T: Element>(&self, len: usize) -> Result<Buffer<T>, Error> {
+    pub fn create_buffer<T: Element>(&self, len: usize) -> Result<Buffer<T>, Error> {
         let native_size = core::mem::size_of::<T::Native>() as u64;
         let size = len as u64 * native_size;
-        if size > MAX_STORAGE_BUFFER_SIZE {
-            return Err(Error::Device(format!(
-                "buffer size {size} bytes exceeds limit ({MAX_STORAGE_BUFFER_SIZE} bytes)"
+        let limit = self.inner.max_binding_size;
+        if size > limit {
+            return Err(self.device_error(format_args!(
+                "buffer size {size} bytes exceeds limit ({limit} bytes)"
             )));
         }
 
-        let padded_len = (len.div_ceil(4) * 4) as u64;
+        let padded_len = (len.div_ceil(4).max(1) * 4) as u64;
         let padded_size = padded_len * native_size;
         let buffer = self.inner.device.create_buffer(&wgpu::BufferDescriptor {
             label: None,
@@ -181,24 +290,22 @@ impl Context {
 
     /// Creates a GPU buffer initialized from a slice.
     ///
-    /// The buffer is padded to a multiple of 4 elements.
+    /// The buffer is padded to a non-zero multiple of 4 elements, so empty buffers stay bindable.
     ///
     /// # Errors
     ///
     /// Returns [`Error::Device`] if buffer size exceeds max storage buffer binding size.
-    pub(crate) fn create_buffer_from_slice<T: Element>(
-        &self,
-        data: &[T],
-    ) -> Result<Buffer<T>, Error> {
+    pub fn create_buffer_from_slice<T: Element>(&self, data: &[T]) -> Result<Buffer<T>, Error> {
         let native_size = core::mem::size_of::<T::Native>() as u64;
         let size = data.len() as u64 * native_size;
-        if size > MAX_STORAGE_BUFFER_SIZE {
-            return Err(Error::Device(format!(
-                "buffer size {size} bytes exceeds limit ({MAX_STORAGE_BUFFER_SIZE} bytes)"
+        let limit = self.inner.max_binding_size;
+        if size > limit {
+            return Err(self.device_error(format_args!(
+                "buffer size {size} bytes exceeds limit ({limit} bytes)"
             )));
         }
 
-        let padded_len = data.len().div_ceil(4) * 4;
+        let padded_len = data.len().div_ceil(4).max(1) * 4;
         let mut native_data: Vec<T::Native> = data.iter().map(|x| x.to_native()).collect();
         native_data.resize(padded_len, T::Native::default());
 
@@ -227,21 +334,135 @@ impl Context {
             })
     }
 
+    /// Creates a read-only storage buffer from a slice.
+    ///
+    /// Empty slices produce a single zeroed element, as zero-sized bindings are invalid.
+    pub(crate) fn create_storage_buffer<T: bytemuck::Pod>(&self, data: &[T]) -> wgpu::Buffer {
+        let zeroed = [T::zeroed()];
+        let data = if data.is_empty() { &zeroed[..] } else { data };
+
+        self.inner
+            .device
+            .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: None,
+                contents: bytemuck::cast_slice(data),
+                usage: wgpu::BufferUsages::STORAGE,
+            })
+    }
+
+    /// Creates a buffer for the `(x, y, z)` workgroup counts of an indirect dispatch.
+    ///
+    /// The buffer is bound as storage by the kernel that computes the counts.
+    pub(crate) fn create_indirect_buffer(&self) -> wgpu::Buffer {
+        self.inner.device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size: core::mem::size_of::<[u32; 4]>() as u64,
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::INDIRECT,
+            mapped_at_creation: false,
+        })
+    }
+
+    /// Writes `data` into `buffer` starting at element `offset`.
+    ///
+    /// The write is queued and lands before any later submission.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidArgument`] if `offset + data.len()` exceeds the buffer length.
+    pub fn write_buffer<T: Element>(
+        &self,
+        buffer: &Buffer<T>,
+        offset: usize,
+        data: &[T],
+    ) -> Result<(), Error> {
+        check_range(buffer, offset, data.len())?;
+
+        if data.is_empty() {
+            return Ok(());
+        }
+
+        let native_size = core::mem::size_of::<T::Native>() as u64;
+        let native_data: Vec<T::Native> = data.iter().map(|x| x.to_native()).collect();
+
+        self.inner.queue.write_buffer(
+            buffer.inner(),
+            offset as u64 * native_size,
+            bytemuck::cast_slice(&native_data),
+        );
+        Ok(())
+    }
+
     /// Asynchronously copies buffer contents from GPU to CPU memory.
     ///
     /// # Errors
     ///
     /// Returns [`Error::Device`] if the read operation fails.
-    pub(crate) async fn read_buffer_async<T: Element>(
+    pub async fn read_buffer_async<T: Element>(&self, buffer: &Buffer<T>) -> Result<Vec<T>, Error> {
+        if buffer.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        self.read_slice_async(buffer, 0, buffer.len()).await
+    }
+
+    /// Asynchronously copies `len` elements starting at `offset` from GPU to CPU memory.
+    ///
+    /// Only that range is transferred.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidArgument`] if the range is out of bounds.
+    /// - [`Error::Device`] if the read operation fails.
+    pub async fn read_slice_async<T: Element>(
         &self,
         buffer: &Buffer<T>,
+        offset: usize,
+        len: usize,
     ) -> Result<Vec<T>, Error> {
-        if buffer.is_empty() {
+        check_range(buffer, offset, len)?;
+
+        if len == 0 {
             return Ok(Vec::new());
         }
 
+        self.read_range_async(buffer, offset, len, |native: &[T::Native]| {
+            native.iter().map(|x| T::from_native(*x)).collect()
+        })
+        .await
+    }
+
+    /// Asynchronously copies the element at `index` from GPU to CPU memory.
+    ///
+    /// Only that element is transferred, and no host allocation is made for it.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidArgument`] if `index` is out of bounds.
+    /// - [`Error::Device`] if the read operation fails.
+    pub async fn read_element_async<T: Element>(
+        &self,
+        buffer: &Buffer<T>,
+        index: usize,
+    ) -> Result<T, Error> {
+        check_range(buffer, index, 1)?;
+
+        self.read_range_async(buffer, index, 1, |native: &[T::Native]| {
+            T::from_native(native[0])
+        })
+        .await
+    }
+
+    /// Copies `len` elements starting at `offset` into a staging buffer and passes the mapped
+    /// native data to `read`.
+    async fn read_range_async<T: Element, R>(
+        &self,
+        buffer: &Buffer<T>,
+        offset: usize,
+        len: usize,
+        read: impl FnOnce(&[T::Native]) -> R,
+    ) -> Result<R, Error> {
         let native_size = core::mem::size_of::<T::Native>() as u64;
-        let size = buffer.len() as u64 * native_size;
+        let size = len as u64 * native_size;
 
         let staging = self.inner.device.create_buffer(&wgpu::BufferDescriptor {
             label: None,
@@ -254,7 +475,14 @@ impl Context {
             .inner
             .device
             .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
-        encoder.copy_buffer_to_buffer(buffer.inner(), 0, &staging, 0, size);
+        encoder.copy_buffer_to_buffer(
+            buffer.inner(),
+            offset as u64 * native_size,
+            &staging,
+            0,
+            size,
+        );
+        self.begin_submission("read");
         self.inner.queue.submit(Some(encoder.finish()));
 
         let slice = staging.slice(..);
@@ -267,15 +495,15 @@ impl Context {
         self.inner
             .device
             .poll(wgpu::PollType::wait_indefinitely())
-            .map_err(|e| Error::Device(format!("device poll failed: {e}")))?;
+            .map_err(|e| self.device_error(format_args!("device poll failed: {e}")))?;
 
         rx.await
-            .map_err(|_| Error::Device("channel closed".to_owned()))?
-            .map_err(|e| Error::Device(format!("buffer mapping failed: {e}")))?;
+            .map_err(|_| self.device_error("channel closed"))?
+            .map_err(|e| self.device_error(format_args!("buffer mapping failed: {e}")))?;
+        self.take_device_error()?;
 
         let data = slice.get_mapped_range();
-        let native_data: &[T::Native] = bytemuck::cast_slice(&data);
-        let result: Vec<T> = native_data.iter().map(|x| T::from_native(*x)).collect();
+        let result = read(bytemuck::cast_slice(&data));
         drop(data);
         staging.unmap();
 
@@ -290,10 +518,41 @@ impl Context {
     ///
     /// Returns [`Error::Device`] if the read operation fails.
     #[cfg(not(target_arch = "wasm32"))]
-    pub(crate) fn read_buffer<T: Element>(&self, buffer: &Buffer<T>) -> Result<Vec<T>, Error> {
+    pub fn read_buffer<T: Element>(&self, buffer: &Buffer<T>) -> Result<Vec<T>, Error> {
         pollster::block_on(self.read_buffer_async(buffer))
     }
 
+    /// Copies `len` elements starting at `offset` from GPU to CPU memory.
+    ///
+    /// Blocks until the transfer completes.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidArgument`] if the range is out of bounds.
+    /// - [`Error::Device`] if the read operation fails.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn read_slice<T: Element>(
+        &self,
+        buffer: &Buffer<T>,
+        offset: usize,
+        len: usize,
+    ) -> Result<Vec<T>, Error> {
+        pollster::block_on(self.read_slice_async(buffer, offset, len))
+    }
+
+    /// Copies the element at `index` from GPU to CPU memory.
+    ///
+    /// Blocks until the transfer completes.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidArgument`] if `index` is out of bounds.
+    /// - [`Error::Device`] if the read operation fails.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn read_element<T: Element>(&self, buffer: &Buffer<T>, index: usize) -> Result<T, Error> {
+        pollster::block_on(self.read_element_async(buffer, index))
+    }
+
     /// Gets or creates a cached compute pipeline.
     pub(crate) fn get_or_create_pipeline(
         &self,
@@ -311,28 +570,83 @@ impl Context {
             return Arc::clone(pipeline);
         }
 
+        let pipeline = Arc::new(self.create_pipeline(shader(), label));
+
+        cache.insert(type_id, Arc::clone(&pipeline));
+
+        pipeline
+    }
+
+    /// Gets or creates a cached compute pipeline for WGSL built at run time, keyed by its
+    /// source.
+    ///
+    /// The source is parsed and validated before its first compilation, so invalid WGSL is
+    /// reported here instead of raising an uncaptured device error.
+    ///
+    /// # Errors
+    ///
+    /// Returns the diagnostic message if `source` is not valid WGSL.
+    pub(crate) fn get_or_create_source_pipeline(
+        &self,
+        source: String,
+        label: &'static str,
+    ) -> Result<Arc<wgpu::ComputePipeline>, String> {
+        if let Some(pipeline) = self.inner.sources.read().get(&source) {
+            return Ok(Arc::clone(pipeline));
+        }
+
+        let module =
+            naga::front::wgsl::parse_str(&source).map_err(|error| error.emit_to_string(&source))?;
+        naga::valid::Validator::new(
+            naga::valid::ValidationFlags::all(),
+            naga::valid::Capabilities::default(),
+        )
+        .validate(&module)
+        .map_err(|error| error.emit_to_string(&source))?;
+
+        let mut sources = self.inner.sources.write();
+
+        if let Some(pipeline) = sources.get(&source) {
+            return Ok(Arc::clone(pipeline));
+        }
+
+        let pipeline = Arc::new(self.create_pipeline(source.clone(), label));
+
+        sources.insert(source, Arc::clone(&pipeline));
+
+        Ok(pipeline)
+    }
+
+    /// Compiles WGSL `source` with entry point `main` into a compute pipeline.
+    fn create_pipeline(&self, source: String, label: &'static str) -> wgpu::ComputePipeline {
         let shader_module = self
             .inner
             .device
             .create_shader_module(wgpu::ShaderModuleDescriptor {
                 label: Some(label),
-                source: wgpu::ShaderSource::Wgsl(shader().into()),
+                source: wgpu::ShaderSource::Wgsl(source.into()),
             });
 
-        let pipeline = Arc::new(self.inner.device.create_compute_pipeline(
-            &wgpu::ComputePipelineDescriptor {
+        self.inner
+            .device
+            .create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
                 label: Some(label),
                 layout: None,
                 module: &shader_module,
                 entry_point: Some("main"),
                 compilation_options: wgpu::PipelineCompilationOptions::default(),
                 cache: None,
-            },
-        ));
+            })
+    }
 
-        cache.insert(type_id, Arc::clone(&pipeline));
+    /// Returns the buffer of the installed stop flag, if any.
+    pub(crate) fn stop_flag(&self) -> Option<wgpu::Buffer> {
+        self.inner.stop_flag.read().clone()
+    }
 
-        pipeline
+    /// Installs or removes the buffer whose first element gates compute dispatches.
+    pub(crate) fn set_stop_flag_buffer(&self, flag: Option<wgpu::Buffer>) {
+        *self.inner.stop_flag.write() = flag;
     }
 
     /// Returns the wgpu device.
@@ -363,3 +677,15 @@ impl Clone for Context {
         }
     }
 }
+
+/// Checks that `len` elements starting at `offset` lie within `buffer`.
+fn check_range<T: Element>(buffer: &Buffer<T>, offset: usize, len: usize) -> Result<(), Error> {
+    if offset.checked_add(len).is_none_or(|end| end > buffer.len()) {
+        return Err(TensorError::InvalidArgument(format!(
+            "range of {len} elements at offset {offset} exceeds buffer length {}",
+            buffer.len()
+        ))
+        .into());
+    }
+    Ok(())
+}
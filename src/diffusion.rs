@@ -0,0 +1,262 @@
+//! Diffusion model sampling utilities.
+//!
+//! - [`NoiseSchedule`] — variance schedule with `β`, `α` and cumulative `ᾱ` tensors.
+//!
+//! Sampler steps reduce to `x_{t-1} = a·x_t + b·ε + c·z` and run as a single fused kernel via
+//! [`Tensor::diffusion_step`].
+
+use alloc::format;
+use alloc::vec::Vec;
+
+use crate::error::{Error, TensorError};
+use crate::{Context, Tensor};
+
+/// Variance schedule of a diffusion process.
+///
+/// Holds `β_t`, `α_t = 1 - β_t` and `ᾱ_t = ∏ α_s` for timesteps `0..steps` as `[steps]`
+/// tensors, plus a host copy used to compute sampler coefficients.
+pub struct NoiseSchedule {
+    /// Noise variances `β_t`.
+    betas: Tensor<f32>,
+    /// `α_t = 1 - β_t`.
+    alphas: Tensor<f32>,
+    /// Cumulative products `ᾱ_t`.
+    alphas_cumprod: Tensor<f32>,
+    /// Host copy of `β_t`.
+    host_betas: Vec<f64>,
+    /// Host copy of `ᾱ_t`.
+    host_alphas_cumprod: Vec<f64>,
+}
+
+impl NoiseSchedule {
+    /// Linear schedule with `β` spaced evenly from `beta_start` to `beta_end`.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidArgument`] if `steps` is zero or a `β` is not in `(0, 1)`.
+    /// - [`Error::Device`] if buffer allocation fails.
+    pub fn linear(
+        ctx: &Context,
+        steps: usize,
+        beta_start: f32,
+        beta_end: f32,
+    ) -> Result<Self, Error> {
+        let n = Self::step_count(steps)?;
+        let (start, end) = (f64::from(beta_start), f64::from(beta_end));
+        let betas = (0..n)
+            .map(|i| {
+                let frac = if n > 1 {
+                    f64::from(i) / f64::from(n - 1)
+                } else {
+                    0.0
+                };
+                start + (end - start) * frac
+            })
+            .collect();
+        Self::from_betas(ctx, betas)
+    }
+
+    /// Cosine schedule, `ᾱ_t ∝ cos²(π/2 · (t/steps + s) / (1 + s))`, with `β` clipped to
+    /// `0.999`.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidArgument`] if `steps` is zero or `s` is negative.
+    /// - [`Error::Device`] if buffer allocation fails.
+    pub fn cosine(ctx: &Context, steps: usize, s: f32) -> Result<Self, Error> {
+        let n = Self::step_count(steps)?;
+        if !(0.0..).contains(&s) {
+            return Err(TensorError::InvalidArgument(format!("offset {s} must be >= 0")).into());
+        }
+
+        let s = f64::from(s);
+        let alpha_bar = |i: u32| {
+            let t = (f64::from(i) / f64::from(n) + s) / (1.0 + s);
+            (t * core::f64::consts::FRAC_PI_2).cos().powi(2)
+        };
+        let betas = (0..n)
+            .map(|i| (1.0 - alpha_bar(i + 1) / alpha_bar(i)).min(0.999))
+            .collect();
+        Self::from_betas(ctx, betas)
+    }
+
+    /// Number of timesteps.
+    #[must_use]
+    pub fn steps(&self) -> usize {
+        self.host_betas.len()
+    }
+
+    /// Noise variances `β_t`, shape `[steps]`.
+    #[must_use]
+    pub fn betas(&self) -> &Tensor<f32> {
+        &self.betas
+    }
+
+    /// `α_t = 1 - β_t`, shape `[steps]`.
+    #[must_use]
+    pub fn alphas(&self) -> &Tensor<f32> {
+        &self.alphas
+    }
+
+    /// Cumulative products `ᾱ_t`, shape `[steps]`.
+    #[must_use]
+    pub fn alphas_cumprod(&self) -> &Tensor<f32> {
+        &self.alphas_cumprod
+    }
+
+    /// Coefficients `(a, b, c)` of the DDPM ancestral step from `t` to `t - 1`.
+    ///
+    /// Uses `σ_t² = β_t`; the final step `t = 0` adds no noise.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidArgument`] if `t` is not a valid timestep.
+    pub fn ddpm_coefficients(&self, t: usize) -> Result<(f32, f32, f32), Error> {
+        self.check_timestep(t)?;
+
+        let beta = self.host_betas[t];
+        let alpha = 1.0 - beta;
+        let alpha_bar = self.host_alphas_cumprod[t];
+
+        let a = 1.0 / alpha.sqrt();
+        let b = -beta / (alpha.sqrt() * (1.0 - alpha_bar).sqrt());
+        let c = if t > 0 { beta.sqrt() } else { 0.0 };
+
+        Ok(narrow(a, b, c))
+    }
+
+    /// Coefficients `(a, b, c)` of the DDIM step from `t` to `prev`, or to the clean sample
+    /// when `prev` is `None`.
+    ///
+    /// `eta` scales the injected noise: `0` is deterministic DDIM, `1` matches DDPM variance.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidArgument`] if `t` is not a valid timestep, `prev` is not
+    ///   earlier than `t`, or `eta` is negative.
+    pub fn ddim_coefficients(
+        &self,
+        t: usize,
+        prev: Option<usize>,
+        eta: f32,
+    ) -> Result<(f32, f32, f32), Error> {
+        self.check_timestep(t)?;
+        if prev.is_some_and(|prev| prev >= t) {
+            return Err(TensorError::InvalidArgument(format!(
+                "previous timestep {prev:?} must be earlier than {t}"
+            ))
+            .into());
+        }
+        if !(0.0..).contains(&eta) {
+            return Err(TensorError::InvalidArgument(format!("eta {eta} must be >= 0")).into());
+        }
+
+        let alpha_bar = self.host_alphas_cumprod[t];
+        let alpha_bar_prev = prev.map_or(1.0, |prev| self.host_alphas_cumprod[prev]);
+
+        let sigma = f64::from(eta)
+            * ((1.0 - alpha_bar_prev) / (1.0 - alpha_bar)).sqrt()
+            * (1.0 - alpha_bar / alpha_bar_prev).sqrt();
+
+        let a = (alpha_bar_prev / alpha_bar).sqrt();
+        let b =
+            (1.0 - alpha_bar_prev - sigma * sigma).max(0.0).sqrt() - a * (1.0 - alpha_bar).sqrt();
+
+        Ok(narrow(a, b, sigma))
+    }
+
+    /// DDPM ancestral step from `x_t` to `x_{t-1}` given the predicted noise `eps`.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidArgument`] if `t` is not a valid timestep.
+    /// - [`TensorError::InvalidShape`] if `eps` has different dimensions.
+    /// - [`Error::Device`] if buffer allocation fails.
+    pub fn ddpm_step(
+        &self,
+        x: &Tensor<f32>,
+        eps: &Tensor<f32>,
+        t: usize,
+        seed: u32,
+    ) -> Result<Tensor<f32>, Error> {
+        let (a, b, c) = self.ddpm_coefficients(t)?;
+        x.diffusion_step(eps, a, b, c, seed)
+    }
+
+    /// DDIM step from `x_t` to `x_prev` given the predicted noise `eps`.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidArgument`] if the timesteps or `eta` are invalid.
+    /// - [`TensorError::InvalidShape`] if `eps` has different dimensions.
+    /// - [`Error::Device`] if buffer allocation fails.
+    pub fn ddim_step(
+        &self,
+        x: &Tensor<f32>,
+        eps: &Tensor<f32>,
+        t: usize,
+        prev: Option<usize>,
+        eta: f32,
+        seed: u32,
+    ) -> Result<Tensor<f32>, Error> {
+        let (a, b, c) = self.ddim_coefficients(t, prev, eta)?;
+        x.diffusion_step(eps, a, b, c, seed)
+    }
+
+    /// Validates the step count and converts it for exact float arithmetic.
+    fn step_count(steps: usize) -> Result<u32, Error> {
+        u32::try_from(steps).ok().filter(|&n| n > 0).ok_or_else(|| {
+            TensorError::InvalidArgument(format!("invalid step count {steps}")).into()
+        })
+    }
+
+    /// Builds the schedule tensors from host `β` values.
+    fn from_betas(ctx: &Context, betas: Vec<f64>) -> Result<Self, Error> {
+        if let Some(beta) = betas.iter().find(|&&beta| !(beta > 0.0 && beta < 1.0)) {
+            return Err(
+                TensorError::InvalidArgument(format!("beta {beta} must be in (0, 1)")).into(),
+            );
+        }
+
+        let alphas_cumprod: Vec<f64> = betas
+            .iter()
+            .scan(1.0, |prod, beta| {
+                *prod *= 1.0 - beta;
+                Some(*prod)
+            })
+            .collect();
+
+        let upload = |values: Vec<f32>| Tensor::from_slice(ctx, &values);
+
+        Ok(Self {
+            betas: upload(betas.iter().copied().map(to_f32).collect())?,
+            alphas: upload(betas.iter().map(|beta| to_f32(1.0 - beta)).collect())?,
+            alphas_cumprod: upload(alphas_cumprod.iter().copied().map(to_f32).collect())?,
+            host_betas: betas,
+            host_alphas_cumprod: alphas_cumprod,
+        })
+    }
+
+    /// Checks `t` indexes a timestep.
+    fn check_timestep(&self, t: usize) -> Result<(), Error> {
+        if t >= self.steps() {
+            return Err(TensorError::InvalidArgument(format!(
+                "timestep {t} out of range for {} steps",
+                self.steps()
+            ))
+            .into());
+        }
+        Ok(())
+    }
+}
+
+/// Rounds a host value to `f32`.
+#[allow(clippy::cast_possible_truncation)]
+fn to_f32(value: f64) -> f32 {
+    value as f32
+}
+
+/// Rounds step coefficients to `f32`.
+fn narrow(a: f64, b: f64, c: f64) -> (f32, f32, f32) {
+    (to_f32(a), to_f32(b), to_f32(c))
+}
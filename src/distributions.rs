@@ -0,0 +1,163 @@
+//! Probability distributions for policies and probabilistic models.
+//!
+//! - [`Categorical`] — discrete distribution over classes, parameterized by logits.
+//! - [`Normal`] — element-wise Gaussian, parameterized by means and standard deviations.
+//!
+//! Sampling, log-probabilities and entropies each run as a single fused kernel. Samples are
+//! drawn from a counter-based generator, so the same seed always reproduces the same draws.
+
+use alloc::format;
+
+use crate::Tensor;
+use crate::error::{Error, TensorError};
+
+/// Categorical distribution over the classes of each row of `[rows, classes]` logits.
+///
+/// Probabilities are `softmax(logits)` along the class axis. Logits need not be normalized,
+/// and classes with a logit of `f32::MIN` are never sampled, so action masks can be applied
+/// to the logits.
+pub struct Categorical {
+    /// Unnormalized log-probabilities, `[rows, classes]`.
+    logits: Tensor<f32>,
+}
+
+impl Categorical {
+    /// Creates the distribution from `[rows, classes]` logits.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidShape`] if `logits` is not rank 2 with at least one class.
+    pub fn new(logits: Tensor<f32>) -> Result<Self, Error> {
+        match logits.dimensions() {
+            [_, classes] if *classes > 0 => Ok(Self { logits }),
+            dimensions => Err(TensorError::InvalidShape(format!(
+                "logits must be [rows, classes] with at least one class, got {dimensions:?}"
+            ))
+            .into()),
+        }
+    }
+
+    /// Draws one class per row, as `[rows]` class indices.
+    ///
+    /// # Errors
+    ///
+    /// - [`Error::Device`] if GPU operation fails.
+    pub fn sample(&self, seed: u32) -> Result<Tensor<u32>, Error> {
+        self.logits.categorical_sample(seed)
+    }
+
+    /// Log-probabilities of the `[rows]` class indices `actions`, one per row.
+    ///
+    /// Indices out of range get a log-probability of `f32::MIN`.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidShape`] if `actions` is not `[rows]`.
+    /// - [`Error::Device`] if GPU operation fails.
+    pub fn log_prob(&self, actions: &Tensor<u32>) -> Result<Tensor<f32>, Error> {
+        let rows = self.logits.dimensions()[0];
+        if actions.dimensions() != [rows] {
+            return Err(TensorError::InvalidShape(format!(
+                "actions {:?} must be [{rows}]",
+                actions.dimensions()
+            ))
+            .into());
+        }
+
+        self.logits.categorical_score(Some(actions))
+    }
+
+    /// Entropy of each row, `[rows]`.
+    ///
+    /// # Errors
+    ///
+    /// - [`Error::Device`] if GPU operation fails.
+    pub fn entropy(&self) -> Result<Tensor<f32>, Error> {
+        self.logits.categorical_score(None)
+    }
+
+    /// Unnormalized log-probabilities, `[rows, classes]`.
+    #[must_use]
+    pub fn logits(&self) -> &Tensor<f32> {
+        &self.logits
+    }
+}
+
+/// Element-wise normal distribution with means `mean` and standard deviations `std`.
+pub struct Normal {
+    /// Means.
+    mean: Tensor<f32>,
+    /// Standard deviations.
+    std: Tensor<f32>,
+}
+
+impl Normal {
+    /// Creates the distribution from `mean` and positive `std` of equal dimensions.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidShape`] if `mean` and `std` differ in dimensions.
+    pub fn new(mean: Tensor<f32>, std: Tensor<f32>) -> Result<Self, Error> {
+        if mean.dimensions() != std.dimensions() {
+            return Err(TensorError::InvalidShape(format!(
+                "mean {:?} and std {:?} must have equal dimensions",
+                mean.dimensions(),
+                std.dimensions()
+            ))
+            .into());
+        }
+
+        Ok(Self { mean, std })
+    }
+
+    /// Draws one sample per element, `mean + std·z` with `z ~ N(0, 1)`.
+    ///
+    /// # Errors
+    ///
+    /// - [`Error::Device`] if GPU operation fails.
+    pub fn sample(&self, seed: u32) -> Result<Tensor<f32>, Error> {
+        self.mean.normal_sample(&self.std, seed)
+    }
+
+    /// Log-density of `x` per element.
+    ///
+    /// Sum over the action axis for the log-probability of a diagonal Gaussian policy.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidShape`] if `x` differs from the distribution in dimensions.
+    /// - [`Error::Device`] if GPU operation fails.
+    pub fn log_prob(&self, x: &Tensor<f32>) -> Result<Tensor<f32>, Error> {
+        if x.dimensions() != self.mean.dimensions() {
+            return Err(TensorError::InvalidShape(format!(
+                "values {:?} must match distribution {:?}",
+                x.dimensions(),
+                self.mean.dimensions()
+            ))
+            .into());
+        }
+
+        self.mean.normal_log_prob(&self.std, x)
+    }
+
+    /// Entropy per element.
+    ///
+    /// # Errors
+    ///
+    /// - [`Error::Device`] if GPU operation fails.
+    pub fn entropy(&self) -> Result<Tensor<f32>, Error> {
+        self.mean.normal_entropy(&self.std)
+    }
+
+    /// Means.
+    #[must_use]
+    pub fn mean(&self) -> &Tensor<f32> {
+        &self.mean
+    }
+
+    /// Standard deviations.
+    #[must_use]
+    pub fn std(&self) -> &Tensor<f32> {
+        &self.std
+    }
+}
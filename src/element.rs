@@ -1,11 +1,12 @@
 //! Traits for GPU-compatible element types.
 //!
-//! - [`Element`] — base trait for GPU buffer types (`f32`, `i32`, `u32`, `bool`).
+//! - [`Element`] — base trait for GPU buffer types (`f32`, `i32`, `u32`, `bool`, [`Df64`]).
 //! - [`NumericElement`] — marker for numeric types (`f32`, `i32`, `u32`).
 //! - [`SignedElement`] — marker for signed types (`f32`, `i32`).
 //! - [`IntegerElement`] — marker for integer types (`i32`, `u32`).
 //! - [`FloatElement`] — marker for floating-point types (`f32`).
 //! - [`LogicalElement`] — marker for logical types (`bool`).
+//! - [`Df64`] — emulated double-precision float stored as a pair of `f32`.
 
 use core::fmt::Display;
 
@@ -210,6 +211,108 @@ impl Element for bool {
     }
 }
 
+impl Element for Df64 {
+    type Native = [f32; 2];
+
+    #[inline]
+    fn wgsl_type() -> &'static str {
+        "vec2<f32>"
+    }
+
+    #[inline]
+    fn wgsl_zero() -> &'static str {
+        "vec2<f32>(0.0, 0.0)"
+    }
+
+    #[inline]
+    fn wgsl_one() -> &'static str {
+        "vec2<f32>(1.0, 0.0)"
+    }
+
+    #[inline]
+    fn wgsl_max() -> &'static str {
+        "vec2<f32>(3.402823466e+38, 0.0)"
+    }
+
+    #[inline]
+    fn wgsl_min() -> &'static str {
+        "vec2<f32>(-3.402823466e+38, 0.0)"
+    }
+
+    #[inline]
+    fn from_native(native: [f32; 2]) -> Self {
+        Self {
+            hi: native[0],
+            lo: native[1],
+        }
+    }
+
+    #[inline]
+    fn to_native(self) -> [f32; 2] {
+        [self.hi, self.lo]
+    }
+}
+
+/// Emulated double-precision float (double-single, "df64").
+///
+/// Stores a value as an unevaluated sum `hi + lo` of two `f32` with `|lo| ≤ ulp(hi) / 2`,
+/// giving roughly 48 bits of mantissa. The exponent range is that of `f32`.
+///
+/// `Df64` tensors support element-wise `add`, `sub` and `mul`, `matmul`, `sum_reduce` and
+/// conversion to and from `f32`; other operations need a round trip through `f32`.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Df64 {
+    hi: f32,
+    lo: f32,
+}
+
+impl Df64 {
+    /// Creates a value from its high and low parts.
+    #[must_use]
+    pub const fn new(hi: f32, lo: f32) -> Self {
+        Self { hi, lo }
+    }
+
+    /// Returns the high (leading) part.
+    #[must_use]
+    pub const fn hi(self) -> f32 {
+        self.hi
+    }
+
+    /// Returns the low (trailing) part.
+    #[must_use]
+    pub const fn lo(self) -> f32 {
+        self.lo
+    }
+}
+
+impl From<f64> for Df64 {
+    #[allow(clippy::cast_possible_truncation)]
+    fn from(value: f64) -> Self {
+        let hi = value as f32;
+        let lo = (value - f64::from(hi)) as f32;
+        Self { hi, lo }
+    }
+}
+
+impl From<f32> for Df64 {
+    fn from(value: f32) -> Self {
+        Self { hi: value, lo: 0.0 }
+    }
+}
+
+impl From<Df64> for f64 {
+    fn from(value: Df64) -> Self {
+        f64::from(value.hi) + f64::from(value.lo)
+    }
+}
+
+impl Display for Df64 {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        Display::fmt(&f64::from(*self), f)
+    }
+}
+
 /// Trait for numeric GPU-compatible types.
 pub trait NumericElement: Element {}
 
@@ -230,7 +333,7 @@ impl IntegerElement for i32 {}
 impl IntegerElement for u32 {}
 
 /// Trait for floating-point GPU-compatible types.
-pub trait FloatElement: Element {}
+pub trait FloatElement: NumericElement {}
 
 impl FloatElement for f32 {}
 
@@ -16,6 +16,10 @@ pub enum Error {
     /// GPU device operation failed.
     #[error("{0}")]
     Device(String),
+
+    /// Reading or parsing input data failed.
+    #[error("data error: {0}")]
+    Data(String),
 }
 
 /// Errors from tensor operations.
@@ -25,4 +29,8 @@ pub enum TensorError {
     /// Invalid shape for operation.
     #[error("invalid shape: {0}")]
     InvalidShape(String),
+
+    /// Invalid non-shape argument for operation.
+    #[error("invalid argument: {0}")]
+    InvalidArgument(String),
 }
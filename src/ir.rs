@@ -0,0 +1,760 @@
+//! Operator graphs in a stable, serializable intermediate representation.
+//!
+//! - [`Graph`] — operator nodes over numbered values, exported and imported as text.
+//! - [`Node`] — one named operator applied to values, with attributes.
+//! - [`Attr`] / [`Attrs`] — attribute values of a node.
+//! - [`Registry`] — named operator implementations that run a graph on tensors.
+//!
+//! A graph decouples a model definition from the Rust code that runs it: external tools
+//! such as visualizers and converters read and write the text form, and [`Graph::run`]
+//! evaluates it with the operators of a [`Registry`]. The text form has one statement per
+//! line after a version header; values are numbered `%0`, `%1`, … in definition order,
+//! graph inputs first:
+//!
+//! ```text
+//! xnn-ir 1
+//! input %0 %1
+//! %2 = matmul %0 %1 transpose_b=true
+//! %3 = relu %2
+//! %4 = sum %3 axes=[1]
+//! output %4
+//! ```
+//!
+//! Attributes are `key=value` pairs of booleans, integers, floats (always written with a
+//! `.`, exponent, `inf` or `NaN`) or integer lists without spaces. Blank lines and lines
+//! starting with `#` are ignored.
+
+use alloc::boxed::Box;
+use alloc::collections::BTreeMap;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+use core::ops::Range;
+
+use crate::error::{Error, TensorError};
+use crate::{INFER, Tensor};
+
+/// Version written in and accepted from the header of the text form.
+pub const VERSION: u32 = 1;
+
+/// Attribute value of a node.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Attr {
+    /// Boolean flag.
+    Bool(bool),
+    /// Integer, e.g. a stride.
+    Int(i64),
+    /// Floating-point value, e.g. an epsilon.
+    Float(f32),
+    /// Integer list, e.g. axes or a shape.
+    Ints(Vec<i64>),
+}
+
+impl From<bool> for Attr {
+    fn from(value: bool) -> Self {
+        Self::Bool(value)
+    }
+}
+
+impl From<i64> for Attr {
+    fn from(value: i64) -> Self {
+        Self::Int(value)
+    }
+}
+
+impl From<f32> for Attr {
+    fn from(value: f32) -> Self {
+        Self::Float(value)
+    }
+}
+
+impl From<&[i64]> for Attr {
+    fn from(value: &[i64]) -> Self {
+        Self::Ints(value.to_vec())
+    }
+}
+
+impl<const N: usize> From<[i64; N]> for Attr {
+    fn from(value: [i64; N]) -> Self {
+        Self::Ints(value.to_vec())
+    }
+}
+
+impl fmt::Display for Attr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Bool(value) => write!(f, "{value}"),
+            Self::Int(value) => write!(f, "{value}"),
+            // Debug formatting always marks a float as such and round-trips exactly.
+            Self::Float(value) => write!(f, "{value:?}"),
+            Self::Ints(values) => {
+                f.write_str("[")?;
+                for (i, value) in values.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(",")?;
+                    }
+                    write!(f, "{value}")?;
+                }
+                f.write_str("]")
+            }
+        }
+    }
+}
+
+impl Attr {
+    /// Parses the text form of an attribute value.
+    fn parse(text: &str) -> Option<Self> {
+        match text {
+            "true" => return Some(Self::Bool(true)),
+            "false" => return Some(Self::Bool(false)),
+            _ => {}
+        }
+        if let Some(list) = text.strip_prefix('[').and_then(|t| t.strip_suffix(']')) {
+            if list.is_empty() {
+                return Some(Self::Ints(Vec::new()));
+            }
+            return list
+                .split(',')
+                .map(|value| value.parse().ok())
+                .collect::<Option<_>>()
+                .map(Self::Ints);
+        }
+        text.parse()
+            .map(Self::Int)
+            .ok()
+            .or_else(|| text.parse().map(Self::Float).ok())
+    }
+}
+
+/// Named attributes of a node, in insertion order.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Attrs(Vec<(String, Attr)>);
+
+impl Attrs {
+    /// Creates an empty attribute set.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets attribute `name` to `value`, replacing any previous value.
+    #[must_use]
+    pub fn with(mut self, name: &str, value: impl Into<Attr>) -> Self {
+        self.set(name, value.into());
+        self
+    }
+
+    /// Sets attribute `name` to `value`, replacing any previous value.
+    pub fn set(&mut self, name: &str, value: Attr) {
+        match self.0.iter_mut().find(|(key, _)| key == name) {
+            Some((_, slot)) => *slot = value,
+            None => self.0.push((name.into(), value)),
+        }
+    }
+
+    /// Returns attribute `name`, if set.
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<&Attr> {
+        self.0
+            .iter()
+            .find_map(|(key, value)| (key == name).then_some(value))
+    }
+
+    /// Iterates over the attributes in insertion order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &Attr)> {
+        self.0.iter().map(|(key, value)| (key.as_str(), value))
+    }
+
+    /// Returns boolean attribute `name`, or `default` if unset.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidArgument`] if the attribute is not a boolean.
+    pub fn bool(&self, name: &str, default: bool) -> Result<bool, Error> {
+        match self.get(name) {
+            None => Ok(default),
+            Some(&Attr::Bool(value)) => Ok(value),
+            Some(other) => Err(mismatch(name, "a boolean", other)),
+        }
+    }
+
+    /// Returns integer attribute `name`, or `default` if unset.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidArgument`] if the attribute is not an integer.
+    pub fn int(&self, name: &str, default: i64) -> Result<i64, Error> {
+        match self.get(name) {
+            None => Ok(default),
+            Some(&Attr::Int(value)) => Ok(value),
+            Some(other) => Err(mismatch(name, "an integer", other)),
+        }
+    }
+
+    /// Returns float attribute `name`, or `default` if unset. Integers are accepted.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidArgument`] if the attribute is not a number.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn float(&self, name: &str, default: f32) -> Result<f32, Error> {
+        match self.get(name) {
+            None => Ok(default),
+            Some(&Attr::Float(value)) => Ok(value),
+            Some(&Attr::Int(value)) => Ok(value as f32),
+            Some(other) => Err(mismatch(name, "a float", other)),
+        }
+    }
+
+    /// Returns required integer list attribute `name`.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidArgument`] if the attribute is unset or not an integer list.
+    pub fn ints(&self, name: &str) -> Result<&[i64], Error> {
+        match self.get(name) {
+            Some(Attr::Ints(values)) => Ok(values),
+            Some(other) => Err(mismatch(name, "an integer list", other)),
+            None => Err(TensorError::InvalidArgument(format!("missing attribute {name}")).into()),
+        }
+    }
+}
+
+/// Error for an attribute of the wrong kind.
+fn mismatch(name: &str, expected: &str, got: &Attr) -> Error {
+    TensorError::InvalidArgument(format!("attribute {name} must be {expected}, got {got}")).into()
+}
+
+/// One operator applied to graph values.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Node {
+    /// Operator name, looked up in a [`Registry`].
+    op: String,
+    /// Attributes passed to the operator.
+    attrs: Attrs,
+    /// Input value ids.
+    inputs: Vec<usize>,
+    /// Output value ids, numbered consecutively.
+    outputs: Range<usize>,
+}
+
+impl Node {
+    /// Operator name.
+    #[must_use]
+    pub fn op(&self) -> &str {
+        &self.op
+    }
+
+    /// Operator attributes.
+    #[must_use]
+    pub fn attrs(&self) -> &Attrs {
+        &self.attrs
+    }
+
+    /// Input value ids.
+    #[must_use]
+    pub fn inputs(&self) -> &[usize] {
+        &self.inputs
+    }
+
+    /// Output value ids.
+    #[must_use]
+    pub fn outputs(&self) -> Range<usize> {
+        self.outputs.clone()
+    }
+}
+
+/// Operator graph over numbered values.
+///
+/// Values `0..inputs` are the graph inputs; each node defines the next values in order, so
+/// a node only reads values defined before it and the graph is acyclic by construction.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Graph {
+    /// Number of graph inputs.
+    inputs: usize,
+    /// Nodes in definition order.
+    nodes: Vec<Node>,
+    /// Output value ids.
+    outputs: Vec<usize>,
+}
+
+impl Graph {
+    /// Creates a graph with `inputs` input values and no nodes or outputs.
+    #[must_use]
+    pub fn new(inputs: usize) -> Self {
+        Self {
+            inputs,
+            nodes: Vec::new(),
+            outputs: Vec::new(),
+        }
+    }
+
+    /// Number of graph inputs.
+    #[must_use]
+    pub fn inputs(&self) -> usize {
+        self.inputs
+    }
+
+    /// Nodes in definition order.
+    #[must_use]
+    pub fn nodes(&self) -> &[Node] {
+        &self.nodes
+    }
+
+    /// Output value ids.
+    #[must_use]
+    pub fn outputs(&self) -> &[usize] {
+        &self.outputs
+    }
+
+    /// Number of values defined so far, inputs included.
+    #[must_use]
+    pub fn values(&self) -> usize {
+        self.nodes
+            .last()
+            .map_or(self.inputs, |node| node.outputs.end)
+    }
+
+    /// Appends a node applying `op` with `attrs` to `inputs`, returning its output value id.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidArgument`] if an input is not yet defined.
+    pub fn node(&mut self, op: &str, inputs: &[usize], attrs: Attrs) -> Result<usize, Error> {
+        Ok(self.multi_node(op, inputs, attrs, 1)?.start)
+    }
+
+    /// Appends a node applying `op` with `attrs` to `inputs` that defines `outputs` values,
+    /// returning their ids.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidArgument`] if an input is not yet defined or `op` is not a
+    ///   valid name.
+    pub fn multi_node(
+        &mut self,
+        op: &str,
+        inputs: &[usize],
+        attrs: Attrs,
+        outputs: usize,
+    ) -> Result<Range<usize>, Error> {
+        let start = self.values();
+        if let Some(input) = inputs.iter().find(|&&input| input >= start) {
+            return Err(TensorError::InvalidArgument(format!(
+                "input %{input} of {op} is not defined"
+            ))
+            .into());
+        }
+        if !is_name(op) || attrs.iter().any(|(key, _)| !is_name(key)) {
+            return Err(TensorError::InvalidArgument(format!(
+                "operator {op:?} or its attribute names are not identifiers"
+            ))
+            .into());
+        }
+
+        let outputs = start..start + outputs;
+        self.nodes.push(Node {
+            op: op.into(),
+            attrs,
+            inputs: inputs.to_vec(),
+            outputs: outputs.clone(),
+        });
+        Ok(outputs)
+    }
+
+    /// Sets the graph outputs.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidArgument`] if an output is not defined or listed twice.
+    pub fn set_outputs(&mut self, outputs: &[usize]) -> Result<(), Error> {
+        let values = self.values();
+        for (i, &output) in outputs.iter().enumerate() {
+            if output >= values || outputs[..i].contains(&output) {
+                return Err(TensorError::InvalidArgument(format!(
+                    "output %{output} is not defined or listed twice"
+                ))
+                .into());
+            }
+        }
+        self.outputs = outputs.to_vec();
+        Ok(())
+    }
+
+    /// Parses the text form written by the [`Display`](fmt::Display) implementation.
+    ///
+    /// # Errors
+    ///
+    /// - [`Error::Data`] if the header or a statement is malformed, values are not numbered
+    ///   in definition order, or a node reads an undefined value.
+    pub fn parse(text: &str) -> Result<Self, Error> {
+        let mut lines = text
+            .lines()
+            .enumerate()
+            .map(|(i, line)| (i + 1, line.trim()))
+            .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'));
+
+        let header = format!("xnn-ir {VERSION}");
+        match lines.next() {
+            Some((_, line)) if line == header => {}
+            _ => return Err(Error::Data(format!("missing {header:?} header"))),
+        }
+
+        let mut graph = None::<Self>;
+        let mut finished = false;
+        for (number, line) in lines {
+            let error = |message: &str| Error::Data(format!("line {number}: {message}"));
+            let mut tokens = line.split_whitespace();
+
+            if finished {
+                return Err(error("statement after output"));
+            }
+
+            let Some(graph) = &mut graph else {
+                if tokens.next() != Some("input") {
+                    return Err(error("expected input statement"));
+                }
+                let ids = value_ids(tokens).ok_or_else(|| error("malformed value id"))?;
+                if ids.iter().copied().ne(0..ids.len()) {
+                    return Err(error("inputs must be numbered from %0"));
+                }
+                graph = Some(Self::new(ids.len()));
+                continue;
+            };
+
+            if line.starts_with("output") {
+                tokens.next();
+                let ids = value_ids(tokens).ok_or_else(|| error("malformed value id"))?;
+                graph.set_outputs(&ids).map_err(|e| error(&e.to_string()))?;
+                finished = true;
+                continue;
+            }
+
+            let (defined, call) = line
+                .split_once('=')
+                .ok_or_else(|| error("expected output values, '=' and an operator"))?;
+            let outputs =
+                value_ids(defined.split_whitespace()).ok_or_else(|| error("malformed value id"))?;
+            let start = graph.values();
+            if outputs.is_empty() || outputs.iter().copied().ne(start..start + outputs.len()) {
+                return Err(error(&format!("node must define values from %{start}")));
+            }
+
+            let mut tokens = call.split_whitespace();
+            let op = tokens.next().ok_or_else(|| error("missing operator"))?;
+            let mut inputs = Vec::new();
+            let mut attrs = Attrs::new();
+            for token in tokens {
+                if let Some((key, value)) = token.split_once('=') {
+                    let value = Attr::parse(value)
+                        .ok_or_else(|| error(&format!("malformed attribute {token:?}")))?;
+                    attrs.set(key, value);
+                } else if attrs.0.is_empty() {
+                    inputs.push(value_id(token).ok_or_else(|| error("malformed value id"))?);
+                } else {
+                    return Err(error("inputs must precede attributes"));
+                }
+            }
+
+            graph
+                .multi_node(op, &inputs, attrs, outputs.len())
+                .map_err(|e| error(&e.to_string()))?;
+        }
+
+        match graph {
+            Some(graph) if finished => Ok(graph),
+            _ => Err(Error::Data("missing output statement".into())),
+        }
+    }
+
+    /// Evaluates the graph on `inputs` with the operators of `registry`, returning the
+    /// output tensors.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidArgument`] if the number of inputs does not match, an
+    ///   operator is not registered or returns the wrong number of tensors.
+    /// - Any error of an operator.
+    pub fn run(
+        &self,
+        registry: &Registry,
+        inputs: Vec<Tensor<f32>>,
+    ) -> Result<Vec<Tensor<f32>>, Error> {
+        if inputs.len() != self.inputs {
+            return Err(TensorError::InvalidArgument(format!(
+                "graph takes {} inputs, got {}",
+                self.inputs,
+                inputs.len()
+            ))
+            .into());
+        }
+
+        let mut values = inputs;
+        for node in &self.nodes {
+            let op = registry.ops.get(&node.op).ok_or_else(|| {
+                TensorError::InvalidArgument(format!("operator {} is not registered", node.op))
+            })?;
+            let args: Vec<&Tensor<f32>> = node.inputs.iter().map(|&input| &values[input]).collect();
+            let results = op(&args, &node.attrs)?;
+            if results.len() != node.outputs.len() {
+                return Err(TensorError::InvalidArgument(format!(
+                    "operator {} returned {} outputs, expected {}",
+                    node.op,
+                    results.len(),
+                    node.outputs.len()
+                ))
+                .into());
+            }
+            values.extend(results);
+        }
+
+        // Outputs are distinct, so each value is taken at most once.
+        let mut values: Vec<Option<Tensor<f32>>> = values.into_iter().map(Some).collect();
+        Ok(self
+            .outputs
+            .iter()
+            .filter_map(|&output| values[output].take())
+            .collect())
+    }
+}
+
+impl fmt::Display for Graph {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "xnn-ir {VERSION}")?;
+        f.write_str("input")?;
+        for input in 0..self.inputs {
+            write!(f, " %{input}")?;
+        }
+        writeln!(f)?;
+
+        for node in &self.nodes {
+            for (i, output) in node.outputs.clone().enumerate() {
+                if i > 0 {
+                    f.write_str(" ")?;
+                }
+                write!(f, "%{output}")?;
+            }
+            write!(f, " = {}", node.op)?;
+            for input in &node.inputs {
+                write!(f, " %{input}")?;
+            }
+            for (key, value) in node.attrs.iter() {
+                write!(f, " {key}={value}")?;
+            }
+            writeln!(f)?;
+        }
+
+        f.write_str("output")?;
+        for output in &self.outputs {
+            write!(f, " %{output}")?;
+        }
+        writeln!(f)
+    }
+}
+
+/// Returns whether `name` is a valid operator or attribute name.
+fn is_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '.')
+}
+
+/// Parses a `%n` value id.
+fn value_id(token: &str) -> Option<usize> {
+    token.strip_prefix('%')?.parse().ok()
+}
+
+/// Parses whitespace-separated `%n` value ids.
+fn value_ids<'a>(tokens: impl Iterator<Item = &'a str>) -> Option<Vec<usize>> {
+    tokens.map(value_id).collect()
+}
+
+/// Operator implementation: maps input tensors and attributes to output tensors.
+pub type Op = Box<dyn Fn(&[&Tensor<f32>], &Attrs) -> Result<Vec<Tensor<f32>>, Error>>;
+
+/// Operator implementations by name.
+///
+/// [`Self::new`] registers the built-in operators, each taking the tensor method of the
+/// same name:
+///
+/// - Element-wise: `add`, `sub`, `mul`, `div`, `max`, `min`, `neg`, `exp`, `log`, `sqrt`,
+///   `tanh`, `relu`, `gelu`, `sigmoid`, `silu`, `softmax`.
+/// - `matmul` with boolean `transpose_a` and `transpose_b`, default `false`.
+/// - `reshape` with integer list `shape`, `-1` inferring a dimension; `permute` with
+///   integer list `axes`.
+/// - `sum`, `mean`, `max_reduce` and `min_reduce` with integer list `axes`, keeping the
+///   reduced axes with size 1.
+/// - `conv1d` of input, weight and optional bias, with integers `stride` and `dilation`,
+///   default 1, and integer list `padding` `[before, after]`, default `[0,0]`.
+pub struct Registry {
+    /// Operators by name.
+    ops: BTreeMap<String, Op>,
+}
+
+impl Default for Registry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Registry {
+    /// Creates a registry with the built-in operators.
+    #[must_use]
+    pub fn new() -> Self {
+        let mut registry = Self::empty();
+
+        for (name, f) in [
+            ("add", (|a, b| a.add(b)) as Binary),
+            ("sub", |a, b| a.sub(b)),
+            ("mul", |a, b| a.mul(b)),
+            ("div", |a, b| a.div(b)),
+            ("max", Tensor::max),
+            ("min", Tensor::min),
+        ] {
+            registry.register(name, move |x, _| {
+                let [a, b] = arity(x)?;
+                Ok(Vec::from([f(a, b)?]))
+            });
+        }
+
+        for (name, f) in [
+            ("neg", Tensor::neg as Unary),
+            ("exp", Tensor::exp),
+            ("log", Tensor::log),
+            ("sqrt", Tensor::sqrt),
+            ("tanh", Tensor::tanh),
+            ("relu", Tensor::relu),
+            ("gelu", Tensor::gelu),
+            ("sigmoid", Tensor::sigmoid),
+            ("silu", Tensor::silu),
+            ("softmax", Tensor::softmax),
+        ] {
+            registry.register(name, move |x, _| {
+                let [a] = arity(x)?;
+                Ok(Vec::from([f(a)?]))
+            });
+        }
+
+        for (name, f) in [
+            ("max_reduce", Tensor::max_reduce as Reduce),
+            ("min_reduce", Tensor::min_reduce),
+            ("mean", Tensor::mean_reduce),
+            ("sum", |x, axes| Tensor::<f32>::sum_reduce(x, axes, false)),
+        ] {
+            registry.register(name, move |x, attrs| {
+                let [a] = arity(x)?;
+                Ok(Vec::from([f(a, &usizes(attrs.ints("axes")?)?)?]))
+            });
+        }
+
+        registry.register("matmul", |x, attrs| {
+            let [a, b] = arity(x)?;
+            let transpose_a = attrs.bool("transpose_a", false)?;
+            let transpose_b = attrs.bool("transpose_b", false)?;
+            Ok(Vec::from([a.matmul(b, transpose_a, transpose_b)?]))
+        });
+        registry.register("reshape", |x, attrs| {
+            let [a] = arity(x)?;
+            let shape = attrs
+                .ints("shape")?
+                .iter()
+                .map(|&d| if d == -1 { Ok(INFER) } else { usize_attr(d) })
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok(Vec::from([a.reshape(&shape)?]))
+        });
+        registry.register("permute", |x, attrs| {
+            let [a] = arity(x)?;
+            Ok(Vec::from([a.permute(&usizes(attrs.ints("axes")?)?)?]))
+        });
+        registry.register("conv1d", |x, attrs| {
+            let (input, weight, bias) = match *x {
+                [input, weight] => (input, weight, None),
+                [input, weight, bias] => (input, weight, Some(bias)),
+                _ => {
+                    return Err(TensorError::InvalidArgument(format!(
+                        "expected 2 or 3 inputs, got {}",
+                        x.len()
+                    ))
+                    .into());
+                }
+            };
+            let stride = usize_attr(attrs.int("stride", 1)?)?;
+            let dilation = usize_attr(attrs.int("dilation", 1)?)?;
+            let padding = match attrs
+                .get("padding")
+                .map(|_| attrs.ints("padding"))
+                .transpose()?
+            {
+                None => (0, 0),
+                Some(&[before, after]) => (usize_attr(before)?, usize_attr(after)?),
+                Some(_) => {
+                    return Err(TensorError::InvalidArgument(
+                        "padding must be [before, after]".into(),
+                    )
+                    .into());
+                }
+            };
+            Ok(Vec::from([
+                input.conv1d(weight, bias, stride, dilation, padding)?
+            ]))
+        });
+
+        registry
+    }
+
+    /// Creates a registry without operators.
+    #[must_use]
+    pub fn empty() -> Self {
+        Self {
+            ops: BTreeMap::new(),
+        }
+    }
+
+    /// Registers `op` under `name`, replacing any operator of that name.
+    pub fn register(
+        &mut self,
+        name: &str,
+        op: impl Fn(&[&Tensor<f32>], &Attrs) -> Result<Vec<Tensor<f32>>, Error> + 'static,
+    ) {
+        self.ops.insert(name.into(), Box::new(op));
+    }
+
+    /// Returns whether an operator is registered under `name`.
+    #[must_use]
+    pub fn contains(&self, name: &str) -> bool {
+        self.ops.contains_key(name)
+    }
+
+    /// Iterates over the registered operator names in order.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.ops.keys().map(String::as_str)
+    }
+}
+
+/// Element-wise binary tensor method.
+type Binary = fn(&Tensor<f32>, &Tensor<f32>) -> Result<Tensor<f32>, Error>;
+/// Element-wise unary tensor method.
+type Unary = fn(&Tensor<f32>) -> Result<Tensor<f32>, Error>;
+/// Reduction tensor method over axes.
+type Reduce = fn(&Tensor<f32>, &[usize]) -> Result<Tensor<f32>, Error>;
+
+/// Checks that an operator received exactly `N` inputs.
+fn arity<'a, const N: usize>(inputs: &[&'a Tensor<f32>]) -> Result<[&'a Tensor<f32>; N], Error> {
+    inputs.try_into().map_err(|_| {
+        TensorError::InvalidArgument(format!("expected {N} inputs, got {}", inputs.len())).into()
+    })
+}
+
+/// Converts a non-negative integer attribute to `usize`.
+fn usize_attr(value: i64) -> Result<usize, Error> {
+    usize::try_from(value).map_err(|_| {
+        TensorError::InvalidArgument(format!("attribute value {value} must be non-negative")).into()
+    })
+}
+
+/// Converts an integer list attribute to `usize`s.
+fn usizes(values: &[i64]) -> Result<Vec<usize>, Error> {
+    values.iter().map(|&value| usize_attr(value)).collect()
+}
@@ -0,0 +1,292 @@
+//! Stream compaction kernels.
+
+use core::any::TypeId;
+use core::marker::PhantomData;
+
+use alloc::format;
+use alloc::string::String;
+
+use bytemuck::{Pod, Zeroable};
+
+use crate::kernel::{Kernel, MAX_WORKGROUPS, WORKGROUP_SIZE};
+use crate::{Buffer, Context, Element};
+
+/// Consecutive elements owned by one thread.
+const PER_THREAD: u32 = 8;
+
+/// Consecutive elements compacted by one workgroup.
+pub(crate) const BLOCK: usize = (PER_THREAD * WORKGROUP_SIZE) as usize;
+
+/// Kernel parameters passed to shader as uniform.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Pod, Zeroable)]
+struct Params {
+    len: u32,
+    blocks: u32,
+    rank: u32,
+    _pad: u32,
+}
+
+/// Non-zero count kernel: `counts[b]` is the number of non-zero elements in block `b`.
+///
+/// Each workgroup owns one block of `BLOCK` consecutive elements, the last one possibly
+/// short, and sums the per-thread counts in a tree.
+struct CountNonzero<T>(PhantomData<T>);
+
+impl<T: Element> Kernel for CountNonzero<T> {
+    const LABEL: &'static str = "count_nonzero";
+    type Output = u32;
+
+    fn wgsl() -> String {
+        let ty = T::wgsl_type();
+        let zero = T::wgsl_zero();
+
+        format!(
+            r"
+                const WG_SIZE: u32 = {WORKGROUP_SIZE}u;
+                const PER_THREAD: u32 = {PER_THREAD}u;
+
+                struct Params {{
+                    len: u32,
+                    blocks: u32,
+                    rank: u32,
+                    _pad: u32,
+                }}
+
+                @group(0) @binding(0) var<storage, read> x: array<{ty}>;
+                @group(0) @binding(1) var<storage, read_write> counts: array<u32>;
+                @group(0) @binding(2) var<uniform> params: Params;
+
+                var<workgroup> sdata: array<u32, WG_SIZE>;
+
+                @compute @workgroup_size(WG_SIZE)
+                fn main(
+                    @builtin(local_invocation_id) lid: vec3<u32>,
+                    @builtin(workgroup_id) wid: vec3<u32>
+                ) {{
+                    let tid = lid.x;
+                    let block = wid.x + wid.y * {MAX_WORKGROUPS}u;
+
+                    if block >= params.blocks {{
+                        return;
+                    }}
+
+                    let start = (block * WG_SIZE + tid) * PER_THREAD;
+                    let end = min(start + PER_THREAD, params.len);
+                    var n = 0u;
+                    for (var i = start; i < end; i++) {{
+                        if any(x[i] != {zero}) {{
+                            n += 1u;
+                        }}
+                    }}
+
+                    sdata[tid] = n;
+
+                    for (var s = WG_SIZE / 2u; s > 0u; s >>= 1u) {{
+                        workgroupBarrier();
+                        if tid < s {{
+                            sdata[tid] += sdata[tid + s];
+                        }}
+                    }}
+
+                    if tid == 0u {{
+                        counts[block] = sdata[0];
+                    }}
+                }}
+            "
+        )
+    }
+}
+
+/// Non-zero coordinate kernel, writing the row-major coordinates of each non-zero element.
+///
+/// Each workgroup owns the same block as [`CountNonzero`] and starts writing at the
+/// block's entry of the exclusive scan `offsets` of the block counts. Within the block the
+/// threads scan their counts in shared memory, so every thread knows where its own elements
+/// go and the output keeps the input order. The last thread of the last block writes the
+/// total count.
+struct WriteNonzero<T>(PhantomData<T>);
+
+impl<T: Element> Kernel for WriteNonzero<T> {
+    const LABEL: &'static str = "write_nonzero";
+    type Output = u32;
+
+    fn wgsl() -> String {
+        let ty = T::wgsl_type();
+        let zero = T::wgsl_zero();
+
+        format!(
+            r"
+                const WG_SIZE: u32 = {WORKGROUP_SIZE}u;
+                const PER_THREAD: u32 = {PER_THREAD}u;
+
+                struct Params {{
+                    len: u32,
+                    blocks: u32,
+                    rank: u32,
+                    _pad: u32,
+                }}
+
+                @group(0) @binding(0) var<storage, read> x: array<{ty}>;
+                @group(0) @binding(1) var<storage, read> offsets: array<u32>;
+                @group(0) @binding(2) var<storage, read_write> indices: array<u32>;
+                @group(0) @binding(3) var<storage, read_write> count: array<u32>;
+                @group(0) @binding(4) var<storage, read> strides: array<u32>;
+                @group(0) @binding(5) var<uniform> params: Params;
+
+                var<workgroup> sdata: array<u32, WG_SIZE>;
+
+                @compute @workgroup_size(WG_SIZE)
+                fn main(
+                    @builtin(local_invocation_id) lid: vec3<u32>,
+                    @builtin(workgroup_id) wid: vec3<u32>
+                ) {{
+                    let tid = lid.x;
+                    let block = wid.x + wid.y * {MAX_WORKGROUPS}u;
+
+                    if block >= params.blocks {{
+                        return;
+                    }}
+
+                    let start = (block * WG_SIZE + tid) * PER_THREAD;
+                    let end = min(start + PER_THREAD, params.len);
+                    var n = 0u;
+                    for (var i = start; i < end; i++) {{
+                        if any(x[i] != {zero}) {{
+                            n += 1u;
+                        }}
+                    }}
+
+                    sdata[tid] = n;
+
+                    for (var s = 1u; s < WG_SIZE; s <<= 1u) {{
+                        workgroupBarrier();
+                        var total = sdata[tid];
+                        if tid >= s {{
+                            total += sdata[tid - s];
+                        }}
+                        workgroupBarrier();
+                        sdata[tid] = total;
+                    }}
+
+                    var row = offsets[block] + sdata[tid] - n;
+                    for (var i = start; i < end; i++) {{
+                        if any(x[i] != {zero}) {{
+                            var remaining = i;
+                            for (var d = 0u; d < params.rank; d++) {{
+                                indices[row * params.rank + d] = remaining / strides[d];
+                                remaining = remaining % strides[d];
+                            }}
+                            row += 1u;
+                        }}
+                    }}
+
+                    if block == params.blocks - 1u && tid == WG_SIZE - 1u {{
+                        count[0] = offsets[block] + sdata[tid];
+                    }}
+                }}
+            "
+        )
+    }
+}
+
+/// Returns the block count of `len` elements and the dispatch grid of one workgroup per
+/// block.
+///
+/// # Panics
+///
+/// - Input length exceeds max size
+fn grid(len: usize) -> (u32, (u32, u32, u32)) {
+    let blocks = u32::try_from(len.div_ceil(BLOCK)).expect("input length exceeds max size");
+    (
+        blocks,
+        (
+            blocks.min(MAX_WORKGROUPS),
+            blocks.div_ceil(MAX_WORKGROUPS),
+            1,
+        ),
+    )
+}
+
+/// Counts the non-zero elements of `x` in each block of `BLOCK` consecutive elements into
+/// `counts`, which holds `x.len().div_ceil(BLOCK)` elements.
+///
+/// # Panics
+///
+/// - Input length exceeds max size
+pub(crate) fn count<T: Element>(ctx: &Context, x: &Buffer<T>, counts: &Buffer<u32>) {
+    let (blocks, workgroups) = grid(x.len());
+    if blocks == 0 {
+        return;
+    }
+
+    let pipeline = ctx.get_or_create_pipeline(
+        TypeId::of::<CountNonzero<T>>(),
+        CountNonzero::<T>::wgsl,
+        CountNonzero::<T>::LABEL,
+    );
+    let params = ctx.create_uniform_buffer(&Params {
+        len: u32::try_from(x.len()).expect("input length exceeds max size"),
+        blocks,
+        rank: 0,
+        _pad: 0,
+    });
+
+    crate::kernel::dispatch(
+        ctx,
+        &pipeline,
+        CountNonzero::<T>::LABEL,
+        &[x.inner(), counts.inner(), &params],
+        workgroups,
+    );
+}
+
+/// Writes the coordinates of the non-zero elements of contiguous `x` with `strides` as rows
+/// of `indices`, starting each block at its entry of `offsets`, and their total into
+/// `count[0]`.
+///
+/// # Panics
+///
+/// - Input length or rank exceeds max size
+pub(crate) fn write<T: Element>(
+    ctx: &Context,
+    x: &Buffer<T>,
+    offsets: &Buffer<u32>,
+    indices: &Buffer<u32>,
+    count: &Buffer<u32>,
+    strides: &[usize],
+) {
+    let (blocks, workgroups) = grid(x.len());
+    if blocks == 0 {
+        return;
+    }
+
+    let pipeline = ctx.get_or_create_pipeline(
+        TypeId::of::<WriteNonzero<T>>(),
+        WriteNonzero::<T>::wgsl,
+        WriteNonzero::<T>::LABEL,
+    );
+    let rank = u32::try_from(strides.len()).expect("input rank exceeds max size");
+    let strides = ctx.create_storage_buffer(&crate::kernel::convert_strides(strides));
+    let params = ctx.create_uniform_buffer(&Params {
+        len: u32::try_from(x.len()).expect("input length exceeds max size"),
+        blocks,
+        rank,
+        _pad: 0,
+    });
+
+    crate::kernel::dispatch(
+        ctx,
+        &pipeline,
+        WriteNonzero::<T>::LABEL,
+        &[
+            x.inner(),
+            offsets.inner(),
+            indices.inner(),
+            count.inner(),
+            &strides,
+            &params,
+        ],
+        workgroups,
+    );
+}
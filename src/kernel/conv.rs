@@ -0,0 +1,369 @@
+//! Convolution kernels for NCHW and NHWC memory formats, and for `[n, c, l]` sequences.
+//!
+//! Threads follow the memory order of the output. In NHWC neighbouring threads handle
+//! neighbouring channels of one pixel and read contiguous input and weight memory; in NCHW
+//! they handle neighbouring pixels of one channel.
+
+use core::any::TypeId;
+
+use alloc::format;
+use alloc::string::String;
+
+use bytemuck::{Pod, Zeroable};
+
+use crate::kernel::{Kernel, MAX_WORKGROUPS, WORKGROUP_SIZE};
+use crate::{Buffer, Context};
+
+/// Kernel parameters passed to shader as uniform.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Pod, Zeroable)]
+struct Params {
+    height: u32,
+    width: u32,
+    channels: u32,
+    out_height: u32,
+    out_width: u32,
+    kernel_height: u32,
+    kernel_width: u32,
+    stride_height: u32,
+    stride_width: u32,
+    pad_height: u32,
+    pad_width: u32,
+    has_bias: u32,
+    len: u32,
+    channels_last: u32,
+    _pad1: u32,
+    _pad2: u32,
+}
+
+/// 1D convolution kernel parameters passed to shader as uniform.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Pod, Zeroable)]
+struct Conv1dParams {
+    length: u32,
+    in_channels: u32,
+    out_channels: u32,
+    out_length: u32,
+    kernel: u32,
+    stride: u32,
+    dilation: u32,
+    pad_left: u32,
+    has_bias: u32,
+    len: u32,
+    _pad1: u32,
+    _pad2: u32,
+}
+
+/// Depthwise 2D convolution: each channel is convolved with its own `[kh, kw]` filter.
+struct DepthwiseConv2d;
+
+impl Kernel for DepthwiseConv2d {
+    const LABEL: &'static str = "depthwise_conv2d";
+    type Output = f32;
+
+    fn wgsl() -> String {
+        format!(
+            r"
+                struct Params {{
+                    height: u32,
+                    width: u32,
+                    channels: u32,
+                    out_height: u32,
+                    out_width: u32,
+                    kernel_height: u32,
+                    kernel_width: u32,
+                    stride_height: u32,
+                    stride_width: u32,
+                    pad_height: u32,
+                    pad_width: u32,
+                    has_bias: u32,
+                    len: u32,
+                    channels_last: u32,
+                    _pad1: u32,
+                    _pad2: u32,
+                }}
+
+                @group(0) @binding(0) var<storage, read> x: array<f32>;
+                @group(0) @binding(1) var<storage, read> weight: array<f32>;
+                @group(0) @binding(2) var<storage, read> bias: array<f32>;
+                @group(0) @binding(3) var<storage, read_write> y: array<f32>;
+                @group(0) @binding(4) var<uniform> params: Params;
+
+                @compute @workgroup_size({WORKGROUP_SIZE})
+                fn main(@builtin(global_invocation_id) gid: vec3<u32>) {{
+                    let tid = gid.x + gid.y * {MAX_WORKGROUPS}u * {WORKGROUP_SIZE}u;
+
+                    if tid >= params.len {{
+                        return;
+                    }}
+
+                    let c = params.channels;
+                    let plane = params.out_height * params.out_width;
+                    var ch: u32;
+                    var pixel: u32;
+                    var channel_stride: u32;
+                    var pixel_stride: u32;
+                    if params.channels_last != 0u {{
+                        ch = tid % c;
+                        pixel = tid / c;
+                        channel_stride = 1u;
+                        pixel_stride = c;
+                    }} else {{
+                        ch = tid / plane % c;
+                        pixel = tid / (plane * c) * plane + tid % plane;
+                        channel_stride = params.height * params.width;
+                        pixel_stride = 1u;
+                    }}
+                    let ox = pixel % params.out_width;
+                    let oy = pixel / params.out_width % params.out_height;
+                    let batch = pixel / plane;
+                    let base = batch * c * params.height * params.width + ch * channel_stride;
+
+                    var acc = 0.0;
+                    if params.has_bias != 0u {{
+                        acc = bias[ch];
+                    }}
+
+                    for (var ky = 0u; ky < params.kernel_height; ky++) {{
+                        let iy = i32(oy * params.stride_height + ky) - i32(params.pad_height);
+                        if iy < 0 || iy >= i32(params.height) {{
+                            continue;
+                        }}
+                        let row = u32(iy) * params.width;
+
+                        for (var kx = 0u; kx < params.kernel_width; kx++) {{
+                            let ix = i32(ox * params.stride_width + kx) - i32(params.pad_width);
+                            if ix < 0 || ix >= i32(params.width) {{
+                                continue;
+                            }}
+
+                            let src = base + (row + u32(ix)) * pixel_stride;
+                            acc += x[src] * weight[(ky * params.kernel_width + kx) * c + ch];
+                        }}
+                    }}
+
+                    y[tid] = acc;
+                }}
+            "
+        )
+    }
+}
+
+/// Dilated 1D convolution over `[batch, in_channels, length]` input with
+/// `[kernel, in_channels, out_channels]` weights.
+///
+/// Each thread computes one output `y[b, o, t] = Σ x[b, i, t·stride + j·dilation - pad_left] ·
+/// w[j, i, o]`, skipping taps that fall into the padding.
+struct Conv1d;
+
+impl Kernel for Conv1d {
+    const LABEL: &'static str = "conv1d";
+    type Output = f32;
+
+    fn wgsl() -> String {
+        format!(
+            r"
+                struct Params {{
+                    length: u32,
+                    in_channels: u32,
+                    out_channels: u32,
+                    out_length: u32,
+                    kernel: u32,
+                    stride: u32,
+                    dilation: u32,
+                    pad_left: u32,
+                    has_bias: u32,
+                    len: u32,
+                    _pad1: u32,
+                    _pad2: u32,
+                }}
+
+                @group(0) @binding(0) var<storage, read> x: array<f32>;
+                @group(0) @binding(1) var<storage, read> weight: array<f32>;
+                @group(0) @binding(2) var<storage, read> bias: array<f32>;
+                @group(0) @binding(3) var<storage, read_write> y: array<f32>;
+                @group(0) @binding(4) var<uniform> params: Params;
+
+                @compute @workgroup_size({WORKGROUP_SIZE})
+                fn main(@builtin(global_invocation_id) gid: vec3<u32>) {{
+                    let tid = gid.x + gid.y * {MAX_WORKGROUPS}u * {WORKGROUP_SIZE}u;
+
+                    if tid >= params.len {{
+                        return;
+                    }}
+
+                    let t = tid % params.out_length;
+                    let o = (tid / params.out_length) % params.out_channels;
+                    let b = tid / (params.out_length * params.out_channels);
+
+                    var acc = 0.0;
+                    if params.has_bias != 0u {{
+                        acc = bias[o];
+                    }}
+
+                    let start = i32(t * params.stride) - i32(params.pad_left);
+                    for (var j = 0u; j < params.kernel; j++) {{
+                        let pos = start + i32(j * params.dilation);
+                        if pos < 0 || pos >= i32(params.length) {{
+                            continue;
+                        }}
+
+                        let x_base = b * params.in_channels * params.length + u32(pos);
+                        let w_base = j * params.in_channels * params.out_channels + o;
+                        for (var i = 0u; i < params.in_channels; i++) {{
+                            acc += x[x_base + i * params.length]
+                                * weight[w_base + i * params.out_channels];
+                        }}
+                    }}
+
+                    y[tid] = acc;
+                }}
+            "
+        )
+    }
+}
+
+/// Convolves `[batch, channels, height, width]` input `x` with `[kh, kw, channels]` depthwise
+/// filters into `[batch, channels, out_height, out_width]`, adding `bias` when given.
+///
+/// Input and output are stored in NHWC order with `channels_last` set and NCHW order
+/// otherwise.
+///
+/// # Panics
+///
+/// - Output length exceeds max size
+/// - Dimension exceeds max size
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn depthwise_conv2d(
+    ctx: &Context,
+    x: &Buffer<f32>,
+    weight: &Buffer<f32>,
+    bias: Option<&Buffer<f32>>,
+    y: &Buffer<f32>,
+    [channels, height, width]: [usize; 3],
+    (kernel_height, kernel_width): (usize, usize),
+    (stride_height, stride_width): (usize, usize),
+    (pad_height, pad_width): (usize, usize),
+    channels_last: bool,
+) {
+    let to_u32 = |x: usize| u32::try_from(x).expect("dimension exceeds max size");
+    let len = u32::try_from(y.len()).expect("output length exceeds max size");
+
+    if len == 0 {
+        return;
+    }
+
+    let pipeline = ctx.get_or_create_pipeline(
+        TypeId::of::<DepthwiseConv2d>(),
+        DepthwiseConv2d::wgsl,
+        DepthwiseConv2d::LABEL,
+    );
+
+    let unbiased;
+    let bias_buffer = if let Some(bias) = bias {
+        bias.inner()
+    } else {
+        unbiased = ctx.create_storage_buffer(&[0.0f32]);
+        &unbiased
+    };
+
+    let out_width = (width + 2 * pad_width - kernel_width) / stride_width + 1;
+    let out_height = (height + 2 * pad_height - kernel_height) / stride_height + 1;
+
+    let params = ctx.create_uniform_buffer(&Params {
+        height: to_u32(height),
+        width: to_u32(width),
+        channels: to_u32(channels),
+        out_height: to_u32(out_height),
+        out_width: to_u32(out_width),
+        kernel_height: to_u32(kernel_height),
+        kernel_width: to_u32(kernel_width),
+        stride_height: to_u32(stride_height),
+        stride_width: to_u32(stride_width),
+        pad_height: to_u32(pad_height),
+        pad_width: to_u32(pad_width),
+        has_bias: u32::from(bias.is_some()),
+        len,
+        channels_last: u32::from(channels_last),
+        _pad1: 0,
+        _pad2: 0,
+    });
+
+    let (wx, wy) = crate::kernel::compute_workgroups(len);
+
+    crate::kernel::dispatch(
+        ctx,
+        &pipeline,
+        DepthwiseConv2d::LABEL,
+        &[x.inner(), weight.inner(), bias_buffer, y.inner(), &params],
+        (wx, wy, 1),
+    );
+}
+
+/// Convolves contiguous `[batch, in_channels, length]` input `x` with
+/// `[kernel, in_channels, out_channels]` weights into `[batch, out_channels, out_length]`,
+/// adding `bias` when given.
+///
+/// The input is zero padded by `pad_left` steps ahead of it; padding after it only extends
+/// `out_length`.
+///
+/// # Panics
+///
+/// - Output length exceeds max size
+/// - Dimension exceeds max size
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn conv1d(
+    ctx: &Context,
+    x: &Buffer<f32>,
+    weight: &Buffer<f32>,
+    bias: Option<&Buffer<f32>>,
+    y: &Buffer<f32>,
+    [in_channels, length]: [usize; 2],
+    [out_channels, out_length]: [usize; 2],
+    kernel: usize,
+    stride: usize,
+    dilation: usize,
+    pad_left: usize,
+) {
+    let to_u32 = |x: usize| u32::try_from(x).expect("dimension exceeds max size");
+    let len = u32::try_from(y.len()).expect("output length exceeds max size");
+
+    if len == 0 {
+        return;
+    }
+
+    let pipeline = ctx.get_or_create_pipeline(TypeId::of::<Conv1d>(), Conv1d::wgsl, Conv1d::LABEL);
+
+    let unbiased;
+    let bias_buffer = if let Some(bias) = bias {
+        bias.inner()
+    } else {
+        unbiased = ctx.create_storage_buffer(&[0.0f32]);
+        &unbiased
+    };
+
+    let params = ctx.create_uniform_buffer(&Conv1dParams {
+        length: to_u32(length),
+        in_channels: to_u32(in_channels),
+        out_channels: to_u32(out_channels),
+        out_length: to_u32(out_length),
+        kernel: to_u32(kernel),
+        stride: to_u32(stride),
+        dilation: to_u32(dilation),
+        pad_left: to_u32(pad_left),
+        has_bias: u32::from(bias.is_some()),
+        len,
+        _pad1: 0,
+        _pad2: 0,
+    });
+
+    let (wx, wy) = crate::kernel::compute_workgroups(len);
+
+    crate::kernel::dispatch(
+        ctx,
+        &pipeline,
+        Conv1d::LABEL,
+        &[x.inner(), weight.inner(), bias_buffer, y.inner(), &params],
+        (wx, wy, 1),
+    );
+}
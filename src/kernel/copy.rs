@@ -5,24 +5,34 @@ use crate::Context;
 /// Pipeline label for debugging.
 const LABEL: &str = "copy";
 
-/// Copies buffer contents from source to destination.
+/// Copies `size_bytes` from the start of source to destination at `dst_offset` bytes.
 ///
 /// # Panics
 ///
 /// - Source buffer size mismatch
 /// - Destination buffer size mismatch
-pub(crate) fn execute(ctx: &Context, src: &wgpu::Buffer, dst: &wgpu::Buffer, size_bytes: u64) {
+pub(crate) fn execute(
+    ctx: &Context,
+    src: &wgpu::Buffer,
+    dst: &wgpu::Buffer,
+    dst_offset: u64,
+    size_bytes: u64,
+) {
     if size_bytes == 0 {
         return;
     }
 
     assert!(src.size() >= size_bytes, "source buffer size mismatch");
-    assert!(dst.size() >= size_bytes, "destination buffer size mismatch");
+    assert!(
+        dst.size() >= dst_offset + size_bytes,
+        "destination buffer size mismatch"
+    );
 
+    ctx.begin_submission(LABEL);
     let mut encoder = ctx
         .device()
         .create_command_encoder(&wgpu::CommandEncoderDescriptor { label: Some(LABEL) });
-    encoder.copy_buffer_to_buffer(src, 0, dst, 0, size_bytes);
+    encoder.copy_buffer_to_buffer(src, 0, dst, dst_offset, size_bytes);
 
     ctx.queue().submit(Some(encoder.finish()));
 }
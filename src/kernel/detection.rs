@@ -0,0 +1,407 @@
+//! Object detection kernels.
+
+use core::any::TypeId;
+
+use alloc::format;
+use alloc::string::String;
+
+use bytemuck::{Pod, Zeroable};
+
+use crate::kernel::{Kernel, MAX_WORKGROUPS, WORKGROUP_SIZE};
+use crate::{Buffer, Context};
+
+/// Kernel parameters passed to shader as uniform.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Pod, Zeroable)]
+struct Params {
+    n: u32,
+    threshold: f32,
+}
+
+/// Box overlap kernel parameters passed to shader as uniform.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Pod, Zeroable)]
+struct IouParams {
+    batch: u32,
+    n: u32,
+    m: u32,
+    _pad: u32,
+}
+
+/// ROI align kernel parameters passed to shader as uniform.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Pod, Zeroable)]
+struct RoiAlignParams {
+    rois: u32,
+    batch: u32,
+    channels: u32,
+    height: u32,
+    width: u32,
+    out_height: u32,
+    out_width: u32,
+    sampling_ratio: u32,
+    spatial_scale: f32,
+    _pad: [u32; 3],
+}
+
+/// WGSL intersection over union of two `(x1, y1, x2, y2)` boxes; empty unions give zero.
+pub(crate) const IOU_WGSL: &str = r"
+    fn box_iou(a: vec4<f32>, b: vec4<f32>) -> f32 {
+        let area_a = max(a.z - a.x, 0.0) * max(a.w - a.y, 0.0);
+        let area_b = max(b.z - b.x, 0.0) * max(b.w - b.y, 0.0);
+        let lo = max(a.xy, b.xy);
+        let hi = min(a.zw, b.zw);
+        let size = max(hi - lo, vec2<f32>(0.0));
+        let inter = size.x * size.y;
+        let total = area_a + area_b - inter;
+        return select(0.0, inter / total, total > 0.0);
+    }
+";
+
+/// Pairwise intersection over union between two batched box sets.
+struct BoxIou;
+
+impl Kernel for BoxIou {
+    const LABEL: &'static str = "box_iou";
+    type Output = f32;
+
+    fn wgsl() -> String {
+        format!(
+            r"
+                struct Params {{
+                    batch: u32,
+                    n: u32,
+                    m: u32,
+                    _pad: u32,
+                }}
+
+                {IOU_WGSL}
+
+                @group(0) @binding(0) var<storage, read> a: array<vec4<f32>>;
+                @group(0) @binding(1) var<storage, read> b: array<vec4<f32>>;
+                @group(0) @binding(2) var<storage, read_write> y: array<f32>;
+                @group(0) @binding(3) var<uniform> params: Params;
+
+                @compute @workgroup_size({WORKGROUP_SIZE})
+                fn main(@builtin(global_invocation_id) gid: vec3<u32>) {{
+                    let tid = gid.x + gid.y * {MAX_WORKGROUPS}u * {WORKGROUP_SIZE}u;
+
+                    if tid >= params.batch * params.n * params.m {{
+                        return;
+                    }}
+
+                    let j = tid % params.m;
+                    let i = tid / params.m % params.n;
+                    let k = tid / (params.n * params.m);
+
+                    y[tid] = box_iou(a[k * params.n + i], b[k * params.m + j]);
+                }}
+            "
+        )
+    }
+}
+
+/// Greedy non-maximum suppression over boxes visited in score order.
+///
+/// A single workgroup walks the sorted boxes in turn; each kept box suppresses the later boxes
+/// overlapping it by more than the threshold, with all threads sharing the comparisons.
+struct Nms;
+
+impl Kernel for Nms {
+    const LABEL: &'static str = "nms";
+    type Output = u32;
+
+    fn wgsl() -> String {
+        format!(
+            r"
+                struct Params {{
+                    n: u32,
+                    threshold: f32,
+                }}
+
+                {IOU_WGSL}
+
+                @group(0) @binding(0) var<storage, read> boxes: array<vec4<f32>>;
+                @group(0) @binding(1) var<storage, read> order: array<u32>;
+                @group(0) @binding(2) var<storage, read_write> removed: array<u32>;
+                @group(0) @binding(3) var<storage, read_write> keep: array<u32>;
+                @group(0) @binding(4) var<storage, read_write> count: array<u32>;
+                @group(0) @binding(5) var<uniform> params: Params;
+
+                var<workgroup> current: u32;
+
+                @compute @workgroup_size({WORKGROUP_SIZE})
+                fn main(@builtin(local_invocation_index) lid: u32) {{
+                    var kept = 0u;
+
+                    for (var i = 0u; i < params.n; i++) {{
+                        if lid == 0u {{
+                            current = removed[i];
+                        }}
+
+                        if workgroupUniformLoad(&current) == 0u {{
+                            let a = boxes[order[i]];
+                            if lid == 0u {{
+                                keep[kept] = order[i];
+                                kept += 1u;
+                            }}
+
+                            for (var j = i + 1u + lid; j < params.n; j += {WORKGROUP_SIZE}u) {{
+                                if removed[j] == 0u && box_iou(a, boxes[order[j]]) > params.threshold {{
+                                    removed[j] = 1u;
+                                }}
+                            }}
+                        }}
+
+                        storageBarrier();
+                    }}
+
+                    if lid == 0u {{
+                        current = kept;
+                        count[0] = kept;
+                    }}
+
+                    for (var j = workgroupUniformLoad(&current) + lid; j < params.n; j += {WORKGROUP_SIZE}u) {{
+                        keep[j] = 0xffffffffu;
+                    }}
+                }}
+            "
+        )
+    }
+}
+
+/// Average of bilinear samples over each output bin of each region, with half-pixel alignment.
+///
+/// Regions are `(batch, x1, y1, x2, y2)` rows in input coordinates. Samples more than one
+/// pixel outside the feature map contribute zero, as in the reference implementation.
+struct RoiAlign;
+
+impl Kernel for RoiAlign {
+    const LABEL: &'static str = "roi_align";
+    type Output = f32;
+
+    fn wgsl() -> String {
+        format!(
+            r"
+                struct Params {{
+                    rois: u32,
+                    batch: u32,
+                    channels: u32,
+                    height: u32,
+                    width: u32,
+                    out_height: u32,
+                    out_width: u32,
+                    sampling_ratio: u32,
+                    spatial_scale: f32,
+                    _pad0: u32,
+                    _pad1: u32,
+                    _pad2: u32,
+                }}
+
+                @group(0) @binding(0) var<storage, read> features: array<f32>;
+                @group(0) @binding(1) var<storage, read> boxes: array<f32>;
+                @group(0) @binding(2) var<storage, read_write> y: array<f32>;
+                @group(0) @binding(3) var<uniform> params: Params;
+
+                fn bilinear(base: u32, sy: f32, sx: f32) -> f32 {{
+                    let h = f32(params.height);
+                    let w = f32(params.width);
+                    if sy < -1.0 || sy > h || sx < -1.0 || sx > w {{
+                        return 0.0;
+                    }}
+
+                    var py = max(sy, 0.0);
+                    var px = max(sx, 0.0);
+                    var y0 = u32(py);
+                    var x0 = u32(px);
+                    var y1 = y0 + 1u;
+                    var x1 = x0 + 1u;
+                    if y0 >= params.height - 1u {{
+                        y0 = params.height - 1u;
+                        y1 = y0;
+                        py = f32(y0);
+                    }}
+                    if x0 >= params.width - 1u {{
+                        x0 = params.width - 1u;
+                        x1 = x0;
+                        px = f32(x0);
+                    }}
+
+                    let ly = py - f32(y0);
+                    let lx = px - f32(x0);
+                    let top = mix(features[base + y0 * params.width + x0], features[base + y0 * params.width + x1], lx);
+                    let bottom = mix(features[base + y1 * params.width + x0], features[base + y1 * params.width + x1], lx);
+                    return mix(top, bottom, ly);
+                }}
+
+                @compute @workgroup_size({WORKGROUP_SIZE})
+                fn main(@builtin(global_invocation_id) gid: vec3<u32>) {{
+                    let tid = gid.x + gid.y * {MAX_WORKGROUPS}u * {WORKGROUP_SIZE}u;
+                    let bins = params.out_height * params.out_width;
+
+                    if tid >= params.rois * params.channels * bins {{
+                        return;
+                    }}
+
+                    let pw = tid % params.out_width;
+                    let ph = tid / params.out_width % params.out_height;
+                    let c = tid / bins % params.channels;
+                    let k = tid / (bins * params.channels);
+
+                    let b = u32(max(boxes[k * 5u], 0.0));
+                    if b >= params.batch {{
+                        y[tid] = 0.0;
+                        return;
+                    }}
+
+                    let start_x = boxes[k * 5u + 1u] * params.spatial_scale - 0.5;
+                    let start_y = boxes[k * 5u + 2u] * params.spatial_scale - 0.5;
+                    let roi_w = boxes[k * 5u + 3u] * params.spatial_scale - 0.5 - start_x;
+                    let roi_h = boxes[k * 5u + 4u] * params.spatial_scale - 0.5 - start_y;
+                    let bin_w = roi_w / f32(params.out_width);
+                    let bin_h = roi_h / f32(params.out_height);
+
+                    var grid_w = params.sampling_ratio;
+                    var grid_h = params.sampling_ratio;
+                    if params.sampling_ratio == 0u {{
+                        grid_w = max(u32(ceil(bin_w)), 1u);
+                        grid_h = max(u32(ceil(bin_h)), 1u);
+                    }}
+
+                    let base = (b * params.channels + c) * params.height * params.width;
+                    var sum = 0.0;
+                    for (var iy = 0u; iy < grid_h; iy++) {{
+                        let sy = start_y + f32(ph) * bin_h + (f32(iy) + 0.5) * bin_h / f32(grid_h);
+                        for (var ix = 0u; ix < grid_w; ix++) {{
+                            let sx = start_x + f32(pw) * bin_w + (f32(ix) + 0.5) * bin_w / f32(grid_w);
+                            sum += bilinear(base, sy, sx);
+                        }}
+                    }}
+
+                    y[tid] = sum / f32(grid_h * grid_w);
+                }}
+            "
+        )
+    }
+}
+
+/// Runs non-maximum suppression over `n` boxes visited in `order`.
+///
+/// `removed` is a zeroed scratch buffer of `n` elements. Kept box indices are written to the
+/// front of `keep` in visiting order, followed by `u32::MAX`, and their number to `count`.
+///
+/// # Panics
+///
+/// - Box count exceeds max size
+pub(crate) fn nms(
+    ctx: &Context,
+    boxes: &Buffer<f32>,
+    order: &Buffer<u32>,
+    removed: &Buffer<u32>,
+    keep: &Buffer<u32>,
+    count: &Buffer<u32>,
+    threshold: f32,
+) {
+    let n = u32::try_from(order.len()).expect("box count exceeds max size");
+
+    let pipeline = ctx.get_or_create_pipeline(TypeId::of::<Nms>(), Nms::wgsl, Nms::LABEL);
+    let params = ctx.create_uniform_buffer(&Params { n, threshold });
+
+    crate::kernel::dispatch(
+        ctx,
+        &pipeline,
+        Nms::LABEL,
+        &[
+            boxes.inner(),
+            order.inner(),
+            removed.inner(),
+            keep.inner(),
+            count.inner(),
+            &params,
+        ],
+        (1, 1, 1),
+    );
+}
+
+/// Computes the `[batch, n, m]` intersection over union matrix of `[batch, n, 4]` boxes `a`
+/// and `[batch, m, 4]` boxes `b`.
+///
+/// # Panics
+///
+/// - Output length exceeds max size
+pub(crate) fn box_iou(
+    ctx: &Context,
+    a: &Buffer<f32>,
+    b: &Buffer<f32>,
+    y: &Buffer<f32>,
+    batch: usize,
+    n: usize,
+    m: usize,
+) {
+    let to_u32 = |x: usize| u32::try_from(x).expect("dimension exceeds max size");
+    let len = u32::try_from(y.len()).expect("output length exceeds max size");
+
+    let pipeline = ctx.get_or_create_pipeline(TypeId::of::<BoxIou>(), BoxIou::wgsl, BoxIou::LABEL);
+    let params = ctx.create_uniform_buffer(&IouParams {
+        batch: to_u32(batch),
+        n: to_u32(n),
+        m: to_u32(m),
+        _pad: 0,
+    });
+
+    let (wx, wy) = crate::kernel::compute_workgroups(len);
+
+    crate::kernel::dispatch(
+        ctx,
+        &pipeline,
+        BoxIou::LABEL,
+        &[a.inner(), b.inner(), y.inner(), &params],
+        (wx, wy, 1),
+    );
+}
+
+/// Pools `[batch, channels, height, width]` features over `rois` regions into
+/// `[rois, channels, out_height, out_width]`.
+///
+/// # Panics
+///
+/// - Output length exceeds max size
+pub(crate) fn roi_align(
+    ctx: &Context,
+    features: &Buffer<f32>,
+    boxes: &Buffer<f32>,
+    y: &Buffer<f32>,
+    [batch, channels, height, width]: [usize; 4],
+    (out_height, out_width): (usize, usize),
+    spatial_scale: f32,
+    sampling_ratio: usize,
+) {
+    let to_u32 = |x: usize| u32::try_from(x).expect("dimension exceeds max size");
+    let len = u32::try_from(y.len()).expect("output length exceeds max size");
+
+    let pipeline =
+        ctx.get_or_create_pipeline(TypeId::of::<RoiAlign>(), RoiAlign::wgsl, RoiAlign::LABEL);
+
+    let params = ctx.create_uniform_buffer(&RoiAlignParams {
+        rois: to_u32(boxes.len() / 5),
+        batch: to_u32(batch),
+        channels: to_u32(channels),
+        height: to_u32(height),
+        width: to_u32(width),
+        out_height: to_u32(out_height),
+        out_width: to_u32(out_width),
+        sampling_ratio: to_u32(sampling_ratio),
+        spatial_scale,
+        _pad: [0; 3],
+    });
+
+    let (wx, wy) = crate::kernel::compute_workgroups(len);
+
+    crate::kernel::dispatch(
+        ctx,
+        &pipeline,
+        RoiAlign::LABEL,
+        &[features.inner(), boxes.inner(), y.inner(), &params],
+        (wx, wy, 1),
+    );
+}
@@ -0,0 +1,669 @@
+//! Emulated double-precision (df64) kernels.
+//!
+//! Values are stored as `vec2<f32>` where `x` is the high part and `y` the low part.
+//! Arithmetic follows the error-free transformations of Dekker and Knuth.
+
+use core::any::TypeId;
+use core::marker::PhantomData;
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use bytemuck::{Pod, Zeroable};
+
+use crate::element::Df64;
+use crate::kernel::{Kernel, MAX_WORKGROUPS, WORKGROUP_SIZE};
+use crate::{Buffer, Context, Element};
+
+/// WGSL helper functions for df64 arithmetic.
+///
+/// Every rounded intermediate goes through `df64_opaque`, which XORs its bits with a runtime
+/// zero read from the kernel uniform. This stops shader compilers from reassociating or
+/// contracting the error-free transformations and folding the low part away.
+const DF64_WGSL: &str = r"
+    var<private> df64_zero: u32;
+
+    fn df64_opaque(a: f32) -> f32 {
+        return bitcast<f32>(bitcast<u32>(a) ^ df64_zero);
+    }
+
+    fn df64_quick_two_sum(a: f32, b: f32) -> vec2<f32> {
+        let s = df64_opaque(a + b);
+        let e = b - df64_opaque(s - a);
+        return vec2<f32>(s, e);
+    }
+
+    fn df64_two_sum(a: f32, b: f32) -> vec2<f32> {
+        let s = df64_opaque(a + b);
+        let v = df64_opaque(s - a);
+        let e = (a - df64_opaque(s - v)) + (b - v);
+        return vec2<f32>(s, e);
+    }
+
+    fn df64_split(a: f32) -> vec2<f32> {
+        let t = df64_opaque(4097.0 * a);
+        let hi = t - df64_opaque(t - a);
+        return vec2<f32>(hi, a - hi);
+    }
+
+    fn df64_two_prod(a: f32, b: f32) -> vec2<f32> {
+        let p = df64_opaque(a * b);
+        let a_s = df64_split(a);
+        let b_s = df64_split(b);
+        let e = ((df64_opaque(a_s.x * b_s.x) - p) + a_s.x * b_s.y + a_s.y * b_s.x) + a_s.y * b_s.y;
+        return vec2<f32>(p, e);
+    }
+
+    fn df64_add(a: vec2<f32>, b: vec2<f32>) -> vec2<f32> {
+        var s = df64_two_sum(a.x, b.x);
+        let t = df64_two_sum(a.y, b.y);
+        s.y += t.x;
+        s = df64_quick_two_sum(s.x, s.y);
+        s.y += t.y;
+        return df64_quick_two_sum(s.x, s.y);
+    }
+
+    fn df64_sub(a: vec2<f32>, b: vec2<f32>) -> vec2<f32> {
+        return df64_add(a, -b);
+    }
+
+    fn df64_mul(a: vec2<f32>, b: vec2<f32>) -> vec2<f32> {
+        var p = df64_two_prod(a.x, b.x);
+        p.y += a.x * b.y + a.y * b.x;
+        return df64_quick_two_sum(p.x, p.y);
+    }
+
+    fn df64_div(a: vec2<f32>, b: vec2<f32>) -> vec2<f32> {
+        let q1 = df64_opaque(a.x / b.x);
+        let r = df64_sub(a, df64_mul(vec2<f32>(q1, 0.0), b));
+        let q2 = r.x / b.x;
+        return df64_quick_two_sum(q1, q2);
+    }
+";
+
+/// Element-wise kernel parameters passed to shader as uniform.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Pod, Zeroable)]
+struct Params {
+    rank: u32,
+    len: u32,
+    zero: u32,
+    _pad: u32,
+}
+
+/// Matmul parameters passed to shader as uniform.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Pod, Zeroable)]
+struct MatmulParams {
+    m: u32,
+    k: u32,
+    n: u32,
+    len: u32,
+    batch_rank: u32,
+    transpose_a: u32,
+    transpose_b: u32,
+    zero: u32,
+}
+
+/// Sum reduction parameters passed to shader as uniform.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Pod, Zeroable)]
+struct SumParams {
+    rank: u32,
+    len: u32,
+    reduction_len: u32,
+    normalize: u32,
+    divisor: [f32; 2],
+    zero: u32,
+    _pad: u32,
+}
+
+/// Defines a broadcasting binary df64 kernel module.
+macro_rules! define_binary_kernel {
+    ($kernel:ident, $mod_name:ident, $label:literal, $op:literal) => {
+        pub(crate) mod $mod_name {
+            use super::*;
+
+            /// Kernel marker type.
+            pub(crate) struct $kernel;
+
+            /// Kernel trait implementation.
+            impl Kernel for $kernel {
+                const LABEL: &'static str = $label;
+                type Output = Df64;
+
+                fn wgsl() -> String {
+                    super::binary_wgsl($op)
+                }
+            }
+
+            /// Executes the kernel.
+            pub(crate) fn execute(
+                ctx: &Context,
+                a: &Buffer<Df64>,
+                b: &Buffer<Df64>,
+                c: &Buffer<Df64>,
+                a_strides: &[usize],
+                b_strides: &[usize],
+                c_strides: &[usize],
+            ) {
+                super::execute_binary::<$kernel>(ctx, a, b, c, a_strides, b_strides, c_strides);
+            }
+        }
+    };
+}
+
+/// Generates a broadcasting binary kernel applying the df64 function `op`.
+fn binary_wgsl(op: &str) -> String {
+    format!(
+        r"
+            {DF64_WGSL}
+
+            struct Params {{
+                rank: u32,
+                len: u32,
+                zero: u32,
+                _pad: u32,
+            }}
+
+            @group(0) @binding(0) var<storage, read> a: array<vec2<f32>>;
+            @group(0) @binding(1) var<storage, read> b: array<vec2<f32>>;
+            @group(0) @binding(2) var<storage, read_write> c: array<vec2<f32>>;
+            @group(0) @binding(3) var<storage, read> a_strides: array<u32>;
+            @group(0) @binding(4) var<storage, read> b_strides: array<u32>;
+            @group(0) @binding(5) var<storage, read> c_strides: array<u32>;
+            @group(0) @binding(6) var<uniform> params: Params;
+
+            @compute @workgroup_size({WORKGROUP_SIZE})
+            fn main(@builtin(global_invocation_id) gid: vec3<u32>) {{
+                let tid = gid.x + gid.y * {MAX_WORKGROUPS}u * {WORKGROUP_SIZE}u;
+
+                if tid >= params.len {{
+                    return;
+                }}
+
+                df64_zero = params.zero;
+
+                var remaining = tid;
+                var a_idx = 0u;
+                var b_idx = 0u;
+
+                for (var i = 0u; i < params.rank; i++) {{
+                    let coord = remaining / c_strides[i];
+                    remaining = remaining % c_strides[i];
+                    a_idx += coord * a_strides[i];
+                    b_idx += coord * b_strides[i];
+                }}
+
+                c[tid] = {op}(a[a_idx], b[b_idx]);
+            }}
+        "
+    )
+}
+
+/// Executes a broadcasting binary df64 kernel.
+///
+/// # Panics
+///
+/// - Output length exceeds max size
+/// - Output rank exceeds max size
+fn execute_binary<K: Kernel>(
+    ctx: &Context,
+    a: &Buffer<Df64>,
+    b: &Buffer<Df64>,
+    c: &Buffer<Df64>,
+    a_strides: &[usize],
+    b_strides: &[usize],
+    c_strides: &[usize],
+) {
+    let rank = u32::try_from(c_strides.len()).expect("output rank exceeds max size");
+    let len = u32::try_from(c.len()).expect("output length exceeds max size");
+
+    if len == 0 {
+        return;
+    }
+
+    let pipeline = ctx.get_or_create_pipeline(TypeId::of::<K>(), K::wgsl, K::LABEL);
+
+    let a_strides = ctx.create_storage_buffer(&crate::kernel::convert_strides(a_strides));
+    let b_strides = ctx.create_storage_buffer(&crate::kernel::convert_strides(b_strides));
+    let c_strides = ctx.create_storage_buffer(&crate::kernel::convert_strides(c_strides));
+    let params = ctx.create_uniform_buffer(&Params {
+        rank,
+        len,
+        zero: 0,
+        _pad: 0,
+    });
+
+    let (x, y) = crate::kernel::compute_workgroups(len);
+
+    crate::kernel::dispatch(
+        ctx,
+        &pipeline,
+        K::LABEL,
+        &[
+            a.inner(),
+            b.inner(),
+            c.inner(),
+            &a_strides,
+            &b_strides,
+            &c_strides,
+            &params,
+        ],
+        (x, y, 1),
+    );
+}
+
+define_binary_kernel!(Df64Add, add, "df64_add", "df64_add");
+define_binary_kernel!(Df64Sub, sub, "df64_sub", "df64_sub");
+define_binary_kernel!(Df64Mul, mul, "df64_mul", "df64_mul");
+
+/// Batched df64 matrix multiplication kernel: `C = A × B`.
+struct Df64Matmul;
+
+impl Kernel for Df64Matmul {
+    const LABEL: &'static str = "df64_matmul";
+    type Output = Df64;
+
+    fn wgsl() -> String {
+        format!(
+            r"
+                {DF64_WGSL}
+
+                struct Params {{
+                    m: u32,
+                    k: u32,
+                    n: u32,
+                    len: u32,
+                    batch_rank: u32,
+                    transpose_a: u32,
+                    transpose_b: u32,
+                    zero: u32,
+                }}
+
+                @group(0) @binding(0) var<storage, read> a: array<vec2<f32>>;
+                @group(0) @binding(1) var<storage, read> b: array<vec2<f32>>;
+                @group(0) @binding(2) var<storage, read_write> c: array<vec2<f32>>;
+                @group(0) @binding(3) var<storage, read> batch_dims: array<u32>;
+                @group(0) @binding(4) var<storage, read> a_batch_strides: array<u32>;
+                @group(0) @binding(5) var<storage, read> b_batch_strides: array<u32>;
+                @group(0) @binding(6) var<uniform> params: Params;
+
+                @compute @workgroup_size({WORKGROUP_SIZE})
+                fn main(@builtin(global_invocation_id) gid: vec3<u32>) {{
+                    let tid = gid.x + gid.y * {MAX_WORKGROUPS}u * {WORKGROUP_SIZE}u;
+
+                    if tid >= params.len {{
+                        return;
+                    }}
+
+                    df64_zero = params.zero;
+
+                    let M = params.m;
+                    let K = params.k;
+                    let N = params.n;
+
+                    let col = tid % N;
+                    let row = (tid / N) % M;
+                    var batch = tid / (M * N);
+
+                    var a_batch = 0u;
+                    var b_batch = 0u;
+                    for (var i = params.batch_rank; i > 0u; i--) {{
+                        let coord = batch % batch_dims[i - 1u];
+                        batch = batch / batch_dims[i - 1u];
+                        a_batch += coord * a_batch_strides[i - 1u];
+                        b_batch += coord * b_batch_strides[i - 1u];
+                    }}
+
+                    let a_base = a_batch * M * K;
+                    let b_base = b_batch * K * N;
+
+                    var acc = vec2<f32>(0.0, 0.0);
+                    for (var l = 0u; l < K; l++) {{
+                        let a_idx = select(row * K + l, l * M + row, params.transpose_a != 0u);
+                        let b_idx = select(l * N + col, col * K + l, params.transpose_b != 0u);
+                        acc = df64_add(acc, df64_mul(a[a_base + a_idx], b[b_base + b_idx]));
+                    }}
+
+                    c[tid] = acc;
+                }}
+            "
+        )
+    }
+}
+
+/// Executes batched df64 matrix multiplication: `C = A × B`.
+///
+/// # Panics
+///
+/// - Matrix dimensions exceed max size
+/// - Output length exceeds max size
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn matmul(
+    ctx: &Context,
+    a: &Buffer<Df64>,
+    b: &Buffer<Df64>,
+    c: &Buffer<Df64>,
+    a_dims: &[usize],
+    b_dims: &[usize],
+    c_dims: &[usize],
+    transpose_a: bool,
+    transpose_b: bool,
+) {
+    let rank = c_dims.len();
+    let batch_rank = rank - 2;
+    let (m, n) = (c_dims[rank - 2], c_dims[rank - 1]);
+    let k = if transpose_a {
+        a_dims[rank - 2]
+    } else {
+        a_dims[rank - 1]
+    };
+
+    let to_u32 = |x: usize| u32::try_from(x).expect("dimension exceeds max size");
+    let len = u32::try_from(c.len()).expect("output length exceeds max size");
+
+    if len == 0 {
+        return;
+    }
+
+    let (a_batch_strides, b_batch_strides) = crate::kernel::linalg::matmul::compute_batch_strides(
+        &a_dims[..batch_rank],
+        &b_dims[..batch_rank],
+        &c_dims[..batch_rank],
+    );
+
+    let params = MatmulParams {
+        m: to_u32(m),
+        k: to_u32(k),
+        n: to_u32(n),
+        len,
+        batch_rank: to_u32(batch_rank),
+        transpose_a: u32::from(transpose_a),
+        transpose_b: u32::from(transpose_b),
+        zero: 0,
+    };
+
+    let pipeline = ctx.get_or_create_pipeline(
+        TypeId::of::<Df64Matmul>(),
+        Df64Matmul::wgsl,
+        Df64Matmul::LABEL,
+    );
+
+    let batch_dims =
+        ctx.create_storage_buffer(&crate::kernel::convert_strides(&c_dims[..batch_rank]));
+    let a_batch_strides =
+        ctx.create_storage_buffer(&crate::kernel::convert_strides(&a_batch_strides));
+    let b_batch_strides =
+        ctx.create_storage_buffer(&crate::kernel::convert_strides(&b_batch_strides));
+    let params = ctx.create_uniform_buffer(&params);
+
+    let (x, y) = crate::kernel::compute_workgroups(len);
+
+    crate::kernel::dispatch(
+        ctx,
+        &pipeline,
+        Df64Matmul::LABEL,
+        &[
+            a.inner(),
+            b.inner(),
+            c.inner(),
+            &batch_dims,
+            &a_batch_strides,
+            &b_batch_strides,
+            &params,
+        ],
+        (x, y, 1),
+    );
+}
+
+/// df64 sum reduction kernel: `y[o]` is the sum of the elements reduced into output `o`.
+///
+/// Each workgroup owns one output. Threads accumulate their strided share of the reduced
+/// elements in df64 and the workgroup adds the partial sums in a tree, so the result keeps
+/// the low part that an `f32` sum rounds away.
+struct Df64Sum;
+
+impl Kernel for Df64Sum {
+    const LABEL: &'static str = "df64_sum";
+    type Output = Df64;
+
+    fn wgsl() -> String {
+        format!(
+            r"
+                {DF64_WGSL}
+
+                const WG_SIZE: u32 = {WORKGROUP_SIZE}u;
+
+                struct Params {{
+                    rank: u32,
+                    len: u32,
+                    reduction_len: u32,
+                    normalize: u32,
+                    divisor: vec2<f32>,
+                    zero: u32,
+                    _pad: u32,
+                }}
+
+                @group(0) @binding(0) var<storage, read> x: array<vec2<f32>>;
+                @group(0) @binding(1) var<storage, read_write> y: array<vec2<f32>>;
+                @group(0) @binding(2) var<storage, read> x_strides: array<u32>;
+                @group(0) @binding(3) var<storage, read> y_strides: array<u32>;
+                @group(0) @binding(4) var<storage, read> reduce_strides: array<u32>;
+                @group(0) @binding(5) var<uniform> params: Params;
+
+                var<workgroup> sdata: array<vec2<f32>, WG_SIZE>;
+
+                @compute @workgroup_size(WG_SIZE)
+                fn main(
+                    @builtin(local_invocation_id) lid: vec3<u32>,
+                    @builtin(workgroup_id) wid: vec3<u32>
+                ) {{
+                    let tid = lid.x;
+                    let y_idx = wid.x + wid.y * {MAX_WORKGROUPS}u;
+
+                    if y_idx >= params.len {{
+                        return;
+                    }}
+
+                    df64_zero = params.zero;
+
+                    var base = 0u;
+                    var remaining = y_idx;
+                    for (var i = 0u; i < params.rank; i++) {{
+                        let stride = y_strides[i];
+                        if stride > 0u && reduce_strides[i] == 0u {{
+                            base += remaining / stride * x_strides[i];
+                        }}
+                        if stride > 0u {{
+                            remaining = remaining % stride;
+                        }}
+                    }}
+
+                    var acc = vec2<f32>(0.0, 0.0);
+                    for (var r = tid; r < params.reduction_len; r += WG_SIZE) {{
+                        var input_idx = base;
+                        var red_remaining = r;
+                        for (var i = 0u; i < params.rank; i++) {{
+                            let stride = reduce_strides[i];
+                            if stride > 0u {{
+                                input_idx += red_remaining / stride * x_strides[i];
+                                red_remaining = red_remaining % stride;
+                            }}
+                        }}
+                        acc = df64_add(acc, x[input_idx]);
+                    }}
+
+                    sdata[tid] = acc;
+
+                    for (var s = WG_SIZE / 2u; s > 0u; s >>= 1u) {{
+                        workgroupBarrier();
+                        if tid < s {{
+                            sdata[tid] = df64_add(sdata[tid], sdata[tid + s]);
+                        }}
+                    }}
+
+                    if tid == 0u {{
+                        var total = sdata[0];
+                        if params.normalize != 0u {{
+                            total = df64_div(total, params.divisor);
+                        }}
+                        y[y_idx] = total;
+                    }}
+                }}
+            "
+        )
+    }
+}
+
+/// Sums `x` along `axes` into `y` in df64, divided by the reduction length minus the
+/// correction if `normalize` is given.
+///
+/// # Panics
+///
+/// - Output rank exceeds max size
+/// - Output length exceeds max size
+/// - Reduction length exceeds max size
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn sum(
+    ctx: &Context,
+    x: &Buffer<Df64>,
+    y: &Buffer<Df64>,
+    x_dimensions: &[usize],
+    x_strides: &[usize],
+    y_strides: &[usize],
+    axes: &[usize],
+    normalize: Option<u32>,
+) {
+    let rank = u32::try_from(y_strides.len()).expect("output rank exceeds max size");
+    let len = u32::try_from(y.len()).expect("output length exceeds max size");
+    let reduction_len = u32::try_from(axes.iter().map(|&a| x_dimensions[a]).product::<usize>())
+        .expect("reduction length exceeds max size");
+
+    if len == 0 {
+        return;
+    }
+
+    let pipeline =
+        ctx.get_or_create_pipeline(TypeId::of::<Df64Sum>(), Df64Sum::wgsl, Df64Sum::LABEL);
+
+    // Row-major strides of the reduced axes within the reduction, zero for kept axes.
+    let mut reduce_strides: Vec<usize> = alloc::vec![0; x_dimensions.len()];
+    let mut stride = 1;
+    for axis in (0..x_dimensions.len()).rev() {
+        if axes.contains(&axis) {
+            reduce_strides[axis] = stride;
+            stride *= x_dimensions[axis];
+        }
+    }
+
+    let divisor = normalize.map_or(Df64::default(), |correction| {
+        Df64::from(f64::from(reduction_len) - f64::from(correction))
+    });
+
+    let x_strides = ctx.create_storage_buffer(&crate::kernel::convert_strides(x_strides));
+    let y_strides = ctx.create_storage_buffer(&crate::kernel::convert_strides(y_strides));
+    let reduce_strides =
+        ctx.create_storage_buffer(&crate::kernel::convert_strides(&reduce_strides));
+    let params = ctx.create_uniform_buffer(&SumParams {
+        rank,
+        len,
+        reduction_len,
+        normalize: u32::from(normalize.is_some()),
+        divisor: divisor.to_native(),
+        zero: 0,
+        _pad: 0,
+    });
+
+    crate::kernel::dispatch(
+        ctx,
+        &pipeline,
+        Df64Sum::LABEL,
+        &[
+            x.inner(),
+            y.inner(),
+            &x_strides,
+            &y_strides,
+            &reduce_strides,
+            &params,
+        ],
+        (len.min(MAX_WORKGROUPS), len.div_ceil(MAX_WORKGROUPS), 1),
+    );
+}
+
+/// Conversion kernel between `f32` and df64 representations.
+struct Convert<T, U>(PhantomData<(T, U)>);
+
+impl<T: Element, U: Element> Kernel for Convert<T, U> {
+    const LABEL: &'static str = "df64_convert";
+    type Output = U;
+
+    fn wgsl() -> String {
+        let ty = T::wgsl_type();
+        let out_ty = U::wgsl_type();
+        let op = if TypeId::of::<U>() == TypeId::of::<Df64>() {
+            "vec2<f32>(x[tid], 0.0)"
+        } else {
+            "x[tid].x + x[tid].y"
+        };
+
+        format!(
+            r"
+                @group(0) @binding(0) var<storage, read> x: array<{ty}>;
+                @group(0) @binding(1) var<storage, read_write> y: array<{out_ty}>;
+
+                @compute @workgroup_size({WORKGROUP_SIZE})
+                fn main(@builtin(global_invocation_id) gid: vec3<u32>) {{
+                    let tid = gid.x + gid.y * {MAX_WORKGROUPS}u * {WORKGROUP_SIZE}u;
+                    if tid < arrayLength(&y) {{
+                        y[tid] = {op};
+                    }}
+                }}
+            "
+        )
+    }
+}
+
+/// Converts each `f32` element to df64 (`y = x`).
+pub(crate) fn from_f32(ctx: &Context, x: &Buffer<f32>, y: &Buffer<Df64>) {
+    convert::<f32, Df64>(ctx, x, y);
+}
+
+/// Rounds each df64 element to the nearest `f32` (`y = hi + lo`).
+pub(crate) fn to_f32(ctx: &Context, x: &Buffer<Df64>, y: &Buffer<f32>) {
+    convert::<Df64, f32>(ctx, x, y);
+}
+
+/// Executes a conversion kernel.
+///
+/// # Panics
+///
+/// - Buffer lengths do not match
+/// - Output length exceeds max size
+fn convert<T: Element, U: Element>(ctx: &Context, x: &Buffer<T>, y: &Buffer<U>) {
+    assert_eq!(x.len(), y.len(), "buffer length mismatch");
+
+    let len = u32::try_from(y.byte_size() / U::NATIVE_SIZE as u64)
+        .expect("output length exceeds max size");
+
+    if len == 0 {
+        return;
+    }
+
+    let pipeline = ctx.get_or_create_pipeline(
+        TypeId::of::<Convert<T, U>>(),
+        Convert::<T, U>::wgsl,
+        Convert::<T, U>::LABEL,
+    );
+
+    let (wx, wy) = crate::kernel::compute_workgroups(len);
+
+    crate::kernel::dispatch(
+        ctx,
+        &pipeline,
+        Convert::<T, U>::LABEL,
+        &[x.inner(), y.inner()],
+        (wx, wy, 1),
+    );
+}
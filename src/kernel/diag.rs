@@ -0,0 +1,100 @@
+//! Diagonal matrix kernel.
+
+use core::any::TypeId;
+use core::marker::PhantomData;
+
+use alloc::format;
+use alloc::string::String;
+
+use bytemuck::{Pod, Zeroable};
+
+use crate::kernel::{Kernel, MAX_WORKGROUPS, WORKGROUP_SIZE};
+use crate::{Buffer, Context, Element};
+
+/// Kernel parameters passed to shader as uniform.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Pod, Zeroable)]
+struct Params {
+    n: u32,
+    len: u32,
+}
+
+/// Diagonal matrix kernel: `y[i, j] = x[i]` if `i = j`, zero otherwise.
+struct Diagflat<T>(PhantomData<T>);
+
+impl<T: Element> Kernel for Diagflat<T> {
+    const LABEL: &'static str = "diagflat";
+    type Output = T;
+
+    fn wgsl() -> String {
+        let ty = T::wgsl_type();
+        let zero = T::wgsl_zero();
+
+        format!(
+            r"
+                struct Params {{
+                    n: u32,
+                    len: u32,
+                }}
+
+                @group(0) @binding(0) var<storage, read> x: array<{ty}>;
+                @group(0) @binding(1) var<storage, read_write> y: array<{ty}>;
+                @group(0) @binding(2) var<uniform> params: Params;
+
+                @compute @workgroup_size({WORKGROUP_SIZE})
+                fn main(@builtin(global_invocation_id) gid: vec3<u32>) {{
+                    let tid = gid.x + gid.y * {MAX_WORKGROUPS}u * {WORKGROUP_SIZE}u;
+
+                    if tid >= params.len {{
+                        return;
+                    }}
+
+                    let row = tid / params.n;
+                    let col = tid % params.n;
+
+                    if row == col {{
+                        y[tid] = x[row];
+                    }} else {{
+                        y[tid] = {zero};
+                    }}
+                }}
+            "
+        )
+    }
+}
+
+/// Writes the `[n, n]` diagonal matrix of the `[n]` contiguous `x` into `y`.
+///
+/// # Panics
+///
+/// - Output length exceeds max size
+pub(crate) fn diagflat<T: Element>(ctx: &Context, x: &Buffer<T>, y: &Buffer<T>, n: usize) {
+    let len = n
+        .checked_mul(n)
+        .and_then(|len| u32::try_from(len).ok())
+        .expect("output length exceeds max size");
+
+    if len == 0 {
+        return;
+    }
+
+    let pipeline = ctx.get_or_create_pipeline(
+        TypeId::of::<Diagflat<T>>(),
+        Diagflat::<T>::wgsl,
+        Diagflat::<T>::LABEL,
+    );
+    let params = ctx.create_uniform_buffer(&Params {
+        n: u32::try_from(n).expect("output length exceeds max size"),
+        len,
+    });
+
+    let (wx, wy) = crate::kernel::compute_workgroups(len);
+
+    crate::kernel::dispatch(
+        ctx,
+        &pipeline,
+        Diagflat::<T>::LABEL,
+        &[x.inner(), y.inner(), &params],
+        (wx, wy, 1),
+    );
+}
@@ -0,0 +1,104 @@
+//! Diffusion sampling kernel.
+
+use core::any::TypeId;
+use core::marker::PhantomData;
+
+use alloc::format;
+use alloc::string::String;
+
+use bytemuck::{Pod, Zeroable};
+
+use crate::element::FloatElement;
+use crate::kernel::random::RANDOM_WGSL;
+use crate::kernel::{Kernel, MAX_WORKGROUPS, WORKGROUP_SIZE};
+use crate::{Buffer, Context};
+
+/// Kernel parameters passed to shader as uniform.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Pod, Zeroable)]
+struct Params {
+    len: u32,
+    seed: u32,
+    a: f32,
+    b: f32,
+    c: f32,
+}
+
+/// Fused sampler update: `y = a·x + b·eps + c·z` with `z ~ N(0, 1)` drawn in-kernel.
+struct Step<T>(PhantomData<T>);
+
+impl<T: FloatElement> Kernel for Step<T> {
+    const LABEL: &'static str = "diffusion_step";
+    type Output = T;
+
+    fn wgsl() -> String {
+        let ty = T::wgsl_type();
+
+        format!(
+            r"
+                {RANDOM_WGSL}
+
+                struct Params {{
+                    len: u32,
+                    seed: u32,
+                    a: f32,
+                    b: f32,
+                    c: f32,
+                }}
+
+                @group(0) @binding(0) var<storage, read> x: array<{ty}>;
+                @group(0) @binding(1) var<storage, read> eps: array<{ty}>;
+                @group(0) @binding(2) var<storage, read_write> y: array<{ty}>;
+                @group(0) @binding(3) var<uniform> params: Params;
+
+                @compute @workgroup_size({WORKGROUP_SIZE})
+                fn main(@builtin(global_invocation_id) gid: vec3<u32>) {{
+                    let tid = gid.x + gid.y * {MAX_WORKGROUPS}u * {WORKGROUP_SIZE}u;
+
+                    if tid >= params.len {{
+                        return;
+                    }}
+
+                    var value = params.a * x[tid] + params.b * eps[tid];
+                    if params.c != 0.0 {{
+                        value += params.c * random_normal(params.seed, tid);
+                    }}
+                    y[tid] = value;
+                }}
+            "
+        )
+    }
+}
+
+/// Executes the fused sampler update over contiguous `x` and `eps` of equal length.
+///
+/// # Panics
+///
+/// - Output length exceeds max size
+pub(crate) fn step<T: FloatElement>(
+    ctx: &Context,
+    x: &Buffer<T>,
+    eps: &Buffer<T>,
+    y: &Buffer<T>,
+    a: f32,
+    b: f32,
+    c: f32,
+    seed: u32,
+) {
+    let len = u32::try_from(y.len()).expect("output length exceeds max size");
+
+    let pipeline =
+        ctx.get_or_create_pipeline(TypeId::of::<Step<T>>(), Step::<T>::wgsl, Step::<T>::LABEL);
+
+    let params = ctx.create_uniform_buffer(&Params { len, seed, a, b, c });
+
+    let (wx, wy) = crate::kernel::compute_workgroups(len);
+
+    crate::kernel::dispatch(
+        ctx,
+        &pipeline,
+        Step::<T>::LABEL,
+        &[x.inner(), eps.inner(), y.inner(), &params],
+        (wx, wy, 1),
+    );
+}
@@ -0,0 +1,549 @@
+//! Probability distribution kernels.
+
+use core::any::TypeId;
+use core::marker::PhantomData;
+
+use alloc::format;
+use alloc::string::String;
+
+use bytemuck::{Pod, Zeroable};
+
+use crate::element::FloatElement;
+use crate::kernel::random::RANDOM_WGSL;
+use crate::kernel::{Kernel, MAX_WORKGROUPS, WORKGROUP_SIZE};
+use crate::{Buffer, Context};
+
+/// Categorical kernel parameters passed to shader as uniform.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Pod, Zeroable)]
+struct CategoricalParams {
+    rows: u32,
+    classes: u32,
+    mode: u32,
+    seed: u32,
+}
+
+/// Multinomial kernel parameters passed to shader as uniform.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Pod, Zeroable)]
+struct MultinomialParams {
+    rows: u32,
+    classes: u32,
+    samples: u32,
+    seed: u32,
+}
+
+/// Normal kernel parameters passed to shader as uniform.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Pod, Zeroable)]
+struct NormalParams {
+    len: u32,
+    mode: u32,
+    seed: u32,
+    _pad: u32,
+}
+
+/// Operation of the normal distribution kernel.
+pub(crate) enum NormalOp<'a, T: FloatElement> {
+    /// Draws `y = mean + std·z` with `z ~ N(0, 1)` from a seed.
+    Sample(u32),
+    /// Evaluates the log-density of the given values.
+    LogProb(&'a Buffer<T>),
+    /// Evaluates the entropy.
+    Entropy,
+}
+
+/// Categorical sampling kernel over `[rows, classes]` logits.
+///
+/// Each thread draws one row by the Gumbel-max trick, `argmax(x + g)` with Gumbel noise
+/// `g = -log(-log(u))`, which samples from `softmax(x)` without normalizing.
+struct CategoricalSample<T>(PhantomData<T>);
+
+impl<T: FloatElement> Kernel for CategoricalSample<T> {
+    const LABEL: &'static str = "categorical_sample";
+    type Output = u32;
+
+    fn wgsl() -> String {
+        let ty = T::wgsl_type();
+        let min = T::wgsl_min();
+
+        format!(
+            r"
+                {RANDOM_WGSL}
+
+                struct Params {{
+                    rows: u32,
+                    classes: u32,
+                    mode: u32,
+                    seed: u32,
+                }}
+
+                @group(0) @binding(0) var<storage, read> logits: array<{ty}>;
+                @group(0) @binding(1) var<storage, read_write> y: array<u32>;
+                @group(0) @binding(2) var<uniform> params: Params;
+
+                @compute @workgroup_size({WORKGROUP_SIZE})
+                fn main(@builtin(global_invocation_id) gid: vec3<u32>) {{
+                    let row = gid.x + gid.y * {MAX_WORKGROUPS}u * {WORKGROUP_SIZE}u;
+
+                    if row >= params.rows {{
+                        return;
+                    }}
+
+                    let base = row * params.classes;
+                    var best = 0u;
+                    var best_score = {ty}({min});
+
+                    for (var c = 0u; c < params.classes; c++) {{
+                        let bits = random_u32(params.seed, base + c) >> 8u;
+                        let u = (f32(bits) + 0.5) * (1.0 / 16777216.0);
+                        let score = logits[base + c] - {ty}(log(-log(u)));
+                        if score > best_score {{
+                            best = c;
+                            best_score = score;
+                        }}
+                    }}
+
+                    y[row] = best;
+                }}
+            "
+        )
+    }
+}
+
+/// Categorical log-probability and entropy kernel over `[rows, classes]` logits.
+///
+/// Each thread normalizes one row with a stable log-sum-exp. In log-probability mode
+/// (`mode = 0`) it writes `x[a] - logsumexp(x)` for the row's action `a`, or the lowest finite
+/// value if `a` is out of range; in entropy mode (`mode = 1`) it writes `-Σ p·log p`.
+struct CategoricalScore<T>(PhantomData<T>);
+
+impl<T: FloatElement> Kernel for CategoricalScore<T> {
+    const LABEL: &'static str = "categorical_score";
+    type Output = T;
+
+    fn wgsl() -> String {
+        let ty = T::wgsl_type();
+        let min = T::wgsl_min();
+
+        format!(
+            r"
+                struct Params {{
+                    rows: u32,
+                    classes: u32,
+                    mode: u32,
+                    seed: u32,
+                }}
+
+                @group(0) @binding(0) var<storage, read> logits: array<{ty}>;
+                @group(0) @binding(1) var<storage, read> actions: array<u32>;
+                @group(0) @binding(2) var<storage, read_write> y: array<{ty}>;
+                @group(0) @binding(3) var<uniform> params: Params;
+
+                @compute @workgroup_size({WORKGROUP_SIZE})
+                fn main(@builtin(global_invocation_id) gid: vec3<u32>) {{
+                    let row = gid.x + gid.y * {MAX_WORKGROUPS}u * {WORKGROUP_SIZE}u;
+
+                    if row >= params.rows {{
+                        return;
+                    }}
+
+                    let base = row * params.classes;
+                    var m = {ty}({min});
+                    for (var c = 0u; c < params.classes; c++) {{
+                        m = max(m, logits[base + c]);
+                    }}
+
+                    var sum: {ty} = 0.0;
+                    var weighted: {ty} = 0.0;
+                    for (var c = 0u; c < params.classes; c++) {{
+                        let x = logits[base + c];
+                        let e = exp(x - m);
+                        sum += e;
+                        if e > 0.0 {{
+                            weighted += e * (x - m);
+                        }}
+                    }}
+
+                    let lse = log(sum);
+
+                    if params.mode == 0u {{
+                        let action = actions[row];
+                        if action < params.classes {{
+                            y[row] = logits[base + action] - m - lse;
+                        }} else {{
+                            y[row] = {ty}({min});
+                        }}
+                    }} else {{
+                        y[row] = lse - weighted / sum;
+                    }}
+                }}
+            "
+        )
+    }
+}
+
+/// Multinomial sampling kernel over `[rows, classes]` non-negative weights, with replacement.
+///
+/// Each workgroup owns one row, split into one contiguous segment of classes per thread. The
+/// threads sum their segments and scan the sums in shared memory, then each draw scales a
+/// uniform number by the row total, binary searches the owning segment and walks it to the
+/// class, reading the row only about twice however many samples are drawn. Draws land on
+/// classes with positive weight only; a row without positive total writes `u32::MAX`.
+struct Multinomial<T>(PhantomData<T>);
+
+impl<T: FloatElement> Kernel for Multinomial<T> {
+    const LABEL: &'static str = "multinomial";
+    type Output = u32;
+
+    fn wgsl() -> String {
+        let ty = T::wgsl_type();
+
+        format!(
+            r"
+                {RANDOM_WGSL}
+
+                const WG_SIZE: u32 = {WORKGROUP_SIZE}u;
+
+                struct Params {{
+                    rows: u32,
+                    classes: u32,
+                    samples: u32,
+                    seed: u32,
+                }}
+
+                @group(0) @binding(0) var<storage, read> weights: array<{ty}>;
+                @group(0) @binding(1) var<storage, read_write> y: array<u32>;
+                @group(0) @binding(2) var<uniform> params: Params;
+
+                // Inclusive scan of the segment sums.
+                var<workgroup> sums: array<{ty}, WG_SIZE>;
+
+                @compute @workgroup_size(WG_SIZE)
+                fn main(
+                    @builtin(local_invocation_id) lid: vec3<u32>,
+                    @builtin(workgroup_id) wid: vec3<u32>
+                ) {{
+                    let tid = lid.x;
+                    let row = wid.x + wid.y * {MAX_WORKGROUPS}u;
+
+                    if row >= params.rows {{
+                        return;
+                    }}
+
+                    let base = row * params.classes;
+                    let segment = (params.classes + WG_SIZE - 1u) / WG_SIZE;
+                    let start = min(tid * segment, params.classes);
+                    let end = min(start + segment, params.classes);
+                    var total = {ty}(0);
+                    for (var c = start; c < end; c++) {{
+                        total += weights[base + c];
+                    }}
+
+                    sums[tid] = total;
+
+                    for (var s = 1u; s < WG_SIZE; s <<= 1u) {{
+                        workgroupBarrier();
+                        var sum = sums[tid];
+                        if tid >= s {{
+                            sum += sums[tid - s];
+                        }}
+                        workgroupBarrier();
+                        sums[tid] = sum;
+                    }}
+                    workgroupBarrier();
+
+                    let row_total = sums[WG_SIZE - 1u];
+                    for (var i = tid; i < params.samples; i += WG_SIZE) {{
+                        let out = row * params.samples + i;
+                        if !(row_total > {ty}(0)) {{
+                            y[out] = 0xffffffffu;
+                            continue;
+                        }}
+
+                        let bits = random_u32(params.seed, out) >> 8u;
+                        let point = {ty}((f32(bits) + 0.5) * (1.0 / 16777216.0)) * row_total;
+
+                        var lo = 0u;
+                        var hi = WG_SIZE - 1u;
+                        while lo < hi {{
+                            let mid = (lo + hi) / 2u;
+                            if sums[mid] > point || sums[mid] >= row_total {{
+                                hi = mid;
+                            }} else {{
+                                lo = mid + 1u;
+                            }}
+                        }}
+
+                        var remaining = point;
+                        if lo > 0u {{
+                            remaining -= sums[lo - 1u];
+                        }}
+                        let first = min(lo * segment, params.classes);
+                        let last = min(first + segment, params.classes);
+                        var drawn = 0xffffffffu;
+                        var acc = {ty}(0);
+                        for (var c = first; c < last; c++) {{
+                            let w = weights[base + c];
+                            if w > {ty}(0) {{
+                                drawn = c;
+                                acc += w;
+                                if acc > remaining {{
+                                    break;
+                                }}
+                            }}
+                        }}
+
+                        y[out] = drawn;
+                    }}
+                }}
+            "
+        )
+    }
+}
+
+/// Element-wise normal distribution kernel over `mean` and `std` of equal length.
+///
+/// Mode 0 samples `mean + std·z`, mode 1 evaluates the log-density
+/// `-(x - mean)²/(2·std²) - log(std) - log(2π)/2` of `x`, and mode 2 the entropy
+/// `1/2 + log(2π)/2 + log(std)`.
+struct Normal<T>(PhantomData<T>);
+
+impl<T: FloatElement> Kernel for Normal<T> {
+    const LABEL: &'static str = "normal";
+    type Output = T;
+
+    fn wgsl() -> String {
+        let ty = T::wgsl_type();
+
+        format!(
+            r"
+                {RANDOM_WGSL}
+
+                const HALF_LOG_TAU: f32 = 0.9189385332046727;
+
+                struct Params {{
+                    len: u32,
+                    mode: u32,
+                    seed: u32,
+                    _pad: u32,
+                }}
+
+                @group(0) @binding(0) var<storage, read> mean: array<{ty}>;
+                @group(0) @binding(1) var<storage, read> scale: array<{ty}>;
+                @group(0) @binding(2) var<storage, read> x: array<{ty}>;
+                @group(0) @binding(3) var<storage, read_write> y: array<{ty}>;
+                @group(0) @binding(4) var<uniform> params: Params;
+
+                @compute @workgroup_size({WORKGROUP_SIZE})
+                fn main(@builtin(global_invocation_id) gid: vec3<u32>) {{
+                    let tid = gid.x + gid.y * {MAX_WORKGROUPS}u * {WORKGROUP_SIZE}u;
+
+                    if tid >= params.len {{
+                        return;
+                    }}
+
+                    let s = scale[tid];
+
+                    switch params.mode {{
+                        case 0u: {{
+                            y[tid] = mean[tid] + s * {ty}(random_normal(params.seed, tid));
+                        }}
+                        case 1u: {{
+                            let z = (x[tid] - mean[tid]) / s;
+                            y[tid] = -0.5 * z * z - log(s) - {ty}(HALF_LOG_TAU);
+                        }}
+                        default: {{
+                            y[tid] = 0.5 + {ty}(HALF_LOG_TAU) + log(s);
+                        }}
+                    }}
+                }}
+            "
+        )
+    }
+}
+
+/// Draws one class per row of the contiguous `[rows, classes]` logits into `y`.
+///
+/// # Panics
+///
+/// - Row count or class count exceeds max size
+pub(crate) fn categorical_sample<T: FloatElement>(
+    ctx: &Context,
+    logits: &Buffer<T>,
+    y: &Buffer<u32>,
+    classes: usize,
+    seed: u32,
+) {
+    let rows = u32::try_from(y.len()).expect("row count exceeds max size");
+    let classes = u32::try_from(classes).expect("class count exceeds max size");
+
+    if rows == 0 {
+        return;
+    }
+
+    let pipeline = ctx.get_or_create_pipeline(
+        TypeId::of::<CategoricalSample<T>>(),
+        CategoricalSample::<T>::wgsl,
+        CategoricalSample::<T>::LABEL,
+    );
+    let params = ctx.create_uniform_buffer(&CategoricalParams {
+        rows,
+        classes,
+        mode: 0,
+        seed,
+    });
+
+    let (x, y_groups) = crate::kernel::compute_workgroups(rows);
+
+    crate::kernel::dispatch(
+        ctx,
+        &pipeline,
+        CategoricalSample::<T>::LABEL,
+        &[logits.inner(), y.inner(), &params],
+        (x, y_groups, 1),
+    );
+}
+
+/// Draws `samples` classes with replacement per row of the contiguous `[rows, classes]`
+/// weights into `y`.
+///
+/// # Panics
+///
+/// - Row count, class count or output length exceeds max size
+pub(crate) fn multinomial<T: FloatElement>(
+    ctx: &Context,
+    weights: &Buffer<T>,
+    y: &Buffer<u32>,
+    classes: usize,
+    samples: usize,
+    seed: u32,
+) {
+    u32::try_from(y.len()).expect("output length exceeds max size");
+    if y.is_empty() {
+        return;
+    }
+    let rows = u32::try_from(y.len() / samples).expect("row count exceeds max size");
+    let classes = u32::try_from(classes).expect("class count exceeds max size");
+    let samples = u32::try_from(samples).expect("output length exceeds max size");
+
+    let pipeline = ctx.get_or_create_pipeline(
+        TypeId::of::<Multinomial<T>>(),
+        Multinomial::<T>::wgsl,
+        Multinomial::<T>::LABEL,
+    );
+    let params = ctx.create_uniform_buffer(&MultinomialParams {
+        rows,
+        classes,
+        samples,
+        seed,
+    });
+
+    crate::kernel::dispatch(
+        ctx,
+        &pipeline,
+        Multinomial::<T>::LABEL,
+        &[weights.inner(), y.inner(), &params],
+        (rows.min(MAX_WORKGROUPS), rows.div_ceil(MAX_WORKGROUPS), 1),
+    );
+}
+
+/// Writes the log-probabilities of `actions`, or the entropy if `None`, of each row of the
+/// contiguous `[rows, classes]` logits into `y`.
+///
+/// # Panics
+///
+/// - Row count or class count exceeds max size
+pub(crate) fn categorical_score<T: FloatElement>(
+    ctx: &Context,
+    logits: &Buffer<T>,
+    actions: Option<&Buffer<u32>>,
+    y: &Buffer<T>,
+    classes: usize,
+) {
+    let rows = u32::try_from(y.len()).expect("row count exceeds max size");
+    let classes = u32::try_from(classes).expect("class count exceeds max size");
+
+    if rows == 0 {
+        return;
+    }
+
+    let pipeline = ctx.get_or_create_pipeline(
+        TypeId::of::<CategoricalScore<T>>(),
+        CategoricalScore::<T>::wgsl,
+        CategoricalScore::<T>::LABEL,
+    );
+
+    let mode = u32::from(actions.is_none());
+    let unused;
+    let actions = if let Some(actions) = actions {
+        actions.inner()
+    } else {
+        unused = ctx.create_storage_buffer(&[0u32]);
+        &unused
+    };
+
+    let params = ctx.create_uniform_buffer(&CategoricalParams {
+        rows,
+        classes,
+        mode,
+        seed: 0,
+    });
+
+    let (x, y_groups) = crate::kernel::compute_workgroups(rows);
+
+    crate::kernel::dispatch(
+        ctx,
+        &pipeline,
+        CategoricalScore::<T>::LABEL,
+        &[logits.inner(), actions, y.inner(), &params],
+        (x, y_groups, 1),
+    );
+}
+
+/// Executes `op` of the normal distribution with contiguous `mean` and `std` into `y`.
+///
+/// # Panics
+///
+/// - Output length exceeds max size
+pub(crate) fn normal<T: FloatElement>(
+    ctx: &Context,
+    mean: &Buffer<T>,
+    std: &Buffer<T>,
+    y: &Buffer<T>,
+    op: &NormalOp<'_, T>,
+) {
+    let len = u32::try_from(y.len()).expect("output length exceeds max size");
+
+    if len == 0 {
+        return;
+    }
+
+    let pipeline = ctx.get_or_create_pipeline(
+        TypeId::of::<Normal<T>>(),
+        Normal::<T>::wgsl,
+        Normal::<T>::LABEL,
+    );
+
+    let (mode, seed, x) = match op {
+        NormalOp::Sample(seed) => (0, *seed, mean),
+        NormalOp::LogProb(x) => (1, 0, *x),
+        NormalOp::Entropy => (2, 0, mean),
+    };
+    let params = ctx.create_uniform_buffer(&NormalParams {
+        len,
+        mode,
+        seed,
+        _pad: 0,
+    });
+
+    let (wx, wy) = crate::kernel::compute_workgroups(len);
+
+    crate::kernel::dispatch(
+        ctx,
+        &pipeline,
+        Normal::<T>::LABEL,
+        &[mean.inner(), std.inner(), x.inner(), y.inner(), &params],
+        (wx, wy, 1),
+    );
+}
@@ -0,0 +1,97 @@
+//! Element-wise kernels built from user WGSL expressions.
+
+use core::fmt::Write as _;
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::element::NumericElement;
+use crate::error::{Error, TensorError};
+use crate::kernel::{MAX_WORKGROUPS, WORKGROUP_SIZE};
+use crate::{Buffer, Context};
+
+/// Builds an element-wise kernel that evaluates `expr` once per index `i` and writes the
+/// result, converted to `output`, to `y[i]`.
+///
+/// `inputs` are `(name, type)` pairs bound to storage buffers in order, and each element
+/// `input[i]` is visible to `expr` as a `let` of the input's name. `y` is bound after the
+/// inputs, followed by the uniform element count.
+fn source(inputs: &[(&str, &str)], output: &str, expr: &str) -> String {
+    let mut bindings = String::new();
+    let mut elements = String::new();
+    for (binding, (name, ty)) in inputs.iter().enumerate() {
+        // Writing to a `String` cannot fail.
+        let _ = writeln!(
+            bindings,
+            "@group(0) @binding({binding}) var<storage, read> input_{name}: array<{ty}>;"
+        );
+        let _ = writeln!(elements, "let {name} = input_{name}[i];");
+    }
+    let y = inputs.len();
+    let len = y + 1;
+
+    format!(
+        r"
+            {bindings}
+            @group(0) @binding({y}) var<storage, read_write> y: array<{output}>;
+            @group(0) @binding({len}) var<uniform> len: vec4<u32>;
+
+            @compute @workgroup_size({WORKGROUP_SIZE})
+            fn main(@builtin(global_invocation_id) gid: vec3<u32>) {{
+                let i = gid.x + gid.y * {MAX_WORKGROUPS}u * {WORKGROUP_SIZE}u;
+                if i >= len.x {{
+                    return;
+                }}
+
+                {elements}
+                y[i] = {output}({expr});
+            }}
+        "
+    )
+}
+
+/// Evaluates `expr` for each element of `inputs`, named by the first item of each pair,
+/// into `y`.
+///
+/// The pipeline is cached by its generated source, so repeated calls with the same
+/// expression and element types compile once.
+///
+/// # Errors
+///
+/// - [`TensorError::InvalidArgument`] if `expr` does not form valid WGSL.
+///
+/// # Panics
+///
+/// - Buffer length exceeds max size
+pub(crate) fn execute<T: NumericElement, U: NumericElement>(
+    ctx: &Context,
+    inputs: &[(&str, &Buffer<T>)],
+    y: &Buffer<U>,
+    expr: &str,
+    label: &'static str,
+) -> Result<(), Error> {
+    let types: Vec<_> = inputs
+        .iter()
+        .map(|&(name, _)| (name, T::wgsl_type()))
+        .collect();
+    let pipeline = ctx
+        .get_or_create_source_pipeline(source(&types, U::wgsl_type(), expr), label)
+        .map_err(|error| {
+            TensorError::InvalidArgument(format!("invalid expression `{expr}`: {error}"))
+        })?;
+
+    let len = u32::try_from(y.len()).expect("buffer length exceeds max size");
+    if len == 0 {
+        return Ok(());
+    }
+
+    let params = ctx.create_uniform_buffer(&[len, 0, 0, 0]);
+    let mut resources: Vec<_> = inputs.iter().map(|(_, buffer)| buffer.inner()).collect();
+    resources.push(y.inner());
+    resources.push(&params);
+
+    let (x, y) = super::compute_workgroups(len);
+    super::dispatch(ctx, &pipeline, label, &resources, (x, y, 1));
+    Ok(())
+}
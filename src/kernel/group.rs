@@ -0,0 +1,180 @@
+//! Group-by aggregation kernel.
+
+use core::any::TypeId;
+use core::marker::PhantomData;
+
+use alloc::format;
+use alloc::string::String;
+
+use bytemuck::{Pod, Zeroable};
+
+use crate::element::FloatElement;
+use crate::kernel::{Kernel, MAX_WORKGROUPS, WORKGROUP_SIZE};
+use crate::{Buffer, Context};
+
+/// Kernel parameters passed to shader as uniform.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Pod, Zeroable)]
+struct Params {
+    len: u32,
+    cols: u32,
+    groups: u32,
+    mean: u32,
+    /// `1` if rows are read through `order`, `0` if they are already in key order.
+    permuted: u32,
+    _pad: [u32; 3],
+}
+
+/// Group reduction kernel: `y[g] = Σ values[i]` over rows `i` with `keys[i] = g`.
+///
+/// Rows are read through `order`, the stable sort permutation of the keys, so the rows of
+/// group `g` form the segment of `sorted` equal to `g`; rows whose keys are already sorted
+/// are read directly. Each thread binary-searches the bounds
+/// of its group's segment and sums one column of it in row order, which keeps the result
+/// deterministic without floating-point atomics. The first column's thread also writes the
+/// group size to `counts`; in mean mode sums are divided by it and empty groups are zero.
+struct GroupReduce<T>(PhantomData<T>);
+
+impl<T: FloatElement> Kernel for GroupReduce<T> {
+    const LABEL: &'static str = "group_reduce";
+    type Output = T;
+
+    fn wgsl() -> String {
+        let ty = T::wgsl_type();
+
+        format!(
+            r"
+                struct Params {{
+                    len: u32,
+                    cols: u32,
+                    groups: u32,
+                    mean: u32,
+                    permuted: u32,
+                    _pad0: u32,
+                    _pad1: u32,
+                    _pad2: u32,
+                }}
+
+                @group(0) @binding(0) var<storage, read> values: array<{ty}>;
+                @group(0) @binding(1) var<storage, read> sorted: array<u32>;
+                @group(0) @binding(2) var<storage, read> order: array<u32>;
+                @group(0) @binding(3) var<storage, read_write> y: array<{ty}>;
+                @group(0) @binding(4) var<storage, read_write> counts: array<u32>;
+                @group(0) @binding(5) var<uniform> params: Params;
+
+                fn lower_bound(key: u32) -> u32 {{
+                    var lo = 0u;
+                    var hi = params.len;
+                    while lo < hi {{
+                        let mid = (lo + hi) / 2u;
+                        if sorted[mid] < key {{
+                            lo = mid + 1u;
+                        }} else {{
+                            hi = mid;
+                        }}
+                    }}
+                    return lo;
+                }}
+
+                @compute @workgroup_size({WORKGROUP_SIZE})
+                fn main(@builtin(global_invocation_id) gid: vec3<u32>) {{
+                    let tid = gid.x + gid.y * {MAX_WORKGROUPS}u * {WORKGROUP_SIZE}u;
+                    let width = max(params.cols, 1u);
+
+                    if tid >= params.groups * width {{
+                        return;
+                    }}
+
+                    let group = tid / width;
+                    let col = tid % width;
+
+                    let start = lower_bound(group);
+                    let end = lower_bound(group + 1u);
+
+                    if col == 0u {{
+                        counts[group] = end - start;
+                    }}
+
+                    if col >= params.cols {{
+                        return;
+                    }}
+
+                    var acc: {ty} = 0.0;
+                    for (var i = start; i < end; i++) {{
+                        let row = select(i, order[i], params.permuted != 0u);
+                        acc += values[row * params.cols + col];
+                    }}
+
+                    if params.mean != 0u && end > start {{
+                        acc /= {ty}(end - start);
+                    }}
+
+                    y[tid] = acc;
+                }}
+            "
+        )
+    }
+}
+
+/// Sums or averages the `[len, cols]` `values` rows per key into `[groups, cols]` `y`.
+///
+/// `sorted` holds the keys in ascending order and `order` the row each sorted key came from,
+/// or `None` if `sorted` holds the keys of the rows in place. Keys of `groups` or more are
+/// ignored.
+///
+/// # Panics
+///
+/// - Row count, column count or group count exceeds max size
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn execute<T: FloatElement>(
+    ctx: &Context,
+    values: &Buffer<T>,
+    sorted: &Buffer<u32>,
+    order: Option<&Buffer<u32>>,
+    y: &Buffer<T>,
+    counts: &Buffer<u32>,
+    cols: usize,
+    mean: bool,
+) {
+    let to_u32 = |x: usize| u32::try_from(x).expect("dimension exceeds max size");
+    let params = Params {
+        len: to_u32(sorted.len()),
+        cols: to_u32(cols),
+        groups: to_u32(counts.len()),
+        mean: u32::from(mean),
+        permuted: u32::from(order.is_some()),
+        _pad: [0; 3],
+    };
+
+    let threads = params
+        .groups
+        .checked_mul(params.cols.max(1))
+        .expect("output length exceeds max size");
+    if threads == 0 {
+        return;
+    }
+
+    let pipeline = ctx.get_or_create_pipeline(
+        TypeId::of::<GroupReduce<T>>(),
+        GroupReduce::<T>::wgsl,
+        GroupReduce::<T>::LABEL,
+    );
+    let params = ctx.create_uniform_buffer(&params);
+
+    let (x, y_groups) = crate::kernel::compute_workgroups(threads);
+
+    crate::kernel::dispatch(
+        ctx,
+        &pipeline,
+        GroupReduce::<T>::LABEL,
+        &[
+            values.inner(),
+            sorted.inner(),
+            order.unwrap_or(sorted).inner(),
+            y.inner(),
+            counts.inner(),
+            &params,
+        ],
+        (x, y_groups, 1),
+    );
+}
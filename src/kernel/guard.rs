@@ -0,0 +1,135 @@
+//! Stop flag kernels for skipping dispatches on the GPU.
+
+use core::any::TypeId;
+
+use alloc::format;
+use alloc::string::String;
+
+use bytemuck::{Pod, Zeroable};
+
+use crate::kernel::{Kernel, MAX_WORKGROUPS, WORKGROUP_SIZE};
+use crate::{Buffer, Context};
+
+/// Kernel parameters passed to shader as uniform.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Pod, Zeroable)]
+struct Params {
+    len: u32,
+    _pad: u32,
+}
+
+/// Copies workgroup counts `args = flag[0] ? 0 : source`.
+struct Gate;
+
+impl Kernel for Gate {
+    const LABEL: &'static str = "gate";
+    type Output = u32;
+
+    fn wgsl() -> String {
+        r"
+            @group(0) @binding(0) var<storage, read> flag: array<u32>;
+            @group(0) @binding(1) var<storage, read> source: array<u32>;
+            @group(0) @binding(2) var<storage, read_write> args: array<u32>;
+
+            @compute @workgroup_size(1)
+            fn main() {
+                let open = flag[0] == 0u;
+                for (var i = 0u; i < 3u; i++) {
+                    args[i] = select(0u, source[i], open);
+                }
+            }
+        "
+        .into()
+    }
+}
+
+/// Raises a flag: `flag[0] = true` if any `condition[i]` is true.
+struct SetIfAny;
+
+impl Kernel for SetIfAny {
+    const LABEL: &'static str = "set_if_any";
+    type Output = u32;
+
+    fn wgsl() -> String {
+        format!(
+            r"
+                struct Params {{
+                    len: u32,
+                    _pad: u32,
+                }}
+
+                @group(0) @binding(0) var<storage, read> condition: array<u32>;
+                @group(0) @binding(1) var<storage, read_write> flag: array<u32>;
+                @group(0) @binding(2) var<uniform> params: Params;
+
+                @compute @workgroup_size({WORKGROUP_SIZE})
+                fn main(@builtin(global_invocation_id) gid: vec3<u32>) {{
+                    let tid = gid.x + gid.y * {MAX_WORKGROUPS}u * {WORKGROUP_SIZE}u;
+
+                    if tid < params.len && condition[tid] != 0u {{
+                        flag[0] = 1u;
+                    }}
+                }}
+            "
+        )
+    }
+}
+
+/// Records a pass into `encoder` that writes the workgroup counts in `source`, or zero counts
+/// if `flag` is set, into a new indirect buffer.
+///
+/// The pass itself is never gated, so it always runs ahead of the dispatch it guards.
+pub(crate) fn gate(
+    ctx: &Context,
+    encoder: &mut wgpu::CommandEncoder,
+    flag: &wgpu::Buffer,
+    source: &wgpu::Buffer,
+) -> wgpu::Buffer {
+    let pipeline = ctx.get_or_create_pipeline(TypeId::of::<Gate>(), Gate::wgsl, Gate::LABEL);
+    let args = ctx.create_indirect_buffer();
+
+    let bind_group = crate::kernel::create_bind_group(
+        ctx,
+        &pipeline,
+        Gate::LABEL,
+        &crate::kernel::buffer_entries(&[flag, source, &args]),
+    );
+
+    let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+        label: Some(Gate::LABEL),
+        ..Default::default()
+    });
+    pass.set_pipeline(&pipeline);
+    pass.set_bind_group(0, &bind_group, &[]);
+    pass.dispatch_workgroups(1, 1, 1);
+    drop(pass);
+
+    args
+}
+
+/// Sets `flag[0]` if any element of `condition` is true, leaving it unchanged otherwise.
+///
+/// # Panics
+///
+/// - Condition length exceeds max size
+pub(crate) fn set_if_any(ctx: &Context, condition: &Buffer<bool>, flag: &Buffer<bool>) {
+    let len = u32::try_from(condition.len()).expect("condition length exceeds max size");
+
+    if len == 0 {
+        return;
+    }
+
+    let pipeline =
+        ctx.get_or_create_pipeline(TypeId::of::<SetIfAny>(), SetIfAny::wgsl, SetIfAny::LABEL);
+
+    let params = ctx.create_uniform_buffer(&Params { len, _pad: 0 });
+    let (x, y) = crate::kernel::compute_workgroups(len);
+
+    crate::kernel::dispatch(
+        ctx,
+        &pipeline,
+        SetIfAny::LABEL,
+        &[condition.inner(), flag.inner(), &params],
+        (x, y, 1),
+    );
+}
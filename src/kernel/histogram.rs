@@ -0,0 +1,251 @@
+//! Histogram kernels.
+
+use core::any::TypeId;
+use core::marker::PhantomData;
+
+use alloc::format;
+use alloc::string::String;
+
+use bytemuck::{Pod, Zeroable};
+
+use crate::element::FloatElement;
+use crate::kernel::{Kernel, MAX_WORKGROUPS, WORKGROUP_SIZE};
+use crate::{Buffer, Context};
+
+/// Kernel parameters passed to shader as uniform.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Pod, Zeroable)]
+struct Params {
+    len: u32,
+    bins: u32,
+    min: f32,
+    max: f32,
+}
+
+/// Histogram kernel: `counts[bin(x[i])] += 1` over equal-width bins of `[min, max]`.
+struct Histogram<T>(PhantomData<T>);
+
+impl<T: FloatElement> Kernel for Histogram<T> {
+    const LABEL: &'static str = "histogram";
+    type Output = u32;
+
+    fn wgsl() -> String {
+        let ty = T::wgsl_type();
+
+        format!(
+            r"
+                struct Params {{
+                    len: u32,
+                    bins: u32,
+                    min: f32,
+                    max: f32,
+                }}
+
+                @group(0) @binding(0) var<storage, read> x: array<{ty}>;
+                @group(0) @binding(1) var<storage, read_write> counts: array<atomic<u32>>;
+                @group(0) @binding(2) var<uniform> params: Params;
+
+                @compute @workgroup_size({WORKGROUP_SIZE})
+                fn main(@builtin(global_invocation_id) gid: vec3<u32>) {{
+                    let tid = gid.x + gid.y * {MAX_WORKGROUPS}u * {WORKGROUP_SIZE}u;
+
+                    if tid >= params.len {{
+                        return;
+                    }}
+
+                    let value = f32(x[tid]);
+                    if (bitcast<u32>(value) & 0x7fffffffu) > 0x7f800000u {{
+                        return;
+                    }}
+
+                    let scaled = (value - params.min) / (params.max - params.min) * f32(params.bins);
+                    let bin = u32(clamp(scaled, 0.0, f32(params.bins - 1u)));
+                    atomicAdd(&counts[bin], 1u);
+                }}
+            "
+        )
+    }
+}
+
+/// Accumulates a histogram of `x` into zero-initialized `counts`.
+///
+/// Values outside `[min, max]` land in the edge bins; `NaN` is skipped.
+///
+/// # Panics
+///
+/// - Input length or bin count exceed max size
+pub(crate) fn execute<T: FloatElement>(
+    ctx: &Context,
+    x: &Buffer<T>,
+    counts: &Buffer<u32>,
+    min: f32,
+    max: f32,
+) {
+    let len = u32::try_from(x.len()).expect("input length exceeds max size");
+    let bins = u32::try_from(counts.len()).expect("bin count exceeds max size");
+
+    let pipeline = ctx.get_or_create_pipeline(
+        TypeId::of::<Histogram<T>>(),
+        Histogram::<T>::wgsl,
+        Histogram::<T>::LABEL,
+    );
+
+    let params = ctx.create_uniform_buffer(&Params {
+        len,
+        bins,
+        min,
+        max,
+    });
+
+    let (wx, wy) = crate::kernel::compute_workgroups(len);
+
+    crate::kernel::dispatch(
+        ctx,
+        &pipeline,
+        Histogram::<T>::LABEL,
+        &[x.inner(), counts.inner(), &params],
+        (wx, wy, 1),
+    );
+}
+
+/// Gradient histogram parameters passed to shader as uniform.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Pod, Zeroable)]
+struct GradientParams {
+    rows: u32,
+    features: u32,
+    bins: u32,
+    _pad: u32,
+}
+
+/// Gradient histogram kernel: `hist[f, b] = (Σ grad[i], Σ hess[i])` over rows `i` whose
+/// feature `f` falls in bin `b`.
+///
+/// Each feature's bins arrive sorted in row `f` of `sorted`, with `order` giving the source row
+/// of every entry, so the rows in bin `b` form one segment. Each thread binary-searches the
+/// bounds of its `(feature, bin)` segment and sums it in row order, which keeps the result
+/// deterministic without floating-point atomics.
+struct GradientHistogram;
+
+impl Kernel for GradientHistogram {
+    const LABEL: &'static str = "gradient_histogram";
+    type Output = f32;
+
+    fn wgsl() -> String {
+        format!(
+            r"
+                struct Params {{
+                    rows: u32,
+                    features: u32,
+                    bins: u32,
+                }}
+
+                @group(0) @binding(0) var<storage, read> sorted: array<u32>;
+                @group(0) @binding(1) var<storage, read> order: array<u32>;
+                @group(0) @binding(2) var<storage, read> grad: array<f32>;
+                @group(0) @binding(3) var<storage, read> hess: array<f32>;
+                @group(0) @binding(4) var<storage, read_write> hist: array<f32>;
+                @group(0) @binding(5) var<uniform> params: Params;
+
+                fn lower_bound(base: u32, bin: u32) -> u32 {{
+                    var lo = 0u;
+                    var hi = params.rows;
+                    while lo < hi {{
+                        let mid = (lo + hi) / 2u;
+                        if sorted[base + mid] < bin {{
+                            lo = mid + 1u;
+                        }} else {{
+                            hi = mid;
+                        }}
+                    }}
+                    return lo;
+                }}
+
+                @compute @workgroup_size({WORKGROUP_SIZE})
+                fn main(@builtin(global_invocation_id) gid: vec3<u32>) {{
+                    let tid = gid.x + gid.y * {MAX_WORKGROUPS}u * {WORKGROUP_SIZE}u;
+
+                    if tid >= params.features * params.bins {{
+                        return;
+                    }}
+
+                    let feature = tid / params.bins;
+                    let bin = tid % params.bins;
+                    let base = feature * params.rows;
+
+                    let start = lower_bound(base, bin);
+                    let end = lower_bound(base, bin + 1u);
+
+                    var g = 0.0;
+                    var h = 0.0;
+                    for (var i = start; i < end; i++) {{
+                        let row = order[base + i];
+                        g += grad[row];
+                        h += hess[row];
+                    }}
+
+                    hist[2u * tid] = g;
+                    hist[2u * tid + 1u] = h;
+                }}
+            "
+        )
+    }
+}
+
+/// Accumulates per-bin gradient and hessian sums into `[features, bins, 2]` `hist`.
+///
+/// `sorted` holds the `[features, rows]` bin indices sorted within each feature and `order`
+/// the row of each entry. Bins of `bins` or more are ignored.
+///
+/// # Panics
+///
+/// - Row count, feature count or bin count exceeds max size
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn gradient_histogram(
+    ctx: &Context,
+    sorted: &Buffer<u32>,
+    order: &Buffer<u32>,
+    grad: &Buffer<f32>,
+    hess: &Buffer<f32>,
+    hist: &Buffer<f32>,
+    rows: usize,
+    features: usize,
+    bins: usize,
+) {
+    let to_u32 = |x: usize| u32::try_from(x).expect("dimension exceeds max size");
+    let params = GradientParams {
+        rows: to_u32(rows),
+        features: to_u32(features),
+        bins: to_u32(bins),
+        _pad: 0,
+    };
+
+    let threads = to_u32(features * bins);
+    if threads == 0 {
+        return;
+    }
+
+    let pipeline = ctx.get_or_create_pipeline(
+        TypeId::of::<GradientHistogram>(),
+        GradientHistogram::wgsl,
+        GradientHistogram::LABEL,
+    );
+    let params = ctx.create_uniform_buffer(&params);
+
+    let (wx, wy) = crate::kernel::compute_workgroups(threads);
+
+    crate::kernel::dispatch(
+        ctx,
+        &pipeline,
+        GradientHistogram::LABEL,
+        &[
+            sorted.inner(),
+            order.inner(),
+            grad.inner(),
+            hess.inner(),
+            hist.inner(),
+            &params,
+        ],
+        (wx, wy, 1),
+    );
+}
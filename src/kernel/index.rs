@@ -0,0 +1,458 @@
+//! Indexing kernels.
+
+use core::any::TypeId;
+use core::marker::PhantomData;
+
+use alloc::format;
+use alloc::string::String;
+
+use bytemuck::{Pod, Zeroable};
+
+use crate::kernel::{Kernel, MAX_WORKGROUPS, WORKGROUP_SIZE};
+use crate::{Buffer, Context, Element};
+
+/// Kernel parameters passed to shader as uniform.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Pod, Zeroable)]
+struct Params {
+    len: u32,
+    row_len: u32,
+    rows: u32,
+    _pad: u32,
+}
+
+/// Kernel parameters for masked copy passed to shader as uniform.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Pod, Zeroable)]
+struct MaskedParams {
+    rank: u32,
+    len: u32,
+}
+
+/// Row scatter kernel: `dst[indices[i]] = values[i]`.
+struct Put<T>(PhantomData<T>);
+
+impl<T: Element> Kernel for Put<T> {
+    const LABEL: &'static str = "index_put";
+    type Output = T;
+
+    fn wgsl() -> String {
+        let ty = T::wgsl_type();
+
+        format!(
+            r"
+                struct Params {{
+                    len: u32,
+                    row_len: u32,
+                    rows: u32,
+                    _pad: u32,
+                }}
+
+                @group(0) @binding(0) var<storage, read> indices: array<u32>;
+                @group(0) @binding(1) var<storage, read> values: array<{ty}>;
+                @group(0) @binding(2) var<storage, read_write> dst: array<{ty}>;
+                @group(0) @binding(3) var<uniform> params: Params;
+
+                @compute @workgroup_size({WORKGROUP_SIZE})
+                fn main(@builtin(global_invocation_id) gid: vec3<u32>) {{
+                    let tid = gid.x + gid.y * {MAX_WORKGROUPS}u * {WORKGROUP_SIZE}u;
+
+                    if tid >= params.len {{
+                        return;
+                    }}
+
+                    let row = indices[tid / params.row_len];
+                    if row < params.rows {{
+                        dst[row * params.row_len + tid % params.row_len] = values[tid];
+                    }}
+                }}
+            "
+        )
+    }
+}
+
+/// Writes `values` rows into the rows of `dst` selected by `indices`.
+///
+/// Indices outside `rows` are skipped.
+///
+/// # Panics
+///
+/// - Values length exceeds max size
+/// - Row length or row count exceed max size
+pub(crate) fn put<T: Element>(
+    ctx: &Context,
+    indices: &Buffer<u32>,
+    values: &Buffer<T>,
+    dst: &Buffer<T>,
+    rows: usize,
+    row_len: usize,
+) {
+    let len = u32::try_from(values.len()).expect("values length exceeds max size");
+    let to_u32 = |x: usize| u32::try_from(x).expect("dimension exceeds max size");
+
+    if len == 0 {
+        return;
+    }
+
+    let pipeline =
+        ctx.get_or_create_pipeline(TypeId::of::<Put<T>>(), Put::<T>::wgsl, Put::<T>::LABEL);
+
+    let params = ctx.create_uniform_buffer(&Params {
+        len,
+        row_len: to_u32(row_len),
+        rows: to_u32(rows),
+        _pad: 0,
+    });
+
+    let (x, y) = crate::kernel::compute_workgroups(len);
+
+    crate::kernel::dispatch(
+        ctx,
+        &pipeline,
+        Put::<T>::LABEL,
+        &[indices.inner(), values.inner(), dst.inner(), &params],
+        (x, y, 1),
+    );
+}
+
+/// Row gather kernel: `dst[i] = src[indices[i]]` for the first `count` rows.
+struct Take<T>(PhantomData<T>);
+
+impl<T: Element> Kernel for Take<T> {
+    const LABEL: &'static str = "take_rows";
+    type Output = T;
+
+    fn wgsl() -> String {
+        let ty = T::wgsl_type();
+
+        format!(
+            r"
+                struct Params {{
+                    len: u32,
+                    row_len: u32,
+                    rows: u32,
+                    _pad: u32,
+                }}
+
+                @group(0) @binding(0) var<storage, read> src: array<{ty}>;
+                @group(0) @binding(1) var<storage, read> indices: array<u32>;
+                @group(0) @binding(2) var<storage, read> count: array<u32>;
+                @group(0) @binding(3) var<storage, read_write> dst: array<{ty}>;
+                @group(0) @binding(4) var<uniform> params: Params;
+
+                @compute @workgroup_size({WORKGROUP_SIZE})
+                fn main(@builtin(global_invocation_id) gid: vec3<u32>) {{
+                    let tid = gid.x + gid.y * {MAX_WORKGROUPS}u * {WORKGROUP_SIZE}u;
+                    let i = tid / params.row_len;
+
+                    if tid >= params.len || i >= count[0] {{
+                        return;
+                    }}
+
+                    let row = indices[i];
+                    if row < params.rows {{
+                        dst[tid] = src[row * params.row_len + tid % params.row_len];
+                    }}
+                }}
+            "
+        )
+    }
+}
+
+/// Gathers the rows of `src` selected by the first `count[0]` entries of `indices` into `dst`.
+///
+/// The dispatch is sized on the GPU from `count`, so no readback is needed when an earlier
+/// kernel produced it. Rows of `dst` past the count and indices outside `rows` are left
+/// untouched.
+///
+/// # Panics
+///
+/// - Output length exceeds max size
+/// - Row length or row count exceed max size
+pub(crate) fn take<T: Element>(
+    ctx: &Context,
+    src: &Buffer<T>,
+    indices: &Buffer<u32>,
+    count: &Buffer<u32>,
+    dst: &Buffer<T>,
+    rows: usize,
+    row_len: usize,
+) {
+    let len = u32::try_from(dst.len()).expect("output length exceeds max size");
+    let to_u32 = |x: usize| u32::try_from(x).expect("dimension exceeds max size");
+
+    if len == 0 {
+        return;
+    }
+
+    let pipeline =
+        ctx.get_or_create_pipeline(TypeId::of::<Take<T>>(), Take::<T>::wgsl, Take::<T>::LABEL);
+
+    let params = ctx.create_uniform_buffer(&Params {
+        len,
+        row_len: to_u32(row_len),
+        rows: to_u32(rows),
+        _pad: 0,
+    });
+
+    let args = crate::kernel::indirect::workgroups(ctx, count, indices.len(), row_len);
+
+    crate::kernel::dispatch_indirect(
+        ctx,
+        &pipeline,
+        Take::<T>::LABEL,
+        &[
+            src.inner(),
+            indices.inner(),
+            count.inner(),
+            dst.inner(),
+            &params,
+        ],
+        &args,
+    );
+}
+
+/// Kernel parameters for gather passed to shader as uniform.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Pod, Zeroable)]
+struct GatherParams {
+    rank: u32,
+    len: u32,
+    offset: u32,
+    axis: u32,
+    size: u32,
+    _pad: [u32; 3],
+}
+
+/// Gather kernel: `dst[i] = src[coord(i)]` with the `axis` coordinate replaced by `indices[i]`.
+struct Gather<T>(PhantomData<T>);
+
+impl<T: Element> Kernel for Gather<T> {
+    const LABEL: &'static str = "gather";
+    type Output = T;
+
+    fn wgsl() -> String {
+        let ty = T::wgsl_type();
+        let zero = T::wgsl_zero();
+
+        format!(
+            r"
+                struct Params {{
+                    rank: u32,
+                    len: u32,
+                    offset: u32,
+                    axis: u32,
+                    size: u32,
+                    _pad0: u32,
+                    _pad1: u32,
+                    _pad2: u32,
+                }}
+
+                @group(0) @binding(0) var<storage, read> src: array<{ty}>;
+                @group(0) @binding(1) var<storage, read> indices: array<u32>;
+                @group(0) @binding(2) var<storage, read_write> dst: array<{ty}>;
+                @group(0) @binding(3) var<storage, read> src_strides: array<u32>;
+                @group(0) @binding(4) var<storage, read> dst_strides: array<u32>;
+                @group(0) @binding(5) var<uniform> params: Params;
+
+                @compute @workgroup_size({WORKGROUP_SIZE})
+                fn main(@builtin(global_invocation_id) gid: vec3<u32>) {{
+                    let tid = gid.x + gid.y * {MAX_WORKGROUPS}u * {WORKGROUP_SIZE}u;
+
+                    if tid >= params.len {{
+                        return;
+                    }}
+
+                    let index = indices[tid];
+                    if index >= params.size {{
+                        dst[tid] = {zero};
+                        return;
+                    }}
+
+                    var remaining = tid;
+                    var src_idx = params.offset;
+
+                    for (var i = 0u; i < params.rank; i++) {{
+                        var coord = remaining / dst_strides[i];
+                        remaining = remaining % dst_strides[i];
+                        if i == params.axis {{
+                            coord = index;
+                        }}
+                        src_idx += coord * src_strides[i];
+                    }}
+
+                    dst[tid] = src[src_idx];
+                }}
+            "
+        )
+    }
+}
+
+/// Gathers `src` along `axis` at `indices` into contiguous `dst`.
+///
+/// `src_strides` and `offset` describe the source view and `dst_strides` the contiguous
+/// strides of the output, which has the dimensions of `indices`. Indices of `size` or more
+/// produce zero.
+///
+/// # Panics
+///
+/// - Output length exceeds max size
+/// - Output rank exceeds max size
+/// - Offset, axis or size exceed max size
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn gather<T: Element>(
+    ctx: &Context,
+    src: &Buffer<T>,
+    indices: &Buffer<u32>,
+    dst: &Buffer<T>,
+    src_strides: &[usize],
+    dst_strides: &[usize],
+    offset: usize,
+    axis: usize,
+    size: usize,
+) {
+    let rank = u32::try_from(dst_strides.len()).expect("output rank exceeds max size");
+    let len = u32::try_from(dst.len()).expect("output length exceeds max size");
+    let to_u32 = |x: usize| u32::try_from(x).expect("dimension exceeds max size");
+
+    if len == 0 {
+        return;
+    }
+
+    let pipeline = ctx.get_or_create_pipeline(
+        TypeId::of::<Gather<T>>(),
+        Gather::<T>::wgsl,
+        Gather::<T>::LABEL,
+    );
+
+    let src_strides = ctx.create_storage_buffer(&crate::kernel::convert_strides(src_strides));
+    let dst_strides = ctx.create_storage_buffer(&crate::kernel::convert_strides(dst_strides));
+    let params = ctx.create_uniform_buffer(&GatherParams {
+        rank,
+        len,
+        offset: to_u32(offset),
+        axis: to_u32(axis),
+        size: to_u32(size),
+        _pad: [0; 3],
+    });
+
+    let (x, y) = crate::kernel::compute_workgroups(len);
+
+    crate::kernel::dispatch(
+        ctx,
+        &pipeline,
+        Gather::<T>::LABEL,
+        &[
+            src.inner(),
+            indices.inner(),
+            dst.inner(),
+            &src_strides,
+            &dst_strides,
+            &params,
+        ],
+        (x, y, 1),
+    );
+}
+
+/// Masked copy kernel: `dst[i] = src[i]` where `mask[i]` is true.
+struct MaskedCopy<T>(PhantomData<T>);
+
+impl<T: Element> Kernel for MaskedCopy<T> {
+    const LABEL: &'static str = "masked_copy";
+    type Output = T;
+
+    fn wgsl() -> String {
+        let ty = T::wgsl_type();
+
+        format!(
+            r"
+                struct Params {{
+                    rank: u32,
+                    len: u32,
+                }}
+
+                @group(0) @binding(0) var<storage, read> src: array<{ty}>;
+                @group(0) @binding(1) var<storage, read> mask: array<u32>;
+                @group(0) @binding(2) var<storage, read_write> dst: array<{ty}>;
+                @group(0) @binding(3) var<storage, read> src_strides: array<u32>;
+                @group(0) @binding(4) var<storage, read> mask_strides: array<u32>;
+                @group(0) @binding(5) var<storage, read> dst_strides: array<u32>;
+                @group(0) @binding(6) var<uniform> params: Params;
+
+                @compute @workgroup_size({WORKGROUP_SIZE})
+                fn main(@builtin(global_invocation_id) gid: vec3<u32>) {{
+                    let tid = gid.x + gid.y * {MAX_WORKGROUPS}u * {WORKGROUP_SIZE}u;
+
+                    if tid >= params.len {{
+                        return;
+                    }}
+
+                    var remaining = tid;
+                    var src_idx = 0u;
+                    var mask_idx = 0u;
+
+                    for (var i = 0u; i < params.rank; i++) {{
+                        let coord = remaining / dst_strides[i];
+                        remaining = remaining % dst_strides[i];
+                        src_idx += coord * src_strides[i];
+                        mask_idx += coord * mask_strides[i];
+                    }}
+
+                    if mask[mask_idx] != 0u {{
+                        dst[tid] = src[src_idx];
+                    }}
+                }}
+            "
+        )
+    }
+}
+
+/// Copies `src` into `dst` where `mask` is true, leaving other elements untouched.
+///
+/// `src_strides` and `mask_strides` broadcast their operands to the dimensions of `dst`.
+///
+/// # Panics
+///
+/// - Output length exceeds max size
+/// - Output rank exceeds max size
+pub(crate) fn masked_copy<T: Element>(
+    ctx: &Context,
+    src: &Buffer<T>,
+    mask: &Buffer<bool>,
+    dst: &Buffer<T>,
+    src_strides: &[usize],
+    mask_strides: &[usize],
+    dst_strides: &[usize],
+) {
+    let rank = u32::try_from(dst_strides.len()).expect("output rank exceeds max size");
+    let len = u32::try_from(dst.len()).expect("output length exceeds max size");
+
+    let pipeline = ctx.get_or_create_pipeline(
+        TypeId::of::<MaskedCopy<T>>(),
+        MaskedCopy::<T>::wgsl,
+        MaskedCopy::<T>::LABEL,
+    );
+
+    let src_strides = ctx.create_storage_buffer(&crate::kernel::convert_strides(src_strides));
+    let mask_strides = ctx.create_storage_buffer(&crate::kernel::convert_strides(mask_strides));
+    let dst_strides = ctx.create_storage_buffer(&crate::kernel::convert_strides(dst_strides));
+    let params = ctx.create_uniform_buffer(&MaskedParams { rank, len });
+
+    let (x, y) = crate::kernel::compute_workgroups(len);
+
+    crate::kernel::dispatch(
+        ctx,
+        &pipeline,
+        MaskedCopy::<T>::LABEL,
+        &[
+            src.inner(),
+            mask.inner(),
+            dst.inner(),
+            &src_strides,
+            &mask_strides,
+            &dst_strides,
+            &params,
+        ],
+        (x, y, 1),
+    );
+}
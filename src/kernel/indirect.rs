@@ -0,0 +1,91 @@
+//! Workgroup counts for indirect dispatch.
+
+use core::any::TypeId;
+
+use alloc::format;
+use alloc::string::String;
+
+use bytemuck::{Pod, Zeroable};
+
+use crate::kernel::{Kernel, MAX_WORKGROUPS, WORKGROUP_SIZE};
+use crate::{Buffer, Context};
+
+/// Kernel parameters passed to shader as uniform.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Pod, Zeroable)]
+struct Params {
+    capacity: u32,
+    scale: u32,
+}
+
+/// Writes the workgroups covering `min(count[0], capacity) · scale` invocations.
+///
+/// The counts are split over `x` and `y` as in [`crate::kernel::compute_workgroups`].
+struct IndirectArgs;
+
+impl Kernel for IndirectArgs {
+    const LABEL: &'static str = "indirect_args";
+    type Output = u32;
+
+    fn wgsl() -> String {
+        format!(
+            r"
+                struct Params {{
+                    capacity: u32,
+                    scale: u32,
+                }}
+
+                @group(0) @binding(0) var<storage, read> count: array<u32>;
+                @group(0) @binding(1) var<storage, read_write> args: array<u32>;
+                @group(0) @binding(2) var<uniform> params: Params;
+
+                @compute @workgroup_size(1)
+                fn main() {{
+                    let len = min(count[0], params.capacity) * params.scale;
+                    let workgroups = (len + {WORKGROUP_SIZE}u - 1u) / {WORKGROUP_SIZE}u;
+
+                    args[0] = min(workgroups, {MAX_WORKGROUPS}u);
+                    args[1] = (workgroups + {MAX_WORKGROUPS}u - 1u) / {MAX_WORKGROUPS}u;
+                    args[2] = 1u;
+                }}
+            "
+        )
+    }
+}
+
+/// Computes the indirect dispatch arguments for `min(count[0], capacity) · scale` invocations
+/// of a 1D kernel, entirely on the GPU.
+///
+/// # Panics
+///
+/// - Capacity or scale exceeds max size
+pub(crate) fn workgroups(
+    ctx: &Context,
+    count: &Buffer<u32>,
+    capacity: usize,
+    scale: usize,
+) -> wgpu::Buffer {
+    let to_u32 = |x: usize| u32::try_from(x).expect("dimension exceeds max size");
+
+    let pipeline = ctx.get_or_create_pipeline(
+        TypeId::of::<IndirectArgs>(),
+        IndirectArgs::wgsl,
+        IndirectArgs::LABEL,
+    );
+
+    let args = ctx.create_indirect_buffer();
+    let params = ctx.create_uniform_buffer(&Params {
+        capacity: to_u32(capacity),
+        scale: to_u32(scale),
+    });
+
+    crate::kernel::dispatch(
+        ctx,
+        &pipeline,
+        IndirectArgs::LABEL,
+        &[count.inner(), &args, &params],
+        (1, 1, 1),
+    );
+
+    args
+}
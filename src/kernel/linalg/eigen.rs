@@ -0,0 +1,220 @@
+//! Batched symmetric eigendecomposition kernel.
+
+use core::any::TypeId;
+use core::marker::PhantomData;
+
+use alloc::format;
+use alloc::string::String;
+
+use bytemuck::{Pod, Zeroable};
+
+use crate::element::FloatElement;
+use crate::kernel::{Kernel, MAX_WORKGROUPS};
+use crate::{Buffer, Context};
+
+/// Largest matrix size handled by the Jacobi solver, one thread per row.
+pub(crate) const MAX_SIZE: usize = 32;
+
+/// Largest number of cyclic sweeps over all off-diagonal pairs.
+const MAX_SWEEPS: u32 = 16;
+
+/// Kernel parameters passed to shader as uniform.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Pod, Zeroable)]
+struct Params {
+    n: u32,
+    batch: u32,
+    _pad: [u32; 2],
+}
+
+/// Cyclic Jacobi eigensolver kernel: `A = V·diag(λ)·Vᵀ` for each symmetric matrix of a batch.
+///
+/// Each workgroup owns one matrix, held with its accumulated rotations in workgroup memory.
+/// One thread picks the rotation zeroing each off-diagonal pair `(p, q)` in turn and the
+/// threads apply it to their own row and column. A sweep that rotates nothing, every pair
+/// being negligible against its diagonal, ends the iteration early. Each thread finally ranks
+/// its eigenvalue among the others, so the eigenvalues come out ascending with their vectors
+/// as matching columns.
+struct SymmetricEigen<T>(PhantomData<T>);
+
+impl<T: FloatElement> Kernel for SymmetricEigen<T> {
+    const LABEL: &'static str = "symmetric_eigen";
+    type Output = T;
+
+    #[allow(clippy::too_many_lines)]
+    fn wgsl() -> String {
+        let ty = T::wgsl_type();
+
+        format!(
+            r"
+                const N: u32 = {MAX_SIZE}u;
+                const SWEEPS: u32 = {MAX_SWEEPS}u;
+                const EPS: {ty} = 1.1920929e-7;
+
+                struct Params {{
+                    n: u32,
+                    batch: u32,
+                    _pad0: u32,
+                    _pad1: u32,
+                }}
+
+                @group(0) @binding(0) var<storage, read> x: array<{ty}>;
+                @group(0) @binding(1) var<storage, read_write> values: array<{ty}>;
+                @group(0) @binding(2) var<storage, read_write> vectors: array<{ty}>;
+                @group(0) @binding(3) var<uniform> params: Params;
+
+                var<workgroup> a: array<{ty}, N * N>;
+                var<workgroup> v: array<{ty}, N * N>;
+                var<workgroup> rotation: vec2<{ty}>;
+                var<workgroup> rotated: u32;
+
+                @compute @workgroup_size(N)
+                fn main(
+                    @builtin(local_invocation_index) k: u32,
+                    @builtin(workgroup_id) wid: vec3<u32>
+                ) {{
+                    let matrix = wid.x + wid.y * {MAX_WORKGROUPS}u;
+                    if matrix >= params.batch {{
+                        return;
+                    }}
+
+                    let n = params.n;
+                    let base = matrix * n * n;
+                    if k < n {{
+                        for (var j = 0u; j < n; j++) {{
+                            a[k * N + j] = x[base + max(k, j) * n + min(k, j)];
+                            v[k * N + j] = select({ty}(0), {ty}(1), k == j);
+                        }}
+                    }}
+                    workgroupBarrier();
+
+                    var scale = {ty}(0);
+                    for (var i = 0u; i < n * n; i++) {{
+                        scale = max(scale, abs(a[i / n * N + i % n]));
+                    }}
+
+                    for (var sweep = 0u; sweep < SWEEPS; sweep++) {{
+                        if k == 0u {{
+                            rotated = 0u;
+                        }}
+
+                        for (var p = 0u; p + 1u < n; p++) {{
+                            for (var q = p + 1u; q < n; q++) {{
+                                if k == 0u {{
+                                    let apq = a[p * N + q];
+                                    let app = a[p * N + p];
+                                    let aqq = a[q * N + q];
+                                    var cs = vec2<{ty}>(1, 0);
+                                    if abs(apq) > EPS * max(sqrt(abs(app * aqq)), EPS * scale) {{
+                                        let theta = (aqq - app) / (2 * apq);
+                                        let sign = select({ty}(-1), {ty}(1), theta >= 0);
+                                        let t = sign / (abs(theta) + sqrt(theta * theta + 1));
+                                        let c = 1 / sqrt(t * t + 1);
+                                        cs = vec2<{ty}>(c, t * c);
+                                        rotated = 1u;
+                                    }}
+                                    rotation = cs;
+                                }}
+
+                                let cs = workgroupUniformLoad(&rotation);
+                                if cs.y == 0 {{
+                                    continue;
+                                }}
+
+                                let c = cs.x;
+                                let s = cs.y;
+                                if k < n {{
+                                    let akp = a[k * N + p];
+                                    let akq = a[k * N + q];
+                                    a[k * N + p] = c * akp - s * akq;
+                                    a[k * N + q] = s * akp + c * akq;
+                                    let vkp = v[k * N + p];
+                                    let vkq = v[k * N + q];
+                                    v[k * N + p] = c * vkp - s * vkq;
+                                    v[k * N + q] = s * vkp + c * vkq;
+                                }}
+                                workgroupBarrier();
+
+                                if k < n {{
+                                    let apk = a[p * N + k];
+                                    let aqk = a[q * N + k];
+                                    a[p * N + k] = c * apk - s * aqk;
+                                    a[q * N + k] = s * apk + c * aqk;
+                                }}
+                                workgroupBarrier();
+
+                                if k == 0u {{
+                                    a[p * N + q] = {ty}(0);
+                                    a[q * N + p] = {ty}(0);
+                                }}
+                            }}
+                        }}
+
+                        if workgroupUniformLoad(&rotated) == 0u {{
+                            break;
+                        }}
+                    }}
+                    workgroupBarrier();
+
+                    if k < n {{
+                        let value = a[k * N + k];
+                        var rank = 0u;
+                        for (var j = 0u; j < n; j++) {{
+                            let other = a[j * N + j];
+                            if other < value || (other == value && j < k) {{
+                                rank += 1u;
+                            }}
+                        }}
+
+                        values[matrix * n + rank] = value;
+                        for (var row = 0u; row < n; row++) {{
+                            vectors[base + row * n + rank] = v[row * N + k];
+                        }}
+                    }}
+                }}
+            "
+        )
+    }
+}
+
+/// Decomposes each symmetric `n × n` matrix of contiguous `x`, reading its lower triangle,
+/// into ascending eigenvalues in rows of `values` and eigenvectors in matching columns of
+/// `vectors`.
+///
+/// # Panics
+///
+/// - Batch size exceeds max size
+pub(crate) fn execute<T: FloatElement>(
+    ctx: &Context,
+    x: &Buffer<T>,
+    values: &Buffer<T>,
+    vectors: &Buffer<T>,
+    n: usize,
+) {
+    if n == 0 {
+        return;
+    }
+    let batch = u32::try_from(x.len() / (n * n)).expect("batch size exceeds max size");
+    if batch == 0 {
+        return;
+    }
+
+    let pipeline = ctx.get_or_create_pipeline(
+        TypeId::of::<SymmetricEigen<T>>(),
+        SymmetricEigen::<T>::wgsl,
+        SymmetricEigen::<T>::LABEL,
+    );
+    let params = ctx.create_uniform_buffer(&Params {
+        n: u32::try_from(n).expect("matrix size exceeds max size"),
+        batch,
+        _pad: [0; 2],
+    });
+
+    crate::kernel::dispatch(
+        ctx,
+        &pipeline,
+        SymmetricEigen::<T>::LABEL,
+        &[x.inner(), values.inner(), vectors.inner(), &params],
+        (batch.min(MAX_WORKGROUPS), batch.div_ceil(MAX_WORKGROUPS), 1),
+    );
+}
@@ -10,12 +10,10 @@ use alloc::{format, vec};
 use bytemuck::{Pod, Zeroable};
 
 use crate::element::FloatElement;
+use crate::kernel::linalg::small;
 use crate::kernel::{Kernel, MAX_WORKGROUPS};
 use crate::{Buffer, Context};
 
-/// Maximum batch dimensions supported.
-const MAX_BATCH_RANK: usize = 6;
-
 /// Block size for register tiling (each thread computes BM×BN elements).
 const BLOCK_SIZE: u32 = 4;
 
@@ -43,14 +41,13 @@ struct Params {
     batch_rank: u32,
     transpose_a: u32,
     transpose_b: u32,
-    _pad: u32,
-    batch_dims: [[u32; 4]; 2],
-    a_batch_strides: [[u32; 4]; 2],
-    b_batch_strides: [[u32; 4]; 2],
+    parts: u32,
     a_matrix_stride: u32,
     b_matrix_stride: u32,
     c_matrix_stride: u32,
-    _pad2: u32,
+    part_stride: u32,
+    scaled: u32,
+    _pad: [u32; 3],
 }
 
 /// Batched matrix multiplication kernel: `C = A × B`.
@@ -74,7 +71,6 @@ impl<T: FloatElement> Kernel for Matmul<T> {
                 const TILE_K_PAD: u32 = {TILE_K_PAD}u;
                 const WG: u32 = {WG_SIZE}u;
                 const BLK: u32 = {BLOCK_SIZE}u;
-                const MAX_BATCH: u32 = {MAX_BATCH_RANK}u;
 
                 struct Params {{
                     m: u32,
@@ -84,52 +80,59 @@ impl<T: FloatElement> Kernel for Matmul<T> {
                     batch_rank: u32,
                     transpose_a: u32,
                     transpose_b: u32,
-                    _pad: u32,
-                    batch_dims: array<vec4<u32>, 2>,
-                    a_batch_strides: array<vec4<u32>, 2>,
-                    b_batch_strides: array<vec4<u32>, 2>,
+                    parts: u32,
                     a_matrix_stride: u32,
                     b_matrix_stride: u32,
                     c_matrix_stride: u32,
-                    _pad2: u32,
+                    part_stride: u32,
+                    scaled: u32,
                 }}
 
                 @group(0) @binding(0) var<storage, read> a: array<{ty}>;
                 @group(0) @binding(1) var<storage, read> b: array<{ty}>;
                 @group(0) @binding(2) var<storage, read_write> c: array<{ty}>;
                 @group(0) @binding(3) var<uniform> params: Params;
+                @group(0) @binding(4) var<storage, read> alpha: array<{ty}>;
+                @group(0) @binding(5) var<storage, read> beta: array<{ty}>;
+                @group(0) @binding(6) var<storage, read> batch_dims: array<u32>;
+                @group(0) @binding(7) var<storage, read> a_batch_strides: array<u32>;
+                @group(0) @binding(8) var<storage, read> b_batch_strides: array<u32>;
 
                 var<workgroup> As: array<{ty}, {as_size}>;
                 var<workgroup> Bs: array<{ty}, {bs_size}>;
 
-                fn get_batch_dim(idx: u32) -> u32 {{
-                    return params.batch_dims[idx / 4u][idx % 4u];
+                fn c_index(batch_offset: u32, row: u32, col: u32) -> u32 {{
+                    let part_n = params.n / params.parts;
+                    let part = col / part_n;
+                    return part * params.part_stride + batch_offset + row * part_n + col % part_n;
                 }}
 
-                fn get_a_batch_stride(idx: u32) -> u32 {{
-                    return params.a_batch_strides[idx / 4u][idx % 4u];
-                }}
+                fn store(batch_idx: u32, batch_offset: u32, row: u32, col: u32, value: {ty}) {{
+                    let idx = c_index(batch_offset, row, col);
+                    if params.scaled == 0u {{
+                        c[idx] = value;
+                        return;
+                    }}
 
-                fn get_b_batch_stride(idx: u32) -> u32 {{
-                    return params.b_batch_strides[idx / 4u][idx % 4u];
+                    var out = alpha[batch_idx] * value;
+                    if beta[batch_idx] != 0.0 {{
+                        out += beta[batch_idx] * c[idx];
+                    }}
+                    c[idx] = out;
                 }}
 
                 fn compute_batch_offset(batch_idx: u32, is_a: bool) -> u32 {{
                     var offset = 0u;
                     var remaining = batch_idx;
 
-                    for (var i = 0u; i < params.batch_rank; i++) {{
-                        var prod = 1u;
-                        for (var j = i + 1u; j < params.batch_rank; j++) {{
-                            prod *= get_batch_dim(j);
-                        }}
-                        let coord = remaining / prod;
-                        remaining = remaining % prod;
+                    for (var i = params.batch_rank; i > 0u; i--) {{
+                        let coord = remaining % batch_dims[i - 1u];
+                        remaining = remaining / batch_dims[i - 1u];
 
                         if is_a {{
-                            offset += coord * get_a_batch_stride(i);
+                            offset += coord * a_batch_strides[i - 1u];
                         }} else {{
-                            offset += coord * get_b_batch_stride(i);
+                            offset += coord * b_batch_strides[i - 1u];
                         }}
                     }}
 
@@ -233,25 +236,25 @@ impl<T: FloatElement> Kernel for Matmul<T> {
                         workgroupBarrier();
                     }}
 
-                    if c_row < M && c_col < N {{ c[c_batch_offset + c_row * N + c_col] = acc00; }}
-                    if c_row < M && c_col + 1u < N {{ c[c_batch_offset + c_row * N + c_col + 1u] = acc01; }}
-                    if c_row < M && c_col + 2u < N {{ c[c_batch_offset + c_row * N + c_col + 2u] = acc02; }}
-                    if c_row < M && c_col + 3u < N {{ c[c_batch_offset + c_row * N + c_col + 3u] = acc03; }}
-
-                    if c_row + 1u < M && c_col < N {{ c[c_batch_offset + (c_row + 1u) * N + c_col] = acc10; }}
-                    if c_row + 1u < M && c_col + 1u < N {{ c[c_batch_offset + (c_row + 1u) * N + c_col + 1u] = acc11; }}
-                    if c_row + 1u < M && c_col + 2u < N {{ c[c_batch_offset + (c_row + 1u) * N + c_col + 2u] = acc12; }}
-                    if c_row + 1u < M && c_col + 3u < N {{ c[c_batch_offset + (c_row + 1u) * N + c_col + 3u] = acc13; }}
-
-                    if c_row + 2u < M && c_col < N {{ c[c_batch_offset + (c_row + 2u) * N + c_col] = acc20; }}
-                    if c_row + 2u < M && c_col + 1u < N {{ c[c_batch_offset + (c_row + 2u) * N + c_col + 1u] = acc21; }}
-                    if c_row + 2u < M && c_col + 2u < N {{ c[c_batch_offset + (c_row + 2u) * N + c_col + 2u] = acc22; }}
-                    if c_row + 2u < M && c_col + 3u < N {{ c[c_batch_offset + (c_row + 2u) * N + c_col + 3u] = acc23; }}
-
-                    if c_row + 3u < M && c_col < N {{ c[c_batch_offset + (c_row + 3u) * N + c_col] = acc30; }}
-                    if c_row + 3u < M && c_col + 1u < N {{ c[c_batch_offset + (c_row + 3u) * N + c_col + 1u] = acc31; }}
-                    if c_row + 3u < M && c_col + 2u < N {{ c[c_batch_offset + (c_row + 3u) * N + c_col + 2u] = acc32; }}
-                    if c_row + 3u < M && c_col + 3u < N {{ c[c_batch_offset + (c_row + 3u) * N + c_col + 3u] = acc33; }}
+                    if c_row < M && c_col < N {{ store(batch_idx, c_batch_offset, c_row, c_col, acc00); }}
+                    if c_row < M && c_col + 1u < N {{ store(batch_idx, c_batch_offset, c_row, c_col + 1u, acc01); }}
+                    if c_row < M && c_col + 2u < N {{ store(batch_idx, c_batch_offset, c_row, c_col + 2u, acc02); }}
+                    if c_row < M && c_col + 3u < N {{ store(batch_idx, c_batch_offset, c_row, c_col + 3u, acc03); }}
+
+                    if c_row + 1u < M && c_col < N {{ store(batch_idx, c_batch_offset, (c_row + 1u), c_col, acc10); }}
+                    if c_row + 1u < M && c_col + 1u < N {{ store(batch_idx, c_batch_offset, (c_row + 1u), c_col + 1u, acc11); }}
+                    if c_row + 1u < M && c_col + 2u < N {{ store(batch_idx, c_batch_offset, (c_row + 1u), c_col + 2u, acc12); }}
+                    if c_row + 1u < M && c_col + 3u < N {{ store(batch_idx, c_batch_offset, (c_row + 1u), c_col + 3u, acc13); }}
+
+                    if c_row + 2u < M && c_col < N {{ store(batch_idx, c_batch_offset, (c_row + 2u), c_col, acc20); }}
+                    if c_row + 2u < M && c_col + 1u < N {{ store(batch_idx, c_batch_offset, (c_row + 2u), c_col + 1u, acc21); }}
+                    if c_row + 2u < M && c_col + 2u < N {{ store(batch_idx, c_batch_offset, (c_row + 2u), c_col + 2u, acc22); }}
+                    if c_row + 2u < M && c_col + 3u < N {{ store(batch_idx, c_batch_offset, (c_row + 2u), c_col + 3u, acc23); }}
+
+                    if c_row + 3u < M && c_col < N {{ store(batch_idx, c_batch_offset, (c_row + 3u), c_col, acc30); }}
+                    if c_row + 3u < M && c_col + 1u < N {{ store(batch_idx, c_batch_offset, (c_row + 3u), c_col + 1u, acc31); }}
+                    if c_row + 3u < M && c_col + 2u < N {{ store(batch_idx, c_batch_offset, (c_row + 3u), c_col + 2u, acc32); }}
+                    if c_row + 3u < M && c_col + 3u < N {{ store(batch_idx, c_batch_offset, (c_row + 3u), c_col + 3u, acc33); }}
                 }}
             "
         )
@@ -260,11 +263,20 @@ impl<T: FloatElement> Kernel for Matmul<T> {
 
 /// Batched matrix multiplication: `C = A × B`.
 ///
+/// With `scale = Some((alpha, beta))`, each batch `i` instead computes
+/// `C = alpha[i] · A × B + beta[i] · C`, where `C` is not read when `beta[i]` is zero.
+///
+/// Matrices no larger than [`small::MAX_SIZE`] in every dimension take the one-thread-per-matrix
+/// path of [`small::execute`] instead of the tiled kernel.
+///
+/// With `parts > 1`, the output columns are split into `parts` equal groups and each group is
+/// written as a separate matrix, giving a packed `[parts, ..., m, n / parts]` output.
+///
 /// # Panics
 ///
-/// - Batch rank exceeds maximum supported
 /// - Matrix dimensions exceed workgroup limits
 /// - Output buffer too small
+/// - Output columns not divisible by `parts`
 #[allow(clippy::too_many_lines)]
 pub(crate) fn execute<T: FloatElement>(
     ctx: &Context,
@@ -276,12 +288,12 @@ pub(crate) fn execute<T: FloatElement>(
     c_dims: &[usize],
     transpose_a: bool,
     transpose_b: bool,
+    parts: usize,
+    scale: Option<(&Buffer<T>, &Buffer<T>)>,
 ) {
     let rank = a_dims.len();
     let batch_rank = rank.saturating_sub(2);
 
-    assert!(batch_rank <= MAX_BATCH_RANK, "batch rank exceeds maximum");
-
     let (a_rows, a_cols) = matrix_dims(a_dims);
     let (b_rows, b_cols) = matrix_dims(b_dims);
 
@@ -296,6 +308,11 @@ pub(crate) fn execute<T: FloatElement>(
         return;
     }
 
+    assert!(
+        parts > 0 && n.is_multiple_of(parts),
+        "output columns not divisible by parts"
+    );
+
     let batch_size: usize = c_dims[..batch_rank].iter().product::<usize>().max(1);
     let out_len = batch_size * m * n;
 
@@ -304,6 +321,30 @@ pub(crate) fn execute<T: FloatElement>(
         "output buffer too small"
     );
 
+    let (a_batch_strides, b_batch_strides) = compute_batch_strides(
+        &a_dims[..batch_rank],
+        &b_dims[..batch_rank],
+        &c_dims[..batch_rank],
+    );
+
+    if m.max(k).max(n) <= small::MAX_SIZE {
+        small::execute(
+            ctx,
+            a,
+            b,
+            c,
+            (m, k, n),
+            &c_dims[..batch_rank],
+            &a_batch_strides,
+            &b_batch_strides,
+            transpose_a,
+            transpose_b,
+            parts,
+            scale,
+        );
+        return;
+    }
+
     let m_tiles = u32::try_from(m)
         .expect("m dimension exceeds max size")
         .div_ceil(TILE_SIZE);
@@ -316,35 +357,8 @@ pub(crate) fn execute<T: FloatElement>(
         "matrix dimensions exceed workgroup limits"
     );
 
-    let (a_batch_strides, b_batch_strides) = if batch_rank > 0 {
-        compute_batch_strides(
-            &a_dims[..batch_rank],
-            &b_dims[..batch_rank],
-            &c_dims[..batch_rank],
-        )
-    } else {
-        (vec![0; MAX_BATCH_RANK], vec![0; MAX_BATCH_RANK])
-    };
-
     let to_u32 = |x: usize| u32::try_from(x).expect("dimension exceeds max size");
 
-    let mut batch_dims_arr = [[0u32; 4]; 2];
-    let mut a_strides_arr = [[0u32; 4]; 2];
-    let mut b_strides_arr = [[0u32; 4]; 2];
-
-    fill_packed(
-        &mut batch_dims_arr,
-        c_dims[..batch_rank].iter().map(|&d| to_u32(d)),
-    );
-    fill_packed(
-        &mut a_strides_arr,
-        a_batch_strides.iter().map(|&s| to_u32(s)),
-    );
-    fill_packed(
-        &mut b_strides_arr,
-        b_batch_strides.iter().map(|&s| to_u32(s)),
-    );
-
     let params = Params {
         m: to_u32(m),
         k: to_u32(k),
@@ -353,14 +367,13 @@ pub(crate) fn execute<T: FloatElement>(
         batch_rank: to_u32(batch_rank),
         transpose_a: u32::from(transpose_a),
         transpose_b: u32::from(transpose_b),
-        _pad: 0,
-        batch_dims: batch_dims_arr,
-        a_batch_strides: a_strides_arr,
-        b_batch_strides: b_strides_arr,
+        parts: to_u32(parts),
         a_matrix_stride: to_u32(a_rows * a_cols),
         b_matrix_stride: to_u32(b_rows * b_cols),
-        c_matrix_stride: to_u32(m * n),
-        _pad2: 0,
+        c_matrix_stride: to_u32(m * n / parts),
+        part_stride: to_u32(out_len / parts),
+        scaled: u32::from(scale.is_some()),
+        _pad: [0; 3],
     };
 
     let batch_size = params.batch_size;
@@ -374,6 +387,21 @@ pub(crate) fn execute<T: FloatElement>(
         Matmul::<T>::LABEL,
     );
 
+    let unscaled;
+    let (alpha, beta) = if let Some((alpha, beta)) = scale {
+        (alpha.inner(), beta.inner())
+    } else {
+        unscaled = ctx.create_storage_buffer(&[T::zeroed().to_native()]);
+        (&unscaled, &unscaled)
+    };
+
+    let batch_dims =
+        ctx.create_storage_buffer(&crate::kernel::convert_strides(&c_dims[..batch_rank]));
+    let a_batch_strides =
+        ctx.create_storage_buffer(&crate::kernel::convert_strides(&a_batch_strides));
+    let b_batch_strides =
+        ctx.create_storage_buffer(&crate::kernel::convert_strides(&b_batch_strides));
+
     let create_bind_group = |params_buf: &wgpu::Buffer| {
         ctx.device().create_bind_group(&wgpu::BindGroupDescriptor {
             label: Some(Matmul::<T>::LABEL),
@@ -395,27 +423,41 @@ pub(crate) fn execute<T: FloatElement>(
                     binding: 3,
                     resource: params_buf.as_entire_binding(),
                 },
+                wgpu::BindGroupEntry {
+                    binding: 4,
+                    resource: alpha.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 5,
+                    resource: beta.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 6,
+                    resource: batch_dims.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 7,
+                    resource: a_batch_strides.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 8,
+                    resource: b_batch_strides.as_entire_binding(),
+                },
             ],
         })
     };
 
-    let mut encoder = ctx
-        .device()
-        .create_command_encoder(&wgpu::CommandEncoderDescriptor {
-            label: Some(Matmul::<T>::LABEL),
-        });
-
     if batch_size <= MAX_WORKGROUPS {
         let params_buffer = ctx.create_uniform_buffer(&params);
         let bind_group = create_bind_group(&params_buffer);
 
-        let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
-            label: Some(Matmul::<T>::LABEL),
-            ..Default::default()
-        });
-        pass.set_pipeline(&pipeline);
-        pass.set_bind_group(0, &bind_group, &[]);
-        pass.dispatch_workgroups(m_tiles, n_tiles, batch_size);
+        crate::kernel::dispatch_bind_group(
+            ctx,
+            &pipeline,
+            Matmul::<T>::LABEL,
+            &bind_group,
+            (m_tiles, n_tiles, batch_size),
+        );
     } else {
         let num_dispatches = batch_size.div_ceil(MAX_WORKGROUPS);
 
@@ -428,17 +470,15 @@ pub(crate) fn execute<T: FloatElement>(
             let params_buffer = ctx.create_uniform_buffer(&dispatch_params);
             let bind_group = create_bind_group(&params_buffer);
 
-            let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
-                label: Some(Matmul::<T>::LABEL),
-                ..Default::default()
-            });
-            pass.set_pipeline(&pipeline);
-            pass.set_bind_group(0, &bind_group, &[]);
-            pass.dispatch_workgroups(m_tiles, n_tiles, batch_count);
+            crate::kernel::dispatch_bind_group(
+                ctx,
+                &pipeline,
+                Matmul::<T>::LABEL,
+                &bind_group,
+                (m_tiles, n_tiles, batch_count),
+            );
         }
     }
-
-    ctx.queue().submit(Some(encoder.finish()));
 }
 
 /// Extracts matrix dimensions (rows, cols) from tensor shape.
@@ -450,14 +490,8 @@ fn matrix_dims(dims: &[usize]) -> (usize, usize) {
     }
 }
 
-/// Fills packed array from iterator.
-fn fill_packed(arr: &mut [[u32; 4]; 2], iter: impl Iterator<Item = u32>) {
-    for (i, v) in iter.enumerate() {
-        arr[i / 4][i % 4] = v;
-    }
-}
-
-fn compute_batch_strides(
+/// Computes per-batch broadcast strides (in matrices) for `A` and `B`.
+pub(crate) fn compute_batch_strides(
     a_batch: &[usize],
     b_batch: &[usize],
     out_batch: &[usize],
@@ -1,3 +1,6 @@
 //! Linear algebra kernels.
 
+pub(crate) mod eigen;
 pub(crate) mod matmul;
+pub(crate) mod small;
+pub(crate) mod vector;
@@ -0,0 +1,243 @@
+//! Batched small-matrix multiplication kernel.
+
+use core::any::TypeId;
+use core::marker::PhantomData;
+
+use alloc::format;
+use alloc::string::String;
+
+use bytemuck::{Pod, Zeroable};
+
+use crate::element::FloatElement;
+use crate::kernel::{Kernel, MAX_WORKGROUPS, WORKGROUP_SIZE};
+use crate::{Buffer, Context};
+
+/// Largest `m`, `k` and `n` handled by the small-matrix path.
+pub(crate) const MAX_SIZE: usize = 8;
+
+/// Register capacity of one operand (`MAX_SIZE * MAX_SIZE`).
+const REGISTERS: usize = MAX_SIZE * MAX_SIZE;
+
+/// Kernel parameters passed to shader as uniform.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Pod, Zeroable)]
+struct Params {
+    m: u32,
+    k: u32,
+    n: u32,
+    batch_size: u32,
+    batch_rank: u32,
+    transpose_a: u32,
+    transpose_b: u32,
+    parts: u32,
+    a_matrix_stride: u32,
+    b_matrix_stride: u32,
+    c_matrix_stride: u32,
+    part_stride: u32,
+    scaled: u32,
+    _pad: [u32; 3],
+}
+
+/// Small-matrix multiplication kernel: `C = A × B` with one thread per matrix.
+///
+/// Each thread loads its `A` and `B` into private arrays and writes the whole of its `C`, so
+/// thousands of 4×4 or 8×8 products run without workgroup memory or barriers.
+struct SmallMatmul<T>(PhantomData<T>);
+
+impl<T: FloatElement> Kernel for SmallMatmul<T> {
+    const LABEL: &'static str = "small_matmul";
+    type Output = T;
+
+    fn wgsl() -> String {
+        let ty = T::wgsl_type();
+
+        format!(
+            r"
+                struct Params {{
+                    m: u32,
+                    k: u32,
+                    n: u32,
+                    batch_size: u32,
+                    batch_rank: u32,
+                    transpose_a: u32,
+                    transpose_b: u32,
+                    parts: u32,
+                    a_matrix_stride: u32,
+                    b_matrix_stride: u32,
+                    c_matrix_stride: u32,
+                    part_stride: u32,
+                    scaled: u32,
+                }}
+
+                @group(0) @binding(0) var<storage, read> a: array<{ty}>;
+                @group(0) @binding(1) var<storage, read> b: array<{ty}>;
+                @group(0) @binding(2) var<storage, read_write> c: array<{ty}>;
+                @group(0) @binding(3) var<storage, read> batch_dims: array<u32>;
+                @group(0) @binding(4) var<storage, read> a_batch_strides: array<u32>;
+                @group(0) @binding(5) var<storage, read> b_batch_strides: array<u32>;
+                @group(0) @binding(6) var<uniform> params: Params;
+                @group(0) @binding(7) var<storage, read> alpha: array<{ty}>;
+                @group(0) @binding(8) var<storage, read> beta: array<{ty}>;
+
+                @compute @workgroup_size({WORKGROUP_SIZE})
+                fn main(@builtin(global_invocation_id) gid: vec3<u32>) {{
+                    let batch = gid.x + gid.y * {MAX_WORKGROUPS}u * {WORKGROUP_SIZE}u;
+
+                    if batch >= params.batch_size {{
+                        return;
+                    }}
+
+                    let M = params.m;
+                    let K = params.k;
+                    let N = params.n;
+
+                    var remaining = batch;
+                    var a_offset = 0u;
+                    var b_offset = 0u;
+                    for (var i = params.batch_rank; i > 0u; i--) {{
+                        let coord = remaining % batch_dims[i - 1u];
+                        remaining = remaining / batch_dims[i - 1u];
+                        a_offset += coord * a_batch_strides[i - 1u];
+                        b_offset += coord * b_batch_strides[i - 1u];
+                    }}
+                    a_offset *= params.a_matrix_stride;
+                    b_offset *= params.b_matrix_stride;
+
+                    var a_reg: array<{ty}, {REGISTERS}>;
+                    var b_reg: array<{ty}, {REGISTERS}>;
+
+                    for (var i = 0u; i < M; i++) {{
+                        for (var kk = 0u; kk < K; kk++) {{
+                            let idx = select(i * K + kk, kk * M + i, params.transpose_a != 0u);
+                            a_reg[i * {MAX_SIZE}u + kk] = a[a_offset + idx];
+                        }}
+                    }}
+
+                    for (var kk = 0u; kk < K; kk++) {{
+                        for (var j = 0u; j < N; j++) {{
+                            let idx = select(kk * N + j, j * K + kk, params.transpose_b != 0u);
+                            b_reg[kk * {MAX_SIZE}u + j] = b[b_offset + idx];
+                        }}
+                    }}
+
+                    let part_n = N / params.parts;
+                    let c_offset = batch * params.c_matrix_stride;
+
+                    for (var i = 0u; i < M; i++) {{
+                        for (var j = 0u; j < N; j++) {{
+                            var acc: {ty} = 0.0;
+                            for (var kk = 0u; kk < K; kk++) {{
+                                acc += a_reg[i * {MAX_SIZE}u + kk] * b_reg[kk * {MAX_SIZE}u + j];
+                            }}
+
+                            let part = j / part_n;
+                            let idx = part * params.part_stride + c_offset + i * part_n + j % part_n;
+                            if params.scaled != 0u {{
+                                acc *= alpha[batch];
+                                if beta[batch] != 0.0 {{
+                                    acc += beta[batch] * c[idx];
+                                }}
+                            }}
+                            c[idx] = acc;
+                        }}
+                    }}
+                }}
+            "
+        )
+    }
+}
+
+/// Batched multiplication of matrices with `m`, `k` and `n` at most [`MAX_SIZE`].
+///
+/// `batch_dims` are the output batch dimensions and `a_batch_strides`/`b_batch_strides` the
+/// broadcast strides of each operand in matrices. Output columns are split into `parts`
+/// groups and `scale` applied as in [`super::matmul::execute`].
+///
+/// # Panics
+///
+/// - Matrix dimensions exceed [`MAX_SIZE`]
+/// - Batch size exceeds max size
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn execute<T: FloatElement>(
+    ctx: &Context,
+    a: &Buffer<T>,
+    b: &Buffer<T>,
+    c: &Buffer<T>,
+    (m, k, n): (usize, usize, usize),
+    batch_dims: &[usize],
+    a_batch_strides: &[usize],
+    b_batch_strides: &[usize],
+    transpose_a: bool,
+    transpose_b: bool,
+    parts: usize,
+    scale: Option<(&Buffer<T>, &Buffer<T>)>,
+) {
+    assert!(
+        m <= MAX_SIZE && k <= MAX_SIZE && n <= MAX_SIZE,
+        "matrix dimensions exceed small-matrix limit"
+    );
+
+    let to_u32 = |x: usize| u32::try_from(x).expect("dimension exceeds max size");
+
+    let batch_size = to_u32(batch_dims.iter().product::<usize>());
+    if batch_size == 0 {
+        return;
+    }
+
+    let params = ctx.create_uniform_buffer(&Params {
+        m: to_u32(m),
+        k: to_u32(k),
+        n: to_u32(n),
+        batch_size,
+        batch_rank: to_u32(batch_dims.len()),
+        transpose_a: u32::from(transpose_a),
+        transpose_b: u32::from(transpose_b),
+        parts: to_u32(parts),
+        a_matrix_stride: to_u32(m * k),
+        b_matrix_stride: to_u32(k * n),
+        c_matrix_stride: to_u32(m * n / parts),
+        part_stride: to_u32(m * n / parts) * batch_size,
+        scaled: u32::from(scale.is_some()),
+        _pad: [0; 3],
+    });
+
+    let pipeline = ctx.get_or_create_pipeline(
+        TypeId::of::<SmallMatmul<T>>(),
+        SmallMatmul::<T>::wgsl,
+        SmallMatmul::<T>::LABEL,
+    );
+
+    let batch_dims = ctx.create_storage_buffer(&crate::kernel::convert_strides(batch_dims));
+    let a_batch_strides =
+        ctx.create_storage_buffer(&crate::kernel::convert_strides(a_batch_strides));
+    let b_batch_strides =
+        ctx.create_storage_buffer(&crate::kernel::convert_strides(b_batch_strides));
+
+    let unscaled;
+    let (alpha, beta) = if let Some((alpha, beta)) = scale {
+        (alpha.inner(), beta.inner())
+    } else {
+        unscaled = ctx.create_storage_buffer(&[T::zeroed().to_native()]);
+        (&unscaled, &unscaled)
+    };
+
+    let (x, y) = crate::kernel::compute_workgroups(batch_size);
+
+    crate::kernel::dispatch(
+        ctx,
+        &pipeline,
+        SmallMatmul::<T>::LABEL,
+        &[
+            a.inner(),
+            b.inner(),
+            c.inner(),
+            &batch_dims,
+            &a_batch_strides,
+            &b_batch_strides,
+            &params,
+            alpha,
+            beta,
+        ],
+        (x, y, 1),
+    );
+}
@@ -0,0 +1,219 @@
+//! Vector kernels: chunked dot products and scaled accumulation.
+
+use core::any::TypeId;
+use core::marker::PhantomData;
+
+use alloc::format;
+use alloc::string::String;
+
+use bytemuck::{Pod, Zeroable};
+
+use crate::element::FloatElement;
+use crate::kernel::reduction::full::CHUNK;
+use crate::kernel::{Kernel, MAX_WORKGROUPS, WORKGROUP_SIZE};
+use crate::{Buffer, Context};
+
+/// Dot kernel parameters passed to shader as uniform.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Pod, Zeroable)]
+struct DotParams {
+    len: u32,
+    chunks: u32,
+    _pad: [u32; 2],
+}
+
+/// Axpy kernel parameters passed to shader as uniform.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Pod, Zeroable)]
+struct AxpyParams {
+    len: u32,
+    scale: f32,
+    _pad: [u32; 2],
+}
+
+/// Chunked dot product kernel: `partial[c] = Σ x[i]·y[i]` over chunk `c` of `CHUNK`
+/// consecutive elements.
+///
+/// Each workgroup owns one chunk, the last one possibly short, and merges the per-thread sums
+/// in a tree. The partial sums are reduced further like the first pass of a full reduction.
+struct Dot<T>(PhantomData<T>);
+
+impl<T: FloatElement> Kernel for Dot<T> {
+    const LABEL: &'static str = "dot";
+    type Output = T;
+
+    fn wgsl() -> String {
+        let ty = T::wgsl_type();
+
+        format!(
+            r"
+                const WG_SIZE: u32 = {WORKGROUP_SIZE}u;
+                const CHUNK: u32 = {CHUNK}u;
+
+                struct Params {{
+                    len: u32,
+                    chunks: u32,
+                    _pad0: u32,
+                    _pad1: u32,
+                }}
+
+                @group(0) @binding(0) var<storage, read> x: array<{ty}>;
+                @group(0) @binding(1) var<storage, read> y: array<{ty}>;
+                @group(0) @binding(2) var<storage, read_write> partial: array<{ty}>;
+                @group(0) @binding(3) var<uniform> params: Params;
+
+                var<workgroup> sdata: array<{ty}, WG_SIZE>;
+
+                @compute @workgroup_size(WG_SIZE)
+                fn main(
+                    @builtin(local_invocation_id) lid: vec3<u32>,
+                    @builtin(workgroup_id) wid: vec3<u32>
+                ) {{
+                    let tid = lid.x;
+                    let chunk = wid.x + wid.y * {MAX_WORKGROUPS}u;
+
+                    if chunk >= params.chunks {{
+                        return;
+                    }}
+
+                    let start = chunk * CHUNK;
+                    let end = min(start + CHUNK, params.len);
+                    var acc = {ty}(0);
+                    for (var i = start + tid; i < end; i += WG_SIZE) {{
+                        acc += x[i] * y[i];
+                    }}
+
+                    sdata[tid] = acc;
+
+                    for (var s = WG_SIZE / 2u; s > 0u; s >>= 1u) {{
+                        workgroupBarrier();
+                        if tid < s {{
+                            sdata[tid] += sdata[tid + s];
+                        }}
+                    }}
+
+                    if tid == 0u {{
+                        partial[chunk] = sdata[0];
+                    }}
+                }}
+            "
+        )
+    }
+}
+
+/// Scaled accumulation kernel: `y[i] += scale·alpha[0]·x[i]`.
+///
+/// The coefficient `alpha` is read from a one-element buffer, so a scalar computed on the GPU
+/// scales the update without being read back.
+struct Axpy<T>(PhantomData<T>);
+
+impl<T: FloatElement> Kernel for Axpy<T> {
+    const LABEL: &'static str = "axpy";
+    type Output = T;
+
+    fn wgsl() -> String {
+        let ty = T::wgsl_type();
+
+        format!(
+            r"
+                struct Params {{
+                    len: u32,
+                    scale: f32,
+                    _pad0: u32,
+                    _pad1: u32,
+                }}
+
+                @group(0) @binding(0) var<storage, read> alpha: array<{ty}>;
+                @group(0) @binding(1) var<storage, read> x: array<{ty}>;
+                @group(0) @binding(2) var<storage, read_write> y: array<{ty}>;
+                @group(0) @binding(3) var<uniform> params: Params;
+
+                @compute @workgroup_size({WORKGROUP_SIZE})
+                fn main(@builtin(global_invocation_id) gid: vec3<u32>) {{
+                    let tid = gid.x + gid.y * {MAX_WORKGROUPS}u * {WORKGROUP_SIZE}u;
+
+                    if tid >= params.len {{
+                        return;
+                    }}
+
+                    y[tid] += {ty}(params.scale) * alpha[0] * x[tid];
+                }}
+            "
+        )
+    }
+}
+
+/// Computes the dot product of each chunk of `CHUNK` consecutive elements of `x` and `y` into
+/// `partial`, which holds `x.len().div_ceil(CHUNK)` elements.
+///
+/// # Panics
+///
+/// - Vector length exceeds max size
+pub(crate) fn dot<T: FloatElement>(
+    ctx: &Context,
+    x: &Buffer<T>,
+    y: &Buffer<T>,
+    partial: &Buffer<T>,
+) {
+    let len = u32::try_from(x.len()).expect("vector length exceeds max size");
+    let chunks = u32::try_from(x.len().div_ceil(CHUNK)).expect("vector length exceeds max size");
+    if chunks == 0 {
+        return;
+    }
+
+    let pipeline =
+        ctx.get_or_create_pipeline(TypeId::of::<Dot<T>>(), Dot::<T>::wgsl, Dot::<T>::LABEL);
+    let params = ctx.create_uniform_buffer(&DotParams {
+        len,
+        chunks,
+        _pad: [0; 2],
+    });
+
+    crate::kernel::dispatch(
+        ctx,
+        &pipeline,
+        Dot::<T>::LABEL,
+        &[x.inner(), y.inner(), partial.inner(), &params],
+        (
+            chunks.min(MAX_WORKGROUPS),
+            chunks.div_ceil(MAX_WORKGROUPS),
+            1,
+        ),
+    );
+}
+
+/// Adds `scale·alpha[0]·x` to `y` in place.
+///
+/// # Panics
+///
+/// - Vector length exceeds max size
+pub(crate) fn axpy<T: FloatElement>(
+    ctx: &Context,
+    alpha: &Buffer<T>,
+    scale: f32,
+    x: &Buffer<T>,
+    y: &Buffer<T>,
+) {
+    let len = u32::try_from(y.len()).expect("vector length exceeds max size");
+    if len == 0 {
+        return;
+    }
+
+    let pipeline =
+        ctx.get_or_create_pipeline(TypeId::of::<Axpy<T>>(), Axpy::<T>::wgsl, Axpy::<T>::LABEL);
+    let params = ctx.create_uniform_buffer(&AxpyParams {
+        len,
+        scale,
+        _pad: [0; 2],
+    });
+
+    let (x_groups, y_groups) = crate::kernel::compute_workgroups(len);
+
+    crate::kernel::dispatch(
+        ctx,
+        &pipeline,
+        Axpy::<T>::LABEL,
+        &[alpha.inner(), x.inner(), y.inner(), &params],
+        (x_groups, y_groups, 1),
+    );
+}
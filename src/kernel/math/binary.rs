@@ -183,22 +183,7 @@ fn execute<K: Kernel, T: Element, U: Element>(
 
     let (x, y) = super::compute_workgroups(len);
 
-    let mut encoder = ctx
-        .device()
-        .create_command_encoder(&wgpu::CommandEncoderDescriptor {
-            label: Some(K::LABEL),
-        });
-    {
-        let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
-            label: Some(K::LABEL),
-            ..Default::default()
-        });
-        pass.set_pipeline(&pipeline);
-        pass.set_bind_group(0, &bind_group, &[]);
-        pass.dispatch_workgroups(x, y, 1);
-    }
-
-    ctx.queue().submit(Some(encoder.finish()));
+    crate::kernel::dispatch_bind_group(ctx, &pipeline, K::LABEL, &bind_group, (x, y, 1));
 }
 
 // Arithmetic
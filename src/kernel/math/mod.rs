@@ -14,7 +14,7 @@ pub(crate) use unary::{
     rcp, round, rsqr, rsqrt, sign, sin, sinh, sqr, sqrt, tan, tanh,
 };
 
-use crate::kernel::{MAX_WORKGROUPS, WORKGROUP_SIZE};
+use crate::kernel::compute_workgroups;
 
 /// Kernel parameters passed to shader as uniform.
 #[repr(C)]
@@ -23,11 +23,3 @@ pub(super) struct Params {
     pub(super) rank: u32,
     pub(super) len: u32,
 }
-
-/// Computes workgroup dimensions for dispatch.
-pub(super) fn compute_workgroups(len: u32) -> (u32, u32) {
-    let workgroups = len.div_ceil(WORKGROUP_SIZE);
-    let x = workgroups.min(MAX_WORKGROUPS);
-    let y = workgroups.div_ceil(MAX_WORKGROUPS);
-    (x, y)
-}
@@ -186,20 +186,11 @@ pub(crate) fn execute<T: LogicalElement, U: NumericElement>(
 
     let (x, y) = super::compute_workgroups(len);
 
-    let mut encoder = ctx
-        .device()
-        .create_command_encoder(&wgpu::CommandEncoderDescriptor {
-            label: Some(Select::<T, U>::LABEL),
-        });
-    {
-        let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
-            label: Some(Select::<T, U>::LABEL),
-            ..Default::default()
-        });
-        pass.set_pipeline(&pipeline);
-        pass.set_bind_group(0, &bind_group, &[]);
-        pass.dispatch_workgroups(x, y, 1);
-    }
-
-    ctx.queue().submit(Some(encoder.finish()));
+    crate::kernel::dispatch_bind_group(
+        ctx,
+        &pipeline,
+        Select::<T, U>::LABEL,
+        &bind_group,
+        (x, y, 1),
+    );
 }
@@ -3,15 +3,40 @@
 use alloc::string::String;
 use alloc::vec::Vec;
 
-use crate::Element;
+use crate::{Context, Element};
 
+pub(crate) mod compact;
 pub(crate) mod constant;
+pub(crate) mod conv;
 pub(crate) mod copy;
+pub(crate) mod detection;
+pub(crate) mod df64;
+pub(crate) mod diag;
+pub(crate) mod diffusion;
+pub(crate) mod distribution;
+pub(crate) mod expr;
+pub(crate) mod group;
+pub(crate) mod guard;
+pub(crate) mod histogram;
+pub(crate) mod index;
+pub(crate) mod indirect;
 pub(crate) mod linalg;
 pub(crate) mod math;
 pub(crate) mod nn;
 pub(crate) mod ops;
+pub(crate) mod pad;
+pub(crate) mod random;
 pub(crate) mod reduction;
+pub(crate) mod rl;
+pub(crate) mod rolling;
+pub(crate) mod sort;
+pub(crate) mod spatial;
+pub(crate) mod ssm;
+pub(crate) mod stats;
+pub(crate) mod stencil;
+pub(crate) mod strided;
+pub(crate) mod texture;
+pub(crate) mod triangle;
 
 /// Maximum workgroups per dimension.
 pub(crate) const MAX_WORKGROUPS: u32 = 65535;
@@ -40,3 +65,188 @@ pub(crate) fn convert_strides(strides: &[usize]) -> Vec<u32> {
         strides.iter().map(|&s| s as u32).collect()
     }
 }
+
+/// Computes workgroup dimensions for a 1D dispatch of `len` invocations.
+pub(crate) fn compute_workgroups(len: u32) -> (u32, u32) {
+    let workgroups = len.div_ceil(WORKGROUP_SIZE);
+    let x = workgroups.min(MAX_WORKGROUPS);
+    let y = workgroups.div_ceil(MAX_WORKGROUPS);
+    (x, y)
+}
+
+/// Workgroup counts of a dispatch, given directly or read from a GPU buffer.
+enum Workgroups<'a> {
+    /// `(x, y, z)` counts known on the host.
+    Direct(u32, u32, u32),
+    /// Buffer holding `(x, y, z)` counts written on the GPU.
+    Indirect(&'a wgpu::Buffer),
+}
+
+/// Binds `resources` to consecutive bindings of group 0 and submits a single compute pass.
+pub(crate) fn dispatch(
+    ctx: &Context,
+    pipeline: &wgpu::ComputePipeline,
+    label: &'static str,
+    resources: &[&wgpu::Buffer],
+    workgroups: (u32, u32, u32),
+) {
+    dispatch_entries(ctx, pipeline, label, &buffer_entries(resources), workgroups);
+}
+
+/// Binds `resources` like [`dispatch`], running even while the stop flag of the context is set.
+///
+/// Used by layout copies that readbacks and views depend on, which like buffer copies must not
+/// be skipped.
+pub(crate) fn dispatch_ungated(
+    ctx: &Context,
+    pipeline: &wgpu::ComputePipeline,
+    label: &'static str,
+    resources: &[&wgpu::Buffer],
+    workgroups: (u32, u32, u32),
+) {
+    let (x, y, z) = workgroups;
+    ctx.begin_submission(label);
+    let bind_group = create_bind_group(ctx, pipeline, label, &buffer_entries(resources));
+    submit(
+        ctx,
+        pipeline,
+        label,
+        &bind_group,
+        Workgroups::Direct(x, y, z),
+        false,
+    );
+}
+
+/// Binds `resources` like [`dispatch`], reading the workgroup counts from `args` on the GPU.
+///
+/// `args` holds `(x, y, z)` as written by [`indirect::workgroups`], so a count produced by an
+/// earlier kernel sizes the dispatch without a readback.
+pub(crate) fn dispatch_indirect(
+    ctx: &Context,
+    pipeline: &wgpu::ComputePipeline,
+    label: &'static str,
+    resources: &[&wgpu::Buffer],
+    args: &wgpu::Buffer,
+) {
+    ctx.begin_submission(label);
+    let bind_group = create_bind_group(ctx, pipeline, label, &buffer_entries(resources));
+    submit(
+        ctx,
+        pipeline,
+        label,
+        &bind_group,
+        Workgroups::Indirect(args),
+        true,
+    );
+}
+
+/// Binds `entries` to group 0 and submits a single compute pass.
+///
+/// Used by kernels that bind resources other than whole buffers, such as textures.
+pub(crate) fn dispatch_entries(
+    ctx: &Context,
+    pipeline: &wgpu::ComputePipeline,
+    label: &'static str,
+    entries: &[wgpu::BindGroupEntry<'_>],
+    workgroups: (u32, u32, u32),
+) {
+    let (x, y, z) = workgroups;
+    ctx.begin_submission(label);
+    let bind_group = create_bind_group(ctx, pipeline, label, entries);
+    submit(
+        ctx,
+        pipeline,
+        label,
+        &bind_group,
+        Workgroups::Direct(x, y, z),
+        true,
+    );
+}
+
+/// Submits a single compute pass with a bind group built by the caller.
+pub(crate) fn dispatch_bind_group(
+    ctx: &Context,
+    pipeline: &wgpu::ComputePipeline,
+    label: &'static str,
+    bind_group: &wgpu::BindGroup,
+    workgroups: (u32, u32, u32),
+) {
+    let (x, y, z) = workgroups;
+    ctx.begin_submission(label);
+    submit(
+        ctx,
+        pipeline,
+        label,
+        bind_group,
+        Workgroups::Direct(x, y, z),
+        true,
+    );
+}
+
+/// Binds each of `resources` whole to consecutive bindings.
+fn buffer_entries<'a>(resources: &[&'a wgpu::Buffer]) -> Vec<wgpu::BindGroupEntry<'a>> {
+    resources
+        .iter()
+        .zip(0..)
+        .map(|(buffer, binding)| wgpu::BindGroupEntry {
+            binding,
+            resource: buffer.as_entire_binding(),
+        })
+        .collect()
+}
+
+/// Creates a bind group for group 0 of `pipeline`.
+fn create_bind_group(
+    ctx: &Context,
+    pipeline: &wgpu::ComputePipeline,
+    label: &'static str,
+    entries: &[wgpu::BindGroupEntry<'_>],
+) -> wgpu::BindGroup {
+    ctx.device().create_bind_group(&wgpu::BindGroupDescriptor {
+        label: Some(label),
+        layout: &pipeline.get_bind_group_layout(0),
+        entries,
+    })
+}
+
+/// Submits a single compute pass with `bind_group` bound to group 0.
+///
+/// While the context has a stop flag installed and `gate` is true, the workgroup counts first
+/// pass through [`guard::gate`], so the dispatch runs zero workgroups once the flag is set.
+fn submit(
+    ctx: &Context,
+    pipeline: &wgpu::ComputePipeline,
+    label: &'static str,
+    bind_group: &wgpu::BindGroup,
+    workgroups: Workgroups<'_>,
+    gate: bool,
+) {
+    let mut encoder = ctx
+        .device()
+        .create_command_encoder(&wgpu::CommandEncoderDescriptor { label: Some(label) });
+
+    let gated = ctx.stop_flag().filter(|_| gate).map(|flag| {
+        let source = match workgroups {
+            Workgroups::Direct(x, y, z) => ctx.create_storage_buffer(&[x, y, z]),
+            Workgroups::Indirect(args) => args.clone(),
+        };
+        guard::gate(ctx, &mut encoder, &flag, &source)
+    });
+
+    {
+        let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+            label: Some(label),
+            ..Default::default()
+        });
+        pass.set_pipeline(pipeline);
+        pass.set_bind_group(0, bind_group, &[]);
+        match (&gated, workgroups) {
+            (Some(args), _) | (None, Workgroups::Indirect(args)) => {
+                pass.dispatch_workgroups_indirect(args, 0);
+            }
+            (None, Workgroups::Direct(x, y, z)) => pass.dispatch_workgroups(x, y, z),
+        }
+    }
+
+    ctx.queue().submit(Some(encoder.finish()));
+}
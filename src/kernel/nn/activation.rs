@@ -130,22 +130,7 @@ fn execute<K: Kernel, T: Element>(
     let x = workgroups.min(MAX_WORKGROUPS);
     let y = workgroups.div_ceil(MAX_WORKGROUPS);
 
-    let mut encoder = ctx
-        .device()
-        .create_command_encoder(&wgpu::CommandEncoderDescriptor {
-            label: Some(K::LABEL),
-        });
-    {
-        let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
-            label: Some(K::LABEL),
-            ..Default::default()
-        });
-        pass.set_pipeline(&pipeline);
-        pass.set_bind_group(0, &bind_group, &[]);
-        pass.dispatch_workgroups(x, y, 1);
-    }
-
-    ctx.queue().submit(Some(encoder.finish()));
+    crate::kernel::dispatch_bind_group(ctx, &pipeline, K::LABEL, &bind_group, (x, y, 1));
 }
 
 define_kernel!(
@@ -258,21 +243,12 @@ pub(crate) mod prelu {
         let x = workgroups.min(MAX_WORKGROUPS);
         let y = workgroups.div_ceil(MAX_WORKGROUPS);
 
-        let mut encoder = ctx
-            .device()
-            .create_command_encoder(&wgpu::CommandEncoderDescriptor {
-                label: Some(Prelu::<T>::LABEL),
-            });
-        {
-            let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
-                label: Some(Prelu::<T>::LABEL),
-                ..Default::default()
-            });
-            pass.set_pipeline(&pipeline);
-            pass.set_bind_group(0, &bind_group, &[]);
-            pass.dispatch_workgroups(x, y, 1);
-        }
-
-        ctx.queue().submit(Some(encoder.finish()));
+        crate::kernel::dispatch_bind_group(
+            ctx,
+            &pipeline,
+            Prelu::<T>::LABEL,
+            &bind_group,
+            (x, y, 1),
+        );
     }
 }
@@ -0,0 +1,345 @@
+//! Chunked softmax cross-entropy kernels.
+//!
+//! Logits may be split along the class axis into several `[rows, cols]` chunks. Row statistics
+//! are accumulated chunk by chunk with an online log-sum-exp, so the full softmax is never
+//! materialized.
+
+use core::any::TypeId;
+use core::marker::PhantomData;
+
+use alloc::format;
+use alloc::string::String;
+
+use bytemuck::{Pod, Zeroable};
+
+use crate::element::FloatElement;
+use crate::kernel::{Kernel, MAX_WORKGROUPS, WORKGROUP_SIZE};
+use crate::{Buffer, Context};
+
+/// Kernel parameters passed to shader as uniform.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Pod, Zeroable)]
+struct Params {
+    rows: u32,
+    cols: u32,
+    offset: u32,
+    _pad: u32,
+}
+
+/// WGSL parameter struct shared by the chunk kernels.
+const PARAMS_WGSL: &str = r"
+    struct Params {
+        rows: u32,
+        cols: u32,
+        offset: u32,
+        _pad: u32,
+    }
+";
+
+/// Folds one logits chunk into the running row max, row sum and target logit.
+struct Accumulate<T>(PhantomData<T>);
+
+impl<T: FloatElement> Kernel for Accumulate<T> {
+    const LABEL: &'static str = "cross_entropy_accumulate";
+    type Output = T;
+
+    fn wgsl() -> String {
+        let ty = T::wgsl_type();
+        let min = T::wgsl_min();
+
+        format!(
+            r"
+                {PARAMS_WGSL}
+
+                const WG_SIZE: u32 = {WORKGROUP_SIZE}u;
+
+                @group(0) @binding(0) var<storage, read> x: array<{ty}>;
+                @group(0) @binding(1) var<storage, read> targets: array<u32>;
+                @group(0) @binding(2) var<storage, read_write> row_max: array<{ty}>;
+                @group(0) @binding(3) var<storage, read_write> row_sum: array<{ty}>;
+                @group(0) @binding(4) var<storage, read_write> target_logit: array<{ty}>;
+                @group(0) @binding(5) var<uniform> params: Params;
+
+                var<workgroup> sdata: array<{ty}, WG_SIZE>;
+
+                @compute @workgroup_size(WG_SIZE)
+                fn main(
+                    @builtin(local_invocation_id) lid: vec3<u32>,
+                    @builtin(workgroup_id) wid: vec3<u32>
+                ) {{
+                    let tid = lid.x;
+                    let row = wid.x + wid.y * {MAX_WORKGROUPS}u;
+
+                    if row >= params.rows {{
+                        return;
+                    }}
+
+                    let base = row * params.cols;
+
+                    var local_max: {ty} = {min};
+                    for (var i = tid; i < params.cols; i += WG_SIZE) {{
+                        local_max = max(local_max, x[base + i]);
+                    }}
+
+                    sdata[tid] = local_max;
+                    workgroupBarrier();
+
+                    for (var s = WG_SIZE / 2u; s > 0u; s >>= 1u) {{
+                        if tid < s {{
+                            sdata[tid] = max(sdata[tid], sdata[tid + s]);
+                        }}
+                        workgroupBarrier();
+                    }}
+
+                    let chunk_max = sdata[0];
+                    workgroupBarrier();
+
+                    var local_sum: {ty} = 0.0;
+                    for (var i = tid; i < params.cols; i += WG_SIZE) {{
+                        local_sum += exp(x[base + i] - chunk_max);
+                    }}
+
+                    sdata[tid] = local_sum;
+                    workgroupBarrier();
+
+                    for (var s = WG_SIZE / 2u; s > 0u; s >>= 1u) {{
+                        if tid < s {{
+                            sdata[tid] = sdata[tid] + sdata[tid + s];
+                        }}
+                        workgroupBarrier();
+                    }}
+
+                    if tid == 0u {{
+                        if params.offset == 0u {{
+                            row_max[row] = chunk_max;
+                            row_sum[row] = sdata[0];
+                        }} else {{
+                            let old_max = row_max[row];
+                            let new_max = max(old_max, chunk_max);
+                            row_sum[row] = row_sum[row] * exp(old_max - new_max)
+                                + sdata[0] * exp(chunk_max - new_max);
+                            row_max[row] = new_max;
+                        }}
+
+                        let label = targets[row];
+                        if label >= params.offset && label < params.offset + params.cols {{
+                            target_logit[row] = x[base + label - params.offset];
+                        }}
+                    }}
+                }}
+            "
+        )
+    }
+}
+
+/// Computes `loss = log(row_sum) + row_max - target_logit` per row.
+struct Loss<T>(PhantomData<T>);
+
+impl<T: FloatElement> Kernel for Loss<T> {
+    const LABEL: &'static str = "cross_entropy_loss";
+    type Output = T;
+
+    fn wgsl() -> String {
+        let ty = T::wgsl_type();
+
+        format!(
+            r"
+                {PARAMS_WGSL}
+
+                @group(0) @binding(0) var<storage, read> row_max: array<{ty}>;
+                @group(0) @binding(1) var<storage, read> row_sum: array<{ty}>;
+                @group(0) @binding(2) var<storage, read> target_logit: array<{ty}>;
+                @group(0) @binding(3) var<storage, read_write> loss: array<{ty}>;
+                @group(0) @binding(4) var<uniform> params: Params;
+
+                @compute @workgroup_size({WORKGROUP_SIZE})
+                fn main(@builtin(global_invocation_id) gid: vec3<u32>) {{
+                    let tid = gid.x + gid.y * {MAX_WORKGROUPS}u * {WORKGROUP_SIZE}u;
+
+                    if tid >= params.rows {{
+                        return;
+                    }}
+
+                    loss[tid] = log(row_sum[tid]) + row_max[tid] - target_logit[tid];
+                }}
+            "
+        )
+    }
+}
+
+/// Computes the logits gradient of one chunk: `g = softmax(x) - onehot(target)`.
+struct Grad<T>(PhantomData<T>);
+
+impl<T: FloatElement> Kernel for Grad<T> {
+    const LABEL: &'static str = "cross_entropy_grad";
+    type Output = T;
+
+    fn wgsl() -> String {
+        let ty = T::wgsl_type();
+
+        format!(
+            r"
+                {PARAMS_WGSL}
+
+                @group(0) @binding(0) var<storage, read> x: array<{ty}>;
+                @group(0) @binding(1) var<storage, read> targets: array<u32>;
+                @group(0) @binding(2) var<storage, read> row_max: array<{ty}>;
+                @group(0) @binding(3) var<storage, read> row_sum: array<{ty}>;
+                @group(0) @binding(4) var<storage, read_write> g: array<{ty}>;
+                @group(0) @binding(5) var<uniform> params: Params;
+
+                @compute @workgroup_size({WORKGROUP_SIZE})
+                fn main(@builtin(global_invocation_id) gid: vec3<u32>) {{
+                    let tid = gid.x + gid.y * {MAX_WORKGROUPS}u * {WORKGROUP_SIZE}u;
+
+                    if tid >= params.rows * params.cols {{
+                        return;
+                    }}
+
+                    let row = tid / params.cols;
+                    let col = tid % params.cols + params.offset;
+                    let p = exp(x[tid] - row_max[row]) / row_sum[row];
+
+                    g[tid] = p - select(0.0, 1.0, col == targets[row]);
+                }}
+            "
+        )
+    }
+}
+
+/// Builds the uniform for a `[rows, cols]` chunk starting at class `offset`.
+fn params(ctx: &Context, rows: usize, cols: usize, offset: usize) -> wgpu::Buffer {
+    let to_u32 = |x: usize| u32::try_from(x).expect("dimension exceeds max size");
+    ctx.create_uniform_buffer(&Params {
+        rows: to_u32(rows),
+        cols: to_u32(cols),
+        offset: to_u32(offset),
+        _pad: 0,
+    })
+}
+
+/// Folds the `[rows, cols]` chunk `x` starting at class `offset` into the row statistics.
+///
+/// The chunk at offset 0 initializes the statistics, so chunks must be folded in class order.
+///
+/// # Panics
+///
+/// - Row count exceeds max size
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn accumulate<T: FloatElement>(
+    ctx: &Context,
+    x: &Buffer<T>,
+    targets: &Buffer<u32>,
+    row_max: &Buffer<T>,
+    row_sum: &Buffer<T>,
+    target_logit: &Buffer<T>,
+    rows: usize,
+    cols: usize,
+    offset: usize,
+) {
+    let workgroups = u32::try_from(rows).expect("row count exceeds max size");
+
+    let pipeline = ctx.get_or_create_pipeline(
+        TypeId::of::<Accumulate<T>>(),
+        Accumulate::<T>::wgsl,
+        Accumulate::<T>::LABEL,
+    );
+
+    let params = params(ctx, rows, cols, offset);
+
+    crate::kernel::dispatch(
+        ctx,
+        &pipeline,
+        Accumulate::<T>::LABEL,
+        &[
+            x.inner(),
+            targets.inner(),
+            row_max.inner(),
+            row_sum.inner(),
+            target_logit.inner(),
+            &params,
+        ],
+        (
+            workgroups.min(MAX_WORKGROUPS),
+            workgroups.div_ceil(MAX_WORKGROUPS),
+            1,
+        ),
+    );
+}
+
+/// Computes the per-row loss from the accumulated row statistics.
+///
+/// # Panics
+///
+/// - Row count exceeds max size
+pub(crate) fn loss<T: FloatElement>(
+    ctx: &Context,
+    row_max: &Buffer<T>,
+    row_sum: &Buffer<T>,
+    target_logit: &Buffer<T>,
+    loss: &Buffer<T>,
+    rows: usize,
+) {
+    let len = u32::try_from(rows).expect("row count exceeds max size");
+
+    let pipeline =
+        ctx.get_or_create_pipeline(TypeId::of::<Loss<T>>(), Loss::<T>::wgsl, Loss::<T>::LABEL);
+
+    let params = params(ctx, rows, 1, 0);
+    let (x, y) = crate::kernel::compute_workgroups(len);
+
+    crate::kernel::dispatch(
+        ctx,
+        &pipeline,
+        Loss::<T>::LABEL,
+        &[
+            row_max.inner(),
+            row_sum.inner(),
+            target_logit.inner(),
+            loss.inner(),
+            &params,
+        ],
+        (x, y, 1),
+    );
+}
+
+/// Computes the logits gradient of the `[rows, cols]` chunk `x` starting at class `offset`.
+///
+/// # Panics
+///
+/// - Chunk length exceeds max size
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn grad<T: FloatElement>(
+    ctx: &Context,
+    x: &Buffer<T>,
+    targets: &Buffer<u32>,
+    row_max: &Buffer<T>,
+    row_sum: &Buffer<T>,
+    g: &Buffer<T>,
+    rows: usize,
+    cols: usize,
+    offset: usize,
+) {
+    let len = u32::try_from(rows * cols).expect("chunk length exceeds max size");
+
+    let pipeline =
+        ctx.get_or_create_pipeline(TypeId::of::<Grad<T>>(), Grad::<T>::wgsl, Grad::<T>::LABEL);
+
+    let params = params(ctx, rows, cols, offset);
+    let (wx, wy) = crate::kernel::compute_workgroups(len);
+
+    crate::kernel::dispatch(
+        ctx,
+        &pipeline,
+        Grad::<T>::LABEL,
+        &[
+            x.inner(),
+            targets.inner(),
+            row_max.inner(),
+            row_sum.inner(),
+            g.inner(),
+            &params,
+        ],
+        (wx, wy, 1),
+    );
+}
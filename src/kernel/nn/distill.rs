@@ -0,0 +1,193 @@
+//! Knowledge distillation kernel.
+
+use core::any::TypeId;
+use core::marker::PhantomData;
+
+use alloc::format;
+use alloc::string::String;
+
+use bytemuck::{Pod, Zeroable};
+
+use crate::element::FloatElement;
+use crate::kernel::{Kernel, MAX_WORKGROUPS, WORKGROUP_SIZE};
+use crate::{Buffer, Context};
+
+/// Kernel parameters passed to shader as uniform.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Pod, Zeroable)]
+struct Params {
+    rows: u32,
+    cols: u32,
+    temperature: f32,
+    _pad: u32,
+}
+
+/// Fused temperature-scaled KL divergence and student logits gradient.
+///
+/// With `p = softmax(t / T)` and `q = softmax(s / T)`, each row computes
+/// `loss = T² · Σ p (log p - log q)` and `g = T (q - p)`.
+struct KlDiv<T>(PhantomData<T>);
+
+impl<T: FloatElement> Kernel for KlDiv<T> {
+    const LABEL: &'static str = "kl_div";
+    type Output = T;
+
+    fn wgsl() -> String {
+        let ty = T::wgsl_type();
+        let min = T::wgsl_min();
+
+        format!(
+            r"
+                const WG_SIZE: u32 = {WORKGROUP_SIZE}u;
+
+                struct Params {{
+                    rows: u32,
+                    cols: u32,
+                    temperature: f32,
+                    _pad: u32,
+                }}
+
+                @group(0) @binding(0) var<storage, read> s: array<{ty}>;
+                @group(0) @binding(1) var<storage, read> t: array<{ty}>;
+                @group(0) @binding(2) var<storage, read_write> loss: array<{ty}>;
+                @group(0) @binding(3) var<storage, read_write> g: array<{ty}>;
+                @group(0) @binding(4) var<uniform> params: Params;
+
+                var<workgroup> s_data: array<{ty}, WG_SIZE>;
+                var<workgroup> t_data: array<{ty}, WG_SIZE>;
+
+                fn reduce_max(tid: u32) {{
+                    for (var k = WG_SIZE / 2u; k > 0u; k >>= 1u) {{
+                        if tid < k {{
+                            s_data[tid] = max(s_data[tid], s_data[tid + k]);
+                            t_data[tid] = max(t_data[tid], t_data[tid + k]);
+                        }}
+                        workgroupBarrier();
+                    }}
+                }}
+
+                fn reduce_sum(tid: u32) {{
+                    for (var k = WG_SIZE / 2u; k > 0u; k >>= 1u) {{
+                        if tid < k {{
+                            s_data[tid] = s_data[tid] + s_data[tid + k];
+                            t_data[tid] = t_data[tid] + t_data[tid + k];
+                        }}
+                        workgroupBarrier();
+                    }}
+                }}
+
+                @compute @workgroup_size(WG_SIZE)
+                fn main(
+                    @builtin(local_invocation_id) lid: vec3<u32>,
+                    @builtin(workgroup_id) wid: vec3<u32>
+                ) {{
+                    let tid = lid.x;
+                    let row = wid.x + wid.y * {MAX_WORKGROUPS}u;
+
+                    if row >= params.rows {{
+                        return;
+                    }}
+
+                    let base = row * params.cols;
+                    let scale = 1.0 / params.temperature;
+
+                    var s_max: {ty} = {min};
+                    var t_max: {ty} = {min};
+                    for (var i = tid; i < params.cols; i += WG_SIZE) {{
+                        s_max = max(s_max, s[base + i] * scale);
+                        t_max = max(t_max, t[base + i] * scale);
+                    }}
+
+                    s_data[tid] = s_max;
+                    t_data[tid] = t_max;
+                    workgroupBarrier();
+                    reduce_max(tid);
+
+                    s_max = s_data[0];
+                    t_max = t_data[0];
+                    workgroupBarrier();
+
+                    var s_sum: {ty} = 0.0;
+                    var t_sum: {ty} = 0.0;
+                    for (var i = tid; i < params.cols; i += WG_SIZE) {{
+                        s_sum += exp(s[base + i] * scale - s_max);
+                        t_sum += exp(t[base + i] * scale - t_max);
+                    }}
+
+                    s_data[tid] = s_sum;
+                    t_data[tid] = t_sum;
+                    workgroupBarrier();
+                    reduce_sum(tid);
+
+                    let s_lse = s_max + log(s_data[0]);
+                    let t_lse = t_max + log(t_data[0]);
+                    workgroupBarrier();
+
+                    var kl: {ty} = 0.0;
+                    for (var i = tid; i < params.cols; i += WG_SIZE) {{
+                        let log_q = s[base + i] * scale - s_lse;
+                        let log_p = t[base + i] * scale - t_lse;
+                        let p = exp(log_p);
+                        kl += p * (log_p - log_q);
+                        g[base + i] = params.temperature * (exp(log_q) - p);
+                    }}
+
+                    s_data[tid] = kl;
+                    t_data[tid] = 0.0;
+                    workgroupBarrier();
+                    reduce_sum(tid);
+
+                    if tid == 0u {{
+                        loss[row] = params.temperature * params.temperature * s_data[0];
+                    }}
+                }}
+            "
+        )
+    }
+}
+
+/// Computes the per-row distillation loss and student gradient for `[rows, cols]` logits.
+///
+/// # Panics
+///
+/// - Row count or row length exceed max size
+pub(crate) fn kl_div<T: FloatElement>(
+    ctx: &Context,
+    student: &Buffer<T>,
+    teacher: &Buffer<T>,
+    loss: &Buffer<T>,
+    g: &Buffer<T>,
+    rows: usize,
+    cols: usize,
+    temperature: f32,
+) {
+    let rows = u32::try_from(rows).expect("row count exceeds max size");
+    let cols = u32::try_from(cols).expect("row length exceeds max size");
+
+    let pipeline = ctx.get_or_create_pipeline(
+        TypeId::of::<KlDiv<T>>(),
+        KlDiv::<T>::wgsl,
+        KlDiv::<T>::LABEL,
+    );
+
+    let params = ctx.create_uniform_buffer(&Params {
+        rows,
+        cols,
+        temperature,
+        _pad: 0,
+    });
+
+    crate::kernel::dispatch(
+        ctx,
+        &pipeline,
+        KlDiv::<T>::LABEL,
+        &[
+            student.inner(),
+            teacher.inner(),
+            loss.inner(),
+            g.inner(),
+            &params,
+        ],
+        (rows.min(MAX_WORKGROUPS), rows.div_ceil(MAX_WORKGROUPS), 1),
+    );
+}
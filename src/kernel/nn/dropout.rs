@@ -0,0 +1,147 @@
+//! Dropout kernels.
+
+use core::any::TypeId;
+use core::marker::PhantomData;
+
+use alloc::format;
+use alloc::string::String;
+
+use bytemuck::{Pod, Zeroable};
+
+use crate::element::FloatElement;
+use crate::kernel::random::RANDOM_WGSL;
+use crate::kernel::{Kernel, MAX_WORKGROUPS, WORKGROUP_SIZE};
+use crate::{Buffer, Context};
+
+/// Kernel parameters passed to shader as uniform.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Pod, Zeroable)]
+struct Params {
+    rank: u32,
+    len: u32,
+    p: f32,
+    seed: u32,
+}
+
+/// Fused bias-dropout-residual kernel: `y = dropout(x + bias) + residual`.
+struct BiasDropoutResidual<T>(PhantomData<T>);
+
+impl<T: FloatElement> Kernel for BiasDropoutResidual<T> {
+    const LABEL: &'static str = "bias_dropout_residual";
+    type Output = T;
+
+    fn wgsl() -> String {
+        let ty = T::wgsl_type();
+
+        format!(
+            r"
+                {RANDOM_WGSL}
+
+                struct Params {{
+                    rank: u32,
+                    len: u32,
+                    p: f32,
+                    seed: u32,
+                }}
+
+                @group(0) @binding(0) var<storage, read> x: array<{ty}>;
+                @group(0) @binding(1) var<storage, read> bias: array<{ty}>;
+                @group(0) @binding(2) var<storage, read> residual: array<{ty}>;
+                @group(0) @binding(3) var<storage, read_write> y: array<{ty}>;
+                @group(0) @binding(4) var<storage, read> x_strides: array<u32>;
+                @group(0) @binding(5) var<storage, read> bias_strides: array<u32>;
+                @group(0) @binding(6) var<storage, read> residual_strides: array<u32>;
+                @group(0) @binding(7) var<storage, read> y_strides: array<u32>;
+                @group(0) @binding(8) var<uniform> params: Params;
+
+                @compute @workgroup_size({WORKGROUP_SIZE})
+                fn main(@builtin(global_invocation_id) gid: vec3<u32>) {{
+                    let tid = gid.x + gid.y * {MAX_WORKGROUPS}u * {WORKGROUP_SIZE}u;
+
+                    if tid >= params.len {{
+                        return;
+                    }}
+
+                    var remaining = tid;
+                    var x_idx = 0u;
+                    var bias_idx = 0u;
+                    var residual_idx = 0u;
+
+                    for (var i = 0u; i < params.rank; i++) {{
+                        let coord = remaining / y_strides[i];
+                        remaining = remaining % y_strides[i];
+                        x_idx += coord * x_strides[i];
+                        bias_idx += coord * bias_strides[i];
+                        residual_idx += coord * residual_strides[i];
+                    }}
+
+                    let keep = random_uniform(params.seed, tid) >= params.p;
+                    let value = (x[x_idx] + bias[bias_idx]) / (1.0 - params.p);
+
+                    y[tid] = select(0.0, value, keep) + residual[residual_idx];
+                }}
+            "
+        )
+    }
+}
+
+/// Executes the fused bias-dropout-residual kernel.
+///
+/// # Panics
+///
+/// - Output length exceeds max size
+/// - Output rank exceeds max size
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn bias_dropout_residual<T: FloatElement>(
+    ctx: &Context,
+    x: &Buffer<T>,
+    bias: &Buffer<T>,
+    residual: &Buffer<T>,
+    y: &Buffer<T>,
+    x_strides: &[usize],
+    bias_strides: &[usize],
+    residual_strides: &[usize],
+    y_strides: &[usize],
+    p: f32,
+    seed: u32,
+) {
+    let rank = u32::try_from(y_strides.len()).expect("output rank exceeds max size");
+    let len = u32::try_from(y.len()).expect("output length exceeds max size");
+
+    if len == 0 {
+        return;
+    }
+
+    let pipeline = ctx.get_or_create_pipeline(
+        TypeId::of::<BiasDropoutResidual<T>>(),
+        BiasDropoutResidual::<T>::wgsl,
+        BiasDropoutResidual::<T>::LABEL,
+    );
+
+    let x_strides = ctx.create_storage_buffer(&crate::kernel::convert_strides(x_strides));
+    let bias_strides = ctx.create_storage_buffer(&crate::kernel::convert_strides(bias_strides));
+    let residual_strides =
+        ctx.create_storage_buffer(&crate::kernel::convert_strides(residual_strides));
+    let y_strides = ctx.create_storage_buffer(&crate::kernel::convert_strides(y_strides));
+    let params = ctx.create_uniform_buffer(&Params { rank, len, p, seed });
+
+    let (wx, wy) = crate::kernel::compute_workgroups(len);
+
+    crate::kernel::dispatch(
+        ctx,
+        &pipeline,
+        BiasDropoutResidual::<T>::LABEL,
+        &[
+            x.inner(),
+            bias.inner(),
+            residual.inner(),
+            y.inner(),
+            &x_strides,
+            &bias_strides,
+            &residual_strides,
+            &y_strides,
+            &params,
+        ],
+        (wx, wy, 1),
+    );
+}
@@ -0,0 +1,166 @@
+//! Embedding bag kernels.
+
+use core::any::TypeId;
+use core::marker::PhantomData;
+
+use alloc::format;
+use alloc::string::String;
+
+use bytemuck::{Pod, Zeroable};
+
+use crate::element::FloatElement;
+use crate::kernel::random::RANDOM_WGSL;
+use crate::kernel::{Kernel, MAX_WORKGROUPS, WORKGROUP_SIZE};
+use crate::{Buffer, Context};
+
+/// Kernel parameters passed to shader as uniform.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Pod, Zeroable)]
+struct Params {
+    rows: u32,
+    dim: u32,
+    len: u32,
+    bags: u32,
+    mean: u32,
+    hashed: u32,
+    seed: u32,
+    _pad: u32,
+}
+
+/// Embedding bag kernel: `y[b] = Σ weights[indices[i]]` for `i` in bag `b`.
+///
+/// Bag `b` covers `indices[offsets[b]..offsets[b + 1]]`, the last bag running to the end.
+/// Indices out of bounds are skipped; in mean mode the sum is divided by the bag length and
+/// empty bags are zero.
+///
+/// In hashed mode the entries are feature ids rather than rows: `h = random_u32(seed, id)`
+/// selects row `h % rows`, added with sign `+1` or `-1` from the low bit of `pcg_hash(h)`.
+/// The signs make colliding ids cancel in expectation instead of piling up.
+struct EmbeddingBag<T>(PhantomData<T>);
+
+impl<T: FloatElement> Kernel for EmbeddingBag<T> {
+    const LABEL: &'static str = "embedding_bag";
+    type Output = T;
+
+    fn wgsl() -> String {
+        let ty = T::wgsl_type();
+
+        format!(
+            r"
+                {RANDOM_WGSL}
+
+                struct Params {{
+                    rows: u32,
+                    dim: u32,
+                    len: u32,
+                    bags: u32,
+                    mean: u32,
+                    hashed: u32,
+                    seed: u32,
+                }}
+
+                @group(0) @binding(0) var<storage, read> weights: array<{ty}>;
+                @group(0) @binding(1) var<storage, read> indices: array<u32>;
+                @group(0) @binding(2) var<storage, read> offsets: array<u32>;
+                @group(0) @binding(3) var<storage, read_write> y: array<{ty}>;
+                @group(0) @binding(4) var<uniform> params: Params;
+
+                @compute @workgroup_size({WORKGROUP_SIZE})
+                fn main(@builtin(global_invocation_id) gid: vec3<u32>) {{
+                    let tid = gid.x + gid.y * {MAX_WORKGROUPS}u * {WORKGROUP_SIZE}u;
+
+                    if tid >= params.bags * params.dim {{
+                        return;
+                    }}
+
+                    let bag = tid / params.dim;
+                    let col = tid % params.dim;
+
+                    let start = min(offsets[bag], params.len);
+                    var end = params.len;
+                    if bag + 1u < params.bags {{
+                        end = clamp(offsets[bag + 1u], start, params.len);
+                    }}
+
+                    var acc: {ty} = 0.0;
+                    for (var i = start; i < end; i++) {{
+                        if params.hashed != 0u {{
+                            let h = random_u32(params.seed, indices[i]);
+                            let value = weights[(h % params.rows) * params.dim + col];
+                            acc += select(value, -value, (pcg_hash(h) & 1u) != 0u);
+                        }} else if indices[i] < params.rows {{
+                            acc += weights[indices[i] * params.dim + col];
+                        }}
+                    }}
+
+                    if params.mean != 0u && end > start {{
+                        acc /= {ty}(end - start);
+                    }}
+
+                    y[tid] = acc;
+                }}
+            "
+        )
+    }
+}
+
+/// Sums or averages the `[rows, dim]` `weights` rows selected by each bag of `indices`.
+///
+/// With `hash = Some(seed)`, `indices` are feature ids hashed into signed rows.
+///
+/// # Panics
+///
+/// - Output length exceeds max size
+/// - Weight rows or index count exceeds max size
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn embedding_bag<T: FloatElement>(
+    ctx: &Context,
+    weights: &Buffer<T>,
+    indices: &Buffer<u32>,
+    offsets: &Buffer<u32>,
+    y: &Buffer<T>,
+    rows: usize,
+    dim: usize,
+    mean: bool,
+    hash: Option<u32>,
+) {
+    let len = u32::try_from(y.len()).expect("output length exceeds max size");
+    let to_u32 = |x: usize| u32::try_from(x).expect("dimension exceeds max size");
+
+    if len == 0 {
+        return;
+    }
+
+    let pipeline = ctx.get_or_create_pipeline(
+        TypeId::of::<EmbeddingBag<T>>(),
+        EmbeddingBag::<T>::wgsl,
+        EmbeddingBag::<T>::LABEL,
+    );
+
+    let params = ctx.create_uniform_buffer(&Params {
+        rows: to_u32(rows),
+        dim: to_u32(dim),
+        len: to_u32(indices.len()),
+        bags: to_u32(offsets.len()),
+        mean: u32::from(mean),
+        hashed: u32::from(hash.is_some()),
+        seed: hash.unwrap_or(0),
+        _pad: 0,
+    });
+
+    let (x, y_groups) = crate::kernel::compute_workgroups(len);
+
+    crate::kernel::dispatch(
+        ctx,
+        &pipeline,
+        EmbeddingBag::<T>::LABEL,
+        &[
+            weights.inner(),
+            indices.inner(),
+            offsets.inner(),
+            y.inner(),
+            &params,
+        ],
+        (x, y_groups, 1),
+    );
+}
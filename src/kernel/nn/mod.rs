@@ -1,3 +1,9 @@
 //! Neural network kernels.
 
 pub(crate) mod activation;
+pub(crate) mod cross_entropy;
+pub(crate) mod distill;
+pub(crate) mod dropout;
+pub(crate) mod embedding;
+pub(crate) mod moe;
+pub(crate) mod softmax;
@@ -0,0 +1,140 @@
+//! Mixture-of-experts routing kernel.
+
+use core::any::TypeId;
+
+use alloc::format;
+use alloc::string::String;
+
+use bytemuck::{Pod, Zeroable};
+
+use crate::kernel::{Kernel, MAX_WORKGROUPS, WORKGROUP_SIZE};
+use crate::{Buffer, Context};
+
+/// Kernel parameters passed to shader as uniform.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Pod, Zeroable)]
+struct Params {
+    tokens: u32,
+    k: u32,
+    experts: u32,
+    capacity: u32,
+}
+
+/// Capacity-limited routing kernel, one thread per expert.
+///
+/// Each expert walks its assignments in priority order (every token's first choice, then every
+/// token's second choice, ...) and accepts them into consecutive slots until `capacity` is
+/// reached. Accepted assignments get `slots[t, j] = expert * capacity + position` and
+/// `sources[slot] = t`; rejected assignments get `slots[t, j] = experts * capacity`, and unused
+/// slots get `sources[slot] = tokens`.
+struct Route;
+
+impl Kernel for Route {
+    const LABEL: &'static str = "moe_route";
+    type Output = u32;
+
+    fn wgsl() -> String {
+        format!(
+            r"
+                struct Params {{
+                    tokens: u32,
+                    k: u32,
+                    experts: u32,
+                    capacity: u32,
+                }}
+
+                @group(0) @binding(0) var<storage, read> choices: array<u32>;
+                @group(0) @binding(1) var<storage, read_write> slots: array<u32>;
+                @group(0) @binding(2) var<storage, read_write> sources: array<u32>;
+                @group(0) @binding(3) var<storage, read_write> counts: array<u32>;
+                @group(0) @binding(4) var<uniform> params: Params;
+
+                @compute @workgroup_size({WORKGROUP_SIZE})
+                fn main(@builtin(global_invocation_id) gid: vec3<u32>) {{
+                    let expert = gid.x + gid.y * {MAX_WORKGROUPS}u * {WORKGROUP_SIZE}u;
+
+                    if expert >= params.experts {{
+                        return;
+                    }}
+
+                    let base = expert * params.capacity;
+                    let dropped = params.experts * params.capacity;
+                    var count = 0u;
+
+                    for (var j = 0u; j < params.k; j++) {{
+                        for (var t = 0u; t < params.tokens; t++) {{
+                            let idx = t * params.k + j;
+                            if choices[idx] != expert {{
+                                continue;
+                            }}
+
+                            if count < params.capacity {{
+                                slots[idx] = base + count;
+                                sources[base + count] = t;
+                                count++;
+                            }} else {{
+                                slots[idx] = dropped;
+                            }}
+                        }}
+                    }}
+
+                    for (var c = count; c < params.capacity; c++) {{
+                        sources[base + c] = params.tokens;
+                    }}
+
+                    counts[expert] = count;
+                }}
+            "
+        )
+    }
+}
+
+/// Routes `[tokens, k]` expert `choices` into `experts` groups of `capacity` slots.
+///
+/// Every choice must be less than `experts`.
+///
+/// # Panics
+///
+/// - Token count, `k`, expert count or capacity exceeds max size
+pub(crate) fn route(
+    ctx: &Context,
+    choices: &Buffer<u32>,
+    slots: &Buffer<u32>,
+    sources: &Buffer<u32>,
+    counts: &Buffer<u32>,
+    tokens: usize,
+    k: usize,
+    experts: usize,
+    capacity: usize,
+) {
+    let to_u32 = |x: usize| u32::try_from(x).expect("routing size exceeds max size");
+    let params = Params {
+        tokens: to_u32(tokens),
+        k: to_u32(k),
+        experts: to_u32(experts),
+        capacity: to_u32(capacity),
+    };
+
+    if params.experts == 0 {
+        return;
+    }
+
+    let pipeline = ctx.get_or_create_pipeline(TypeId::of::<Route>(), Route::wgsl, Route::LABEL);
+    let params_buffer = ctx.create_uniform_buffer(&params);
+
+    let (x, y) = crate::kernel::compute_workgroups(params.experts);
+
+    crate::kernel::dispatch(
+        ctx,
+        &pipeline,
+        Route::LABEL,
+        &[
+            choices.inner(),
+            slots.inner(),
+            sources.inner(),
+            counts.inner(),
+            &params_buffer,
+        ],
+        (x, y, 1),
+    );
+}
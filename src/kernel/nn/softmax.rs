@@ -0,0 +1,205 @@
+//! Softmax kernel.
+
+use core::any::TypeId;
+use core::marker::PhantomData;
+
+use alloc::format;
+use alloc::string::String;
+
+use bytemuck::{Pod, Zeroable};
+
+use crate::element::FloatElement;
+use crate::kernel::{Kernel, MAX_WORKGROUPS, WORKGROUP_SIZE};
+use crate::{Buffer, Context};
+
+/// Kernel parameters passed to shader as uniform.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Pod, Zeroable)]
+struct Params {
+    rank: u32,
+    rows: u32,
+    cols: u32,
+    masked: u32,
+}
+
+/// Softmax over the last axis, skipping elements where the mask is false.
+struct Softmax<T>(PhantomData<T>);
+
+impl<T: FloatElement> Kernel for Softmax<T> {
+    const LABEL: &'static str = "softmax";
+    type Output = T;
+
+    fn wgsl() -> String {
+        let ty = T::wgsl_type();
+        let min = T::wgsl_min();
+
+        format!(
+            r"
+                const WG_SIZE: u32 = {WORKGROUP_SIZE}u;
+
+                struct Params {{
+                    rank: u32,
+                    rows: u32,
+                    cols: u32,
+                    masked: u32,
+                }}
+
+                @group(0) @binding(0) var<storage, read> x: array<{ty}>;
+                @group(0) @binding(1) var<storage, read> mask: array<u32>;
+                @group(0) @binding(2) var<storage, read_write> y: array<{ty}>;
+                @group(0) @binding(3) var<storage, read> x_strides: array<u32>;
+                @group(0) @binding(4) var<storage, read> mask_strides: array<u32>;
+                @group(0) @binding(5) var<uniform> params: Params;
+
+                var<workgroup> sdata: array<{ty}, WG_SIZE>;
+
+                fn keep(idx: u32) -> bool {{
+                    if params.masked == 0u {{
+                        return true;
+                    }}
+
+                    var remaining = idx;
+                    var mask_idx = 0u;
+                    for (var i = 0u; i < params.rank; i++) {{
+                        let coord = remaining / x_strides[i];
+                        remaining = remaining % x_strides[i];
+                        mask_idx += coord * mask_strides[i];
+                    }}
+
+                    return mask[mask_idx] != 0u;
+                }}
+
+                @compute @workgroup_size(WG_SIZE)
+                fn main(
+                    @builtin(local_invocation_id) lid: vec3<u32>,
+                    @builtin(workgroup_id) wid: vec3<u32>
+                ) {{
+                    let tid = lid.x;
+                    let row = wid.x + wid.y * {MAX_WORKGROUPS}u;
+
+                    if row >= params.rows {{
+                        return;
+                    }}
+
+                    let base = row * params.cols;
+
+                    var local_max: {ty} = {min};
+                    for (var i = tid; i < params.cols; i += WG_SIZE) {{
+                        if keep(base + i) {{
+                            local_max = max(local_max, x[base + i]);
+                        }}
+                    }}
+
+                    sdata[tid] = local_max;
+                    workgroupBarrier();
+
+                    for (var s = WG_SIZE / 2u; s > 0u; s >>= 1u) {{
+                        if tid < s {{
+                            sdata[tid] = max(sdata[tid], sdata[tid + s]);
+                        }}
+                        workgroupBarrier();
+                    }}
+
+                    let row_max = sdata[0];
+                    workgroupBarrier();
+
+                    var local_sum: {ty} = 0.0;
+                    for (var i = tid; i < params.cols; i += WG_SIZE) {{
+                        if keep(base + i) {{
+                            local_sum += exp(x[base + i] - row_max);
+                        }}
+                    }}
+
+                    sdata[tid] = local_sum;
+                    workgroupBarrier();
+
+                    for (var s = WG_SIZE / 2u; s > 0u; s >>= 1u) {{
+                        if tid < s {{
+                            sdata[tid] = sdata[tid] + sdata[tid + s];
+                        }}
+                        workgroupBarrier();
+                    }}
+
+                    let row_sum = sdata[0];
+
+                    for (var i = tid; i < params.cols; i += WG_SIZE) {{
+                        var value: {ty} = 0.0;
+                        if row_sum > 0.0 && keep(base + i) {{
+                            value = exp(x[base + i] - row_max) / row_sum;
+                        }}
+                        y[base + i] = value;
+                    }}
+                }}
+            "
+        )
+    }
+}
+
+/// Executes softmax over the last axis of contiguous `x`.
+///
+/// With a mask, `mask_strides` broadcast `mask` to the dimensions of `x`; masked elements are
+/// excluded from the normalization and produce zero, as do fully masked rows.
+///
+/// # Panics
+///
+/// - Row count or row length exceed max size
+/// - Rank exceeds max size
+pub(crate) fn execute<T: FloatElement>(
+    ctx: &Context,
+    x: &Buffer<T>,
+    mask: Option<(&Buffer<bool>, &[usize])>,
+    y: &Buffer<T>,
+    x_strides: &[usize],
+    cols: usize,
+) {
+    if cols == 0 {
+        return;
+    }
+
+    let rank = u32::try_from(x_strides.len()).expect("rank exceeds max size");
+    let rows = u32::try_from(y.len() / cols).expect("row count exceeds max size");
+    let cols = u32::try_from(cols).expect("row length exceeds max size");
+
+    if rows == 0 {
+        return;
+    }
+
+    let pipeline = ctx.get_or_create_pipeline(
+        TypeId::of::<Softmax<T>>(),
+        Softmax::<T>::wgsl,
+        Softmax::<T>::LABEL,
+    );
+
+    let masked = u32::from(mask.is_some());
+    let unmasked;
+    let (mask, mask_strides) = if let Some((mask, strides)) = mask {
+        (mask.inner(), strides)
+    } else {
+        unmasked = ctx.create_storage_buffer(&[1u32]);
+        (&unmasked, x_strides)
+    };
+
+    let x_strides = ctx.create_storage_buffer(&crate::kernel::convert_strides(x_strides));
+    let mask_strides = ctx.create_storage_buffer(&crate::kernel::convert_strides(mask_strides));
+    let params = ctx.create_uniform_buffer(&Params {
+        rank,
+        rows,
+        cols,
+        masked,
+    });
+
+    crate::kernel::dispatch(
+        ctx,
+        &pipeline,
+        Softmax::<T>::LABEL,
+        &[
+            x.inner(),
+            mask,
+            y.inner(),
+            &x_strides,
+            &mask_strides,
+            &params,
+        ],
+        (rows.min(MAX_WORKGROUPS), rows.div_ceil(MAX_WORKGROUPS), 1),
+    );
+}
@@ -1,18 +1,320 @@
 //! Kernel operations.
 
-use crate::element::{FloatElement, IntegerElement, LogicalElement, NumericElement, SignedElement};
-use crate::kernel::{constant, copy, linalg, math, nn, reduction};
+use crate::element::{
+    Df64, FloatElement, IntegerElement, LogicalElement, NumericElement, SignedElement,
+};
+use crate::kernel::{
+    compact, constant, conv, copy, detection, df64, diag, diffusion, distribution, group, guard,
+    histogram, index, linalg, math, nn, pad, random, reduction, rl, rolling, sort, spatial, ssm,
+    stats, strided, texture, triangle,
+};
 use crate::{Buffer, Context, Element};
 
 /// Fills buffer with constant value.
-pub(crate) fn constant<T: Element>(ctx: &Context, buffer: &Buffer<T>, value: &wgpu::Buffer) {
+pub(crate) fn constant<T: Element>(ctx: &Context, buffer: &Buffer<T>, value: T) {
     constant::execute::<T>(ctx, buffer, value);
 }
 
 /// Copies buffer contents from source to destination.
 pub(crate) fn copy<T: Element>(ctx: &Context, src: &Buffer<T>, dst: &Buffer<T>) {
-    let size_bytes = (src.len() * core::mem::size_of::<T>()) as u64;
-    copy::execute(ctx, src.inner(), dst.inner(), size_bytes);
+    copy_into(ctx, src, dst, 0);
+}
+
+/// Copies buffer contents from source into destination starting at element `offset`.
+pub(crate) fn copy_into<T: Element>(
+    ctx: &Context,
+    src: &Buffer<T>,
+    dst: &Buffer<T>,
+    offset: usize,
+) {
+    let native_size = core::mem::size_of::<T::Native>() as u64;
+    copy::execute(
+        ctx,
+        src.inner(),
+        dst.inner(),
+        offset as u64 * native_size,
+        src.len() as u64 * native_size,
+    );
+}
+
+/// Copies a strided view of `src` into contiguous `dst`.
+pub(crate) fn strided_copy<T: Element>(
+    ctx: &Context,
+    src: &Buffer<T>,
+    dst: &Buffer<T>,
+    src_strides: &[usize],
+    dst_strides: &[usize],
+    offset: usize,
+) {
+    strided::execute::<T>(ctx, src, dst, src_strides, dst_strides, offset);
+}
+
+/// Pads a strided view of `src` into contiguous `dst`, filling borders according to `mode`.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn pad<T: Element>(
+    ctx: &Context,
+    src: &Buffer<T>,
+    dst: &Buffer<T>,
+    src_strides: &[usize],
+    dst_strides: &[usize],
+    dimensions: &[usize],
+    before: &[usize],
+    offset: usize,
+    mode: u32,
+) {
+    pad::execute::<T>(
+        ctx,
+        src,
+        dst,
+        src_strides,
+        dst_strides,
+        dimensions,
+        before,
+        offset,
+        mode,
+    );
+}
+
+/// Converts an RGBA texture into planar normalized RGB: `y = (texel - mean) · scale`.
+pub(crate) fn from_texture(
+    ctx: &Context,
+    view: &wgpu::TextureView,
+    y: &Buffer<f32>,
+    width: u32,
+    height: u32,
+    mean: [f32; 3],
+    scale: [f32; 3],
+) {
+    texture::from_texture(ctx, view, y, width, height, mean, scale);
+}
+
+/// Resizes and pads an RGBA texture into one planar normalized RGB image of `y`.
+pub(crate) fn letterbox(
+    ctx: &Context,
+    view: &wgpu::TextureView,
+    y: &Buffer<f32>,
+    offset: usize,
+    input: (u32, u32),
+    output: (u32, u32),
+    origin: (u32, u32),
+    resized: (u32, u32),
+    fill: f32,
+    mean: [f32; 3],
+    scale: [f32; 3],
+) {
+    texture::letterbox(
+        ctx, view, y, offset, input, output, origin, resized, fill, mean, scale,
+    );
+}
+
+/// Pairwise intersection over union of batched `(x1, y1, x2, y2)` box sets.
+pub(crate) fn box_iou(
+    ctx: &Context,
+    a: &Buffer<f32>,
+    b: &Buffer<f32>,
+    y: &Buffer<f32>,
+    batch: usize,
+    n: usize,
+    m: usize,
+) {
+    detection::box_iou(ctx, a, b, y, batch, n, m);
+}
+
+/// Greedy non-maximum suppression of `boxes` visited in `order`.
+pub(crate) fn nms(
+    ctx: &Context,
+    boxes: &Buffer<f32>,
+    order: &Buffer<u32>,
+    removed: &Buffer<u32>,
+    keep: &Buffer<u32>,
+    count: &Buffer<u32>,
+    threshold: f32,
+) {
+    detection::nms(ctx, boxes, order, removed, keep, count, threshold);
+}
+
+/// ROI align of `[n, c, h, w]` features over `(batch, x1, y1, x2, y2)` regions.
+pub(crate) fn roi_align(
+    ctx: &Context,
+    features: &Buffer<f32>,
+    boxes: &Buffer<f32>,
+    y: &Buffer<f32>,
+    dimensions: [usize; 4],
+    output_size: (usize, usize),
+    spatial_scale: f32,
+    sampling_ratio: usize,
+) {
+    detection::roi_align(
+        ctx,
+        features,
+        boxes,
+        y,
+        dimensions,
+        output_size,
+        spatial_scale,
+        sampling_ratio,
+    );
+}
+
+/// Local correlation volume between feature maps `a` and `b`.
+pub(crate) fn correlation(
+    ctx: &Context,
+    a: &Buffer<f32>,
+    b: &Buffer<f32>,
+    y: &Buffer<f32>,
+    dimensions: [usize; 4],
+    displacement: usize,
+) {
+    spatial::correlation(ctx, a, b, y, dimensions, displacement);
+}
+
+/// Dilated 1D convolution of `[batch, in_channels, length]` input `x`, zero padded by
+/// `pad_left` steps ahead of it.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn conv1d(
+    ctx: &Context,
+    x: &Buffer<f32>,
+    weight: &Buffer<f32>,
+    bias: Option<&Buffer<f32>>,
+    y: &Buffer<f32>,
+    input: [usize; 2],
+    output: [usize; 2],
+    kernel: usize,
+    stride: usize,
+    dilation: usize,
+    pad_left: usize,
+) {
+    conv::conv1d(
+        ctx, x, weight, bias, y, input, output, kernel, stride, dilation, pad_left,
+    );
+}
+
+/// Depthwise 2D convolution of NCHW or NHWC input `x` with per-channel filters.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn depthwise_conv2d(
+    ctx: &Context,
+    x: &Buffer<f32>,
+    weight: &Buffer<f32>,
+    bias: Option<&Buffer<f32>>,
+    y: &Buffer<f32>,
+    dimensions: [usize; 3],
+    kernel_size: (usize, usize),
+    stride: (usize, usize),
+    padding: (usize, usize),
+    channels_last: bool,
+) {
+    conv::depthwise_conv2d(
+        ctx,
+        x,
+        weight,
+        bias,
+        y,
+        dimensions,
+        kernel_size,
+        stride,
+        padding,
+        channels_last,
+    );
+}
+
+/// Grid sample: bilinear or nearest reads of `x` at normalized `grid` locations.
+pub(crate) fn grid_sample(
+    ctx: &Context,
+    x: &Buffer<f32>,
+    grid: &Buffer<f32>,
+    y: &Buffer<f32>,
+    dimensions: [usize; 4],
+    output_size: (usize, usize),
+    nearest: bool,
+) {
+    spatial::grid_sample(ctx, x, grid, y, dimensions, output_size, nearest);
+}
+
+/// Row scatter: `dst[indices[i]] = values[i]`.
+pub(crate) fn index_put<T: Element>(
+    ctx: &Context,
+    indices: &Buffer<u32>,
+    values: &Buffer<T>,
+    dst: &Buffer<T>,
+    rows: usize,
+    row_len: usize,
+) {
+    index::put(ctx, indices, values, dst, rows, row_len);
+}
+
+/// Row gather sized by a GPU count: `dst[i] = src[indices[i]]` for `i < count[0]`.
+pub(crate) fn take_rows<T: Element>(
+    ctx: &Context,
+    src: &Buffer<T>,
+    indices: &Buffer<u32>,
+    count: &Buffer<u32>,
+    dst: &Buffer<T>,
+    rows: usize,
+    row_len: usize,
+) {
+    index::take(ctx, src, indices, count, dst, rows, row_len);
+}
+
+/// Counts the non-zero elements of each block of `x` into `counts`.
+pub(crate) fn count_nonzero<T: Element>(ctx: &Context, x: &Buffer<T>, counts: &Buffer<u32>) {
+    compact::count(ctx, x, counts);
+}
+
+/// Writes the coordinates of the non-zero elements of `x` as rows of `indices`, starting
+/// each block at its entry of `offsets`, and their total into `count[0]`.
+pub(crate) fn write_nonzero<T: Element>(
+    ctx: &Context,
+    x: &Buffer<T>,
+    offsets: &Buffer<u32>,
+    indices: &Buffer<u32>,
+    count: &Buffer<u32>,
+    strides: &[usize],
+) {
+    compact::write(ctx, x, offsets, indices, count, strides);
+}
+
+/// Raises `flag[0]` if any element of `condition` is true.
+pub(crate) fn set_if_any(ctx: &Context, condition: &Buffer<bool>, flag: &Buffer<bool>) {
+    guard::set_if_any(ctx, condition, flag);
+}
+
+/// Gather along `axis`: `dst[..., i, ...] = src[..., indices[..., i, ...], ...]`.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn gather<T: Element>(
+    ctx: &Context,
+    src: &Buffer<T>,
+    indices: &Buffer<u32>,
+    dst: &Buffer<T>,
+    src_strides: &[usize],
+    dst_strides: &[usize],
+    offset: usize,
+    axis: usize,
+    size: usize,
+) {
+    index::gather(
+        ctx,
+        src,
+        indices,
+        dst,
+        src_strides,
+        dst_strides,
+        offset,
+        axis,
+        size,
+    );
+}
+
+/// Masked copy: `dst = mask ? src : dst`.
+pub(crate) fn masked_copy<T: Element>(
+    ctx: &Context,
+    src: &Buffer<T>,
+    mask: &Buffer<bool>,
+    dst: &Buffer<T>,
+    src_strides: &[usize],
+    mask_strides: &[usize],
+    dst_strides: &[usize],
+) {
+    index::masked_copy(ctx, src, mask, dst, src_strides, mask_strides, dst_strides);
 }
 
 /// Batched matrix multiplication: `C = A × B`.
@@ -37,6 +339,68 @@ pub(crate) fn matmul<T: FloatElement>(
         c_dims,
         transpose_a,
         transpose_b,
+        1,
+        None,
+    );
+}
+
+/// Batched scaled matrix multiplication: `C = alpha[i] · A × B + beta[i] · C` per batch `i`.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn matmul_scaled<T: FloatElement>(
+    ctx: &Context,
+    a: &Buffer<T>,
+    b: &Buffer<T>,
+    c: &Buffer<T>,
+    alpha: &Buffer<T>,
+    beta: &Buffer<T>,
+    a_dims: &[usize],
+    b_dims: &[usize],
+    c_dims: &[usize],
+    transpose_a: bool,
+    transpose_b: bool,
+) {
+    linalg::matmul::execute::<T>(
+        ctx,
+        a,
+        b,
+        c,
+        a_dims,
+        b_dims,
+        c_dims,
+        transpose_a,
+        transpose_b,
+        1,
+        Some((alpha, beta)),
+    );
+}
+
+/// Batched matrix multiplication with the output columns packed into `parts` matrices.
+///
+/// `c_dims` are the unpacked output dimensions `[..., m, n]`.
+pub(crate) fn matmul_packed<T: FloatElement>(
+    ctx: &Context,
+    a: &Buffer<T>,
+    b: &Buffer<T>,
+    c: &Buffer<T>,
+    a_dims: &[usize],
+    b_dims: &[usize],
+    c_dims: &[usize],
+    transpose_a: bool,
+    transpose_b: bool,
+    parts: usize,
+) {
+    linalg::matmul::execute::<T>(
+        ctx,
+        a,
+        b,
+        c,
+        a_dims,
+        b_dims,
+        c_dims,
+        transpose_a,
+        transpose_b,
+        parts,
+        None,
     );
 }
 
@@ -469,6 +833,238 @@ pub(crate) fn softplus<T: FloatElement>(ctx: &Context, x: &Buffer<T>, y: &Buffer
     nn::activation::softplus::execute(ctx, x, y, 0.0, 0.0);
 }
 
+/// Softmax over the last axis: `y = eˣ / Σ eˣ`, excluding masked elements.
+pub(crate) fn softmax<T: FloatElement>(
+    ctx: &Context,
+    x: &Buffer<T>,
+    mask: Option<(&Buffer<bool>, &[usize])>,
+    y: &Buffer<T>,
+    x_strides: &[usize],
+    cols: usize,
+) {
+    nn::softmax::execute(ctx, x, mask, y, x_strides, cols);
+}
+
+/// Embedding bag: `y[b] = Σ weights[indices[i]]` over bag `b`, averaged if `mean`.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn embedding_bag<T: FloatElement>(
+    ctx: &Context,
+    weights: &Buffer<T>,
+    indices: &Buffer<u32>,
+    offsets: &Buffer<u32>,
+    y: &Buffer<T>,
+    rows: usize,
+    dim: usize,
+    mean: bool,
+) {
+    nn::embedding::embedding_bag(ctx, weights, indices, offsets, y, rows, dim, mean, None);
+}
+
+/// Hashed embedding bag: `y[b] = Σ ±weights[hash(ids[i]) % rows]` over bag `b`.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn hashed_embedding_bag<T: FloatElement>(
+    ctx: &Context,
+    weights: &Buffer<T>,
+    ids: &Buffer<u32>,
+    offsets: &Buffer<u32>,
+    y: &Buffer<T>,
+    rows: usize,
+    dim: usize,
+    mean: bool,
+    seed: u32,
+) {
+    nn::embedding::embedding_bag(ctx, weights, ids, offsets, y, rows, dim, mean, Some(seed));
+}
+
+/// Capacity-limited assignment of `[tokens, k]` expert choices to expert slots.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn moe_route(
+    ctx: &Context,
+    choices: &Buffer<u32>,
+    slots: &Buffer<u32>,
+    sources: &Buffer<u32>,
+    counts: &Buffer<u32>,
+    tokens: usize,
+    k: usize,
+    experts: usize,
+    capacity: usize,
+) {
+    nn::moe::route(
+        ctx, choices, slots, sources, counts, tokens, k, experts, capacity,
+    );
+}
+
+/// Fused bias-dropout-residual: `y = dropout(x + bias, p) + residual`.
+pub(crate) fn bias_dropout_residual<T: FloatElement>(
+    ctx: &Context,
+    x: &Buffer<T>,
+    bias: &Buffer<T>,
+    residual: &Buffer<T>,
+    y: &Buffer<T>,
+    x_strides: &[usize],
+    bias_strides: &[usize],
+    residual_strides: &[usize],
+    y_strides: &[usize],
+    p: f32,
+    seed: u32,
+) {
+    nn::dropout::bias_dropout_residual(
+        ctx,
+        x,
+        bias,
+        residual,
+        y,
+        x_strides,
+        bias_strides,
+        residual_strides,
+        y_strides,
+        p,
+        seed,
+    );
+}
+
+/// Folds a logits chunk into the running cross-entropy row statistics.
+pub(crate) fn cross_entropy_accumulate<T: FloatElement>(
+    ctx: &Context,
+    x: &Buffer<T>,
+    targets: &Buffer<u32>,
+    row_max: &Buffer<T>,
+    row_sum: &Buffer<T>,
+    target_logit: &Buffer<T>,
+    rows: usize,
+    cols: usize,
+    offset: usize,
+) {
+    nn::cross_entropy::accumulate(
+        ctx,
+        x,
+        targets,
+        row_max,
+        row_sum,
+        target_logit,
+        rows,
+        cols,
+        offset,
+    );
+}
+
+/// Cross-entropy loss per row: `loss = log(Σ eˣ) - x[target]`.
+pub(crate) fn cross_entropy_loss<T: FloatElement>(
+    ctx: &Context,
+    row_max: &Buffer<T>,
+    row_sum: &Buffer<T>,
+    target_logit: &Buffer<T>,
+    loss: &Buffer<T>,
+    rows: usize,
+) {
+    nn::cross_entropy::loss(ctx, row_max, row_sum, target_logit, loss, rows);
+}
+
+/// Cross-entropy logits gradient of a chunk: `g = softmax(x) - onehot(target)`.
+pub(crate) fn cross_entropy_grad<T: FloatElement>(
+    ctx: &Context,
+    x: &Buffer<T>,
+    targets: &Buffer<u32>,
+    row_max: &Buffer<T>,
+    row_sum: &Buffer<T>,
+    g: &Buffer<T>,
+    rows: usize,
+    cols: usize,
+    offset: usize,
+) {
+    nn::cross_entropy::grad(ctx, x, targets, row_max, row_sum, g, rows, cols, offset);
+}
+
+/// Temperature-scaled KL divergence from teacher to student logits, with student gradient.
+pub(crate) fn kl_div<T: FloatElement>(
+    ctx: &Context,
+    student: &Buffer<T>,
+    teacher: &Buffer<T>,
+    loss: &Buffer<T>,
+    g: &Buffer<T>,
+    rows: usize,
+    cols: usize,
+    temperature: f32,
+) {
+    nn::distill::kl_div(ctx, student, teacher, loss, g, rows, cols, temperature);
+}
+
+/// Fused diffusion sampler update: `y = a·x + b·eps + c·z`, `z ~ N(0, 1)`.
+pub(crate) fn diffusion_step<T: FloatElement>(
+    ctx: &Context,
+    x: &Buffer<T>,
+    eps: &Buffer<T>,
+    y: &Buffer<T>,
+    a: f32,
+    b: f32,
+    c: f32,
+    seed: u32,
+) {
+    diffusion::step(ctx, x, eps, y, a, b, c, seed);
+}
+
+/// Chunked dot product: `partial[c] = Σ x[i]·y[i]` over each chunk of consecutive elements.
+pub(crate) fn dot<T: FloatElement>(
+    ctx: &Context,
+    x: &Buffer<T>,
+    y: &Buffer<T>,
+    partial: &Buffer<T>,
+) {
+    linalg::vector::dot(ctx, x, y, partial);
+}
+
+/// Scaled accumulation in place: `y += scale·alpha[0]·x`.
+pub(crate) fn axpy<T: FloatElement>(
+    ctx: &Context,
+    alpha: &Buffer<T>,
+    scale: f32,
+    x: &Buffer<T>,
+    y: &Buffer<T>,
+) {
+    linalg::vector::axpy(ctx, alpha, scale, x, y);
+}
+
+/// Batched symmetric eigendecomposition: `x = vectors·diag(values)·vectorsᵀ` per matrix.
+pub(crate) fn symmetric_eigen<T: FloatElement>(
+    ctx: &Context,
+    x: &Buffer<T>,
+    values: &Buffer<T>,
+    vectors: &Buffer<T>,
+    n: usize,
+) {
+    linalg::eigen::execute(ctx, x, values, vectors, n);
+}
+
+/// Merges batch moments into running moments in place.
+pub(crate) fn merge_moments<T: FloatElement>(
+    ctx: &Context,
+    mean: &Buffer<T>,
+    var: &Buffer<T>,
+    batch_mean: &Buffer<T>,
+    batch_var: &Buffer<T>,
+    running: f32,
+    batch: f32,
+    cross: f32,
+) {
+    stats::merge(ctx, mean, var, batch_mean, batch_var, running, batch, cross);
+}
+
+/// Standardization with broadcast moments: `y = clamp((x - mean) / sqrt(var + eps), ±clip)`.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn standardize<T: FloatElement>(
+    ctx: &Context,
+    x: &Buffer<T>,
+    mean: &Buffer<T>,
+    var: &Buffer<T>,
+    y: &Buffer<T>,
+    dimensions: &[usize],
+    stat_strides: &[usize],
+    eps: f32,
+    clip: f32,
+) {
+    stats::standardize(ctx, x, mean, var, y, dimensions, stat_strides, eps, clip);
+}
+
 /// Max reduction along specified axes: `y = max(x, axes)`.
 pub(crate) fn max_reduce<T: NumericElement>(
     ctx: &Context,
@@ -511,7 +1107,70 @@ pub(crate) fn min_reduce<T: NumericElement>(
     );
 }
 
-/// Sum reduction along specified axes: `y = sum(x, axes)`.
+/// Product reduction along specified axes: `y = prod(x, axes)`.
+pub(crate) fn prod_reduce<T: NumericElement>(
+    ctx: &Context,
+    x: &Buffer<T>,
+    y: &Buffer<T>,
+    x_dimensions: &[usize],
+    x_strides: &[usize],
+    y_strides: &[usize],
+    axes: &[usize],
+) {
+    reduction::execute::<reduction::ProdReduce<T>, T>(
+        ctx,
+        x,
+        y,
+        x_dimensions,
+        x_strides,
+        y_strides,
+        axes,
+    );
+}
+
+/// Index of the largest (`max`) or smallest element along the middle axis of
+/// `[outer, size, inner]` input.
+pub(crate) fn arg_reduce<T: NumericElement>(
+    ctx: &Context,
+    x: &Buffer<T>,
+    y: &Buffer<u32>,
+    size: usize,
+    inner: usize,
+    max: bool,
+) {
+    reduction::arg::execute(ctx, x, y, size, inner, max);
+}
+
+/// One pass of a multi-pass reduction: each chunk of consecutive elements within the rows
+/// of `[rows, row_len]` `x` reduced by `op` into `y`, divided by `divisor` if given.
+pub(crate) fn full_reduce<T: NumericElement>(
+    ctx: &Context,
+    x: &Buffer<T>,
+    y: &Buffer<T>,
+    rows: usize,
+    row_len: usize,
+    op: reduction::full::FullReduction,
+    divisor: Option<usize>,
+) {
+    reduction::full::execute(ctx, x, y, rows, row_len, op, divisor);
+}
+
+/// Running sum or product by `op` along the middle axis of `[outer, size, inner]` input,
+/// excluding each element from its own output if `exclusive`.
+pub(crate) fn prefix_scan<T: NumericElement>(
+    ctx: &Context,
+    x: &Buffer<T>,
+    y: &Buffer<T>,
+    size: usize,
+    inner: usize,
+    op: reduction::scan::Scan,
+    exclusive: bool,
+) {
+    reduction::scan::execute(ctx, x, y, size, inner, op, exclusive);
+}
+
+/// Sum reduction along specified axes: `y = sum(x, axes)`, divided by the reduction length
+/// minus the correction if `normalize` is given.
 pub(crate) fn sum_reduce<T: NumericElement>(
     ctx: &Context,
     x: &Buffer<T>,
@@ -520,7 +1179,7 @@ pub(crate) fn sum_reduce<T: NumericElement>(
     x_strides: &[usize],
     y_strides: &[usize],
     axes: &[usize],
-    normalize: bool,
+    normalize: Option<u32>,
 ) {
     reduction::sum::execute::<T>(
         ctx,
@@ -533,3 +1192,347 @@ pub(crate) fn sum_reduce<T: NumericElement>(
         normalize,
     );
 }
+
+/// Mean and variance along specified axes in one pass: `mean = mean(x, axes)`,
+/// `var = Σ (x - mean)² / (n - correction)`.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn moments<T: FloatElement>(
+    ctx: &Context,
+    x: &Buffer<T>,
+    mean: &Buffer<T>,
+    var: &Buffer<T>,
+    x_dimensions: &[usize],
+    x_strides: &[usize],
+    y_strides: &[usize],
+    axes: &[usize],
+    correction: u32,
+) {
+    reduction::moments::execute(
+        ctx,
+        x,
+        mean,
+        var,
+        x_dimensions,
+        x_strides,
+        y_strides,
+        axes,
+        correction,
+    );
+}
+
+/// Histogram over equal-width bins: `counts[bin(x)] += 1`.
+pub(crate) fn histogram<T: FloatElement>(
+    ctx: &Context,
+    x: &Buffer<T>,
+    counts: &Buffer<u32>,
+    min: f32,
+    max: f32,
+) {
+    histogram::execute(ctx, x, counts, min, max);
+}
+
+/// Group reduction: `y[g] = Σ values[i]` over rows with key `g`, averaged if `mean`, read
+/// through `order` unless the keys are already sorted.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn group_reduce<T: FloatElement>(
+    ctx: &Context,
+    values: &Buffer<T>,
+    sorted: &Buffer<u32>,
+    order: Option<&Buffer<u32>>,
+    y: &Buffer<T>,
+    counts: &Buffer<u32>,
+    cols: usize,
+    mean: bool,
+) {
+    group::execute(ctx, values, sorted, order, y, counts, cols, mean);
+}
+
+/// Gradient histogram: `hist[f, b] = (Σ grad, Σ hess)` over rows whose feature `f` is in bin `b`.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn gradient_histogram(
+    ctx: &Context,
+    sorted: &Buffer<u32>,
+    order: &Buffer<u32>,
+    grad: &Buffer<f32>,
+    hess: &Buffer<f32>,
+    hist: &Buffer<f32>,
+    rows: usize,
+    features: usize,
+    bins: usize,
+) {
+    histogram::gradient_histogram(ctx, sorted, order, grad, hess, hist, rows, features, bins);
+}
+
+/// Sorts each row along the last axis and keeps the first `k` entries.
+pub(crate) fn sort<T: NumericElement>(
+    ctx: &Context,
+    x: &Buffer<T>,
+    keys: &Buffer<T>,
+    idx: &Buffer<u32>,
+    values: &Buffer<T>,
+    indices: &Buffer<u32>,
+    cols: usize,
+    k: usize,
+    descending: bool,
+    stable: bool,
+) {
+    sort::execute(
+        ctx, x, keys, idx, values, indices, cols, k, descending, stable,
+    );
+}
+
+/// Element-wise df64 addition: `c = a + b`.
+pub(crate) fn df64_add(
+    ctx: &Context,
+    a: &Buffer<Df64>,
+    b: &Buffer<Df64>,
+    c: &Buffer<Df64>,
+    a_strides: &[usize],
+    b_strides: &[usize],
+    c_strides: &[usize],
+) {
+    df64::add::execute(ctx, a, b, c, a_strides, b_strides, c_strides);
+}
+
+/// Element-wise df64 subtraction: `c = a - b`.
+pub(crate) fn df64_sub(
+    ctx: &Context,
+    a: &Buffer<Df64>,
+    b: &Buffer<Df64>,
+    c: &Buffer<Df64>,
+    a_strides: &[usize],
+    b_strides: &[usize],
+    c_strides: &[usize],
+) {
+    df64::sub::execute(ctx, a, b, c, a_strides, b_strides, c_strides);
+}
+
+/// Element-wise df64 multiplication: `c = a * b`.
+pub(crate) fn df64_mul(
+    ctx: &Context,
+    a: &Buffer<Df64>,
+    b: &Buffer<Df64>,
+    c: &Buffer<Df64>,
+    a_strides: &[usize],
+    b_strides: &[usize],
+    c_strides: &[usize],
+) {
+    df64::mul::execute(ctx, a, b, c, a_strides, b_strides, c_strides);
+}
+
+/// df64 sum reduction along specified axes: `y = sum(x, axes)`, divided by the reduction
+/// length minus the correction if `normalize` is given.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn df64_sum_reduce(
+    ctx: &Context,
+    x: &Buffer<Df64>,
+    y: &Buffer<Df64>,
+    x_dimensions: &[usize],
+    x_strides: &[usize],
+    y_strides: &[usize],
+    axes: &[usize],
+    normalize: Option<u32>,
+) {
+    df64::sum(
+        ctx,
+        x,
+        y,
+        x_dimensions,
+        x_strides,
+        y_strides,
+        axes,
+        normalize,
+    );
+}
+
+/// Batched df64 matrix multiplication: `C = A × B`.
+pub(crate) fn df64_matmul(
+    ctx: &Context,
+    a: &Buffer<Df64>,
+    b: &Buffer<Df64>,
+    c: &Buffer<Df64>,
+    a_dims: &[usize],
+    b_dims: &[usize],
+    c_dims: &[usize],
+    transpose_a: bool,
+    transpose_b: bool,
+) {
+    df64::matmul(
+        ctx,
+        a,
+        b,
+        c,
+        a_dims,
+        b_dims,
+        c_dims,
+        transpose_a,
+        transpose_b,
+    );
+}
+
+/// Converts `f32` elements to df64: `y = x`.
+pub(crate) fn df64_from_f32(ctx: &Context, x: &Buffer<f32>, y: &Buffer<Df64>) {
+    df64::from_f32(ctx, x, y);
+}
+
+/// Rounds df64 elements to `f32`: `y = hi + lo`.
+pub(crate) fn df64_to_f32(ctx: &Context, x: &Buffer<Df64>, y: &Buffer<f32>) {
+    df64::to_f32(ctx, x, y);
+}
+
+/// Random matrices with an entry `pattern`: `y[..., i, j] ~ pattern(i, j)`.
+pub(crate) fn random_matrix(
+    ctx: &Context,
+    y: &Buffer<f32>,
+    rows: usize,
+    cols: usize,
+    pattern: &random::Pattern,
+    seed: u32,
+) {
+    random::matrix(ctx, y, rows, cols, pattern, seed);
+}
+
+/// Random orthogonal matrices: `q = H₁·…·Hₙ` with Householder reflections `H`.
+pub(crate) fn random_orthogonal(ctx: &Context, q: &Buffer<f32>, n: usize, seed: u32) {
+    random::householder(ctx, q, n, seed);
+}
+
+/// Random bits: `y[i] = random_u32(seed, i)`.
+pub(crate) fn random_bits(ctx: &Context, y: &Buffer<u32>, seed: u32) {
+    random::bits(ctx, y, seed);
+}
+
+/// Generalized advantage estimation over `[steps, envs]` rollouts, scanning steps in reverse.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn gae<T: FloatElement>(
+    ctx: &Context,
+    rewards: &Buffer<T>,
+    values: &Buffer<T>,
+    dones: &Buffer<T>,
+    last_values: &Buffer<T>,
+    advantages: &Buffer<T>,
+    returns: &Buffer<T>,
+    gamma: f32,
+    lambda: f32,
+) {
+    rl::gae(
+        ctx,
+        rewards,
+        values,
+        dones,
+        last_values,
+        advantages,
+        returns,
+        gamma,
+        lambda,
+    );
+}
+
+/// Clipped PPO surrogate: per-element loss `-min(r·A, clamp(r, 1 - ε, 1 + ε)·A)` and its
+/// gradient with respect to the ratio.
+pub(crate) fn ppo_clip<T: FloatElement>(
+    ctx: &Context,
+    ratio: &Buffer<T>,
+    advantages: &Buffer<T>,
+    loss: &Buffer<T>,
+    grad: &Buffer<T>,
+    epsilon: f32,
+) {
+    rl::ppo_clip(ctx, ratio, advantages, loss, grad, epsilon);
+}
+
+/// Rolling window mean, or standard deviation with variance `correction`, along the leading
+/// axis of `[steps, inner]` series.
+pub(crate) fn rolling<T: FloatElement>(
+    ctx: &Context,
+    x: &Buffer<T>,
+    y: &Buffer<T>,
+    inner: usize,
+    window: usize,
+    correction: Option<u32>,
+) {
+    rolling::rolling(ctx, x, y, inner, window, correction);
+}
+
+/// Diagonal matrix: `y[i, i] = x[i]`, zero elsewhere.
+pub(crate) fn diagflat<T: Element>(ctx: &Context, x: &Buffer<T>, y: &Buffer<T>, n: usize) {
+    diag::diagflat(ctx, x, y, n);
+}
+
+/// Triangular mask: keeps the lower (or upper) triangle of each `[rows, cols]` matrix
+/// relative to `diagonal`, zeroing the rest.
+pub(crate) fn triangle<T: Element>(
+    ctx: &Context,
+    x: &Buffer<T>,
+    y: &Buffer<T>,
+    rows: usize,
+    cols: usize,
+    diagonal: i32,
+    upper: bool,
+) {
+    triangle::triangle(ctx, x, y, rows, cols, diagonal, upper);
+}
+
+/// Categorical sampling: one class per row of `[rows, classes]` logits, by Gumbel-max.
+pub(crate) fn categorical_sample<T: FloatElement>(
+    ctx: &Context,
+    logits: &Buffer<T>,
+    y: &Buffer<u32>,
+    classes: usize,
+    seed: u32,
+) {
+    distribution::categorical_sample(ctx, logits, y, classes, seed);
+}
+
+/// Multinomial draws with replacement: `y[r, s] ~ weights[r] / Σ weights[r]`.
+pub(crate) fn multinomial<T: FloatElement>(
+    ctx: &Context,
+    weights: &Buffer<T>,
+    y: &Buffer<u32>,
+    classes: usize,
+    samples: usize,
+    seed: u32,
+) {
+    distribution::multinomial(ctx, weights, y, classes, samples, seed);
+}
+
+/// Categorical log-probabilities of `actions`, or entropies if `None`, per logits row.
+pub(crate) fn categorical_score<T: FloatElement>(
+    ctx: &Context,
+    logits: &Buffer<T>,
+    actions: Option<&Buffer<u32>>,
+    y: &Buffer<T>,
+    classes: usize,
+) {
+    distribution::categorical_score(ctx, logits, actions, y, classes);
+}
+
+/// Normal distribution sampling, log-density or entropy, element-wise over `mean` and `std`.
+pub(crate) fn normal<T: FloatElement>(
+    ctx: &Context,
+    mean: &Buffer<T>,
+    std: &Buffer<T>,
+    y: &Buffer<T>,
+    op: &distribution::NormalOp<'_, T>,
+) {
+    distribution::normal(ctx, mean, std, y, op);
+}
+
+/// Selective scan of a state-space model: `h_t = exp(Δ_t·A)·h_{t-1} + Δ_t·B_t·x_t` and
+/// `y_t = C_t·h_t + D·x_t` over `[batch, channels, length]` sequences.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn selective_scan<T: FloatElement>(
+    ctx: &Context,
+    x: &Buffer<T>,
+    delta: &Buffer<T>,
+    a: &Buffer<T>,
+    b: &Buffer<T>,
+    c: &Buffer<T>,
+    d: Option<&Buffer<T>>,
+    y: &Buffer<T>,
+    state: &Buffer<T>,
+    initial: bool,
+    dimensions: [usize; 4],
+) {
+    ssm::selective_scan(ctx, x, delta, a, b, c, d, y, state, initial, dimensions);
+}
@@ -0,0 +1,165 @@
+//! Padding kernel.
+
+use core::any::TypeId;
+use core::marker::PhantomData;
+
+use alloc::format;
+use alloc::string::String;
+
+use bytemuck::{Pod, Zeroable};
+
+use crate::kernel::{Kernel, MAX_WORKGROUPS, WORKGROUP_SIZE};
+use crate::{Buffer, Context, Element};
+
+/// Padded positions are left untouched.
+pub(crate) const MODE_CONSTANT: u32 = 0;
+
+/// Padded positions mirror the input without repeating the edge.
+pub(crate) const MODE_REFLECT: u32 = 1;
+
+/// Padded positions repeat the edge of the input.
+pub(crate) const MODE_REPLICATE: u32 = 2;
+
+/// Kernel parameters passed to shader as uniform.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Pod, Zeroable)]
+struct Params {
+    rank: u32,
+    len: u32,
+    offset: u32,
+    mode: u32,
+}
+
+/// Padding kernel: `dst[i] = src[offset + Σ source(coord(i)ₖ - beforeₖ) · src_strides[k]]`.
+///
+/// `source` maps a coordinate outside the input back inside it according to the mode. In
+/// constant mode, outputs outside the input are skipped so that a prefilled value remains.
+struct Pad<T>(PhantomData<T>);
+
+impl<T: Element> Kernel for Pad<T> {
+    const LABEL: &'static str = "pad";
+    type Output = T;
+
+    fn wgsl() -> String {
+        let ty = T::wgsl_type();
+
+        format!(
+            r"
+                struct Params {{
+                    rank: u32,
+                    len: u32,
+                    offset: u32,
+                    mode: u32,
+                }}
+
+                @group(0) @binding(0) var<storage, read> src: array<{ty}>;
+                @group(0) @binding(1) var<storage, read_write> dst: array<{ty}>;
+                @group(0) @binding(2) var<storage, read> src_strides: array<u32>;
+                @group(0) @binding(3) var<storage, read> dst_strides: array<u32>;
+                @group(0) @binding(4) var<storage, read> dimensions: array<u32>;
+                @group(0) @binding(5) var<storage, read> before: array<u32>;
+                @group(0) @binding(6) var<uniform> params: Params;
+
+                @compute @workgroup_size({WORKGROUP_SIZE})
+                fn main(@builtin(global_invocation_id) gid: vec3<u32>) {{
+                    let tid = gid.x + gid.y * {MAX_WORKGROUPS}u * {WORKGROUP_SIZE}u;
+
+                    if tid >= params.len {{
+                        return;
+                    }}
+
+                    var remaining = tid;
+                    var src_idx = params.offset;
+
+                    for (var i = 0u; i < params.rank; i++) {{
+                        let size = i32(dimensions[i]);
+                        var coord = i32(remaining / dst_strides[i]) - i32(before[i]);
+                        remaining = remaining % dst_strides[i];
+
+                        if coord < 0 || coord >= size {{
+                            switch params.mode {{
+                                case {MODE_REFLECT}u: {{
+                                    coord = select(2 * (size - 1) - coord, -coord, coord < 0);
+                                }}
+                                case {MODE_REPLICATE}u: {{
+                                    coord = clamp(coord, 0, size - 1);
+                                }}
+                                default: {{
+                                    return;
+                                }}
+                            }}
+                        }}
+
+                        src_idx += u32(coord) * src_strides[i];
+                    }}
+
+                    dst[tid] = src[src_idx];
+                }}
+            "
+        )
+    }
+}
+
+/// Pads the strided view `(src_strides, offset)` of `src` with `dimensions` into contiguous
+/// `dst`.
+///
+/// `dst_strides` are the contiguous strides of the output and `before` the padding ahead of
+/// each axis. In reflect mode the padding must be smaller than the axis.
+///
+/// # Panics
+///
+/// - Output length exceeds max size
+/// - Output rank exceeds max size
+/// - Offset exceeds max size
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn execute<T: Element>(
+    ctx: &Context,
+    src: &Buffer<T>,
+    dst: &Buffer<T>,
+    src_strides: &[usize],
+    dst_strides: &[usize],
+    dimensions: &[usize],
+    before: &[usize],
+    offset: usize,
+    mode: u32,
+) {
+    let rank = u32::try_from(dst_strides.len()).expect("output rank exceeds max size");
+    let len = u32::try_from(dst.len()).expect("output length exceeds max size");
+    let offset = u32::try_from(offset).expect("offset exceeds max size");
+
+    if len == 0 {
+        return;
+    }
+
+    let pipeline =
+        ctx.get_or_create_pipeline(TypeId::of::<Pad<T>>(), Pad::<T>::wgsl, Pad::<T>::LABEL);
+
+    let src_strides = ctx.create_storage_buffer(&crate::kernel::convert_strides(src_strides));
+    let dst_strides = ctx.create_storage_buffer(&crate::kernel::convert_strides(dst_strides));
+    let dimensions = ctx.create_storage_buffer(&crate::kernel::convert_strides(dimensions));
+    let before = ctx.create_storage_buffer(&crate::kernel::convert_strides(before));
+    let params = ctx.create_uniform_buffer(&Params {
+        rank,
+        len,
+        offset,
+        mode,
+    });
+
+    let (x, y) = crate::kernel::compute_workgroups(len);
+
+    crate::kernel::dispatch(
+        ctx,
+        &pipeline,
+        Pad::<T>::LABEL,
+        &[
+            src.inner(),
+            dst.inner(),
+            &src_strides,
+            &dst_strides,
+            &dimensions,
+            &before,
+            &params,
+        ],
+        (x, y, 1),
+    );
+}
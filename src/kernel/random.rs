@@ -0,0 +1,359 @@
+//! Counter-based random number generation for compute shaders.
+
+use core::any::TypeId;
+
+use alloc::format;
+use alloc::string::String;
+
+use bytemuck::{Pod, Zeroable};
+
+use crate::kernel::{Kernel, MAX_WORKGROUPS, WORKGROUP_SIZE};
+use crate::{Buffer, Context};
+
+/// WGSL helpers producing stateless pseudo-random numbers from `(seed, index)` pairs.
+///
+/// Uses the PCG hash, so every invocation draws independently without shared state and the
+/// same seed always reproduces the same stream.
+pub(crate) const RANDOM_WGSL: &str = r"
+    fn pcg_hash(x: u32) -> u32 {
+        let state = x * 747796405u + 2891336453u;
+        let word = ((state >> ((state >> 28u) + 4u)) ^ state) * 277803737u;
+        return (word >> 22u) ^ word;
+    }
+
+    fn random_u32(seed: u32, index: u32) -> u32 {
+        return pcg_hash(pcg_hash(seed) ^ index);
+    }
+
+    fn random_uniform(seed: u32, index: u32) -> f32 {
+        return f32(random_u32(seed, index) >> 8u) * (1.0 / 16777216.0);
+    }
+
+    fn random_normal(seed: u32, index: u32) -> f32 {
+        let u1 = 1.0 - random_uniform(seed, 2u * index);
+        let u2 = random_uniform(seed, 2u * index + 1u);
+        return sqrt(-2.0 * log(u1)) * cos(6.283185307179586 * u2);
+    }
+";
+
+/// Kernel parameters passed to shader as uniform.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Pod, Zeroable)]
+struct Params {
+    len: u32,
+    seed: u32,
+}
+
+/// Random matrix kernel parameters passed to shader as uniform.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Pod, Zeroable)]
+struct MatrixParams {
+    len: u32,
+    rows: u32,
+    cols: u32,
+    seed: u32,
+    mode: u32,
+    lower: u32,
+    upper: u32,
+    density: f32,
+}
+
+/// Householder kernel parameters passed to shader as uniform.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Pod, Zeroable)]
+struct HouseholderParams {
+    n: u32,
+    rows: u32,
+    seed: u32,
+    step: u32,
+}
+
+/// Entry pattern of the random matrix kernel.
+pub(crate) enum Pattern {
+    /// Square matrices with diagonal `2` and off-diagonal entries uniform in `(-1/n, 1/n)`.
+    Conditioned,
+    /// Standard normal entries within `lower` diagonals below and `upper` above the main
+    /// diagonal, zero elsewhere.
+    Banded { lower: u32, upper: u32 },
+    /// Standard normal entries kept with probability `density`, zero elsewhere.
+    Sparse(f32),
+}
+
+/// Random bits kernel: `y[i] = random_u32(seed, i)`.
+struct RandomBits;
+
+impl Kernel for RandomBits {
+    const LABEL: &'static str = "random_bits";
+    type Output = u32;
+
+    fn wgsl() -> String {
+        format!(
+            r"
+                {RANDOM_WGSL}
+
+                struct Params {{
+                    len: u32,
+                    seed: u32,
+                }}
+
+                @group(0) @binding(0) var<storage, read_write> y: array<u32>;
+                @group(0) @binding(1) var<uniform> params: Params;
+
+                @compute @workgroup_size({WORKGROUP_SIZE})
+                fn main(@builtin(global_invocation_id) gid: vec3<u32>) {{
+                    let tid = gid.x + gid.y * {MAX_WORKGROUPS}u * {WORKGROUP_SIZE}u;
+
+                    if tid >= params.len {{
+                        return;
+                    }}
+
+                    y[tid] = random_u32(params.seed, tid);
+                }}
+            "
+        )
+    }
+}
+
+/// Random matrix kernel over a batch of `[rows, cols]` matrices.
+///
+/// Mode 0 writes a strictly diagonally dominant matrix, mode 1 a banded one and mode 2 a
+/// sparse one, each element from its flat index alone.
+struct RandomMatrix;
+
+impl Kernel for RandomMatrix {
+    const LABEL: &'static str = "random_matrix";
+    type Output = f32;
+
+    fn wgsl() -> String {
+        format!(
+            r"
+                {RANDOM_WGSL}
+
+                struct Params {{
+                    len: u32,
+                    rows: u32,
+                    cols: u32,
+                    seed: u32,
+                    mode: u32,
+                    lower: u32,
+                    upper: u32,
+                    density: f32,
+                }}
+
+                @group(0) @binding(0) var<storage, read_write> y: array<f32>;
+                @group(0) @binding(1) var<uniform> params: Params;
+
+                @compute @workgroup_size({WORKGROUP_SIZE})
+                fn main(@builtin(global_invocation_id) gid: vec3<u32>) {{
+                    let tid = gid.x + gid.y * {MAX_WORKGROUPS}u * {WORKGROUP_SIZE}u;
+
+                    if tid >= params.len {{
+                        return;
+                    }}
+
+                    let i = tid / params.cols % params.rows;
+                    let j = tid % params.cols;
+
+                    switch params.mode {{
+                        case 0u: {{
+                            let u = 2.0 * random_uniform(params.seed, tid) - 1.0;
+                            y[tid] = select(u / f32(params.cols), 2.0, i == j);
+                        }}
+                        case 1u: {{
+                            let inside = j + params.lower >= i && j <= i + params.upper;
+                            y[tid] = select(0.0, random_normal(params.seed, tid), inside);
+                        }}
+                        default: {{
+                            let kept = random_uniform(~params.seed, tid) < params.density;
+                            y[tid] = select(0.0, random_normal(params.seed, tid), kept);
+                        }}
+                    }}
+                }}
+            "
+        )
+    }
+}
+
+/// Householder reflection kernel: `Q ← Q·(I - 2vvᵀ/vᵀv)` for each `n × n` matrix of a batch.
+///
+/// Each thread owns one row of `Q`. The direction `v` of step `step` is standard normal,
+/// drawn per matrix from its element index, so every thread regenerates it instead of sharing
+/// it: one pass over the row accumulates `Q[i]·v` and `vᵀv`, and a second applies the update.
+/// Step 0 reads `Q` as the identity, so the buffer needs no initialization.
+struct Householder;
+
+impl Kernel for Householder {
+    const LABEL: &'static str = "householder";
+    type Output = f32;
+
+    fn wgsl() -> String {
+        format!(
+            r"
+                {RANDOM_WGSL}
+
+                struct Params {{
+                    n: u32,
+                    rows: u32,
+                    seed: u32,
+                    step: u32,
+                }}
+
+                @group(0) @binding(0) var<storage, read_write> q: array<f32>;
+                @group(0) @binding(1) var<uniform> params: Params;
+
+                fn entry(row: u32, j: u32) -> f32 {{
+                    if params.step == 0u {{
+                        return select(0.0, 1.0, row % params.n == j);
+                    }}
+                    return q[row * params.n + j];
+                }}
+
+                @compute @workgroup_size({WORKGROUP_SIZE})
+                fn main(@builtin(global_invocation_id) gid: vec3<u32>) {{
+                    let row = gid.x + gid.y * {MAX_WORKGROUPS}u * {WORKGROUP_SIZE}u;
+
+                    if row >= params.rows {{
+                        return;
+                    }}
+
+                    let n = params.n;
+                    let base = row * n;
+                    let draws = (row / n * n + params.step) * n;
+
+                    var dot = 0.0;
+                    var norm = 0.0;
+                    for (var j = 0u; j < n; j++) {{
+                        let v = random_normal(params.seed, draws + j);
+                        dot += entry(row, j) * v;
+                        norm += v * v;
+                    }}
+
+                    let w = 2.0 * dot / norm;
+                    for (var j = 0u; j < n; j++) {{
+                        q[base + j] = entry(row, j) - w * random_normal(params.seed, draws + j);
+                    }}
+                }}
+            "
+        )
+    }
+}
+
+/// Fills the batch of `[rows, cols]` matrices in `y` with `pattern` drawn from `seed`.
+///
+/// # Panics
+///
+/// - Output length or matrix size exceeds max size
+pub(crate) fn matrix(
+    ctx: &Context,
+    y: &Buffer<f32>,
+    rows: usize,
+    cols: usize,
+    pattern: &Pattern,
+    seed: u32,
+) {
+    let len = u32::try_from(y.len()).expect("output length exceeds max size");
+
+    if len == 0 {
+        return;
+    }
+
+    let pipeline = ctx.get_or_create_pipeline(
+        TypeId::of::<RandomMatrix>(),
+        RandomMatrix::wgsl,
+        RandomMatrix::LABEL,
+    );
+    let (mode, lower, upper, density) = match *pattern {
+        Pattern::Conditioned => (0, 0, 0, 0.0),
+        Pattern::Banded { lower, upper } => (1, lower, upper, 0.0),
+        Pattern::Sparse(density) => (2, 0, 0, density),
+    };
+    let params = ctx.create_uniform_buffer(&MatrixParams {
+        len,
+        rows: u32::try_from(rows).expect("matrix size exceeds max size"),
+        cols: u32::try_from(cols).expect("matrix size exceeds max size"),
+        seed,
+        mode,
+        lower,
+        upper,
+        density,
+    });
+
+    let (x, y_groups) = crate::kernel::compute_workgroups(len);
+
+    crate::kernel::dispatch(
+        ctx,
+        &pipeline,
+        RandomMatrix::LABEL,
+        &[y.inner(), &params],
+        (x, y_groups, 1),
+    );
+}
+
+/// Multiplies each `n × n` matrix of `q` in place by `n` Householder reflections along
+/// standard normal directions drawn from `seed`, one dispatch per reflection.
+///
+/// # Panics
+///
+/// - Output length exceeds max size
+pub(crate) fn householder(ctx: &Context, q: &Buffer<f32>, n: usize, seed: u32) {
+    u32::try_from(q.len()).expect("output length exceeds max size");
+    if q.is_empty() {
+        return;
+    }
+    let rows = u32::try_from(q.len() / n).expect("output length exceeds max size");
+    let n = u32::try_from(n).expect("output length exceeds max size");
+
+    let pipeline = ctx.get_or_create_pipeline(
+        TypeId::of::<Householder>(),
+        Householder::wgsl,
+        Householder::LABEL,
+    );
+    let (x, y_groups) = crate::kernel::compute_workgroups(rows);
+
+    for step in 0..n {
+        let params = ctx.create_uniform_buffer(&HouseholderParams {
+            n,
+            rows,
+            seed,
+            step,
+        });
+
+        crate::kernel::dispatch(
+            ctx,
+            &pipeline,
+            Householder::LABEL,
+            &[q.inner(), &params],
+            (x, y_groups, 1),
+        );
+    }
+}
+
+/// Fills `y` with uniformly distributed random bits drawn from `seed`.
+///
+/// # Panics
+///
+/// - Output length exceeds max size
+pub(crate) fn bits(ctx: &Context, y: &Buffer<u32>, seed: u32) {
+    let len = u32::try_from(y.len()).expect("output length exceeds max size");
+
+    if len == 0 {
+        return;
+    }
+
+    let pipeline = ctx.get_or_create_pipeline(
+        TypeId::of::<RandomBits>(),
+        RandomBits::wgsl,
+        RandomBits::LABEL,
+    );
+    let params = ctx.create_uniform_buffer(&Params { len, seed });
+
+    let (x, y_groups) = crate::kernel::compute_workgroups(len);
+
+    crate::kernel::dispatch(
+        ctx,
+        &pipeline,
+        RandomBits::LABEL,
+        &[y.inner(), &params],
+        (x, y_groups, 1),
+    );
+}
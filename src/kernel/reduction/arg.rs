@@ -0,0 +1,159 @@
+//! Index-tracking reduction kernel.
+
+use core::any::TypeId;
+use core::marker::PhantomData;
+
+use alloc::format;
+use alloc::string::String;
+
+use bytemuck::{Pod, Zeroable};
+
+use crate::element::NumericElement;
+use crate::kernel::{Kernel, MAX_WORKGROUPS, WORKGROUP_SIZE};
+use crate::{Buffer, Context};
+
+/// Kernel parameters passed to shader as uniform.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Pod, Zeroable)]
+struct Params {
+    len: u32,
+    size: u32,
+    inner: u32,
+    /// `1` for the maximum, `0` for the minimum.
+    max: u32,
+}
+
+/// Argmax and argmin kernel over the middle axis of contiguous `[outer, size, inner]` input.
+///
+/// Each workgroup owns one output and strides its threads over the `size` elements, keeping
+/// the best value with its index, then merges the candidates in a tree. Ties resolve to the
+/// lowest index.
+struct ArgReduce<T>(PhantomData<T>);
+
+impl<T: NumericElement> Kernel for ArgReduce<T> {
+    const LABEL: &'static str = "arg_reduce";
+    type Output = u32;
+
+    fn wgsl() -> String {
+        let ty = T::wgsl_type();
+
+        format!(
+            r"
+                const WG_SIZE: u32 = {WORKGROUP_SIZE}u;
+
+                struct Params {{
+                    len: u32,
+                    size: u32,
+                    inner: u32,
+                    max: u32,
+                }}
+
+                @group(0) @binding(0) var<storage, read> x: array<{ty}>;
+                @group(0) @binding(1) var<storage, read_write> y: array<u32>;
+                @group(0) @binding(2) var<uniform> params: Params;
+
+                var<workgroup> values: array<{ty}, WG_SIZE>;
+                var<workgroup> indices: array<u32, WG_SIZE>;
+
+                fn better(a: {ty}, i: u32, b: {ty}, j: u32) -> bool {{
+                    if j == 0xffffffffu {{
+                        return true;
+                    }}
+                    if a == b {{
+                        return i < j;
+                    }}
+                    if params.max == 1u {{
+                        return a > b;
+                    }}
+                    return a < b;
+                }}
+
+                @compute @workgroup_size(WG_SIZE)
+                fn main(
+                    @builtin(local_invocation_id) lid: vec3<u32>,
+                    @builtin(workgroup_id) wid: vec3<u32>
+                ) {{
+                    let tid = lid.x;
+                    let out = wid.x + wid.y * {MAX_WORKGROUPS}u;
+
+                    if out >= params.len {{
+                        return;
+                    }}
+
+                    let base = (out / params.inner) * params.size * params.inner
+                        + out % params.inner;
+
+                    var best = x[base];
+                    var best_idx = 0xffffffffu;
+                    for (var k = tid; k < params.size; k += WG_SIZE) {{
+                        let v = x[base + k * params.inner];
+                        if better(v, k, best, best_idx) {{
+                            best = v;
+                            best_idx = k;
+                        }}
+                    }}
+
+                    values[tid] = best;
+                    indices[tid] = best_idx;
+
+                    for (var s = WG_SIZE / 2u; s > 0u; s >>= 1u) {{
+                        workgroupBarrier();
+                        if tid < s {{
+                            let j = indices[tid + s];
+                            if j != 0xffffffffu
+                                && better(values[tid + s], j, values[tid], indices[tid]) {{
+                                values[tid] = values[tid + s];
+                                indices[tid] = j;
+                            }}
+                        }}
+                    }}
+
+                    if tid == 0u {{
+                        y[out] = indices[0];
+                    }}
+                }}
+            "
+        )
+    }
+}
+
+/// Writes the index of the largest (`max`) or smallest element along the middle axis of
+/// contiguous `[outer, size, inner]` `x` into `y`.
+///
+/// # Panics
+///
+/// - Output length, axis size or inner size exceeds max size
+pub(crate) fn execute<T: NumericElement>(
+    ctx: &Context,
+    x: &Buffer<T>,
+    y: &Buffer<u32>,
+    size: usize,
+    inner: usize,
+    max: bool,
+) {
+    let len = u32::try_from(y.len()).expect("output length exceeds max size");
+
+    if len == 0 || size == 0 {
+        return;
+    }
+
+    let pipeline = ctx.get_or_create_pipeline(
+        TypeId::of::<ArgReduce<T>>(),
+        ArgReduce::<T>::wgsl,
+        ArgReduce::<T>::LABEL,
+    );
+    let params = ctx.create_uniform_buffer(&Params {
+        len,
+        size: u32::try_from(size).expect("axis size exceeds max size"),
+        inner: u32::try_from(inner).expect("inner size exceeds max size"),
+        max: u32::from(max),
+    });
+
+    crate::kernel::dispatch(
+        ctx,
+        &pipeline,
+        ArgReduce::<T>::LABEL,
+        &[x.inner(), y.inner(), &params],
+        (len.min(MAX_WORKGROUPS), len.div_ceil(MAX_WORKGROUPS), 1),
+    );
+}
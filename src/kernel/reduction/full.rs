@@ -0,0 +1,205 @@
+//! Chunked row reduction kernel, chained into multi-pass reductions.
+
+use core::any::TypeId;
+use core::marker::PhantomData;
+
+use alloc::format;
+use alloc::string::String;
+
+use bytemuck::{Pod, Zeroable};
+
+use crate::element::NumericElement;
+use crate::kernel::{Kernel, MAX_WORKGROUPS, WORKGROUP_SIZE};
+use crate::{Buffer, Context};
+
+/// Number of consecutive elements reduced by one workgroup in each pass.
+pub(crate) const CHUNK: usize = 8 * WORKGROUP_SIZE as usize;
+
+/// Combining operation of a chunked reduction.
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum FullReduction {
+    /// Sum of the elements.
+    Sum,
+    /// Largest element.
+    Max,
+    /// Smallest element.
+    Min,
+    /// Product of the elements.
+    Prod,
+}
+
+/// Kernel parameters passed to shader as uniform.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Pod, Zeroable)]
+struct Params {
+    len: u32,
+    row_len: u32,
+    /// Chunks per row.
+    chunks: u32,
+    mode: u32,
+    /// Divides each result if non-zero.
+    divisor: u32,
+    _pad: [u32; 3],
+}
+
+/// Chunked reduction kernel, one pass of a multi-pass tree over contiguous `[rows, row_len]`
+/// input.
+///
+/// Each row is split into chunks of `CHUNK` consecutive elements, the last one possibly
+/// short, and each workgroup reduces one chunk to a partial result, striding its threads over
+/// the chunk and merging the thread results in a tree. The partial results form a
+/// `[rows, chunks]` output that the next pass reduces further. The sum starts from zero and
+/// the product from one; the maximum and minimum start from the first element of the chunk,
+/// so threads past the end of a short chunk never contribute a value that is not in it.
+struct FullReduce<T>(PhantomData<T>);
+
+impl<T: NumericElement> Kernel for FullReduce<T> {
+    const LABEL: &'static str = "full_reduce";
+    type Output = T;
+
+    fn wgsl() -> String {
+        let ty = T::wgsl_type();
+
+        format!(
+            r"
+                const WG_SIZE: u32 = {WORKGROUP_SIZE}u;
+                const CHUNK: u32 = {CHUNK}u;
+
+                struct Params {{
+                    len: u32,
+                    row_len: u32,
+                    chunks: u32,
+                    mode: u32,
+                    divisor: u32,
+                    _pad0: u32,
+                    _pad1: u32,
+                    _pad2: u32,
+                }}
+
+                @group(0) @binding(0) var<storage, read> x: array<{ty}>;
+                @group(0) @binding(1) var<storage, read_write> y: array<{ty}>;
+                @group(0) @binding(2) var<uniform> params: Params;
+
+                var<workgroup> sdata: array<{ty}, WG_SIZE>;
+
+                fn combine(a: {ty}, b: {ty}) -> {ty} {{
+                    switch params.mode {{
+                        case 0u: {{
+                            return a + b;
+                        }}
+                        case 1u: {{
+                            return max(a, b);
+                        }}
+                        case 2u: {{
+                            return min(a, b);
+                        }}
+                        default: {{
+                            return a * b;
+                        }}
+                    }}
+                }}
+
+                @compute @workgroup_size(WG_SIZE)
+                fn main(
+                    @builtin(local_invocation_id) lid: vec3<u32>,
+                    @builtin(workgroup_id) wid: vec3<u32>
+                ) {{
+                    let tid = lid.x;
+                    let group = wid.x + wid.y * {MAX_WORKGROUPS}u;
+
+                    if group >= params.len {{
+                        return;
+                    }}
+
+                    let row = group / params.chunks;
+                    let start = row * params.row_len + (group % params.chunks) * CHUNK;
+                    let end = min(start + CHUNK, (row + 1u) * params.row_len);
+                    var acc = x[start];
+                    if params.mode == 0u {{
+                        acc = {ty}(0);
+                    }} else if params.mode == 3u {{
+                        acc = {ty}(1);
+                    }}
+                    for (var i = start + tid; i < end; i += WG_SIZE) {{
+                        acc = combine(acc, x[i]);
+                    }}
+
+                    sdata[tid] = acc;
+
+                    for (var s = WG_SIZE / 2u; s > 0u; s >>= 1u) {{
+                        workgroupBarrier();
+                        if tid < s {{
+                            sdata[tid] = combine(sdata[tid], sdata[tid + s]);
+                        }}
+                    }}
+
+                    if tid == 0u {{
+                        var result = sdata[0];
+                        if params.divisor != 0u {{
+                            result = result / {ty}(params.divisor);
+                        }}
+                        y[group] = result;
+                    }}
+                }}
+            "
+        )
+    }
+}
+
+/// Reduces each chunk of `CHUNK` consecutive elements within the rows of contiguous
+/// `[rows, row_len]` `x` by `op` into `y`, which holds `[rows, row_len.div_ceil(CHUNK)]`
+/// partial results, dividing each by `divisor` if given.
+///
+/// # Panics
+///
+/// - Row length, chunk count or divisor exceeds max size
+pub(crate) fn execute<T: NumericElement>(
+    ctx: &Context,
+    x: &Buffer<T>,
+    y: &Buffer<T>,
+    rows: usize,
+    row_len: usize,
+    op: FullReduction,
+    divisor: Option<usize>,
+) {
+    let chunks = row_len.div_ceil(CHUNK);
+    let groups = u32::try_from(rows * chunks).expect("chunk count exceeds max size");
+    let row_len = u32::try_from(row_len).expect("row length exceeds max size");
+
+    if groups == 0 {
+        return;
+    }
+
+    let pipeline = ctx.get_or_create_pipeline(
+        TypeId::of::<FullReduce<T>>(),
+        FullReduce::<T>::wgsl,
+        FullReduce::<T>::LABEL,
+    );
+    let params = ctx.create_uniform_buffer(&Params {
+        len: groups,
+        row_len,
+        chunks: u32::try_from(chunks).expect("chunk count exceeds max size"),
+        mode: match op {
+            FullReduction::Sum => 0,
+            FullReduction::Max => 1,
+            FullReduction::Min => 2,
+            FullReduction::Prod => 3,
+        },
+        divisor: divisor.map_or(0, |divisor| {
+            u32::try_from(divisor).expect("divisor exceeds max size")
+        }),
+        _pad: [0; 3],
+    });
+
+    crate::kernel::dispatch(
+        ctx,
+        &pipeline,
+        FullReduce::<T>::LABEL,
+        &[x.inner(), y.inner(), &params],
+        (
+            groups.min(MAX_WORKGROUPS),
+            groups.div_ceil(MAX_WORKGROUPS),
+            1,
+        ),
+    );
+}
@@ -14,6 +14,10 @@ use crate::element::NumericElement;
 use crate::kernel::{Kernel, MAX_WORKGROUPS, WORKGROUP_SIZE};
 use crate::{Buffer, Context};
 
+pub(crate) mod arg;
+pub(crate) mod full;
+pub(crate) mod moments;
+pub(crate) mod scan;
 pub(crate) mod sum;
 
 /// Reduction parameters passed to shader as uniform.
@@ -62,13 +66,17 @@ macro_rules! define_kernel {
 
                         var<workgroup> sdata: array<{ty}, WG_SIZE>;
 
+                        fn product(a: {ty}, b: {ty}) -> {ty} {{
+                            return a * b;
+                        }}
+
                         @compute @workgroup_size(WG_SIZE)
                         fn main(
                             @builtin(local_invocation_id) lid: vec3<u32>,
                             @builtin(workgroup_id) wid: vec3<u32>
                         ) {{
                             let tid = lid.x;
-                            let y_idx = wid.x;
+                            let y_idx = wid.x + wid.y * {MAX_WORKGROUPS}u;
 
                             if y_idx >= params.len {{
                                 return;
@@ -147,6 +155,7 @@ macro_rules! define_kernel {
 
 define_kernel!(MaxReduce, "max_reduce", wgsl_min, "max");
 define_kernel!(MinReduce, "min_reduce", wgsl_max, "min");
+define_kernel!(ProdReduce, "prod_reduce", wgsl_one, "product");
 
 /// Executes a reduction kernel along specified axes.
 ///
@@ -154,7 +163,6 @@ define_kernel!(MinReduce, "min_reduce", wgsl_max, "min");
 ///
 /// - Output rank exceeds max size
 /// - Output length exceeds max size
-/// - Output length exceeds maximum workgroups
 /// - Reduction length exceeds max size
 #[allow(clippy::too_many_lines)]
 pub(crate) fn execute<K: Kernel + 'static, T: NumericElement>(
@@ -171,15 +179,11 @@ pub(crate) fn execute<K: Kernel + 'static, T: NumericElement>(
     let reduction_len = u32::try_from(axes.iter().map(|&a| x_dimensions[a]).product::<usize>())
         .expect("reduction length exceeds max size");
 
-    if len == 0 || reduction_len == 0 {
+    // An empty reduction still runs, so each output holds the identity of the operation.
+    if len == 0 {
         return;
     }
 
-    assert!(
-        len <= MAX_WORKGROUPS,
-        "output length exceeds maximum workgroups"
-    );
-
     let pipeline = ctx.get_or_create_pipeline(TypeId::of::<K>(), K::wgsl, K::LABEL);
 
     let x_dimensions = crate::kernel::convert_strides(x_dimensions);
@@ -266,21 +270,11 @@ pub(crate) fn execute<K: Kernel + 'static, T: NumericElement>(
         ],
     });
 
-    let mut encoder = ctx
-        .device()
-        .create_command_encoder(&wgpu::CommandEncoderDescriptor {
-            label: Some(K::LABEL),
-        });
-
-    {
-        let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
-            label: Some(K::LABEL),
-            ..Default::default()
-        });
-        pass.set_pipeline(&pipeline);
-        pass.set_bind_group(0, &bind_group, &[]);
-        pass.dispatch_workgroups(len, 1, 1);
-    }
-
-    ctx.queue().submit(Some(encoder.finish()));
+    crate::kernel::dispatch_bind_group(
+        ctx,
+        &pipeline,
+        K::LABEL,
+        &bind_group,
+        (len.min(MAX_WORKGROUPS), len.div_ceil(MAX_WORKGROUPS), 1),
+    );
 }
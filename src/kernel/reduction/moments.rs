@@ -0,0 +1,210 @@
+//! Single-pass mean and variance reduction kernel.
+
+use core::any::TypeId;
+use core::marker::PhantomData;
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use bytemuck::{Pod, Zeroable};
+
+use crate::element::FloatElement;
+use crate::kernel::{Kernel, MAX_WORKGROUPS, WORKGROUP_SIZE};
+use crate::{Buffer, Context};
+
+/// Kernel parameters passed to shader as uniform.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Pod, Zeroable)]
+struct Params {
+    rank: u32,
+    len: u32,
+    reduction_len: u32,
+    correction: u32,
+}
+
+/// Moments kernel: `mean[o]` and `var[o]` of the elements reduced into output `o`.
+///
+/// Each workgroup owns one output and reads every reduced element once. Threads keep a running
+/// count, mean and sum of squared deviations by Welford's update, and the workgroup merges the
+/// per-thread triples in a tree with Chan's parallel formula, so the variance never subtracts
+/// two large sums and needs no second pass over the input.
+struct Moments<T>(PhantomData<T>);
+
+impl<T: FloatElement> Kernel for Moments<T> {
+    const LABEL: &'static str = "moments";
+    type Output = T;
+
+    fn wgsl() -> String {
+        let ty = T::wgsl_type();
+
+        format!(
+            r"
+                const WG_SIZE: u32 = {WORKGROUP_SIZE}u;
+
+                struct Params {{
+                    rank: u32,
+                    len: u32,
+                    reduction_len: u32,
+                    correction: u32,
+                }}
+
+                @group(0) @binding(0) var<storage, read> x: array<{ty}>;
+                @group(0) @binding(1) var<storage, read_write> mean: array<{ty}>;
+                @group(0) @binding(2) var<storage, read_write> variance: array<{ty}>;
+                @group(0) @binding(3) var<storage, read> x_strides: array<u32>;
+                @group(0) @binding(4) var<storage, read> y_strides: array<u32>;
+                @group(0) @binding(5) var<storage, read> reduce_strides: array<u32>;
+                @group(0) @binding(6) var<uniform> params: Params;
+
+                // Count, mean and sum of squared deviations of each thread.
+                var<workgroup> sdata: array<vec3<{ty}>, WG_SIZE>;
+
+                fn merge(a: vec3<{ty}>, b: vec3<{ty}>) -> vec3<{ty}> {{
+                    let n = a.x + b.x;
+                    if n == {ty}(0) {{
+                        return a;
+                    }}
+                    let delta = b.y - a.y;
+                    return vec3<{ty}>(
+                        n,
+                        a.y + delta * (b.x / n),
+                        a.z + b.z + delta * delta * (a.x * b.x / n),
+                    );
+                }}
+
+                @compute @workgroup_size(WG_SIZE)
+                fn main(
+                    @builtin(local_invocation_id) lid: vec3<u32>,
+                    @builtin(workgroup_id) wid: vec3<u32>
+                ) {{
+                    let tid = lid.x;
+                    let y_idx = wid.x + wid.y * {MAX_WORKGROUPS}u;
+
+                    if y_idx >= params.len {{
+                        return;
+                    }}
+
+                    var base = 0u;
+                    var remaining = y_idx;
+                    for (var i = 0u; i < params.rank; i++) {{
+                        let stride = y_strides[i];
+                        if stride > 0u && reduce_strides[i] == 0u {{
+                            base += remaining / stride * x_strides[i];
+                        }}
+                        if stride > 0u {{
+                            remaining = remaining % stride;
+                        }}
+                    }}
+
+                    var acc = vec3<{ty}>(0);
+                    for (var r = tid; r < params.reduction_len; r += WG_SIZE) {{
+                        var input_idx = base;
+                        var red_remaining = r;
+                        for (var i = 0u; i < params.rank; i++) {{
+                            let stride = reduce_strides[i];
+                            if stride > 0u {{
+                                input_idx += red_remaining / stride * x_strides[i];
+                                red_remaining = red_remaining % stride;
+                            }}
+                        }}
+
+                        let value = x[input_idx];
+                        acc.x += {ty}(1);
+                        let delta = value - acc.y;
+                        acc.y += delta / acc.x;
+                        acc.z += delta * (value - acc.y);
+                    }}
+
+                    sdata[tid] = acc;
+
+                    for (var s = WG_SIZE / 2u; s > 0u; s >>= 1u) {{
+                        workgroupBarrier();
+                        if tid < s {{
+                            sdata[tid] = merge(sdata[tid], sdata[tid + s]);
+                        }}
+                    }}
+
+                    if tid == 0u {{
+                        let total = sdata[0];
+                        mean[y_idx] = total.y;
+                        variance[y_idx] = total.z / {ty}(params.reduction_len - params.correction);
+                    }}
+                }}
+            "
+        )
+    }
+}
+
+/// Computes the mean and variance of `x` along `axes` into `mean` and `var` in one pass,
+/// dividing the squared deviations by the reduction length minus `correction`.
+///
+/// # Panics
+///
+/// - Output rank exceeds max size
+/// - Output length exceeds max size
+/// - Reduction length exceeds max size
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn execute<T: FloatElement>(
+    ctx: &Context,
+    x: &Buffer<T>,
+    mean: &Buffer<T>,
+    var: &Buffer<T>,
+    x_dimensions: &[usize],
+    x_strides: &[usize],
+    y_strides: &[usize],
+    axes: &[usize],
+    correction: u32,
+) {
+    let rank = u32::try_from(y_strides.len()).expect("output rank exceeds max size");
+    let len = u32::try_from(mean.len()).expect("output length exceeds max size");
+    let reduction_len = u32::try_from(axes.iter().map(|&a| x_dimensions[a]).product::<usize>())
+        .expect("reduction length exceeds max size");
+
+    if len == 0 || reduction_len == 0 {
+        return;
+    }
+
+    let pipeline = ctx.get_or_create_pipeline(
+        TypeId::of::<Moments<T>>(),
+        Moments::<T>::wgsl,
+        Moments::<T>::LABEL,
+    );
+
+    // Row-major strides of the reduced axes within the reduction, zero for kept axes.
+    let mut reduce_strides: Vec<usize> = alloc::vec![0; x_dimensions.len()];
+    let mut stride = 1;
+    for axis in (0..x_dimensions.len()).rev() {
+        if axes.contains(&axis) {
+            reduce_strides[axis] = stride;
+            stride *= x_dimensions[axis];
+        }
+    }
+
+    let x_strides = ctx.create_storage_buffer(&crate::kernel::convert_strides(x_strides));
+    let y_strides = ctx.create_storage_buffer(&crate::kernel::convert_strides(y_strides));
+    let reduce_strides =
+        ctx.create_storage_buffer(&crate::kernel::convert_strides(&reduce_strides));
+    let params = ctx.create_uniform_buffer(&Params {
+        rank,
+        len,
+        reduction_len,
+        correction,
+    });
+
+    crate::kernel::dispatch(
+        ctx,
+        &pipeline,
+        Moments::<T>::LABEL,
+        &[
+            x.inner(),
+            mean.inner(),
+            var.inner(),
+            &x_strides,
+            &y_strides,
+            &reduce_strides,
+            &params,
+        ],
+        (len.min(MAX_WORKGROUPS), len.div_ceil(MAX_WORKGROUPS), 1),
+    );
+}
@@ -0,0 +1,193 @@
+//! Prefix scan kernel.
+
+use core::any::TypeId;
+use core::marker::PhantomData;
+
+use alloc::format;
+use alloc::string::String;
+
+use bytemuck::{Pod, Zeroable};
+
+use crate::element::NumericElement;
+use crate::kernel::{Kernel, MAX_WORKGROUPS, WORKGROUP_SIZE};
+use crate::{Buffer, Context};
+
+/// Combining operation of a prefix scan.
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum Scan {
+    /// Running sum, starting from zero.
+    Sum,
+    /// Running product, starting from one.
+    Prod,
+}
+
+/// Kernel parameters passed to shader as uniform.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Pod, Zeroable)]
+struct Params {
+    len: u32,
+    size: u32,
+    inner: u32,
+    chunk: u32,
+    /// `0` for the sum, `1` for the product.
+    mode: u32,
+    /// `1` if each output excludes its own element.
+    exclusive: u32,
+    _pad: [u32; 2],
+}
+
+/// Prefix scan kernel over the middle axis of contiguous `[outer, size, inner]` input.
+///
+/// Each workgroup owns one line of `size` elements and each thread a contiguous chunk of
+/// it: the threads fold their chunks, scan the chunk totals across the workgroup in shared
+/// memory, then replay their chunks from the carried-in total. The work stays linear in
+/// `size`, with only the `WORKGROUP_SIZE` totals scanned in logarithmic steps.
+struct PrefixScan<T>(PhantomData<T>);
+
+impl<T: NumericElement> Kernel for PrefixScan<T> {
+    const LABEL: &'static str = "prefix_scan";
+    type Output = T;
+
+    fn wgsl() -> String {
+        let ty = T::wgsl_type();
+
+        format!(
+            r"
+                const WG_SIZE: u32 = {WORKGROUP_SIZE}u;
+
+                struct Params {{
+                    len: u32,
+                    size: u32,
+                    inner: u32,
+                    chunk: u32,
+                    mode: u32,
+                    exclusive: u32,
+                    _pad0: u32,
+                    _pad1: u32,
+                }}
+
+                @group(0) @binding(0) var<storage, read> x: array<{ty}>;
+                @group(0) @binding(1) var<storage, read_write> y: array<{ty}>;
+                @group(0) @binding(2) var<uniform> params: Params;
+
+                var<workgroup> totals: array<{ty}, WG_SIZE>;
+
+                fn identity() -> {ty} {{
+                    return {ty}(params.mode);
+                }}
+
+                fn combine(a: {ty}, b: {ty}) -> {ty} {{
+                    if params.mode == 0u {{
+                        return a + b;
+                    }}
+                    return a * b;
+                }}
+
+                @compute @workgroup_size(WG_SIZE)
+                fn main(
+                    @builtin(local_invocation_id) lid: vec3<u32>,
+                    @builtin(workgroup_id) wid: vec3<u32>
+                ) {{
+                    let tid = lid.x;
+                    let line = wid.x + wid.y * {MAX_WORKGROUPS}u;
+
+                    if line >= params.len {{
+                        return;
+                    }}
+
+                    let base = (line / params.inner) * params.size * params.inner
+                        + line % params.inner;
+                    let start = min(tid * params.chunk, params.size);
+                    let end = min(start + params.chunk, params.size);
+
+                    var acc = identity();
+                    for (var k = start; k < end; k++) {{
+                        acc = combine(acc, x[base + k * params.inner]);
+                    }}
+
+                    totals[tid] = acc;
+
+                    for (var offset = 1u; offset < WG_SIZE; offset <<= 1u) {{
+                        workgroupBarrier();
+                        var next = totals[tid];
+                        if tid >= offset {{
+                            next = combine(totals[tid - offset], next);
+                        }}
+                        workgroupBarrier();
+                        totals[tid] = next;
+                    }}
+                    workgroupBarrier();
+
+                    var running = identity();
+                    if tid > 0u {{
+                        running = totals[tid - 1u];
+                    }}
+                    for (var k = start; k < end; k++) {{
+                        let i = base + k * params.inner;
+                        let v = x[i];
+                        if params.exclusive == 1u {{
+                            y[i] = running;
+                            running = combine(running, v);
+                        }} else {{
+                            running = combine(running, v);
+                            y[i] = running;
+                        }}
+                    }}
+                }}
+            "
+        )
+    }
+}
+
+/// Writes the inclusive or `exclusive` running sum or product along the middle axis of
+/// contiguous `[outer, size, inner]` `x` into `y`.
+///
+/// # Panics
+///
+/// - Line count, axis size or inner size exceeds max size
+pub(crate) fn execute<T: NumericElement>(
+    ctx: &Context,
+    x: &Buffer<T>,
+    y: &Buffer<T>,
+    size: usize,
+    inner: usize,
+    op: Scan,
+    exclusive: bool,
+) {
+    if size == 0 || inner == 0 {
+        return;
+    }
+
+    let len = u32::try_from(y.len() / size).expect("line count exceeds max size");
+    let size = u32::try_from(size).expect("axis size exceeds max size");
+
+    if len == 0 {
+        return;
+    }
+
+    let pipeline = ctx.get_or_create_pipeline(
+        TypeId::of::<PrefixScan<T>>(),
+        PrefixScan::<T>::wgsl,
+        PrefixScan::<T>::LABEL,
+    );
+    let params = ctx.create_uniform_buffer(&Params {
+        len,
+        size,
+        inner: u32::try_from(inner).expect("inner size exceeds max size"),
+        chunk: size.div_ceil(WORKGROUP_SIZE),
+        mode: match op {
+            Scan::Sum => 0,
+            Scan::Prod => 1,
+        },
+        exclusive: u32::from(exclusive),
+        _pad: [0; 2],
+    });
+
+    crate::kernel::dispatch(
+        ctx,
+        &pipeline,
+        PrefixScan::<T>::LABEL,
+        &[x.inner(), y.inner(), &params],
+        (len.min(MAX_WORKGROUPS), len.div_ceil(MAX_WORKGROUPS), 1),
+    );
+}
@@ -25,6 +25,10 @@ struct Params {
     len: u32,
     reduction_len: u32,
     normalize: u32,
+    correction: u32,
+    _pad0: u32,
+    _pad1: u32,
+    _pad2: u32,
 }
 
 impl<T: NumericElement> Kernel for SumReduce<T> {
@@ -43,6 +47,10 @@ impl<T: NumericElement> Kernel for SumReduce<T> {
                     len: u32,
                     reduction_len: u32,
                     normalize: u32,
+                    correction: u32,
+                    _pad0: u32,
+                    _pad1: u32,
+                    _pad2: u32,
                 }}
 
                 @group(0) @binding(0) var<storage, read> x: array<{ty}>;
@@ -61,7 +69,7 @@ impl<T: NumericElement> Kernel for SumReduce<T> {
                     @builtin(workgroup_id) wid: vec3<u32>
                 ) {{
                     let tid = lid.x;
-                    let y_idx = wid.x;
+                    let y_idx = wid.x + wid.y * {MAX_WORKGROUPS}u;
 
                     if y_idx >= params.len {{
                         return;
@@ -129,7 +137,7 @@ impl<T: NumericElement> Kernel for SumReduce<T> {
                     if tid == 0u {{
                         var result = sdata[0];
                         if params.normalize != 0u {{
-                            result = result / {ty}(params.reduction_len);
+                            result = result / {ty}(params.reduction_len - params.correction);
                         }}
                         y[y_idx] = result;
                     }}
@@ -141,11 +149,13 @@ impl<T: NumericElement> Kernel for SumReduce<T> {
 
 /// Executes sum reduction kernel along specified axes.
 ///
+/// With `normalize` of `Some(correction)`, the sums are divided by the reduction length minus
+/// `correction`.
+///
 /// # Panics
 ///
 /// - Output rank exceeds max size
 /// - Output length exceeds max size
-/// - Output length exceeds maximum workgroups
 /// - Reduction length exceeds max size
 #[allow(clippy::too_many_lines)]
 pub(crate) fn execute<T: NumericElement>(
@@ -156,7 +166,7 @@ pub(crate) fn execute<T: NumericElement>(
     x_strides: &[usize],
     y_strides: &[usize],
     axes: &[usize],
-    normalize: bool,
+    normalize: Option<u32>,
 ) {
     let rank = u32::try_from(y_strides.len()).expect("output rank exceeds max size");
     let len = u32::try_from(y.len()).expect("output length exceeds max size");
@@ -167,11 +177,6 @@ pub(crate) fn execute<T: NumericElement>(
         return;
     }
 
-    assert!(
-        len <= MAX_WORKGROUPS,
-        "output length exceeds maximum workgroups"
-    );
-
     let pipeline = ctx.get_or_create_pipeline(
         TypeId::of::<SumReduce<T>>(),
         SumReduce::<T>::wgsl,
@@ -190,7 +195,11 @@ pub(crate) fn execute<T: NumericElement>(
         rank,
         len,
         reduction_len,
-        normalize: u32::from(normalize),
+        normalize: u32::from(normalize.is_some()),
+        correction: normalize.unwrap_or(0),
+        _pad0: 0,
+        _pad1: 0,
+        _pad2: 0,
     };
 
     let x_dimensions = ctx
@@ -262,21 +271,11 @@ pub(crate) fn execute<T: NumericElement>(
         ],
     });
 
-    let mut encoder = ctx
-        .device()
-        .create_command_encoder(&wgpu::CommandEncoderDescriptor {
-            label: Some(SumReduce::<T>::LABEL),
-        });
-
-    {
-        let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
-            label: Some(SumReduce::<T>::LABEL),
-            ..Default::default()
-        });
-        pass.set_pipeline(&pipeline);
-        pass.set_bind_group(0, &bind_group, &[]);
-        pass.dispatch_workgroups(len, 1, 1);
-    }
-
-    ctx.queue().submit(Some(encoder.finish()));
+    crate::kernel::dispatch_bind_group(
+        ctx,
+        &pipeline,
+        SumReduce::<T>::LABEL,
+        &bind_group,
+        (len.min(MAX_WORKGROUPS), len.div_ceil(MAX_WORKGROUPS), 1),
+    );
 }
@@ -0,0 +1,252 @@
+//! Reinforcement learning kernels.
+
+use core::any::TypeId;
+use core::marker::PhantomData;
+
+use alloc::format;
+use alloc::string::String;
+
+use bytemuck::{Pod, Zeroable};
+
+use crate::element::FloatElement;
+use crate::kernel::{Kernel, MAX_WORKGROUPS, WORKGROUP_SIZE};
+use crate::{Buffer, Context};
+
+/// GAE kernel parameters passed to shader as uniform.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Pod, Zeroable)]
+struct GaeParams {
+    steps: u32,
+    envs: u32,
+    gamma: f32,
+    lambda: f32,
+}
+
+/// PPO kernel parameters passed to shader as uniform.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Pod, Zeroable)]
+struct PpoParams {
+    len: u32,
+    epsilon: f32,
+    _pad0: u32,
+    _pad1: u32,
+}
+
+/// Generalized advantage estimation kernel over `[steps, envs]` rollouts.
+///
+/// Each thread owns one environment and scans its steps in reverse:
+/// `δ_t = r_t + γ·(1 - d_t)·V_{t+1} - V_t` and `A_t = δ_t + γλ·(1 - d_t)·A_{t+1}`, where
+/// `V_steps` is the bootstrap value `last_values`. Returns are `A_t + V_t`.
+struct Gae<T>(PhantomData<T>);
+
+impl<T: FloatElement> Kernel for Gae<T> {
+    const LABEL: &'static str = "gae";
+    type Output = T;
+
+    fn wgsl() -> String {
+        let ty = T::wgsl_type();
+
+        format!(
+            r"
+                struct Params {{
+                    steps: u32,
+                    envs: u32,
+                    gamma: f32,
+                    lambda: f32,
+                }}
+
+                @group(0) @binding(0) var<storage, read> rewards: array<{ty}>;
+                @group(0) @binding(1) var<storage, read> values: array<{ty}>;
+                @group(0) @binding(2) var<storage, read> dones: array<{ty}>;
+                @group(0) @binding(3) var<storage, read> last_values: array<{ty}>;
+                @group(0) @binding(4) var<storage, read_write> advantages: array<{ty}>;
+                @group(0) @binding(5) var<storage, read_write> returns: array<{ty}>;
+                @group(0) @binding(6) var<uniform> params: Params;
+
+                @compute @workgroup_size({WORKGROUP_SIZE})
+                fn main(@builtin(global_invocation_id) gid: vec3<u32>) {{
+                    let env = gid.x + gid.y * {MAX_WORKGROUPS}u * {WORKGROUP_SIZE}u;
+
+                    if env >= params.envs {{
+                        return;
+                    }}
+
+                    let gamma = {ty}(params.gamma);
+                    let lambda = {ty}(params.lambda);
+                    var next_value = last_values[env];
+                    var gae: {ty} = 0.0;
+
+                    for (var i = 0u; i < params.steps; i++) {{
+                        let k = (params.steps - 1u - i) * params.envs + env;
+                        let live = 1.0 - dones[k];
+                        let delta = rewards[k] + gamma * live * next_value - values[k];
+                        gae = delta + gamma * lambda * live * gae;
+                        advantages[k] = gae;
+                        returns[k] = gae + values[k];
+                        next_value = values[k];
+                    }}
+                }}
+            "
+        )
+    }
+}
+
+/// Clipped PPO surrogate kernel over element-wise probability ratios and advantages.
+///
+/// With `c = clamp(r, 1 - ε, 1 + ε)`, each thread writes the loss `-min(r·A, c·A)` and its
+/// gradient with respect to the ratio, `-A` where the unclipped term is the minimum and zero
+/// where the clipped term is, which has no gradient.
+struct PpoClip<T>(PhantomData<T>);
+
+impl<T: FloatElement> Kernel for PpoClip<T> {
+    const LABEL: &'static str = "ppo_clip";
+    type Output = T;
+
+    fn wgsl() -> String {
+        let ty = T::wgsl_type();
+
+        format!(
+            r"
+                struct Params {{
+                    len: u32,
+                    epsilon: f32,
+                    _pad0: u32,
+                    _pad1: u32,
+                }}
+
+                @group(0) @binding(0) var<storage, read> ratio: array<{ty}>;
+                @group(0) @binding(1) var<storage, read> advantages: array<{ty}>;
+                @group(0) @binding(2) var<storage, read_write> loss: array<{ty}>;
+                @group(0) @binding(3) var<storage, read_write> grad: array<{ty}>;
+                @group(0) @binding(4) var<uniform> params: Params;
+
+                @compute @workgroup_size({WORKGROUP_SIZE})
+                fn main(@builtin(global_invocation_id) gid: vec3<u32>) {{
+                    let tid = gid.x + gid.y * {MAX_WORKGROUPS}u * {WORKGROUP_SIZE}u;
+
+                    if tid >= params.len {{
+                        return;
+                    }}
+
+                    let epsilon = {ty}(params.epsilon);
+                    let r = ratio[tid];
+                    let a = advantages[tid];
+                    let unclipped = r * a;
+                    let clipped = clamp(r, 1.0 - epsilon, 1.0 + epsilon) * a;
+
+                    if unclipped <= clipped {{
+                        loss[tid] = -unclipped;
+                        grad[tid] = -a;
+                    }} else {{
+                        loss[tid] = -clipped;
+                        grad[tid] = 0.0;
+                    }}
+                }}
+            "
+        )
+    }
+}
+
+/// Computes advantages and returns of contiguous `[steps, envs]` rollouts.
+///
+/// # Panics
+///
+/// - Step count or environment count exceeds max size
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn gae<T: FloatElement>(
+    ctx: &Context,
+    rewards: &Buffer<T>,
+    values: &Buffer<T>,
+    dones: &Buffer<T>,
+    last_values: &Buffer<T>,
+    advantages: &Buffer<T>,
+    returns: &Buffer<T>,
+    gamma: f32,
+    lambda: f32,
+) {
+    let envs = u32::try_from(last_values.len()).expect("environment count exceeds max size");
+    let steps = u32::try_from(rewards.len().checked_div(last_values.len()).unwrap_or(0))
+        .expect("step count exceeds max size");
+
+    if envs == 0 || steps == 0 {
+        return;
+    }
+
+    let pipeline =
+        ctx.get_or_create_pipeline(TypeId::of::<Gae<T>>(), Gae::<T>::wgsl, Gae::<T>::LABEL);
+
+    let params = ctx.create_uniform_buffer(&GaeParams {
+        steps,
+        envs,
+        gamma,
+        lambda,
+    });
+
+    let (x, y) = crate::kernel::compute_workgroups(envs);
+
+    crate::kernel::dispatch(
+        ctx,
+        &pipeline,
+        Gae::<T>::LABEL,
+        &[
+            rewards.inner(),
+            values.inner(),
+            dones.inner(),
+            last_values.inner(),
+            advantages.inner(),
+            returns.inner(),
+            &params,
+        ],
+        (x, y, 1),
+    );
+}
+
+/// Computes the clipped PPO loss and its gradient with respect to the ratio of contiguous
+/// `ratio` and `advantages` of equal length.
+///
+/// # Panics
+///
+/// - Element count exceeds max size
+pub(crate) fn ppo_clip<T: FloatElement>(
+    ctx: &Context,
+    ratio: &Buffer<T>,
+    advantages: &Buffer<T>,
+    loss: &Buffer<T>,
+    grad: &Buffer<T>,
+    epsilon: f32,
+) {
+    let len = u32::try_from(loss.len()).expect("element count exceeds max size");
+
+    if len == 0 {
+        return;
+    }
+
+    let pipeline = ctx.get_or_create_pipeline(
+        TypeId::of::<PpoClip<T>>(),
+        PpoClip::<T>::wgsl,
+        PpoClip::<T>::LABEL,
+    );
+
+    let params = ctx.create_uniform_buffer(&PpoParams {
+        len,
+        epsilon,
+        _pad0: 0,
+        _pad1: 0,
+    });
+
+    let (x, y) = crate::kernel::compute_workgroups(len);
+
+    crate::kernel::dispatch(
+        ctx,
+        &pipeline,
+        PpoClip::<T>::LABEL,
+        &[
+            ratio.inner(),
+            advantages.inner(),
+            loss.inner(),
+            grad.inner(),
+            &params,
+        ],
+        (x, y, 1),
+    );
+}
@@ -0,0 +1,136 @@
+//! Rolling window kernel.
+
+use core::any::TypeId;
+use core::marker::PhantomData;
+
+use alloc::format;
+use alloc::string::String;
+
+use bytemuck::{Pod, Zeroable};
+
+use crate::element::FloatElement;
+use crate::kernel::{Kernel, MAX_WORKGROUPS, WORKGROUP_SIZE};
+use crate::{Buffer, Context};
+
+/// Kernel parameters passed to shader as uniform.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Pod, Zeroable)]
+struct Params {
+    len: u32,
+    inner: u32,
+    window: u32,
+    /// `0` for the mean, otherwise one plus the variance correction for the standard deviation.
+    mode: u32,
+}
+
+/// Rolling window kernel over contiguous `[steps, inner]` series.
+///
+/// Each thread owns one output `y[t, c]` and reads the window `x[t - window + 1 ..= t, c]`:
+/// the mean (`mode = 0`), or the standard deviation with the deviations taken against the
+/// window mean (`mode = 1 + correction`). Outputs with fewer than `window` preceding steps are
+/// `NaN`.
+struct Rolling<T>(PhantomData<T>);
+
+impl<T: FloatElement> Kernel for Rolling<T> {
+    const LABEL: &'static str = "rolling";
+    type Output = T;
+
+    fn wgsl() -> String {
+        let ty = T::wgsl_type();
+
+        format!(
+            r"
+                struct Params {{
+                    len: u32,
+                    inner: u32,
+                    window: u32,
+                    mode: u32,
+                }}
+
+                @group(0) @binding(0) var<storage, read> x: array<{ty}>;
+                @group(0) @binding(1) var<storage, read_write> y: array<{ty}>;
+                @group(0) @binding(2) var<uniform> params: Params;
+
+                @compute @workgroup_size({WORKGROUP_SIZE})
+                fn main(@builtin(global_invocation_id) gid: vec3<u32>) {{
+                    let tid = gid.x + gid.y * {MAX_WORKGROUPS}u * {WORKGROUP_SIZE}u;
+
+                    if tid >= params.len {{
+                        return;
+                    }}
+
+                    let t = tid / params.inner;
+
+                    if t + 1u < params.window {{
+                        y[tid] = {ty}(bitcast<f32>(0x7fc00000u));
+                        return;
+                    }}
+
+                    let first = tid - (params.window - 1u) * params.inner;
+                    let count = {ty}(params.window);
+
+                    var sum: {ty} = 0.0;
+                    for (var i = 0u; i < params.window; i++) {{
+                        sum += x[first + i * params.inner];
+                    }}
+                    let mean = sum / count;
+
+                    if params.mode == 0u {{
+                        y[tid] = mean;
+                        return;
+                    }}
+
+                    var squares: {ty} = 0.0;
+                    for (var i = 0u; i < params.window; i++) {{
+                        let d = x[first + i * params.inner] - mean;
+                        squares += d * d;
+                    }}
+                    y[tid] = sqrt(squares / (count - {ty}(params.mode - 1u)));
+                }}
+            "
+        )
+    }
+}
+
+/// Writes the rolling mean, or the rolling standard deviation with variance `correction` if
+/// given, of contiguous `[steps, inner]` `x` over `window` steps into `y`.
+///
+/// # Panics
+///
+/// - Output length, inner size or window exceeds max size
+pub(crate) fn rolling<T: FloatElement>(
+    ctx: &Context,
+    x: &Buffer<T>,
+    y: &Buffer<T>,
+    inner: usize,
+    window: usize,
+    correction: Option<u32>,
+) {
+    let len = u32::try_from(y.len()).expect("output length exceeds max size");
+
+    if len == 0 {
+        return;
+    }
+
+    let pipeline = ctx.get_or_create_pipeline(
+        TypeId::of::<Rolling<T>>(),
+        Rolling::<T>::wgsl,
+        Rolling::<T>::LABEL,
+    );
+    let params = ctx.create_uniform_buffer(&Params {
+        len,
+        inner: u32::try_from(inner).expect("inner size exceeds max size"),
+        window: u32::try_from(window).expect("window exceeds max size"),
+        mode: correction.map_or(0, |correction| correction + 1),
+    });
+
+    let (wx, wy) = crate::kernel::compute_workgroups(len);
+
+    crate::kernel::dispatch(
+        ctx,
+        &pipeline,
+        Rolling::<T>::LABEL,
+        &[x.inner(), y.inner(), &params],
+        (wx, wy, 1),
+    );
+}
@@ -0,0 +1,288 @@
+//! Bitonic sort kernels.
+//!
+//! Each row of length `cols` is padded to the next power of two and sorted as `(key, index)`
+//! pairs by a fixed bitonic network. Padding slots carry an out-of-range index and always order
+//! last. The network is identical on every adapter, and with `stable` set, equal keys are ordered
+//! by ascending index, so the lowest index wins ties.
+
+use core::any::TypeId;
+use core::marker::PhantomData;
+
+use alloc::format;
+use alloc::string::String;
+
+use bytemuck::{Pod, Zeroable};
+
+use crate::element::NumericElement;
+use crate::kernel::{Kernel, MAX_WORKGROUPS, WORKGROUP_SIZE};
+use crate::{Buffer, Context};
+
+/// Kernel parameters passed to shader as uniform.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Pod, Zeroable)]
+struct Params {
+    rows: u32,
+    cols: u32,
+    padded: u32,
+    k: u32,
+    descending: u32,
+    stable: u32,
+    block: u32,
+    step: u32,
+}
+
+/// WGSL parameter struct shared by the sort kernels.
+const PARAMS_WGSL: &str = r"
+    struct Params {
+        rows: u32,
+        cols: u32,
+        padded: u32,
+        k: u32,
+        descending: u32,
+        stable: u32,
+        block: u32,
+        step: u32,
+    }
+";
+
+/// Copies rows of `x` into the padded key and index scratch buffers.
+struct Init<T>(PhantomData<T>);
+
+impl<T: NumericElement> Kernel for Init<T> {
+    const LABEL: &'static str = "sort_init";
+    type Output = T;
+
+    fn wgsl() -> String {
+        let ty = T::wgsl_type();
+        let zero = T::wgsl_zero();
+
+        format!(
+            r"
+                {PARAMS_WGSL}
+
+                @group(0) @binding(0) var<storage, read> x: array<{ty}>;
+                @group(0) @binding(1) var<storage, read_write> keys: array<{ty}>;
+                @group(0) @binding(2) var<storage, read_write> idx: array<u32>;
+                @group(0) @binding(3) var<uniform> params: Params;
+
+                @compute @workgroup_size({WORKGROUP_SIZE})
+                fn main(@builtin(global_invocation_id) gid: vec3<u32>) {{
+                    let tid = gid.x + gid.y * {MAX_WORKGROUPS}u * {WORKGROUP_SIZE}u;
+
+                    if tid >= params.rows * params.padded {{
+                        return;
+                    }}
+
+                    let row = tid / params.padded;
+                    let col = tid % params.padded;
+
+                    if col < params.cols {{
+                        keys[tid] = x[row * params.cols + col];
+                        idx[tid] = col;
+                    }} else {{
+                        keys[tid] = {zero};
+                        idx[tid] = 0xffffffffu;
+                    }}
+                }}
+            "
+        )
+    }
+}
+
+/// One compare-exchange step of the bitonic network.
+struct Step<T>(PhantomData<T>);
+
+impl<T: NumericElement> Kernel for Step<T> {
+    const LABEL: &'static str = "sort_step";
+    type Output = T;
+
+    fn wgsl() -> String {
+        let ty = T::wgsl_type();
+
+        format!(
+            r"
+                {PARAMS_WGSL}
+
+                @group(0) @binding(0) var<storage, read_write> keys: array<{ty}>;
+                @group(0) @binding(1) var<storage, read_write> idx: array<u32>;
+                @group(0) @binding(2) var<uniform> params: Params;
+
+                fn before(a: u32, b: u32) -> bool {{
+                    if idx[a] >= params.cols {{
+                        return false;
+                    }}
+                    if idx[b] >= params.cols {{
+                        return true;
+                    }}
+
+                    let ka = keys[a];
+                    let kb = keys[b];
+                    if ka != kb {{
+                        return select(ka < kb, ka > kb, params.descending != 0u);
+                    }}
+
+                    return params.stable != 0u && idx[a] < idx[b];
+                }}
+
+                @compute @workgroup_size({WORKGROUP_SIZE})
+                fn main(@builtin(global_invocation_id) gid: vec3<u32>) {{
+                    let tid = gid.x + gid.y * {MAX_WORKGROUPS}u * {WORKGROUP_SIZE}u;
+
+                    if tid >= params.rows * params.padded {{
+                        return;
+                    }}
+
+                    let i = tid % params.padded;
+                    let l = i ^ params.step;
+                    if l <= i {{
+                        return;
+                    }}
+
+                    let a = tid;
+                    let b = tid - i + l;
+                    let swap = select(before(a, b), before(b, a), (i & params.block) == 0u);
+
+                    if swap {{
+                        let key = keys[a];
+                        keys[a] = keys[b];
+                        keys[b] = key;
+
+                        let index = idx[a];
+                        idx[a] = idx[b];
+                        idx[b] = index;
+                    }}
+                }}
+            "
+        )
+    }
+}
+
+/// Writes the first `k` sorted entries of each row to the outputs.
+struct Gather<T>(PhantomData<T>);
+
+impl<T: NumericElement> Kernel for Gather<T> {
+    const LABEL: &'static str = "sort_gather";
+    type Output = T;
+
+    fn wgsl() -> String {
+        let ty = T::wgsl_type();
+
+        format!(
+            r"
+                {PARAMS_WGSL}
+
+                @group(0) @binding(0) var<storage, read> keys: array<{ty}>;
+                @group(0) @binding(1) var<storage, read> idx: array<u32>;
+                @group(0) @binding(2) var<storage, read_write> values: array<{ty}>;
+                @group(0) @binding(3) var<storage, read_write> indices: array<u32>;
+                @group(0) @binding(4) var<uniform> params: Params;
+
+                @compute @workgroup_size({WORKGROUP_SIZE})
+                fn main(@builtin(global_invocation_id) gid: vec3<u32>) {{
+                    let tid = gid.x + gid.y * {MAX_WORKGROUPS}u * {WORKGROUP_SIZE}u;
+
+                    if tid >= params.rows * params.k {{
+                        return;
+                    }}
+
+                    let src = tid / params.k * params.padded + tid % params.k;
+                    values[tid] = keys[src];
+                    indices[tid] = idx[src];
+                }}
+            "
+        )
+    }
+}
+
+/// Sorts each `cols`-long row of `x` and writes the first `k` entries per row.
+///
+/// `keys` and `idx` are scratch buffers of `rows × cols.next_power_of_two()` elements;
+/// `values` and `indices` hold `rows × k` elements.
+///
+/// # Panics
+///
+/// - Padded length exceeds max size
+pub(crate) fn execute<T: NumericElement>(
+    ctx: &Context,
+    x: &Buffer<T>,
+    keys: &Buffer<T>,
+    idx: &Buffer<u32>,
+    values: &Buffer<T>,
+    indices: &Buffer<u32>,
+    cols: usize,
+    k: usize,
+    descending: bool,
+    stable: bool,
+) {
+    let to_u32 = |x: usize| u32::try_from(x).expect("dimension exceeds max size");
+    let rows = x.len().checked_div(cols).unwrap_or(0);
+    let padded = cols.next_power_of_two();
+    let len = u32::try_from(rows * padded).expect("padded length exceeds max size");
+
+    if len == 0 {
+        return;
+    }
+
+    let params = |block: usize, step: usize| {
+        ctx.create_uniform_buffer(&Params {
+            rows: to_u32(rows),
+            cols: to_u32(cols),
+            padded: to_u32(padded),
+            k: to_u32(k),
+            descending: u32::from(descending),
+            stable: u32::from(stable),
+            block: to_u32(block),
+            step: to_u32(step),
+        })
+    };
+
+    let (wx, wy) = crate::kernel::compute_workgroups(len);
+
+    let pipeline =
+        ctx.get_or_create_pipeline(TypeId::of::<Init<T>>(), Init::<T>::wgsl, Init::<T>::LABEL);
+    crate::kernel::dispatch(
+        ctx,
+        &pipeline,
+        Init::<T>::LABEL,
+        &[x.inner(), keys.inner(), idx.inner(), &params(0, 0)],
+        (wx, wy, 1),
+    );
+
+    let pipeline =
+        ctx.get_or_create_pipeline(TypeId::of::<Step<T>>(), Step::<T>::wgsl, Step::<T>::LABEL);
+    let mut block = 2;
+    while block <= padded {
+        let mut step = block / 2;
+        while step > 0 {
+            crate::kernel::dispatch(
+                ctx,
+                &pipeline,
+                Step::<T>::LABEL,
+                &[keys.inner(), idx.inner(), &params(block, step)],
+                (wx, wy, 1),
+            );
+            step /= 2;
+        }
+        block *= 2;
+    }
+
+    let (wx, wy) = crate::kernel::compute_workgroups(to_u32(rows * k));
+    let pipeline = ctx.get_or_create_pipeline(
+        TypeId::of::<Gather<T>>(),
+        Gather::<T>::wgsl,
+        Gather::<T>::LABEL,
+    );
+    crate::kernel::dispatch(
+        ctx,
+        &pipeline,
+        Gather::<T>::LABEL,
+        &[
+            keys.inner(),
+            idx.inner(),
+            values.inner(),
+            indices.inner(),
+            &params(0, 0),
+        ],
+        (wx, wy, 1),
+    );
+}
@@ -0,0 +1,304 @@
+//! Spatial sampling kernels.
+
+use core::any::TypeId;
+
+use alloc::format;
+use alloc::string::String;
+
+use bytemuck::{Pod, Zeroable};
+
+use crate::kernel::{Kernel, MAX_WORKGROUPS, WORKGROUP_SIZE};
+use crate::{Buffer, Context};
+
+/// Largest displacement searched by the correlation kernel.
+pub(crate) const MAX_DISPLACEMENT: usize = 15;
+
+/// Side of the square pixel tile handled by one correlation workgroup.
+const TILE: u32 = 16;
+
+/// Kernel parameters passed to shader as uniform.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Pod, Zeroable)]
+struct Params {
+    batch: u32,
+    channels: u32,
+    height: u32,
+    width: u32,
+    out_height: u32,
+    out_width: u32,
+    nearest: u32,
+    _pad: u32,
+}
+
+/// Correlation kernel parameters passed to shader as uniform.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Pod, Zeroable)]
+struct CorrelationParams {
+    channels: u32,
+    height: u32,
+    width: u32,
+    displacement: u32,
+}
+
+/// Samples `[n, c, h, w]` input at normalized `(x, y)` grid locations.
+///
+/// Grid coordinates span `[-1, 1]` across the outer pixel edges. Texels outside the input read
+/// as zero.
+struct GridSample;
+
+impl Kernel for GridSample {
+    const LABEL: &'static str = "grid_sample";
+    type Output = f32;
+
+    fn wgsl() -> String {
+        format!(
+            r"
+                struct Params {{
+                    batch: u32,
+                    channels: u32,
+                    height: u32,
+                    width: u32,
+                    out_height: u32,
+                    out_width: u32,
+                    nearest: u32,
+                    _pad: u32,
+                }}
+
+                @group(0) @binding(0) var<storage, read> x: array<f32>;
+                @group(0) @binding(1) var<storage, read> grid: array<vec2<f32>>;
+                @group(0) @binding(2) var<storage, read_write> y: array<f32>;
+                @group(0) @binding(3) var<uniform> params: Params;
+
+                fn texel(base: u32, px: i32, py: i32) -> f32 {{
+                    if px < 0 || py < 0 || px >= i32(params.width) || py >= i32(params.height) {{
+                        return 0.0;
+                    }}
+                    return x[base + u32(py) * params.width + u32(px)];
+                }}
+
+                @compute @workgroup_size({WORKGROUP_SIZE})
+                fn main(@builtin(global_invocation_id) gid: vec3<u32>) {{
+                    let tid = gid.x + gid.y * {MAX_WORKGROUPS}u * {WORKGROUP_SIZE}u;
+                    let pixels = params.out_height * params.out_width;
+
+                    if tid >= params.batch * params.channels * pixels {{
+                        return;
+                    }}
+
+                    let pixel = tid % pixels;
+                    let c = tid / pixels % params.channels;
+                    let b = tid / (pixels * params.channels);
+
+                    let g = grid[b * pixels + pixel];
+                    let sx = ((g.x + 1.0) * f32(params.width) - 1.0) * 0.5;
+                    let sy = ((g.y + 1.0) * f32(params.height) - 1.0) * 0.5;
+                    let base = (b * params.channels + c) * params.height * params.width;
+
+                    if params.nearest != 0u {{
+                        y[tid] = texel(base, i32(round(sx)), i32(round(sy)));
+                        return;
+                    }}
+
+                    let fx = floor(sx);
+                    let fy = floor(sy);
+                    let x0 = i32(fx);
+                    let y0 = i32(fy);
+                    let lx = sx - fx;
+                    let ly = sy - fy;
+
+                    let top = mix(texel(base, x0, y0), texel(base, x0 + 1, y0), lx);
+                    let bottom = mix(texel(base, x0, y0 + 1), texel(base, x0 + 1, y0 + 1), lx);
+                    y[tid] = mix(top, bottom, ly);
+                }}
+            "
+        )
+    }
+}
+
+/// Local correlation between two `[n, c, h, w]` feature maps.
+///
+/// Each workgroup covers a square tile of pixels in one batch and, for every row of
+/// displacements and every channel, stages the tile rows of `b` shifted by that row, plus a
+/// horizontal halo, in workgroup memory. Every element of `b` is then read from global memory
+/// once per displacement row instead of once per displacement.
+struct Correlation;
+
+impl Kernel for Correlation {
+    const LABEL: &'static str = "correlation";
+    type Output = f32;
+
+    fn wgsl() -> String {
+        let max_span = 2 * MAX_DISPLACEMENT + 1;
+        let halo = TILE as usize + 2 * MAX_DISPLACEMENT;
+
+        format!(
+            r"
+                struct Params {{
+                    channels: u32,
+                    height: u32,
+                    width: u32,
+                    displacement: u32,
+                }}
+
+                const TILE: u32 = {TILE}u;
+                const HALO: u32 = {halo}u;
+
+                @group(0) @binding(0) var<storage, read> a: array<f32>;
+                @group(0) @binding(1) var<storage, read> b: array<f32>;
+                @group(0) @binding(2) var<storage, read_write> y: array<f32>;
+                @group(0) @binding(3) var<uniform> params: Params;
+
+                var<workgroup> rows: array<f32, {shared}>;
+
+                @compute @workgroup_size({TILE}, {TILE})
+                fn main(
+                    @builtin(workgroup_id) wid: vec3<u32>,
+                    @builtin(local_invocation_id) lid: vec3<u32>,
+                    @builtin(local_invocation_index) index: u32,
+                ) {{
+                    let d = i32(params.displacement);
+                    let span = 2u * params.displacement + 1u;
+                    let width = TILE + 2u * params.displacement;
+                    let plane = params.height * params.width;
+                    let px = wid.x * TILE + lid.x;
+                    let py = wid.y * TILE + lid.y;
+                    let inside = px < params.width && py < params.height;
+                    let origin_x = i32(wid.x * TILE) - d;
+
+                    var acc: array<f32, {max_span}>;
+                    for (var dy = 0u; dy < span; dy++) {{
+                        for (var dx = 0u; dx < span; dx++) {{
+                            acc[dx] = 0.0;
+                        }}
+                        let row0 = i32(wid.y * TILE) + i32(dy) - d;
+
+                        for (var c = 0u; c < params.channels; c++) {{
+                            let base = (wid.z * params.channels + c) * plane;
+
+                            for (var i = index; i < TILE * width; i += TILE * TILE) {{
+                                let sy = row0 + i32(i / width);
+                                let sx = origin_x + i32(i % width);
+                                var value = 0.0;
+                                if sx >= 0 && sy >= 0 && sx < i32(params.width) && sy < i32(params.height) {{
+                                    value = b[base + u32(sy) * params.width + u32(sx)];
+                                }}
+                                rows[i] = value;
+                            }}
+                            workgroupBarrier();
+
+                            if inside {{
+                                let value = a[base + py * params.width + px];
+                                for (var dx = 0u; dx < span; dx++) {{
+                                    acc[dx] += value * rows[lid.y * width + lid.x + dx];
+                                }}
+                            }}
+                            workgroupBarrier();
+                        }}
+
+                        if inside {{
+                            let scale = 1.0 / f32(params.channels);
+                            for (var dx = 0u; dx < span; dx++) {{
+                                let channel = wid.z * span * span + dy * span + dx;
+                                y[channel * plane + py * params.width + px] = acc[dx] * scale;
+                            }}
+                        }}
+                    }}
+                }}
+            ",
+            shared = TILE as usize * halo,
+        )
+    }
+}
+
+/// Correlates `[batch, channels, height, width]` features `a` and `b` over displacements up
+/// to `displacement` into `[batch, (2 * displacement + 1)², height, width]`.
+///
+/// # Panics
+///
+/// - Displacement exceeds [`MAX_DISPLACEMENT`]
+/// - Dimension exceeds max size
+pub(crate) fn correlation(
+    ctx: &Context,
+    a: &Buffer<f32>,
+    b: &Buffer<f32>,
+    y: &Buffer<f32>,
+    [batch, channels, height, width]: [usize; 4],
+    displacement: usize,
+) {
+    assert!(
+        displacement <= MAX_DISPLACEMENT,
+        "displacement exceeds max size"
+    );
+    let to_u32 = |x: usize| u32::try_from(x).expect("dimension exceeds max size");
+
+    let pipeline = ctx.get_or_create_pipeline(
+        TypeId::of::<Correlation>(),
+        Correlation::wgsl,
+        Correlation::LABEL,
+    );
+
+    let params = ctx.create_uniform_buffer(&CorrelationParams {
+        channels: to_u32(channels),
+        height: to_u32(height),
+        width: to_u32(width),
+        displacement: to_u32(displacement),
+    });
+
+    crate::kernel::dispatch(
+        ctx,
+        &pipeline,
+        Correlation::LABEL,
+        &[a.inner(), b.inner(), y.inner(), &params],
+        (
+            to_u32(width).div_ceil(TILE),
+            to_u32(height).div_ceil(TILE),
+            to_u32(batch),
+        ),
+    );
+}
+
+/// Samples `[batch, channels, height, width]` input `x` at the `[batch, out_height,
+/// out_width, 2]` locations of `grid` into `[batch, channels, out_height, out_width]`.
+///
+/// # Panics
+///
+/// - Output length exceeds max size
+pub(crate) fn grid_sample(
+    ctx: &Context,
+    x: &Buffer<f32>,
+    grid: &Buffer<f32>,
+    y: &Buffer<f32>,
+    [batch, channels, height, width]: [usize; 4],
+    (out_height, out_width): (usize, usize),
+    nearest: bool,
+) {
+    let to_u32 = |x: usize| u32::try_from(x).expect("dimension exceeds max size");
+    let len = u32::try_from(y.len()).expect("output length exceeds max size");
+
+    let pipeline = ctx.get_or_create_pipeline(
+        TypeId::of::<GridSample>(),
+        GridSample::wgsl,
+        GridSample::LABEL,
+    );
+
+    let params = ctx.create_uniform_buffer(&Params {
+        batch: to_u32(batch),
+        channels: to_u32(channels),
+        height: to_u32(height),
+        width: to_u32(width),
+        out_height: to_u32(out_height),
+        out_width: to_u32(out_width),
+        nearest: u32::from(nearest),
+        _pad: 0,
+    });
+
+    let (wx, wy) = crate::kernel::compute_workgroups(len);
+
+    crate::kernel::dispatch(
+        ctx,
+        &pipeline,
+        GridSample::LABEL,
+        &[x.inner(), grid.inner(), y.inner(), &params],
+        (wx, wy, 1),
+    );
+}
@@ -0,0 +1,225 @@
+//! State-space model kernels.
+
+use core::any::TypeId;
+use core::marker::PhantomData;
+
+use alloc::format;
+use alloc::string::String;
+
+use bytemuck::{Pod, Zeroable};
+
+use crate::element::FloatElement;
+use crate::kernel::{Kernel, MAX_WORKGROUPS, WORKGROUP_SIZE};
+use crate::{Buffer, Context};
+
+/// Kernel parameters passed to shader as uniform.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Pod, Zeroable)]
+struct Params {
+    len: u32,
+    channels: u32,
+    length: u32,
+    states: u32,
+    chunk: u32,
+    /// `1` if the skip weights are bound.
+    skip: u32,
+    /// `1` if the state buffer holds initial states.
+    initial: u32,
+    _pad: u32,
+}
+
+/// Selective scan kernel over contiguous `[batch, channels, length]` sequences.
+///
+/// Each workgroup owns one `(batch, channel)` sequence and runs the recurrence
+/// `h_t = exp(Δ_t·A)·h_{t-1} + Δ_t·B_t·x_t` of every state in turn. The recurrence is a scan
+/// of the affine maps `h ↦ a·h + b`, composed associatively as
+/// `(a₁, b₁) ∘ (a₂, b₂) = (a₁·a₂, a₂·b₁ + b₂)`: each thread folds its chunk of steps into
+/// one map, the maps are scanned across the workgroup in shared memory, and each thread then
+/// replays its chunk from the carried-in state, accumulating `y_t += C_t·h_t`. The output
+/// starts from the skip term `D·x_t`. The states are read from `state` if flagged as initial,
+/// before the barriers of the scan, and overwritten with the states after the last step.
+struct SelectiveScan<T>(PhantomData<T>);
+
+impl<T: FloatElement> Kernel for SelectiveScan<T> {
+    const LABEL: &'static str = "selective_scan";
+    type Output = T;
+
+    fn wgsl() -> String {
+        let ty = T::wgsl_type();
+
+        format!(
+            r"
+                const WG_SIZE: u32 = {WORKGROUP_SIZE}u;
+
+                struct Params {{
+                    len: u32,
+                    channels: u32,
+                    length: u32,
+                    states: u32,
+                    chunk: u32,
+                    skip: u32,
+                    initial: u32,
+                    _pad: u32,
+                }}
+
+                @group(0) @binding(0) var<storage, read> x: array<{ty}>;
+                @group(0) @binding(1) var<storage, read> delta: array<{ty}>;
+                @group(0) @binding(2) var<storage, read> a: array<{ty}>;
+                @group(0) @binding(3) var<storage, read> b: array<{ty}>;
+                @group(0) @binding(4) var<storage, read> c: array<{ty}>;
+                @group(0) @binding(5) var<storage, read> d: array<{ty}>;
+                @group(0) @binding(6) var<storage, read_write> y: array<{ty}>;
+                @group(0) @binding(7) var<storage, read_write> state: array<{ty}>;
+                @group(0) @binding(8) var<uniform> params: Params;
+
+                var<workgroup> scan_a: array<{ty}, WG_SIZE>;
+                var<workgroup> scan_b: array<{ty}, WG_SIZE>;
+
+                @compute @workgroup_size(WG_SIZE)
+                fn main(
+                    @builtin(local_invocation_id) lid: vec3<u32>,
+                    @builtin(workgroup_id) wid: vec3<u32>
+                ) {{
+                    let tid = lid.x;
+                    let seq = wid.x + wid.y * {MAX_WORKGROUPS}u;
+
+                    if seq >= params.len {{
+                        return;
+                    }}
+
+                    let batch = seq / params.channels;
+                    let channel = seq % params.channels;
+                    let row = seq * params.length;
+                    let start = min(tid * params.chunk, params.length);
+                    let end = min(start + params.chunk, params.length);
+
+                    var skip: {ty} = 0.0;
+                    if params.skip == 1u {{
+                        skip = d[channel];
+                    }}
+                    for (var t = start; t < end; t++) {{
+                        y[row + t] = skip * x[row + t];
+                    }}
+
+                    for (var s = 0u; s < params.states; s++) {{
+                        let a_s = a[channel * params.states + s];
+                        let bc = (batch * params.states + s) * params.length;
+
+                        var init: {ty} = 0.0;
+                        if params.initial == 1u {{
+                            init = state[seq * params.states + s];
+                        }}
+
+                        var fold_a: {ty} = 1.0;
+                        var fold_b: {ty} = 0.0;
+                        for (var t = start; t < end; t++) {{
+                            let dt = delta[row + t];
+                            let decay = exp(dt * a_s);
+                            fold_a *= decay;
+                            fold_b = decay * fold_b + dt * b[bc + t] * x[row + t];
+                        }}
+
+                        scan_a[tid] = fold_a;
+                        scan_b[tid] = fold_b;
+
+                        for (var offset = 1u; offset < WG_SIZE; offset <<= 1u) {{
+                            workgroupBarrier();
+                            var next_a = scan_a[tid];
+                            var next_b = scan_b[tid];
+                            if tid >= offset {{
+                                next_b += next_a * scan_b[tid - offset];
+                                next_a *= scan_a[tid - offset];
+                            }}
+                            workgroupBarrier();
+                            scan_a[tid] = next_a;
+                            scan_b[tid] = next_b;
+                        }}
+                        workgroupBarrier();
+
+                        var h = init;
+                        if tid > 0u {{
+                            h = scan_a[tid - 1u] * init + scan_b[tid - 1u];
+                        }}
+                        for (var t = start; t < end; t++) {{
+                            let dt = delta[row + t];
+                            h = exp(dt * a_s) * h + dt * b[bc + t] * x[row + t];
+                            y[row + t] += c[bc + t] * h;
+                        }}
+
+                        if tid == 0u {{
+                            state[seq * params.states + s] =
+                                scan_a[WG_SIZE - 1u] * init + scan_b[WG_SIZE - 1u];
+                        }}
+                        workgroupBarrier();
+                    }}
+                }}
+            "
+        )
+    }
+}
+
+/// Runs the selective scan of contiguous `[batch, channels, length]` `x` and `delta` with
+/// `[channels, states]` `a`, `[batch, states, length]` `b` and `c`, optional `[channels]` skip
+/// weights `d`, writing the output into `y` and the final `[batch, channels, states]` states
+/// into `state`, which holds the initial states if `initial`.
+///
+/// # Panics
+///
+/// - Sequence count, channel count, length or state count exceeds max size
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn selective_scan<T: FloatElement>(
+    ctx: &Context,
+    x: &Buffer<T>,
+    delta: &Buffer<T>,
+    a: &Buffer<T>,
+    b: &Buffer<T>,
+    c: &Buffer<T>,
+    d: Option<&Buffer<T>>,
+    y: &Buffer<T>,
+    state: &Buffer<T>,
+    initial: bool,
+    [batch, channels, length, states]: [usize; 4],
+) {
+    let len = u32::try_from(batch * channels).expect("sequence count exceeds max size");
+    let length = u32::try_from(length).expect("length exceeds max size");
+
+    if len == 0 {
+        return;
+    }
+
+    let pipeline = ctx.get_or_create_pipeline(
+        TypeId::of::<SelectiveScan<T>>(),
+        SelectiveScan::<T>::wgsl,
+        SelectiveScan::<T>::LABEL,
+    );
+
+    let unused = ctx.create_storage_buffer(&[0u32]);
+    let params = ctx.create_uniform_buffer(&Params {
+        len,
+        channels: u32::try_from(channels).expect("channel count exceeds max size"),
+        length,
+        states: u32::try_from(states).expect("state count exceeds max size"),
+        chunk: length.div_ceil(WORKGROUP_SIZE),
+        skip: u32::from(d.is_some()),
+        initial: u32::from(initial),
+        _pad: 0,
+    });
+
+    crate::kernel::dispatch(
+        ctx,
+        &pipeline,
+        SelectiveScan::<T>::LABEL,
+        &[
+            x.inner(),
+            delta.inner(),
+            a.inner(),
+            b.inner(),
+            c.inner(),
+            d.map_or(&unused, Buffer::inner),
+            y.inner(),
+            state.inner(),
+            &params,
+        ],
+        (len.min(MAX_WORKGROUPS), len.div_ceil(MAX_WORKGROUPS), 1),
+    );
+}
@@ -0,0 +1,244 @@
+//! Running statistics kernels.
+
+use core::any::TypeId;
+use core::marker::PhantomData;
+
+use alloc::format;
+use alloc::string::String;
+
+use bytemuck::{Pod, Zeroable};
+
+use crate::element::FloatElement;
+use crate::kernel::{Kernel, MAX_WORKGROUPS, WORKGROUP_SIZE};
+use crate::{Buffer, Context};
+
+/// Merge kernel parameters passed to shader as uniform.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Pod, Zeroable)]
+struct MergeParams {
+    len: u32,
+    running: f32,
+    batch: f32,
+    cross: f32,
+}
+
+/// Standardize kernel parameters passed to shader as uniform.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Pod, Zeroable)]
+struct StandardizeParams {
+    rank: u32,
+    len: u32,
+    eps: f32,
+    clip: f32,
+}
+
+/// Fused in-place merge of batch moments into running moments.
+///
+/// `mean = w_r·mean + w_b·m_b` and `var = w_r·var + w_b·v_b + w_c·(m_b - mean)²`, with the
+/// difference taken against the running mean before the update.
+struct Merge<T>(PhantomData<T>);
+
+impl<T: FloatElement> Kernel for Merge<T> {
+    const LABEL: &'static str = "merge_moments";
+    type Output = T;
+
+    fn wgsl() -> String {
+        let ty = T::wgsl_type();
+
+        format!(
+            r"
+                struct Params {{
+                    len: u32,
+                    running: f32,
+                    batch: f32,
+                    cross: f32,
+                }}
+
+                @group(0) @binding(0) var<storage, read_write> mean: array<{ty}>;
+                @group(0) @binding(1) var<storage, read_write> variance: array<{ty}>;
+                @group(0) @binding(2) var<storage, read> batch_mean: array<{ty}>;
+                @group(0) @binding(3) var<storage, read> batch_var: array<{ty}>;
+                @group(0) @binding(4) var<uniform> params: Params;
+
+                @compute @workgroup_size({WORKGROUP_SIZE})
+                fn main(@builtin(global_invocation_id) gid: vec3<u32>) {{
+                    let tid = gid.x + gid.y * {MAX_WORKGROUPS}u * {WORKGROUP_SIZE}u;
+
+                    if tid >= params.len {{
+                        return;
+                    }}
+
+                    let m = mean[tid];
+                    let bm = batch_mean[tid];
+                    let delta = bm - m;
+
+                    mean[tid] = params.running * m + params.batch * bm;
+                    variance[tid] = params.running * variance[tid] + params.batch * batch_var[tid]
+                        + params.cross * delta * delta;
+                }}
+            "
+        )
+    }
+}
+
+/// Fused standardization with broadcast running moments.
+///
+/// `y = clamp((x - mean) / sqrt(var + eps), -clip, clip)`, where the moments are read through
+/// broadcast strides, zero along axes they do not span.
+struct Standardize<T>(PhantomData<T>);
+
+impl<T: FloatElement> Kernel for Standardize<T> {
+    const LABEL: &'static str = "standardize";
+    type Output = T;
+
+    fn wgsl() -> String {
+        let ty = T::wgsl_type();
+
+        format!(
+            r"
+                struct Params {{
+                    rank: u32,
+                    len: u32,
+                    eps: f32,
+                    clip: f32,
+                }}
+
+                @group(0) @binding(0) var<storage, read> x: array<{ty}>;
+                @group(0) @binding(1) var<storage, read> mean: array<{ty}>;
+                @group(0) @binding(2) var<storage, read> variance: array<{ty}>;
+                @group(0) @binding(3) var<storage, read_write> y: array<{ty}>;
+                @group(0) @binding(4) var<storage, read> dimensions: array<u32>;
+                @group(0) @binding(5) var<storage, read> stat_strides: array<u32>;
+                @group(0) @binding(6) var<uniform> params: Params;
+
+                @compute @workgroup_size({WORKGROUP_SIZE})
+                fn main(@builtin(global_invocation_id) gid: vec3<u32>) {{
+                    let tid = gid.x + gid.y * {MAX_WORKGROUPS}u * {WORKGROUP_SIZE}u;
+
+                    if tid >= params.len {{
+                        return;
+                    }}
+
+                    var remaining = tid;
+                    var stat = 0u;
+                    for (var i = params.rank; i > 0u; i--) {{
+                        let size = dimensions[i - 1u];
+                        stat += (remaining % size) * stat_strides[i - 1u];
+                        remaining = remaining / size;
+                    }}
+
+                    let clip = {ty}(params.clip);
+                    let z = (x[tid] - mean[stat]) / sqrt(variance[stat] + {ty}(params.eps));
+                    y[tid] = clamp(z, -clip, clip);
+                }}
+            "
+        )
+    }
+}
+
+/// Merges contiguous batch moments into the running moments in place.
+///
+/// # Panics
+///
+/// - Statistics length exceeds max size
+pub(crate) fn merge<T: FloatElement>(
+    ctx: &Context,
+    mean: &Buffer<T>,
+    var: &Buffer<T>,
+    batch_mean: &Buffer<T>,
+    batch_var: &Buffer<T>,
+    running: f32,
+    batch: f32,
+    cross: f32,
+) {
+    let len = u32::try_from(mean.len()).expect("statistics length exceeds max size");
+
+    let pipeline = ctx.get_or_create_pipeline(
+        TypeId::of::<Merge<T>>(),
+        Merge::<T>::wgsl,
+        Merge::<T>::LABEL,
+    );
+
+    let params = ctx.create_uniform_buffer(&MergeParams {
+        len,
+        running,
+        batch,
+        cross,
+    });
+
+    let (wx, wy) = crate::kernel::compute_workgroups(len);
+
+    crate::kernel::dispatch(
+        ctx,
+        &pipeline,
+        Merge::<T>::LABEL,
+        &[
+            mean.inner(),
+            var.inner(),
+            batch_mean.inner(),
+            batch_var.inner(),
+            &params,
+        ],
+        (wx, wy, 1),
+    );
+}
+
+/// Standardizes contiguous `x` of `dimensions` into `y` with the moments `mean` and `var`,
+/// read at the broadcast `stat_strides`, clamping to `[-clip, clip]`.
+///
+/// # Panics
+///
+/// - Output length exceeds max size
+/// - Output rank exceeds max size
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn standardize<T: FloatElement>(
+    ctx: &Context,
+    x: &Buffer<T>,
+    mean: &Buffer<T>,
+    var: &Buffer<T>,
+    y: &Buffer<T>,
+    dimensions: &[usize],
+    stat_strides: &[usize],
+    eps: f32,
+    clip: f32,
+) {
+    let rank = u32::try_from(dimensions.len()).expect("output rank exceeds max size");
+    let len = u32::try_from(y.len()).expect("output length exceeds max size");
+
+    if len == 0 {
+        return;
+    }
+
+    let pipeline = ctx.get_or_create_pipeline(
+        TypeId::of::<Standardize<T>>(),
+        Standardize::<T>::wgsl,
+        Standardize::<T>::LABEL,
+    );
+
+    let dimensions = ctx.create_storage_buffer(&crate::kernel::convert_strides(dimensions));
+    let stat_strides = ctx.create_storage_buffer(&crate::kernel::convert_strides(stat_strides));
+    let params = ctx.create_uniform_buffer(&StandardizeParams {
+        rank,
+        len,
+        eps,
+        clip,
+    });
+
+    let (wx, wy) = crate::kernel::compute_workgroups(len);
+
+    crate::kernel::dispatch(
+        ctx,
+        &pipeline,
+        Standardize::<T>::LABEL,
+        &[
+            x.inner(),
+            mean.inner(),
+            var.inner(),
+            y.inner(),
+            &dimensions,
+            &stat_strides,
+            &params,
+        ],
+        (wx, wy, 1),
+    );
+}
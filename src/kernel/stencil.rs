@@ -0,0 +1,229 @@
+//! Stencil kernels built from user WGSL expressions.
+
+use alloc::format;
+use alloc::string::String;
+
+use bytemuck::{Pod, Zeroable};
+
+use crate::element::NumericElement;
+use crate::error::{Error, TensorError};
+use crate::kernel::MAX_WORKGROUPS;
+use crate::kernel::pad::{MODE_REFLECT, MODE_REPLICATE};
+use crate::{Buffer, Context};
+
+/// Largest neighborhood radius along each axis.
+pub(crate) const MAX_RADIUS: usize = 4;
+
+/// Largest supported rank.
+pub(crate) const MAX_RANK: usize = 3;
+
+/// Kernel parameters passed to shader as uniform.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Pod, Zeroable)]
+struct Params {
+    /// Dimensions padded to rank 3 with leading ones.
+    dimensions: [u32; 4],
+    mode: u32,
+    _pad: [u32; 3],
+}
+
+/// Output tile of one workgroup for each rank, slowest axis first, all of `WORKGROUP_SIZE`
+/// elements.
+fn tile(rank: usize) -> [u32; 3] {
+    match rank {
+        1 => [1, 1, 256],
+        2 => [1, 16, 16],
+        _ => [4, 8, 8],
+    }
+}
+
+/// Builds a stencil kernel over input of `rank` padded to rank 3, evaluating `expr` per
+/// element.
+///
+/// Each workgroup stages its output tile plus a halo of `radius` along every real axis in
+/// workgroup memory, resolving coordinates outside the input by the boundary mode, so that
+/// every input element is read from global memory about once per tile instead of once per
+/// neighbor. `expr` sees the element as `x`, its flat index as `i`, and the neighbor at
+/// per-axis offsets as `at(d0, ...)`, with one offset per axis of the input.
+fn source(ty: &str, rank: usize, radius: usize, expr: &str) -> String {
+    let [t0, t1, t2] = tile(rank);
+    let radius = u32::try_from(radius).expect("radius exceeds max size");
+    let [h0, h1, h2] = [
+        if rank >= 3 { radius } else { 0 },
+        if rank >= 2 { radius } else { 0 },
+        radius,
+    ];
+    let at = match rank {
+        1 => format!("fn at(d0: i32) -> {ty} {{ return neighbor(0, 0, d0); }}"),
+        2 => format!("fn at(d0: i32, d1: i32) -> {ty} {{ return neighbor(0, d0, d1); }}"),
+        _ => format!("fn at(d0: i32, d1: i32, d2: i32) -> {ty} {{ return neighbor(d0, d1, d2); }}"),
+    };
+
+    format!(
+        r"
+            const T0: u32 = {t0}u;
+            const T1: u32 = {t1}u;
+            const T2: u32 = {t2}u;
+            const H0: u32 = {h0}u;
+            const H1: u32 = {h1}u;
+            const H2: u32 = {h2}u;
+            const S0: u32 = T0 + 2u * H0;
+            const S1: u32 = T1 + 2u * H1;
+            const S2: u32 = T2 + 2u * H2;
+
+            struct Params {{
+                dimensions: vec4<u32>,
+                mode: u32,
+                _pad0: u32,
+                _pad1: u32,
+                _pad2: u32,
+            }}
+
+            @group(0) @binding(0) var<storage, read> input: array<{ty}>;
+            @group(0) @binding(1) var<storage, read> fill: array<{ty}>;
+            @group(0) @binding(2) var<storage, read_write> y: array<{ty}>;
+            @group(0) @binding(3) var<uniform> params: Params;
+
+            var<workgroup> tile: array<{ty}, S0 * S1 * S2>;
+            var<private> local: vec3<u32>;
+
+            // Maps `coord` outside `[0, size)` back inside by the boundary mode, or to -1
+            // for the constant fill.
+            fn resolve(coord: i32, size: u32) -> i32 {{
+                let n = i32(size);
+                if coord >= 0 && coord < n {{
+                    return coord;
+                }}
+                switch params.mode {{
+                    case {MODE_REFLECT}u: {{
+                        return clamp(select(2 * (n - 1) - coord, -coord, coord < 0), 0, n - 1);
+                    }}
+                    case {MODE_REPLICATE}u: {{
+                        return clamp(coord, 0, n - 1);
+                    }}
+                    default: {{
+                        return -1;
+                    }}
+                }}
+            }}
+
+            fn neighbor(d0: i32, d1: i32, d2: i32) -> {ty} {{
+                let k0 = u32(i32(local.x + H0) + clamp(d0, -i32(H0), i32(H0)));
+                let k1 = u32(i32(local.y + H1) + clamp(d1, -i32(H1), i32(H1)));
+                let k2 = u32(i32(local.z + H2) + clamp(d2, -i32(H2), i32(H2)));
+                return tile[(k0 * S1 + k1) * S2 + k2];
+            }}
+
+            {at}
+
+            @compute @workgroup_size(T2, T1, T0)
+            fn main(
+                @builtin(workgroup_id) wid: vec3<u32>,
+                @builtin(local_invocation_id) lid: vec3<u32>,
+                @builtin(local_invocation_index) index: u32,
+            ) {{
+                let dims = params.dimensions.xyz;
+                let origin = vec3<i32>(
+                    i32(wid.z * T0) - i32(H0),
+                    i32(wid.y * T1) - i32(H1),
+                    i32(wid.x * T2) - i32(H2),
+                );
+
+                for (var k = index; k < S0 * S1 * S2; k += T0 * T1 * T2) {{
+                    let c0 = resolve(origin.x + i32(k / (S1 * S2)), dims.x);
+                    let c1 = resolve(origin.y + i32((k / S2) % S1), dims.y);
+                    let c2 = resolve(origin.z + i32(k % S2), dims.z);
+                    var value = fill[0];
+                    if c0 >= 0 && c1 >= 0 && c2 >= 0 {{
+                        value = input[(u32(c0) * dims.y + u32(c1)) * dims.z + u32(c2)];
+                    }}
+                    tile[k] = value;
+                }}
+                workgroupBarrier();
+
+                local = vec3<u32>(lid.z, lid.y, lid.x);
+                let p = vec3<u32>(wid.z * T0, wid.y * T1, wid.x * T2) + local;
+                if p.x >= dims.x || p.y >= dims.y || p.z >= dims.z {{
+                    return;
+                }}
+
+                let i = (p.x * dims.y + p.y) * dims.z + p.z;
+                let x = neighbor(0, 0, 0);
+                y[i] = {ty}({expr});
+            }}
+        "
+    )
+}
+
+/// Evaluates the stencil `expr` for each element of contiguous `x` with `dimensions` into
+/// `y`, staging neighborhoods of `radius` resolved by the pad `mode`, with `fill` outside
+/// the input in constant mode.
+///
+/// The pipeline is cached by its generated source, so repeated calls with the same
+/// expression, element type, rank and radius compile once.
+///
+/// # Errors
+///
+/// - [`TensorError::InvalidShape`] if an axis needs more than `MAX_WORKGROUPS` tiles.
+/// - [`TensorError::InvalidArgument`] if `expr` does not form valid WGSL.
+/// - [`Error::Device`] if buffer allocation fails.
+///
+/// # Panics
+///
+/// - Dimension exceeds max size
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn execute<T: NumericElement>(
+    ctx: &Context,
+    x: &Buffer<T>,
+    y: &Buffer<T>,
+    dimensions: &[usize],
+    radius: usize,
+    mode: u32,
+    fill: T,
+    expr: &str,
+) -> Result<(), Error> {
+    let rank = dimensions.len();
+    let mut padded = [1; 4];
+    for (padded, &size) in padded[MAX_RANK - rank..].iter_mut().zip(dimensions) {
+        *padded = u32::try_from(size).expect("dimension exceeds max size");
+    }
+
+    let [t0, t1, t2] = tile(rank);
+    let workgroups = (
+        padded[2].div_ceil(t2),
+        padded[1].div_ceil(t1),
+        padded[0].div_ceil(t0),
+    );
+    if workgroups.0.max(workgroups.1).max(workgroups.2) > MAX_WORKGROUPS {
+        return Err(TensorError::InvalidShape(format!(
+            "stencil over {dimensions:?} exceeds {MAX_WORKGROUPS} tiles along an axis"
+        ))
+        .into());
+    }
+
+    let pipeline = ctx
+        .get_or_create_source_pipeline(source(T::wgsl_type(), rank, radius, expr), "stencil")
+        .map_err(|error| {
+            TensorError::InvalidArgument(format!("invalid stencil expression `{expr}`: {error}"))
+        })?;
+
+    if y.is_empty() {
+        return Ok(());
+    }
+
+    let fill = ctx.create_buffer_from_slice(&[fill])?;
+    let params = ctx.create_uniform_buffer(&Params {
+        dimensions: padded,
+        mode,
+        _pad: [0; 3],
+    });
+
+    crate::kernel::dispatch(
+        ctx,
+        &pipeline,
+        "stencil",
+        &[x.inner(), fill.inner(), y.inner(), &params],
+        (workgroups.0, workgroups.1, workgroups.2),
+    );
+    Ok(())
+}
@@ -0,0 +1,129 @@
+//! Strided copy kernel.
+
+use core::any::TypeId;
+use core::marker::PhantomData;
+
+use alloc::format;
+use alloc::string::String;
+
+use bytemuck::{Pod, Zeroable};
+
+use crate::kernel::{Kernel, MAX_WORKGROUPS, WORKGROUP_SIZE};
+use crate::{Buffer, Context, Element};
+
+/// Kernel parameters passed to shader as uniform.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Pod, Zeroable)]
+struct Params {
+    rank: u32,
+    len: u32,
+    offset: u32,
+    _pad: u32,
+}
+
+/// Strided copy kernel: `dst[i] = src[offset + Σ coord(i)ₖ · src_strides[k]]`.
+struct StridedCopy<T>(PhantomData<T>);
+
+impl<T: Element> Kernel for StridedCopy<T> {
+    const LABEL: &'static str = "strided_copy";
+    type Output = T;
+
+    fn wgsl() -> String {
+        let ty = T::wgsl_type();
+
+        format!(
+            r"
+                struct Params {{
+                    rank: u32,
+                    len: u32,
+                    offset: u32,
+                    _pad: u32,
+                }}
+
+                @group(0) @binding(0) var<storage, read> src: array<{ty}>;
+                @group(0) @binding(1) var<storage, read_write> dst: array<{ty}>;
+                @group(0) @binding(2) var<storage, read> src_strides: array<u32>;
+                @group(0) @binding(3) var<storage, read> dst_strides: array<u32>;
+                @group(0) @binding(4) var<uniform> params: Params;
+
+                @compute @workgroup_size({WORKGROUP_SIZE})
+                fn main(@builtin(global_invocation_id) gid: vec3<u32>) {{
+                    let tid = gid.x + gid.y * {MAX_WORKGROUPS}u * {WORKGROUP_SIZE}u;
+
+                    if tid >= params.len {{
+                        return;
+                    }}
+
+                    var remaining = tid;
+                    var src_idx = params.offset;
+
+                    for (var i = 0u; i < params.rank; i++) {{
+                        let coord = remaining / dst_strides[i];
+                        remaining = remaining % dst_strides[i];
+                        src_idx += coord * src_strides[i];
+                    }}
+
+                    dst[tid] = src[src_idx];
+                }}
+            "
+        )
+    }
+}
+
+/// Copies the strided view `(src_strides, offset)` of `src` into contiguous `dst`.
+///
+/// `dst_strides` are the contiguous strides of the output dimensions. The copy runs even while
+/// a stop flag is set, so views stay readable.
+///
+/// # Panics
+///
+/// - Output length exceeds max size
+/// - Output rank exceeds max size
+/// - Offset exceeds max size
+pub(crate) fn execute<T: Element>(
+    ctx: &Context,
+    src: &Buffer<T>,
+    dst: &Buffer<T>,
+    src_strides: &[usize],
+    dst_strides: &[usize],
+    offset: usize,
+) {
+    let rank = u32::try_from(dst_strides.len()).expect("output rank exceeds max size");
+    let len = u32::try_from(dst.len()).expect("output length exceeds max size");
+    let offset = u32::try_from(offset).expect("offset exceeds max size");
+
+    if len == 0 {
+        return;
+    }
+
+    let pipeline = ctx.get_or_create_pipeline(
+        TypeId::of::<StridedCopy<T>>(),
+        StridedCopy::<T>::wgsl,
+        StridedCopy::<T>::LABEL,
+    );
+
+    let src_strides = ctx.create_storage_buffer(&crate::kernel::convert_strides(src_strides));
+    let dst_strides = ctx.create_storage_buffer(&crate::kernel::convert_strides(dst_strides));
+    let params = ctx.create_uniform_buffer(&Params {
+        rank,
+        len,
+        offset,
+        _pad: 0,
+    });
+
+    let (x, y) = crate::kernel::compute_workgroups(len);
+
+    crate::kernel::dispatch_ungated(
+        ctx,
+        &pipeline,
+        StridedCopy::<T>::LABEL,
+        &[
+            src.inner(),
+            dst.inner(),
+            &src_strides,
+            &dst_strides,
+            &params,
+        ],
+        (x, y, 1),
+    );
+}
@@ -0,0 +1,290 @@
+//! Texture conversion kernel.
+
+use core::any::TypeId;
+
+use alloc::format;
+use alloc::string::String;
+
+use bytemuck::{Pod, Zeroable};
+
+use crate::kernel::{Kernel, MAX_WORKGROUPS, WORKGROUP_SIZE};
+use crate::{Buffer, Context};
+
+/// Kernel parameters passed to shader as uniform.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Pod, Zeroable)]
+struct Params {
+    width: u32,
+    height: u32,
+    _pad: [u32; 2],
+    mean: [f32; 4],
+    scale: [f32; 4],
+}
+
+/// Converts an RGBA texture into planar RGB: `y[c, i, j] = (texel[i, j][c] - mean[c]) · scale[c]`.
+struct FromTexture;
+
+impl Kernel for FromTexture {
+    const LABEL: &'static str = "from_texture";
+    type Output = f32;
+
+    fn wgsl() -> String {
+        format!(
+            r"
+                struct Params {{
+                    width: u32,
+                    height: u32,
+                    _pad: vec2<u32>,
+                    mean: vec4<f32>,
+                    scale: vec4<f32>,
+                }}
+
+                @group(0) @binding(0) var input: texture_2d<f32>;
+                @group(0) @binding(1) var<storage, read_write> y: array<f32>;
+                @group(0) @binding(2) var<uniform> params: Params;
+
+                @compute @workgroup_size({WORKGROUP_SIZE})
+                fn main(@builtin(global_invocation_id) gid: vec3<u32>) {{
+                    let tid = gid.x + gid.y * {MAX_WORKGROUPS}u * {WORKGROUP_SIZE}u;
+                    let plane = params.width * params.height;
+
+                    if tid >= plane {{
+                        return;
+                    }}
+
+                    let coords = vec2<u32>(tid % params.width, tid / params.width);
+                    let texel = (textureLoad(input, coords, 0) - params.mean) * params.scale;
+
+                    y[tid] = texel.r;
+                    y[plane + tid] = texel.g;
+                    y[2u * plane + tid] = texel.b;
+                }}
+            "
+        )
+    }
+}
+
+/// Letterbox kernel parameters passed to shader as uniform.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Pod, Zeroable)]
+struct LetterboxParams {
+    in_width: u32,
+    in_height: u32,
+    out_width: u32,
+    out_height: u32,
+    left: u32,
+    top: u32,
+    new_width: u32,
+    new_height: u32,
+    offset: u32,
+    fill: f32,
+    step_x: f32,
+    step_y: f32,
+    mean: [f32; 4],
+    scale: [f32; 4],
+}
+
+/// Bilinear resize into a padded region of a planar RGB image, with normalization.
+///
+/// Output pixels inside the `new_width × new_height` region at `(left, top)` sample the
+/// texture bilinearly at pixel centers; the rest are set to `fill` before normalization.
+struct Letterbox;
+
+impl Kernel for Letterbox {
+    const LABEL: &'static str = "letterbox";
+    type Output = f32;
+
+    fn wgsl() -> String {
+        format!(
+            r"
+                struct Params {{
+                    in_width: u32,
+                    in_height: u32,
+                    out_width: u32,
+                    out_height: u32,
+                    left: u32,
+                    top: u32,
+                    new_width: u32,
+                    new_height: u32,
+                    offset: u32,
+                    fill: f32,
+                    step_x: f32,
+                    step_y: f32,
+                    mean: vec4<f32>,
+                    scale: vec4<f32>,
+                }}
+
+                @group(0) @binding(0) var input: texture_2d<f32>;
+                @group(0) @binding(1) var<storage, read_write> y: array<f32>;
+                @group(0) @binding(2) var<uniform> params: Params;
+
+                fn texel(x: u32, y: u32) -> vec4<f32> {{
+                    return textureLoad(input, vec2<u32>(x, y), 0);
+                }}
+
+                @compute @workgroup_size({WORKGROUP_SIZE})
+                fn main(@builtin(global_invocation_id) gid: vec3<u32>) {{
+                    let tid = gid.x + gid.y * {MAX_WORKGROUPS}u * {WORKGROUP_SIZE}u;
+                    let plane = params.out_width * params.out_height;
+
+                    if tid >= plane {{
+                        return;
+                    }}
+
+                    let ox = tid % params.out_width;
+                    let oy = tid / params.out_width;
+
+                    var color = vec4<f32>(params.fill);
+                    if ox >= params.left && ox < params.left + params.new_width
+                        && oy >= params.top && oy < params.top + params.new_height {{
+                        let max_x = f32(params.in_width - 1u);
+                        let max_y = f32(params.in_height - 1u);
+                        let sx = clamp((f32(ox - params.left) + 0.5) * params.step_x - 0.5, 0.0, max_x);
+                        let sy = clamp((f32(oy - params.top) + 0.5) * params.step_y - 0.5, 0.0, max_y);
+
+                        let x0 = u32(sx);
+                        let y0 = u32(sy);
+                        let x1 = min(x0 + 1u, params.in_width - 1u);
+                        let y1 = min(y0 + 1u, params.in_height - 1u);
+                        let fx = sx - f32(x0);
+                        let fy = sy - f32(y0);
+
+                        let upper = mix(texel(x0, y0), texel(x1, y0), fx);
+                        let lower = mix(texel(x0, y1), texel(x1, y1), fx);
+                        color = mix(upper, lower, fy);
+                    }}
+
+                    let value = (color - params.mean) * params.scale;
+                    y[params.offset + tid] = value.r;
+                    y[params.offset + plane + tid] = value.g;
+                    y[params.offset + 2u * plane + tid] = value.b;
+                }}
+            "
+        )
+    }
+}
+
+/// Converts mip level 0 of a 2D float-sampled texture into a `[3, height, width]` buffer.
+///
+/// # Panics
+///
+/// - Texture size exceeds max size
+pub(crate) fn from_texture(
+    ctx: &Context,
+    view: &wgpu::TextureView,
+    y: &Buffer<f32>,
+    width: u32,
+    height: u32,
+    mean: [f32; 3],
+    scale: [f32; 3],
+) {
+    let len = width
+        .checked_mul(height)
+        .expect("texture size exceeds max size");
+
+    let pipeline = ctx.get_or_create_pipeline(
+        TypeId::of::<FromTexture>(),
+        FromTexture::wgsl,
+        FromTexture::LABEL,
+    );
+
+    let params = ctx.create_uniform_buffer(&Params {
+        width,
+        height,
+        _pad: [0; 2],
+        mean: [mean[0], mean[1], mean[2], 0.0],
+        scale: [scale[0], scale[1], scale[2], 0.0],
+    });
+
+    let (wx, wy) = crate::kernel::compute_workgroups(len);
+
+    crate::kernel::dispatch_entries(
+        ctx,
+        &pipeline,
+        FromTexture::LABEL,
+        &[
+            wgpu::BindGroupEntry {
+                binding: 0,
+                resource: wgpu::BindingResource::TextureView(view),
+            },
+            wgpu::BindGroupEntry {
+                binding: 1,
+                resource: y.inner().as_entire_binding(),
+            },
+            wgpu::BindGroupEntry {
+                binding: 2,
+                resource: params.as_entire_binding(),
+            },
+        ],
+        (wx, wy, 1),
+    );
+}
+
+/// Letterboxes mip level 0 of a 2D float-sampled texture into `[3, out_height, out_width]`
+/// elements of `y` starting at `offset`.
+///
+/// The texture is resized to `new_width × new_height` and placed at `(left, top)`.
+///
+/// # Panics
+///
+/// - Output offset exceeds max size
+pub(crate) fn letterbox(
+    ctx: &Context,
+    view: &wgpu::TextureView,
+    y: &Buffer<f32>,
+    offset: usize,
+    (in_width, in_height): (u32, u32),
+    (out_width, out_height): (u32, u32),
+    (left, top): (u32, u32),
+    (new_width, new_height): (u32, u32),
+    fill: f32,
+    mean: [f32; 3],
+    scale: [f32; 3],
+) {
+    let offset = u32::try_from(offset).expect("output offset exceeds max size");
+    #[allow(clippy::cast_precision_loss)]
+    let step = |input: u32, resized: u32| input as f32 / resized as f32;
+
+    let pipeline =
+        ctx.get_or_create_pipeline(TypeId::of::<Letterbox>(), Letterbox::wgsl, Letterbox::LABEL);
+
+    let params = ctx.create_uniform_buffer(&LetterboxParams {
+        in_width,
+        in_height,
+        out_width,
+        out_height,
+        left,
+        top,
+        new_width,
+        new_height,
+        offset,
+        fill,
+        step_x: step(in_width, new_width),
+        step_y: step(in_height, new_height),
+        mean: [mean[0], mean[1], mean[2], 0.0],
+        scale: [scale[0], scale[1], scale[2], 0.0],
+    });
+
+    let (wx, wy) = crate::kernel::compute_workgroups(out_width * out_height);
+
+    crate::kernel::dispatch_entries(
+        ctx,
+        &pipeline,
+        Letterbox::LABEL,
+        &[
+            wgpu::BindGroupEntry {
+                binding: 0,
+                resource: wgpu::BindingResource::TextureView(view),
+            },
+            wgpu::BindGroupEntry {
+                binding: 1,
+                resource: y.inner().as_entire_binding(),
+            },
+            wgpu::BindGroupEntry {
+                binding: 2,
+                resource: params.as_entire_binding(),
+            },
+        ],
+        (wx, wy, 1),
+    );
+}
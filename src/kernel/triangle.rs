@@ -0,0 +1,135 @@
+//! Triangular masking kernel.
+
+use core::any::TypeId;
+use core::marker::PhantomData;
+
+use alloc::format;
+use alloc::string::String;
+
+use bytemuck::{Pod, Zeroable};
+
+use crate::kernel::{Kernel, MAX_WORKGROUPS, WORKGROUP_SIZE};
+use crate::{Buffer, Context, Element};
+
+/// Kernel parameters passed to shader as uniform.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Pod, Zeroable)]
+struct Params {
+    rows: u32,
+    cols: u32,
+    len: u32,
+    diagonal: i32,
+    upper: u32,
+    _pad0: u32,
+    _pad1: u32,
+    _pad2: u32,
+}
+
+/// Triangular masking kernel over a batch of contiguous `[rows, cols]` matrices.
+///
+/// Keeps `y[i, j] = x[i, j]` where `j - i ≤ diagonal` (lower, `upper = 0`) or
+/// `j - i ≥ diagonal` (upper, `upper = 1`), and writes zero elsewhere.
+struct Triangle<T>(PhantomData<T>);
+
+impl<T: Element> Kernel for Triangle<T> {
+    const LABEL: &'static str = "triangle";
+    type Output = T;
+
+    fn wgsl() -> String {
+        let ty = T::wgsl_type();
+        let zero = T::wgsl_zero();
+
+        format!(
+            r"
+                struct Params {{
+                    rows: u32,
+                    cols: u32,
+                    len: u32,
+                    diagonal: i32,
+                    upper: u32,
+                    _pad0: u32,
+                    _pad1: u32,
+                    _pad2: u32,
+                }}
+
+                @group(0) @binding(0) var<storage, read> x: array<{ty}>;
+                @group(0) @binding(1) var<storage, read_write> y: array<{ty}>;
+                @group(0) @binding(2) var<uniform> params: Params;
+
+                @compute @workgroup_size({WORKGROUP_SIZE})
+                fn main(@builtin(global_invocation_id) gid: vec3<u32>) {{
+                    let tid = gid.x + gid.y * {MAX_WORKGROUPS}u * {WORKGROUP_SIZE}u;
+
+                    if tid >= params.len {{
+                        return;
+                    }}
+
+                    let row = i32((tid / params.cols) % params.rows);
+                    let col = i32(tid % params.cols);
+                    let offset = col - row;
+
+                    var keep: bool;
+                    if params.upper == 1u {{
+                        keep = offset >= params.diagonal;
+                    }} else {{
+                        keep = offset <= params.diagonal;
+                    }}
+
+                    if keep {{
+                        y[tid] = x[tid];
+                    }} else {{
+                        y[tid] = {zero};
+                    }}
+                }}
+            "
+        )
+    }
+}
+
+/// Writes the lower (or `upper`) triangle of each contiguous `[rows, cols]` matrix of `x`
+/// relative to `diagonal` into `y`, zeroing the rest.
+///
+/// # Panics
+///
+/// - Output length exceeds max size
+pub(crate) fn triangle<T: Element>(
+    ctx: &Context,
+    x: &Buffer<T>,
+    y: &Buffer<T>,
+    rows: usize,
+    cols: usize,
+    diagonal: i32,
+    upper: bool,
+) {
+    let len = u32::try_from(y.len()).expect("output length exceeds max size");
+
+    if len == 0 {
+        return;
+    }
+
+    let pipeline = ctx.get_or_create_pipeline(
+        TypeId::of::<Triangle<T>>(),
+        Triangle::<T>::wgsl,
+        Triangle::<T>::LABEL,
+    );
+    let params = ctx.create_uniform_buffer(&Params {
+        rows: u32::try_from(rows).expect("output length exceeds max size"),
+        cols: u32::try_from(cols).expect("output length exceeds max size"),
+        len,
+        diagonal,
+        upper: u32::from(upper),
+        _pad0: 0,
+        _pad1: 0,
+        _pad2: 0,
+    });
+
+    let (wx, wy) = crate::kernel::compute_workgroups(len);
+
+    crate::kernel::dispatch(
+        ctx,
+        &pipeline,
+        Triangle::<T>::LABEL,
+        &[x.inner(), y.inner(), &params],
+        (wx, wy, 1),
+    );
+}
@@ -6,24 +6,77 @@
 //! # Types
 //!
 //! - [`Context`] — GPU context for buffer and pipeline management.
-//! - [`Buffer`] — Typed GPU buffer for element data.
-//! - [`Element`] — Trait for GPU-compatible types (`f32`, `i32`, `u32`, `bool`).
+//! - [`Buffer`] — Typed GPU buffer for element data, usable without tensors through [`compute`].
+//! - [`Element`] — Trait for GPU-compatible types (`f32`, `i32`, `u32`, `bool`, [`Df64`]).
 //! - [`Error`] — Error type for GPU operations.
 //! - [`Tensor`] — N-dimensional array with GPU-accelerated operations.
+//! - [`ShardedMatrix`] — Weight matrix split across buffers to fit binding limits.
+//! - [`Normalization`] — Per-channel normalization for [`Tensor::from_texture`].
+//! - [`Letterbox`] — Placement of an image in a letterboxed detector batch.
+//! - [`Interpolation`] — Sampling mode for [`Tensor::grid_sample`].
+//! - [`MemoryFormat`] — NCHW or NHWC storage order of image tensors.
+//! - [`PadMode`] — Border fill mode for [`Tensor::pad`].
+//! - [`BagMode`] — Bag reduction for [`Tensor::embedding_bag`].
+//! - [`INFER`] — Inferred dimension for [`Tensor::reshape`].
+//!
+//! # Modules
+//!
+//! - [`compute`] — Fill, copy, matrix multiplication, reductions, and WGSL expression maps and
+//!   stencils on raw buffers.
+//! - `checkpoint` — Safetensors import of `PyTorch` state dicts (requires the `checkpoint`
+//!   feature).
+//! - `data` — CSV ingestion into column tensors (requires the `data` feature).
+//! - [`diffusion`] — Noise schedules and sampler steps for diffusion models.
+//! - [`distributions`] — Categorical and normal distributions with fused sampling kernels.
+//! - [`ir`] — Serializable operator graphs and a registry of named operators to run them.
+//! - [`linear_model`] — Linear and logistic regression trained on the GPU.
+//! - [`model_selection`] — Train/test splits, k-fold cross-validation and fold metrics.
+//! - [`moe`] — Mixture-of-experts routing and grouped expert matmul.
+//! - [`optim`] — L-BFGS search directions from caller-supplied gradients, Armijo/Wolfe line
+//!   search, and GPU-side convergence flags.
+//! - [`preprocessing`] — Feature scalers and target encoding for tabular data.
+//! - [`prune`] — Magnitude pruning masks.
+//! - [`quant`] — Calibration observers for int8 quantization.
+//! - [`rl`] — Rollout storage and advantage estimation for reinforcement learning.
+//! - [`stats`] — Running statistics over tensor streams.
+//! - [`wavenet`] — Dilated causal convolution stacks with residual and skip connections.
 
 #![warn(missing_docs)]
 #![no_std]
 
 extern crate alloc;
+#[cfg(any(feature = "checkpoint", feature = "data"))]
+extern crate std;
 
+#[cfg(feature = "checkpoint")]
+pub mod checkpoint;
+pub mod compute;
+#[cfg(feature = "data")]
+pub mod data;
+pub mod diffusion;
+pub mod distributions;
 pub mod element;
 pub mod error;
+pub mod ir;
+pub mod linear_model;
+pub mod model_selection;
+pub mod moe;
+pub mod optim;
+pub mod preprocessing;
+pub mod prune;
+pub mod quant;
+pub mod rl;
+pub mod stats;
+pub mod wavenet;
 
 mod device;
 mod kernel;
 mod tensor;
 
 pub use device::{Buffer, Context};
-pub use element::Element;
+pub use element::{Df64, Element};
 pub use error::Error;
-pub use tensor::Tensor;
+pub use tensor::{
+    BagMode, INFER, Interpolation, Letterbox, MemoryFormat, Normalization, PadMode, ShardedMatrix,
+    Tensor,
+};
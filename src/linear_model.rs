@@ -0,0 +1,204 @@
+//! Linear models trained by full-batch gradient descent.
+//!
+//! - [`LinearRegression`] — least-squares regression.
+//! - [`LogisticRegression`] — binary classification through a sigmoid link.
+//!
+//! Both fit `[samples, features]` inputs against `[samples]` targets. Each step evaluates the
+//! model with one matmul and takes the gradient of the mean loss with a second, transposed
+//! matmul `Xᵀ · residual`, so training stays on the GPU. Gradient descent converges fastest on
+//! features of similar scale, e.g. standardized by
+//! [`StandardScaler`](crate::preprocessing::StandardScaler).
+
+use alloc::format;
+
+use crate::Tensor;
+use crate::error::{Error, TensorError};
+
+/// Fits `y ≈ X · w + b` by minimizing half the mean squared error.
+pub struct LinearRegression {
+    /// Fitted weights and bias.
+    model: Linear,
+}
+
+impl LinearRegression {
+    /// Fits the model to `[samples, features]` `x` and `[samples]` `y` with `epochs` gradient
+    /// descent steps of size `learning_rate`, starting from zero weights.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidShape`] if `x` is not rank 2 with at least one sample, or `y`
+    ///   is not `[samples]`.
+    /// - [`TensorError::InvalidArgument`] if `learning_rate` is not positive and finite.
+    /// - [`Error::Device`] if GPU operation fails.
+    pub fn fit(
+        x: &Tensor<f32>,
+        y: &Tensor<f32>,
+        learning_rate: f32,
+        epochs: usize,
+    ) -> Result<Self, Error> {
+        let model = Linear::fit(x, y, learning_rate, epochs, Ok)?;
+        Ok(Self { model })
+    }
+
+    /// Predicts the `[samples]` targets of `[samples, features]` `x`.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidShape`] if `x` does not have the fitted number of features.
+    /// - [`Error::Device`] if GPU operation fails.
+    pub fn predict(&self, x: &Tensor<f32>) -> Result<Tensor<f32>, Error> {
+        self.model.predict(x)
+    }
+
+    /// Fitted weights, `[features, 1]`.
+    #[must_use]
+    pub fn weights(&self) -> &Tensor<f32> {
+        &self.model.weights
+    }
+
+    /// Fitted bias, `[1, 1]`.
+    #[must_use]
+    pub fn bias(&self) -> &Tensor<f32> {
+        &self.model.bias
+    }
+}
+
+/// Fits `P(y = 1) = σ(X · w + b)` by minimizing the mean binary cross-entropy.
+///
+/// Targets are `0.0` or `1.0`; soft labels in between are accepted as well.
+pub struct LogisticRegression {
+    /// Fitted weights and bias of the logits.
+    model: Linear,
+}
+
+impl LogisticRegression {
+    /// Fits the model to `[samples, features]` `x` and `[samples]` labels `y` with `epochs`
+    /// gradient descent steps of size `learning_rate`, starting from zero weights.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidShape`] if `x` is not rank 2 with at least one sample, or `y`
+    ///   is not `[samples]`.
+    /// - [`TensorError::InvalidArgument`] if `learning_rate` is not positive and finite.
+    /// - [`Error::Device`] if GPU operation fails.
+    pub fn fit(
+        x: &Tensor<f32>,
+        y: &Tensor<f32>,
+        learning_rate: f32,
+        epochs: usize,
+    ) -> Result<Self, Error> {
+        let model = Linear::fit(x, y, learning_rate, epochs, |z| z.sigmoid())?;
+        Ok(Self { model })
+    }
+
+    /// Predicts the `[samples]` probabilities of the positive class for `[samples, features]`
+    /// `x`.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidShape`] if `x` does not have the fitted number of features.
+    /// - [`Error::Device`] if GPU operation fails.
+    pub fn predict_proba(&self, x: &Tensor<f32>) -> Result<Tensor<f32>, Error> {
+        self.model.predict(x)?.sigmoid()
+    }
+
+    /// Predicts the `[samples]` classes of `[samples, features]` `x`: true where the positive
+    /// class has a probability of at least one half.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidShape`] if `x` does not have the fitted number of features.
+    /// - [`Error::Device`] if GPU operation fails.
+    pub fn predict(&self, x: &Tensor<f32>) -> Result<Tensor<bool>, Error> {
+        let zero = Tensor::scalar(x.context(), 0.0)?;
+        self.model.predict(x)?.ge(&zero)
+    }
+
+    /// Fitted weights of the logits, `[features, 1]`.
+    #[must_use]
+    pub fn weights(&self) -> &Tensor<f32> {
+        &self.model.weights
+    }
+
+    /// Fitted bias of the logits, `[1, 1]`.
+    #[must_use]
+    pub fn bias(&self) -> &Tensor<f32> {
+        &self.model.bias
+    }
+}
+
+/// Weights and bias of an affine map, fitted as a generalized linear model.
+struct Linear {
+    /// Weights, `[features, 1]`.
+    weights: Tensor<f32>,
+    /// Bias, `[1, 1]`.
+    bias: Tensor<f32>,
+}
+
+impl Linear {
+    /// Fits the model whose mean prediction is `link(X · w + b)`.
+    ///
+    /// For the canonical loss of `link` the gradient of the mean loss is `Xᵀ · r / n` for the
+    /// weights and `mean(r)` for the bias, with residual `r = link(X · w + b) - y`.
+    fn fit(
+        x: &Tensor<f32>,
+        y: &Tensor<f32>,
+        learning_rate: f32,
+        epochs: usize,
+        link: impl Fn(Tensor<f32>) -> Result<Tensor<f32>, Error>,
+    ) -> Result<Self, Error> {
+        let &[samples, features] = x.dimensions() else {
+            return Err(TensorError::InvalidShape(format!(
+                "inputs must be [samples, features], got {:?}",
+                x.dimensions()
+            ))
+            .into());
+        };
+
+        if samples == 0 || y.dimensions() != [samples] {
+            return Err(TensorError::InvalidShape(format!(
+                "targets {:?} must be [{samples}] with at least one sample",
+                y.dimensions()
+            ))
+            .into());
+        }
+
+        if !(learning_rate.is_finite() && learning_rate > 0.0) {
+            return Err(TensorError::InvalidArgument(format!(
+                "learning rate {learning_rate} must be positive and finite"
+            ))
+            .into());
+        }
+
+        let ctx = x.context();
+        let y = y.reshape(&[samples, 1])?;
+        #[allow(clippy::cast_precision_loss)]
+        let step = Tensor::scalar(ctx, learning_rate / samples as f32)?;
+        let rate = Tensor::scalar(ctx, learning_rate)?;
+
+        let mut model = Self {
+            weights: Tensor::constant(ctx, &[features, 1], &[0.0])?,
+            bias: Tensor::constant(ctx, &[1, 1], &[0.0])?,
+        };
+
+        for _ in 0..epochs {
+            let residual = link(model.forward(x)?)?.sub(&y)?;
+            let grad = x.matmul(&residual, true, false)?;
+            model.weights = model.weights.sub(&grad.mul(&step)?)?;
+            model.bias = model.bias.sub(&residual.mean_reduce(&[0])?.mul(&rate)?)?;
+        }
+
+        Ok(model)
+    }
+
+    /// Evaluates `X · w + b` as `[samples, 1]`.
+    fn forward(&self, x: &Tensor<f32>) -> Result<Tensor<f32>, Error> {
+        x.matmul(&self.weights, false, false)?.add(&self.bias)
+    }
+
+    /// Evaluates `X · w + b` as `[samples]`.
+    fn predict(&self, x: &Tensor<f32>) -> Result<Tensor<f32>, Error> {
+        let z = self.forward(x)?;
+        z.reshape(&z.dimensions()[..1])
+    }
+}
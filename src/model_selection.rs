@@ -0,0 +1,179 @@
+//! Dataset splitting for model evaluation.
+//!
+//! - [`train_test_split`] — holds out a subset of samples for testing.
+//! - [`KFold`] — k-fold cross-validation splits.
+//! - [`take_samples`] — gathers the samples of a split.
+//! - [`fold_summary`] — mean and standard deviation of per-fold metrics.
+//!
+//! Splits are `[n]` `u32` index tensors over the sample axis 0. With a seed the samples are
+//! shuffled on the GPU by [`Tensor::randperm`], so evaluation loops gather their subsets
+//! without reading the dataset back to the host.
+
+use alloc::format;
+use alloc::vec::Vec;
+
+use crate::error::{Error, TensorError};
+use crate::{Context, Element, Tensor};
+
+/// Splits `samples` into train and test indices, with `test_size` samples for testing.
+///
+/// With a `seed` the samples are shuffled before splitting; otherwise the test indices are
+/// the first `test_size` samples in order. Returns `(train, test)`.
+///
+/// # Errors
+///
+/// - [`TensorError::InvalidArgument`] if `test_size` exceeds `samples`, or `samples` exceeds
+///   `u32::MAX`.
+/// - [`Error::Device`] if GPU operation fails.
+pub fn train_test_split(
+    ctx: &Context,
+    samples: usize,
+    test_size: usize,
+    seed: Option<u32>,
+) -> Result<(Tensor<u32>, Tensor<u32>), Error> {
+    if test_size > samples {
+        return Err(TensorError::InvalidArgument(format!(
+            "test size {test_size} exceeds {samples} samples"
+        ))
+        .into());
+    }
+
+    let order = permutation(ctx, samples, seed)?;
+    let train = order.narrow(0, test_size, samples - test_size)?;
+    let test = order.narrow(0, 0, test_size)?;
+
+    Ok((train, test))
+}
+
+/// K-fold cross-validation splits.
+///
+/// The samples, shuffled if a seed is given, are cut into `folds` consecutive folds whose
+/// sizes differ by at most one, the first `samples % folds` folds taking one extra sample.
+/// Each fold validates once while the others train.
+pub struct KFold {
+    /// Sample indices in fold order.
+    order: Tensor<u32>,
+    /// Start of each fold in `order`, followed by the number of samples.
+    bounds: Vec<u32>,
+}
+
+impl KFold {
+    /// Creates `folds` folds over `samples` samples, shuffled by `seed` if given.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidArgument`] if `folds` is less than 2 or exceeds `samples`, or
+    ///   `samples` exceeds `u32::MAX`.
+    /// - [`Error::Device`] if GPU operation fails.
+    pub fn new(
+        ctx: &Context,
+        samples: usize,
+        folds: usize,
+        seed: Option<u32>,
+    ) -> Result<Self, Error> {
+        if folds < 2 || folds > samples {
+            return Err(TensorError::InvalidArgument(format!(
+                "{folds} folds must be at least 2 and at most {samples} samples"
+            ))
+            .into());
+        }
+
+        let order = permutation(ctx, samples, seed)?;
+        let (size, extra) = (samples / folds, samples % folds);
+        let bounds = (0..=folds)
+            .map(|fold| fold * size + fold.min(extra))
+            .map(|bound| u32::try_from(bound).unwrap_or(u32::MAX))
+            .collect();
+
+        Ok(Self { order, bounds })
+    }
+
+    /// Number of folds.
+    #[must_use]
+    pub fn folds(&self) -> usize {
+        self.bounds.len() - 1
+    }
+
+    /// Returns the `(train, validation)` indices of `fold`.
+    ///
+    /// Validation indices are the samples of `fold`; train indices are the samples of every
+    /// other fold, in fold order.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidArgument`] if `fold` is out of bounds.
+    /// - [`Error::Device`] if GPU operation fails.
+    pub fn split(&self, fold: usize) -> Result<(Tensor<u32>, Tensor<u32>), Error> {
+        if fold >= self.folds() {
+            return Err(TensorError::InvalidArgument(format!(
+                "fold {fold} out of bounds for {} folds",
+                self.folds()
+            ))
+            .into());
+        }
+
+        let (start, end) = (self.bounds[fold], self.bounds[fold + 1]);
+        let samples = self.bounds[self.folds()];
+        let validation = self
+            .order
+            .narrow(0, start as usize, (end - start) as usize)?;
+
+        let positions: Vec<u32> = (0..start).chain(end..samples).collect();
+        let positions = Tensor::from_slice(self.order.context(), &positions)?;
+        let train = take_samples(&self.order, &positions)?;
+
+        Ok((train, validation))
+    }
+}
+
+/// Gathers the samples of `x` along axis 0 selected by the `[n]` `indices` of a split.
+///
+/// Indices out of bounds for `x` give zero samples.
+///
+/// # Errors
+///
+/// - [`TensorError::InvalidShape`] if `x` is a scalar or `indices` is not rank 1.
+/// - [`TensorError::InvalidArgument`] if `indices` has more than `u32::MAX` entries.
+/// - [`Error::Device`] if GPU operation fails.
+pub fn take_samples<T: Element>(x: &Tensor<T>, indices: &Tensor<u32>) -> Result<Tensor<T>, Error> {
+    let len = indices.dimensions().first().copied().unwrap_or(1);
+    let count = u32::try_from(len)
+        .map_err(|_| TensorError::InvalidArgument(format!("{len} indices exceed u32 counts")))?;
+
+    x.take_rows(indices, &Tensor::from_slice(x.context(), &[count])?)
+}
+
+/// Reduces per-fold `metrics` of equal shape to their mean and population standard
+/// deviation across folds, returned as `(mean, std)` with the shape of one metric.
+///
+/// # Errors
+///
+/// - [`TensorError::InvalidArgument`] if `metrics` is empty.
+/// - [`TensorError::InvalidShape`] if the metrics differ in shape.
+/// - [`Error::Device`] if GPU operation fails.
+pub fn fold_summary(metrics: &[&Tensor<f32>]) -> Result<(Tensor<f32>, Tensor<f32>), Error> {
+    let Some(first) = metrics.first() else {
+        return Err(TensorError::InvalidArgument("no fold metrics to summarize".into()).into());
+    };
+
+    let dimensions = first.dimensions();
+    let stacked = Tensor::stack(metrics, 0)?;
+    let (mean, var) = stacked.moments(&[0], false)?;
+    let std = var.sqrt()?;
+
+    Ok((mean.reshape(dimensions)?, std.reshape(dimensions)?))
+}
+
+/// Returns the sample indices `0..samples`, shuffled by `seed` if given.
+fn permutation(ctx: &Context, samples: usize, seed: Option<u32>) -> Result<Tensor<u32>, Error> {
+    let Ok(len) = u32::try_from(samples) else {
+        return Err(
+            TensorError::InvalidArgument(format!("{samples} samples exceed u32 indices")).into(),
+        );
+    };
+
+    match seed {
+        Some(seed) => Tensor::randperm(ctx, samples, seed),
+        None => Tensor::from_slice(ctx, &(0..len).collect::<Vec<_>>()),
+    }
+}
@@ -0,0 +1,244 @@
+//! Mixture-of-experts routing.
+//!
+//! - [`Routing`] — capacity-limited assignment of tokens to experts.
+//! - [`top_k_gating`] — routes each token to the experts with its `k` highest gate logits.
+//! - [`dispatch`] — gathers routed tokens into per-expert slots.
+//! - [`grouped_matmul`] — multiplies the tokens routed to each expert by its own weights.
+//! - [`combine`] — sums the gated expert outputs back into token order.
+//!
+//! A layer is `combine(&grouped_matmul(x, &routing, w)?, &routing)` with `routing` from
+//! [`top_k_gating`], and every step stays on the GPU.
+
+use alloc::format;
+
+use crate::Tensor;
+use crate::error::{Error, TensorError};
+
+/// Assignment of `k` experts per token, each expert holding at most `capacity` tokens.
+///
+/// Slots are numbered `expert * capacity + position` over `experts * capacity` slots in total.
+/// Assignments made after an expert filled up are dropped: their slot is
+/// `experts * capacity` and their gate zero.
+pub struct Routing {
+    /// Chosen experts `[tokens, k]`, best first.
+    experts: Tensor<u32>,
+    /// Gate weights `[tokens, k]`, zero for dropped assignments.
+    gates: Tensor<f32>,
+    /// Slot of each assignment `[tokens, k]`.
+    slots: Tensor<u32>,
+    /// Token held by each slot `[experts, capacity]`, `tokens` if empty.
+    sources: Tensor<u32>,
+    /// Tokens accepted by each expert `[experts]`.
+    counts: Tensor<u32>,
+}
+
+impl Routing {
+    /// Chosen experts `[tokens, k]`, best first.
+    #[must_use]
+    pub fn experts(&self) -> &Tensor<u32> {
+        &self.experts
+    }
+
+    /// Gate weights `[tokens, k]`, zero for dropped assignments.
+    #[must_use]
+    pub fn gates(&self) -> &Tensor<f32> {
+        &self.gates
+    }
+
+    /// Slot of each assignment `[tokens, k]`, `experts * capacity` if dropped.
+    #[must_use]
+    pub fn slots(&self) -> &Tensor<u32> {
+        &self.slots
+    }
+
+    /// Token held by each slot `[experts, capacity]`, `tokens` if the slot is empty.
+    #[must_use]
+    pub fn sources(&self) -> &Tensor<u32> {
+        &self.sources
+    }
+
+    /// Number of tokens accepted by each expert `[experts]`.
+    #[must_use]
+    pub fn counts(&self) -> &Tensor<u32> {
+        &self.counts
+    }
+
+    /// Number of tokens routed.
+    #[must_use]
+    pub fn tokens(&self) -> usize {
+        self.experts.dimensions()[0]
+    }
+
+    /// Number of experts.
+    #[must_use]
+    pub fn expert_count(&self) -> usize {
+        self.sources.dimensions()[0]
+    }
+
+    /// Maximum number of tokens per expert.
+    #[must_use]
+    pub fn capacity(&self) -> usize {
+        self.sources.dimensions()[1]
+    }
+}
+
+/// Routes each token to the `k` experts with the highest `[tokens, experts]` gate `logits`.
+///
+/// Gates are the softmax over the `k` selected logits. Experts accept tokens in priority
+/// order, every token's first choice before any second choice and ties by token index, until
+/// `capacity` tokens are held; later assignments are dropped.
+///
+/// # Errors
+///
+/// - [`TensorError::InvalidShape`] if `logits` is not rank 2.
+/// - [`TensorError::InvalidArgument`] if `k` is zero or exceeds the number of experts, or
+///   `capacity` is zero or too large to number the slots in `u32`.
+/// - [`Error::Device`] if GPU operation fails.
+pub fn top_k_gating(logits: &Tensor<f32>, k: usize, capacity: usize) -> Result<Routing, Error> {
+    let &[_, experts] = logits.dimensions() else {
+        return Err(TensorError::InvalidShape(format!(
+            "gate logits must be rank 2, got {:?}",
+            logits.dimensions()
+        ))
+        .into());
+    };
+
+    if capacity == 0 {
+        return Err(TensorError::InvalidArgument("capacity must be non-zero".into()).into());
+    }
+
+    let dropped = dropped_slot(experts, capacity)?;
+
+    let (values, choices) = logits.topk(k, true)?;
+    let (slots, sources, counts) = choices.route_experts(experts, capacity)?;
+
+    let ctx = logits.context();
+    let dropped = Tensor::constant(ctx, &[1], &[dropped])?;
+    let zero = Tensor::constant(ctx, &[1], &[0.0])?;
+    let gates = slots.lt(&dropped)?.select(&values.softmax()?, &zero)?;
+
+    Ok(Routing {
+        experts: choices,
+        gates,
+        slots,
+        sources,
+        counts,
+    })
+}
+
+/// Gathers the `[tokens, features]` rows of `x` into `[experts, capacity, features]` slots.
+///
+/// Empty slots are zero.
+///
+/// # Errors
+///
+/// - [`TensorError::InvalidShape`] if `x` is not rank 2 with one row per routed token.
+/// - [`Error::Device`] if GPU operation fails.
+pub fn dispatch(x: &Tensor<f32>, routing: &Routing) -> Result<Tensor<f32>, Error> {
+    let &[tokens, features] = x.dimensions() else {
+        return Err(TensorError::InvalidShape(format!(
+            "tokens must be rank 2, got {:?}",
+            x.dimensions()
+        ))
+        .into());
+    };
+
+    if tokens != routing.tokens() {
+        return Err(TensorError::InvalidShape(format!(
+            "got {tokens} tokens, routing has {}",
+            routing.tokens()
+        ))
+        .into());
+    }
+
+    let (experts, capacity) = (routing.expert_count(), routing.capacity());
+    let slots = experts * capacity;
+    let indices = routing
+        .sources
+        .reshape(&[slots, 1])?
+        .broadcast_to(&[slots, features])?;
+
+    x.gather(0, &indices)?
+        .reshape(&[experts, capacity, features])
+}
+
+/// Multiplies the tokens routed to each expert by that expert's `[experts, in, out]` weights.
+///
+/// Gathers `x` with [`dispatch`] and runs one batched matmul over all experts, giving
+/// `[experts, capacity, out]`. Empty slots produce zero rows.
+///
+/// # Errors
+///
+/// - [`TensorError::InvalidShape`] if `x` does not match the routing, or `weights` is not
+///   `[experts, in, out]` with `in` the feature count of `x`.
+/// - [`Error::Device`] if GPU operation fails.
+pub fn grouped_matmul(
+    x: &Tensor<f32>,
+    routing: &Routing,
+    weights: &Tensor<f32>,
+) -> Result<Tensor<f32>, Error> {
+    let experts = routing.expert_count();
+    if weights.dimensions().len() != 3 || weights.dimensions()[0] != experts {
+        return Err(TensorError::InvalidShape(format!(
+            "weights dimensions {:?} must be [{experts}, in, out]",
+            weights.dimensions()
+        ))
+        .into());
+    }
+
+    dispatch(x, routing)?.matmul(weights, false, false)
+}
+
+/// Sums the `[experts, capacity, features]` expert outputs back into `[tokens, features]`.
+///
+/// `y[t] = Σⱼ gates[t, j] · expert_out[slots[t, j]]`; dropped assignments contribute nothing.
+///
+/// # Errors
+///
+/// - [`TensorError::InvalidShape`] if `expert_out` is not `[experts, capacity, features]`.
+/// - [`Error::Device`] if GPU operation fails.
+pub fn combine(expert_out: &Tensor<f32>, routing: &Routing) -> Result<Tensor<f32>, Error> {
+    let (experts, capacity) = (routing.expert_count(), routing.capacity());
+    let &[e, c, features] = expert_out.dimensions() else {
+        return Err(TensorError::InvalidShape(format!(
+            "expert outputs must be rank 3, got {:?}",
+            expert_out.dimensions()
+        ))
+        .into());
+    };
+
+    if (e, c) != (experts, capacity) {
+        return Err(TensorError::InvalidShape(format!(
+            "expert outputs dimensions {:?} don't match [{experts}, {capacity}, _]",
+            expert_out.dimensions()
+        ))
+        .into());
+    }
+
+    let (tokens, k) = (routing.tokens(), routing.slots.dimensions()[1]);
+    let indices = routing
+        .slots
+        .reshape(&[tokens * k, 1])?
+        .broadcast_to(&[tokens * k, features])?;
+
+    expert_out
+        .reshape(&[experts * capacity, features])?
+        .gather(0, &indices)?
+        .reshape(&[tokens, k, features])?
+        .mul(&routing.gates.reshape(&[tokens, k, 1])?)?
+        .sum_reduce(&[1], false)?
+        .reshape(&[tokens, features])
+}
+
+/// Slot index marking a dropped assignment.
+fn dropped_slot(experts: usize, capacity: usize) -> Result<u32, Error> {
+    experts
+        .checked_mul(capacity)
+        .and_then(|slots| u32::try_from(slots).ok())
+        .ok_or_else(|| {
+            TensorError::InvalidArgument(format!(
+                "{experts} experts with capacity {capacity} exceed u32 slots"
+            ))
+            .into()
+        })
+}
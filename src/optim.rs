@@ -0,0 +1,525 @@
+//! Quasi-Newton optimization of parameters from caller-supplied gradients.
+//!
+//! - [`Lbfgs`] — limited-memory BFGS search directions.
+//! - [`LineSearch`] — step sizes along a search direction satisfying the Armijo or Wolfe
+//!   conditions, returned as a [`LineStep`].
+//! - [`Convergence`] — GPU stop flag raised once the gradient norm falls below a threshold.
+//!
+//! Parameters and gradients are tensors of any shape, treated as flat vectors. The optimizer
+//! only needs the gradient at each iterate, however it was computed, and keeps its whole state
+//! on the GPU: curvature history, step scalars and the search direction never leave the
+//! device, so a fitting loop runs without per-step readbacks. A line search reads back one or
+//! two scalars per trial step to decide on it; [`Convergence`] instead raises a flag that
+//! [`Context::set_stop_flag`](crate::Context::set_stop_flag) turns into a stop of all further
+//! GPU work, so a loop with fixed steps can run ahead and check the flag rarely.
+
+use alloc::collections::VecDeque;
+use alloc::format;
+
+use crate::error::{Error, TensorError};
+use crate::prune::apply_mask;
+use crate::{Context, Tensor};
+
+/// Smallest curvature `yᵀs` for which a step is added to the history.
+const CURVATURE_EPS: f32 = 1e-10;
+
+/// Limited-memory BFGS optimizer.
+///
+/// Approximates the inverse Hessian from the last `history` steps `s = x_{k+1} - x_k` and
+/// gradient changes `y = g_{k+1} - g_k`, and applies it to the gradient with the two-loop
+/// recursion, scaling the initial approximation by `sᵀy / yᵀy` of the newest step. Each dot
+/// product and update is a GPU kernel whose scalar coefficients stay on the device. A step
+/// whose curvature `yᵀs` is not positive, as on a nonconvex region, contributes nothing
+/// instead of breaking the approximation; checking it needs no readback either.
+///
+/// The first direction, without history, is the steepest descent `-g`. Unit steps along
+/// later directions suit smooth problems; combine with a line search otherwise.
+///
+/// With a pruning mask from [`Self::set_mask`], the optimizer works in the subspace of kept
+/// parameters: gradients, directions and steps are zero where the mask is false, so weights
+/// pruned by [`magnitude_mask`](crate::prune::magnitude_mask) stay at zero.
+pub struct Lbfgs {
+    /// Number of curvature pairs kept.
+    history: usize,
+    /// Curvature pairs, oldest first.
+    pairs: VecDeque<Pair>,
+    /// Parameters and gradient of the previous iterate.
+    previous: Option<(Tensor<f32>, Tensor<f32>)>,
+    /// Keep-mask of the parameters, if pruned.
+    mask: Option<Tensor<bool>>,
+}
+
+/// Step and gradient change of one iteration, with the scalars derived from them.
+struct Pair {
+    /// Parameter step `s`.
+    s: Tensor<f32>,
+    /// Gradient change `y`.
+    y: Tensor<f32>,
+    /// `1 / yᵀs`, or zero if the curvature is not positive.
+    rho: Tensor<f32>,
+    /// Initial inverse Hessian scale `sᵀy / yᵀy`, or one if the curvature is not positive.
+    gamma: Tensor<f32>,
+}
+
+impl Lbfgs {
+    /// Creates an optimizer keeping the last `history` curvature pairs, typically 5 to 20.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidArgument`] if `history` is zero.
+    pub fn new(history: usize) -> Result<Self, Error> {
+        if history == 0 {
+            return Err(
+                TensorError::InvalidArgument("L-BFGS history must be positive".into()).into(),
+            );
+        }
+
+        Ok(Self {
+            history,
+            pairs: VecDeque::with_capacity(history),
+            previous: None,
+            mask: None,
+        })
+    }
+
+    /// Sets the keep-mask of the parameters, or removes it with `None`.
+    ///
+    /// `mask` broadcasts against the parameters. Later directions and steps are zero where it
+    /// is false; pass parameters already masked with [`apply_mask`] so that pruned weights
+    /// start at zero.
+    pub fn set_mask(&mut self, mask: Option<Tensor<bool>>) {
+        self.mask = mask;
+    }
+
+    /// Returns the search direction `-H·g` at `params` with `gradient`, after adding the step
+    /// from the previous iterate to the history.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidShape`] if `gradient` does not have the dimensions of
+    ///   `params`, they differ from those of the previous iterate, or the mask does not
+    ///   broadcast against them.
+    /// - [`Error::Device`] if GPU operation fails.
+    pub fn direction(
+        &mut self,
+        params: &Tensor<f32>,
+        gradient: &Tensor<f32>,
+    ) -> Result<Tensor<f32>, Error> {
+        if gradient.dimensions() != params.dimensions() {
+            return Err(TensorError::InvalidShape(format!(
+                "gradient {:?} does not match parameters {:?}",
+                gradient.dimensions(),
+                params.dimensions()
+            ))
+            .into());
+        }
+
+        let gradient = match &self.mask {
+            Some(mask) => apply_mask(gradient, mask)?,
+            None => gradient.copy()?,
+        };
+
+        if let Some((previous_params, previous_gradient)) = self.previous.take() {
+            if previous_params.dimensions() != params.dimensions() {
+                return Err(TensorError::InvalidShape(format!(
+                    "parameters {:?} do not match previous iterate {:?}",
+                    params.dimensions(),
+                    previous_params.dimensions()
+                ))
+                .into());
+            }
+
+            let pair = Pair::new(
+                params.sub(&previous_params)?,
+                gradient.sub(&previous_gradient)?,
+            )?;
+            if self.pairs.len() == self.history {
+                self.pairs.pop_front();
+            }
+            self.pairs.push_back(pair);
+        }
+
+        let mut q = gradient.neg()?;
+        let mut alphas = VecDeque::with_capacity(self.pairs.len());
+        for pair in self.pairs.iter().rev() {
+            let alpha = pair.rho.mul(&pair.s.dot(&q)?)?;
+            q.axpy_(&alpha, -1.0, &pair.y)?;
+            alphas.push_front(alpha);
+        }
+
+        let mut r = match self.pairs.back() {
+            Some(pair) => q.mul(&pair.gamma)?,
+            None => q,
+        };
+        for (pair, alpha) in self.pairs.iter().zip(&alphas) {
+            let beta = pair.rho.mul(&pair.y.dot(&r)?)?;
+            r.axpy_(&alpha.sub(&beta)?, 1.0, &pair.s)?;
+        }
+        if let Some(mask) = &self.mask {
+            r = apply_mask(&r, mask)?;
+        }
+
+        self.previous = Some((params.copy()?, gradient));
+        Ok(r)
+    }
+
+    /// Returns `params + learning_rate · d` for the search direction `d` of
+    /// [`Self::direction`].
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidArgument`] if `learning_rate` is not positive and finite.
+    /// - [`TensorError::InvalidShape`] if `gradient` does not have the dimensions of
+    ///   `params`, they differ from those of the previous iterate, or the mask does not
+    ///   broadcast against them.
+    /// - [`Error::Device`] if GPU operation fails.
+    pub fn step(
+        &mut self,
+        params: &Tensor<f32>,
+        gradient: &Tensor<f32>,
+        learning_rate: f32,
+    ) -> Result<Tensor<f32>, Error> {
+        if !(learning_rate.is_finite() && learning_rate > 0.0) {
+            return Err(TensorError::InvalidArgument(format!(
+                "learning rate {learning_rate} must be positive and finite"
+            ))
+            .into());
+        }
+
+        let direction = self.direction(params, gradient)?;
+        let mut next = params.copy()?;
+        let rate = Tensor::scalar(params.context(), learning_rate)?;
+        next.axpy_(&rate, 1.0, &direction)?;
+        match &self.mask {
+            Some(mask) => apply_mask(&next, mask),
+            None => Ok(next),
+        }
+    }
+
+    /// Number of curvature pairs currently in the history.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.pairs.len()
+    }
+
+    /// Whether the history is empty, so the next direction is the steepest descent.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.pairs.is_empty()
+    }
+
+    /// Clears the history and the previous iterate, e.g. after changing the objective.
+    pub fn reset(&mut self) {
+        self.pairs.clear();
+        self.previous = None;
+    }
+}
+
+impl Pair {
+    /// Derives the scalars of step `s` and gradient change `y`.
+    fn new(s: Tensor<f32>, y: Tensor<f32>) -> Result<Self, Error> {
+        let ctx = s.context();
+        let ys = y.dot(&s)?;
+        let yy = y.dot(&y)?;
+        let curved = ys.gt(&Tensor::scalar(ctx, CURVATURE_EPS)?)?;
+        let rho = curved.select(&ys.rcp()?, &Tensor::scalar(ctx, 0.0)?)?;
+        let gamma = curved.select(&ys.div(&yy)?, &Tensor::scalar(ctx, 1.0)?)?;
+        Ok(Self { s, y, rho, gamma })
+    }
+}
+
+/// Step acceptance rule of a [`LineSearch`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Condition {
+    /// Sufficient decrease `f(x + t·d) ≤ f(x) + c₁·t·∇f(x)ᵀd` alone, found by halving the
+    /// step.
+    Armijo,
+    /// Sufficient decrease plus the curvature condition `∇f(x + t·d)ᵀd ≥ c₂·∇f(x)ᵀd` with the
+    /// given `c₂`, found by bisection between too long and too short steps. This is the
+    /// condition under which L-BFGS keeps a positive definite approximation.
+    Wolfe(f32),
+}
+
+/// Line search along a descent direction of an objective evaluated on the GPU.
+///
+/// The objective maps parameters to their loss, a one-element tensor, and gradient. Each trial
+/// step evaluates it once and reads back the loss and, for [`Condition::Wolfe`], the
+/// directional derivative; parameters, gradients and directions stay on the GPU.
+#[derive(Debug, Clone, Copy)]
+pub struct LineSearch {
+    /// Acceptance rule.
+    condition: Condition,
+    /// Sufficient decrease constant `c₁`.
+    decrease: f32,
+    /// Largest number of objective evaluations per search.
+    max_evaluations: usize,
+}
+
+impl LineSearch {
+    /// Creates a line search accepting steps by `condition` with sufficient decrease constant
+    /// `decrease`, typically `1e-4`, and giving up after `max_evaluations` trial steps.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidArgument`] if `decrease` is not in `(0, 1)`, the Wolfe
+    ///   curvature constant is not in `(decrease, 1)`, or `max_evaluations` is zero.
+    pub fn new(condition: Condition, decrease: f32, max_evaluations: usize) -> Result<Self, Error> {
+        if !(decrease > 0.0 && decrease < 1.0) {
+            return Err(TensorError::InvalidArgument(format!(
+                "sufficient decrease constant {decrease} must be in (0, 1)"
+            ))
+            .into());
+        }
+
+        if let Condition::Wolfe(curvature) = condition
+            && !(curvature > decrease && curvature < 1.0)
+        {
+            return Err(TensorError::InvalidArgument(format!(
+                "curvature constant {curvature} must be in ({decrease}, 1)"
+            ))
+            .into());
+        }
+
+        if max_evaluations == 0 {
+            return Err(TensorError::InvalidArgument(
+                "line search needs at least one evaluation".into(),
+            )
+            .into());
+        }
+
+        Ok(Self {
+            condition,
+            decrease,
+            max_evaluations,
+        })
+    }
+
+    /// Searches along `direction` from `params`, where the objective has `loss` and
+    /// `gradient`, starting with step size `step`.
+    ///
+    /// Returns the first trial satisfying the condition, or the last one tried if none does
+    /// within the evaluation limit; [`LineStep::satisfied`] tells them apart.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidArgument`] if `step` is not positive and finite, or
+    ///   `direction` is not a descent direction, `∇f(x)ᵀd < 0`.
+    /// - [`TensorError::InvalidShape`] if `gradient`, `direction` or a gradient returned by
+    ///   `objective` does not have the dimensions of `params`, or `loss` does not have one
+    ///   element.
+    /// - [`Error::Device`] if GPU operation fails.
+    /// - Any error returned by `objective`.
+    pub fn search<F>(
+        &self,
+        params: &Tensor<f32>,
+        loss: &Tensor<f32>,
+        gradient: &Tensor<f32>,
+        direction: &Tensor<f32>,
+        step: f32,
+        mut objective: F,
+    ) -> Result<LineStep, Error>
+    where
+        F: FnMut(&Tensor<f32>) -> Result<(Tensor<f32>, Tensor<f32>), Error>,
+    {
+        if !(step.is_finite() && step > 0.0) {
+            return Err(TensorError::InvalidArgument(format!(
+                "initial step {step} must be positive and finite"
+            ))
+            .into());
+        }
+
+        if direction.dimensions() != params.dimensions() {
+            return Err(TensorError::InvalidShape(format!(
+                "direction {:?} does not match parameters {:?}",
+                direction.dimensions(),
+                params.dimensions()
+            ))
+            .into());
+        }
+
+        let initial = loss.item()?;
+        let slope = gradient.dot(direction)?.item()?;
+        if slope.is_nan() || slope >= 0.0 {
+            return Err(TensorError::InvalidArgument(format!(
+                "direction with slope {slope} is not a descent direction"
+            ))
+            .into());
+        }
+
+        let (mut short, mut long) = (0.0, f32::INFINITY);
+        let mut step = step;
+        for evaluation in 1..=self.max_evaluations {
+            let mut trial = params.copy()?;
+            trial.axpy_(&Tensor::scalar(params.context(), step)?, 1.0, direction)?;
+            let (loss, gradient) = objective(&trial)?;
+
+            let sufficient = loss.item()? <= initial + self.decrease * step * slope;
+            let satisfied = match self.condition {
+                Condition::Armijo => sufficient,
+                Condition::Wolfe(curvature) if sufficient => {
+                    let curved = gradient.dot(direction)?.item()? >= curvature * slope;
+                    if !curved {
+                        short = step;
+                    }
+                    curved
+                }
+                Condition::Wolfe(_) => false,
+            };
+
+            if satisfied || evaluation == self.max_evaluations {
+                return Ok(LineStep {
+                    params: trial,
+                    loss,
+                    gradient,
+                    step,
+                    evaluations: evaluation,
+                    satisfied,
+                });
+            }
+
+            if !sufficient {
+                long = step;
+            }
+            step = if long.is_finite() {
+                0.5 * (short + long)
+            } else {
+                2.0 * short
+            };
+        }
+
+        unreachable!("the last evaluation always returns")
+    }
+}
+
+/// Iterate found by a [`LineSearch`].
+pub struct LineStep {
+    /// Parameters `x + t·d`.
+    params: Tensor<f32>,
+    /// Loss at the parameters.
+    loss: Tensor<f32>,
+    /// Gradient at the parameters.
+    gradient: Tensor<f32>,
+    /// Step size `t`.
+    step: f32,
+    /// Objective evaluations used.
+    evaluations: usize,
+    /// Whether the step satisfies the search condition.
+    satisfied: bool,
+}
+
+impl LineStep {
+    /// Parameters `x + t·d`.
+    #[must_use]
+    pub fn params(&self) -> &Tensor<f32> {
+        &self.params
+    }
+
+    /// Loss at the parameters.
+    #[must_use]
+    pub fn loss(&self) -> &Tensor<f32> {
+        &self.loss
+    }
+
+    /// Gradient at the parameters.
+    #[must_use]
+    pub fn gradient(&self) -> &Tensor<f32> {
+        &self.gradient
+    }
+
+    /// Step size `t`.
+    #[must_use]
+    pub fn step(&self) -> f32 {
+        self.step
+    }
+
+    /// Objective evaluations used.
+    #[must_use]
+    pub fn evaluations(&self) -> usize {
+        self.evaluations
+    }
+
+    /// Whether the step satisfies the search condition, `false` if the search gave up.
+    #[must_use]
+    pub fn satisfied(&self) -> bool {
+        self.satisfied
+    }
+
+    /// Takes the parameters, loss and gradient, to continue from them.
+    #[must_use]
+    pub fn into_parts(self) -> (Tensor<f32>, Tensor<f32>, Tensor<f32>) {
+        (self.params, self.loss, self.gradient)
+    }
+}
+
+/// Convergence test on the gradient norm, kept on the GPU.
+///
+/// Each [`Self::update`] compares the L2 norm of a gradient with the tolerance in kernels and
+/// raises a `[1]` flag once it is within it; the flag stays raised until [`Self::reset`].
+/// Installed with [`Context::set_stop_flag`], it stops every later dispatch, so the rest of a
+/// fitting loop becomes free and the host needs to read [`Self::converged`] only now and
+/// then. A NaN gradient norm never converges.
+pub struct Convergence {
+    /// Largest gradient norm considered converged.
+    tolerance: f32,
+    /// Raised once a gradient norm is within the tolerance.
+    flag: Tensor<bool>,
+}
+
+impl Convergence {
+    /// Creates a lowered flag for gradients with L2 norm up to `tolerance`.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidArgument`] if `tolerance` is negative or not finite.
+    /// - [`Error::Device`] if buffer allocation fails.
+    pub fn new(ctx: &Context, tolerance: f32) -> Result<Self, Error> {
+        if !(tolerance.is_finite() && tolerance >= 0.0) {
+            return Err(TensorError::InvalidArgument(format!(
+                "tolerance {tolerance} must be non-negative and finite"
+            ))
+            .into());
+        }
+
+        Ok(Self {
+            tolerance,
+            flag: Tensor::from_slice(ctx, &[false])?,
+        })
+    }
+
+    /// Raises the flag if the L2 norm of `gradient` is within the tolerance, without a
+    /// readback.
+    ///
+    /// # Errors
+    ///
+    /// - [`Error::Device`] if GPU operation fails.
+    pub fn update(&mut self, gradient: &Tensor<f32>) -> Result<(), Error> {
+        let axes: alloc::vec::Vec<usize> = (0..gradient.dimensions().len()).collect();
+        let norm = gradient.norm(2.0, &axes)?;
+        let tolerance = Tensor::scalar(gradient.context(), self.tolerance)?;
+        self.flag.set_if_any_(&norm.le(&tolerance)?)
+    }
+
+    /// The `[1]` flag, to install with [`Context::set_stop_flag`].
+    #[must_use]
+    pub fn flag(&self) -> &Tensor<bool> {
+        &self.flag
+    }
+
+    /// Reads back whether the flag is raised.
+    ///
+    /// # Errors
+    ///
+    /// - [`Error::Device`] if the readback fails.
+    pub fn converged(&self) -> Result<bool, Error> {
+        self.flag.item()
+    }
+
+    /// Lowers the flag.
+    ///
+    /// # Errors
+    ///
+    /// - [`Error::Device`] if the write fails.
+    pub fn reset(&mut self) -> Result<(), Error> {
+        self.flag.write_slice(0, &[false])
+    }
+}
@@ -0,0 +1,323 @@
+//! Feature scaling for tabular data.
+//!
+//! - [`StandardScaler`] — standardizes features to zero mean and unit variance.
+//! - [`MinMaxScaler`] — maps features linearly onto a target range.
+//! - [`TargetEncoder`] — replaces categories by smoothed per-category target means.
+//!
+//! The scalers fit their statistics over the sample axis 0 of a `[samples, ...]` tensor,
+//! keeping that axis with size 1 so the statistics broadcast against later batches.
+
+use core::cmp::Ordering;
+
+use alloc::format;
+
+use crate::Tensor;
+use crate::error::{Error, TensorError};
+
+/// Standardizes features as `(x - mean) / std`.
+///
+/// Statistics are population statistics of the fitted samples. Constant features have a
+/// standard deviation of zero and are only centered, so they transform to zero rather than
+/// `NaN`.
+pub struct StandardScaler {
+    /// Mean of each feature.
+    mean: Tensor<f32>,
+    /// Standard deviation of each feature, with zeros replaced by one.
+    scale: Tensor<f32>,
+}
+
+impl StandardScaler {
+    /// Fits the mean and standard deviation of every feature of `x` over axis 0.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidShape`] if `x` is a scalar or has no samples.
+    /// - [`Error::Device`] if GPU operation fails.
+    pub fn fit(x: &Tensor<f32>) -> Result<Self, Error> {
+        check_samples(x)?;
+
+        let (mean, var) = x.moments(&[0], false)?;
+        let std = var.sqrt()?;
+
+        Ok(Self {
+            mean,
+            scale: nonzero(&std)?,
+        })
+    }
+
+    /// Fits the scaler to `x` and returns it with the standardized `x`.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidShape`] if `x` is a scalar or has no samples.
+    /// - [`Error::Device`] if GPU operation fails.
+    pub fn fit_transform(x: &Tensor<f32>) -> Result<(Self, Tensor<f32>), Error> {
+        let scaler = Self::fit(x)?;
+        let y = scaler.transform(x)?;
+        Ok((scaler, y))
+    }
+
+    /// Standardizes `x` with the fitted statistics.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidShape`] if the statistics do not broadcast against `x`.
+    /// - [`Error::Device`] if GPU operation fails.
+    pub fn transform(&self, x: &Tensor<f32>) -> Result<Tensor<f32>, Error> {
+        x.sub(&self.mean)?.div(&self.scale)
+    }
+
+    /// Maps standardized `y` back to the original feature scale.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidShape`] if the statistics do not broadcast against `y`.
+    /// - [`Error::Device`] if GPU operation fails.
+    pub fn inverse_transform(&self, y: &Tensor<f32>) -> Result<Tensor<f32>, Error> {
+        y.mul(&self.scale)?.add(&self.mean)
+    }
+
+    /// Mean of each feature, with axis 0 of size 1.
+    #[must_use]
+    pub fn mean(&self) -> &Tensor<f32> {
+        &self.mean
+    }
+
+    /// Divisor of each feature: the standard deviation, or one for constant features.
+    #[must_use]
+    pub fn scale(&self) -> &Tensor<f32> {
+        &self.scale
+    }
+}
+
+/// Maps features linearly from their fitted `[min, max]` onto `range`.
+///
+/// Constant features have a span of zero and are only shifted, so they transform to the lower
+/// bound of `range`.
+pub struct MinMaxScaler {
+    /// Minimum of each feature.
+    min: Tensor<f32>,
+    /// Maximum of each feature.
+    max: Tensor<f32>,
+    /// `(hi - lo) / (max - min)` of each feature, with zero spans replaced by one.
+    scale: Tensor<f32>,
+    /// Lower bound of the target range.
+    lo: Tensor<f32>,
+}
+
+impl MinMaxScaler {
+    /// Fits the minimum and maximum of every feature of `x` over axis 0, to be mapped onto
+    /// `range = (lo, hi)`, e.g. `(0.0, 1.0)`.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidShape`] if `x` is a scalar or has no samples.
+    /// - [`TensorError::InvalidArgument`] if `lo < hi` does not hold.
+    /// - [`Error::Device`] if GPU operation fails.
+    pub fn fit(x: &Tensor<f32>, range: (f32, f32)) -> Result<Self, Error> {
+        let (lo, hi) = range;
+        if lo.partial_cmp(&hi) != Some(Ordering::Less) {
+            return Err(TensorError::InvalidArgument(format!(
+                "feature range {range:?} must satisfy lo < hi"
+            ))
+            .into());
+        }
+
+        check_samples(x)?;
+
+        let ctx = x.context();
+        let min = x.min_reduce(&[0])?;
+        let max = x.max_reduce(&[0])?;
+        let span = nonzero(&max.sub(&min)?)?;
+        let scale = Tensor::scalar(ctx, hi - lo)?.div(&span)?;
+
+        Ok(Self {
+            min,
+            max,
+            scale,
+            lo: Tensor::scalar(ctx, lo)?,
+        })
+    }
+
+    /// Fits the scaler to `x` and returns it with the scaled `x`.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidShape`] if `x` is a scalar or has no samples.
+    /// - [`TensorError::InvalidArgument`] if `lo < hi` does not hold.
+    /// - [`Error::Device`] if GPU operation fails.
+    pub fn fit_transform(x: &Tensor<f32>, range: (f32, f32)) -> Result<(Self, Tensor<f32>), Error> {
+        let scaler = Self::fit(x, range)?;
+        let y = scaler.transform(x)?;
+        Ok((scaler, y))
+    }
+
+    /// Scales `x` with the fitted bounds: `(x - min) · scale + lo`.
+    ///
+    /// Values outside the fitted bounds map outside the target range; they are not clipped.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidShape`] if the statistics do not broadcast against `x`.
+    /// - [`Error::Device`] if GPU operation fails.
+    pub fn transform(&self, x: &Tensor<f32>) -> Result<Tensor<f32>, Error> {
+        x.sub(&self.min)?.mul(&self.scale)?.add(&self.lo)
+    }
+
+    /// Maps scaled `y` back to the original feature scale.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidShape`] if the statistics do not broadcast against `y`.
+    /// - [`Error::Device`] if GPU operation fails.
+    pub fn inverse_transform(&self, y: &Tensor<f32>) -> Result<Tensor<f32>, Error> {
+        y.sub(&self.lo)?.div(&self.scale)?.add(&self.min)
+    }
+
+    /// Minimum of each feature, with axis 0 of size 1.
+    #[must_use]
+    pub fn min(&self) -> &Tensor<f32> {
+        &self.min
+    }
+
+    /// Maximum of each feature, with axis 0 of size 1.
+    #[must_use]
+    pub fn max(&self) -> &Tensor<f32> {
+        &self.max
+    }
+}
+
+/// Encodes categorical keys by the mean target of their category.
+///
+/// Category `g` is encoded as `(sum_g + m · prior) / (count_g + m)`, where `prior` is the
+/// mean of all targets and `m` the smoothing weight, so rare categories are pulled towards the
+/// prior. Categories without samples, and keys outside the fitted categories, encode to the
+/// prior. Per-category sums and counts come from a single [`Tensor::group_sum`].
+pub struct TargetEncoder {
+    /// Encoding of each category.
+    encodings: Tensor<f32>,
+    /// Mean of all fitted targets, `[1]`.
+    prior: Tensor<f32>,
+    /// Number of categories as the `u32` bound for keys.
+    groups: Tensor<u32>,
+}
+
+impl TargetEncoder {
+    /// Fits encodings of `groups` categories from `[n]` `keys` and `[n]` `targets`, with
+    /// smoothing weight `smoothing`.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidShape`] if `targets` is not rank 1 with at least one sample,
+    ///   or `keys` does not match it.
+    /// - [`TensorError::InvalidArgument`] if `smoothing` is negative or not finite, or
+    ///   `groups` exceeds `u32::MAX`.
+    /// - [`Error::Device`] if GPU operation fails.
+    pub fn fit(
+        keys: &Tensor<u32>,
+        targets: &Tensor<f32>,
+        groups: usize,
+        smoothing: f32,
+    ) -> Result<Self, Error> {
+        if !(smoothing.is_finite() && smoothing >= 0.0) {
+            return Err(TensorError::InvalidArgument(format!(
+                "smoothing {smoothing} must be non-negative and finite"
+            ))
+            .into());
+        }
+
+        let bound = u32::try_from(groups).map_err(|_| {
+            TensorError::InvalidArgument(format!("{groups} categories exceed u32 keys"))
+        })?;
+
+        if targets.dimensions().len() != 1 {
+            return Err(TensorError::InvalidShape(format!(
+                "targets must be rank 1, got {:?}",
+                targets.dimensions()
+            ))
+            .into());
+        }
+        check_samples(targets)?;
+
+        let ctx = targets.context();
+        let ones = Tensor::constant(ctx, targets.dimensions(), &[1.0])?;
+        let (totals, _) = Tensor::stack(&[targets, &ones], 1)?.group_sum(keys, groups)?;
+        let sums = totals.narrow(1, 0, 1)?.reshape(&[groups])?;
+        let counts = totals.narrow(1, 1, 1)?.reshape(&[groups])?;
+
+        let prior = targets.mean_reduce(&[0])?;
+        let weight = Tensor::scalar(ctx, smoothing)?;
+        let smoothed = sums.add(&prior.mul(&weight)?)?.div(&counts.add(&weight)?)?;
+        let encodings = counts
+            .eq(&Tensor::scalar(ctx, 0.0)?)?
+            .select(&prior, &smoothed)?;
+
+        Ok(Self {
+            encodings,
+            prior,
+            groups: Tensor::scalar(ctx, bound)?,
+        })
+    }
+
+    /// Fits the encoder and returns it with the encoded `keys`.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidShape`] if `targets` is not rank 1 with at least one sample,
+    ///   or `keys` does not match it.
+    /// - [`TensorError::InvalidArgument`] if `smoothing` is negative or not finite, or
+    ///   `groups` exceeds `u32::MAX`.
+    /// - [`Error::Device`] if GPU operation fails.
+    pub fn fit_transform(
+        keys: &Tensor<u32>,
+        targets: &Tensor<f32>,
+        groups: usize,
+        smoothing: f32,
+    ) -> Result<(Self, Tensor<f32>), Error> {
+        let encoder = Self::fit(keys, targets, groups, smoothing)?;
+        let y = encoder.transform(keys)?;
+        Ok((encoder, y))
+    }
+
+    /// Replaces each of the `[n]` `keys` by the encoding of its category.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidShape`] if `keys` is not rank 1.
+    /// - [`Error::Device`] if GPU operation fails.
+    pub fn transform(&self, keys: &Tensor<u32>) -> Result<Tensor<f32>, Error> {
+        let known = keys.lt(&self.groups)?;
+        known.select(&self.encodings.gather(0, keys)?, &self.prior)
+    }
+
+    /// Encoding of each category.
+    #[must_use]
+    pub fn encodings(&self) -> &Tensor<f32> {
+        &self.encodings
+    }
+
+    /// Mean of all fitted targets, `[1]`.
+    #[must_use]
+    pub fn prior(&self) -> &Tensor<f32> {
+        &self.prior
+    }
+}
+
+/// Checks that `x` has a sample axis with at least one sample.
+fn check_samples(x: &Tensor<f32>) -> Result<(), Error> {
+    match x.dimensions().first() {
+        Some(&samples) if samples > 0 => Ok(()),
+        _ => Err(TensorError::InvalidShape(format!(
+            "scaler requires at least one sample along axis 0, got dimensions {:?}",
+            x.dimensions()
+        ))
+        .into()),
+    }
+}
+
+/// Replaces zeros in `x` by one, so constant features are not divided by zero.
+fn nonzero(x: &Tensor<f32>) -> Result<Tensor<f32>, Error> {
+    let ctx = x.context();
+    let zero = Tensor::scalar(ctx, 0.0)?;
+    x.eq(&zero)?.select(&Tensor::scalar(ctx, 1.0)?, x)
+}
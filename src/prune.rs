@@ -0,0 +1,105 @@
+//! Magnitude pruning utilities.
+//!
+//! - [`magnitude_mask`] — keep-mask that prunes the smallest-magnitude weights.
+//! - [`apply_mask`] — zeroes the pruned weights.
+//! - [`Snapshot`] — on-device copy of weights for lottery-ticket style rewinding.
+//!
+//! Re-applying the mask after each optimizer step keeps pruned weights at zero;
+//! [`Lbfgs::set_mask`](crate::optim::Lbfgs::set_mask) does so within L-BFGS.
+
+use alloc::format;
+
+use crate::Tensor;
+use crate::error::{Error, TensorError};
+
+/// Boolean keep-mask that prunes the `sparsity` fraction of `weights` with smallest magnitude.
+///
+/// Exactly `floor(sparsity × len)` elements are pruned (false in the mask). Ties in magnitude
+/// are broken by index, so the result is deterministic; the lowest indices are pruned first.
+///
+/// # Errors
+///
+/// - [`TensorError::InvalidArgument`] if `sparsity` is not in `[0, 1]`.
+/// - [`Error::Device`] if GPU operation fails.
+pub fn magnitude_mask(weights: &Tensor<f32>, sparsity: f32) -> Result<Tensor<bool>, Error> {
+    if !(0.0..=1.0).contains(&sparsity) {
+        return Err(
+            TensorError::InvalidArgument(format!("sparsity {sparsity} must be in [0, 1]")).into(),
+        );
+    }
+
+    let ctx = weights.context();
+    let dimensions = weights.dimensions();
+    let len = dimensions.iter().product();
+    let pruned = pruned_count(len, sparsity);
+
+    let mut mask = Tensor::constant(ctx, &[len], &[true])?;
+    if pruned > 0 {
+        let (_, indices) = weights.reshape(&[len])?.abs()?.neg()?.topk(pruned, true)?;
+        mask.index_put_(&indices, &Tensor::constant(ctx, &[pruned], &[false])?)?;
+    }
+
+    mask.reshape(dimensions)
+}
+
+/// Zeroes the elements of `weights` where `mask` is false.
+///
+/// `mask` broadcasts against `weights`.
+///
+/// # Errors
+///
+/// - [`TensorError::InvalidShape`] if shapes are not broadcast-compatible.
+/// - [`Error::Device`] if GPU operation fails.
+pub fn apply_mask(weights: &Tensor<f32>, mask: &Tensor<bool>) -> Result<Tensor<f32>, Error> {
+    let zero = Tensor::constant(weights.context(), &[1], &[0.0])?;
+    mask.select(weights, &zero)
+}
+
+/// Number of elements pruned at `sparsity`: `floor(sparsity × len)`.
+#[allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_precision_loss,
+    clippy::cast_sign_loss
+)]
+fn pruned_count(len: usize, sparsity: f32) -> usize {
+    ((sparsity * len as f32).floor() as usize).min(len)
+}
+
+/// On-device copy of weights, for rewinding live weights to their stored values.
+pub struct Snapshot {
+    /// Stored weights.
+    weights: Tensor<f32>,
+}
+
+impl Snapshot {
+    /// Snapshots `weights` into a new GPU buffer.
+    ///
+    /// # Errors
+    ///
+    /// - [`Error::Device`] if buffer allocation fails.
+    pub fn new(weights: &Tensor<f32>) -> Result<Self, Error> {
+        Ok(Self {
+            weights: weights.copy()?,
+        })
+    }
+
+    /// Stored weights.
+    #[must_use]
+    pub fn weights(&self) -> &Tensor<f32> {
+        &self.weights
+    }
+
+    /// Restores the stored values into `live` where `mask` is true, in place.
+    ///
+    /// With a keep-mask from [`magnitude_mask`], this rewinds the surviving weights to their
+    /// snapshot values; combine with [`apply_mask`] to also zero the pruned ones.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidShape`] if the snapshot or `mask` does not broadcast to the shape
+    ///   of `live`.
+    /// - [`Error::Device`] if GPU operation fails.
+    pub fn rewind(&self, live: &mut Tensor<f32>, mask: &Tensor<bool>) -> Result<(), Error> {
+        live.masked_copy_(&self.weights, mask)
+    }
+}
@@ -0,0 +1,206 @@
+//! Calibration observers for int8 quantization.
+//!
+//! Observers accumulate statistics over the tensors seen during a calibration run. All
+//! statistics stay on the GPU until read back.
+//!
+//! - [`MinMaxObserver`] — running minimum and maximum, per tensor or per channel.
+//! - [`HistogramObserver`] — running histogram over a fixed range.
+
+use core::cmp::Ordering;
+
+use alloc::format;
+use alloc::vec::Vec;
+
+use crate::error::{Error, TensorError};
+use crate::{Context, Tensor};
+
+/// Smallest quantized value of the int8 range.
+const QMIN: f32 = -128.0;
+
+/// Largest quantized value of the int8 range.
+const QMAX: f32 = 127.0;
+
+/// Granularity of observed statistics.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Granularity {
+    /// One statistic for the whole tensor.
+    PerTensor,
+    /// One statistic per index along the given channel axis.
+    PerChannel(usize),
+}
+
+/// Running minimum and maximum of observed tensors.
+///
+/// Statistics keep the rank of the observed tensors with reduced axes set to 1, so they
+/// broadcast against them.
+pub struct MinMaxObserver {
+    /// GPU context for operations.
+    ctx: Context,
+    /// Per-tensor or per-channel statistics.
+    granularity: Granularity,
+    /// Running minimum, `None` until the first observation.
+    min: Option<Tensor<f32>>,
+    /// Running maximum, `None` until the first observation.
+    max: Option<Tensor<f32>>,
+}
+
+impl MinMaxObserver {
+    /// Creates an observer with no statistics.
+    #[must_use]
+    pub fn new(ctx: &Context, granularity: Granularity) -> Self {
+        Self {
+            ctx: ctx.clone(),
+            granularity,
+            min: None,
+            max: None,
+        }
+    }
+
+    /// Folds `x` into the running minimum and maximum.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidShape`] if the channel axis is out of bounds, or the channel
+    ///   count differs from earlier observations.
+    /// - [`Error::Device`] if GPU operation fails.
+    pub fn observe(&mut self, x: &Tensor<f32>) -> Result<(), Error> {
+        let rank = x.dimensions().len();
+        let axes: Vec<usize> = match self.granularity {
+            Granularity::PerTensor => (0..rank).collect(),
+            Granularity::PerChannel(axis) if axis < rank => {
+                (0..rank).filter(|&i| i != axis).collect()
+            }
+            Granularity::PerChannel(axis) => {
+                return Err(TensorError::InvalidShape(format!(
+                    "channel axis {axis} out of bounds for tensor with rank {rank}"
+                ))
+                .into());
+            }
+        };
+
+        let min = x.min_reduce(&axes)?;
+        let max = x.max_reduce(&axes)?;
+
+        self.min = Some(match self.min.take() {
+            Some(running) => running.min(&min)?,
+            None => min,
+        });
+        self.max = Some(match self.max.take() {
+            Some(running) => running.max(&max)?,
+            None => max,
+        });
+
+        Ok(())
+    }
+
+    /// Running minimum, or `None` before the first observation.
+    #[must_use]
+    pub fn min(&self) -> Option<&Tensor<f32>> {
+        self.min.as_ref()
+    }
+
+    /// Running maximum, or `None` before the first observation.
+    #[must_use]
+    pub fn max(&self) -> Option<&Tensor<f32>> {
+        self.max.as_ref()
+    }
+
+    /// Affine int8 quantization parameters `(scale, zero_point)` for the observed range.
+    ///
+    /// The range is widened to include zero so that zero is exactly representable:
+    /// `scale = (max - min) / 255` and `zero_point = round(-128 - min / scale)`, clamped to
+    /// `[-128, 127]`. Both tensors have the shape of the statistics.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidArgument`] if nothing has been observed.
+    /// - [`Error::Device`] if GPU operation fails.
+    pub fn calculate_qparams(&self) -> Result<(Tensor<f32>, Tensor<f32>), Error> {
+        let (Some(min), Some(max)) = (&self.min, &self.max) else {
+            return Err(TensorError::InvalidArgument("no tensors observed".into()).into());
+        };
+
+        let scalar = |value: f32| Tensor::constant(&self.ctx, &[1], &[value]);
+        let zero = scalar(0.0)?;
+
+        let min = min.min(&zero)?;
+        let max = max.max(&zero)?;
+
+        let scale = max
+            .sub(&min)?
+            .div(&scalar(QMAX - QMIN)?)?
+            .max(&scalar(f32::EPSILON)?)?;
+        let zero_point = scalar(QMIN)?
+            .sub(&min.div(&scale)?)?
+            .round()?
+            .clamp(&scalar(QMIN)?, &scalar(QMAX)?)?;
+
+        Ok((scale, zero_point))
+    }
+}
+
+/// Running histogram of observed tensors over a fixed range.
+///
+/// Values outside the range are counted in the edge bins. A typical calibration run first
+/// finds the range with a [`MinMaxObserver`], then collects a histogram for choosing clipping
+/// thresholds.
+pub struct HistogramObserver {
+    /// Number of bins.
+    bins: usize,
+    /// Lower bound of the first bin.
+    min: f32,
+    /// Upper bound of the last bin.
+    max: f32,
+    /// Accumulated bin counts.
+    counts: Tensor<u32>,
+}
+
+impl HistogramObserver {
+    /// Creates an observer with `bins` empty bins spanning `[min, max]`.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidArgument`] if `bins` is zero or `min < max` does not hold.
+    /// - [`Error::Device`] if buffer allocation fails.
+    pub fn new(ctx: &Context, bins: usize, min: f32, max: f32) -> Result<Self, Error> {
+        if bins == 0 {
+            return Err(TensorError::InvalidArgument("bins must be positive".into()).into());
+        }
+        if min.partial_cmp(&max) != Some(Ordering::Less) {
+            return Err(TensorError::InvalidArgument(format!(
+                "histogram range [{min}, {max}] is empty"
+            ))
+            .into());
+        }
+
+        Ok(Self {
+            bins,
+            min,
+            max,
+            counts: Tensor::constant(ctx, &[bins], &[0])?,
+        })
+    }
+
+    /// Adds the elements of `x` to the histogram.
+    ///
+    /// # Errors
+    ///
+    /// - [`Error::Device`] if GPU operation fails.
+    pub fn observe(&mut self, x: &Tensor<f32>) -> Result<(), Error> {
+        let counts = x.histogram(self.bins, self.min, self.max)?;
+        self.counts = self.counts.add(&counts)?;
+        Ok(())
+    }
+
+    /// Accumulated bin counts.
+    #[must_use]
+    pub fn counts(&self) -> &Tensor<u32> {
+        &self.counts
+    }
+
+    /// Bin range `(min, max)`.
+    #[must_use]
+    pub fn range(&self) -> (f32, f32) {
+        (self.min, self.max)
+    }
+}
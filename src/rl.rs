@@ -0,0 +1,305 @@
+//! Reinforcement learning rollout storage.
+//!
+//! - [`RolloutBuffer`] — on-policy transitions of vectorized environments, with GAE.
+//! - [`ObservationNormalizer`] — running standardization of batched observations.
+//!
+//! Transitions stay in GPU tensors from collection to the policy update, and advantages come
+//! from the fused reverse scan of [`Tensor::gae`].
+
+use alloc::format;
+
+use crate::error::{Error, TensorError};
+use crate::stats::RunningStats;
+use crate::{Context, Element, Tensor};
+
+/// Fixed-capacity storage of `steps` transitions from `envs` parallel environments.
+///
+/// Every per-step input has a leading `[envs]` axis and is stored at row `t` of a
+/// `[steps, envs, ...]` tensor, copied on the GPU. Actions have element type `A`, e.g. `u32`
+/// for discrete and `f32` for continuous action spaces. The storage accessors return all
+/// `capacity` steps; rows from [`Self::len`] on have not been written since the last clear.
+pub struct RolloutBuffer<A: Element = f32> {
+    /// Observations, `[steps, envs, ...]`.
+    observations: Tensor<f32>,
+    /// Actions, `[steps, envs, ...]`.
+    actions: Tensor<A>,
+    /// Log-probabilities of the actions under the behavior policy, `[steps, envs]`.
+    log_probs: Tensor<f32>,
+    /// Rewards, `[steps, envs]`.
+    rewards: Tensor<f32>,
+    /// Value estimates, `[steps, envs]`.
+    values: Tensor<f32>,
+    /// One where the transition ended its episode, `[steps, envs]`.
+    dones: Tensor<f32>,
+    /// Number of steps stored.
+    len: usize,
+}
+
+impl<A: Element> RolloutBuffer<A> {
+    /// Creates an empty buffer of `steps` steps for `envs` environments, with per-environment
+    /// observations of dimensions `observation` and actions of dimensions `action`.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidArgument`] if `steps` exceeds `u32::MAX`.
+    /// - [`Error::Device`] if buffer allocation fails.
+    pub fn new(
+        ctx: &Context,
+        steps: usize,
+        envs: usize,
+        observation: &[usize],
+        action: &[usize],
+    ) -> Result<Self, Error> {
+        if u32::try_from(steps).is_err() {
+            return Err(
+                TensorError::InvalidArgument(format!("{steps} steps exceed u32 indices")).into(),
+            );
+        }
+
+        let storage = |item: &[usize]| [&[steps, envs], item].concat();
+        let zeros = || Tensor::constant(ctx, &[steps, envs], &[0.0]);
+
+        Ok(Self {
+            observations: Tensor::constant(ctx, &storage(observation), &[0.0])?,
+            actions: Tensor::constant(ctx, &storage(action), &[A::zeroed()])?,
+            log_probs: zeros()?,
+            rewards: zeros()?,
+            values: zeros()?,
+            dones: zeros()?,
+            len: 0,
+        })
+    }
+
+    /// Appends one step of transitions.
+    ///
+    /// `observation` and `action` are `[envs, ...]`; `log_prob`, `reward`, `value` and `done`
+    /// are `[envs]`, with `done` one where the transition ended its episode.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidArgument`] if the buffer is full.
+    /// - [`TensorError::InvalidShape`] if an input does not match the stored dimensions.
+    /// - [`Error::Device`] if GPU operation fails.
+    #[allow(clippy::too_many_arguments)]
+    pub fn push(
+        &mut self,
+        observation: &Tensor<f32>,
+        action: &Tensor<A>,
+        log_prob: &Tensor<f32>,
+        reward: &Tensor<f32>,
+        value: &Tensor<f32>,
+        done: &Tensor<f32>,
+    ) -> Result<(), Error> {
+        if self.is_full() {
+            return Err(TensorError::InvalidArgument(format!(
+                "rollout buffer is full at {} steps",
+                self.len
+            ))
+            .into());
+        }
+
+        let inputs = [
+            (self.observations.dimensions(), observation.dimensions()),
+            (self.actions.dimensions(), action.dimensions()),
+            (self.log_probs.dimensions(), log_prob.dimensions()),
+            (self.rewards.dimensions(), reward.dimensions()),
+            (self.values.dimensions(), value.dimensions()),
+            (self.dones.dimensions(), done.dimensions()),
+        ];
+        if let Some((stored, dimensions)) = inputs
+            .iter()
+            .find(|(stored, dimensions)| stored[1..] != **dimensions)
+        {
+            return Err(TensorError::InvalidShape(format!(
+                "step dimensions {dimensions:?} do not match stored {:?}",
+                &stored[1..]
+            ))
+            .into());
+        }
+
+        let step = u32::try_from(self.len).unwrap_or(u32::MAX);
+        let row = Tensor::from_slice(observation.context(), &[step])?;
+
+        self.observations
+            .index_put_(&row, &observation.unsqueeze(0)?)?;
+        self.actions.index_put_(&row, &action.unsqueeze(0)?)?;
+        self.log_probs.index_put_(&row, &log_prob.unsqueeze(0)?)?;
+        self.rewards.index_put_(&row, &reward.unsqueeze(0)?)?;
+        self.values.index_put_(&row, &value.unsqueeze(0)?)?;
+        self.dones.index_put_(&row, &done.unsqueeze(0)?)?;
+
+        self.len += 1;
+        Ok(())
+    }
+
+    /// Computes `(advantages, returns)` of the stored steps with generalized advantage
+    /// estimation, bootstrapping from the `[envs]` values of the states after the last step.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidShape`] if `last_values` is not `[envs]`.
+    /// - [`TensorError::InvalidArgument`] if `gamma` or `lambda` is not in `[0, 1]`.
+    /// - [`Error::Device`] if GPU operation fails.
+    pub fn compute_gae(
+        &self,
+        last_values: &Tensor<f32>,
+        gamma: f32,
+        lambda: f32,
+    ) -> Result<(Tensor<f32>, Tensor<f32>), Error> {
+        let stored = |x: &Tensor<f32>| x.narrow(0, 0, self.len);
+        stored(&self.rewards)?.gae(
+            &stored(&self.values)?,
+            &stored(&self.dones)?,
+            last_values,
+            gamma,
+            lambda,
+        )
+    }
+
+    /// Discards the stored steps, keeping the allocated storage.
+    pub fn clear(&mut self) {
+        self.len = 0;
+    }
+
+    /// Number of steps stored.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if no steps are stored.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns `true` if every step is stored.
+    #[must_use]
+    pub fn is_full(&self) -> bool {
+        self.len == self.capacity()
+    }
+
+    /// Maximum number of steps.
+    #[must_use]
+    pub fn capacity(&self) -> usize {
+        self.rewards.dimensions()[0]
+    }
+
+    /// Stored observations, `[steps, envs, ...]`.
+    #[must_use]
+    pub fn observations(&self) -> &Tensor<f32> {
+        &self.observations
+    }
+
+    /// Stored actions, `[steps, envs, ...]`.
+    #[must_use]
+    pub fn actions(&self) -> &Tensor<A> {
+        &self.actions
+    }
+
+    /// Stored action log-probabilities, `[steps, envs]`.
+    #[must_use]
+    pub fn log_probs(&self) -> &Tensor<f32> {
+        &self.log_probs
+    }
+
+    /// Stored rewards, `[steps, envs]`.
+    #[must_use]
+    pub fn rewards(&self) -> &Tensor<f32> {
+        &self.rewards
+    }
+
+    /// Stored value estimates, `[steps, envs]`.
+    #[must_use]
+    pub fn values(&self) -> &Tensor<f32> {
+        &self.values
+    }
+
+    /// Stored episode ends, `[steps, envs]`.
+    #[must_use]
+    pub fn dones(&self) -> &Tensor<f32> {
+        &self.dones
+    }
+}
+
+/// Standardizes batched observations of vectorized environments with running statistics.
+///
+/// Observations are `[envs, ...]`. Their per-feature mean and variance are tracked across all
+/// environments and steps by a [`RunningStats`] over axis 0, and each batch is standardized
+/// and clipped to `[-clip, clip]` by a single fused kernel, so observations never leave the
+/// GPU between the environments and the policy.
+pub struct ObservationNormalizer {
+    /// Running statistics over the environment axis.
+    stats: RunningStats,
+    /// Added to the variance before the square root.
+    eps: f32,
+    /// Bound of the standardized observations.
+    clip: f32,
+}
+
+impl ObservationNormalizer {
+    /// Creates a normalizer with an optional `momentum` in `(0, 1]` for the running
+    /// statistics, adding `eps` to the variance and clipping to `[-clip, clip]`.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidArgument`] if `momentum` is not in `(0, 1]`, `eps` is negative or
+    ///   not finite, or `clip` is not positive.
+    pub fn new(momentum: Option<f32>, eps: f32, clip: f32) -> Result<Self, Error> {
+        if !(eps.is_finite() && eps >= 0.0) || clip.is_nan() || clip <= 0.0 {
+            return Err(TensorError::InvalidArgument(format!(
+                "eps {eps} must be non-negative and finite and clip {clip} positive"
+            ))
+            .into());
+        }
+
+        Ok(Self {
+            stats: RunningStats::new(&[0], momentum)?,
+            eps,
+            clip,
+        })
+    }
+
+    /// Folds the `[envs, ...]` `observations` into the running statistics.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidShape`] if `observations` is a scalar, or its dimensions differ
+    ///   from earlier batches beyond the environment axis.
+    /// - [`Error::Device`] if GPU operation fails.
+    pub fn update(&mut self, observations: &Tensor<f32>) -> Result<(), Error> {
+        self.stats.observe(observations)
+    }
+
+    /// Standardizes the `[envs, ...]` `observations` with the running statistics:
+    /// `clamp((x - mean) / sqrt(var + eps), -clip, clip)`.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidArgument`] if nothing has been observed.
+    /// - [`TensorError::InvalidShape`] if `observations` differs from the observed batches
+    ///   beyond the environment axis.
+    /// - [`Error::Device`] if GPU operation fails.
+    pub fn normalize(&self, observations: &Tensor<f32>) -> Result<Tensor<f32>, Error> {
+        self.stats.standardize(observations, self.eps, self.clip)
+    }
+
+    /// Folds `observations` into the running statistics, then standardizes them with the
+    /// updated statistics.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidShape`] if `observations` is a scalar, or its dimensions differ
+    ///   from earlier batches beyond the environment axis.
+    /// - [`Error::Device`] if GPU operation fails.
+    pub fn update_normalize(&mut self, observations: &Tensor<f32>) -> Result<Tensor<f32>, Error> {
+        self.update(observations)?;
+        self.normalize(observations)
+    }
+
+    /// Running statistics of the observations.
+    #[must_use]
+    pub fn stats(&self) -> &RunningStats {
+        &self.stats
+    }
+}
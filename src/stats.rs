@@ -0,0 +1,138 @@
+//! Running statistics over tensor streams.
+//!
+//! - [`RunningStats`] — running mean and variance, cumulative or exponentially weighted.
+
+use alloc::format;
+use alloc::vec::Vec;
+
+use crate::Tensor;
+use crate::error::{Error, TensorError};
+
+/// Running mean and variance of observed tensors.
+///
+/// Each observation reduces over `axes` and is merged into the running statistics by a single
+/// fused kernel. Statistics keep the rank of the observed tensors with reduced axes set to 1,
+/// so they broadcast against them. Variances are population variances.
+///
+/// Without momentum every observed sample has equal weight, giving the exact mean and
+/// variance of everything seen so far. With momentum `m`, an observation updates the running
+/// values as `s = (1 - m)·s + m·s_batch`, the batch normalization rule. In both modes the first
+/// observation initializes the statistics.
+pub struct RunningStats {
+    /// Axes reduced in each observation.
+    axes: Vec<usize>,
+    /// Weight of each new observation, `None` for a cumulative average.
+    momentum: Option<f32>,
+    /// Running mean, `None` until the first observation.
+    mean: Option<Tensor<f32>>,
+    /// Running variance, `None` until the first observation.
+    var: Option<Tensor<f32>>,
+    /// Number of samples merged into each statistic.
+    count: usize,
+}
+
+impl RunningStats {
+    /// Creates a tracker reducing over `axes`, with an optional momentum in `(0, 1]`.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidArgument`] if `momentum` is not in `(0, 1]`.
+    pub fn new(axes: &[usize], momentum: Option<f32>) -> Result<Self, Error> {
+        if let Some(momentum) = momentum
+            && !(momentum > 0.0 && momentum <= 1.0)
+        {
+            return Err(TensorError::InvalidArgument(format!(
+                "momentum {momentum} must be in (0, 1]"
+            ))
+            .into());
+        }
+
+        Ok(Self {
+            axes: axes.to_vec(),
+            momentum,
+            mean: None,
+            var: None,
+            count: 0,
+        })
+    }
+
+    /// Folds the samples of `x` into the running mean and variance.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidShape`] if the axes are invalid for `x`, or the statistics
+    ///   shape differs from earlier observations.
+    /// - [`Error::Device`] if GPU operation fails.
+    pub fn observe(&mut self, x: &Tensor<f32>) -> Result<(), Error> {
+        let (mean, var) = x.moments(&self.axes, false)?;
+        let samples: usize = self.axes.iter().map(|&axis| x.dimensions()[axis]).product();
+
+        if let (Some(running_mean), Some(running_var)) = (&mut self.mean, &mut self.var) {
+            let (running, batch, cross) = match self.momentum {
+                Some(momentum) => (1.0 - momentum, momentum, 0.0),
+                None => weights(self.count, samples),
+            };
+            running_mean.merge_moments_(running_var, &mean, &var, running, batch, cross)?;
+        } else {
+            self.mean = Some(mean);
+            self.var = Some(var);
+        }
+
+        self.count += samples;
+        Ok(())
+    }
+
+    /// Running mean, or `None` before the first observation.
+    #[must_use]
+    pub fn mean(&self) -> Option<&Tensor<f32>> {
+        self.mean.as_ref()
+    }
+
+    /// Running population variance, or `None` before the first observation.
+    #[must_use]
+    pub fn variance(&self) -> Option<&Tensor<f32>> {
+        self.var.as_ref()
+    }
+
+    /// Number of samples merged into each statistic.
+    #[must_use]
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Standardizes `x` with the running statistics: `(x - mean) / sqrt(var + eps)`, in one
+    /// kernel.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidArgument`] if nothing has been observed.
+    /// - [`TensorError::InvalidShape`] if the statistics do not broadcast against `x`.
+    /// - [`Error::Device`] if GPU operation fails.
+    pub fn normalize(&self, x: &Tensor<f32>, eps: f32) -> Result<Tensor<f32>, Error> {
+        self.standardize(x, eps, f32::MAX)
+    }
+
+    /// Standardizes `x` with the running statistics and clamps the result to `[-clip, clip]`,
+    /// in one kernel.
+    pub(crate) fn standardize(
+        &self,
+        x: &Tensor<f32>,
+        eps: f32,
+        clip: f32,
+    ) -> Result<Tensor<f32>, Error> {
+        let (Some(mean), Some(var)) = (&self.mean, &self.var) else {
+            return Err(TensorError::InvalidArgument("no tensors observed".into()).into());
+        };
+
+        x.standardize(mean, var, eps, clip)
+    }
+}
+
+/// Merge weights `(running, batch, cross)` for an exact combination of `count` running and
+/// `samples` new samples.
+#[allow(clippy::cast_possible_truncation, clippy::cast_precision_loss)]
+fn weights(count: usize, samples: usize) -> (f32, f32, f32) {
+    let (na, nb) = (count as f64, samples as f64);
+    let n = na + nb;
+    ((na / n) as f32, (nb / n) as f32, (na * nb / (n * n)) as f32)
+}
@@ -0,0 +1,73 @@
+//! Gradient boosting primitives.
+
+use alloc::format;
+
+use crate::error::{Error, TensorError};
+use crate::kernel::ops;
+use crate::tensor::Tensor;
+use crate::tensor::layout::Layout;
+
+impl Tensor<u32> {
+    /// Builds the gradient and hessian histogram of the `[rows, features]` bin indices in
+    /// `self`, the hot loop of histogram-based gradient boosted tree training.
+    ///
+    /// `grad` and `hess` hold the `[rows]` first and second loss derivatives. The result is
+    /// `[features, bins, 2]`, where `[f, b, 0]` sums `grad` and `[f, b, 1]` sums `hess` over
+    /// the rows whose feature `f` falls in bin `b`; split gains follow from prefix sums over
+    /// the bins. Bin indices of `bins` or more are ignored. To build the histogram of a tree
+    /// node, pass only its rows, e.g. gathered with [`Self::take_rows`].
+    ///
+    /// Each feature's bins are stable-sorted and every `(feature, bin)` segment is summed in
+    /// row order, so the result is deterministic.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidShape`] if `self` is not rank 2, or `grad` or `hess` is not
+    ///   `[rows]`.
+    /// - [`Error::Device`] if buffer allocation fails.
+    pub fn gradient_histogram(
+        &self,
+        grad: &Tensor<f32>,
+        hess: &Tensor<f32>,
+        bins: usize,
+    ) -> Result<Tensor<f32>, Error> {
+        let &[rows, features] = self.dimensions() else {
+            return Err(TensorError::InvalidShape(format!(
+                "binned features must be rank 2, got {:?}",
+                self.dimensions()
+            ))
+            .into());
+        };
+
+        if grad.dimensions() != [rows] || hess.dimensions() != [rows] {
+            return Err(TensorError::InvalidShape(format!(
+                "gradients {:?} and hessians {:?} must be [{rows}]",
+                grad.dimensions(),
+                hess.dimensions()
+            ))
+            .into());
+        }
+
+        let layout = Layout::from_dimensions(&[features, bins, 2]);
+        let buffer = self.ctx.create_buffer(layout.size())?;
+
+        let (sorted, order) = self.transpose(0, 1)?.sort(false, true)?;
+        ops::gradient_histogram(
+            &self.ctx,
+            &sorted.buffer,
+            &order.buffer,
+            &grad.materialize()?.buffer,
+            &hess.materialize()?.buffer,
+            &buffer,
+            rows,
+            features,
+            bins,
+        );
+
+        Ok(Tensor {
+            buffer,
+            layout,
+            ctx: self.ctx.clone(),
+        })
+    }
+}
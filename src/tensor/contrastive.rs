@@ -0,0 +1,122 @@
+//! Contrastive embedding losses.
+
+use alloc::format;
+use alloc::vec::Vec;
+
+use crate::error::{Error, TensorError};
+use crate::tensor::Tensor;
+
+impl Tensor<f32> {
+    /// `InfoNCE` loss of `[n, d]` anchor embeddings `self` against `positives`, using in-batch
+    /// negatives.
+    ///
+    /// Row `i` of `positives` is the positive for anchor `i`; every other row is a negative.
+    /// The similarity logits `self × positivesᵀ / τ` go through softmax cross-entropy with the
+    /// diagonal as targets. Returns the per-anchor loss `[n]` and the gradients of the summed
+    /// loss with respect to `self` and `positives`.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidShape`] if `self` is not rank 2 or `positives` has different
+    ///   dimensions.
+    /// - [`TensorError::InvalidArgument`] if `temperature` is not positive and finite.
+    /// - [`Error::Device`] if GPU operation fails.
+    pub fn info_nce(
+        &self,
+        positives: &Self,
+        temperature: f32,
+    ) -> Result<(Self, Self, Self), Error> {
+        let n = self.embedding_rows(&[positives])?;
+        if !(temperature.is_finite() && temperature > 0.0) {
+            return Err(TensorError::InvalidArgument(format!(
+                "temperature {temperature} must be positive and finite"
+            ))
+            .into());
+        }
+
+        let scale = Self::constant(&self.ctx, &[1], &[temperature.recip()])?;
+        let labels = u32::try_from(n)
+            .map_err(|_| TensorError::InvalidShape(format!("{n} rows exceed max size")))?;
+        let targets = Tensor::from_slice(&self.ctx, &(0..labels).collect::<Vec<_>>())?;
+
+        let logits = self.matmul(positives, false, true)?.mul(&scale)?;
+        let (loss, grad) = logits.cross_entropy(&targets)?;
+
+        let grad_anchor = grad.matmul(positives, false, false)?.mul(&scale)?;
+        let grad_positive = grad.matmul(self, true, false)?.mul(&scale)?;
+
+        Ok((loss, grad_anchor, grad_positive))
+    }
+
+    /// Triplet margin loss of `[n, d]` anchor embeddings `self`.
+    ///
+    /// Per row, `loss = max(0, ‖a - p‖ - ‖a - n‖ + margin)` with Euclidean distances. Returns
+    /// the per-anchor loss `[n]` and the gradients of the summed loss with respect to `self`,
+    /// `positives` and `negatives`; rows with zero loss have zero gradient.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidShape`] if `self` is not rank 2 or `positives` or `negatives`
+    ///   have different dimensions.
+    /// - [`TensorError::InvalidArgument`] if `margin` is negative or not finite.
+    /// - [`Error::Device`] if GPU operation fails.
+    pub fn triplet_margin_loss(
+        &self,
+        positives: &Self,
+        negatives: &Self,
+        margin: f32,
+    ) -> Result<(Self, Self, Self, Self), Error> {
+        let n = self.embedding_rows(&[positives, negatives])?;
+        if !(margin.is_finite() && margin >= 0.0) {
+            return Err(TensorError::InvalidArgument(format!(
+                "margin {margin} must be non-negative and finite"
+            ))
+            .into());
+        }
+
+        let scalar = |value: f32| Self::constant(&self.ctx, &[1], &[value]);
+        let zero = scalar(0.0)?;
+        let eps = scalar(1e-12)?;
+
+        let d_pos = self.sub(positives)?;
+        let d_neg = self.sub(negatives)?;
+        let dist_pos = d_pos.sqr()?.sum_reduce(&[1], false)?.sqrt()?;
+        let dist_neg = d_neg.sqr()?.sum_reduce(&[1], false)?.sqrt()?;
+
+        let margins = dist_pos.sub(&dist_neg)?.add(&scalar(margin)?)?;
+        let active = margins.gt(&zero)?.select(&scalar(1.0)?, &zero)?;
+        let loss = margins.max(&zero)?.reshape(&[n])?;
+
+        let unit_pos = d_pos.div(&dist_pos.max(&eps)?)?.mul(&active)?;
+        let unit_neg = d_neg.div(&dist_neg.max(&eps)?)?.mul(&active)?;
+
+        let grad_anchor = unit_pos.sub(&unit_neg)?;
+        let grad_positive = unit_pos.neg()?;
+
+        Ok((loss, grad_anchor, grad_positive, unit_neg))
+    }
+
+    /// Number of rows of `[n, d]` embeddings, checking `others` have the same dimensions.
+    fn embedding_rows(&self, others: &[&Self]) -> Result<usize, Error> {
+        let &[n, _] = self.dimensions() else {
+            return Err(TensorError::InvalidShape(format!(
+                "embeddings must be rank 2, got {:?}",
+                self.dimensions()
+            ))
+            .into());
+        };
+
+        for other in others {
+            if other.dimensions() != self.dimensions() {
+                return Err(TensorError::InvalidShape(format!(
+                    "embedding dimensions {:?} do not match {:?}",
+                    other.dimensions(),
+                    self.dimensions()
+                ))
+                .into());
+            }
+        }
+
+        Ok(n)
+    }
+}
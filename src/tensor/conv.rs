@@ -0,0 +1,301 @@
+//! Depthwise separable convolutions on `[n, c, h, w]` feature maps, and dilated 1D
+//! convolutions on `[n, c, l]` sequences.
+//!
+//! Both memory formats are supported natively by the 2D convolutions; outputs keep the memory
+//! format of the input.
+
+use alloc::format;
+
+use crate::error::{Error, TensorError};
+use crate::kernel::ops;
+use crate::tensor::layout::Layout;
+use crate::tensor::memory_format::{FROM_CHANNELS_LAST, TO_CHANNELS_LAST};
+use crate::tensor::{MemoryFormat, Tensor};
+
+impl Tensor<f32> {
+    /// Depthwise 2D convolution of `[n, c, h, w]` input `self` with `[kh, kw, c]` filters.
+    ///
+    /// Channel `i` is convolved with filter `weight[.., .., i]` only, a grouped convolution
+    /// with one group per channel. `bias` of shape `[c]` is added when given. The input is
+    /// zero padded by `padding` pixels on each side. Returns `[n, c, out_h, out_w]` with
+    /// `out_h = (h + 2·pad_h - kh) / stride_h + 1`, and likewise for the width, in the memory
+    /// format of `self`.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidShape`] if `self` is not rank 4, `weight` is not `[kh, kw, c]`
+    ///   with a non-empty window, `bias` is not `[c]`, or the window exceeds the padded input.
+    /// - [`TensorError::InvalidArgument`] if a stride is zero.
+    /// - [`Error::Device`] if buffer allocation fails.
+    pub fn depthwise_conv2d(
+        &self,
+        weight: &Self,
+        bias: Option<&Self>,
+        stride: (usize, usize),
+        padding: (usize, usize),
+    ) -> Result<Self, Error> {
+        let (n, c, h, w) = self.conv_input_dimensions()?;
+
+        let &[kh, kw, weight_c] = weight.dimensions() else {
+            return Err(TensorError::InvalidShape(format!(
+                "weight must have dimensions [kh, kw, c], got {:?}",
+                weight.dimensions()
+            ))
+            .into());
+        };
+
+        if weight_c != c || kh == 0 || kw == 0 {
+            return Err(TensorError::InvalidShape(format!(
+                "weight dimensions {:?} do not match {c} channels",
+                weight.dimensions()
+            ))
+            .into());
+        }
+
+        Self::check_bias(bias, c)?;
+
+        if stride.0 == 0 || stride.1 == 0 {
+            return Err(TensorError::InvalidArgument(format!(
+                "stride {stride:?} must be positive"
+            ))
+            .into());
+        }
+
+        let (padded_h, padded_w) = (h + 2 * padding.0, w + 2 * padding.1);
+        if kh > padded_h || kw > padded_w {
+            return Err(TensorError::InvalidShape(format!(
+                "window [{kh}, {kw}] exceeds padded input [{padded_h}, {padded_w}]"
+            ))
+            .into());
+        }
+
+        let out_h = (padded_h - kh) / stride.0 + 1;
+        let out_w = (padded_w - kw) / stride.1 + 1;
+
+        let format = self.memory_format();
+        let channels_last = format == MemoryFormat::ChannelsLast;
+        let layout = if channels_last {
+            Layout::from_dimensions(&[n, out_h, out_w, c]).permute(&FROM_CHANNELS_LAST)
+        } else {
+            Layout::from_dimensions(&[n, c, out_h, out_w])
+        };
+        let buffer = self.ctx.create_buffer(layout.size())?;
+        let bias = bias.map(Self::materialize).transpose()?;
+
+        ops::depthwise_conv2d(
+            &self.ctx,
+            &self.to_memory_format(format)?.buffer,
+            &weight.materialize()?.buffer,
+            bias.as_ref().map(|bias| &bias.buffer),
+            &buffer,
+            [c, h, w],
+            (kh, kw),
+            stride,
+            padding,
+            channels_last,
+        );
+
+        Ok(Self {
+            buffer,
+            layout,
+            ctx: self.ctx.clone(),
+        })
+    }
+
+    /// Pointwise 1×1 convolution of `[n, c_in, h, w]` input `self` with `[c_in, c_out]` weights.
+    ///
+    /// Runs as a single matrix multiplication in the memory format of `self`: channels-last
+    /// pixels are the rows of `[n·h·w, c_in] × [c_in, c_out]`, channels-first planes the
+    /// columns of `[c_out, c_in] × [n, c_in, h·w]`. `bias` of shape `[c_out]` is added when
+    /// given. Returns `[n, c_out, h, w]` in the memory format of `self`; following
+    /// [`Self::depthwise_conv2d`] it forms a depthwise separable convolution.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidShape`] if `self` is not rank 4, `weight` is not
+    ///   `[c_in, c_out]`, or `bias` is not `[c_out]`.
+    /// - [`Error::Device`] if GPU operation fails.
+    pub fn pointwise_conv2d(&self, weight: &Self, bias: Option<&Self>) -> Result<Self, Error> {
+        let (n, c, h, w) = self.conv_input_dimensions()?;
+
+        let &[weight_c, out_c] = weight.dimensions() else {
+            return Err(TensorError::InvalidShape(format!(
+                "weight must have dimensions [c_in, c_out], got {:?}",
+                weight.dimensions()
+            ))
+            .into());
+        };
+
+        if weight_c != c {
+            return Err(TensorError::InvalidShape(format!(
+                "weight dimensions {:?} do not match {c} input channels",
+                weight.dimensions()
+            ))
+            .into());
+        }
+
+        Self::check_bias(bias, out_c)?;
+
+        if self.memory_format() == MemoryFormat::ChannelsLast {
+            let y = self
+                .permute(&TO_CHANNELS_LAST)?
+                .reshape(&[n * h * w, c])?
+                .matmul(weight, false, false)?;
+            let y = match bias {
+                Some(bias) => y.add(bias)?,
+                None => y,
+            };
+            y.reshape(&[n, h, w, out_c])?.permute(&FROM_CHANNELS_LAST)
+        } else {
+            let y = weight.reshape(&[1, c, out_c])?.matmul(
+                &self.reshape(&[n, c, h * w])?,
+                true,
+                false,
+            )?;
+            let y = match bias {
+                Some(bias) => y.add(&bias.reshape(&[out_c, 1])?)?,
+                None => y,
+            };
+            y.reshape(&[n, out_c, h, w])
+        }
+    }
+
+    /// Dilated 1D convolution of `[n, c_in, l]` input `self` with `[k, c_in, c_out]` weights.
+    ///
+    /// Output step `t` sums `self[.., .., t·stride + j·dilation - before] · weight[j, .., ..]`
+    /// over the taps `j`, with the input zero padded by `padding = (before, after)` steps.
+    /// `bias` of shape `[c_out]` is added when given. Returns `[n, c_out, out_l]` with
+    /// `out_l = (l + before + after - dilation·(k - 1) - 1) / stride + 1`.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidShape`] if `self` is not rank 3, `weight` is not
+    ///   `[k, c_in, c_out]` with a non-empty window, `bias` is not `[c_out]`, or the dilated
+    ///   window exceeds the padded input.
+    /// - [`TensorError::InvalidArgument`] if `stride` or `dilation` is zero.
+    /// - [`Error::Device`] if buffer allocation fails.
+    pub fn conv1d(
+        &self,
+        weight: &Self,
+        bias: Option<&Self>,
+        stride: usize,
+        dilation: usize,
+        padding: (usize, usize),
+    ) -> Result<Self, Error> {
+        let &[n, c, l] = self.dimensions() else {
+            return Err(TensorError::InvalidShape(format!(
+                "input must have dimensions [n, c, l], got {:?}",
+                self.dimensions()
+            ))
+            .into());
+        };
+
+        let &[k, weight_c, out_c] = weight.dimensions() else {
+            return Err(TensorError::InvalidShape(format!(
+                "weight must have dimensions [k, c_in, c_out], got {:?}",
+                weight.dimensions()
+            ))
+            .into());
+        };
+
+        if weight_c != c || k == 0 {
+            return Err(TensorError::InvalidShape(format!(
+                "weight dimensions {:?} do not match {c} input channels",
+                weight.dimensions()
+            ))
+            .into());
+        }
+
+        Self::check_bias(bias, out_c)?;
+
+        if stride == 0 || dilation == 0 {
+            return Err(TensorError::InvalidArgument(format!(
+                "stride {stride} and dilation {dilation} must be positive"
+            ))
+            .into());
+        }
+
+        let padded = l + padding.0 + padding.1;
+        let span = dilation * (k - 1) + 1;
+        if span > padded {
+            return Err(TensorError::InvalidShape(format!(
+                "dilated window {span} exceeds padded input {padded}"
+            ))
+            .into());
+        }
+
+        let out_l = (padded - span) / stride + 1;
+        let layout = Layout::from_dimensions(&[n, out_c, out_l]);
+        let buffer = self.ctx.create_buffer(layout.size())?;
+        let bias = bias.map(Self::materialize).transpose()?;
+
+        ops::conv1d(
+            &self.ctx,
+            &self.materialize()?.buffer,
+            &weight.materialize()?.buffer,
+            bias.as_ref().map(|bias| &bias.buffer),
+            &buffer,
+            [c, l],
+            [out_c, out_l],
+            k,
+            stride,
+            dilation,
+            padding.0,
+        );
+
+        Ok(Self {
+            buffer,
+            layout,
+            ctx: self.ctx.clone(),
+        })
+    }
+
+    /// Causal 1D convolution: [`Self::conv1d`] with stride 1, padded by `dilation·(k - 1)`
+    /// steps ahead of the input only.
+    ///
+    /// Output step `t` depends on input steps up to `t` alone, and the output keeps the length
+    /// of the input, as required by autoregressive models.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidShape`] if `self` is not rank 3, `weight` is not
+    ///   `[k, c_in, c_out]` with a non-empty window, or `bias` is not `[c_out]`.
+    /// - [`TensorError::InvalidArgument`] if `dilation` is zero.
+    /// - [`Error::Device`] if buffer allocation fails.
+    pub fn causal_conv1d(
+        &self,
+        weight: &Self,
+        bias: Option<&Self>,
+        dilation: usize,
+    ) -> Result<Self, Error> {
+        let k = weight.dimensions().first().copied().unwrap_or(0);
+        let before = dilation * k.saturating_sub(1);
+        self.conv1d(weight, bias, 1, dilation, (before, 0))
+    }
+
+    /// Dimensions `(n, c, h, w)` of a rank 4 input.
+    fn conv_input_dimensions(&self) -> Result<(usize, usize, usize, usize), Error> {
+        let &[n, c, h, w] = self.dimensions() else {
+            return Err(TensorError::InvalidShape(format!(
+                "input must have dimensions [n, c, h, w], got {:?}",
+                self.dimensions()
+            ))
+            .into());
+        };
+        Ok((n, c, h, w))
+    }
+
+    /// Checks an optional bias has dimensions `[channels]`.
+    fn check_bias(bias: Option<&Self>, channels: usize) -> Result<(), Error> {
+        match bias {
+            Some(bias) if bias.dimensions() != [channels] => {
+                Err(TensorError::InvalidShape(format!(
+                    "bias dimensions {:?} do not match [{channels}]",
+                    bias.dimensions()
+                ))
+                .into())
+            }
+            _ => Ok(()),
+        }
+    }
+}
@@ -0,0 +1,212 @@
+//! Object detection postprocessing.
+
+use alloc::format;
+
+use crate::error::{Error, TensorError};
+use crate::kernel::ops;
+use crate::tensor::Tensor;
+use crate::tensor::layout::Layout;
+
+impl Tensor<f32> {
+    /// Pairwise intersection over union of `[..., n, 4]` boxes `self` and `[..., m, 4]` boxes
+    /// `other` in `(x1, y1, x2, y2)` form.
+    ///
+    /// Leading batch dimensions must match. Returns the `[..., n, m]` overlap matrix used for
+    /// anchor matching and evaluation metrics; pairs with an empty union have zero overlap.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidShape`] if either input is not `[..., k, 4]` or batch
+    ///   dimensions differ.
+    /// - [`Error::Device`] if buffer allocation fails.
+    pub fn box_iou(&self, other: &Self) -> Result<Self, Error> {
+        let (batch, n) = self.box_sets()?;
+        let (other_batch, m) = other.box_sets()?;
+
+        if batch != other_batch {
+            return Err(TensorError::InvalidShape(format!(
+                "batch dimensions {batch:?} and {other_batch:?} do not match"
+            ))
+            .into());
+        }
+
+        let mut dimensions = batch.to_vec();
+        dimensions.extend([n, m]);
+        let layout = Layout::from_dimensions(&dimensions);
+        let buffer = self.ctx.create_buffer(layout.size())?;
+
+        ops::box_iou(
+            &self.ctx,
+            &self.materialize()?.buffer,
+            &other.materialize()?.buffer,
+            &buffer,
+            batch.iter().product(),
+            n,
+            m,
+        );
+
+        Ok(Self {
+            buffer,
+            layout,
+            ctx: self.ctx.clone(),
+        })
+    }
+
+    /// Non-maximum suppression of `[n, 4]` boxes `self` in `(x1, y1, x2, y2)` form.
+    ///
+    /// Boxes are visited by descending `scores`, ties by lower index. Each kept box suppresses
+    /// every later box whose intersection over union with it exceeds `iou_threshold`. Returns
+    /// the kept box indices `[n]`, in score order and padded with `u32::MAX`, and their count
+    /// `[1]`; both stay on the GPU.
+    ///
+    /// Suppression runs in a single workgroup, which suits the few thousand candidates left
+    /// after score thresholding.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidShape`] if `self` is not `[n, 4]` or `scores` is not `[n]`.
+    /// - [`TensorError::InvalidArgument`] if `iou_threshold` is NaN.
+    /// - [`Error::Device`] if buffer allocation fails.
+    pub fn nms(
+        &self,
+        scores: &Self,
+        iou_threshold: f32,
+    ) -> Result<(Tensor<u32>, Tensor<u32>), Error> {
+        let n = self.box_count()?;
+        if scores.dimensions() != [n] {
+            return Err(TensorError::InvalidShape(format!(
+                "scores dimensions {:?} do not match [{n}]",
+                scores.dimensions()
+            ))
+            .into());
+        }
+
+        if iou_threshold.is_nan() {
+            return Err(TensorError::InvalidArgument("iou threshold is NaN".into()).into());
+        }
+
+        let order = scores.argsort(true, true)?;
+        let removed = self.ctx.create_buffer(n)?;
+        let keep = self.ctx.create_buffer(n)?;
+        let count = self.ctx.create_buffer(1)?;
+
+        ops::nms(
+            &self.ctx,
+            &self.materialize()?.buffer,
+            &order.buffer,
+            &removed,
+            &keep,
+            &count,
+            iou_threshold,
+        );
+
+        Ok((
+            Tensor {
+                buffer: keep,
+                layout: Layout::from_dimensions(&[n]),
+                ctx: self.ctx.clone(),
+            },
+            Tensor {
+                buffer: count,
+                layout: Layout::from_dimensions(&[1]),
+                ctx: self.ctx.clone(),
+            },
+        ))
+    }
+
+    /// ROI align of `[n, c, h, w]` features `self` over regions of interest.
+    ///
+    /// `boxes` is `[k, 5]` with rows `(batch, x1, y1, x2, y2)` in input image coordinates,
+    /// which `spatial_scale` maps onto the feature map. Each region is split into an
+    /// `output_size` grid of bins, and every bin averages bilinear samples taken on a
+    /// `sampling_ratio × sampling_ratio` grid, or `ceil(bin size)` samples per axis when
+    /// `sampling_ratio` is zero. Coordinates use the half-pixel aligned convention. Regions
+    /// with an out-of-range batch index produce zeros. Returns `[k, c, out_h, out_w]`.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidShape`] if `self` is not rank 4, `boxes` is not `[k, 5]`, or an
+    ///   output size is zero.
+    /// - [`TensorError::InvalidArgument`] if `spatial_scale` is not positive and finite.
+    /// - [`Error::Device`] if buffer allocation fails.
+    pub fn roi_align(
+        &self,
+        boxes: &Self,
+        output_size: (usize, usize),
+        spatial_scale: f32,
+        sampling_ratio: usize,
+    ) -> Result<Self, Error> {
+        let &[n, c, h, w] = self.dimensions() else {
+            return Err(TensorError::InvalidShape(format!(
+                "features must have dimensions [n, c, h, w], got {:?}",
+                self.dimensions()
+            ))
+            .into());
+        };
+
+        let &[k, 5] = boxes.dimensions() else {
+            return Err(TensorError::InvalidShape(format!(
+                "boxes must have dimensions [k, 5], got {:?}",
+                boxes.dimensions()
+            ))
+            .into());
+        };
+
+        if output_size.0 == 0 || output_size.1 == 0 {
+            return Err(TensorError::InvalidShape(format!(
+                "output size {output_size:?} must be non-zero"
+            ))
+            .into());
+        }
+
+        if !(spatial_scale.is_finite() && spatial_scale > 0.0) {
+            return Err(TensorError::InvalidArgument(format!(
+                "spatial scale {spatial_scale} must be positive and finite"
+            ))
+            .into());
+        }
+
+        let layout = Layout::from_dimensions(&[k, c, output_size.0, output_size.1]);
+        let buffer = self.ctx.create_buffer(layout.size())?;
+
+        ops::roi_align(
+            &self.ctx,
+            &self.materialize()?.buffer,
+            &boxes.materialize()?.buffer,
+            &buffer,
+            [n, c, h, w],
+            output_size,
+            spatial_scale,
+            sampling_ratio,
+        );
+
+        Ok(Self {
+            buffer,
+            layout,
+            ctx: self.ctx.clone(),
+        })
+    }
+
+    /// Number of boxes in `[n, 4]` box coordinates.
+    fn box_count(&self) -> Result<usize, Error> {
+        match *self.dimensions() {
+            [n, 4] => Ok(n),
+            _ => Err(TensorError::InvalidShape(format!(
+                "boxes must have dimensions [n, 4], got {:?}",
+                self.dimensions()
+            ))
+            .into()),
+        }
+    }
+
+    /// Batch dimensions and box count of `[..., k, 4]` box coordinates.
+    fn box_sets(&self) -> Result<(&[usize], usize), Error> {
+        match self.dimensions() {
+            [batch @ .., k, 4] => Ok((batch, *k)),
+            dims => Err(TensorError::InvalidShape(format!(
+                "boxes must have dimensions [..., k, 4], got {dims:?}"
+            ))
+            .into()),
+        }
+    }
+}
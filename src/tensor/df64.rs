@@ -0,0 +1,164 @@
+//! Emulated double-precision tensor operations.
+
+use crate::element::Df64;
+use crate::error::Error;
+use crate::kernel::ops;
+use crate::tensor::{Layout, Tensor, matmul_dimensions};
+
+impl Tensor<Df64> {
+    /// Element-wise df64 addition with broadcasting.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidShape`](crate::error::TensorError::InvalidShape) if shapes are
+    ///   not broadcast-compatible.
+    /// - [`Error::Device`] if buffer allocation fails.
+    pub fn add(&self, other: &Self) -> Result<Self, Error> {
+        self.math_binary(other, |ctx, a, b, c, dimensions, a_strides, b_strides| {
+            ops::df64_add(ctx, a, b, c, dimensions, a_strides, b_strides);
+        })
+    }
+
+    /// Element-wise df64 subtraction with broadcasting.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidShape`](crate::error::TensorError::InvalidShape) if shapes are
+    ///   not broadcast-compatible.
+    /// - [`Error::Device`] if buffer allocation fails.
+    pub fn sub(&self, other: &Self) -> Result<Self, Error> {
+        self.math_binary(other, |ctx, a, b, c, dimensions, a_strides, b_strides| {
+            ops::df64_sub(ctx, a, b, c, dimensions, a_strides, b_strides);
+        })
+    }
+
+    /// Element-wise df64 multiplication with broadcasting.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidShape`](crate::error::TensorError::InvalidShape) if shapes are
+    ///   not broadcast-compatible.
+    /// - [`Error::Device`] if buffer allocation fails.
+    pub fn mul(&self, other: &Self) -> Result<Self, Error> {
+        self.math_binary(other, |ctx, a, b, c, dimensions, a_strides, b_strides| {
+            ops::df64_mul(ctx, a, b, c, dimensions, a_strides, b_strides);
+        })
+    }
+
+    /// Batched df64 matrix multiplication with optional transposes.
+    ///
+    /// `A[..., m, k] × B[..., k, n] → C[..., m, n]`
+    ///
+    /// Accumulation is performed in df64, so long inner dimensions keep their precision.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidShape`](crate::error::TensorError::InvalidShape) if ranks differ
+    ///   or are less than 2.
+    /// - [`TensorError::InvalidShape`](crate::error::TensorError::InvalidShape) if inner
+    ///   dimensions don't match.
+    /// - [`Error::Device`] if GPU operation fails.
+    pub fn matmul(
+        &self,
+        other: &Self,
+        transpose_a: bool,
+        transpose_b: bool,
+    ) -> Result<Self, Error> {
+        let (a, transpose_a) = self.matmul_operand(transpose_a)?;
+        let (b, transpose_b) = other.matmul_operand(transpose_b)?;
+        let a_dims = a.layout.dimensions();
+        let b_dims = b.layout.dimensions();
+        let out_dims = matmul_dimensions(a_dims, b_dims, transpose_a, transpose_b)?;
+
+        let layout = Layout::from_dimensions(&out_dims);
+        let buffer = self.ctx.create_buffer(layout.size())?;
+
+        ops::df64_matmul(
+            &self.ctx,
+            &a.buffer,
+            &b.buffer,
+            &buffer,
+            a_dims,
+            b_dims,
+            &out_dims,
+            transpose_a,
+            transpose_b,
+        );
+
+        Ok(Self {
+            buffer,
+            layout,
+            ctx: self.ctx.clone(),
+        })
+    }
+
+    /// df64 sum reduction along specified axes.
+    ///
+    /// Output shape equals input shape with reduced axes set to 1. With `normalize` the sums
+    /// are divided by the number of reduced elements, giving the mean. Partial sums are kept
+    /// in df64, so long reductions do not lose the digits an `f32` sum rounds away.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidShape`](crate::error::TensorError::InvalidShape) if axes are
+    ///   invalid or duplicate.
+    /// - [`Error::Device`] if GPU operation fails.
+    pub fn sum_reduce(&self, axes: &[usize], normalize: bool) -> Result<Self, Error> {
+        let layout = self.reduced_layout(axes)?;
+        let buffer = self.ctx.create_buffer(layout.size())?;
+
+        let x = self.strided()?;
+        ops::df64_sum_reduce(
+            &self.ctx,
+            &x.buffer,
+            &buffer,
+            self.layout.dimensions(),
+            x.layout.strides(),
+            layout.strides(),
+            axes,
+            normalize.then_some(0),
+        );
+
+        Ok(Self {
+            buffer,
+            layout,
+            ctx: self.ctx.clone(),
+        })
+    }
+
+    /// Rounds each element to the nearest `f32`.
+    ///
+    /// # Errors
+    ///
+    /// - [`Error::Device`] if buffer allocation fails.
+    pub fn to_f32(&self) -> Result<Tensor<f32>, Error> {
+        let x = self.dense()?;
+        let buffer = self.ctx.create_buffer(x.buffer.len())?;
+        ops::df64_to_f32(&self.ctx, &x.buffer, &buffer);
+
+        Ok(Tensor {
+            buffer,
+            layout: x.layout,
+            ctx: self.ctx.clone(),
+        })
+    }
+}
+
+impl Tensor<f32> {
+    /// Widens each element to emulated double precision.
+    ///
+    /// # Errors
+    ///
+    /// - [`Error::Device`] if buffer allocation fails.
+    pub fn to_df64(&self) -> Result<Tensor<Df64>, Error> {
+        let x = self.dense()?;
+        let buffer = self.ctx.create_buffer(x.buffer.len())?;
+        ops::df64_from_f32(&self.ctx, &x.buffer, &buffer);
+
+        Ok(Tensor {
+            buffer,
+            layout: x.layout,
+            ctx: self.ctx.clone(),
+        })
+    }
+}
@@ -0,0 +1,136 @@
+//! Probability distribution primitives.
+
+use alloc::format;
+
+use crate::element::FloatElement;
+use crate::error::{Error, TensorError};
+use crate::kernel::distribution::NormalOp;
+use crate::kernel::ops;
+use crate::tensor::Tensor;
+use crate::tensor::layout::Layout;
+
+impl<T: FloatElement> Tensor<T> {
+    /// Draws `num_samples` class indices with replacement from each row of the
+    /// `[..., classes]` weights in `self`.
+    ///
+    /// Output shape equals input shape with the last axis set to `num_samples`. Weights are
+    /// non-negative and need not be normalized: class `c` of a row is drawn with probability
+    /// `w[c] / Σ w`, and never if its weight is zero. Draws happen on the GPU, so e.g. tokens
+    /// can be sampled from softmax probabilities without reading them back; the same seed
+    /// always gives the same draws. A row whose weights sum to zero or less, or to NaN, gives
+    /// `u32::MAX` for all its samples.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidShape`] if `self` is a scalar or has no classes.
+    /// - [`TensorError::InvalidArgument`] if the draws exceed `u32` indices.
+    /// - [`Error::Device`] if buffer allocation fails.
+    pub fn multinomial(&self, num_samples: usize, seed: u32) -> Result<Tensor<u32>, Error> {
+        let dimensions = self.dimensions();
+        let Some(&classes) = dimensions.last().filter(|&&classes| classes > 0) else {
+            return Err(TensorError::InvalidShape(format!(
+                "multinomial sampling requires a non-empty class axis, got dimensions \
+                 {dimensions:?}"
+            ))
+            .into());
+        };
+
+        let mut out_dimensions = dimensions.to_vec();
+        out_dimensions[dimensions.len() - 1] = num_samples;
+        let layout = Layout::from_dimensions(&out_dimensions);
+        if u32::try_from(layout.size()).is_err() || u32::try_from(self.layout.size()).is_err() {
+            return Err(TensorError::InvalidArgument(format!(
+                "{num_samples} draws from weights {dimensions:?} exceed u32 indices"
+            ))
+            .into());
+        }
+
+        let buffer = self.ctx.create_buffer(layout.size())?;
+        ops::multinomial(
+            &self.ctx,
+            &self.materialize()?.buffer,
+            &buffer,
+            classes,
+            num_samples,
+            seed,
+        );
+
+        Ok(Tensor {
+            buffer,
+            layout,
+            ctx: self.ctx.clone(),
+        })
+    }
+
+    /// Draws one class per row of the `[rows, classes]` logits in `self`.
+    pub(crate) fn categorical_sample(&self, seed: u32) -> Result<Tensor<u32>, Error> {
+        let (rows, classes) = (self.dimensions()[0], self.dimensions()[1]);
+        let buffer = self.ctx.create_buffer(rows)?;
+        ops::categorical_sample(
+            &self.ctx,
+            &self.materialize()?.buffer,
+            &buffer,
+            classes,
+            seed,
+        );
+
+        Ok(Tensor {
+            buffer,
+            layout: Layout::from_dimensions(&[rows]),
+            ctx: self.ctx.clone(),
+        })
+    }
+
+    /// Log-probabilities of `[rows]` `actions`, or entropies if `None`, of each row of the
+    /// `[rows, classes]` logits in `self`.
+    pub(crate) fn categorical_score(&self, actions: Option<&Tensor<u32>>) -> Result<Self, Error> {
+        let (rows, classes) = (self.dimensions()[0], self.dimensions()[1]);
+        let buffer = self.ctx.create_buffer(rows)?;
+        let actions = actions.map(Tensor::materialize).transpose()?;
+
+        ops::categorical_score(
+            &self.ctx,
+            &self.materialize()?.buffer,
+            actions.as_ref().map(|actions| &actions.buffer),
+            &buffer,
+            classes,
+        );
+
+        Ok(Self {
+            buffer,
+            layout: Layout::from_dimensions(&[rows]),
+            ctx: self.ctx.clone(),
+        })
+    }
+
+    /// Draws from the normal distribution with mean `self` and standard deviation `std`.
+    pub(crate) fn normal_sample(&self, std: &Self, seed: u32) -> Result<Self, Error> {
+        self.normal(std, &NormalOp::Sample(seed))
+    }
+
+    /// Log-density of `x` under the normal distribution with mean `self` and standard
+    /// deviation `std`.
+    pub(crate) fn normal_log_prob(&self, std: &Self, x: &Self) -> Result<Self, Error> {
+        let x = x.materialize()?;
+        self.normal(std, &NormalOp::LogProb(&x.buffer))
+    }
+
+    /// Entropy of the normal distribution with mean `self` and standard deviation `std`.
+    pub(crate) fn normal_entropy(&self, std: &Self) -> Result<Self, Error> {
+        self.normal(std, &NormalOp::Entropy)
+    }
+
+    /// Applies `op` of the normal distribution with mean `self` and standard deviation `std`
+    /// of the same dimensions.
+    fn normal(&self, std: &Self, op: &NormalOp<'_, T>) -> Result<Self, Error> {
+        let (mean, std) = (self.materialize()?, std.materialize()?);
+        let buffer = self.ctx.create_buffer(mean.layout.size())?;
+        ops::normal(&self.ctx, &mean.buffer, &std.buffer, &buffer, op);
+
+        Ok(Self {
+            buffer,
+            layout: mean.layout,
+            ctx: self.ctx.clone(),
+        })
+    }
+}
@@ -0,0 +1,66 @@
+//! Eigendecomposition of batched symmetric matrices.
+
+use alloc::format;
+
+use crate::element::FloatElement;
+use crate::error::{Error, TensorError};
+use crate::kernel::linalg::eigen::MAX_SIZE;
+use crate::kernel::ops;
+use crate::tensor::Tensor;
+use crate::tensor::layout::Layout;
+
+impl<T: FloatElement> Tensor<T> {
+    /// Eigenvalues and eigenvectors of the symmetric matrices in the last two axes, as
+    /// `(values, vectors)`.
+    ///
+    /// For input `[..., n, n]`, `values` is `[..., n]` in ascending order and `vectors` is
+    /// `[..., n, n]` with the unit eigenvector of `values[..., j]` in column `j`, so each
+    /// matrix equals `vectors · diag(values) · vectorsᵀ`. Only the lower triangle is read.
+    /// Runs cyclic Jacobi rotations with one workgroup per matrix, suited to batches of
+    /// covariance or kernel matrices of up to 32 rows, e.g. for whitening or Mahalanobis
+    /// distances. Eigenvectors are unique only up to sign, and within a repeated eigenvalue
+    /// only up to rotation.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidShape`] if `self` has rank less than 2, its last two axes
+    ///   differ, or they exceed 32.
+    /// - [`Error::Device`] if buffer allocation fails.
+    pub fn eigh(&self) -> Result<(Self, Self), Error> {
+        let dimensions = self.dimensions();
+        let &[.., rows, cols] = dimensions else {
+            return Err(TensorError::InvalidShape(format!(
+                "eigendecomposition requires rank >= 2, got dimensions {dimensions:?}"
+            ))
+            .into());
+        };
+        if rows != cols || rows > MAX_SIZE {
+            return Err(TensorError::InvalidShape(format!(
+                "eigendecomposition requires square matrices of at most {MAX_SIZE} rows, \
+                 got dimensions {dimensions:?}"
+            ))
+            .into());
+        }
+
+        let x = self.materialize()?;
+        let values_layout = Layout::from_dimensions(&dimensions[..dimensions.len() - 1]);
+        let vectors_layout = Layout::from_dimensions(dimensions);
+        let values = self.ctx.create_buffer(values_layout.size())?;
+        let vectors = self.ctx.create_buffer(vectors_layout.size())?;
+
+        ops::symmetric_eigen(&self.ctx, &x.buffer, &values, &vectors, rows);
+
+        Ok((
+            Self {
+                buffer: values,
+                layout: values_layout,
+                ctx: self.ctx.clone(),
+            },
+            Self {
+                buffer: vectors,
+                layout: vectors_layout,
+                ctx: self.ctx.clone(),
+            },
+        ))
+    }
+}
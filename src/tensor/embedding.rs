@@ -0,0 +1,143 @@
+//! Embedding lookups.
+
+use alloc::format;
+
+use crate::element::FloatElement;
+use crate::error::{Error, TensorError};
+use crate::kernel::ops;
+use crate::tensor::Tensor;
+use crate::tensor::layout::Layout;
+use crate::{Buffer, Context};
+
+/// How [`Tensor::embedding_bag`] reduces the rows of a bag.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BagMode {
+    /// Sums the rows of each bag.
+    Sum,
+    /// Averages the rows of each bag; empty bags are zero.
+    Mean,
+}
+
+impl<T: FloatElement> Tensor<T> {
+    /// Sums or averages bags of rows of the `[rows, dim]` embedding table `self`.
+    ///
+    /// `indices` holds the rows of all bags back to back and `offsets[b]` the position where
+    /// bag `b` starts, so bag `b` is `indices[offsets[b]..offsets[b + 1]]` and the last bag
+    /// runs to the end of `indices`. The result is `[bags, dim]`, gathered and reduced in a
+    /// single kernel. Indices out of bounds are skipped.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidShape`] if `self` is not rank 2, or `indices` or `offsets` is
+    ///   not rank 1.
+    /// - [`Error::Device`] if buffer allocation fails.
+    pub fn embedding_bag(
+        &self,
+        indices: &Tensor<u32>,
+        offsets: &Tensor<u32>,
+        mode: BagMode,
+    ) -> Result<Self, Error> {
+        self.reduce_bags(
+            indices,
+            offsets,
+            |ctx, weights, indices, offsets, y, rows, dim| {
+                ops::embedding_bag(
+                    ctx,
+                    weights,
+                    indices,
+                    offsets,
+                    y,
+                    rows,
+                    dim,
+                    mode == BagMode::Mean,
+                );
+            },
+        )
+    }
+
+    /// Sums or averages bags of hashed feature ids over the `[rows, dim]` table `self`.
+    ///
+    /// Works like [`Self::embedding_bag`], except that `ids` may be any `u32` feature ids:
+    /// each id is hashed with `seed` to a row and to a sign of `+1` or `-1`, and the signed
+    /// row is accumulated. Hashing, gathering and reducing run in a single kernel, so sparse
+    /// features with unbounded vocabularies need no id-to-row mapping. The same `seed` always
+    /// maps an id to the same row and sign.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidShape`] if `self` is not rank 2, or `ids` or `offsets` is not
+    ///   rank 1.
+    /// - [`Error::Device`] if buffer allocation fails.
+    pub fn hashed_embedding_bag(
+        &self,
+        ids: &Tensor<u32>,
+        offsets: &Tensor<u32>,
+        mode: BagMode,
+        seed: u32,
+    ) -> Result<Self, Error> {
+        self.reduce_bags(ids, offsets, |ctx, weights, ids, offsets, y, rows, dim| {
+            ops::hashed_embedding_bag(
+                ctx,
+                weights,
+                ids,
+                offsets,
+                y,
+                rows,
+                dim,
+                mode == BagMode::Mean,
+                seed,
+            );
+        })
+    }
+
+    /// Validates a bag lookup and runs `op` into a new `[bags, dim]` tensor.
+    fn reduce_bags(
+        &self,
+        indices: &Tensor<u32>,
+        offsets: &Tensor<u32>,
+        op: impl FnOnce(&Context, &Buffer<T>, &Buffer<u32>, &Buffer<u32>, &Buffer<T>, usize, usize),
+    ) -> Result<Self, Error> {
+        let &[rows, dim] = self.dimensions() else {
+            return Err(TensorError::InvalidShape(format!(
+                "embedding table must be rank 2, got {:?}",
+                self.dimensions()
+            ))
+            .into());
+        };
+
+        let [bags] = *offsets.dimensions() else {
+            return Err(TensorError::InvalidShape(format!(
+                "offsets must be rank 1, got {:?}",
+                offsets.dimensions()
+            ))
+            .into());
+        };
+
+        if indices.dimensions().len() != 1 {
+            return Err(TensorError::InvalidShape(format!(
+                "indices must be rank 1, got {:?}",
+                indices.dimensions()
+            ))
+            .into());
+        }
+
+        let layout = Layout::from_dimensions(&[bags, dim]);
+        let buffer = self.ctx.create_buffer(layout.size())?;
+
+        op(
+            &self.ctx,
+            &self.materialize()?.buffer,
+            &indices.materialize()?.buffer,
+            &offsets.materialize()?.buffer,
+            &buffer,
+            rows,
+            dim,
+        );
+
+        Ok(Self {
+            buffer,
+            layout,
+            ctx: self.ctx.clone(),
+        })
+    }
+}
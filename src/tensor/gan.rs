@@ -0,0 +1,102 @@
+//! Adversarial training losses and spectral normalization.
+
+use alloc::format;
+
+use crate::error::{Error, TensorError};
+use crate::tensor::Tensor;
+
+impl Tensor<f32> {
+    /// Hinge loss of critic scores `self`, with the gradient with respect to the scores.
+    ///
+    /// For scores on real samples `loss = max(0, 1 - x)`, for fake samples
+    /// `loss = max(0, 1 + x)`. Both outputs have the shape of `self`. The generator hinge loss
+    /// `-x` is [`Self::wasserstein_loss`] with `real` set.
+    ///
+    /// # Errors
+    ///
+    /// - [`Error::Device`] if GPU operation fails.
+    pub fn hinge_loss(&self, real: bool) -> Result<(Self, Self), Error> {
+        let scalar = |value: f32| Self::constant(&self.ctx, &[1], &[value]);
+        let zero = scalar(0.0)?;
+        let one = scalar(1.0)?;
+
+        if real {
+            let loss = one.sub(self)?.relu()?;
+            let grad = self.lt(&one)?.select(&scalar(-1.0)?, &zero)?;
+            Ok((loss, grad))
+        } else {
+            let loss = self.add(&one)?.relu()?;
+            let grad = self.gt(&scalar(-1.0)?)?.select(&one, &zero)?;
+            Ok((loss, grad))
+        }
+    }
+
+    /// Wasserstein loss of critic scores `self`, with the gradient with respect to the scores.
+    ///
+    /// For scores on real samples `loss = -x`, for fake samples `loss = x`. Both outputs have
+    /// the shape of `self`.
+    ///
+    /// # Errors
+    ///
+    /// - [`Error::Device`] if GPU operation fails.
+    pub fn wasserstein_loss(&self, real: bool) -> Result<(Self, Self), Error> {
+        let sign = if real { -1.0 } else { 1.0 };
+        let loss = if real { self.neg()? } else { self.copy()? };
+        Ok((loss, Self::constant(&self.ctx, self.dimensions(), &[sign])?))
+    }
+
+    /// Spectral normalization of a `[m, n]` weight matrix by power iteration.
+    ///
+    /// Starting from the left singular vector estimate `u` of shape `[m]`, runs `iterations`
+    /// steps of `v = normalize(Wᵀu)`, `u = normalize(Wv)` and estimates `σ = uᵀWv`. Returns
+    /// `W / σ`, the updated `u` to carry into the next call, and `σ` of shape `[1]`.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidShape`] if `self` is not rank 2 or `u` is not `[m]`.
+    /// - [`TensorError::InvalidArgument`] if `iterations` is zero.
+    /// - [`Error::Device`] if GPU operation fails.
+    pub fn spectral_norm(&self, u: &Self, iterations: usize) -> Result<(Self, Self, Self), Error> {
+        let &[m, _] = self.dimensions() else {
+            return Err(TensorError::InvalidShape(format!(
+                "spectral norm requires a rank 2 weight, got {:?}",
+                self.dimensions()
+            ))
+            .into());
+        };
+
+        if u.dimensions() != [m] {
+            return Err(TensorError::InvalidShape(format!(
+                "u dimensions {:?} do not match [{m}]",
+                u.dimensions()
+            ))
+            .into());
+        }
+
+        if iterations == 0 {
+            return Err(TensorError::InvalidArgument("iterations must be positive".into()).into());
+        }
+
+        let eps = Self::constant(&self.ctx, &[1], &[1e-12])?;
+        let normalize = |x: &Self| -> Result<Self, Error> {
+            let norm = x.sqr()?.sum_reduce(&[0], false)?.sqrt()?;
+            x.div(&norm.max(&eps)?)
+        };
+
+        let mut u = u.reshape(&[m, 1])?;
+        let mut v = normalize(&self.matmul(&u, true, false)?)?;
+        u = normalize(&self.matmul(&v, false, false)?)?;
+        for _ in 1..iterations {
+            v = normalize(&self.matmul(&u, true, false)?)?;
+            u = normalize(&self.matmul(&v, false, false)?)?;
+        }
+
+        let sigma = u
+            .mul(&self.matmul(&v, false, false)?)?
+            .sum_reduce(&[0], false)?
+            .reshape(&[1])?;
+        let weight = self.div(&sigma.max(&eps)?)?;
+
+        Ok((weight, u.reshape(&[m])?, sigma))
+    }
+}
@@ -0,0 +1,154 @@
+//! Group-by aggregation.
+
+use alloc::format;
+use alloc::vec::Vec;
+
+use crate::element::FloatElement;
+use crate::error::{Error, TensorError};
+use crate::kernel::ops;
+use crate::tensor::Tensor;
+use crate::tensor::layout::Layout;
+
+impl<T: FloatElement> Tensor<T> {
+    /// Sums the rows of `self` that share a key, returning the sums and the group sizes.
+    ///
+    /// Row `i` of `self` (its first axis) belongs to group `keys[i]`. The result is
+    /// `[groups, ...]` with the trailing dimensions of `self`, alongside `[groups]` row counts.
+    /// Rows whose key is `groups` or more are ignored, and empty groups sum to zero. Rows are
+    /// grouped by a stable sort of the keys and each group is summed in row order, so the
+    /// result is deterministic.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidShape`] if `self` is a scalar, `keys` is not rank 1, or the
+    ///   number of keys differs from the number of rows.
+    /// - [`Error::Device`] if buffer allocation fails.
+    pub fn group_sum(
+        &self,
+        keys: &Tensor<u32>,
+        groups: usize,
+    ) -> Result<(Self, Tensor<u32>), Error> {
+        self.group_reduce(keys, groups, false, false)
+    }
+
+    /// Averages the rows of `self` that share a key, returning the means and the group sizes.
+    ///
+    /// Groups rows as [`Self::group_sum`]; empty groups have a mean of zero. With a `[n]`
+    /// target tensor this gives the per-category target means used for target encoding.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidShape`] if `self` is a scalar, `keys` is not rank 1, or the
+    ///   number of keys differs from the number of rows.
+    /// - [`Error::Device`] if buffer allocation fails.
+    pub fn group_mean(
+        &self,
+        keys: &Tensor<u32>,
+        groups: usize,
+    ) -> Result<(Self, Tensor<u32>), Error> {
+        self.group_reduce(keys, groups, true, false)
+    }
+
+    /// Sums the rows of `self` in each segment of `segment_ids`.
+    ///
+    /// Row `i` of `self` (its first axis) belongs to segment `segment_ids[i]`, and the ids
+    /// must be sorted in ascending order, as when variable-length sequences are packed one
+    /// after another. The result is `[segments, ...]` with the trailing dimensions of `self`.
+    /// Rows whose id is `segments` or more are ignored, and empty segments sum to zero. Unlike
+    /// [`Self::group_sum`], no sort is needed; unsorted ids give unspecified sums.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidShape`] if `self` is a scalar, `segment_ids` is not rank 1, or
+    ///   the number of ids differs from the number of rows.
+    /// - [`Error::Device`] if buffer allocation fails.
+    pub fn segment_sum(&self, segment_ids: &Tensor<u32>, segments: usize) -> Result<Self, Error> {
+        Ok(self.group_reduce(segment_ids, segments, false, true)?.0)
+    }
+
+    /// Averages the rows of `self` in each segment of `segment_ids`.
+    ///
+    /// Segments rows as [`Self::segment_sum`]; empty segments have a mean of zero. With
+    /// packed `[tokens, features]` sequences this pools each sequence into one row.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidShape`] if `self` is a scalar, `segment_ids` is not rank 1, or
+    ///   the number of ids differs from the number of rows.
+    /// - [`Error::Device`] if buffer allocation fails.
+    pub fn segment_mean(&self, segment_ids: &Tensor<u32>, segments: usize) -> Result<Self, Error> {
+        Ok(self.group_reduce(segment_ids, segments, true, true)?.0)
+    }
+
+    /// Validates a grouping and reduces the rows of each group, sorting the keys first
+    /// unless `sorted`.
+    fn group_reduce(
+        &self,
+        keys: &Tensor<u32>,
+        groups: usize,
+        mean: bool,
+        sorted: bool,
+    ) -> Result<(Self, Tensor<u32>), Error> {
+        let dimensions = self.dimensions();
+        let Some(&rows) = dimensions.first() else {
+            return Err(
+                TensorError::InvalidShape("group values must have rank >= 1".into()).into(),
+            );
+        };
+
+        if keys.dimensions() != [rows] {
+            return Err(TensorError::InvalidShape(format!(
+                "keys {:?} must be [{rows}] for values {dimensions:?}",
+                keys.dimensions()
+            ))
+            .into());
+        }
+
+        let mut out_dims: Vec<usize> = dimensions.to_vec();
+        out_dims[0] = groups;
+        let cols = dimensions[1..].iter().product();
+
+        let layout = Layout::from_dimensions(&out_dims);
+        let buffer = self.ctx.create_buffer(layout.size())?;
+        let counts = self.ctx.create_buffer(groups)?;
+
+        let values = self.materialize()?;
+        if sorted {
+            ops::group_reduce(
+                &self.ctx,
+                &values.buffer,
+                &keys.materialize()?.buffer,
+                None,
+                &buffer,
+                &counts,
+                cols,
+                mean,
+            );
+        } else {
+            let (sorted, order) = keys.sort(false, true)?;
+            ops::group_reduce(
+                &self.ctx,
+                &values.buffer,
+                &sorted.buffer,
+                Some(&order.buffer),
+                &buffer,
+                &counts,
+                cols,
+                mean,
+            );
+        }
+
+        Ok((
+            Self {
+                buffer,
+                layout,
+                ctx: self.ctx.clone(),
+            },
+            Tensor {
+                buffer: counts,
+                layout: Layout::from_dimensions(&[groups]),
+                ctx: self.ctx.clone(),
+            },
+        ))
+    }
+}
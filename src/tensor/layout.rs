@@ -4,9 +4,6 @@ use alloc::boxed::Box;
 use alloc::vec;
 use alloc::vec::Vec;
 
-use crate::Error;
-use crate::error::TensorError;
-
 /// Tensor memory layout descriptor.
 #[derive(Debug, Clone)]
 pub(crate) struct Layout {
@@ -18,19 +15,13 @@ pub(crate) struct Layout {
 impl Layout {
     /// Creates a new contiguous layout from dimensions.
     ///
-    /// # Errors
-    ///
-    /// - [`TensorError::InvalidShape`] if any dimension is zero.
-    pub(crate) fn from_dimensions(dimensions: &[usize]) -> Result<Self, Error> {
-        if dimensions.contains(&0) {
-            return Err(TensorError::InvalidShape("dimensions must be non-zero".into()).into());
-        }
-
-        Ok(Self {
+    /// Dimensions may be zero, giving an empty layout of size 0.
+    pub(crate) fn from_dimensions(dimensions: &[usize]) -> Self {
+        Self {
             dimensions: dimensions.into(),
             strides: Self::compute_strides(dimensions),
             offset: 0,
-        })
+        }
     }
 
     /// Returns the dimensions as a slice.
@@ -45,16 +36,131 @@ impl Layout {
     }
 
     /// Returns the memory offset.
-    #[allow(dead_code)]
     pub(crate) fn offset(&self) -> usize {
         self.offset
     }
 
+    /// Returns a copy of this layout starting at `offset`.
+    pub(crate) fn with_offset(mut self, offset: usize) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    /// Returns true if elements are stored in row-major order without gaps.
+    ///
+    /// Strides of size-1 dimensions are ignored, as they never step.
+    pub(crate) fn is_contiguous(&self) -> bool {
+        let mut expected = 1;
+        for (&dim, &stride) in self.dimensions.iter().zip(&self.strides).rev() {
+            if dim != 1 && stride != expected {
+                return false;
+            }
+            expected *= dim;
+        }
+        true
+    }
+
+    /// Returns true if the layout starts at offset 0 and visits `0..size` exactly once.
+    ///
+    /// Holds for contiguous layouts and any permutation of their axes.
+    pub(crate) fn is_dense(&self) -> bool {
+        let mut axes: Vec<(usize, usize)> = self
+            .dimensions
+            .iter()
+            .copied()
+            .zip(self.strides.iter().copied())
+            .filter(|&(dim, _)| dim != 1)
+            .collect();
+        axes.sort_unstable_by_key(|&(_, stride)| stride);
+
+        let mut expected = 1;
+        for (dim, stride) in axes {
+            if stride != expected {
+                return false;
+            }
+            expected *= dim;
+        }
+        self.offset == 0
+    }
+
+    /// Returns a layout with axes reordered so that axis `i` is axis `axes[i]` of `self`.
+    ///
+    /// `axes` must be distinct axes of `self`, and any axis left out must have size 1.
+    pub(crate) fn permute(&self, axes: &[usize]) -> Self {
+        Self {
+            dimensions: axes.iter().map(|&axis| self.dimensions[axis]).collect(),
+            strides: axes.iter().map(|&axis| self.strides[axis]).collect(),
+            offset: self.offset,
+        }
+    }
+
+    /// Returns a layout without the size-1 `axis`, or without every size-1 axis if `None`.
+    pub(crate) fn squeeze(&self, axis: Option<usize>) -> Self {
+        let keep = |i: usize| self.dimensions[i] != 1 || axis.is_some_and(|axis| axis != i);
+        let kept: Vec<usize> = (0..self.dimensions.len()).filter(|&i| keep(i)).collect();
+        self.permute(&kept)
+    }
+
+    /// Returns a layout restricted to `len` entries of `axis` starting at `start`.
+    ///
+    /// `start + len` must not exceed the size of `axis`.
+    pub(crate) fn narrow(&self, axis: usize, start: usize, len: usize) -> Self {
+        let mut dimensions = self.dimensions.clone();
+        dimensions[axis] = len;
+
+        Self {
+            dimensions,
+            strides: self.strides.clone(),
+            offset: self.offset + start * self.strides[axis],
+        }
+    }
+
+    /// Returns a layout with a size-1 axis inserted before `axis`.
+    ///
+    /// `axis` must be at most the rank.
+    pub(crate) fn unsqueeze(&self, axis: usize) -> Self {
+        let stride = self
+            .dimensions
+            .get(axis)
+            .map_or(1, |&dim| dim * self.strides[axis]);
+
+        let mut dimensions = self.dimensions.to_vec();
+        let mut strides = self.strides.to_vec();
+        dimensions.insert(axis, 1);
+        strides.insert(axis, stride);
+
+        Self {
+            dimensions: dimensions.into_boxed_slice(),
+            strides: strides.into_boxed_slice(),
+            offset: self.offset,
+        }
+    }
+
+    /// Returns a layout over the main diagonal of the last two axes.
+    ///
+    /// The two axes are replaced by one of size `min(rows, cols)` whose stride steps one row
+    /// and one column at once. The rank must be at least 2.
+    pub(crate) fn diagonal(&self) -> Self {
+        let rank = self.dimensions.len();
+        let (rows, cols) = (self.dimensions[rank - 2], self.dimensions[rank - 1]);
+
+        let mut dimensions = self.dimensions[..rank - 2].to_vec();
+        let mut strides = self.strides[..rank - 2].to_vec();
+        dimensions.push(rows.min(cols));
+        strides.push(self.strides[rank - 2] + self.strides[rank - 1]);
+
+        Self {
+            dimensions: dimensions.into_boxed_slice(),
+            strides: strides.into_boxed_slice(),
+            offset: self.offset,
+        }
+    }
+
     /// Returns the total number of elements.
     ///
-    /// Returns 1 for scalars.
+    /// Returns 1 for scalars and 0 if any dimension is zero.
     pub(crate) fn size(&self) -> usize {
-        self.dimensions.iter().product::<usize>().max(1)
+        self.dimensions.iter().product::<usize>()
     }
 
     /// Computes broadcast dimensions and strides for multiple layouts.
@@ -87,6 +193,24 @@ impl Layout {
         Some((out_dims, strides))
     }
 
+    /// Returns a view of this layout broadcast to `target`, with stride 0 along broadcast
+    /// dimensions, or `None` if the dimensions do not broadcast to exactly `target` or its
+    /// volume overflows.
+    pub(crate) fn broadcast_to(&self, target: &[usize]) -> Option<Self> {
+        if *Self::broadcast_dimensions(&self.dimensions, target)? != *target {
+            return None;
+        }
+        target
+            .iter()
+            .try_fold(1usize, |acc, &d| acc.checked_mul(d))?;
+
+        Some(Self {
+            dimensions: target.into(),
+            strides: self.broadcast_strides(target),
+            offset: self.offset,
+        })
+    }
+
     /// Computes broadcast dimensions for two dimension slices.
     fn broadcast_dimensions(a: &[usize], b: &[usize]) -> Option<Box<[usize]>> {
         let mut result: Vec<usize> = a
@@ -154,76 +278,184 @@ mod tests {
 
     #[test]
     fn test_from_dimensions() {
-        // valid
-        assert!(Layout::from_dimensions(&[1, 2, 3, 4]).is_ok());
-        assert!(Layout::from_dimensions(&[2, 2]).is_ok());
-        assert!(Layout::from_dimensions(&[4]).is_ok());
-        assert!(Layout::from_dimensions(&[]).is_ok());
+        assert_eq!(Layout::from_dimensions(&[1, 2, 3, 4]).size(), 24);
+        assert_eq!(Layout::from_dimensions(&[2, 2]).size(), 4);
+        assert_eq!(Layout::from_dimensions(&[4]).size(), 4);
+        assert_eq!(Layout::from_dimensions(&[]).size(), 1);
 
         // zero dimension
-        assert!(Layout::from_dimensions(&[0, 1, 1]).is_err());
-        assert!(Layout::from_dimensions(&[1, 0, 1]).is_err());
-        assert!(Layout::from_dimensions(&[1, 1, 0]).is_err());
-        assert!(Layout::from_dimensions(&[0]).is_err());
+        assert_eq!(Layout::from_dimensions(&[0, 1, 1]).size(), 0);
+        assert_eq!(Layout::from_dimensions(&[1, 0, 1]).size(), 0);
+        assert_eq!(Layout::from_dimensions(&[1, 1, 0]).size(), 0);
+        assert_eq!(Layout::from_dimensions(&[0]).size(), 0);
     }
 
     #[test]
     fn test_dimensions() {
-        let l = Layout::from_dimensions(&[1, 2, 3, 4]).unwrap();
+        let l = Layout::from_dimensions(&[1, 2, 3, 4]);
         assert_eq!(l.dimensions(), &[1, 2, 3, 4]);
 
-        let l = Layout::from_dimensions(&[2, 2]).unwrap();
+        let l = Layout::from_dimensions(&[2, 2]);
         assert_eq!(l.dimensions(), &[2, 2]);
 
-        let l = Layout::from_dimensions(&[4]).unwrap();
+        let l = Layout::from_dimensions(&[4]);
         assert_eq!(l.dimensions(), &[4]);
 
-        let l = Layout::from_dimensions(&[]).unwrap();
+        let l = Layout::from_dimensions(&[]);
         assert_eq!(l.dimensions(), &[] as &[usize]);
     }
 
     #[test]
     fn test_strides() {
-        let l = Layout::from_dimensions(&[1, 2, 3, 4]).unwrap();
+        let l = Layout::from_dimensions(&[1, 2, 3, 4]);
         assert_eq!(l.strides(), &[24, 12, 4, 1]);
 
-        let l = Layout::from_dimensions(&[2, 2]).unwrap();
+        let l = Layout::from_dimensions(&[2, 2]);
         assert_eq!(l.strides(), &[2, 1]);
 
-        let l = Layout::from_dimensions(&[4]).unwrap();
+        let l = Layout::from_dimensions(&[4]);
         assert_eq!(l.strides(), &[1]);
 
-        let l = Layout::from_dimensions(&[]).unwrap();
+        let l = Layout::from_dimensions(&[]);
         assert_eq!(l.strides(), &[] as &[usize]);
     }
 
     #[test]
     fn test_offset() {
-        let l = Layout::from_dimensions(&[1, 2, 3, 4]).unwrap();
+        let l = Layout::from_dimensions(&[1, 2, 3, 4]);
         assert_eq!(l.offset(), 0);
 
-        let l = Layout::from_dimensions(&[2, 2]).unwrap();
+        let l = Layout::from_dimensions(&[2, 2]);
         assert_eq!(l.offset(), 0);
 
-        let l = Layout::from_dimensions(&[4]).unwrap();
+        let l = Layout::from_dimensions(&[4]);
         assert_eq!(l.offset(), 0);
 
-        let l = Layout::from_dimensions(&[]).unwrap();
+        let l = Layout::from_dimensions(&[]);
         assert_eq!(l.offset(), 0);
     }
 
+    #[test]
+    fn test_is_contiguous() {
+        assert!(Layout::from_dimensions(&[2, 3, 4]).is_contiguous());
+        assert!(Layout::from_dimensions(&[]).is_contiguous());
+
+        let l = Layout::from_dimensions(&[2, 3]).with_offset(6);
+        assert!(l.is_contiguous());
+        assert_eq!(l.offset(), 6);
+
+        let l = Layout {
+            dimensions: Box::new([3, 2]),
+            strides: Box::new([1, 3]),
+            offset: 0,
+        };
+        assert!(!l.is_contiguous());
+
+        let l = Layout {
+            dimensions: Box::new([1, 4]),
+            strides: Box::new([100, 1]),
+            offset: 0,
+        };
+        assert!(l.is_contiguous());
+    }
+
+    #[test]
+    fn test_is_dense() {
+        assert!(Layout::from_dimensions(&[2, 3, 4]).is_dense());
+        assert!(Layout::from_dimensions(&[]).is_dense());
+        assert!(!Layout::from_dimensions(&[2, 3]).with_offset(6).is_dense());
+
+        let l = Layout::from_dimensions(&[2, 3, 4]).permute(&[2, 0, 1]);
+        assert_eq!(l.dimensions(), &[4, 2, 3]);
+        assert_eq!(l.strides(), &[1, 12, 4]);
+        assert!(l.is_dense());
+        assert!(!l.is_contiguous());
+
+        let l = Layout {
+            dimensions: Box::new([3, 2]),
+            strides: Box::new([4, 1]),
+            offset: 0,
+        };
+        assert!(!l.is_dense());
+
+        let l = Layout {
+            dimensions: Box::new([3, 4]),
+            strides: Box::new([0, 1]),
+            offset: 0,
+        };
+        assert!(!l.is_dense());
+    }
+
+    #[test]
+    fn test_narrow() {
+        let l = Layout::from_dimensions(&[4, 3]);
+
+        let rows = l.narrow(0, 1, 2);
+        assert_eq!(rows.dimensions(), &[2, 3]);
+        assert_eq!(rows.offset(), 3);
+        assert!(rows.is_contiguous());
+        assert!(!rows.is_dense());
+
+        let cols = rows.narrow(1, 2, 1);
+        assert_eq!(cols.dimensions(), &[2, 1]);
+        assert_eq!(cols.strides(), &[3, 1]);
+        assert_eq!(cols.offset(), 5);
+        assert!(!cols.is_contiguous());
+    }
+
+    #[test]
+    fn test_squeeze_unsqueeze() {
+        let l = Layout::from_dimensions(&[1, 3, 1, 4]);
+
+        let squeezed = l.squeeze(None);
+        assert_eq!(squeezed.dimensions(), &[3, 4]);
+        assert_eq!(squeezed.strides(), &[4, 1]);
+
+        let squeezed = l.squeeze(Some(2));
+        assert_eq!(squeezed.dimensions(), &[1, 3, 4]);
+        assert_eq!(squeezed.strides(), &[12, 4, 1]);
+
+        let l = Layout::from_dimensions(&[3, 4]);
+        let unsqueezed = l.unsqueeze(1);
+        assert_eq!(unsqueezed.dimensions(), &[3, 1, 4]);
+        assert_eq!(unsqueezed.strides(), &[4, 4, 1]);
+        assert!(unsqueezed.is_contiguous());
+
+        let unsqueezed = l.unsqueeze(2);
+        assert_eq!(unsqueezed.dimensions(), &[3, 4, 1]);
+        assert!(unsqueezed.is_contiguous());
+    }
+
+    #[test]
+    fn test_diagonal() {
+        let l = Layout::from_dimensions(&[2, 3, 4]);
+
+        let d = l.diagonal();
+        assert_eq!(d.dimensions(), &[2, 3]);
+        assert_eq!(d.strides(), &[12, 5]);
+        assert_eq!(d.offset(), 0);
+
+        let d = l.narrow(2, 1, 3).diagonal();
+        assert_eq!(d.dimensions(), &[2, 3]);
+        assert_eq!(d.offset(), 1);
+
+        let d = l.permute(&[0, 2, 1]).diagonal();
+        assert_eq!(d.dimensions(), &[2, 3]);
+        assert_eq!(d.strides(), &[12, 5]);
+    }
+
     #[test]
     fn test_size() {
-        let l = Layout::from_dimensions(&[1, 2, 3, 4]).unwrap();
+        let l = Layout::from_dimensions(&[1, 2, 3, 4]);
         assert_eq!(l.size(), 24);
 
-        let l = Layout::from_dimensions(&[2, 2]).unwrap();
+        let l = Layout::from_dimensions(&[2, 2]);
         assert_eq!(l.size(), 4);
 
-        let l = Layout::from_dimensions(&[4]).unwrap();
+        let l = Layout::from_dimensions(&[4]);
         assert_eq!(l.size(), 4);
 
-        let l = Layout::from_dimensions(&[]).unwrap();
+        let l = Layout::from_dimensions(&[]);
         assert_eq!(l.size(), 1);
     }
 
@@ -236,7 +468,7 @@ mod tests {
 
     #[test]
     fn test_broadcast_single() {
-        let a = Layout::from_dimensions(&[2, 3, 4]).unwrap();
+        let a = Layout::from_dimensions(&[2, 3, 4]);
         let (dims, strides) = Layout::broadcast(&[&a]).unwrap();
         assert_eq!(dims.as_ref(), &[2, 3, 4]);
         assert_eq!(strides.len(), 1);
@@ -245,8 +477,8 @@ mod tests {
 
     #[test]
     fn test_broadcast_two_same() {
-        let a = Layout::from_dimensions(&[2, 3, 4]).unwrap();
-        let b = Layout::from_dimensions(&[2, 3, 4]).unwrap();
+        let a = Layout::from_dimensions(&[2, 3, 4]);
+        let b = Layout::from_dimensions(&[2, 3, 4]);
         let (dims, strides) = Layout::broadcast(&[&a, &b]).unwrap();
         assert_eq!(dims.as_ref(), &[2, 3, 4]);
         assert_eq!(strides[0].as_ref(), &[12, 4, 1]);
@@ -255,8 +487,8 @@ mod tests {
 
     #[test]
     fn test_broadcast_two_scalar() {
-        let a = Layout::from_dimensions(&[2, 3, 4]).unwrap();
-        let b = Layout::from_dimensions(&[]).unwrap();
+        let a = Layout::from_dimensions(&[2, 3, 4]);
+        let b = Layout::from_dimensions(&[]);
         let (dims, strides) = Layout::broadcast(&[&a, &b]).unwrap();
         assert_eq!(dims.as_ref(), &[2, 3, 4]);
         assert_eq!(strides[0].as_ref(), &[12, 4, 1]);
@@ -265,8 +497,8 @@ mod tests {
 
     #[test]
     fn test_broadcast_two_trailing() {
-        let a = Layout::from_dimensions(&[2, 3, 4]).unwrap();
-        let b = Layout::from_dimensions(&[4]).unwrap();
+        let a = Layout::from_dimensions(&[2, 3, 4]);
+        let b = Layout::from_dimensions(&[4]);
         let (dims, strides) = Layout::broadcast(&[&a, &b]).unwrap();
         assert_eq!(dims.as_ref(), &[2, 3, 4]);
         assert_eq!(strides[0].as_ref(), &[12, 4, 1]);
@@ -275,8 +507,8 @@ mod tests {
 
     #[test]
     fn test_broadcast_two_expand() {
-        let a = Layout::from_dimensions(&[3, 1]).unwrap();
-        let b = Layout::from_dimensions(&[1, 4]).unwrap();
+        let a = Layout::from_dimensions(&[3, 1]);
+        let b = Layout::from_dimensions(&[1, 4]);
         let (dims, strides) = Layout::broadcast(&[&a, &b]).unwrap();
         assert_eq!(dims.as_ref(), &[3, 4]);
         assert_eq!(strides[0].as_ref(), &[1, 0]);
@@ -285,8 +517,8 @@ mod tests {
 
     #[test]
     fn test_broadcast_two_multi_expand() {
-        let a = Layout::from_dimensions(&[2, 1, 4]).unwrap();
-        let b = Layout::from_dimensions(&[3, 1]).unwrap();
+        let a = Layout::from_dimensions(&[2, 1, 4]);
+        let b = Layout::from_dimensions(&[3, 1]);
         let (dims, strides) = Layout::broadcast(&[&a, &b]).unwrap();
         assert_eq!(dims.as_ref(), &[2, 3, 4]);
         assert_eq!(strides[0].as_ref(), &[4, 0, 1]);
@@ -295,9 +527,9 @@ mod tests {
 
     #[test]
     fn test_broadcast_three() {
-        let a = Layout::from_dimensions(&[2, 1, 4]).unwrap();
-        let b = Layout::from_dimensions(&[3, 1]).unwrap();
-        let c = Layout::from_dimensions(&[1]).unwrap();
+        let a = Layout::from_dimensions(&[2, 1, 4]);
+        let b = Layout::from_dimensions(&[3, 1]);
+        let c = Layout::from_dimensions(&[1]);
         let (dims, strides) = Layout::broadcast(&[&a, &b, &c]).unwrap();
         assert_eq!(dims.as_ref(), &[2, 3, 4]);
         assert_eq!(strides[0].as_ref(), &[4, 0, 1]);
@@ -307,61 +539,78 @@ mod tests {
 
     #[test]
     fn test_broadcast_incompatible() {
-        let a = Layout::from_dimensions(&[3]).unwrap();
-        let b = Layout::from_dimensions(&[4]).unwrap();
+        let a = Layout::from_dimensions(&[3]);
+        let b = Layout::from_dimensions(&[4]);
         assert!(Layout::broadcast(&[&a, &b]).is_none());
 
-        let a = Layout::from_dimensions(&[2, 3]).unwrap();
-        let b = Layout::from_dimensions(&[3, 2]).unwrap();
+        let a = Layout::from_dimensions(&[2, 3]);
+        let b = Layout::from_dimensions(&[3, 2]);
         assert!(Layout::broadcast(&[&a, &b]).is_none());
     }
 
     #[test]
     fn test_broadcast_three_incompatible() {
-        let a = Layout::from_dimensions(&[2, 3]).unwrap();
-        let b = Layout::from_dimensions(&[3]).unwrap();
-        let c = Layout::from_dimensions(&[4]).unwrap();
+        let a = Layout::from_dimensions(&[2, 3]);
+        let b = Layout::from_dimensions(&[3]);
+        let c = Layout::from_dimensions(&[4]);
         assert!(Layout::broadcast(&[&a, &b, &c]).is_none());
     }
 
+    #[test]
+    fn test_broadcast_to() {
+        let a = Layout::from_dimensions(&[3, 1]);
+
+        let l = a.broadcast_to(&[2, 3, 4]).unwrap();
+        assert_eq!(l.dimensions(), &[2, 3, 4]);
+        assert_eq!(l.strides(), &[0, 1, 0]);
+        assert!(!l.is_contiguous());
+        assert!(!l.is_dense());
+
+        assert!(a.broadcast_to(&[3, 1]).unwrap().is_contiguous());
+        assert!(a.broadcast_to(&[1]).is_none());
+        assert!(a.broadcast_to(&[2, 4]).is_none());
+        assert!(a.broadcast_to(&[0, 2, 4]).is_none());
+        assert_eq!(a.broadcast_to(&[0, 3, 4]).unwrap().size(), 0);
+    }
+
     #[test]
     fn test_broadcast_strides_same() {
-        let a = Layout::from_dimensions(&[2, 3, 4]).unwrap();
+        let a = Layout::from_dimensions(&[2, 3, 4]);
         let target = [2, 3, 4];
         assert_eq!(a.broadcast_strides(&target).as_ref(), &[12, 4, 1]);
     }
 
     #[test]
     fn test_broadcast_strides_scalar() {
-        let a = Layout::from_dimensions(&[]).unwrap();
+        let a = Layout::from_dimensions(&[]);
         let target = [2, 3, 4];
         assert_eq!(a.broadcast_strides(&target).as_ref(), &[0, 0, 0]);
     }
 
     #[test]
     fn test_broadcast_strides_trailing() {
-        let a = Layout::from_dimensions(&[4]).unwrap();
+        let a = Layout::from_dimensions(&[4]);
         let target = [2, 3, 4];
         assert_eq!(a.broadcast_strides(&target).as_ref(), &[0, 0, 1]);
     }
 
     #[test]
     fn test_broadcast_strides_expand() {
-        let a = Layout::from_dimensions(&[3, 1]).unwrap();
+        let a = Layout::from_dimensions(&[3, 1]);
         let target = [3, 4];
         assert_eq!(a.broadcast_strides(&target).as_ref(), &[1, 0]);
 
-        let b = Layout::from_dimensions(&[1, 4]).unwrap();
+        let b = Layout::from_dimensions(&[1, 4]);
         assert_eq!(b.broadcast_strides(&target).as_ref(), &[0, 1]);
     }
 
     #[test]
     fn test_broadcast_strides_multi_expand() {
-        let a = Layout::from_dimensions(&[2, 1, 4]).unwrap();
+        let a = Layout::from_dimensions(&[2, 1, 4]);
         let target = [2, 3, 4];
         assert_eq!(a.broadcast_strides(&target).as_ref(), &[4, 0, 1]);
 
-        let b = Layout::from_dimensions(&[3, 1]).unwrap();
+        let b = Layout::from_dimensions(&[3, 1]);
         assert_eq!(b.broadcast_strides(&target).as_ref(), &[0, 1, 0]);
     }
 }
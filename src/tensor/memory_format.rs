@@ -0,0 +1,74 @@
+//! Memory formats of `[n, c, h, w]` image tensors.
+
+use alloc::format;
+
+use crate::Element;
+use crate::error::{Error, TensorError};
+use crate::tensor::Tensor;
+
+/// Axis order that views a channels-last `[n, c, h, w]` tensor as contiguous `[n, h, w, c]`.
+pub(crate) const TO_CHANNELS_LAST: [usize; 4] = [0, 2, 3, 1];
+
+/// Axis order that views contiguous `[n, h, w, c]` data as a channels-last `[n, c, h, w]` tensor.
+pub(crate) const FROM_CHANNELS_LAST: [usize; 4] = [0, 3, 1, 2];
+
+/// Order in which the axes of an `[n, c, h, w]` tensor are laid out in memory.
+///
+/// The dimensions of a tensor are the same in both formats; only the strides differ.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MemoryFormat {
+    /// NCHW: each channel is a contiguous `h × w` plane.
+    #[default]
+    ChannelsFirst,
+    /// NHWC: the channels of each pixel are contiguous.
+    ChannelsLast,
+}
+
+impl<T: Element> Tensor<T> {
+    /// Memory format of `self`.
+    ///
+    /// Rank 4 tensors whose strides order the channel axis last report
+    /// [`MemoryFormat::ChannelsLast`]; every other tensor reports
+    /// [`MemoryFormat::ChannelsFirst`].
+    #[must_use]
+    pub fn memory_format(&self) -> MemoryFormat {
+        if self.dimensions().len() == 4
+            && !self.layout.is_contiguous()
+            && self.layout.permute(&TO_CHANNELS_LAST).is_contiguous()
+        {
+            MemoryFormat::ChannelsLast
+        } else {
+            MemoryFormat::ChannelsFirst
+        }
+    }
+
+    /// Lays out `[n, c, h, w]` tensor `self` in `format`, keeping its dimensions.
+    ///
+    /// Shares the buffer if `self` already spans it in `format`, otherwise copies. Element-wise
+    /// operations keep the memory format of their input, and the convolutions pick the kernel
+    /// matching it.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidShape`] if `self` is not rank 4.
+    /// - [`Error::Device`] if buffer allocation fails.
+    pub fn to_memory_format(&self, format: MemoryFormat) -> Result<Self, Error> {
+        if self.dimensions().len() != 4 {
+            return Err(TensorError::InvalidShape(format!(
+                "memory format requires dimensions [n, c, h, w], got {:?}",
+                self.dimensions()
+            ))
+            .into());
+        }
+
+        match format {
+            MemoryFormat::ChannelsFirst => self.materialize(),
+            MemoryFormat::ChannelsLast => {
+                let nhwc = self
+                    .with_layout(self.layout.permute(&TO_CHANNELS_LAST))
+                    .materialize()?;
+                Ok(nhwc.with_layout(nhwc.layout.permute(&FROM_CHANNELS_LAST)))
+            }
+        }
+    }
+}
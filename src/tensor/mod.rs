@@ -1,16 +1,56 @@
 //! N-dimensional tensor with GPU-backed storage.
 
+mod boosting;
+mod contrastive;
+mod conv;
+mod detection;
+mod df64;
+mod distribution;
+mod eigen;
+mod embedding;
+mod gan;
+mod group;
 mod layout;
+mod memory_format;
+mod moe;
+mod norm;
+mod pad;
+mod random;
+mod rearrange;
+mod rl;
+mod rolling;
+mod sharded;
+mod spatial;
+mod ssm;
+mod stop;
+mod texture;
+mod triangle;
+
+use core::cmp::Ordering;
 
 use alloc::vec::Vec;
 use alloc::{format, vec};
 
+use crate::compute;
 use crate::element::{FloatElement, IntegerElement, LogicalElement, NumericElement, SignedElement};
 use crate::error::{Error, TensorError};
-use crate::kernel::ops;
+use crate::kernel::reduction::full::{self, FullReduction};
+use crate::kernel::reduction::scan::Scan;
+use crate::kernel::{compact, ops};
 use crate::{Buffer, Context, Element};
 use layout::Layout;
 
+pub use embedding::BagMode;
+pub use memory_format::MemoryFormat;
+pub use pad::PadMode;
+pub use sharded::ShardedMatrix;
+pub use spatial::Interpolation;
+pub use texture::{Letterbox, Normalization};
+
+/// Dimension placeholder for [`Tensor::reshape`] and [`Tensor::view`] that is inferred from
+/// the volume, e.g. `x.reshape(&[INFER, 128])` flattens all but the last axis.
+pub const INFER: usize = usize::MAX;
+
 /// N-dimensional tensor with GPU-backed storage.
 pub struct Tensor<T: Element> {
     /// GPU buffer storing tensor elements.
@@ -25,103 +65,1309 @@ impl<T: Element> Tensor<T> {
     /// Creates a tensor with constant values.
     ///
     /// If `value` has length 1, that single value is broadcast to fill the entire tensor.
-    /// Otherwise, `value` length must equal the shape volume.
+    /// Otherwise, `value` length must equal the shape volume. Zero dimensions give an empty
+    /// tensor, which `value` may fill with an empty slice.
     ///
     /// # Errors
     ///
-    /// - [`TensorError::InvalidShape`] if `value` is empty, any dimension is zero,
-    ///   or value length is neither 1 nor equal to shape volume.
+    /// - [`TensorError::InvalidShape`] if value length is neither 1 nor equal to shape volume.
     /// - [`Error::Device`] if operation fails.
     pub fn constant(ctx: &Context, shape: &[usize], value: &[T]) -> Result<Self, Error> {
-        if value.is_empty() {
-            return Err(TensorError::InvalidShape("value must not be empty".into()).into());
+        let layout = Layout::from_dimensions(shape);
+        let volume = layout.size();
+
+        let buffer = match value.len() {
+            1 => {
+                let buffer = ctx.create_buffer(volume)?;
+                ops::constant(ctx, &buffer, value[0]);
+                buffer
+            }
+            n if n == volume => ctx.create_buffer_from_slice(value)?,
+            n => {
+                return Err(TensorError::InvalidShape(format!(
+                    "value length {n} must be 1 or equal to shape volume {volume}"
+                ))
+                .into());
+            }
+        };
+
+        Ok(Self {
+            buffer,
+            layout,
+            ctx: ctx.clone(),
+        })
+    }
+
+    /// Creates a tensor from shape and data slice.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidShape`] if size doesn't match data length.
+    /// - [`Error::Device`] if operation fails.
+    pub fn from_shape_slice(ctx: &Context, shape: &[usize], data: &[T]) -> Result<Self, Error> {
+        Self::constant(ctx, shape, data)
+    }
+
+    /// Creates a 1D tensor from a data slice.
+    ///
+    /// An empty slice gives an empty tensor of shape `[0]`.
+    ///
+    /// # Errors
+    ///
+    /// - [`Error::Device`] if operation fails.
+    pub fn from_slice(ctx: &Context, data: &[T]) -> Result<Self, Error> {
+        Self::constant(ctx, &[data.len()], data)
+    }
+
+    /// Creates a rank-0 tensor holding `value`.
+    ///
+    /// Scalars broadcast against tensors of any shape, and [`Self::item`] reads them back.
+    ///
+    /// # Errors
+    ///
+    /// - [`Error::Device`] if operation fails.
+    pub fn scalar(ctx: &Context, value: T) -> Result<Self, Error> {
+        Self::constant(ctx, &[], &[value])
+    }
+
+    /// Overwrites elements starting at flat row-major `offset` with `data`, in place.
+    ///
+    /// Lets large tensors be filled chunk by chunk, e.g. while weights are still downloading,
+    /// without holding the whole payload in host memory.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidShape`] if `self` is not contiguous.
+    /// - [`TensorError::InvalidArgument`] if `offset + data.len()` exceeds the tensor size.
+    pub fn write_slice(&mut self, offset: usize, data: &[T]) -> Result<(), Error> {
+        if !self.layout.is_contiguous() {
+            return Err(TensorError::InvalidShape(format!(
+                "write_slice requires a contiguous tensor, got strides {:?}",
+                self.layout.strides()
+            ))
+            .into());
+        }
+
+        let size = self.layout.size();
+        if offset.checked_add(data.len()).is_none_or(|end| end > size) {
+            return Err(TensorError::InvalidArgument(format!(
+                "write of {} elements at offset {offset} exceeds tensor size {size}",
+                data.len()
+            ))
+            .into());
+        }
+
+        self.ctx
+            .write_buffer(&self.buffer, self.layout.offset() + offset, data)
+    }
+
+    /// Creates a copy of this tensor.
+    ///
+    /// # Errors
+    ///
+    /// - [`Error::Device`] if operation fails.
+    pub fn copy(&self) -> Result<Self, Error> {
+        if !self.spans_buffer() {
+            return self.materialize();
+        }
+
+        let buffer = self.ctx.create_buffer(self.buffer.len())?;
+        ops::copy(&self.ctx, &self.buffer, &buffer);
+
+        Ok(Self {
+            buffer,
+            layout: self.layout.clone(),
+            ctx: self.ctx.clone(),
+        })
+    }
+
+    /// Returns the tensor dimensions.
+    #[must_use]
+    pub fn dimensions(&self) -> &[usize] {
+        self.layout.dimensions()
+    }
+
+    /// Returns true if the tensor is packed in row-major order and covers its whole buffer.
+    ///
+    /// Fresh tensors are contiguous; views such as [`Self::transpose`], [`Self::narrow`] or
+    /// [`Self::broadcast_to`] generally are not.
+    #[must_use]
+    pub fn is_contiguous(&self) -> bool {
+        self.layout.is_contiguous() && self.spans_buffer()
+    }
+
+    /// Returns a packed row-major tensor with the same elements.
+    ///
+    /// Contiguous tensors are returned as a view sharing the buffer; any other layout,
+    /// with arbitrary strides and offset, is gathered into a new buffer by a copy kernel.
+    ///
+    /// # Errors
+    ///
+    /// - [`Error::Device`] if a copy is needed and buffer allocation fails.
+    pub fn contiguous(&self) -> Result<Self, Error> {
+        self.materialize()
+    }
+
+    /// Returns the GPU context of this tensor.
+    pub(crate) fn context(&self) -> &Context {
+        &self.ctx
+    }
+
+    /// Returns a tensor with new dimensions of the same volume, sharing the buffer if possible.
+    ///
+    /// Contiguous tensors are reinterpreted without a copy, as in [`Self::view`]; strided
+    /// views are first copied into a contiguous buffer. One dimension may be [`INFER`], which
+    /// takes the size that keeps the volume.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidShape`] if the volume differs or the inferred dimension is
+    ///   ambiguous.
+    /// - [`Error::Device`] if a copy is needed and buffer allocation fails.
+    pub fn reshape(&self, dimensions: &[usize]) -> Result<Self, Error> {
+        if self.layout.is_contiguous() {
+            return self.view(dimensions);
+        }
+
+        self.materialize()?.view(dimensions)
+    }
+
+    /// Reinterprets the buffer with new dimensions of the same volume, without a copy.
+    ///
+    /// The view shares the buffer, so in-place writes through either tensor are visible in
+    /// both. One dimension may be [`INFER`], as in [`Self::reshape`].
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidShape`] if the volume differs, the inferred dimension is
+    ///   ambiguous, or `self` is not contiguous.
+    pub fn view(&self, dimensions: &[usize]) -> Result<Self, Error> {
+        if !self.layout.is_contiguous() {
+            return Err(TensorError::InvalidShape(format!(
+                "cannot view non-contiguous tensor {:?} as {dimensions:?}",
+                self.dimensions()
+            ))
+            .into());
+        }
+
+        let dimensions = &self.infer_dimensions(dimensions)?;
+        let layout = Layout::from_dimensions(dimensions).with_offset(self.layout.offset());
+        if layout.size() != self.layout.size() {
+            return Err(TensorError::InvalidShape(format!(
+                "cannot reshape {:?} to {dimensions:?}",
+                self.dimensions()
+            ))
+            .into());
+        }
+
+        Ok(self.with_layout(layout))
+    }
+
+    /// Swaps axes `d0` and `d1`, sharing the buffer.
+    ///
+    /// The result is a strided view; see [`Self::permute`].
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidShape`] if `d0` or `d1` is out of bounds.
+    pub fn transpose(&self, d0: usize, d1: usize) -> Result<Self, Error> {
+        let rank = self.layout.dimensions().len();
+        if let Some(axis) = [d0, d1].into_iter().find(|&axis| axis >= rank) {
+            return Err(TensorError::InvalidShape(format!(
+                "axis {axis} out of bounds for tensor with rank {rank}"
+            ))
+            .into());
+        }
+
+        let mut axes: Vec<usize> = (0..rank).collect();
+        axes.swap(d0, d1);
+        self.permute(&axes)
+    }
+
+    /// Reorders axes so that axis `i` of the result is axis `axes[i]` of `self`, sharing the
+    /// buffer.
+    ///
+    /// Only the strides are rewritten. Element-wise and broadcasting operations read the
+    /// view in place, and [`Self::matmul`] folds a swap of the last two axes into its
+    /// transpose flags; other operations copy the view into a contiguous buffer first.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidShape`] if `axes` is not a permutation of `0..rank`.
+    pub fn permute(&self, axes: &[usize]) -> Result<Self, Error> {
+        let rank = self.layout.dimensions().len();
+
+        let mut seen = vec![false; rank];
+        let valid = axes.len() == rank
+            && axes
+                .iter()
+                .all(|&axis| axis < rank && !core::mem::replace(&mut seen[axis], true));
+        if !valid {
+            return Err(TensorError::InvalidShape(format!(
+                "axes {axes:?} are not a permutation of 0..{rank}"
+            ))
+            .into());
+        }
+
+        Ok(self.with_layout(self.layout.permute(axes)))
+    }
+
+    /// Removes every size-1 axis, sharing the buffer.
+    #[must_use]
+    pub fn squeeze(&self) -> Self {
+        self.with_layout(self.layout.squeeze(None))
+    }
+
+    /// Removes the size-1 `axis`, sharing the buffer.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidShape`] if `axis` is out of bounds or its size is not 1.
+    pub fn squeeze_dim(&self, axis: usize) -> Result<Self, Error> {
+        match self.dimensions().get(axis) {
+            Some(1) => Ok(self.with_layout(self.layout.squeeze(Some(axis)))),
+            Some(size) => Err(TensorError::InvalidShape(format!(
+                "cannot squeeze axis {axis} of size {size}"
+            ))
+            .into()),
+            None => Err(TensorError::InvalidShape(format!(
+                "axis {axis} out of bounds for tensor with rank {}",
+                self.dimensions().len()
+            ))
+            .into()),
+        }
+    }
+
+    /// Inserts a size-1 axis at position `axis`, sharing the buffer.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidShape`] if `axis` exceeds the rank.
+    pub fn unsqueeze(&self, axis: usize) -> Result<Self, Error> {
+        let rank = self.dimensions().len();
+        if axis > rank {
+            return Err(TensorError::InvalidShape(format!(
+                "axis {axis} out of bounds for inserting into tensor with rank {rank}"
+            ))
+            .into());
+        }
+
+        Ok(self.with_layout(self.layout.unsqueeze(axis)))
+    }
+
+    /// Restricts `axis` to `len` entries starting at `start`, sharing the buffer.
+    ///
+    /// Only the offset and dimensions of the layout change, so taking a mini-batch out of a
+    /// large resident tensor costs nothing until an operation reads it. Rows taken along the
+    /// first axis stay contiguous and can be written in place with [`Self::write_slice`].
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidShape`] if `axis` is out of bounds or `start + len` exceeds the
+    ///   size of `axis`.
+    pub fn narrow(&self, axis: usize, start: usize, len: usize) -> Result<Self, Error> {
+        let dimensions = self.dimensions();
+        let Some(&size) = dimensions.get(axis) else {
+            return Err(TensorError::InvalidShape(format!(
+                "axis {axis} out of bounds for tensor with rank {}",
+                dimensions.len()
+            ))
+            .into());
+        };
+
+        if start.checked_add(len).is_none_or(|end| end > size) {
+            return Err(TensorError::InvalidShape(format!(
+                "range {start}..{} out of bounds for axis {axis} of size {size}",
+                start.saturating_add(len)
+            ))
+            .into());
+        }
+
+        Ok(self.with_layout(self.layout.narrow(axis, start, len)))
+    }
+
+    /// Main diagonal of the last two axes, sharing the buffer.
+    ///
+    /// `[..., n, m]` becomes `[..., min(n, m)]`, so a batch of matrices gives a batch of
+    /// diagonals. The result is a strided view; see [`Self::permute`].
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidShape`] if `self` has rank less than 2.
+    pub fn diag(&self) -> Result<Self, Error> {
+        if self.dimensions().len() < 2 {
+            return Err(TensorError::InvalidShape(format!(
+                "diag requires rank >= 2, got dimensions {:?}",
+                self.dimensions()
+            ))
+            .into());
+        }
+
+        Ok(self.with_layout(self.layout.diagonal()))
+    }
+
+    /// Square matrix with the elements of `self`, flattened, on its main diagonal and zeros
+    /// elsewhere.
+    ///
+    /// A tensor of `n` elements gives an `[n, n]` matrix.
+    ///
+    /// # Errors
+    ///
+    /// - [`Error::Device`] if buffer allocation fails.
+    pub fn diagflat(&self) -> Result<Self, Error> {
+        let n = self.layout.size();
+        let layout = Layout::from_dimensions(&[n, n]);
+        let buffer = self.ctx.create_buffer(layout.size())?;
+
+        ops::diagflat(&self.ctx, &self.materialize()?.buffer, &buffer, n);
+
+        Ok(Self {
+            buffer,
+            layout,
+            ctx: self.ctx.clone(),
+        })
+    }
+
+    /// Splits `axis` into consecutive parts of the given `sizes`, sharing the buffer.
+    ///
+    /// Each part is a [`Self::narrow`] view, so splitting fused projections into attention
+    /// heads copies nothing.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidShape`] if `axis` is out of bounds or the sizes do not add up
+    ///   to the size of `axis`.
+    pub fn split(&self, sizes: &[usize], axis: usize) -> Result<Vec<Self>, Error> {
+        let dimensions = self.dimensions();
+        let Some(&size) = dimensions.get(axis) else {
+            return Err(TensorError::InvalidShape(format!(
+                "axis {axis} out of bounds for tensor with rank {}",
+                dimensions.len()
+            ))
+            .into());
+        };
+
+        let total = sizes
+            .iter()
+            .try_fold(0usize, |acc, &len| acc.checked_add(len));
+        if total != Some(size) {
+            return Err(TensorError::InvalidShape(format!(
+                "split sizes {sizes:?} do not partition axis {axis} of size {size}"
+            ))
+            .into());
+        }
+
+        let mut start = 0;
+        sizes
+            .iter()
+            .map(|&len| {
+                let part = self.narrow(axis, start, len);
+                start += len;
+                part
+            })
+            .collect()
+    }
+
+    /// Splits `axis` into at most `chunks` parts of equal size, sharing the buffer.
+    ///
+    /// Every part has `ceil(size / chunks)` entries except the last, which takes the
+    /// remainder, so fewer than `chunks` parts are returned when the size does not allow
+    /// that many. An axis of size zero gives `chunks` empty parts.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidShape`] if `axis` is out of bounds.
+    /// - [`TensorError::InvalidArgument`] if `chunks` is zero.
+    pub fn chunk(&self, chunks: usize, axis: usize) -> Result<Vec<Self>, Error> {
+        if chunks == 0 {
+            return Err(TensorError::InvalidArgument("chunks must be positive".into()).into());
+        }
+
+        let dimensions = self.dimensions();
+        let Some(&size) = dimensions.get(axis) else {
+            return Err(TensorError::InvalidShape(format!(
+                "axis {axis} out of bounds for tensor with rank {}",
+                dimensions.len()
+            ))
+            .into());
+        };
+
+        if size == 0 {
+            return self.split(&vec![0; chunks], axis);
+        }
+
+        let len = size.div_ceil(chunks);
+        let sizes: Vec<usize> = (0..size)
+            .step_by(len)
+            .map(|start| len.min(size - start))
+            .collect();
+        self.split(&sizes, axis)
+    }
+
+    /// Joins same-shape `tensors` along a new dimension inserted at `axis`.
+    ///
+    /// The result has the dimensions of the inputs with `tensors.len()` inserted at `axis`, so
+    /// stacking `k` samples of shape `[c, h, w]` at axis 0 gives a `[k, c, h, w]` batch. Each
+    /// input is copied on the GPU.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidArgument`] if `tensors` is empty.
+    /// - [`TensorError::InvalidShape`] if the inputs have different dimensions or `axis`
+    ///   exceeds their rank.
+    /// - [`Error::Device`] if buffer allocation fails.
+    pub fn stack(tensors: &[&Self], axis: usize) -> Result<Self, Error> {
+        let Some(first) = tensors.first() else {
+            return Err(TensorError::InvalidArgument("no tensors to stack".into()).into());
+        };
+
+        let dimensions = first.dimensions();
+        let rank = dimensions.len();
+        if axis > rank {
+            return Err(TensorError::InvalidShape(format!(
+                "axis {axis} out of bounds for stacking tensors with rank {rank}"
+            ))
+            .into());
+        }
+
+        if let Some(other) = tensors.iter().find(|t| t.dimensions() != dimensions) {
+            return Err(TensorError::InvalidShape(format!(
+                "stacked dimensions {:?} do not match {dimensions:?}",
+                other.dimensions()
+            ))
+            .into());
+        }
+
+        let mut stacked = Vec::with_capacity(rank + 1);
+        stacked.push(tensors.len());
+        stacked.extend_from_slice(dimensions);
+
+        let layout = Layout::from_dimensions(&stacked);
+        let buffer = first.ctx.create_buffer(layout.size())?;
+        let len = first.layout.size();
+        for (i, tensor) in tensors.iter().enumerate() {
+            ops::copy_into(&first.ctx, &tensor.materialize()?.buffer, &buffer, i * len);
+        }
+
+        let stacked = Self {
+            buffer,
+            layout,
+            ctx: first.ctx.clone(),
+        };
+        if axis == 0 {
+            return Ok(stacked);
+        }
+
+        let mut order: Vec<usize> = (1..=rank).collect();
+        order.insert(axis, 0);
+        stacked
+            .with_layout(stacked.layout.permute(&order))
+            .materialize()
+    }
+
+    /// Tiles the data `repeats[i]` times along each axis into a new contiguous tensor.
+    ///
+    /// Axis `i` of the result has size `dimensions[i] * repeats[i]`. With more repeats than
+    /// axes, leading size-1 axes are added first, so `[1, 2]` repeated `[2, 1, 3]` gives
+    /// `[2, 1, 6]`. A zero repeat gives an empty axis. Unlike [`Self::broadcast_to`] the
+    /// repeats are written out, for operations that need dense operands.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidShape`] if `repeats` has fewer entries than the rank, or a
+    ///   repeated size overflows.
+    /// - [`Error::Device`] if buffer allocation fails.
+    pub fn repeat(&self, repeats: &[usize]) -> Result<Self, Error> {
+        let rank = self.dimensions().len();
+        if repeats.len() < rank {
+            return Err(TensorError::InvalidShape(format!(
+                "repeats {repeats:?} invalid for tensor with rank {rank}"
+            ))
+            .into());
+        }
+
+        let mut x = self.with_layout(self.layout.clone());
+        for _ in rank..repeats.len() {
+            x = x.unsqueeze(0)?;
+        }
+
+        let mut tiled = Vec::with_capacity(2 * repeats.len());
+        let mut repeated = Vec::with_capacity(repeats.len());
+        for (axis, (&size, &count)) in x.dimensions().iter().zip(repeats).enumerate() {
+            let len = size.checked_mul(count).ok_or_else(|| {
+                TensorError::InvalidShape(format!(
+                    "repeating axis {axis} of size {size} {count} times overflows"
+                ))
+            })?;
+            tiled.extend([count, size]);
+            repeated.push(len);
+        }
+
+        for axis in 0..repeats.len() {
+            x = x.unsqueeze(2 * axis)?;
+        }
+
+        x.broadcast_to(&tiled)?.reshape(&repeated)
+    }
+
+    /// Broadcasts to `dimensions` as a view, sharing the buffer.
+    ///
+    /// Follows the rules of the broadcasting operations: trailing dimensions are aligned, and
+    /// size-1 or missing dimensions are repeated with stride 0, so no data is copied.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidShape`] if `self` does not broadcast to `dimensions`, or their
+    ///   volume overflows.
+    pub fn broadcast_to(&self, dimensions: &[usize]) -> Result<Self, Error> {
+        let layout = self.layout.broadcast_to(dimensions).ok_or_else(|| {
+            TensorError::InvalidShape(format!(
+                "cannot broadcast {:?} to {dimensions:?}",
+                self.dimensions()
+            ))
+        })?;
+
+        Ok(self.with_layout(layout))
+    }
+
+    /// Asynchronously copies tensor data from GPU to CPU.
+    ///
+    /// # Errors
+    ///
+    /// - [`Error::Device`] if operation fails.
+    pub async fn to_vec_async(&self) -> Result<Vec<T>, Error> {
+        let x = self.materialize()?;
+        self.ctx.read_buffer_async(&x.buffer).await
+    }
+
+    /// Copies tensor data from GPU to CPU.
+    ///
+    /// # Errors
+    ///
+    /// - [`Error::Device`] if operation fails.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn to_vec(&self) -> Result<Vec<T>, Error> {
+        let x = self.materialize()?;
+        self.ctx.read_buffer(&x.buffer)
+    }
+
+    /// Asynchronously reads the single element of a one-element tensor, such as a scalar or
+    /// the result of reducing over every axis.
+    ///
+    /// Only that element is transferred, without a copy kernel or a host `Vec`.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidShape`] if `self` does not have exactly one element.
+    /// - [`Error::Device`] if operation fails.
+    pub async fn item_async(&self) -> Result<T, Error> {
+        self.check_item()?;
+        self.ctx
+            .read_element_async(&self.buffer, self.layout.offset())
+            .await
+    }
+
+    /// Reads the single element of a one-element tensor, such as a scalar or the result of
+    /// reducing over every axis.
+    ///
+    /// Only that element is transferred, without a copy kernel or a host `Vec`.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidShape`] if `self` does not have exactly one element.
+    /// - [`Error::Device`] if operation fails.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn item(&self) -> Result<T, Error> {
+        self.check_item()?;
+        self.ctx.read_element(&self.buffer, self.layout.offset())
+    }
+
+    /// Asynchronously reads the element at `index`, given as one coordinate per axis.
+    ///
+    /// Only that element is transferred, so reading one logit of a large output does not copy
+    /// the whole tensor.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidShape`] if `index` does not have one coordinate per axis or a
+    ///   coordinate is out of bounds.
+    /// - [`Error::Device`] if operation fails.
+    pub async fn get_async(&self, index: &[usize]) -> Result<T, Error> {
+        let offset = self.element_offset(index)?;
+        self.ctx.read_element_async(&self.buffer, offset).await
+    }
+
+    /// Reads the element at `index`, given as one coordinate per axis.
+    ///
+    /// Only that element is transferred, so reading one logit of a large output does not copy
+    /// the whole tensor.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidShape`] if `index` does not have one coordinate per axis or a
+    ///   coordinate is out of bounds.
+    /// - [`Error::Device`] if operation fails.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn get(&self, index: &[usize]) -> Result<T, Error> {
+        let offset = self.element_offset(index)?;
+        self.ctx.read_element(&self.buffer, offset)
+    }
+
+    /// Asynchronously reads the region starting at `offsets` with size `extents` along each
+    /// axis, in row-major order.
+    ///
+    /// A region that is contiguous in memory is transferred directly; otherwise only the
+    /// region is gathered on the GPU before the transfer.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidShape`] if `offsets` or `extents` does not have one entry per
+    ///   axis, or the region exceeds the bounds of `self`.
+    /// - [`Error::Device`] if operation fails.
+    pub async fn read_region_async(
+        &self,
+        offsets: &[usize],
+        extents: &[usize],
+    ) -> Result<Vec<T>, Error> {
+        let region = self.region(offsets, extents)?;
+        if region.layout.is_contiguous() {
+            let (offset, len) = (region.layout.offset(), region.layout.size());
+            return self.ctx.read_slice_async(&self.buffer, offset, len).await;
+        }
+        region.to_vec_async().await
+    }
+
+    /// Reads the region starting at `offsets` with size `extents` along each axis, in
+    /// row-major order.
+    ///
+    /// A region that is contiguous in memory is transferred directly; otherwise only the
+    /// region is gathered on the GPU before the transfer.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidShape`] if `offsets` or `extents` does not have one entry per
+    ///   axis, or the region exceeds the bounds of `self`.
+    /// - [`Error::Device`] if operation fails.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn read_region(&self, offsets: &[usize], extents: &[usize]) -> Result<Vec<T>, Error> {
+        let region = self.region(offsets, extents)?;
+        if region.layout.is_contiguous() {
+            let (offset, len) = (region.layout.offset(), region.layout.size());
+            return self.ctx.read_slice(&self.buffer, offset, len);
+        }
+        region.to_vec()
+    }
+
+    /// Copies `src` into `self` where `mask` is true, in place.
+    ///
+    /// Elements where `mask` is false keep their value. `src` and `mask` broadcast to the shape
+    /// of `self`, and are copied first if they share the buffer of `self`.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidShape`] if `self` is a strided view, or `src` or `mask` does not
+    ///   broadcast to the shape of `self`.
+    /// - [`Error::Device`] if a strided operand needs a copy and buffer allocation fails.
+    pub fn masked_copy_(&mut self, src: &Self, mask: &Tensor<bool>) -> Result<(), Error> {
+        self.check_writable()?;
+        let src = self.unaliased(src)?.strided()?;
+        let mask = self.unaliased(mask)?.strided()?;
+        let dimensions = self.layout.dimensions();
+
+        let strides = Layout::broadcast(&[&self.layout, &src.layout, &mask.layout])
+            .filter(|(out_dims, _)| **out_dims == *dimensions)
+            .map(|(_, strides)| strides)
+            .ok_or_else(|| {
+                TensorError::InvalidShape(format!(
+                    "dimensions {:?} and {:?} do not broadcast to {dimensions:?}",
+                    src.dimensions(),
+                    mask.dimensions()
+                ))
+            })?;
+
+        ops::masked_copy(
+            &self.ctx,
+            &src.buffer,
+            &mask.buffer,
+            &self.buffer,
+            &strides[1],
+            &strides[2],
+            self.layout.strides(),
+        );
+
+        Ok(())
+    }
+
+    /// Writes `values` into the rows selected by `indices`, in place.
+    ///
+    /// Rows are taken along the first axis: `self[indices[i]] = values[i]`. For rank 1 tensors
+    /// rows are single elements. `values` has dimensions `[n, ...]` matching the trailing
+    /// dimensions of `self`, where `n` is the number of indices. Indices out of bounds are
+    /// skipped; with duplicate indices, which write wins is unspecified. Operands sharing the
+    /// buffer of `self` are copied first.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidShape`] if `self` is a scalar or a strided view, `indices` is
+    ///   not rank 1, or `values` dimensions don't match.
+    /// - [`Error::Device`] if a strided operand needs a copy and buffer allocation fails.
+    pub fn index_put_(&mut self, indices: &Tensor<u32>, values: &Self) -> Result<(), Error> {
+        self.check_writable()?;
+        let dimensions = self.layout.dimensions();
+
+        let Some((&rows, row_dims)) = dimensions.split_first() else {
+            return Err(TensorError::InvalidShape("index_put_ requires rank >= 1".into()).into());
+        };
+
+        let [n] = *indices.dimensions() else {
+            return Err(TensorError::InvalidShape(format!(
+                "indices must be rank 1, got {:?}",
+                indices.dimensions()
+            ))
+            .into());
+        };
+
+        let mut expected = vec![n];
+        expected.extend(row_dims);
+        if values.dimensions() != expected {
+            return Err(TensorError::InvalidShape(format!(
+                "values dimensions {:?} don't match expected {expected:?}",
+                values.dimensions()
+            ))
+            .into());
+        }
+
+        let row_len = row_dims.iter().product();
+        let indices = self.unaliased(indices)?.materialize()?;
+        let values = self.unaliased(values)?.materialize()?;
+        ops::index_put(
+            &self.ctx,
+            &indices.buffer,
+            &values.buffer,
+            &self.buffer,
+            rows,
+            row_len,
+        );
+
+        Ok(())
+    }
+
+    /// Gathers values along `axis` at `indices`.
+    ///
+    /// `indices` has the rank of `self` and gives, for every output position, the `axis`
+    /// coordinate to read: `y[i][j][k] = self[i][indices[i][j][k]][k]` for `axis = 1`. The
+    /// result has the dimensions of `indices`, whose other axes may be shorter than those
+    /// of `self`. Out-of-bounds indices produce zero.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidShape`] if `axis` is out of bounds, the ranks differ, or an
+    ///   axis of `indices` other than `axis` exceeds that of `self`.
+    /// - [`Error::Device`] if buffer allocation fails.
+    pub fn gather(&self, axis: usize, indices: &Tensor<u32>) -> Result<Self, Error> {
+        let dimensions = self.dimensions();
+        let rank = dimensions.len();
+        if axis >= rank {
+            return Err(TensorError::InvalidShape(format!(
+                "axis {axis} out of bounds for tensor with rank {rank}"
+            ))
+            .into());
+        }
+
+        let index_dims = indices.dimensions();
+        let fits = index_dims.len() == rank
+            && index_dims
+                .iter()
+                .zip(dimensions)
+                .enumerate()
+                .all(|(i, (&n, &size))| i == axis || n <= size);
+        if !fits {
+            return Err(TensorError::InvalidShape(format!(
+                "indices dimensions {index_dims:?} do not fit {dimensions:?} along axis {axis}"
+            ))
+            .into());
+        }
+
+        let layout = Layout::from_dimensions(index_dims);
+        let buffer = self.ctx.create_buffer(layout.size())?;
+
+        ops::gather(
+            &self.ctx,
+            &self.buffer,
+            &indices.materialize()?.buffer,
+            &buffer,
+            self.layout.strides(),
+            layout.strides(),
+            self.layout.offset(),
+            axis,
+            dimensions[axis],
+        );
+
+        Ok(Self {
+            buffer,
+            layout,
+            ctx: self.ctx.clone(),
+        })
+    }
+
+    /// Gathers the rows selected by the first `count[0]` entries of `indices`.
+    ///
+    /// Rows are taken along the first axis: `y[i] = self[indices[i]]`. The result has
+    /// dimensions `[n, ...]` matching the trailing dimensions of `self`, where `n` is the
+    /// number of indices; rows past the count and rows for out-of-bounds indices are zero.
+    ///
+    /// `count` stays on the GPU and sizes the dispatch indirectly, so the output of a
+    /// compaction such as [`Tensor::nms`] can be consumed without reading the count back.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidShape`] if `self` is a scalar, `indices` is not rank 1, or
+    ///   `count` is not `[1]`.
+    /// - [`Error::Device`] if buffer allocation fails.
+    pub fn take_rows(&self, indices: &Tensor<u32>, count: &Tensor<u32>) -> Result<Self, Error> {
+        let Some((&rows, row_dims)) = self.dimensions().split_first() else {
+            return Err(TensorError::InvalidShape("take_rows requires rank >= 1".into()).into());
+        };
+
+        let [n] = *indices.dimensions() else {
+            return Err(TensorError::InvalidShape(format!(
+                "indices must be rank 1, got {:?}",
+                indices.dimensions()
+            ))
+            .into());
+        };
+
+        if count.dimensions() != [1] {
+            return Err(TensorError::InvalidShape(format!(
+                "count must have dimensions [1], got {:?}",
+                count.dimensions()
+            ))
+            .into());
+        }
+
+        let mut dimensions = vec![n];
+        dimensions.extend(row_dims);
+        let layout = Layout::from_dimensions(&dimensions);
+        let buffer = self.ctx.create_buffer(layout.size())?;
+        ops::constant(&self.ctx, &buffer, T::zeroed());
+
+        ops::take_rows(
+            &self.ctx,
+            &self.materialize()?.buffer,
+            &indices.materialize()?.buffer,
+            &count.materialize()?.buffer,
+            &buffer,
+            rows,
+            row_dims.iter().product(),
+        );
+
+        Ok(Self {
+            buffer,
+            layout,
+            ctx: self.ctx.clone(),
+        })
+    }
+
+    /// Coordinates of the non-zero elements of `self`, or the true elements of a mask, in
+    /// row-major order.
+    ///
+    /// Returns the coordinates `[n, rank]`, one row per element of `self` and padded with
+    /// `u32::MAX` past the count, and their count `[1]`; both stay on the GPU. For a rank-1
+    /// mask, the reshaped `[n]` coordinates and the count select the masked rows of another
+    /// tensor through [`Tensor::take_rows`] without reading the count back.
+    ///
+    /// Runs as a stream compaction: one pass counts the elements of each block, a prefix
+    /// scan turns the counts into output offsets, and a second pass writes the coordinates
+    /// of each block from its offset.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidShape`] if `self` is a scalar.
+    /// - [`Error::Device`] if buffer allocation fails.
+    pub fn nonzero(&self) -> Result<(Tensor<u32>, Tensor<u32>), Error> {
+        let rank = self.dimensions().len();
+        if rank == 0 {
+            return Err(TensorError::InvalidShape("nonzero requires rank >= 1".into()).into());
+        }
+
+        let x = self.materialize()?;
+        let n = x.buffer.len();
+        let layout = Layout::from_dimensions(&[n, rank]);
+        let indices = self.ctx.create_buffer(layout.size())?;
+        ops::constant(&self.ctx, &indices, u32::MAX);
+        let count = self.ctx.create_buffer(1)?;
+        ops::constant(&self.ctx, &count, 0);
+
+        let blocks = n.div_ceil(compact::BLOCK);
+        let counts = self.ctx.create_buffer(blocks)?;
+        let offsets = self.ctx.create_buffer(blocks)?;
+        ops::count_nonzero(&self.ctx, &x.buffer, &counts);
+        ops::prefix_scan(&self.ctx, &counts, &offsets, blocks, 1, Scan::Sum, true);
+        ops::write_nonzero(
+            &self.ctx,
+            &x.buffer,
+            &offsets,
+            &indices,
+            &count,
+            x.layout.strides(),
+        );
+
+        Ok((
+            Tensor {
+                buffer: indices,
+                layout,
+                ctx: self.ctx.clone(),
+            },
+            Tensor {
+                buffer: count,
+                layout: Layout::from_dimensions(&[1]),
+                ctx: self.ctx.clone(),
+            },
+        ))
+    }
+
+    /// Splits `axis` into `shards` equal contiguous parts.
+    ///
+    /// Useful for separating fused weights, e.g. a `[3 * d, k]` QKV projection into three
+    /// `[d, k]` tensors.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidShape`] if `axis` is out of bounds or its size is not divisible
+    ///   by `shards`.
+    /// - [`Error::Device`] if buffer allocation fails.
+    pub fn shard(&self, axis: usize, shards: usize) -> Result<Vec<Self>, Error> {
+        let len = self.axis_parts(axis, shards)?;
+        let stride = self.layout.strides()[axis];
+
+        let mut dimensions = self.layout.dimensions().to_vec();
+        dimensions[axis] = len;
+
+        (0..shards)
+            .map(|i| {
+                self.strided_copy(
+                    &dimensions,
+                    self.layout.strides(),
+                    i * len * stride,
+                    &dimensions,
+                )
+            })
+            .collect()
+    }
+
+    /// Reorders `axis` from an interleaved to a sharded layout.
+    ///
+    /// `axis` is viewed as `[groups, shards, block]` and rearranged to `[shards, groups, block]`,
+    /// so the blocks of each shard become contiguous. The shape is unchanged.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidShape`] if `axis` is out of bounds or its size is not divisible
+    ///   by `shards * block`.
+    /// - [`Error::Device`] if buffer allocation fails.
+    pub fn interleaved_to_sharded(
+        &self,
+        axis: usize,
+        shards: usize,
+        block: usize,
+    ) -> Result<Self, Error> {
+        self.regroup(axis, shards, block, true)
+    }
+
+    /// Reorders `axis` from a sharded to an interleaved layout.
+    ///
+    /// Inverse of [`Tensor::interleaved_to_sharded`]: `axis` is viewed as
+    /// `[shards, groups, block]` and rearranged to `[groups, shards, block]`.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidShape`] if `axis` is out of bounds or its size is not divisible
+    ///   by `shards * block`.
+    /// - [`Error::Device`] if buffer allocation fails.
+    pub fn sharded_to_interleaved(
+        &self,
+        axis: usize,
+        shards: usize,
+        block: usize,
+    ) -> Result<Self, Error> {
+        self.regroup(axis, shards, block, false)
+    }
+
+    /// Swaps the group and shard sub-axes of `axis` viewed as blocks.
+    fn regroup(
+        &self,
+        axis: usize,
+        shards: usize,
+        block: usize,
+        to_sharded: bool,
+    ) -> Result<Self, Error> {
+        if block == 0 {
+            return Err(TensorError::InvalidShape("block must be non-zero".into()).into());
+        }
+
+        let groups = self.axis_parts(axis, shards.saturating_mul(block))?;
+        let dimensions = self.layout.dimensions();
+        let strides = self.layout.strides();
+        let stride = strides[axis];
+
+        // Source strides of the (groups, shards) sub-axes in the interleaved and sharded orders.
+        let (outer, inner) = if to_sharded {
+            ((shards, block * stride), (groups, shards * block * stride))
+        } else {
+            ((groups, block * stride), (shards, groups * block * stride))
+        };
+
+        let mut view_dims = dimensions[..axis].to_vec();
+        view_dims.extend([outer.0, inner.0, block]);
+        view_dims.extend(&dimensions[axis + 1..]);
+
+        let mut view_strides = strides[..axis].to_vec();
+        view_strides.extend([outer.1, inner.1, stride]);
+        view_strides.extend(&strides[axis + 1..]);
+
+        self.strided_copy(&view_dims, &view_strides, 0, dimensions)
+    }
+
+    /// Validates `axis` and returns its size divided by `parts`.
+    fn axis_parts(&self, axis: usize, parts: usize) -> Result<usize, Error> {
+        let dimensions = self.layout.dimensions();
+        let rank = dimensions.len();
+
+        if axis >= rank {
+            return Err(TensorError::InvalidShape(format!(
+                "axis {axis} out of bounds for tensor with rank {rank}"
+            ))
+            .into());
+        }
+
+        let size = dimensions[axis];
+        if parts == 0 || !size.is_multiple_of(parts) {
+            return Err(TensorError::InvalidShape(format!(
+                "axis {axis} of size {size} is not divisible into {parts} parts"
+            ))
+            .into());
+        }
+
+        Ok(size / parts)
+    }
+
+    /// Copies a strided view of the underlying buffer into a new contiguous tensor.
+    ///
+    /// The view is described by `view_dims`, `view_strides` and an element `offset` relative to
+    /// this tensor's layout; the result is laid out contiguously with dimensions `shape`.
+    fn strided_copy(
+        &self,
+        view_dims: &[usize],
+        view_strides: &[usize],
+        offset: usize,
+        shape: &[usize],
+    ) -> Result<Self, Error> {
+        let layout = Layout::from_dimensions(shape);
+        let view = Layout::from_dimensions(view_dims);
+        let buffer = self.ctx.create_buffer(layout.size())?;
+
+        ops::strided_copy(
+            &self.ctx,
+            &self.buffer,
+            &buffer,
+            view_strides,
+            view.strides(),
+            self.layout.offset() + offset,
+        );
+
+        Ok(Self {
+            buffer,
+            layout,
+            ctx: self.ctx.clone(),
+        })
+    }
+
+    /// Returns a tensor sharing the buffer of `self` with a different layout.
+    fn with_layout(&self, layout: Layout) -> Self {
+        Self {
+            buffer: self.buffer.clone(),
+            layout,
+            ctx: self.ctx.clone(),
+        }
+    }
+
+    /// Returns `operand` as is, or a copy of it if it shares the buffer of `self`, for in-place
+    /// kernels that cannot bind one buffer as both input and output.
+    fn unaliased<U: Element>(&self, operand: &Tensor<U>) -> Result<Tensor<U>, Error> {
+        if operand.buffer.inner() == self.buffer.inner() {
+            return operand.copy();
+        }
+        Ok(operand.with_layout(operand.layout.clone()))
+    }
+
+    /// Returns `self` if it is contiguous and spans its whole buffer, otherwise a contiguous
+    /// copy, for kernels that index elements by their flat position.
+    fn materialize(&self) -> Result<Self, Error> {
+        if self.layout.is_contiguous() && self.spans_buffer() {
+            return Ok(self.with_layout(self.layout.clone()));
+        }
+
+        let shape = self.layout.dimensions();
+        self.strided_copy(shape, self.layout.strides(), 0, shape)
+    }
+
+    /// Returns `self` if its layout starts at offset 0, otherwise a contiguous copy, for
+    /// kernels that take input strides.
+    fn strided(&self) -> Result<Self, Error> {
+        if self.layout.offset() == 0 {
+            return Ok(self.with_layout(self.layout.clone()));
+        }
+        self.materialize()
+    }
+
+    /// Returns `self` if it spans its buffer in any axis order, otherwise a contiguous copy,
+    /// for element-wise kernels whose output keeps the input layout.
+    fn dense(&self) -> Result<Self, Error> {
+        if self.spans_buffer() {
+            return Ok(self.with_layout(self.layout.clone()));
         }
+        self.materialize()
+    }
 
-        let layout = Layout::from_dimensions(shape)?;
-        let volume = layout.size();
+    /// Replaces an [`INFER`] entry of `dimensions` by the size that keeps the volume of `self`.
+    fn infer_dimensions(&self, dimensions: &[usize]) -> Result<Vec<usize>, Error> {
+        let mut inferred = dimensions.to_vec();
+        let Some(axis) = dimensions.iter().position(|&size| size == INFER) else {
+            return Ok(inferred);
+        };
 
-        let buffer = match value.len() {
-            1 => {
-                let buffer = ctx.create_buffer(volume)?;
-                let uniform = ctx.create_uniform_buffer(&value[0].to_native());
-                ops::constant(ctx, &buffer, &uniform);
-                buffer
-            }
-            n if n == volume => ctx.create_buffer_from_slice(value)?,
-            n => {
-                return Err(TensorError::InvalidShape(format!(
-                    "value length {n} must be 1 or equal to shape volume {volume}"
+        let size = self.layout.size();
+        let known = dimensions
+            .iter()
+            .enumerate()
+            .filter(|&(i, _)| i != axis)
+            .try_fold(1usize, |acc, (_, &size)| {
+                (size != INFER).then(|| acc.checked_mul(size)).flatten()
+            })
+            .filter(|&known| known != 0 && size.is_multiple_of(known))
+            .ok_or_else(|| {
+                TensorError::InvalidShape(format!(
+                    "cannot infer a dimension of {dimensions:?} for {:?}",
+                    self.dimensions()
                 ))
-                .into());
-            }
-        };
+            })?;
 
-        Ok(Self {
-            buffer,
-            layout,
-            ctx: ctx.clone(),
-        })
+        inferred[axis] = size / known;
+        Ok(inferred)
     }
 
-    /// Creates a tensor from shape and data slice.
-    ///
-    /// # Errors
-    ///
-    /// - [`TensorError::InvalidShape`] if any dimension is zero or size doesn't match data length.
-    /// - [`Error::Device`] if operation fails.
-    pub fn from_shape_slice(ctx: &Context, shape: &[usize], data: &[T]) -> Result<Self, Error> {
-        Self::constant(ctx, shape, data)
+    /// Returns true if every buffer element belongs to the view exactly once.
+    fn spans_buffer(&self) -> bool {
+        self.layout.is_dense() && self.buffer.len() == self.layout.size()
     }
 
-    /// Creates a 1D tensor from a data slice.
+    /// Returns a contiguous matmul operand and its transpose flag.
     ///
-    /// # Errors
-    ///
-    /// - [`TensorError::InvalidShape`] if data is empty.
-    /// - [`Error::Device`] if operation fails.
-    pub fn from_slice(ctx: &Context, data: &[T]) -> Result<Self, Error> {
-        Self::constant(ctx, &[data.len()], data)
+    /// A view whose last two axes are swapped relative to a contiguous layout is read as the
+    /// transpose of its buffer rather than copied.
+    fn matmul_operand(&self, transpose: bool) -> Result<(Self, bool), Error> {
+        let rank = self.layout.dimensions().len();
+        if rank >= 2 && !self.layout.is_contiguous() {
+            let mut axes: Vec<usize> = (0..rank).collect();
+            axes.swap(rank - 2, rank - 1);
+
+            let swapped = self.with_layout(self.layout.permute(&axes));
+            if swapped.layout.is_contiguous() && swapped.spans_buffer() {
+                return Ok((swapped, !transpose));
+            }
+        }
+
+        Ok((self.materialize()?, transpose))
     }
 
-    /// Creates a copy of this tensor.
-    ///
-    /// # Errors
-    ///
-    /// - [`Error::Device`] if operation fails.
-    pub fn copy(&self) -> Result<Self, Error> {
-        let buffer = self.ctx.create_buffer(self.buffer.len())?;
-        ops::copy(&self.ctx, &self.buffer, &buffer);
+    /// Returns an error if `self` cannot be written in place through its flat positions.
+    fn check_writable(&self) -> Result<(), Error> {
+        if self.layout.is_contiguous() && self.spans_buffer() {
+            return Ok(());
+        }
 
-        Ok(Self {
-            buffer,
-            layout: self.layout.clone(),
-            ctx: self.ctx.clone(),
-        })
+        Err(TensorError::InvalidShape(format!(
+            "in-place write requires a contiguous tensor, got strides {:?}",
+            self.layout.strides()
+        ))
+        .into())
     }
 
-    /// Returns the tensor dimensions.
-    #[must_use]
-    pub fn dimensions(&self) -> &[usize] {
-        self.layout.dimensions()
+    /// Returns the contiguous layout of a reduction of `self` along `axes`, with the reduced
+    /// axes set to 1.
+    fn reduced_layout(&self, axes: &[usize]) -> Result<Layout, Error> {
+        let dimensions = self.layout.dimensions();
+        let rank = dimensions.len();
+
+        let mut seen = vec![false; rank];
+        for &axis in axes {
+            if axis >= rank {
+                return Err(TensorError::InvalidShape(format!(
+                    "axis {axis} out of bounds for tensor with rank {rank}"
+                ))
+                .into());
+            }
+            if seen[axis] {
+                return Err(TensorError::InvalidShape(format!("duplicate axis {axis}")).into());
+            }
+            seen[axis] = true;
+        }
+
+        let out_dimensions: Vec<usize> = dimensions
+            .iter()
+            .enumerate()
+            .map(|(i, &d)| if seen[i] { 1 } else { d })
+            .collect();
+        Ok(Layout::from_dimensions(&out_dimensions))
     }
 
-    /// Asynchronously copies tensor data from GPU to CPU.
-    ///
-    /// # Errors
-    ///
-    /// - [`Error::Device`] if operation fails.
-    pub async fn to_vec_async(&self) -> Result<Vec<T>, Error> {
-        self.ctx.read_buffer_async(&self.buffer).await
+    /// Checks that `self` has exactly one element for [`Self::item`].
+    fn check_item(&self) -> Result<(), Error> {
+        if self.layout.size() != 1 {
+            return Err(TensorError::InvalidShape(format!(
+                "item requires a single element, got dimensions {:?}",
+                self.dimensions()
+            ))
+            .into());
+        }
+
+        Ok(())
     }
 
-    /// Copies tensor data from GPU to CPU.
-    ///
-    /// # Errors
-    ///
-    /// - [`Error::Device`] if operation fails.
-    #[cfg(not(target_arch = "wasm32"))]
-    pub fn to_vec(&self) -> Result<Vec<T>, Error> {
-        self.ctx.read_buffer(&self.buffer)
+    /// Returns the buffer offset of the element at `index` for [`Self::get`].
+    fn element_offset(&self, index: &[usize]) -> Result<usize, Error> {
+        let dimensions = self.dimensions();
+        if index.len() != dimensions.len()
+            || index.iter().zip(dimensions).any(|(&i, &size)| i >= size)
+        {
+            return Err(TensorError::InvalidShape(format!(
+                "index {index:?} out of bounds for tensor {dimensions:?}"
+            ))
+            .into());
+        }
+
+        Ok(index
+            .iter()
+            .zip(self.layout.strides())
+            .fold(self.layout.offset(), |offset, (&i, &stride)| {
+                offset + i * stride
+            }))
+    }
+
+    /// Narrows every axis of `self` to the region for [`Self::read_region`].
+    fn region(&self, offsets: &[usize], extents: &[usize]) -> Result<Self, Error> {
+        let rank = self.dimensions().len();
+        if offsets.len() != rank || extents.len() != rank {
+            return Err(TensorError::InvalidShape(format!(
+                "region offsets {offsets:?} and extents {extents:?} must have rank {rank}"
+            ))
+            .into());
+        }
+
+        offsets.iter().zip(extents).enumerate().try_fold(
+            self.with_layout(self.layout.clone()),
+            |x, (axis, (&start, &len))| x.narrow(axis, start, len),
+        )
     }
 
     /// Applies a math binary operation with broadcasting.
@@ -130,8 +1376,9 @@ impl<T: Element> Tensor<T> {
         other: &Self,
         op: impl FnOnce(&Context, &Buffer<T>, &Buffer<T>, &Buffer<U>, &[usize], &[usize], &[usize]),
     ) -> Result<Tensor<U>, Error> {
+        let (a, b) = (self.strided()?, other.strided()?);
         let (dimensions, strides) =
-            Layout::broadcast(&[&self.layout, &other.layout]).ok_or_else(|| {
+            Layout::broadcast(&[&a.layout, &b.layout]).ok_or_else(|| {
                 TensorError::InvalidShape(format!(
                     "dimensions {:?} and {:?} are not broadcast-compatible",
                     self.dimensions(),
@@ -139,13 +1386,13 @@ impl<T: Element> Tensor<T> {
                 ))
             })?;
 
-        let layout = Layout::from_dimensions(&dimensions)?;
+        let layout = Layout::from_dimensions(&dimensions);
         let buffer = self.ctx.create_buffer(layout.size())?;
 
         op(
             &self.ctx,
-            &self.buffer,
-            &other.buffer,
+            &a.buffer,
+            &b.buffer,
             &buffer,
             &strides[0],
             &strides[1],
@@ -161,12 +1408,13 @@ impl<T: Element> Tensor<T> {
 
     /// Applies a math unary operation and returns a new tensor.
     fn math_unary(&self, op: impl FnOnce(&Context, &Buffer<T>, &Buffer<T>)) -> Result<Self, Error> {
-        let buffer = self.ctx.create_buffer(self.buffer.len())?;
-        op(&self.ctx, &self.buffer, &buffer);
+        let x = self.dense()?;
+        let buffer = self.ctx.create_buffer(x.buffer.len())?;
+        op(&self.ctx, &x.buffer, &buffer);
 
         Ok(Self {
             buffer,
-            layout: self.layout.clone(),
+            layout: x.layout,
             ctx: self.ctx.clone(),
         })
     }
@@ -180,7 +1428,8 @@ impl<T: NumericElement> Tensor<T> {
     /// - [`TensorError::InvalidShape`] if shapes are not broadcast-compatible.
     /// - [`Error::Device`] if GPU operation fails.
     pub fn clamp(&self, a: &Self, b: &Self) -> Result<Self, Error> {
-        let (dimensions, strides) = Layout::broadcast(&[&self.layout, &a.layout, &b.layout])
+        let (x, a, b) = (self.strided()?, a.strided()?, b.strided()?);
+        let (dimensions, strides) = Layout::broadcast(&[&x.layout, &a.layout, &b.layout])
             .ok_or_else(|| {
                 TensorError::InvalidShape(format!(
                     "dimensions {:?}, {:?}, and {:?} are not broadcast-compatible",
@@ -190,12 +1439,12 @@ impl<T: NumericElement> Tensor<T> {
                 ))
             })?;
 
-        let layout = Layout::from_dimensions(&dimensions)?;
+        let layout = Layout::from_dimensions(&dimensions);
         let buffer = self.ctx.create_buffer(layout.size())?;
 
         ops::clamp(
             &self.ctx,
-            &self.buffer,
+            &x.buffer,
             &a.buffer,
             &b.buffer,
             &buffer,
@@ -308,114 +1557,405 @@ impl<T: NumericElement> Tensor<T> {
         })
     }
 
-    /// Element-wise greater-than-or-equal comparison with broadcasting.
+    /// Element-wise greater-than-or-equal comparison with broadcasting.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidShape`] if shapes are not broadcast-compatible.
+    /// - [`Error::Device`] if buffer allocation fails.
+    pub fn ge(&self, other: &Self) -> Result<Tensor<bool>, Error> {
+        self.math_binary(other, |ctx, a, b, c, dimensions, a_strides, b_strides| {
+            ops::ge(ctx, a, b, c, dimensions, a_strides, b_strides);
+        })
+    }
+
+    /// Element-wise greater-than comparison with broadcasting.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidShape`] if shapes are not broadcast-compatible.
+    /// - [`Error::Device`] if buffer allocation fails.
+    pub fn gt(&self, other: &Self) -> Result<Tensor<bool>, Error> {
+        self.math_binary(other, |ctx, a, b, c, dimensions, a_strides, b_strides| {
+            ops::gt(ctx, a, b, c, dimensions, a_strides, b_strides);
+        })
+    }
+
+    /// Element-wise less-than-or-equal comparison with broadcasting.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidShape`] if shapes are not broadcast-compatible.
+    /// - [`Error::Device`] if buffer allocation fails.
+    pub fn le(&self, other: &Self) -> Result<Tensor<bool>, Error> {
+        self.math_binary(other, |ctx, a, b, c, dimensions, a_strides, b_strides| {
+            ops::le(ctx, a, b, c, dimensions, a_strides, b_strides);
+        })
+    }
+
+    /// Element-wise less-than comparison with broadcasting.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidShape`] if shapes are not broadcast-compatible.
+    /// - [`Error::Device`] if buffer allocation fails.
+    pub fn lt(&self, other: &Self) -> Result<Tensor<bool>, Error> {
+        self.math_binary(other, |ctx, a, b, c, dimensions, a_strides, b_strides| {
+            ops::lt(ctx, a, b, c, dimensions, a_strides, b_strides);
+        })
+    }
+
+    /// Max reduction along specified axes.
+    ///
+    /// Output shape equals input shape with reduced axes set to 1.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidShape`] if axes are invalid or duplicate, or a reduced axis is
+    ///   empty.
+    /// - [`Error::Device`] if GPU operation fails.
+    pub fn max_reduce(&self, axes: &[usize]) -> Result<Self, Error> {
+        self.reduction(axes, FullReduction::Max, None)
+    }
+
+    /// Min reduction along specified axes.
+    ///
+    /// Output shape equals input shape with reduced axes set to 1.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidShape`] if axes are invalid or duplicate, or a reduced axis is
+    ///   empty.
+    /// - [`Error::Device`] if GPU operation fails.
+    pub fn min_reduce(&self, axes: &[usize]) -> Result<Self, Error> {
+        self.reduction(axes, FullReduction::Min, None)
+    }
+
+    /// Product reduction along specified axes.
+    ///
+    /// Output shape equals input shape with reduced axes set to 1. Zeros and negative values
+    /// multiply exactly, unlike a sum of logarithms. An empty axis reduces to one.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidShape`] if axes are invalid or duplicate.
+    /// - [`Error::Device`] if GPU operation fails.
+    pub fn prod_reduce(&self, axes: &[usize]) -> Result<Self, Error> {
+        self.reduction(axes, FullReduction::Prod, None)
+    }
+
+    /// Sum reduction along specified axes.
+    ///
+    /// Output shape equals input shape with reduced axes set to 1.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidShape`] if axes are invalid or duplicate, or `normalize` is set
+    ///   and a reduced axis is empty.
+    /// - [`Error::Device`] if GPU operation fails.
+    pub fn sum_reduce(&self, axes: &[usize], normalize: bool) -> Result<Self, Error> {
+        self.normalized_sum(axes, normalize.then_some(0))
+    }
+
+    /// Mean reduction along specified axes.
+    ///
+    /// Output shape equals input shape with reduced axes set to 1.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidShape`] if axes are invalid or duplicate, or a reduced axis is
+    ///   empty.
+    /// - [`Error::Device`] if GPU operation fails.
+    pub fn mean_reduce(&self, axes: &[usize]) -> Result<Self, Error> {
+        self.sum_reduce(axes, true)
+    }
+
+    /// Sum of all elements, as a rank 0 tensor.
+    ///
+    /// Runs as a multi-pass tree over the flat elements instead of one reduction per axis;
+    /// read the result with [`Self::item`]. An empty tensor sums to zero.
+    ///
+    /// # Errors
+    ///
+    /// - [`Error::Device`] if GPU operation fails.
+    pub fn sum_all(&self) -> Result<Self, Error> {
+        if self.layout.size() == 0 {
+            return Self::scalar(&self.ctx, T::zeroed());
+        }
+        self.full_reduction(FullReduction::Sum, false)
+    }
+
+    /// Mean of all elements, as a rank 0 tensor.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidShape`] if `self` is empty.
+    /// - [`Error::Device`] if GPU operation fails.
+    pub fn mean_all(&self) -> Result<Self, Error> {
+        self.full_reduction(FullReduction::Sum, true)
+    }
+
+    /// Largest element, as a rank 0 tensor.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidShape`] if `self` is empty.
+    /// - [`Error::Device`] if GPU operation fails.
+    pub fn max_all(&self) -> Result<Self, Error> {
+        self.full_reduction(FullReduction::Max, false)
+    }
+
+    /// Smallest element, as a rank 0 tensor.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidShape`] if `self` is empty.
+    /// - [`Error::Device`] if GPU operation fails.
+    pub fn min_all(&self) -> Result<Self, Error> {
+        self.full_reduction(FullReduction::Min, false)
+    }
+
+    /// Reduces all elements by `op` in passes of one partial result per chunk, until a single
+    /// result is left, divided by the element count if `mean`.
+    fn full_reduction(&self, op: FullReduction, mean: bool) -> Result<Self, Error> {
+        let size = self.layout.size();
+        if size == 0 {
+            return Err(TensorError::InvalidShape(format!(
+                "cannot reduce empty tensor with dimensions {:?}",
+                self.dimensions()
+            ))
+            .into());
+        }
+
+        let x = self.dense()?;
+        let buffer = compute::reduce_rows(&self.ctx, &x.buffer, 1, size, op, mean.then_some(size))?;
+        Ok(Self {
+            buffer,
+            layout: Layout::from_dimensions(&[]),
+            ctx: self.ctx.clone(),
+        })
+    }
+
+    /// Sum reduction along `axes`, divided by the number of reduced elements minus
+    /// `correction` if given.
+    fn normalized_sum(&self, axes: &[usize], correction: Option<u32>) -> Result<Self, Error> {
+        self.reduction(axes, FullReduction::Sum, correction)
+    }
+
+    /// Index of the largest element along `axis`.
+    ///
+    /// Output shape equals input shape with `axis` set to 1. Ties resolve to the lowest index,
+    /// so e.g. the predicted classes of `[batch, classes]` scores stay on the GPU.
     ///
     /// # Errors
     ///
-    /// - [`TensorError::InvalidShape`] if shapes are not broadcast-compatible.
+    /// - [`TensorError::InvalidShape`] if `axis` is out of bounds or has size 0.
     /// - [`Error::Device`] if buffer allocation fails.
-    pub fn ge(&self, other: &Self) -> Result<Tensor<bool>, Error> {
-        self.math_binary(other, |ctx, a, b, c, dimensions, a_strides, b_strides| {
-            ops::ge(ctx, a, b, c, dimensions, a_strides, b_strides);
-        })
+    pub fn argmax(&self, axis: usize) -> Result<Tensor<u32>, Error> {
+        self.arg_reduce(axis, true)
     }
 
-    /// Element-wise greater-than comparison with broadcasting.
+    /// Index of the smallest element along `axis`.
+    ///
+    /// Output shape equals input shape with `axis` set to 1. Ties resolve to the lowest index.
     ///
     /// # Errors
     ///
-    /// - [`TensorError::InvalidShape`] if shapes are not broadcast-compatible.
+    /// - [`TensorError::InvalidShape`] if `axis` is out of bounds or has size 0.
     /// - [`Error::Device`] if buffer allocation fails.
-    pub fn gt(&self, other: &Self) -> Result<Tensor<bool>, Error> {
-        self.math_binary(other, |ctx, a, b, c, dimensions, a_strides, b_strides| {
-            ops::gt(ctx, a, b, c, dimensions, a_strides, b_strides);
+    pub fn argmin(&self, axis: usize) -> Result<Tensor<u32>, Error> {
+        self.arg_reduce(axis, false)
+    }
+
+    /// Index of the largest (`max`) or smallest element along `axis`.
+    fn arg_reduce(&self, axis: usize, max: bool) -> Result<Tensor<u32>, Error> {
+        let dimensions = self.layout.dimensions();
+        let size = dimensions.get(axis).copied().unwrap_or(0);
+        if size == 0 {
+            return Err(TensorError::InvalidShape(format!(
+                "axis {axis} must be in bounds and non-empty for dimensions {dimensions:?}"
+            ))
+            .into());
+        }
+
+        let inner = dimensions[axis + 1..].iter().product();
+        let mut out_dimensions = dimensions.to_vec();
+        out_dimensions[axis] = 1;
+
+        let x = self.materialize()?;
+        let layout = Layout::from_dimensions(&out_dimensions);
+        let buffer = self.ctx.create_buffer(layout.size())?;
+
+        ops::arg_reduce(&self.ctx, &x.buffer, &buffer, size, inner, max);
+
+        Ok(Tensor {
+            buffer,
+            layout,
+            ctx: self.ctx.clone(),
         })
     }
 
-    /// Element-wise less-than-or-equal comparison with broadcasting.
+    /// Cumulative sum along `axis`.
+    ///
+    /// Output shape equals input shape. Element `i` holds the sum of elements `0..=i`, or
+    /// `0..i` if `exclusive`, which starts from zero; e.g. the exclusive sum of sequence
+    /// lengths gives their offsets in a packed buffer. Runs as a work-efficient parallel scan
+    /// with one workgroup per line along `axis`.
     ///
     /// # Errors
     ///
-    /// - [`TensorError::InvalidShape`] if shapes are not broadcast-compatible.
+    /// - [`TensorError::InvalidShape`] if `axis` is out of bounds.
     /// - [`Error::Device`] if buffer allocation fails.
-    pub fn le(&self, other: &Self) -> Result<Tensor<bool>, Error> {
-        self.math_binary(other, |ctx, a, b, c, dimensions, a_strides, b_strides| {
-            ops::le(ctx, a, b, c, dimensions, a_strides, b_strides);
-        })
+    pub fn cumsum(&self, axis: usize, exclusive: bool) -> Result<Self, Error> {
+        self.prefix_scan(axis, Scan::Sum, exclusive)
     }
 
-    /// Element-wise less-than comparison with broadcasting.
+    /// Cumulative product along `axis`.
+    ///
+    /// Output shape equals input shape. Element `i` holds the product of elements `0..=i`, or
+    /// `0..i` if `exclusive`, which starts from one.
     ///
     /// # Errors
     ///
-    /// - [`TensorError::InvalidShape`] if shapes are not broadcast-compatible.
+    /// - [`TensorError::InvalidShape`] if `axis` is out of bounds.
     /// - [`Error::Device`] if buffer allocation fails.
-    pub fn lt(&self, other: &Self) -> Result<Tensor<bool>, Error> {
-        self.math_binary(other, |ctx, a, b, c, dimensions, a_strides, b_strides| {
-            ops::lt(ctx, a, b, c, dimensions, a_strides, b_strides);
+    pub fn cumprod(&self, axis: usize, exclusive: bool) -> Result<Self, Error> {
+        self.prefix_scan(axis, Scan::Prod, exclusive)
+    }
+
+    /// Running sum or product by `op` along `axis`.
+    fn prefix_scan(&self, axis: usize, op: Scan, exclusive: bool) -> Result<Self, Error> {
+        let dimensions = self.layout.dimensions();
+        let Some(&size) = dimensions.get(axis) else {
+            return Err(TensorError::InvalidShape(format!(
+                "axis {axis} out of bounds for dimensions {dimensions:?}"
+            ))
+            .into());
+        };
+
+        let inner = dimensions[axis + 1..].iter().product();
+        let x = self.materialize()?;
+        let buffer = self.ctx.create_buffer(x.layout.size())?;
+
+        ops::prefix_scan(&self.ctx, &x.buffer, &buffer, size, inner, op, exclusive);
+
+        Ok(Self {
+            buffer,
+            layout: x.layout,
+            ctx: self.ctx.clone(),
         })
     }
 
-    /// Max reduction along specified axes.
+    /// Indices that sort `self` along the last axis.
     ///
-    /// Output shape equals input shape with reduced axes set to 1.
+    /// With `stable`, equal elements keep their original order, so the lowest index wins ties.
+    /// Otherwise the order of ties is unspecified but still deterministic across adapters.
     ///
     /// # Errors
     ///
-    /// - [`TensorError::InvalidShape`] if axes are invalid or duplicate.
-    /// - [`Error::Device`] if GPU operation fails.
-    pub fn max_reduce(&self, axes: &[usize]) -> Result<Self, Error> {
-        self.reduction(axes, ops::max_reduce)
+    /// - [`TensorError::InvalidShape`] if `self` is a scalar.
+    /// - [`Error::Device`] if buffer allocation fails.
+    pub fn argsort(&self, descending: bool, stable: bool) -> Result<Tensor<u32>, Error> {
+        let cols = self.sort_cols()?;
+        Ok(self.sorted(cols, descending, stable)?.1)
     }
 
-    /// Min reduction along specified axes.
+    /// Sorts `self` along the last axis, returning the sorted values and their indices.
     ///
-    /// Output shape equals input shape with reduced axes set to 1.
+    /// Ties are broken as in [`Self::argsort`].
     ///
     /// # Errors
     ///
-    /// - [`TensorError::InvalidShape`] if axes are invalid or duplicate.
-    /// - [`Error::Device`] if GPU operation fails.
-    pub fn min_reduce(&self, axes: &[usize]) -> Result<Self, Error> {
-        self.reduction(axes, ops::min_reduce)
+    /// - [`TensorError::InvalidShape`] if `self` is a scalar.
+    /// - [`Error::Device`] if buffer allocation fails.
+    pub fn sort(&self, descending: bool, stable: bool) -> Result<(Self, Tensor<u32>), Error> {
+        let cols = self.sort_cols()?;
+        self.sorted(cols, descending, stable)
     }
 
-    /// Sum reduction along specified axes.
+    /// The `k` largest elements along the last axis in descending order, with their indices.
     ///
-    /// Output shape equals input shape with reduced axes set to 1.
+    /// With `stable`, equal elements are ordered by index, so the lowest index wins ties,
+    /// including at the cut-off.
     ///
     /// # Errors
     ///
-    /// - [`TensorError::InvalidShape`] if axes are invalid or duplicate.
-    /// - [`Error::Device`] if GPU operation fails.
-    pub fn sum_reduce(&self, axes: &[usize], normalize: bool) -> Result<Self, Error> {
-        self.reduction(
-            axes,
-            |ctx, input, output, dims, x_strides, y_strides, axes| {
-                ops::sum_reduce(
-                    ctx, input, output, dims, x_strides, y_strides, axes, normalize,
-                );
-            },
-        )
+    /// - [`TensorError::InvalidShape`] if `self` is a scalar.
+    /// - [`TensorError::InvalidArgument`] if `k` is zero or exceeds the last axis length.
+    /// - [`Error::Device`] if buffer allocation fails.
+    pub fn topk(&self, k: usize, stable: bool) -> Result<(Self, Tensor<u32>), Error> {
+        let cols = self.sort_cols()?;
+        if k == 0 || k > cols {
+            return Err(
+                TensorError::InvalidArgument(format!("k = {k} must be in 1..={cols}")).into(),
+            );
+        }
+        self.sorted(k, true, stable)
     }
 
-    /// Mean reduction along specified axes.
-    ///
-    /// Output shape equals input shape with reduced axes set to 1.
-    ///
-    /// # Errors
-    ///
-    /// - [`TensorError::InvalidShape`] if axes are invalid or duplicate.
-    /// - [`Error::Device`] if GPU operation fails.
-    pub fn mean_reduce(&self, axes: &[usize]) -> Result<Self, Error> {
-        self.sum_reduce(axes, true)
+    /// Length of the last axis, which sort operations run along.
+    fn sort_cols(&self) -> Result<usize, Error> {
+        self.layout
+            .dimensions()
+            .last()
+            .copied()
+            .ok_or_else(|| TensorError::InvalidShape("sort requires rank >= 1".into()).into())
+    }
+
+    /// Sorts along the last axis and keeps the first `k` entries of each row.
+    fn sorted(
+        &self,
+        k: usize,
+        descending: bool,
+        stable: bool,
+    ) -> Result<(Self, Tensor<u32>), Error> {
+        let dimensions = self.layout.dimensions();
+        let cols = dimensions[dimensions.len() - 1];
+        let rows = self.layout.size().checked_div(cols).unwrap_or(0);
+        let padded = rows * cols.next_power_of_two();
+
+        let mut out_dimensions = dimensions.to_vec();
+        out_dimensions[dimensions.len() - 1] = k;
+        let layout = Layout::from_dimensions(&out_dimensions);
+
+        let keys = self.ctx.create_buffer(padded)?;
+        let idx = self.ctx.create_buffer(padded)?;
+        let values = self.ctx.create_buffer(layout.size())?;
+        let indices = self.ctx.create_buffer(layout.size())?;
+
+        let x = self.materialize()?;
+        ops::sort(
+            &self.ctx, &x.buffer, &keys, &idx, &values, &indices, cols, k, descending, stable,
+        );
+
+        Ok((
+            Self {
+                buffer: values,
+                layout: layout.clone(),
+                ctx: self.ctx.clone(),
+            },
+            Tensor {
+                buffer: indices,
+                layout,
+                ctx: self.ctx.clone(),
+            },
+        ))
     }
 
-    /// Applies a reduce operation with strides and returns a new tensor.
-    fn reduction<F>(&self, axes: &[usize], op: F) -> Result<Self, Error>
-    where
-        F: FnOnce(&Context, &Buffer<T>, &Buffer<T>, &[usize], &[usize], &[usize], &[usize]),
-    {
+    /// Reduction by `op` along `axes`, with sums divided by the number of reduced elements
+    /// minus `correction` if given.
+    ///
+    /// Short reductions run in one pass with one workgroup per output, reading the input
+    /// through its strides. Reductions over more than [`full::CHUNK`] elements are tiled
+    /// instead: the reduced axes are moved last in a contiguous copy and its rows reduced in
+    /// chained passes, so a long axis spreads over many workgroups
+    /// rather than serializing in one.
+    fn reduction(
+        &self,
+        axes: &[usize],
+        op: FullReduction,
+        correction: Option<u32>,
+    ) -> Result<Self, Error> {
         let dimensions = self.layout.dimensions();
         let rank = dimensions.len();
 
@@ -439,18 +1979,63 @@ impl<T: NumericElement> Tensor<T> {
             .map(|(i, &d)| if seen[i] { 1 } else { d })
             .collect();
 
-        let layout = Layout::from_dimensions(&out_dimensions)?;
-        let buffer = self.ctx.create_buffer(layout.size())?;
+        let layout = Layout::from_dimensions(&out_dimensions);
+        let reduction_len = axes.iter().map(|&axis| dimensions[axis]).product::<usize>();
+        let has_identity = matches!(op, FullReduction::Sum | FullReduction::Prod);
+        if reduction_len == 0 && (!has_identity || correction.is_some()) {
+            return Err(TensorError::InvalidShape(format!(
+                "cannot reduce empty axes {axes:?} of tensor with dimensions {dimensions:?}"
+            ))
+            .into());
+        }
 
-        op(
-            &self.ctx,
-            &self.buffer,
-            &buffer,
-            dimensions,
-            self.layout.strides(),
-            layout.strides(),
-            axes,
-        );
+        if reduction_len > full::CHUNK {
+            let order: Vec<usize> = (0..rank)
+                .filter(|&axis| !seen[axis])
+                .chain(axes.iter().copied())
+                .collect();
+            let x = self.permute(&order)?.materialize()?;
+            let divisor = correction.map(|correction| reduction_len - correction as usize);
+            let buffer = compute::reduce_rows(
+                &self.ctx,
+                &x.buffer,
+                layout.size(),
+                reduction_len,
+                op,
+                divisor,
+            )?;
+
+            return Ok(Self {
+                buffer,
+                layout,
+                ctx: self.ctx.clone(),
+            });
+        }
+
+        let buffer = self.ctx.create_buffer(layout.size())?;
+        let x = self.strided()?;
+        let (ctx, dimensions, x_strides, y_strides) =
+            (&self.ctx, dimensions, x.layout.strides(), layout.strides());
+        match op {
+            FullReduction::Sum => ops::sum_reduce(
+                ctx, &x.buffer, &buffer, dimensions, x_strides, y_strides, axes, correction,
+            ),
+            FullReduction::Max => {
+                ops::max_reduce(
+                    ctx, &x.buffer, &buffer, dimensions, x_strides, y_strides, axes,
+                );
+            }
+            FullReduction::Min => {
+                ops::min_reduce(
+                    ctx, &x.buffer, &buffer, dimensions, x_strides, y_strides, axes,
+                );
+            }
+            FullReduction::Prod => {
+                ops::prod_reduce(
+                    ctx, &x.buffer, &buffer, dimensions, x_strides, y_strides, axes,
+                );
+            }
+        }
 
         Ok(Self {
             buffer,
@@ -510,7 +2095,9 @@ impl<T: FloatElement> Tensor<T> {
     ///
     /// `A[..., m, k] × B[..., k, n] → C[..., m, n]`
     ///
-    /// Batch dimensions are broadcast-compatible.
+    /// Batch dimensions are broadcast-compatible. When `m`, `k` and `n` are all at most 8,
+    /// each matrix is multiplied by a single thread in registers, which suits large batches of
+    /// tiny matrices such as 4×4 transforms.
     ///
     /// # Errors
     ///
@@ -523,73 +2110,86 @@ impl<T: FloatElement> Tensor<T> {
         transpose_a: bool,
         transpose_b: bool,
     ) -> Result<Self, Error> {
-        let a_dims = self.layout.dimensions();
-        let b_dims = other.layout.dimensions();
-        let rank = a_dims.len();
+        let (a, transpose_a) = self.matmul_operand(transpose_a)?;
+        let (b, transpose_b) = other.matmul_operand(transpose_b)?;
+        let a_dims = a.layout.dimensions();
+        let b_dims = b.layout.dimensions();
+        let out_dims = matmul_dimensions(a_dims, b_dims, transpose_a, transpose_b)?;
 
-        if rank < 2 || b_dims.len() < 2 {
-            return Err(
-                TensorError::InvalidShape("matmul requires tensors with rank >= 2".into()).into(),
-            );
-        }
-
-        if rank != b_dims.len() {
-            return Err(TensorError::InvalidShape(format!(
-                "matmul requires equal ranks, got {} and {}",
-                rank,
-                b_dims.len()
-            ))
-            .into());
-        }
+        let layout = Layout::from_dimensions(&out_dims);
+        let buffer = self.ctx.create_buffer(layout.size())?;
 
-        let (a_rows, a_cols) = (a_dims[rank - 2], a_dims[rank - 1]);
-        let (b_rows, b_cols) = (b_dims[rank - 2], b_dims[rank - 1]);
+        ops::matmul(
+            &self.ctx,
+            &a.buffer,
+            &b.buffer,
+            &buffer,
+            a_dims,
+            b_dims,
+            &out_dims,
+            transpose_a,
+            transpose_b,
+        );
 
-        let (m, a_k) = if transpose_a {
-            (a_cols, a_rows)
-        } else {
-            (a_rows, a_cols)
-        };
-        let (b_k, n) = if transpose_b {
-            (b_cols, b_rows)
-        } else {
-            (b_rows, b_cols)
-        };
+        Ok(Self {
+            buffer,
+            layout,
+            ctx: self.ctx.clone(),
+        })
+    }
 
-        if a_k != b_k {
+    /// Batched matrix multiplication with the output columns packed into `parts` matrices.
+    ///
+    /// `A[..., m, k] × B[..., k, parts * n] → C[parts, ..., m, n]`
+    ///
+    /// Projects onto fused weights (e.g. concatenated Q, K and V) in a single dispatch, writing
+    /// each column group as its own contiguous matrix along a new leading axis.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidShape`] if ranks differ or are less than 2.
+    /// - [`TensorError::InvalidShape`] if inner dimensions don't match.
+    /// - [`TensorError::InvalidShape`] if output columns are not divisible by `parts`.
+    /// - [`Error::Device`] if GPU operation fails.
+    pub fn matmul_packed(
+        &self,
+        other: &Self,
+        transpose_a: bool,
+        transpose_b: bool,
+        parts: usize,
+    ) -> Result<Self, Error> {
+        let (a, transpose_a) = self.matmul_operand(transpose_a)?;
+        let (b, transpose_b) = other.matmul_operand(transpose_b)?;
+        let a_dims = a.layout.dimensions();
+        let b_dims = b.layout.dimensions();
+        let out_dims = matmul_dimensions(a_dims, b_dims, transpose_a, transpose_b)?;
+
+        let n = out_dims[out_dims.len() - 1];
+        if parts == 0 || !n.is_multiple_of(parts) {
             return Err(TensorError::InvalidShape(format!(
-                "matmul inner dimensions don't match: {a_k} vs {b_k}"
+                "output columns {n} are not divisible into {parts} parts"
             ))
             .into());
         }
 
-        let mut out_dims: Vec<usize> = a_dims[..rank - 2]
-            .iter()
-            .zip(&b_dims[..rank - 2])
-            .map(|(&da, &db)| match (da, db) {
-                (a, b) if a == b => Ok(a),
-                (1, b) => Ok(b),
-                (a, 1) => Ok(a),
-                _ => Err(TensorError::InvalidShape(format!(
-                    "batch dimensions not broadcast-compatible: {da} vs {db}"
-                ))),
-            })
-            .collect::<Result<_, _>>()?;
-        out_dims.extend([m, n]);
+        let mut packed_dims = vec![parts];
+        packed_dims.extend(&out_dims[..out_dims.len() - 1]);
+        packed_dims.push(n / parts);
 
-        let layout = Layout::from_dimensions(&out_dims)?;
+        let layout = Layout::from_dimensions(&packed_dims);
         let buffer = self.ctx.create_buffer(layout.size())?;
 
-        ops::matmul(
+        ops::matmul_packed(
             &self.ctx,
-            &self.buffer,
-            &other.buffer,
+            &a.buffer,
+            &b.buffer,
             &buffer,
             a_dims,
             b_dims,
             &out_dims,
             transpose_a,
             transpose_b,
+            parts,
         );
 
         Ok(Self {
@@ -599,6 +2199,82 @@ impl<T: FloatElement> Tensor<T> {
         })
     }
 
+    /// Batched matrix multiplication scaled per batch and accumulated into `self`, in place.
+    ///
+    /// `self[i] = alpha[i] · A[i] × B[i] + beta[i] · self[i]` for every batch index `i`, so
+    /// weighted combinations (e.g. expert outputs weighted by their gates) need no extra pass
+    /// over the output. `alpha` and `beta` have the batch dimensions of `self`, or `[1]` when
+    /// `self` is a single matrix. Where `beta[i]` is zero the previous contents of `self[i]`
+    /// are not read. Operands sharing the buffer of `self` are copied first.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidShape`] if ranks differ or are less than 2.
+    /// - [`TensorError::InvalidShape`] if inner dimensions don't match.
+    /// - [`TensorError::InvalidShape`] if `self` is a strided view or its dimensions differ
+    ///   from the product's.
+    /// - [`TensorError::InvalidShape`] if `alpha` or `beta` dimensions don't match.
+    /// - [`Error::Device`] if GPU operation fails.
+    #[allow(clippy::too_many_arguments)]
+    pub fn matmul_scaled_(
+        &mut self,
+        a: &Self,
+        b: &Self,
+        transpose_a: bool,
+        transpose_b: bool,
+        alpha: &Self,
+        beta: &Self,
+    ) -> Result<(), Error> {
+        self.check_writable()?;
+        let (a, transpose_a) = self.unaliased(a)?.matmul_operand(transpose_a)?;
+        let (b, transpose_b) = self.unaliased(b)?.matmul_operand(transpose_b)?;
+        let a_dims = a.layout.dimensions();
+        let b_dims = b.layout.dimensions();
+        let out_dims = matmul_dimensions(a_dims, b_dims, transpose_a, transpose_b)?;
+
+        let dimensions = self.layout.dimensions();
+        if out_dims != dimensions {
+            return Err(TensorError::InvalidShape(format!(
+                "product dimensions {out_dims:?} don't match output {dimensions:?}"
+            ))
+            .into());
+        }
+
+        let batch_dims = &dimensions[..dimensions.len() - 2];
+        let scale_dims = if batch_dims.is_empty() {
+            &[1][..]
+        } else {
+            batch_dims
+        };
+        for scale in [alpha, beta] {
+            if scale.dimensions() != scale_dims {
+                return Err(TensorError::InvalidShape(format!(
+                    "scale dimensions {:?} don't match expected {scale_dims:?}",
+                    scale.dimensions()
+                ))
+                .into());
+            }
+        }
+
+        let alpha = self.unaliased(alpha)?.materialize()?;
+        let beta = self.unaliased(beta)?.materialize()?;
+        ops::matmul_scaled(
+            &self.ctx,
+            &a.buffer,
+            &b.buffer,
+            &self.buffer,
+            &alpha.buffer,
+            &beta.buffer,
+            a_dims,
+            b_dims,
+            &out_dims,
+            transpose_a,
+            transpose_b,
+        );
+
+        Ok(())
+    }
+
     /// Element-wise power with broadcasting.
     ///
     /// # Errors
@@ -809,13 +2485,115 @@ impl<T: FloatElement> Tensor<T> {
         self.math_unary(ops::floor)
     }
 
-    /// Rounds to nearest integer element-wise.
+    /// Rounds to nearest integer element-wise.
+    ///
+    /// # Errors
+    ///
+    /// - [`Error::Device`] if operation fails.
+    pub fn round(&self) -> Result<Self, Error> {
+        self.math_unary(ops::round)
+    }
+
+    /// Variance reduction along specified axes.
+    ///
+    /// Output shape equals input shape with reduced axes set to 1. With `correction`, the
+    /// squared deviations from the mean are divided by `n - 1` (Bessel's correction) instead of
+    /// the `n` reduced elements, giving the unbiased sample variance; a single element then
+    /// has an undefined variance.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidShape`] if axes are invalid or duplicate.
+    /// - [`Error::Device`] if GPU operation fails.
+    pub fn var_reduce(&self, axes: &[usize], correction: bool) -> Result<Self, Error> {
+        Ok(self.moments(axes, correction)?.1)
+    }
+
+    /// Mean and variance along specified axes, as `(mean, var)`, in a single pass.
+    ///
+    /// Output shapes equal input shape with reduced axes set to 1. One kernel reads each
+    /// element once and combines partial results with Welford's parallel update, halving the
+    /// memory traffic of a mean followed by a variance and avoiding the cancellation of
+    /// `E[x²] - E[x]²`. `correction` applies Bessel's correction as in [`Self::var_reduce`].
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidShape`] if axes are invalid or duplicate.
+    /// - [`Error::Device`] if GPU operation fails.
+    pub fn moments(&self, axes: &[usize], correction: bool) -> Result<(Self, Self), Error> {
+        let dimensions = self.layout.dimensions();
+        let layout = self.reduced_layout(axes)?;
+        let mean = self.ctx.create_buffer(layout.size())?;
+        let var = self.ctx.create_buffer(layout.size())?;
+
+        let x = self.strided()?;
+        ops::moments(
+            &self.ctx,
+            &x.buffer,
+            &mean,
+            &var,
+            dimensions,
+            x.layout.strides(),
+            layout.strides(),
+            axes,
+            u32::from(correction),
+        );
+
+        Ok((
+            Self {
+                buffer: mean,
+                layout: layout.clone(),
+                ctx: self.ctx.clone(),
+            },
+            Self {
+                buffer: var,
+                layout,
+                ctx: self.ctx.clone(),
+            },
+        ))
+    }
+
+    /// Standard deviation reduction along specified axes: the square root of
+    /// [`Self::var_reduce`].
+    ///
+    /// Output shape equals input shape with reduced axes set to 1.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidShape`] if axes are invalid or duplicate.
+    /// - [`Error::Device`] if GPU operation fails.
+    pub fn std_reduce(&self, axes: &[usize], correction: bool) -> Result<Self, Error> {
+        self.var_reduce(axes, correction)?.sqrt()
+    }
+
+    /// Counts elements into `bins` equal-width bins spanning `[min, max]`.
+    ///
+    /// Values below `min` or above `max` are counted in the first or last bin; `NaN` is skipped.
     ///
     /// # Errors
     ///
-    /// - [`Error::Device`] if operation fails.
-    pub fn round(&self) -> Result<Self, Error> {
-        self.math_unary(ops::round)
+    /// - [`TensorError::InvalidArgument`] if `bins` is zero or `min < max` does not hold.
+    /// - [`Error::Device`] if buffer allocation fails.
+    pub fn histogram(&self, bins: usize, min: f32, max: f32) -> Result<Tensor<u32>, Error> {
+        if bins == 0 {
+            return Err(TensorError::InvalidArgument("bins must be positive".into()).into());
+        }
+        if min.partial_cmp(&max) != Some(Ordering::Less) {
+            return Err(TensorError::InvalidArgument(format!(
+                "histogram range [{min}, {max}] is empty"
+            ))
+            .into());
+        }
+
+        let layout = Layout::from_dimensions(&[bins]);
+        let buffer = self.ctx.create_buffer(bins)?;
+        ops::histogram(&self.ctx, &self.dense()?.buffer, &buffer, min, max);
+
+        Ok(Tensor {
+            buffer,
+            layout,
+            ctx: self.ctx.clone(),
+        })
     }
 
     /// `ELU` activation: `y = x < 0 ? α(eˣ - 1) : x`.
@@ -874,7 +2652,9 @@ impl<T: FloatElement> Tensor<T> {
             ))
             .into());
         }
-        self.nn_activation(|ctx, x, y| ops::prelu(ctx, x, y, &alpha.buffer))
+        let alpha = alpha.materialize()?;
+        self.materialize()?
+            .nn_activation(|ctx, x, y| ops::prelu(ctx, x, y, &alpha.buffer))
     }
 
     /// `ReLU` activation: `y = max(x, 0)`.
@@ -929,16 +2709,548 @@ impl<T: FloatElement> Tensor<T> {
         self.nn_activation(ops::softplus)
     }
 
+    /// Softmax over the last axis: `y = eˣ / Σ eˣ`.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidShape`] if `self` is a scalar.
+    /// - [`Error::Device`] if buffer allocation fails.
+    pub fn softmax(&self) -> Result<Self, Error> {
+        self.nn_softmax(None)
+    }
+
+    /// Softmax over the last axis, excluding elements where `mask` is false.
+    ///
+    /// `mask` broadcasts to the shape of `self`. Masked elements take no part in the
+    /// normalization and are written as zero; fully masked rows are all zero rather than `NaN`.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidShape`] if `self` is a scalar or `mask` does not broadcast to
+    ///   the shape of `self`.
+    /// - [`Error::Device`] if buffer allocation fails.
+    pub fn masked_softmax(&self, mask: &Tensor<bool>) -> Result<Self, Error> {
+        self.nn_softmax(Some(mask))
+    }
+
+    /// Applies softmax over the last axis with an optional mask.
+    fn nn_softmax(&self, mask: Option<&Tensor<bool>>) -> Result<Self, Error> {
+        let x = self.materialize()?;
+        let mask = mask.map(Tensor::strided).transpose()?;
+        let dimensions = x.layout.dimensions();
+        let Some(&cols) = dimensions.last() else {
+            return Err(TensorError::InvalidShape("softmax requires rank >= 1".into()).into());
+        };
+
+        let mask = mask
+            .as_ref()
+            .map(|mask| {
+                Layout::broadcast(&[&x.layout, &mask.layout])
+                    .filter(|(out_dims, _)| **out_dims == *dimensions)
+                    .map(|(_, mut strides)| (&mask.buffer, strides.swap_remove(1)))
+                    .ok_or_else(|| {
+                        TensorError::InvalidShape(format!(
+                            "mask dimensions {:?} do not broadcast to {dimensions:?}",
+                            mask.dimensions()
+                        ))
+                    })
+            })
+            .transpose()?;
+
+        let buffer = self.ctx.create_buffer(x.layout.size())?;
+        ops::softmax(
+            &self.ctx,
+            &x.buffer,
+            mask.as_ref().map(|(mask, strides)| (*mask, &strides[..])),
+            &buffer,
+            x.layout.strides(),
+            cols,
+        );
+
+        Ok(Self {
+            buffer,
+            layout: x.layout,
+            ctx: self.ctx.clone(),
+        })
+    }
+
+    /// Fused bias, dropout and residual: `y = dropout(x + bias, p) + residual`.
+    ///
+    /// Each element of `x + bias` is zeroed with probability `p` and otherwise scaled by
+    /// `1 / (1 - p)`. The mask is drawn in-kernel from `seed`, so the same seed reproduces the
+    /// same mask. All three operands broadcast together.
+    ///
+    /// # Arguments
+    ///
+    /// * `bias` - Bias added before dropout.
+    /// * `residual` - Residual added after dropout.
+    /// * `p` - Drop probability in `[0, 1)`.
+    /// * `seed` - Seed for the dropout mask.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidShape`] if shapes are not broadcast-compatible.
+    /// - [`TensorError::InvalidArgument`] if `p` is not in `[0, 1)`.
+    /// - [`Error::Device`] if buffer allocation fails.
+    pub fn bias_dropout_residual(
+        &self,
+        bias: &Self,
+        residual: &Self,
+        p: f32,
+        seed: u32,
+    ) -> Result<Self, Error> {
+        if !(0.0..1.0).contains(&p) {
+            return Err(TensorError::InvalidArgument(format!(
+                "dropout probability {p} must be in [0, 1)"
+            ))
+            .into());
+        }
+
+        let (x, bias, residual) = (self.strided()?, bias.strided()?, residual.strided()?);
+        let (dimensions, strides) = Layout::broadcast(&[&x.layout, &bias.layout, &residual.layout])
+            .ok_or_else(|| {
+                TensorError::InvalidShape(format!(
+                    "dimensions {:?}, {:?}, and {:?} are not broadcast-compatible",
+                    self.dimensions(),
+                    bias.dimensions(),
+                    residual.dimensions()
+                ))
+            })?;
+
+        let layout = Layout::from_dimensions(&dimensions);
+        let buffer = self.ctx.create_buffer(layout.size())?;
+
+        ops::bias_dropout_residual(
+            &self.ctx,
+            &x.buffer,
+            &bias.buffer,
+            &residual.buffer,
+            &buffer,
+            &strides[0],
+            &strides[1],
+            &strides[2],
+            layout.strides(),
+            p,
+            seed,
+        );
+
+        Ok(Self {
+            buffer,
+            layout,
+            ctx: self.ctx.clone(),
+        })
+    }
+
+    /// Softmax cross-entropy over the last axis of `[rows, classes]` logits.
+    ///
+    /// Returns the per-row loss `[rows]` and the logits gradient of the summed loss,
+    /// `softmax(x) - onehot(target)`. Divide the gradient by `rows` for a mean loss. A target
+    /// out of bounds has no target logit: its row loss is the log-sum-exp of the logits and
+    /// its gradient the softmax alone.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidShape`] if `self` is not rank 2, has no classes, or `targets` is
+    ///   not `[rows]`.
+    /// - [`Error::Device`] if buffer allocation fails.
+    pub fn cross_entropy(&self, targets: &Tensor<u32>) -> Result<(Self, Self), Error> {
+        let (loss, mut grads) = Self::cross_entropy_chunked(core::slice::from_ref(self), targets)?;
+        Ok((loss, grads.remove(0)))
+    }
+
+    /// Softmax cross-entropy over logits split into class chunks.
+    ///
+    /// `chunks` are `[rows, classes_i]` slices of the logits along the class axis, in class
+    /// order, so vocabularies too large for one buffer can be processed without materializing
+    /// the full logits or softmax. `targets` holds class indices into the concatenated axis.
+    ///
+    /// Returns the per-row loss `[rows]` and one gradient tensor per chunk. Targets out of
+    /// bounds are handled as in [`Self::cross_entropy`].
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidShape`] if `chunks` is empty, any chunk is not rank 2, row
+    ///   counts differ, the chunks have no classes in total, or `targets` is not `[rows]`.
+    /// - [`Error::Device`] if buffer allocation fails.
+    pub fn cross_entropy_chunked(
+        chunks: &[Self],
+        targets: &Tensor<u32>,
+    ) -> Result<(Self, Vec<Self>), Error> {
+        let first = chunks
+            .first()
+            .ok_or_else(|| TensorError::InvalidShape("chunks must not be empty".into()))?;
+
+        let rows = match first.dimensions() {
+            [rows, _] => *rows,
+            dims => {
+                return Err(TensorError::InvalidShape(format!(
+                    "cross entropy requires rank 2 logits, got {dims:?}"
+                ))
+                .into());
+            }
+        };
+
+        for chunk in chunks {
+            if chunk.dimensions().len() != 2 || chunk.dimensions()[0] != rows {
+                return Err(TensorError::InvalidShape(format!(
+                    "chunk dimensions {:?} do not match {rows} rows",
+                    chunk.dimensions()
+                ))
+                .into());
+            }
+        }
+
+        if chunks.iter().all(|chunk| chunk.dimensions()[1] == 0) {
+            return Err(TensorError::InvalidShape(
+                "cross entropy requires at least one class".into(),
+            )
+            .into());
+        }
+
+        if targets.dimensions() != [rows] {
+            return Err(TensorError::InvalidShape(format!(
+                "targets dimensions {:?} do not match {rows} rows",
+                targets.dimensions()
+            ))
+            .into());
+        }
+
+        let chunks = chunks
+            .iter()
+            .map(Self::materialize)
+            .collect::<Result<Vec<_>, Error>>()?;
+        let targets = targets.materialize()?;
+
+        let ctx = &first.ctx;
+        let layout = Layout::from_dimensions(&[rows]);
+        let row_max = ctx.create_buffer(rows)?;
+        let row_sum = ctx.create_buffer(rows)?;
+        let target_logit = ctx.create_buffer(rows)?;
+
+        let mut offset = 0;
+        for chunk in &chunks {
+            let cols = chunk.dimensions()[1];
+            ops::cross_entropy_accumulate(
+                ctx,
+                &chunk.buffer,
+                &targets.buffer,
+                &row_max,
+                &row_sum,
+                &target_logit,
+                rows,
+                cols,
+                offset,
+            );
+            offset += cols;
+        }
+
+        let loss = ctx.create_buffer(rows)?;
+        ops::cross_entropy_loss(ctx, &row_max, &row_sum, &target_logit, &loss, rows);
+
+        let mut offset = 0;
+        let grads = chunks
+            .iter()
+            .map(|chunk| {
+                let cols = chunk.dimensions()[1];
+                let buffer = ctx.create_buffer(rows * cols)?;
+                ops::cross_entropy_grad(
+                    ctx,
+                    &chunk.buffer,
+                    &targets.buffer,
+                    &row_max,
+                    &row_sum,
+                    &buffer,
+                    rows,
+                    cols,
+                    offset,
+                );
+                offset += cols;
+
+                Ok(Self {
+                    buffer,
+                    layout: chunk.layout.clone(),
+                    ctx: ctx.clone(),
+                })
+            })
+            .collect::<Result<_, Error>>()?;
+
+        Ok((
+            Self {
+                buffer: loss,
+                layout,
+                ctx: ctx.clone(),
+            },
+            grads,
+        ))
+    }
+
+    /// Knowledge distillation loss between student logits `self` and `teacher` logits.
+    ///
+    /// With `p = softmax(teacher / T)` and `q = softmax(self / T)` over the last axis of
+    /// `[rows, classes]` logits, returns the per-row loss `T² · KL(p ‖ q)` of shape `[rows]`
+    /// and the student logits gradient of the summed loss, `T (q - p)`. The `T²` factor keeps
+    /// gradient magnitudes comparable across temperatures.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidShape`] if `self` is not rank 2 or `teacher` has different
+    ///   dimensions.
+    /// - [`TensorError::InvalidArgument`] if `temperature` is not positive and finite.
+    /// - [`Error::Device`] if buffer allocation fails.
+    pub fn kl_div(&self, teacher: &Self, temperature: f32) -> Result<(Self, Self), Error> {
+        let &[rows, cols] = self.dimensions() else {
+            return Err(TensorError::InvalidShape(format!(
+                "kl divergence requires rank 2 logits, got {:?}",
+                self.dimensions()
+            ))
+            .into());
+        };
+
+        if teacher.dimensions() != self.dimensions() {
+            return Err(TensorError::InvalidShape(format!(
+                "teacher dimensions {:?} do not match {:?}",
+                teacher.dimensions(),
+                self.dimensions()
+            ))
+            .into());
+        }
+
+        if !(temperature.is_finite() && temperature > 0.0) {
+            return Err(TensorError::InvalidArgument(format!(
+                "temperature {temperature} must be positive and finite"
+            ))
+            .into());
+        }
+
+        let loss = self.ctx.create_buffer(rows)?;
+        let grad = self.ctx.create_buffer(rows * cols)?;
+        ops::kl_div(
+            &self.ctx,
+            &self.materialize()?.buffer,
+            &teacher.materialize()?.buffer,
+            &loss,
+            &grad,
+            rows,
+            cols,
+            temperature,
+        );
+
+        Ok((
+            Self {
+                buffer: loss,
+                layout: Layout::from_dimensions(&[rows]),
+                ctx: self.ctx.clone(),
+            },
+            Self {
+                buffer: grad,
+                layout: Layout::from_dimensions(self.dimensions()),
+                ctx: self.ctx.clone(),
+            },
+        ))
+    }
+
+    /// Fused diffusion sampler update: `y = a·x + b·eps + c·z` with `z ~ N(0, 1)`.
+    ///
+    /// `self` is the current sample `x_t` and `eps` the predicted noise; DDPM and DDIM steps
+    /// differ only in the coefficients. The noise `z` is drawn in-kernel from `seed` and is
+    /// skipped when `c` is zero.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidShape`] if `eps` has different dimensions.
+    /// - [`Error::Device`] if buffer allocation fails.
+    pub fn diffusion_step(
+        &self,
+        eps: &Self,
+        a: f32,
+        b: f32,
+        c: f32,
+        seed: u32,
+    ) -> Result<Self, Error> {
+        if eps.dimensions() != self.dimensions() {
+            return Err(TensorError::InvalidShape(format!(
+                "eps dimensions {:?} do not match {:?}",
+                eps.dimensions(),
+                self.dimensions()
+            ))
+            .into());
+        }
+
+        let buffer = self.ctx.create_buffer(self.layout.size())?;
+        let (x, eps) = (self.materialize()?, eps.materialize()?);
+        ops::diffusion_step(&self.ctx, &x.buffer, &eps.buffer, &buffer, a, b, c, seed);
+
+        Ok(Self {
+            buffer,
+            layout: x.layout,
+            ctx: self.ctx.clone(),
+        })
+    }
+
+    /// Dot product of the flat elements of `self` and `other`, as a rank 0 tensor.
+    ///
+    /// Each chunk of elements is multiplied and summed in one pass, and the chunk sums are
+    /// reduced like [`Self::sum_all`]. Both tensors must have the same dimensions.
+    pub(crate) fn dot(&self, other: &Self) -> Result<Self, Error> {
+        if other.dimensions() != self.dimensions() {
+            return Err(TensorError::InvalidShape(format!(
+                "dot product of {:?} and {:?}",
+                self.dimensions(),
+                other.dimensions()
+            ))
+            .into());
+        }
+
+        let (x, y) = (self.materialize()?, other.materialize()?);
+        if x.buffer.is_empty() {
+            return Self::scalar(&self.ctx, T::zeroed());
+        }
+
+        let chunks = x.buffer.len().div_ceil(full::CHUNK);
+        let partial = self.ctx.create_buffer(chunks)?;
+        ops::dot(&self.ctx, &x.buffer, &y.buffer, &partial);
+        let buffer = if chunks == 1 {
+            partial
+        } else {
+            compute::reduce_rows(&self.ctx, &partial, 1, chunks, FullReduction::Sum, None)?
+        };
+
+        Ok(Self {
+            buffer,
+            layout: Layout::from_dimensions(&[]),
+            ctx: self.ctx.clone(),
+        })
+    }
+
+    /// Adds `scale·alpha·x` to `self` in place, with `alpha` a one-element tensor that stays
+    /// on the GPU.
+    ///
+    /// `x` must have the dimensions of `self`.
+    pub(crate) fn axpy_(&mut self, alpha: &Self, scale: f32, x: &Self) -> Result<(), Error> {
+        if x.dimensions() != self.dimensions() || alpha.layout.size() != 1 {
+            return Err(TensorError::InvalidShape(format!(
+                "axpy of {:?} scaled by {:?} into {:?}",
+                x.dimensions(),
+                alpha.dimensions(),
+                self.dimensions()
+            ))
+            .into());
+        }
+
+        self.check_writable()?;
+
+        ops::axpy(
+            &self.ctx,
+            &alpha.materialize()?.buffer,
+            scale,
+            &x.materialize()?.buffer,
+            &self.buffer,
+        );
+
+        Ok(())
+    }
+
+    /// Merges batch moments into the running moments `self` (mean) and `var`, in place.
+    ///
+    /// Computes `var = running·var + batch·v_b + cross·(m_b - mean)²` and
+    /// `mean = running·mean + batch·m_b` in one kernel. All four tensors must have the same
+    /// dimensions.
+    pub(crate) fn merge_moments_(
+        &mut self,
+        var: &mut Self,
+        batch_mean: &Self,
+        batch_var: &Self,
+        running: f32,
+        batch: f32,
+        cross: f32,
+    ) -> Result<(), Error> {
+        for other in [&*var, batch_mean, batch_var] {
+            if other.dimensions() != self.dimensions() {
+                return Err(TensorError::InvalidShape(format!(
+                    "moment dimensions {:?} do not match {:?}",
+                    other.dimensions(),
+                    self.dimensions()
+                ))
+                .into());
+            }
+        }
+
+        self.check_writable()?;
+        var.check_writable()?;
+
+        ops::merge_moments(
+            &self.ctx,
+            &self.buffer,
+            &var.buffer,
+            &batch_mean.materialize()?.buffer,
+            &batch_var.materialize()?.buffer,
+            running,
+            batch,
+            cross,
+        );
+
+        Ok(())
+    }
+
+    /// Standardizes `self` with the moments `mean` and `var`, in one kernel.
+    ///
+    /// Computes `clamp((x - mean) / sqrt(var + eps), -clip, clip)`. The moments must have equal
+    /// dimensions broadcasting to those of `self`.
+    pub(crate) fn standardize(
+        &self,
+        mean: &Self,
+        var: &Self,
+        eps: f32,
+        clip: f32,
+    ) -> Result<Self, Error> {
+        let x = self.materialize()?;
+        let (mean, var) = (mean.materialize()?, var.materialize()?);
+        let dimensions = x.layout.dimensions();
+
+        let strides = (mean.dimensions() == var.dimensions())
+            .then(|| Layout::broadcast(&[&x.layout, &mean.layout]))
+            .flatten()
+            .filter(|(out_dims, _)| **out_dims == *dimensions)
+            .map(|(_, mut strides)| strides.swap_remove(1))
+            .ok_or_else(|| {
+                TensorError::InvalidShape(format!(
+                    "moments {:?} and {:?} do not broadcast to {dimensions:?}",
+                    mean.dimensions(),
+                    var.dimensions()
+                ))
+            })?;
+
+        let buffer = self.ctx.create_buffer(x.layout.size())?;
+        ops::standardize(
+            &self.ctx,
+            &x.buffer,
+            &mean.buffer,
+            &var.buffer,
+            &buffer,
+            dimensions,
+            &strides,
+            eps,
+            clip,
+        );
+
+        Ok(Self {
+            buffer,
+            layout: x.layout,
+            ctx: self.ctx.clone(),
+        })
+    }
+
     /// Applies an activation operation.
     fn nn_activation(
         &self,
         op: impl FnOnce(&Context, &Buffer<T>, &Buffer<T>),
     ) -> Result<Self, Error> {
-        let buffer = self.ctx.create_buffer(self.buffer.len())?;
-        op(&self.ctx, &self.buffer, &buffer);
+        let x = self.dense()?;
+        let buffer = self.ctx.create_buffer(x.buffer.len())?;
+        op(&self.ctx, &x.buffer, &buffer);
         Ok(Self {
             buffer,
-            layout: self.layout.clone(),
+            layout: x.layout,
             ctx: self.ctx.clone(),
         })
     }
@@ -958,22 +3270,23 @@ impl<T: LogicalElement> Tensor<T> {
         a: &Tensor<U>,
         b: &Tensor<U>,
     ) -> Result<Tensor<U>, Error> {
-        let (dimensions, strides) = Layout::broadcast(&[&self.layout, &a.layout, &b.layout])
+        let (condition, a, b) = (self.strided()?, a.strided()?, b.strided()?);
+        let (dimensions, strides) = Layout::broadcast(&[&condition.layout, &a.layout, &b.layout])
             .ok_or_else(|| {
-                TensorError::InvalidShape(format!(
-                    "dimensions {:?}, {:?}, and {:?} are not broadcast-compatible",
-                    self.dimensions(),
-                    a.dimensions(),
-                    b.dimensions()
-                ))
-            })?;
+            TensorError::InvalidShape(format!(
+                "dimensions {:?}, {:?}, and {:?} are not broadcast-compatible",
+                self.dimensions(),
+                a.dimensions(),
+                b.dimensions()
+            ))
+        })?;
 
-        let layout = Layout::from_dimensions(&dimensions)?;
+        let layout = Layout::from_dimensions(&dimensions);
         let buffer = self.ctx.create_buffer(layout.size())?;
 
         ops::select(
             &self.ctx,
-            &self.buffer,
+            &condition.buffer,
             &a.buffer,
             &b.buffer,
             &buffer,
@@ -1023,3 +3336,71 @@ impl<T: LogicalElement> Tensor<T> {
         self.math_unary(ops::not)
     }
 }
+
+/// Validates matmul operand shapes and returns the output dimensions.
+///
+/// # Errors
+///
+/// - [`TensorError::InvalidShape`] if ranks differ or are less than 2.
+/// - [`TensorError::InvalidShape`] if inner dimensions don't match.
+/// - [`TensorError::InvalidShape`] if batch dimensions are not broadcast-compatible.
+fn matmul_dimensions(
+    a_dims: &[usize],
+    b_dims: &[usize],
+    transpose_a: bool,
+    transpose_b: bool,
+) -> Result<Vec<usize>, Error> {
+    let rank = a_dims.len();
+
+    if rank < 2 || b_dims.len() < 2 {
+        return Err(
+            TensorError::InvalidShape("matmul requires tensors with rank >= 2".into()).into(),
+        );
+    }
+
+    if rank != b_dims.len() {
+        return Err(TensorError::InvalidShape(format!(
+            "matmul requires equal ranks, got {} and {}",
+            rank,
+            b_dims.len()
+        ))
+        .into());
+    }
+
+    let (a_rows, a_cols) = (a_dims[rank - 2], a_dims[rank - 1]);
+    let (b_rows, b_cols) = (b_dims[rank - 2], b_dims[rank - 1]);
+
+    let (m, a_k) = if transpose_a {
+        (a_cols, a_rows)
+    } else {
+        (a_rows, a_cols)
+    };
+    let (b_k, n) = if transpose_b {
+        (b_cols, b_rows)
+    } else {
+        (b_rows, b_cols)
+    };
+
+    if a_k != b_k {
+        return Err(TensorError::InvalidShape(format!(
+            "matmul inner dimensions don't match: {a_k} vs {b_k}"
+        ))
+        .into());
+    }
+
+    let mut out_dims: Vec<usize> = a_dims[..rank - 2]
+        .iter()
+        .zip(&b_dims[..rank - 2])
+        .map(|(&da, &db)| match (da, db) {
+            (a, b) if a == b => Ok(a),
+            (1, b) => Ok(b),
+            (a, 1) => Ok(a),
+            _ => Err(TensorError::InvalidShape(format!(
+                "batch dimensions not broadcast-compatible: {da} vs {db}"
+            ))),
+        })
+        .collect::<Result<_, _>>()?;
+    out_dims.extend([m, n]);
+
+    Ok(out_dims)
+}
@@ -0,0 +1,64 @@
+//! Mixture-of-experts routing.
+
+use alloc::format;
+
+use crate::error::{Error, TensorError};
+use crate::kernel::ops;
+use crate::tensor::Tensor;
+use crate::tensor::layout::Layout;
+
+impl Tensor<u32> {
+    /// Assigns the `[tokens, k]` expert choices in `self` to `experts` groups of `capacity`
+    /// slots.
+    ///
+    /// Returns `(slots, sources, counts)`: the `[tokens, k]` flat slot of each choice, or
+    /// `experts * capacity` if the expert was full; the `[experts, capacity]` token held by
+    /// each slot, or `tokens` if it is empty; and the `[experts]` number of tokens accepted.
+    /// Choices are served every token's first choice first, then every second choice, each
+    /// in token order. Every choice must be less than `experts` and `capacity` non-zero.
+    pub(crate) fn route_experts(
+        &self,
+        experts: usize,
+        capacity: usize,
+    ) -> Result<(Self, Self, Self), Error> {
+        let &[tokens, k] = self.dimensions() else {
+            return Err(TensorError::InvalidShape(format!(
+                "expert choices must be rank 2, got {:?}",
+                self.dimensions()
+            ))
+            .into());
+        };
+
+        let slot_layout = Layout::from_dimensions(&[tokens, k]);
+        let source_layout = Layout::from_dimensions(&[experts, capacity]);
+        let count_layout = Layout::from_dimensions(&[experts]);
+
+        let slots = self.ctx.create_buffer(slot_layout.size())?;
+        let sources = self.ctx.create_buffer(source_layout.size())?;
+        let counts = self.ctx.create_buffer(count_layout.size())?;
+
+        let choices = self.materialize()?;
+        ops::moe_route(
+            &self.ctx,
+            &choices.buffer,
+            &slots,
+            &sources,
+            &counts,
+            tokens,
+            k,
+            experts,
+            capacity,
+        );
+
+        let wrap = |buffer, layout| Self {
+            buffer,
+            layout,
+            ctx: self.ctx.clone(),
+        };
+        Ok((
+            wrap(slots, slot_layout),
+            wrap(sources, source_layout),
+            wrap(counts, count_layout),
+        ))
+    }
+}
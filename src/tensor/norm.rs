@@ -0,0 +1,67 @@
+//! Vector norms.
+
+use alloc::format;
+
+use crate::error::{Error, TensorError};
+use crate::tensor::Tensor;
+
+impl Tensor<f32> {
+    /// `p`-norm along `axes`: `(Σ |x|ᵖ)^(1/p)`, or the largest magnitude for
+    /// `p = f32::INFINITY`.
+    ///
+    /// Output shape equals input shape with reduced axes set to 1, so the result broadcasts
+    /// back against `self`. The L1, L2 and infinity norms take dedicated paths without `pow`.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidArgument`] if `p` is not positive.
+    /// - [`TensorError::InvalidShape`] if axes are invalid or duplicate.
+    /// - [`Error::Device`] if GPU operation fails.
+    #[allow(clippy::float_cmp)]
+    pub fn norm(&self, p: f32, axes: &[usize]) -> Result<Self, Error> {
+        if p.is_nan() || p <= 0.0 {
+            return Err(
+                TensorError::InvalidArgument(format!("norm order {p} must be positive")).into(),
+            );
+        }
+
+        if p == f32::INFINITY {
+            return self.abs()?.max_reduce(axes);
+        }
+        if p == 1.0 {
+            return self.abs()?.sum_reduce(axes, false);
+        }
+        if p == 2.0 {
+            return self.sqr()?.sum_reduce(axes, false)?.sqrt();
+        }
+
+        let scalar = |value: f32| Self::constant(&self.ctx, &[1], &[value]);
+        self.abs()?
+            .pow(&scalar(p)?)?
+            .sum_reduce(axes, false)?
+            .pow(&scalar(p.recip())?)
+    }
+
+    /// Scales `self` to unit L2 norm along `axes`: `x / max(‖x‖₂, eps)`.
+    ///
+    /// `eps` keeps all-zero slices at zero instead of dividing by zero, as in `PyTorch`'s
+    /// `F.normalize`. Rows of an `[n, d]` embedding normalized along axis 1 give cosine
+    /// similarities as plain dot products.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidArgument`] if `eps` is negative or not finite.
+    /// - [`TensorError::InvalidShape`] if axes are invalid or duplicate.
+    /// - [`Error::Device`] if GPU operation fails.
+    pub fn normalize(&self, axes: &[usize], eps: f32) -> Result<Self, Error> {
+        if !(eps.is_finite() && eps >= 0.0) {
+            return Err(TensorError::InvalidArgument(format!(
+                "eps {eps} must be non-negative and finite"
+            ))
+            .into());
+        }
+
+        let eps = Self::constant(&self.ctx, &[1], &[eps])?;
+        self.div(&self.norm(2.0, axes)?.max(&eps)?)
+    }
+}
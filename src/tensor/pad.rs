@@ -0,0 +1,107 @@
+//! Padding of tensor borders.
+
+use alloc::format;
+use alloc::vec::Vec;
+
+use crate::Element;
+use crate::error::{Error, TensorError};
+use crate::kernel::ops;
+use crate::kernel::pad::{MODE_CONSTANT, MODE_REFLECT, MODE_REPLICATE};
+use crate::tensor::Tensor;
+use crate::tensor::layout::Layout;
+
+/// How [`Tensor::pad`] fills the added border.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PadMode<T> {
+    /// Fills the border with a constant value.
+    Constant(T),
+    /// Mirrors the input at its edges without repeating the edge element, so `[1, 2, 3]`
+    /// padded by two on each side gives `[3, 2, 1, 2, 3, 2, 1]`.
+    Reflect,
+    /// Repeats the edge element, so `[1, 2, 3]` padded by two on each side gives
+    /// `[1, 1, 1, 2, 3, 3, 3]`.
+    Replicate,
+}
+
+impl<T: Element> Tensor<T> {
+    /// Pads every axis by `padding[axis] = (before, after)` elements.
+    ///
+    /// Axis `i` of the result has size `before + dimensions[i] + after`. Pass `(0, 0)` for
+    /// axes that keep their size, e.g. `[(0, 0), (0, 0), (1, 1), (1, 1)]` pads the spatial
+    /// axes of an `[n, c, h, w]` batch by one pixel.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidShape`] if `padding` does not have one entry per axis or a padded
+    ///   size overflows.
+    /// - [`TensorError::InvalidArgument`] if reflect padding is not smaller than its axis.
+    /// - [`Error::Device`] if buffer allocation fails.
+    pub fn pad(&self, padding: &[(usize, usize)], mode: PadMode<T>) -> Result<Self, Error> {
+        let dimensions = self.dimensions();
+        if padding.len() != dimensions.len() {
+            return Err(TensorError::InvalidShape(format!(
+                "padding {padding:?} does not match tensor with rank {}",
+                dimensions.len()
+            ))
+            .into());
+        }
+
+        if matches!(mode, PadMode::Reflect) {
+            let exceeds = dimensions
+                .iter()
+                .zip(padding)
+                .find(|&(&size, &(before, after))| before >= size || after >= size);
+            if let Some((size, padding)) = exceeds {
+                return Err(TensorError::InvalidArgument(format!(
+                    "reflect padding {padding:?} must be smaller than axis size {size}"
+                ))
+                .into());
+            }
+        }
+
+        let padded = dimensions
+            .iter()
+            .zip(padding)
+            .map(|(&size, &(before, after))| {
+                size.checked_add(before)
+                    .and_then(|size| size.checked_add(after))
+            })
+            .collect::<Option<Vec<usize>>>()
+            .ok_or_else(|| {
+                TensorError::InvalidShape(format!(
+                    "padding {padding:?} overflows dimensions {dimensions:?}"
+                ))
+            })?;
+        let before: Vec<usize> = padding.iter().map(|&(before, _)| before).collect();
+
+        let layout = Layout::from_dimensions(&padded);
+        let buffer = self.ctx.create_buffer(layout.size())?;
+
+        let mode = match mode {
+            PadMode::Constant(value) => {
+                ops::constant(&self.ctx, &buffer, value);
+                MODE_CONSTANT
+            }
+            PadMode::Reflect => MODE_REFLECT,
+            PadMode::Replicate => MODE_REPLICATE,
+        };
+
+        ops::pad(
+            &self.ctx,
+            &self.buffer,
+            &buffer,
+            self.layout.strides(),
+            layout.strides(),
+            dimensions,
+            &before,
+            self.layout.offset(),
+            mode,
+        );
+
+        Ok(Self {
+            buffer,
+            layout,
+            ctx: self.ctx.clone(),
+        })
+    }
+}
@@ -0,0 +1,176 @@
+//! Einops-style axis rearrangement.
+
+use alloc::format;
+use alloc::vec::Vec;
+
+use crate::Element;
+use crate::error::{Error, TensorError};
+use crate::tensor::Tensor;
+
+impl<T: Element> Tensor<T> {
+    /// Reorders, splits and merges axes as described by an einops-style `pattern`.
+    ///
+    /// The pattern names every axis of the input, then `->`, then every axis of the output:
+    /// `"b h w c -> b c h w"` is a permute. Parenthesized names form one axis, so
+    /// `"b c h w -> b (c h w)"` flattens and `"b (h w) c -> b c h w"` splits. The size of a
+    /// split axis is taken from `sizes`, e.g. `&[("h", 4)]`; one name per group may be left
+    /// out and is inferred. Permutes and splits are views; merging axes that are not adjacent
+    /// in memory copies, as in [`Self::reshape`].
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidArgument`] if the pattern is malformed, a name repeats on one
+    ///   side or appears on only one side, or a size is given for an unknown name.
+    /// - [`TensorError::InvalidShape`] if the input side does not match the rank of `self`,
+    ///   or a group's sizes do not match its axis.
+    /// - [`Error::Device`] if a merge needs a copy and buffer allocation fails.
+    pub fn rearrange(&self, pattern: &str, sizes: &[(&str, usize)]) -> Result<Self, Error> {
+        let (input, output) = pattern.split_once("->").ok_or_else(|| {
+            TensorError::InvalidArgument(format!("pattern {pattern:?} has no \"->\""))
+        })?;
+        let input = parse_side(input, pattern)?;
+        let output = parse_side(output, pattern)?;
+
+        let dimensions = self.dimensions();
+        if input.len() != dimensions.len() {
+            return Err(TensorError::InvalidShape(format!(
+                "pattern {pattern:?} has {} input axes for tensor {dimensions:?}",
+                input.len()
+            ))
+            .into());
+        }
+
+        let names: Vec<&str> = input.iter().flatten().copied().collect();
+        let targets: Vec<&str> = output.iter().flatten().copied().collect();
+        let mut sorted_names = names.clone();
+        let mut sorted_targets = targets.clone();
+        sorted_names.sort_unstable();
+        sorted_targets.sort_unstable();
+        if sorted_names.windows(2).any(|w| w[0] == w[1]) || sorted_names != sorted_targets {
+            return Err(TensorError::InvalidArgument(format!(
+                "pattern {pattern:?} must name each axis exactly once on both sides"
+            ))
+            .into());
+        }
+
+        if let Some((name, _)) = sizes.iter().find(|(name, _)| !names.contains(name)) {
+            return Err(TensorError::InvalidArgument(format!(
+                "size given for {name:?}, which is not in pattern {pattern:?}"
+            ))
+            .into());
+        }
+
+        let mut expanded = Vec::with_capacity(names.len());
+        for (group, &size) in input.iter().zip(dimensions) {
+            expanded.extend(group_sizes(group, size, sizes, pattern)?);
+        }
+
+        let axes: Vec<usize> = targets
+            .iter()
+            .map(|target| names.iter().position(|name| name == target).unwrap_or(0))
+            .collect();
+        let merged: Vec<usize> = output
+            .iter()
+            .map(|group| {
+                group
+                    .iter()
+                    .map(|name| expanded[names.iter().position(|n| n == name).unwrap_or(0)])
+                    .product()
+            })
+            .collect();
+
+        let mut x = if expanded == dimensions {
+            self.with_layout(self.layout.clone())
+        } else {
+            self.reshape(&expanded)?
+        };
+        x = x.permute(&axes)?;
+        if merged != x.dimensions() {
+            x = x.reshape(&merged)?;
+        }
+
+        Ok(x)
+    }
+}
+
+/// Parses one side of a rearrange pattern into axis groups of names.
+fn parse_side<'a>(side: &'a str, pattern: &str) -> Result<Vec<Vec<&'a str>>, Error> {
+    let malformed = || {
+        Error::from(TensorError::InvalidArgument(format!(
+            "malformed pattern {pattern:?}"
+        )))
+    };
+
+    let mut groups = Vec::new();
+    let mut open: Option<Vec<&str>> = None;
+    let mut start = None;
+
+    for (i, c) in side.char_indices().chain([(side.len(), ' ')]) {
+        if c.is_alphanumeric() || c == '_' {
+            start.get_or_insert(i);
+            continue;
+        }
+
+        if let Some(start) = start.take() {
+            let name = &side[start..i];
+            if name.starts_with(|c: char| c.is_ascii_digit()) {
+                return Err(malformed());
+            }
+            match &mut open {
+                Some(group) => group.push(name),
+                None => groups.push(Vec::from([name])),
+            }
+        }
+
+        match c {
+            '(' if open.is_none() => open = Some(Vec::new()),
+            ')' => groups.push(open.take().ok_or_else(malformed)?),
+            c if c.is_whitespace() => {}
+            _ => return Err(malformed()),
+        }
+    }
+
+    if open.is_some() {
+        return Err(malformed());
+    }
+
+    Ok(groups)
+}
+
+/// Sizes of the names in `group` for an axis of `size`, inferring at most one unknown name.
+fn group_sizes(
+    group: &[&str],
+    size: usize,
+    sizes: &[(&str, usize)],
+    pattern: &str,
+) -> Result<Vec<usize>, Error> {
+    let known = |name: &str| {
+        sizes
+            .iter()
+            .find(|(n, _)| *n == name)
+            .map(|&(_, size)| size)
+    };
+    let mismatch = || {
+        Error::from(TensorError::InvalidShape(format!(
+            "cannot split axis of size {size} into {group:?} in pattern {pattern:?}"
+        )))
+    };
+
+    let unknown = group.iter().filter(|name| known(name).is_none()).count();
+    let product = group
+        .iter()
+        .filter_map(|name| known(name))
+        .try_fold(1usize, usize::checked_mul)
+        .ok_or_else(mismatch)?;
+
+    let inferred = match unknown {
+        0 if product == size => None,
+        1 if product != 0 && size.is_multiple_of(product) => Some(size / product),
+        _ => return Err(mismatch()),
+    };
+
+    Ok(group
+        .iter()
+        .map(|name| known(name).or(inferred).unwrap_or(size))
+        .collect())
+}
@@ -0,0 +1,152 @@
+//! Reinforcement learning primitives.
+
+use alloc::format;
+
+use crate::element::FloatElement;
+use crate::error::{Error, TensorError};
+use crate::kernel::ops;
+use crate::tensor::Tensor;
+
+impl<T: FloatElement> Tensor<T> {
+    /// Generalized advantage estimation over the `[steps, envs]` rewards in `self`, returning
+    /// `(advantages, returns)`.
+    ///
+    /// `values` holds the value estimates `V_t` and `dones` is one where the transition at
+    /// step `t` ended its episode, zero otherwise. `last_values` is the `[envs]` value of the
+    /// state after the final step, used to bootstrap unfinished episodes. With
+    /// `δ_t = r_t + γ·(1 - d_t)·V_{t+1} - V_t`, the advantages are
+    /// `A_t = δ_t + γλ·(1 - d_t)·A_{t+1}` and the returns `A_t + V_t`.
+    ///
+    /// The reverse scan runs in a single kernel with one thread per environment.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidShape`] if `self` is not rank 2, `values` or `dones` differ
+    ///   from it, or `last_values` is not `[envs]`.
+    /// - [`TensorError::InvalidArgument`] if `gamma` or `lambda` is not in `[0, 1]`.
+    /// - [`Error::Device`] if buffer allocation fails.
+    pub fn gae(
+        &self,
+        values: &Self,
+        dones: &Self,
+        last_values: &Self,
+        gamma: f32,
+        lambda: f32,
+    ) -> Result<(Self, Self), Error> {
+        let dimensions = self.dimensions();
+        let &[_, envs] = dimensions else {
+            return Err(TensorError::InvalidShape(format!(
+                "rewards must be [steps, envs], got {dimensions:?}"
+            ))
+            .into());
+        };
+
+        if values.dimensions() != dimensions
+            || dones.dimensions() != dimensions
+            || last_values.dimensions() != [envs]
+        {
+            return Err(TensorError::InvalidShape(format!(
+                "values {:?} and dones {:?} must be {dimensions:?} and last values {:?} [{envs}]",
+                values.dimensions(),
+                dones.dimensions(),
+                last_values.dimensions()
+            ))
+            .into());
+        }
+
+        if !(0.0..=1.0).contains(&gamma) || !(0.0..=1.0).contains(&lambda) {
+            return Err(TensorError::InvalidArgument(format!(
+                "gamma {gamma} and lambda {lambda} must be in [0, 1]"
+            ))
+            .into());
+        }
+
+        let rewards = self.materialize()?;
+        let advantages = self.ctx.create_buffer(rewards.buffer.len())?;
+        let returns = self.ctx.create_buffer(rewards.buffer.len())?;
+
+        ops::gae(
+            &self.ctx,
+            &rewards.buffer,
+            &values.materialize()?.buffer,
+            &dones.materialize()?.buffer,
+            &last_values.materialize()?.buffer,
+            &advantages,
+            &returns,
+            gamma,
+            lambda,
+        );
+
+        Ok((
+            Self {
+                buffer: advantages,
+                layout: rewards.layout.clone(),
+                ctx: self.ctx.clone(),
+            },
+            Self {
+                buffer: returns,
+                layout: rewards.layout,
+                ctx: self.ctx.clone(),
+            },
+        ))
+    }
+
+    /// Clipped PPO surrogate loss of the probability ratios `r = π(a|s) / π_old(a|s)` in
+    /// `self` and `advantages` of equal dimensions, returning `(loss, grad)` per element.
+    ///
+    /// With `c = clamp(r, 1 - ε, 1 + ε)` for `epsilon` ε, the loss is the negated objective
+    /// `-min(r·A, c·A)`, to be minimized, and `grad` its derivative with respect to `r`: `-A`
+    /// where the unclipped term is the minimum, zero where clipping cuts the gradient off.
+    /// Multiply `grad` by `r` for the gradient with respect to the log-probabilities.
+    ///
+    /// Both outputs come from a single kernel, in place of separate element-wise operations.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidShape`] if `advantages` differs from `self` in dimensions.
+    /// - [`TensorError::InvalidArgument`] if `epsilon` is not non-negative and finite.
+    /// - [`Error::Device`] if buffer allocation fails.
+    pub fn ppo_clip(&self, advantages: &Self, epsilon: f32) -> Result<(Self, Self), Error> {
+        if advantages.dimensions() != self.dimensions() {
+            return Err(TensorError::InvalidShape(format!(
+                "advantages {:?} must match ratios {:?}",
+                advantages.dimensions(),
+                self.dimensions()
+            ))
+            .into());
+        }
+
+        if !(epsilon.is_finite() && epsilon >= 0.0) {
+            return Err(TensorError::InvalidArgument(format!(
+                "clip epsilon {epsilon} must be non-negative and finite"
+            ))
+            .into());
+        }
+
+        let ratio = self.materialize()?;
+        let loss = self.ctx.create_buffer(ratio.buffer.len())?;
+        let grad = self.ctx.create_buffer(ratio.buffer.len())?;
+
+        ops::ppo_clip(
+            &self.ctx,
+            &ratio.buffer,
+            &advantages.materialize()?.buffer,
+            &loss,
+            &grad,
+            epsilon,
+        );
+
+        Ok((
+            Self {
+                buffer: loss,
+                layout: ratio.layout.clone(),
+                ctx: self.ctx.clone(),
+            },
+            Self {
+                buffer: grad,
+                layout: ratio.layout,
+                ctx: self.ctx.clone(),
+            },
+        ))
+    }
+}
@@ -0,0 +1,123 @@
+//! Time-series window and lag operations along the leading time axis.
+
+use alloc::format;
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::Element;
+use crate::element::FloatElement;
+use crate::error::{Error, TensorError};
+use crate::kernel::ops;
+use crate::tensor::{PadMode, Tensor};
+
+impl<T: FloatElement> Tensor<T> {
+    /// Mean over a trailing window of `window` steps along axis 0, the time axis.
+    ///
+    /// Output `[t, ...]` averages inputs `[t - window + 1 ..= t, ...]`, so the result keeps the
+    /// dimensions of `self` and each series along the other axes is rolled independently. The
+    /// first `window - 1` steps have an incomplete window and are `NaN`.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidShape`] if `self` is a scalar.
+    /// - [`TensorError::InvalidArgument`] if `window` is zero.
+    /// - [`Error::Device`] if buffer allocation fails.
+    pub fn rolling_mean(&self, window: usize) -> Result<Self, Error> {
+        self.rolling(window, None)
+    }
+
+    /// Standard deviation over a trailing window of `window` steps along axis 0, the time axis.
+    ///
+    /// Windows are taken as in [`Self::rolling_mean`]. With `correction`, the squared
+    /// deviations are divided by `window - 1` (Bessel's correction) instead of `window`.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidShape`] if `self` is a scalar.
+    /// - [`TensorError::InvalidArgument`] if `window` is zero.
+    /// - [`Error::Device`] if buffer allocation fails.
+    pub fn rolling_std(&self, window: usize, correction: bool) -> Result<Self, Error> {
+        self.rolling(window, Some(u32::from(correction)))
+    }
+
+    /// Runs the rolling window kernel, computing the standard deviation if `correction` is
+    /// given.
+    fn rolling(&self, window: usize, correction: Option<u32>) -> Result<Self, Error> {
+        let Some(&steps) = self.dimensions().first() else {
+            return Err(TensorError::InvalidShape("rolling requires rank >= 1".into()).into());
+        };
+
+        if window == 0 {
+            return Err(TensorError::InvalidArgument("window must be positive".into()).into());
+        }
+
+        let x = self.materialize()?;
+        let inner = x.layout.size().checked_div(steps).unwrap_or(0);
+        let buffer = self.ctx.create_buffer(x.layout.size())?;
+
+        ops::rolling(&self.ctx, &x.buffer, &buffer, inner, window, correction);
+
+        Ok(Self {
+            buffer,
+            layout: x.layout,
+            ctx: self.ctx.clone(),
+        })
+    }
+}
+
+impl<T: Element> Tensor<T> {
+    /// Shifts `self` by `periods` steps along axis 0, the time axis, filling vacated steps
+    /// with `fill`.
+    ///
+    /// Positive periods lag the series, `y[t] = x[t - periods]`, so each step sees an earlier
+    /// value; negative periods lead it. The result keeps the dimensions of `self`.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidShape`] if `self` is a scalar.
+    /// - [`Error::Device`] if buffer allocation fails.
+    pub fn shift(&self, periods: isize, fill: T) -> Result<Self, Error> {
+        let Some(&steps) = self.dimensions().first() else {
+            return Err(TensorError::InvalidShape("shift requires rank >= 1".into()).into());
+        };
+
+        let moved = periods.unsigned_abs().min(steps);
+        let (start, before, after) = if periods >= 0 {
+            (0, moved, 0)
+        } else {
+            (moved, 0, moved)
+        };
+
+        let mut padding = vec![(0, 0); self.dimensions().len()];
+        padding[0] = (before, after);
+
+        self.narrow(0, start, steps - moved)?
+            .pad(&padding, PadMode::Constant(fill))
+    }
+
+    /// Lag features: the series shifted by each of `periods`, stacked along a new last axis.
+    ///
+    /// A `[steps, ...]` tensor gives `[steps, ..., periods.len()]`, where feature `k` is
+    /// [`Self::shift`] by `periods[k]` with vacated steps set to `fill`.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidShape`] if `self` is a scalar.
+    /// - [`TensorError::InvalidArgument`] if `periods` is empty or a period exceeds
+    ///   `isize::MAX`.
+    /// - [`Error::Device`] if buffer allocation fails.
+    pub fn lags(&self, periods: &[usize], fill: T) -> Result<Self, Error> {
+        let shifted = periods
+            .iter()
+            .map(|&period| {
+                let period = isize::try_from(period).map_err(|_| {
+                    TensorError::InvalidArgument(format!("lag {period} exceeds isize::MAX"))
+                })?;
+                self.shift(period, fill)
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        let shifted: Vec<&Self> = shifted.iter().collect();
+        Self::stack(&shifted, self.dimensions().len())
+    }
+}
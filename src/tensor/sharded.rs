@@ -0,0 +1,166 @@
+//! Weight matrices split across buffers to fit storage binding limits.
+
+use alloc::format;
+use alloc::vec::Vec;
+
+use crate::Context;
+use crate::error::{Error, TensorError};
+use crate::tensor::Tensor;
+
+/// Row-major `[k, n]` matrix stored as consecutive row blocks in separate buffers.
+///
+/// Lets weights slightly larger than a single storage binding (128 MiB on many browsers) still
+/// be used: [`Self::matmul`] multiplies by each block in turn and sums the partial products.
+pub struct ShardedMatrix {
+    /// Row blocks of the matrix, in order.
+    shards: Vec<Tensor<f32>>,
+    /// Full matrix dimensions `[k, n]`.
+    dimensions: [usize; 2],
+}
+
+impl ShardedMatrix {
+    /// Uploads a `[k, n]` matrix in as few row blocks as fit the context's binding limit.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidShape`] if `dimensions` is not rank 2, does not match the data
+    ///   length, or a single row exceeds the binding limit.
+    /// - [`Error::Device`] if buffer allocation fails.
+    pub fn from_shape_slice(
+        ctx: &Context,
+        dimensions: &[usize],
+        data: &[f32],
+    ) -> Result<Self, Error> {
+        let &[_, n] = dimensions else {
+            return Err(matrix_rank_error(dimensions));
+        };
+
+        // Leave room for the padding of the last few elements of each buffer.
+        let row_bytes = (n * size_of::<f32>()) as u64;
+        let budget = ctx
+            .max_binding_size()
+            .saturating_sub(4 * size_of::<f32>() as u64);
+        let rows = usize::try_from(budget / row_bytes.max(1)).unwrap_or(usize::MAX);
+
+        if rows == 0 {
+            return Err(TensorError::InvalidShape(format!(
+                "row of {n} columns exceeds the binding limit of {} bytes",
+                ctx.max_binding_size()
+            ))
+            .into());
+        }
+
+        Self::from_shape_slice_with_rows(ctx, dimensions, data, rows)
+    }
+
+    /// Uploads a `[k, n]` matrix in row blocks of at most `rows` rows.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidShape`] if `dimensions` is not rank 2 or does not match the data
+    ///   length.
+    /// - [`TensorError::InvalidArgument`] if `rows` is zero.
+    /// - [`Error::Device`] if buffer allocation fails.
+    pub fn from_shape_slice_with_rows(
+        ctx: &Context,
+        dimensions: &[usize],
+        data: &[f32],
+        rows: usize,
+    ) -> Result<Self, Error> {
+        let &[k, n] = dimensions else {
+            return Err(matrix_rank_error(dimensions));
+        };
+
+        if k.checked_mul(n) != Some(data.len()) || data.is_empty() {
+            return Err(TensorError::InvalidShape(format!(
+                "data length {} does not match dimensions {dimensions:?}",
+                data.len()
+            ))
+            .into());
+        }
+
+        if rows == 0 {
+            return Err(TensorError::InvalidArgument("rows must be positive".into()).into());
+        }
+
+        let shards = data
+            .chunks(rows.saturating_mul(n))
+            .map(|block| Tensor::from_shape_slice(ctx, &[block.len() / n, n], block))
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self {
+            shards,
+            dimensions: [k, n],
+        })
+    }
+
+    /// Returns the full matrix dimensions `[k, n]`.
+    #[must_use]
+    pub fn dimensions(&self) -> &[usize] {
+        &self.dimensions
+    }
+
+    /// Returns the row blocks, in order.
+    #[must_use]
+    pub fn shards(&self) -> &[Tensor<f32>] {
+        &self.shards
+    }
+
+    /// Multiplies `x` by the matrix: `X[..., m, k] × W[k, n] → Y[..., m, n]`.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidShape`] if `x` has rank less than 2 or its last dimension is
+    ///   not `k`.
+    /// - [`Error::Device`] if GPU operation fails.
+    pub fn matmul(&self, x: &Tensor<f32>) -> Result<Tensor<f32>, Error> {
+        let [k, _] = self.dimensions;
+        let x_dims = x.layout.dimensions();
+        let rank = x_dims.len();
+
+        if rank < 2 || x_dims[rank - 1] != k {
+            return Err(TensorError::InvalidShape(format!(
+                "dimensions {x_dims:?} are not compatible with a [{k}, _] matrix"
+            ))
+            .into());
+        }
+
+        let stride = x.layout.strides()[rank - 1];
+        let mut start = 0;
+        let mut result: Option<Tensor<f32>> = None;
+
+        for shard in &self.shards {
+            let shard_dims = shard.dimensions();
+            let rows = shard_dims[0];
+
+            let mut weight_dims = alloc::vec![1; rank - 2];
+            weight_dims.extend(shard_dims);
+            let weight = shard.reshape(&weight_dims)?;
+
+            let partial = if self.shards.len() == 1 {
+                x.matmul(&weight, false, false)?
+            } else {
+                let mut dims = x_dims.to_vec();
+                dims[rank - 1] = rows;
+                x.strided_copy(&dims, x.layout.strides(), start * stride, &dims)?
+                    .matmul(&weight, false, false)?
+            };
+
+            result = Some(match result {
+                Some(sum) => sum.add(&partial)?,
+                None => partial,
+            });
+            start += rows;
+        }
+
+        result.ok_or_else(|| TensorError::InvalidShape("matrix has no shards".into()).into())
+    }
+}
+
+/// Error for dimensions that do not describe a matrix.
+fn matrix_rank_error(dimensions: &[usize]) -> Error {
+    TensorError::InvalidShape(format!(
+        "sharded matrix requires rank 2 dimensions, got {dimensions:?}"
+    ))
+    .into()
+}
@@ -0,0 +1,135 @@
+//! Spatial sampling and matching for spatial transformers, optical flow and stereo.
+
+use alloc::format;
+
+use crate::error::{Error, TensorError};
+use crate::kernel::ops;
+use crate::kernel::spatial::MAX_DISPLACEMENT;
+use crate::tensor::Tensor;
+use crate::tensor::layout::Layout;
+
+/// Interpolation used when sampling between pixel centers.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Interpolation {
+    /// Weighted average of the four nearest pixels.
+    #[default]
+    Bilinear,
+    /// Value of the nearest pixel, rounding halves to even.
+    Nearest,
+}
+
+impl Tensor<f32> {
+    /// Samples `[n, c, h, w]` input `self` at the locations of a `[n, out_h, out_w, 2]` grid.
+    ///
+    /// Each grid entry is an `(x, y)` location normalized to `[-1, 1]`, where `-1` and `1`
+    /// are the outer edges of the first and last pixels. Locations outside the input read
+    /// zero padding. Returns `[n, c, out_h, out_w]`; an affine grid gives a spatial
+    /// transformer, and a base grid plus normalized flow warps an image by optical flow.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidShape`] if `self` is not rank 4 or `grid` is not
+    ///   `[n, out_h, out_w, 2]`.
+    /// - [`Error::Device`] if buffer allocation fails.
+    pub fn grid_sample(&self, grid: &Self, mode: Interpolation) -> Result<Self, Error> {
+        let &[n, c, h, w] = self.dimensions() else {
+            return Err(TensorError::InvalidShape(format!(
+                "input must have dimensions [n, c, h, w], got {:?}",
+                self.dimensions()
+            ))
+            .into());
+        };
+
+        let &[grid_n, out_h, out_w, 2] = grid.dimensions() else {
+            return Err(TensorError::InvalidShape(format!(
+                "grid must have dimensions [n, out_h, out_w, 2], got {:?}",
+                grid.dimensions()
+            ))
+            .into());
+        };
+
+        if grid_n != n {
+            return Err(TensorError::InvalidShape(format!(
+                "grid batch {grid_n} does not match input batch {n}"
+            ))
+            .into());
+        }
+
+        let layout = Layout::from_dimensions(&[n, c, out_h, out_w]);
+        let buffer = self.ctx.create_buffer(layout.size())?;
+
+        ops::grid_sample(
+            &self.ctx,
+            &self.materialize()?.buffer,
+            &grid.materialize()?.buffer,
+            &buffer,
+            [n, c, h, w],
+            (out_h, out_w),
+            mode == Interpolation::Nearest,
+        );
+
+        Ok(Self {
+            buffer,
+            layout,
+            ctx: self.ctx.clone(),
+        })
+    }
+
+    /// Local correlation volume between `[n, c, h, w]` feature maps `self` and `other`.
+    ///
+    /// For every pixel and every displacement `(dx, dy)` with `|dx|, |dy| <= max_displacement`,
+    /// computes the channel mean of `self[.., y, x] · other[.., y + dy, x + dx]`, reading zero
+    /// outside `other`. Returns `[n, (2d + 1)², h, w]` with displacements in row-major
+    /// `(dy, dx)` order, the cost volume of flow and stereo networks.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidShape`] if `self` is not rank 4 or `other` has different
+    ///   dimensions.
+    /// - [`TensorError::InvalidArgument`] if `max_displacement` exceeds 15.
+    /// - [`Error::Device`] if buffer allocation fails.
+    pub fn correlation(&self, other: &Self, max_displacement: usize) -> Result<Self, Error> {
+        let &[n, c, h, w] = self.dimensions() else {
+            return Err(TensorError::InvalidShape(format!(
+                "features must have dimensions [n, c, h, w], got {:?}",
+                self.dimensions()
+            ))
+            .into());
+        };
+
+        if other.dimensions() != self.dimensions() {
+            return Err(TensorError::InvalidShape(format!(
+                "feature dimensions {:?} do not match {:?}",
+                other.dimensions(),
+                self.dimensions()
+            ))
+            .into());
+        }
+
+        if max_displacement > MAX_DISPLACEMENT {
+            return Err(TensorError::InvalidArgument(format!(
+                "max displacement {max_displacement} exceeds {MAX_DISPLACEMENT}"
+            ))
+            .into());
+        }
+
+        let span = 2 * max_displacement + 1;
+        let layout = Layout::from_dimensions(&[n, span * span, h, w]);
+        let buffer = self.ctx.create_buffer(layout.size())?;
+
+        ops::correlation(
+            &self.ctx,
+            &self.materialize()?.buffer,
+            &other.materialize()?.buffer,
+            &buffer,
+            [n, c, h, w],
+            max_displacement,
+        );
+
+        Ok(Self {
+            buffer,
+            layout,
+            ctx: self.ctx.clone(),
+        })
+    }
+}
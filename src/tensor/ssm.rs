@@ -0,0 +1,123 @@
+//! Selective scan of state-space models.
+
+use alloc::format;
+
+use crate::element::FloatElement;
+use crate::error::{Error, TensorError};
+use crate::kernel::ops;
+use crate::tensor::Tensor;
+use crate::tensor::layout::Layout;
+
+impl<T: FloatElement> Tensor<T> {
+    /// Selective scan of a diagonal state-space model over the `[batch, channels, length]`
+    /// input sequences in `self`, as in Mamba, returning `(y, state)`.
+    ///
+    /// Each channel carries `states` hidden states, discretized per step by the step sizes
+    /// `delta` of the input's dimensions:
+    /// `h_t = exp(Δ_t·A)·h_{t-1} + Δ_t·B_t·x_t` and `y_t = Σ C_t·h_t + D·x_t`, summed over the
+    /// states. `a` is the `[channels, states]` state matrix, usually negative; `b` and `c` are
+    /// the input-dependent `[batch, states, length]` projections shared by all channels; the
+    /// optional `d` is the `[channels]` skip weight. The scan starts from the
+    /// `[batch, channels, states]` states `initial`, zero if `None`, and `state` holds the
+    /// states after the last step, so long sequences can be scanned in chunks.
+    ///
+    /// `delta` is used as given; apply [`Self::softplus`] first for Mamba's
+    /// parameterization. The recurrence runs as a parallel associative scan with one
+    /// workgroup per channel of each sequence.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidShape`] if `self` is not rank 3, or `delta`, `a`, `b`, `c`, `d`
+    ///   or `initial` do not have the dimensions above.
+    /// - [`Error::Device`] if buffer allocation fails.
+    pub fn selective_scan(
+        &self,
+        delta: &Self,
+        a: &Self,
+        b: &Self,
+        c: &Self,
+        d: Option<&Self>,
+        initial: Option<&Self>,
+    ) -> Result<(Self, Self), Error> {
+        let dimensions = self.dimensions();
+        let &[batch, channels, length] = dimensions else {
+            return Err(TensorError::InvalidShape(format!(
+                "input must be [batch, channels, length], got {dimensions:?}"
+            ))
+            .into());
+        };
+
+        let &[a_channels, states] = a.dimensions() else {
+            return Err(TensorError::InvalidShape(format!(
+                "state matrix must be [channels, states], got {:?}",
+                a.dimensions()
+            ))
+            .into());
+        };
+
+        if delta.dimensions() != dimensions || a_channels != channels {
+            return Err(TensorError::InvalidShape(format!(
+                "delta {:?} must be {dimensions:?} and state matrix {:?} [{channels}, states]",
+                delta.dimensions(),
+                a.dimensions()
+            ))
+            .into());
+        }
+
+        let projection = [batch, states, length];
+        if b.dimensions() != projection || c.dimensions() != projection {
+            return Err(TensorError::InvalidShape(format!(
+                "projections {:?} and {:?} must be {projection:?}",
+                b.dimensions(),
+                c.dimensions()
+            ))
+            .into());
+        }
+
+        let state_dimensions = [batch, channels, states];
+        if d.is_some_and(|d| d.dimensions() != [channels])
+            || initial.is_some_and(|initial| initial.dimensions() != state_dimensions)
+        {
+            return Err(TensorError::InvalidShape(format!(
+                "skip weights must be [{channels}] and initial states {state_dimensions:?}"
+            ))
+            .into());
+        }
+
+        let x = self.materialize()?;
+        let y = self.ctx.create_buffer(x.buffer.len())?;
+        let state_layout = Layout::from_dimensions(&state_dimensions);
+        let state = self.ctx.create_buffer(state_layout.size())?;
+        let d = d.map(Self::materialize).transpose()?;
+        if let Some(initial) = initial {
+            ops::copy(&self.ctx, &initial.materialize()?.buffer, &state);
+        }
+
+        ops::selective_scan(
+            &self.ctx,
+            &x.buffer,
+            &delta.materialize()?.buffer,
+            &a.materialize()?.buffer,
+            &b.materialize()?.buffer,
+            &c.materialize()?.buffer,
+            d.as_ref().map(|d| &d.buffer),
+            &y,
+            &state,
+            initial.is_some(),
+            [batch, channels, length, states],
+        );
+
+        Ok((
+            Self {
+                buffer: y,
+                layout: x.layout,
+                ctx: self.ctx.clone(),
+            },
+            Self {
+                buffer: state,
+                layout: state_layout,
+                ctx: self.ctx.clone(),
+            },
+        ))
+    }
+}
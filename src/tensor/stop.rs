@@ -0,0 +1,67 @@
+//! GPU-resident stop flags for skipping work without a host sync.
+
+use alloc::format;
+
+use crate::Context;
+use crate::error::{Error, TensorError};
+use crate::kernel::ops;
+use crate::tensor::Tensor;
+
+impl Context {
+    /// Installs `flag` as the stop flag of this context, or removes it with `None`.
+    ///
+    /// While a flag is installed, every compute dispatch first reads `flag[0]` on the GPU and
+    /// runs no workgroups if it is true, so a loop can keep submitting steps and stop doing
+    /// work as soon as a kernel raises the flag, e.g. with [`Tensor::set_if_any_`] on a stop
+    /// token match or a non-finite loss, without waiting for a readback each iteration.
+    /// Outputs of skipped operations keep their freshly allocated contents. Buffer copies, the
+    /// layout copies behind readbacks of strided views, and host writes are not gated, so views
+    /// stay readable and the flag can be cleared with [`Tensor::write_slice`].
+    ///
+    /// The flag is shared by all clones of this context and must belong to it.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidShape`] if `flag` is not a `[1]` tensor spanning its buffer.
+    pub fn set_stop_flag(&self, flag: Option<&Tensor<bool>>) -> Result<(), Error> {
+        let Some(flag) = flag else {
+            self.set_stop_flag_buffer(None);
+            return Ok(());
+        };
+
+        flag.check_flag()?;
+        self.set_stop_flag_buffer(Some(flag.buffer.inner().clone()));
+        Ok(())
+    }
+}
+
+impl Tensor<bool> {
+    /// Sets the `[1]` flag `self` to true if any element of `condition` is true, in place.
+    ///
+    /// The flag is left unchanged otherwise, so it accumulates over several calls until
+    /// cleared. A `condition` sharing the buffer of the flag is copied first.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidShape`] if `self` is not a `[1]` tensor spanning its buffer.
+    /// - [`Error::Device`] if `condition` needs a copy and buffer allocation fails.
+    pub fn set_if_any_(&mut self, condition: &Self) -> Result<(), Error> {
+        self.check_flag()?;
+        let condition = self.unaliased(condition)?.dense()?;
+        ops::set_if_any(&self.ctx, &condition.buffer, &self.buffer);
+        Ok(())
+    }
+
+    /// Returns an error if `self` is not a writable single-element flag.
+    fn check_flag(&self) -> Result<(), Error> {
+        if self.dimensions() != [1] {
+            return Err(TensorError::InvalidShape(format!(
+                "flag must have dimensions [1], got {:?}",
+                self.dimensions()
+            ))
+            .into());
+        }
+
+        self.check_writable()
+    }
+}
@@ -0,0 +1,269 @@
+//! Conversion of GPU textures into image tensors.
+
+use alloc::format;
+use alloc::vec::Vec;
+
+use crate::Context;
+use crate::error::{Error, TensorError};
+use crate::kernel::ops;
+use crate::tensor::Tensor;
+use crate::tensor::layout::Layout;
+
+/// Per-channel normalization applied when converting textures: `(x - mean) / std`.
+///
+/// Texel values of normalized formats such as `Rgba8Unorm` are in `[0, 1]` before
+/// normalization.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Normalization {
+    /// Mean of the red, green and blue channels.
+    pub mean: [f32; 3],
+    /// Standard deviation of the red, green and blue channels.
+    pub std: [f32; 3],
+}
+
+impl Normalization {
+    /// Leaves texel values unchanged.
+    pub const IDENTITY: Self = Self {
+        mean: [0.0; 3],
+        std: [1.0; 3],
+    };
+
+    /// Channel statistics of the `ImageNet` training set.
+    pub const IMAGENET: Self = Self {
+        mean: [0.485, 0.456, 0.406],
+        std: [0.229, 0.224, 0.225],
+    };
+}
+
+impl Default for Normalization {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+/// Placement of a source image inside a letterboxed tensor.
+///
+/// The image is scaled by `scale` and offset by `(left, top)` output pixels, so a point
+/// `(x, y)` in the output maps back to `((x - left) / scale, (y - top) / scale)` in the source.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Letterbox {
+    /// Resize factor from source to output pixels.
+    pub scale: f32,
+    /// Padding left of the image, in output pixels.
+    pub left: u32,
+    /// Padding above the image, in output pixels.
+    pub top: u32,
+}
+
+impl Letterbox {
+    /// Maps a point in output pixels back to source pixels.
+    #[must_use]
+    pub fn to_source(&self, x: f32, y: f32) -> (f32, f32) {
+        #[allow(clippy::cast_precision_loss)]
+        let (left, top) = (self.left as f32, self.top as f32);
+        ((x - left) / self.scale, (y - top) / self.scale)
+    }
+}
+
+impl Tensor<f32> {
+    /// Creates a `[1, 3, height, width]` image tensor from the RGB channels of a texture.
+    ///
+    /// Reads mip level 0 of a single-sampled 2D texture with a float sample type, such as a
+    /// canvas or video frame copied into an `Rgba8Unorm` or `Bgra8Unorm` texture, in a single
+    /// compute pass. The alpha channel is dropped. The texture must belong to the device of
+    /// `ctx` and be created with [`wgpu::TextureUsages::TEXTURE_BINDING`].
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidArgument`] if the texture is not 2D, is multisampled, lacks
+    ///   texture binding usage, has a non-float sample type, or a `std` entry is zero or not
+    ///   finite.
+    /// - [`Error::Device`] if buffer allocation fails.
+    pub fn from_texture(
+        ctx: &Context,
+        texture: &wgpu::Texture,
+        normalization: Normalization,
+    ) -> Result<Self, Error> {
+        let view = texture_view(texture)?;
+        let scale = reciprocal_std(normalization)?;
+
+        let (width, height) = (texture.width(), texture.height());
+        let layout = Layout::from_dimensions(&[1, 3, height as usize, width as usize]);
+        let buffer = ctx.create_buffer(layout.size())?;
+
+        ops::from_texture(
+            ctx,
+            &view,
+            &buffer,
+            width,
+            height,
+            normalization.mean,
+            scale,
+        );
+
+        Ok(Self {
+            buffer,
+            layout,
+            ctx: ctx.clone(),
+        })
+    }
+
+    /// Creates a `[n, 3, height, width]` detector input batch from the RGB channels of
+    /// textures, resizing each to fit while keeping its aspect ratio.
+    ///
+    /// Each texture is scaled bilinearly to fit `height × width`, centered, and padded with
+    /// `fill` (in texel units, e.g. `114 / 255`) before `normalization` is applied, all in one
+    /// compute pass per texture. Returns the batch and the placement of every image, for
+    /// mapping detections back to source coordinates. Texture requirements match
+    /// [`Self::from_texture`].
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidArgument`] if `textures` is empty, a texture is not usable as
+    ///   in [`Self::from_texture`], or a `std` entry is zero or not finite.
+    /// - [`TensorError::InvalidShape`] if `height` or `width` is zero or exceeds max size.
+    /// - [`Error::Device`] if buffer allocation fails.
+    pub fn from_textures_letterboxed(
+        ctx: &Context,
+        textures: &[&wgpu::Texture],
+        height: usize,
+        width: usize,
+        fill: f32,
+        normalization: Normalization,
+    ) -> Result<(Self, Vec<Letterbox>), Error> {
+        if textures.is_empty() {
+            return Err(TensorError::InvalidArgument("no textures given".into()).into());
+        }
+
+        if height == 0 || width == 0 {
+            return Err(TensorError::InvalidShape(format!(
+                "output size {height}x{width} must be non-zero"
+            ))
+            .into());
+        }
+
+        let output = u32::try_from(width)
+            .ok()
+            .zip(u32::try_from(height).ok())
+            .ok_or_else(|| {
+                TensorError::InvalidShape(format!("output size {height}x{width} exceeds max size"))
+            })?;
+
+        let scale = reciprocal_std(normalization)?;
+        let views = textures
+            .iter()
+            .map(|texture| texture_view(texture))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let layout = Layout::from_dimensions(&[textures.len(), 3, height, width]);
+        let buffer = ctx.create_buffer(layout.size())?;
+        let image = 3 * height * width;
+
+        let mut placements = Vec::with_capacity(textures.len());
+        for (i, (texture, view)) in textures.iter().zip(&views).enumerate() {
+            let input = (texture.width(), texture.height());
+            let (placement, resized) = fit(input, output);
+
+            ops::letterbox(
+                ctx,
+                view,
+                &buffer,
+                i * image,
+                input,
+                output,
+                (placement.left, placement.top),
+                resized,
+                fill,
+                normalization.mean,
+                scale,
+            );
+            placements.push(placement);
+        }
+
+        Ok((
+            Self {
+                buffer,
+                layout,
+                ctx: ctx.clone(),
+            },
+            placements,
+        ))
+    }
+}
+
+/// Validates a texture for conversion and creates a view of its first mip level.
+fn texture_view(texture: &wgpu::Texture) -> Result<wgpu::TextureView, Error> {
+    if texture.dimension() != wgpu::TextureDimension::D2 {
+        return Err(TensorError::InvalidArgument("texture must be 2D".into()).into());
+    }
+
+    if texture.sample_count() != 1 {
+        return Err(TensorError::InvalidArgument(format!(
+            "texture must not be multisampled, got {} samples",
+            texture.sample_count()
+        ))
+        .into());
+    }
+
+    if !texture
+        .usage()
+        .contains(wgpu::TextureUsages::TEXTURE_BINDING)
+    {
+        return Err(
+            TensorError::InvalidArgument("texture must have texture binding usage".into()).into(),
+        );
+    }
+
+    let format = texture.format();
+    if !matches!(
+        format.sample_type(None, None),
+        Some(wgpu::TextureSampleType::Float { .. })
+    ) {
+        return Err(TensorError::InvalidArgument(format!(
+            "texture format {format:?} does not have a float sample type"
+        ))
+        .into());
+    }
+
+    Ok(texture.create_view(&wgpu::TextureViewDescriptor {
+        dimension: Some(wgpu::TextureViewDimension::D2),
+        base_mip_level: 0,
+        mip_level_count: Some(1),
+        base_array_layer: 0,
+        array_layer_count: Some(1),
+        ..Default::default()
+    }))
+}
+
+/// Validates the normalization and returns the per-channel scale `1 / std`.
+fn reciprocal_std(normalization: Normalization) -> Result<[f32; 3], Error> {
+    let std = normalization.std;
+    if std.iter().any(|s| !s.is_finite() || *s == 0.0) {
+        return Err(TensorError::InvalidArgument(format!(
+            "normalization std {std:?} must be non-zero and finite"
+        ))
+        .into());
+    }
+
+    Ok(std.map(f32::recip))
+}
+
+/// Largest aspect-preserving fit of `input` into `output`, centered, with its resized size.
+#[allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_precision_loss,
+    clippy::cast_sign_loss
+)]
+fn fit(input: (u32, u32), output: (u32, u32)) -> (Letterbox, (u32, u32)) {
+    let scale =
+        (f64::from(output.0) / f64::from(input.0)).min(f64::from(output.1) / f64::from(input.1));
+    let resize = |size: u32, limit: u32| ((f64::from(size) * scale).round() as u32).clamp(1, limit);
+    let (width, height) = (resize(input.0, output.0), resize(input.1, output.1));
+
+    let placement = Letterbox {
+        scale: scale as f32,
+        left: (output.0 - width) / 2,
+        top: (output.1 - height) / 2,
+    };
+    (placement, (width, height))
+}
@@ -0,0 +1,75 @@
+//! Lower and upper triangular masking.
+
+use alloc::format;
+
+use crate::error::{Error, TensorError};
+use crate::kernel::ops;
+use crate::tensor::Tensor;
+use crate::tensor::layout::Layout;
+use crate::{Context, Element};
+
+impl<T: Element> Tensor<T> {
+    /// Lower triangle of the last two axes, with elements above `diagonal` set to zero.
+    ///
+    /// Element `[..., i, j]` is kept where `j - i ≤ diagonal`: `0` keeps the main diagonal,
+    /// positive values keep diagonals above it and negative values drop diagonals below it.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidShape`] if `self` has rank less than 2.
+    /// - [`Error::Device`] if buffer allocation fails.
+    pub fn tril(&self, diagonal: i32) -> Result<Self, Error> {
+        self.triangle(diagonal, false)
+    }
+
+    /// Upper triangle of the last two axes, with elements below `diagonal` set to zero.
+    ///
+    /// Element `[..., i, j]` is kept where `j - i ≥ diagonal`: `0` keeps the main diagonal,
+    /// positive values drop diagonals above it and negative values keep diagonals below it.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidShape`] if `self` has rank less than 2.
+    /// - [`Error::Device`] if buffer allocation fails.
+    pub fn triu(&self, diagonal: i32) -> Result<Self, Error> {
+        self.triangle(diagonal, true)
+    }
+
+    /// Keeps the lower or upper triangle of the last two axes relative to `diagonal`.
+    fn triangle(&self, diagonal: i32, upper: bool) -> Result<Self, Error> {
+        let &[.., rows, cols] = self.dimensions() else {
+            return Err(TensorError::InvalidShape(format!(
+                "triangular mask requires rank >= 2, got dimensions {:?}",
+                self.dimensions()
+            ))
+            .into());
+        };
+
+        let x = self.materialize()?;
+        let layout = Layout::from_dimensions(x.dimensions());
+        let buffer = self.ctx.create_buffer(layout.size())?;
+
+        ops::triangle(&self.ctx, &x.buffer, &buffer, rows, cols, diagonal, upper);
+
+        Ok(Self {
+            buffer,
+            layout,
+            ctx: self.ctx.clone(),
+        })
+    }
+}
+
+impl Tensor<bool> {
+    /// Causal attention mask of `[seq_len, seq_len]`, true where query `i` may attend to key
+    /// `j`, i.e. `j ≤ i`.
+    ///
+    /// Pass it to [`Tensor::masked_softmax`] over attention scores so that each position only
+    /// attends to itself and earlier positions.
+    ///
+    /// # Errors
+    ///
+    /// - [`Error::Device`] if buffer allocation fails.
+    pub fn causal_mask(ctx: &Context, seq_len: usize) -> Result<Self, Error> {
+        Self::constant(ctx, &[seq_len, seq_len], &[true])?.tril(0)
+    }
+}
@@ -0,0 +1,215 @@
+//! `WaveNet`-style stacks of dilated causal convolutions.
+//!
+//! - [`DilatedLayer`] — gated dilated causal convolution with residual and skip outputs.
+//! - [`DilatedStack`] — layers applied in sequence, summing their skip outputs.
+//!
+//! Inputs are `[n, c, l]` sequences. Every convolution is causal, built on
+//! [`Tensor::causal_conv1d`], so output step `t` only sees input steps up to `t`. Doubling the
+//! dilation from one layer to the next grows the receptive field exponentially with depth.
+
+use alloc::format;
+use alloc::vec::Vec;
+
+use crate::Tensor;
+use crate::error::{Error, TensorError};
+
+/// Gated activation unit of a `WaveNet` residual block.
+///
+/// With `z = tanh(filter ⋆ x) ⊙ σ(gate ⋆ x)` for dilated causal convolutions `⋆`, the layer
+/// returns the residual output `x + residual · z` and the skip output `skip · z`, both
+/// projections being 1×1 convolutions.
+pub struct DilatedLayer {
+    /// Filter convolution weights, `[k, c, h]`.
+    filter: Tensor<f32>,
+    /// Gate convolution weights, `[k, c, h]`.
+    gate: Tensor<f32>,
+    /// Residual projection weights, `[h, c]`.
+    residual: Tensor<f32>,
+    /// Skip projection weights, `[h, s]`.
+    skip: Tensor<f32>,
+    /// Spacing between the taps of the filter and gate convolutions.
+    dilation: usize,
+}
+
+impl DilatedLayer {
+    /// Creates a layer from `[k, c, h]` `filter` and `gate` weights, `[h, c]` `residual` and
+    /// `[h, s]` `skip` projection weights, for `c` residual, `h` hidden and `s` skip
+    /// channels.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidShape`] if the weights do not have matching dimensions or the
+    ///   window is empty.
+    /// - [`TensorError::InvalidArgument`] if `dilation` is zero.
+    pub fn new(
+        filter: Tensor<f32>,
+        gate: Tensor<f32>,
+        residual: Tensor<f32>,
+        skip: Tensor<f32>,
+        dilation: usize,
+    ) -> Result<Self, Error> {
+        let (&[k, c, h], &[residual_h, residual_c], &[skip_h, _]) = (
+            filter.dimensions(),
+            residual.dimensions(),
+            skip.dimensions(),
+        ) else {
+            return Err(TensorError::InvalidShape(format!(
+                "filter {:?}, residual {:?} and skip {:?} must be [k, c, h], [h, c] and [h, s]",
+                filter.dimensions(),
+                residual.dimensions(),
+                skip.dimensions()
+            ))
+            .into());
+        };
+
+        if k == 0 || gate.dimensions() != [k, c, h] || (residual_h, residual_c) != (h, c) {
+            return Err(TensorError::InvalidShape(format!(
+                "gate {:?} must be [{k}, {c}, {h}] and residual {:?} [{h}, {c}] with k > 0",
+                gate.dimensions(),
+                residual.dimensions()
+            ))
+            .into());
+        }
+
+        if skip_h != h {
+            return Err(TensorError::InvalidShape(format!(
+                "skip {:?} must have {h} hidden channels",
+                skip.dimensions()
+            ))
+            .into());
+        }
+
+        if dilation == 0 {
+            return Err(TensorError::InvalidArgument("dilation must be positive".into()).into());
+        }
+
+        Ok(Self {
+            filter,
+            gate,
+            residual,
+            skip,
+            dilation,
+        })
+    }
+
+    /// Applies the layer to `[n, c, l]` `x`, returning the `[n, c, l]` residual output and
+    /// the `[n, s, l]` skip output.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidShape`] if `x` is not `[n, c, l]`.
+    /// - [`Error::Device`] if GPU operation fails.
+    pub fn forward(&self, x: &Tensor<f32>) -> Result<(Tensor<f32>, Tensor<f32>), Error> {
+        let filter = x.causal_conv1d(&self.filter, None, self.dilation)?.tanh()?;
+        let gate = x
+            .causal_conv1d(&self.gate, None, self.dilation)?
+            .sigmoid()?;
+        let z = filter.mul(&gate)?;
+
+        let residual = z.conv1d(&self.residual.unsqueeze(0)?, None, 1, 1, (0, 0))?;
+        let skip = z.conv1d(&self.skip.unsqueeze(0)?, None, 1, 1, (0, 0))?;
+
+        Ok((x.add(&residual)?, skip))
+    }
+
+    /// Number of past steps each output sees beyond its own, `dilation·(k - 1)`.
+    #[must_use]
+    pub fn lookback(&self) -> usize {
+        self.dilation * (self.filter.dimensions()[0] - 1)
+    }
+
+    /// Number of residual channels `c`.
+    #[must_use]
+    pub fn channels(&self) -> usize {
+        self.filter.dimensions()[1]
+    }
+
+    /// Number of skip channels `s`.
+    #[must_use]
+    pub fn skip_channels(&self) -> usize {
+        self.skip.dimensions()[1]
+    }
+
+    /// Spacing between the taps of the filter and gate convolutions.
+    #[must_use]
+    pub fn dilation(&self) -> usize {
+        self.dilation
+    }
+}
+
+/// Dilated layers applied in sequence, each to the residual output of the previous one.
+///
+/// The skip outputs of all layers are summed, `WaveNet`'s input to its output head.
+pub struct DilatedStack {
+    /// Layers in application order.
+    layers: Vec<DilatedLayer>,
+}
+
+impl DilatedStack {
+    /// Creates a stack of `layers` sharing residual and skip channel counts.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidArgument`] if `layers` is empty.
+    /// - [`TensorError::InvalidShape`] if the layers differ in residual or skip channels.
+    pub fn new(layers: Vec<DilatedLayer>) -> Result<Self, Error> {
+        let Some(first) = layers.first() else {
+            return Err(TensorError::InvalidArgument("stack has no layers".into()).into());
+        };
+
+        let channels = (first.channels(), first.skip_channels());
+        if let Some(layer) = layers
+            .iter()
+            .find(|layer| (layer.channels(), layer.skip_channels()) != channels)
+        {
+            return Err(TensorError::InvalidShape(format!(
+                "layer with {} residual and {} skip channels does not match {channels:?}",
+                layer.channels(),
+                layer.skip_channels()
+            ))
+            .into());
+        }
+
+        Ok(Self { layers })
+    }
+
+    /// Applies the stack to `[n, c, l]` `x`, returning the `[n, c, l]` output of the last
+    /// layer and the `[n, s, l]` sum of all skip outputs.
+    ///
+    /// # Errors
+    ///
+    /// - [`TensorError::InvalidShape`] if `x` is not `[n, c, l]`.
+    /// - [`Error::Device`] if GPU operation fails.
+    pub fn forward(&self, x: &Tensor<f32>) -> Result<(Tensor<f32>, Tensor<f32>), Error> {
+        let mut out: Option<Tensor<f32>> = None;
+        let mut skips: Option<Tensor<f32>> = None;
+
+        for layer in &self.layers {
+            let (residual, skip) = layer.forward(out.as_ref().unwrap_or(x))?;
+            skips = Some(match skips {
+                Some(skips) => skips.add(&skip)?,
+                None => skip,
+            });
+            out = Some(residual);
+        }
+
+        out.zip(skips)
+            .ok_or_else(|| TensorError::InvalidArgument("stack has no layers".into()).into())
+    }
+
+    /// Number of input steps each output depends on, `1 + Σ dilation·(k - 1)`.
+    #[must_use]
+    pub fn receptive_field(&self) -> usize {
+        1 + self
+            .layers
+            .iter()
+            .map(DilatedLayer::lookback)
+            .sum::<usize>()
+    }
+
+    /// Layers in application order.
+    #[must_use]
+    pub fn layers(&self) -> &[DilatedLayer] {
+        &self.layers
+    }
+}
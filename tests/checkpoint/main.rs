@@ -0,0 +1,3 @@
+//! Checkpoint import integration tests.
+
+mod safetensors;
@@ -0,0 +1,265 @@
+//! Tests for `checkpoint::Checkpoint`.
+
+use xnn::Context;
+use xnn::checkpoint::{Checkpoint, Dtype, WeightLayout};
+
+/// Encodes `tensors` of `(name, dtype, shape, little-endian data)` as a safetensors file.
+fn encode(tensors: &[(&str, &str, &[usize], Vec<u8>)], metadata: &str) -> Vec<u8> {
+    let mut fields = vec![format!("\"__metadata__\": {metadata}")];
+    let mut data: Vec<u8> = Vec::new();
+    for (name, dtype, shape, bytes) in tensors {
+        let shape: Vec<String> = shape.iter().map(ToString::to_string).collect();
+        fields.push(format!(
+            "\"{name}\": {{\"dtype\": \"{dtype}\", \"shape\": [{}], \"data_offsets\": [{}, {}]}}",
+            shape.join(", "),
+            data.len(),
+            data.len() + bytes.len()
+        ));
+        data.extend(bytes);
+    }
+
+    let header = format!("{{{}}}", fields.join(", "));
+    let mut file = (header.len() as u64).to_le_bytes().to_vec();
+    file.extend(header.as_bytes());
+    file.extend(data);
+    file
+}
+
+fn f32_bytes(values: &[f32]) -> Vec<u8> {
+    values.iter().flat_map(|v| v.to_le_bytes()).collect()
+}
+
+fn sequence(len: u16) -> Vec<f32> {
+    (0..len).map(f32::from).collect()
+}
+
+#[test]
+fn test_checkpoint_header() {
+    let file = encode(
+        &[
+            ("fc.weight", "F32", &[2, 3], f32_bytes(&sequence(6))),
+            ("steps", "I64", &[], 7i64.to_le_bytes().to_vec()),
+        ],
+        r#"{"format": "pt", "note": "a \"quoted\" é"}"#,
+    );
+    let checkpoint = Checkpoint::parse(file).unwrap();
+
+    assert_eq!(
+        checkpoint.names().collect::<Vec<_>>(),
+        ["fc.weight", "steps"]
+    );
+    assert_eq!(checkpoint.dtype("fc.weight"), Some(Dtype::F32));
+    assert_eq!(checkpoint.dtype("steps"), Some(Dtype::I64));
+    assert_eq!(checkpoint.dimensions("fc.weight"), Some(&[2, 3][..]));
+    assert_eq!(checkpoint.dimensions("missing"), None);
+    assert_eq!(checkpoint.metadata("format"), Some("pt"));
+    assert_eq!(checkpoint.metadata("note"), Some("a \"quoted\" é"));
+}
+
+#[test]
+fn test_checkpoint_load_layouts() {
+    let ctx = Context::try_default().unwrap();
+    let file = encode(
+        &[
+            ("bias", "F32", &[3], f32_bytes(&[1.0, 2.0, 3.0])),
+            ("linear", "F32", &[2, 3], f32_bytes(&sequence(6))),
+            ("conv", "F32", &[2, 3, 4], f32_bytes(&sequence(24))),
+            ("depthwise", "F32", &[2, 1, 2, 2], f32_bytes(&sequence(8))),
+            ("pointwise", "F32", &[3, 2, 1, 1], f32_bytes(&sequence(6))),
+        ],
+        "{}",
+    );
+    let checkpoint = Checkpoint::parse(file).unwrap();
+
+    let bias = checkpoint
+        .load(&ctx, "bias", WeightLayout::Same, &[3])
+        .unwrap();
+    assert_eq!(bias.to_vec().unwrap(), vec![1.0, 2.0, 3.0]);
+
+    // [out, in] → [in, out].
+    let linear = checkpoint
+        .load(&ctx, "linear", WeightLayout::Linear, &[3, 2])
+        .unwrap();
+    assert_eq!(linear.to_vec().unwrap(), vec![0.0, 3.0, 1.0, 4.0, 2.0, 5.0]);
+
+    // [c_out, c_in, k] → [k, c_in, c_out]: conv[j, i, o] = stored[o, i, j] = 12·o + 4·i + j.
+    let conv = checkpoint
+        .load(&ctx, "conv", WeightLayout::Conv1d, &[4, 3, 2])
+        .unwrap();
+    let expected: Vec<f32> = (0..4)
+        .flat_map(|j| {
+            (0..3).flat_map(move |i| (0..2u16).map(move |o| f32::from(12 * o + 4 * i + j)))
+        })
+        .collect();
+    assert_eq!(conv.to_vec().unwrap(), expected);
+
+    // [c, 1, kh, kw] → [kh, kw, c].
+    let depthwise = checkpoint
+        .load(&ctx, "depthwise", WeightLayout::Depthwise2d, &[2, 2, 2])
+        .unwrap();
+    assert_eq!(
+        depthwise.to_vec().unwrap(),
+        vec![0.0, 4.0, 1.0, 5.0, 2.0, 6.0, 3.0, 7.0]
+    );
+
+    // [c_out, c_in, 1, 1] → [c_in, c_out].
+    let pointwise = checkpoint
+        .load(&ctx, "pointwise", WeightLayout::Pointwise2d, &[2, 3])
+        .unwrap();
+    assert_eq!(
+        pointwise.to_vec().unwrap(),
+        vec![0.0, 2.0, 4.0, 1.0, 3.0, 5.0]
+    );
+}
+
+#[test]
+fn test_checkpoint_load_half_precision() {
+    let ctx = Context::try_default().unwrap();
+    // 1.0, -2.5, 65504 (largest finite), 2^-24 (smallest subnormal) and -0.0.
+    let f16: Vec<u8> = [0x3c00u16, 0xc100, 0x7bff, 0x0001, 0x8000]
+        .iter()
+        .flat_map(|v| v.to_le_bytes())
+        .collect();
+    let bf16: Vec<u8> = [0x3f80u16, 0xc020]
+        .iter()
+        .flat_map(|v| v.to_le_bytes())
+        .collect();
+    let f64: Vec<u8> = [0.25f64, -8.0]
+        .iter()
+        .flat_map(|v| v.to_le_bytes())
+        .collect();
+    let checkpoint = Checkpoint::parse(encode(
+        &[
+            ("half", "F16", &[5], f16),
+            ("brain", "BF16", &[2], bf16),
+            ("double", "F64", &[2], f64),
+        ],
+        "{}",
+    ))
+    .unwrap();
+
+    let load = |name: &str, len: usize| {
+        checkpoint
+            .load(&ctx, name, WeightLayout::Same, &[len])
+            .unwrap()
+            .to_vec()
+            .unwrap()
+    };
+    let half = load("half", 5);
+    assert_eq!(half[..4], [1.0, -2.5, 65504.0, 2.0f32.powi(-24)]);
+    assert!(half[4] == 0.0 && half[4].is_sign_negative());
+    assert_eq!(load("brain", 2), vec![1.0, -2.5]);
+    assert_eq!(load("double", 2), vec![0.25, -8.0]);
+}
+
+#[test]
+fn test_checkpoint_load_invalid() {
+    let ctx = Context::try_default().unwrap();
+    let checkpoint = Checkpoint::parse(encode(
+        &[
+            ("weight", "F32", &[2, 3], f32_bytes(&sequence(6))),
+            ("depthwise", "F32", &[2, 2, 1, 1], f32_bytes(&sequence(4))),
+            ("index", "I32", &[1], 3i32.to_le_bytes().to_vec()),
+        ],
+        "{}",
+    ))
+    .unwrap();
+
+    assert!(
+        checkpoint
+            .load(&ctx, "missing", WeightLayout::Same, &[1])
+            .is_err()
+    );
+    assert!(
+        checkpoint
+            .load(&ctx, "weight", WeightLayout::Linear, &[2, 3])
+            .is_err()
+    );
+    assert!(
+        checkpoint
+            .load(&ctx, "weight", WeightLayout::Conv1d, &[3, 2])
+            .is_err()
+    );
+    assert!(
+        checkpoint
+            .load(&ctx, "depthwise", WeightLayout::Depthwise2d, &[1, 1, 2])
+            .is_err()
+    );
+    assert!(
+        checkpoint
+            .load(&ctx, "index", WeightLayout::Same, &[1])
+            .is_err()
+    );
+    assert!(
+        checkpoint
+            .load(&ctx, "depthwise", WeightLayout::Pointwise2d, &[2, 2])
+            .is_ok()
+    );
+}
+
+#[test]
+fn test_checkpoint_parse_invalid() {
+    let valid = encode(&[("w", "F32", &[2], f32_bytes(&[1.0, 2.0]))], "{}");
+    assert!(Checkpoint::parse(valid.clone()).is_ok());
+
+    // Truncated length, header and data.
+    assert!(Checkpoint::parse(valid[..4].to_vec()).is_err());
+    assert!(Checkpoint::parse(valid[..20].to_vec()).is_err());
+    assert!(Checkpoint::parse(valid[..valid.len() - 1].to_vec()).is_err());
+
+    for header in [
+        "[]",
+        "{\"w\": {\"dtype\": \"F32\", \"shape\": [2], \"data_offsets\": [0, 8]}",
+        "{\"w\": {\"dtype\": \"Q4\", \"shape\": [2], \"data_offsets\": [0, 8]}}",
+        "{\"w\": {\"dtype\": \"F32\", \"shape\": [3], \"data_offsets\": [0, 8]}}",
+        "{\"w\": {\"dtype\": \"F32\", \"shape\": [2], \"data_offsets\": [8, 0]}}",
+        "{\"w\": {\"dtype\": \"F32\", \"shape\": [-2], \"data_offsets\": [0, 8]}}",
+        "{\"__metadata__\": {\"n\": 1}}",
+    ] {
+        let mut file = (header.len() as u64).to_le_bytes().to_vec();
+        file.extend(header.as_bytes());
+        file.extend(f32_bytes(&[1.0, 2.0]));
+        assert!(Checkpoint::parse(file).is_err(), "{header}");
+    }
+}
+
+#[test]
+fn test_checkpoint_parse_deep_nesting() {
+    let nested = |depth: usize| {
+        let header = format!(
+            "{{\"__metadata__\": {{}}, \"x\": {}{}}}",
+            "[".repeat(depth),
+            "]".repeat(depth)
+        );
+        let mut file = (header.len() as u64).to_le_bytes().to_vec();
+        file.extend(header.as_bytes());
+        file
+    };
+
+    let too_deep =
+        |depth| Checkpoint::parse(nested(depth)).is_err_and(|e| e.to_string().contains("too deep"));
+    assert!(too_deep(200_000));
+    assert!(too_deep(128));
+    assert!(!too_deep(127));
+}
+
+#[test]
+fn test_checkpoint_read() {
+    let ctx = Context::try_default().unwrap();
+    let path =
+        std::env::temp_dir().join(format!("xnn-checkpoint-{}.safetensors", std::process::id()));
+    std::fs::write(
+        &path,
+        encode(&[("w", "F32", &[2], f32_bytes(&[1.0, 2.0]))], "{}"),
+    )
+    .unwrap();
+
+    let checkpoint = Checkpoint::read(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    let w = checkpoint
+        .load(&ctx, "w", WeightLayout::Same, &[2])
+        .unwrap();
+    assert_eq!(w.to_vec().unwrap(), vec![1.0, 2.0]);
+
+    assert!(Checkpoint::read(&path).is_err());
+}
@@ -0,0 +1,83 @@
+//! Tests for `Context::map`, `Context::zip` and `Context::reduce`.
+
+use xnn::compute::Reduction;
+use xnn::{Buffer, Context};
+
+#[test]
+fn test_map() {
+    let ctx = Context::try_default().unwrap();
+    let x = ctx
+        .create_buffer_from_slice(&[1.0f32, -2.0, 3.0, -4.0])
+        .unwrap();
+
+    let y: Buffer<f32> = ctx.map(&x, "x * x + f32(i)").unwrap();
+    assert_eq!(ctx.read_buffer(&y).unwrap(), [1.0, 5.0, 11.0, 19.0]);
+
+    let signs: Buffer<i32> = ctx.map(&x, "sign(x)").unwrap();
+    assert_eq!(ctx.read_buffer(&signs).unwrap(), [1, -1, 1, -1]);
+}
+
+#[test]
+fn test_map_long() {
+    let ctx = Context::try_default().unwrap();
+    let x: Vec<u32> = (0..100_000).collect();
+    let buffer = ctx.create_buffer_from_slice(&x).unwrap();
+
+    let y: Buffer<u32> = ctx.map(&buffer, "x % 5u + i").unwrap();
+    let expected: Vec<u32> = x.iter().map(|&x| x % 5 + x).collect();
+    assert_eq!(ctx.read_buffer(&y).unwrap(), expected);
+}
+
+#[test]
+fn test_map_empty() {
+    let ctx = Context::try_default().unwrap();
+    let x = ctx.create_buffer::<f32>(0).unwrap();
+
+    let y: Buffer<f32> = ctx.map(&x, "x + 1.0").unwrap();
+    assert!(y.is_empty());
+}
+
+#[test]
+fn test_map_invalid() {
+    let ctx = Context::try_default().unwrap();
+    let x = ctx.create_buffer_from_slice(&[1.0f32]).unwrap();
+
+    let result: Result<Buffer<f32>, _> = ctx.map(&x, "x +");
+    assert!(result.is_err());
+    let result: Result<Buffer<f32>, _> = ctx.map(&x, "undefined(x)");
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_zip() {
+    let ctx = Context::try_default().unwrap();
+    let a = ctx.create_buffer_from_slice(&[1, 2, 3]).unwrap();
+    let b = ctx.create_buffer_from_slice(&[4, 5, 6]).unwrap();
+
+    let y: Buffer<i32> = ctx.zip(&a, &b, "a * b - i32(i)").unwrap();
+    assert_eq!(ctx.read_buffer(&y).unwrap(), [4, 9, 16]);
+
+    let ratio: Buffer<f32> = ctx.zip(&a, &b, "f32(a) / f32(b)").unwrap();
+    let ratio = ctx.read_buffer(&ratio).unwrap();
+    assert!((ratio[1] - 0.4).abs() < 1e-6);
+}
+
+#[test]
+fn test_zip_length_mismatch() {
+    let ctx = Context::try_default().unwrap();
+    let a = ctx.create_buffer_from_slice(&[1.0f32, 2.0]).unwrap();
+    let b = ctx.create_buffer_from_slice(&[1.0f32]).unwrap();
+
+    let result: Result<Buffer<f32>, _> = ctx.zip(&a, &b, "a + b");
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_map_reduce() {
+    let ctx = Context::try_default().unwrap();
+    let x = ctx.create_buffer_from_slice(&[1, 2, 3, 4]).unwrap();
+
+    let squares: Buffer<i32> = ctx.map(&x, "x * x").unwrap();
+    let sum = ctx.reduce(&squares, Reduction::Sum).unwrap();
+    assert_eq!(ctx.read_buffer(&sum).unwrap(), [30]);
+}
@@ -0,0 +1,53 @@
+//! Tests for `compute::gemm`.
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use xnn::{Context, compute};
+
+#[test]
+fn test_gemm_matches_cpu() {
+    let ctx = Context::try_default().unwrap();
+    let mut rng = StdRng::seed_from_u64(87);
+    let (m, k, n) = (33, 70, 17);
+
+    let a: Vec<f32> = (0..m * k).map(|_| rng.random_range(-1.0..1.0)).collect();
+    let b: Vec<f32> = (0..k * n).map(|_| rng.random_range(-1.0..1.0)).collect();
+    let mut expected = vec![0.0f32; m * n];
+    for i in 0..m {
+        for j in 0..n {
+            expected[i * n + j] = (0..k).map(|p| a[i * k + p] * b[p * n + j]).sum();
+        }
+    }
+
+    // Transposed operands hold the same matrices stored column-major.
+    let a_t: Vec<f32> = (0..k * m).map(|i| a[(i % m) * k + i / m]).collect();
+    let b_t: Vec<f32> = (0..n * k).map(|i| b[(i % k) * n + i / k]).collect();
+
+    let c = ctx.create_buffer::<f32>(m * n).unwrap();
+    for (a, b, transpose_a, transpose_b) in [
+        (&a, &b, false, false),
+        (&a_t, &b, true, false),
+        (&a, &b_t, false, true),
+        (&a_t, &b_t, true, true),
+    ] {
+        let a = ctx.create_buffer_from_slice(a).unwrap();
+        let b = ctx.create_buffer_from_slice(b).unwrap();
+        compute::gemm(&ctx, &a, &b, &c, [m, k, n], transpose_a, transpose_b).unwrap();
+
+        for (actual, expected) in ctx.read_buffer(&c).unwrap().iter().zip(&expected) {
+            assert!((actual - expected).abs() < 1e-4, "{actual} != {expected}");
+        }
+    }
+}
+
+#[test]
+fn test_gemm_invalid() {
+    let ctx = Context::try_default().unwrap();
+    let a = ctx.create_buffer::<f32>(6).unwrap();
+    let b = ctx.create_buffer::<f32>(12).unwrap();
+    let c = ctx.create_buffer::<f32>(8).unwrap();
+
+    assert!(compute::gemm(&ctx, &a, &b, &c, [2, 3, 4], false, false).is_ok());
+    assert!(compute::gemm(&ctx, &a, &b, &c, [3, 2, 4], false, false).is_err());
+    assert!(compute::gemm(&ctx, &b, &a, &c, [2, 3, 4], false, false).is_err());
+}
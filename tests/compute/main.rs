@@ -0,0 +1,7 @@
+//! Raw buffer compute integration tests.
+
+mod expr;
+mod gemm;
+mod reduce;
+mod stencil;
+mod transfer;
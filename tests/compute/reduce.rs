@@ -0,0 +1,50 @@
+//! Tests for `compute::reduce`.
+
+use xnn::Context;
+use xnn::compute::{self, Reduction};
+
+#[test]
+fn test_reduce() {
+    let ctx = Context::try_default().unwrap();
+    let x = ctx
+        .create_buffer_from_slice(&[3.0f32, -1.0, 4.0, 0.5])
+        .unwrap();
+
+    for (op, expected) in [
+        (Reduction::Sum, 6.5),
+        (Reduction::Max, 4.0),
+        (Reduction::Min, -1.0),
+        (Reduction::Prod, -6.0),
+    ] {
+        let y = compute::reduce(&ctx, &x, op).unwrap();
+        assert_eq!(y.len(), 1);
+        let actual = ctx.read_element(&y, 0).unwrap();
+        assert!(
+            (actual - expected).abs() < 1e-6,
+            "{op:?}: {actual} != {expected}"
+        );
+    }
+}
+
+#[test]
+fn test_reduce_long() {
+    let ctx = Context::try_default().unwrap();
+    let x: Vec<i32> = (0..100_000).map(|i| i % 7 - 3).collect();
+    let buffer = ctx.create_buffer_from_slice(&x).unwrap();
+
+    let sum = compute::reduce(&ctx, &buffer, Reduction::Sum).unwrap();
+    assert_eq!(ctx.read_buffer(&sum).unwrap(), [x.iter().sum::<i32>()]);
+    let max = compute::reduce(&ctx, &buffer, Reduction::Max).unwrap();
+    assert_eq!(ctx.read_buffer(&max).unwrap(), [3]);
+}
+
+#[test]
+fn test_reduce_empty() {
+    let ctx = Context::try_default().unwrap();
+    let empty = ctx.create_buffer::<u32>(0).unwrap();
+
+    let sum = compute::reduce(&ctx, &empty, Reduction::Sum).unwrap();
+    assert_eq!(ctx.read_buffer(&sum).unwrap(), [0]);
+    assert!(compute::reduce(&ctx, &empty, Reduction::Max).is_err());
+    assert!(compute::reduce(&ctx, &empty, Reduction::Prod).is_err());
+}
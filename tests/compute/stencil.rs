@@ -0,0 +1,106 @@
+//! Tests for `Context::stencil`.
+
+use xnn::{Context, PadMode};
+
+/// Reads `x` of `dimensions` at `coords` shifted by `offsets`, clamping to the edge.
+fn clamped(x: &[i32], dimensions: &[usize], coords: &[usize], offsets: &[isize]) -> i32 {
+    let mut index = 0;
+    for ((&size, &coord), &offset) in dimensions.iter().zip(coords).zip(offsets) {
+        let size = isize::try_from(size).unwrap();
+        let coord = (isize::try_from(coord).unwrap() + offset).clamp(0, size - 1);
+        index = index * usize::try_from(size).unwrap() + usize::try_from(coord).unwrap();
+    }
+    x[index]
+}
+
+#[test]
+fn test_stencil_1d() {
+    let ctx = Context::try_default().unwrap();
+    let x = ctx
+        .create_buffer_from_slice(&[1.0f32, 2.0, 4.0, 8.0])
+        .unwrap();
+    let expr = "at(-1) - 2.0 * x + at(1)";
+
+    let y = ctx
+        .stencil(&x, &[4], 1, PadMode::Constant(0.0), expr)
+        .unwrap();
+    assert_eq!(ctx.read_buffer(&y).unwrap(), [0.0, 1.0, 2.0, -12.0]);
+
+    let y = ctx.stencil(&x, &[4], 1, PadMode::Reflect, expr).unwrap();
+    assert_eq!(ctx.read_buffer(&y).unwrap(), [2.0, 1.0, 2.0, -8.0]);
+
+    let y = ctx.stencil(&x, &[4], 1, PadMode::Replicate, expr).unwrap();
+    assert_eq!(ctx.read_buffer(&y).unwrap(), [1.0, 1.0, 2.0, -4.0]);
+}
+
+#[test]
+fn test_stencil_2d_laplacian() {
+    let ctx = Context::try_default().unwrap();
+    let dimensions = [37, 53];
+    let x: Vec<i32> = (0..37 * 53).map(|i| (i * 7) % 13 - 6).collect();
+    let buffer = ctx.create_buffer_from_slice(&x).unwrap();
+
+    let y = ctx
+        .stencil(
+            &buffer,
+            &dimensions,
+            2,
+            PadMode::Replicate,
+            "at(-2, 0) + at(1, 0) + at(0, -1) + at(0, 2) - 4 * x + i32(i % 2u)",
+        )
+        .unwrap();
+
+    let mut expected = Vec::new();
+    for r in 0..37 {
+        for c in 0..53 {
+            let at = |offsets: [isize; 2]| clamped(&x, &dimensions, &[r, c], &offsets);
+            let i = i32::try_from(r * 53 + c).unwrap();
+            expected
+                .push(at([-2, 0]) + at([1, 0]) + at([0, -1]) + at([0, 2]) - 4 * at([0, 0]) + i % 2);
+        }
+    }
+    assert_eq!(ctx.read_buffer(&y).unwrap(), expected);
+}
+
+#[test]
+fn test_stencil_3d() {
+    let ctx = Context::try_default().unwrap();
+    let dimensions = [5, 9, 11];
+    let x: Vec<i32> = (0..5 * 9 * 11).map(|i| (i * 5) % 17).collect();
+    let buffer = ctx.create_buffer_from_slice(&x).unwrap();
+
+    let y = ctx
+        .stencil(
+            &buffer,
+            &dimensions,
+            1,
+            PadMode::Replicate,
+            "at(-1, 0, 0) + at(0, 1, 0) + at(0, 0, -1) + at(1, 1, 1)",
+        )
+        .unwrap();
+
+    let mut expected = Vec::new();
+    for a in 0..5 {
+        for b in 0..9 {
+            for c in 0..11 {
+                let at = |offsets: [isize; 3]| clamped(&x, &dimensions, &[a, b, c], &offsets);
+                expected.push(at([-1, 0, 0]) + at([0, 1, 0]) + at([0, 0, -1]) + at([1, 1, 1]));
+            }
+        }
+    }
+    assert_eq!(ctx.read_buffer(&y).unwrap(), expected);
+}
+
+#[test]
+fn test_stencil_invalid() {
+    let ctx = Context::try_default().unwrap();
+    let x = ctx.create_buffer_from_slice(&[1.0f32; 6]).unwrap();
+    let zero = PadMode::Constant(0.0);
+
+    assert!(ctx.stencil(&x, &[2, 2], 1, zero, "x").is_err());
+    assert!(ctx.stencil(&x, &[1, 1, 2, 3], 1, zero, "x").is_err());
+    assert!(ctx.stencil(&x, &[6], 5, zero, "x").is_err());
+    assert!(ctx.stencil(&x, &[2, 3], 2, PadMode::Reflect, "x").is_err());
+    assert!(ctx.stencil(&x, &[2, 3], 1, zero, "at(1)").is_err());
+    assert!(ctx.stencil(&x, &[2, 3], 1, zero, "at(0, 1)").is_ok());
+}
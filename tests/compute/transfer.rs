@@ -0,0 +1,22 @@
+//! Tests for `compute::fill` and `compute::copy`.
+
+use xnn::{Context, compute};
+
+#[test]
+fn test_fill() {
+    let ctx = Context::try_default().unwrap();
+    let buffer = ctx.create_buffer::<u32>(5).unwrap();
+    compute::fill(&ctx, &buffer, 7);
+    assert_eq!(ctx.read_buffer(&buffer).unwrap(), [7; 5]);
+}
+
+#[test]
+fn test_copy() {
+    let ctx = Context::try_default().unwrap();
+    let src = ctx.create_buffer_from_slice(&[1.0f32, 2.0, 3.0]).unwrap();
+    let dst = ctx.create_buffer_from_slice(&[0.0f32; 5]).unwrap();
+
+    compute::copy(&ctx, &src, &dst).unwrap();
+    assert_eq!(ctx.read_buffer(&dst).unwrap(), [1.0, 2.0, 3.0, 0.0, 0.0]);
+    assert!(compute::copy(&ctx, &dst, &src).is_err());
+}
@@ -0,0 +1,104 @@
+//! Tests for `data::read_csv` and `data::parse_csv`.
+
+use xnn::Context;
+use xnn::data::{Column, ColumnType, Schema, parse_csv, read_csv};
+
+const TEXT: &str = "\
+id,label,\"name, full\",score,active
+1,-1,\"Ada, L.\",0.5,true
+
+2,3,\"Bob \"\"B\"\"\", 1.25 ,FALSE
+3,0,Cy,-2,1
+";
+
+fn schema() -> Schema {
+    Schema::new()
+        .column("score", ColumnType::F32)
+        .column("id", ColumnType::U32)
+        .column("label", ColumnType::I32)
+        .column("active", ColumnType::Bool)
+}
+
+#[test]
+fn test_parse_csv() {
+    let ctx = Context::try_default().unwrap();
+    let table = parse_csv(&ctx, TEXT, &schema()).unwrap();
+
+    assert_eq!(table.rows(), 3);
+    assert_eq!(
+        table.f32("score").unwrap().to_vec().unwrap(),
+        vec![0.5, 1.25, -2.0]
+    );
+    assert_eq!(table.u32("id").unwrap().to_vec().unwrap(), vec![1, 2, 3]);
+    assert_eq!(
+        table.i32("label").unwrap().to_vec().unwrap(),
+        vec![-1, 3, 0]
+    );
+    assert_eq!(
+        table.bool("active").unwrap().to_vec().unwrap(),
+        vec![true, false, true]
+    );
+
+    let names: Vec<&str> = table.columns().map(|(name, _)| name).collect();
+    assert_eq!(names, ["score", "id", "label", "active"]);
+    assert!(matches!(table.column("id"), Some(Column::U32(_))));
+    assert!(table.f32("id").is_none());
+    assert!(table.column("name, full").is_none());
+}
+
+#[test]
+fn test_features() {
+    let ctx = Context::try_default().unwrap();
+    let text = "a,b,c\n1,2,3\n4,5,6\n";
+    let schema = Schema::new()
+        .column("a", ColumnType::F32)
+        .column("c", ColumnType::F32)
+        .column("b", ColumnType::I32);
+    let table = parse_csv(&ctx, text, &schema).unwrap();
+
+    let x = table.features(&["c", "a"]).unwrap();
+    assert_eq!(x.dimensions(), &[2, 2]);
+    assert_eq!(x.to_vec().unwrap(), vec![3.0, 1.0, 6.0, 4.0]);
+
+    assert!(table.features(&["b"]).is_err());
+    assert!(table.features(&["d"]).is_err());
+    assert!(table.features(&[]).is_err());
+}
+
+#[test]
+fn test_read_csv() {
+    let ctx = Context::try_default().unwrap();
+    let path = std::env::temp_dir().join(format!("xnn-read-csv-{}.tsv", std::process::id()));
+    std::fs::write(&path, "x\ty\n1.5\t2\n-3\t4\n").unwrap();
+
+    let schema = Schema::new()
+        .column("y", ColumnType::U32)
+        .column("x", ColumnType::F32)
+        .delimiter('\t');
+    let table = read_csv(&ctx, &path, &schema).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(table.u32("y").unwrap().to_vec().unwrap(), vec![2, 4]);
+    assert_eq!(table.f32("x").unwrap().to_vec().unwrap(), vec![1.5, -3.0]);
+
+    assert!(read_csv(&ctx, &path, &schema).is_err());
+}
+
+#[test]
+fn test_parse_csv_invalid() {
+    let ctx = Context::try_default().unwrap();
+    let schema = Schema::new().column("a", ColumnType::F32);
+
+    assert!(parse_csv(&ctx, "", &schema).is_err());
+    assert!(parse_csv(&ctx, "a\n", &schema).is_err());
+    assert!(parse_csv(&ctx, "b\n1\n", &schema).is_err());
+    assert!(parse_csv(&ctx, "a,b\n1\n", &schema).is_err());
+    assert!(parse_csv(&ctx, "a\nx\n", &schema).is_err());
+    assert!(parse_csv(&ctx, "a\n\"1\n", &schema).is_err());
+    assert!(parse_csv(&ctx, "a\n1\n", &Schema::new()).is_err());
+
+    let flags = Schema::new().column("a", ColumnType::Bool);
+    assert!(parse_csv(&ctx, "a\nyes\n", &flags).is_err());
+    let ids = Schema::new().column("a", ColumnType::U32);
+    assert!(parse_csv(&ctx, "a\n-1\n", &ids).is_err());
+}
@@ -0,0 +1,3 @@
+//! Data ingestion integration tests.
+
+mod csv;
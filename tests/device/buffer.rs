@@ -0,0 +1,62 @@
+//! Buffer creation, write and read tests.
+
+use xnn::Context;
+
+#[test]
+fn test_create_write_read() {
+    let ctx = Context::try_default().unwrap();
+    let buffer = ctx.create_buffer::<i32>(6).unwrap();
+    assert_eq!(buffer.len(), 6);
+    assert!(!buffer.is_empty());
+    assert_eq!(buffer.byte_size(), 32);
+
+    ctx.write_buffer(&buffer, 0, &[1, 2, 3, 4, 5, 6]).unwrap();
+    ctx.write_buffer(&buffer, 4, &[-5, -6]).unwrap();
+    assert_eq!(ctx.read_buffer(&buffer).unwrap(), [1, 2, 3, 4, -5, -6]);
+    assert_eq!(ctx.read_slice(&buffer, 2, 3).unwrap(), [3, 4, -5]);
+    assert_eq!(ctx.read_element(&buffer, 5).unwrap(), -6);
+}
+
+#[test]
+fn test_create_from_slice() {
+    let ctx = Context::try_default().unwrap();
+    let buffer = ctx.create_buffer_from_slice(&[true, false, true]).unwrap();
+    assert_eq!(buffer.len(), 3);
+    assert_eq!(ctx.read_buffer(&buffer).unwrap(), [true, false, true]);
+
+    let empty = ctx.create_buffer_from_slice::<u32>(&[]).unwrap();
+    assert!(empty.is_empty());
+    assert!(ctx.read_buffer(&empty).unwrap().is_empty());
+}
+
+#[test]
+fn test_create_too_large() {
+    let ctx = Context::try_default().unwrap();
+    let len = usize::try_from(ctx.max_binding_size() / 4 + 1).unwrap();
+    assert!(ctx.create_buffer::<f32>(len).is_err());
+}
+
+#[test]
+fn test_write_out_of_bounds() {
+    let ctx = Context::try_default().unwrap();
+    let buffer = ctx.create_buffer::<i32>(2).unwrap();
+    assert!(ctx.write_buffer(&buffer, 1, &[1, 2]).is_err());
+    assert!(ctx.write_buffer(&buffer, 2, &[]).is_ok());
+}
+
+#[test]
+fn test_write_offset_overflow() {
+    let ctx = Context::try_default().unwrap();
+    let buffer = ctx.create_buffer::<i32>(2).unwrap();
+    assert!(ctx.write_buffer(&buffer, usize::MAX, &[1, 2]).is_err());
+}
+
+#[test]
+fn test_read_out_of_bounds() {
+    let ctx = Context::try_default().unwrap();
+    let buffer = ctx.create_buffer_from_slice(&[1, 2, 3]).unwrap();
+    assert!(ctx.read_slice(&buffer, 2, 2).is_err());
+    assert!(ctx.read_slice(&buffer, usize::MAX, 2).is_err());
+    assert!(ctx.read_element(&buffer, 3).is_err());
+    assert!(ctx.read_slice(&buffer, 3, 0).unwrap().is_empty());
+}
@@ -1,6 +1,6 @@
 //! Context tests.
 
-use xnn::Context;
+use xnn::{Context, Error, Tensor};
 
 #[test]
 fn test_try_default() {
@@ -35,3 +35,44 @@ fn test_debug() {
     let debug = format!("{ctx:?}");
     assert!(debug.contains("Context"));
 }
+
+#[test]
+fn test_max_binding_size() {
+    let ctx = Context::try_default().unwrap();
+    assert!(ctx.max_binding_size() >= 128 * 1024 * 1024);
+}
+
+#[test]
+fn test_last_submission() {
+    let ctx = Context::try_default().unwrap();
+    assert_eq!(ctx.last_submission(), None);
+
+    let a = Tensor::<f32>::from_slice(&ctx, &[1.0, 2.0]).unwrap();
+    let b = a.add(&a).unwrap();
+    let (add, label) = ctx.last_submission().unwrap();
+    assert_eq!(label, "add");
+
+    b.to_vec().unwrap();
+    assert_eq!(ctx.last_submission(), Some((add + 1, "read")));
+
+    // Clones share the counter.
+    a.mul(&a).unwrap();
+    assert_eq!(ctx.clone().last_submission(), Some((add + 2, "mul")));
+}
+
+#[test]
+fn test_device_error_names_submission() {
+    let ctx = Context::try_default().unwrap();
+    let a = Tensor::<f32>::from_slice(&ctx, &[1.0]).unwrap();
+    a.add(&a).unwrap();
+    let (id, _) = ctx.last_submission().unwrap();
+
+    let len = usize::try_from(ctx.max_binding_size() / 4 + 1).unwrap();
+    let Err(Error::Device(message)) = Tensor::<f32>::constant(&ctx, &[len], &[0.0]) else {
+        panic!("oversized buffer must fail");
+    };
+    assert!(
+        message.ends_with(&format!("(after submission {id} (add))")),
+        "{message}"
+    );
+}
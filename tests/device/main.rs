@@ -1,3 +1,4 @@
 //! Device integration tests.
 
+mod buffer;
 mod context;
@@ -0,0 +1,4 @@
+//! Diffusion integration tests.
+
+mod sampler;
+mod schedule;
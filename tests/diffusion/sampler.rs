@@ -0,0 +1,105 @@
+//! Tests for DDPM and DDIM sampler steps.
+
+use xnn::diffusion::NoiseSchedule;
+use xnn::{Context, Tensor};
+
+#[test]
+fn test_ddim_deterministic_recovers_clean_sample() {
+    let ctx = Context::try_default().unwrap();
+    let schedule = NoiseSchedule::linear(&ctx, 10, 0.01, 0.2).unwrap();
+    let alpha_bar = schedule.alphas_cumprod().to_vec().unwrap()[9];
+
+    let x0 = [0.5f32, -1.0, 0.25, 2.0];
+    let eps = [1.0f32, -0.5, 0.0, 0.3];
+    let xt: Vec<f32> = x0
+        .iter()
+        .zip(&eps)
+        .map(|(x, e)| alpha_bar.sqrt() * x + (1.0 - alpha_bar).sqrt() * e)
+        .collect();
+
+    let x = Tensor::<f32>::from_slice(&ctx, &xt).unwrap();
+    let e = Tensor::<f32>::from_slice(&ctx, &eps).unwrap();
+    let result = schedule.ddim_step(&x, &e, 9, None, 0.0, 0).unwrap();
+    let result = result.to_vec().unwrap();
+    for (a, b) in result.iter().zip(&x0) {
+        approx::assert_relative_eq!(a, b, epsilon = 1e-4);
+    }
+}
+
+#[test]
+fn test_ddim_coefficients_match_formula() {
+    let ctx = Context::try_default().unwrap();
+    let schedule = NoiseSchedule::linear(&ctx, 50, 1e-4, 0.02).unwrap();
+    let ab = schedule.alphas_cumprod().to_vec().unwrap();
+    let (at, ap) = (f64::from(ab[40]), f64::from(ab[20]));
+
+    let (a, b, c) = schedule.ddim_coefficients(40, Some(20), 0.0).unwrap();
+    assert!(c.abs() < 1e-12);
+    approx::assert_relative_eq!(f64::from(a), (ap / at).sqrt(), epsilon = 1e-5);
+    approx::assert_relative_eq!(
+        f64::from(b),
+        (1.0 - ap).sqrt() - (ap / at).sqrt() * (1.0 - at).sqrt(),
+        epsilon = 1e-5
+    );
+
+    let (_, _, c) = schedule.ddim_coefficients(40, Some(20), 1.0).unwrap();
+    let sigma = ((1.0 - ap) / (1.0 - at) * (1.0 - at / ap)).sqrt();
+    approx::assert_relative_eq!(f64::from(c), sigma, epsilon = 1e-5);
+}
+
+#[test]
+fn test_ddpm_step_statistics() {
+    let ctx = Context::try_default().unwrap();
+    let schedule = NoiseSchedule::linear(&ctx, 10, 0.05, 0.3).unwrap();
+    let (a, b, c) = schedule.ddpm_coefficients(5).unwrap();
+
+    let n = 20_000;
+    let x = Tensor::<f32>::constant(&ctx, &[n], &[1.0]).unwrap();
+    let eps = Tensor::<f32>::constant(&ctx, &[n], &[0.5]).unwrap();
+    let result = schedule.ddpm_step(&x, &eps, 5, 42).unwrap();
+    let values = result.to_vec().unwrap();
+
+    let count = f64::from(u32::try_from(n).unwrap());
+    let mean = values.iter().map(|&v| f64::from(v)).sum::<f64>() / count;
+    let var = values
+        .iter()
+        .map(|&v| (f64::from(v) - mean).powi(2))
+        .sum::<f64>()
+        / count;
+    approx::assert_relative_eq!(mean, f64::from(a + 0.5 * b), epsilon = 0.01);
+    approx::assert_relative_eq!(var.sqrt(), f64::from(c), epsilon = 0.01);
+
+    let again = schedule.ddpm_step(&x, &eps, 5, 42).unwrap();
+    assert_eq!(again.to_vec().unwrap(), values);
+}
+
+#[test]
+fn test_ddpm_final_step_is_noiseless() {
+    let ctx = Context::try_default().unwrap();
+    let schedule = NoiseSchedule::linear(&ctx, 4, 0.1, 0.4).unwrap();
+    let (a, b, c) = schedule.ddpm_coefficients(0).unwrap();
+    assert!(c.abs() < f32::EPSILON);
+
+    let x = Tensor::<f32>::from_slice(&ctx, &[1.0, 2.0]).unwrap();
+    let eps = Tensor::<f32>::from_slice(&ctx, &[0.5, -0.5]).unwrap();
+    let result = schedule
+        .ddpm_step(&x, &eps, 0, 7)
+        .unwrap()
+        .to_vec()
+        .unwrap();
+    approx::assert_relative_eq!(result[0], a + 0.5 * b, epsilon = 1e-6);
+    approx::assert_relative_eq!(result[1], 2.0 * a - 0.5 * b, epsilon = 1e-6);
+}
+
+#[test]
+fn test_sampler_invalid() {
+    let ctx = Context::try_default().unwrap();
+    let schedule = NoiseSchedule::linear(&ctx, 4, 0.1, 0.4).unwrap();
+    assert!(schedule.ddpm_coefficients(4).is_err());
+    assert!(schedule.ddim_coefficients(2, Some(2), 0.0).is_err());
+    assert!(schedule.ddim_coefficients(2, Some(1), -1.0).is_err());
+
+    let x = Tensor::<f32>::from_slice(&ctx, &[1.0, 2.0]).unwrap();
+    let eps = Tensor::<f32>::from_slice(&ctx, &[1.0]).unwrap();
+    assert!(schedule.ddpm_step(&x, &eps, 1, 0).is_err());
+}
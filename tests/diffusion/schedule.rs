@@ -0,0 +1,47 @@
+//! Tests for `NoiseSchedule` construction.
+
+use xnn::Context;
+use xnn::diffusion::NoiseSchedule;
+
+#[test]
+fn test_linear_schedule() {
+    let ctx = Context::try_default().unwrap();
+    let schedule = NoiseSchedule::linear(&ctx, 5, 0.1, 0.5).unwrap();
+    assert_eq!(schedule.steps(), 5);
+
+    let betas = schedule.betas().to_vec().unwrap();
+    let alphas = schedule.alphas().to_vec().unwrap();
+    let alphas_cumprod = schedule.alphas_cumprod().to_vec().unwrap();
+
+    let expected = [0.1, 0.2, 0.3, 0.4, 0.5];
+    let mut prod = 1.0;
+    for i in 0..5 {
+        prod *= 1.0 - expected[i];
+        approx::assert_relative_eq!(betas[i], expected[i], epsilon = 1e-6);
+        approx::assert_relative_eq!(alphas[i], 1.0 - expected[i], epsilon = 1e-6);
+        approx::assert_relative_eq!(alphas_cumprod[i], prod, epsilon = 1e-6);
+    }
+}
+
+#[test]
+fn test_cosine_schedule() {
+    let ctx = Context::try_default().unwrap();
+    let schedule = NoiseSchedule::cosine(&ctx, 1000, 0.008).unwrap();
+    let betas = schedule.betas().to_vec().unwrap();
+    let alphas_cumprod = schedule.alphas_cumprod().to_vec().unwrap();
+
+    assert_eq!(betas.len(), 1000);
+    assert!(betas.iter().all(|&b| b > 0.0 && b <= 0.999));
+    assert!(betas.windows(2).all(|w| w[0] <= w[1]));
+    assert!(alphas_cumprod[0] > 0.999);
+    assert!(alphas_cumprod[999] < 1e-4);
+}
+
+#[test]
+fn test_schedule_invalid() {
+    let ctx = Context::try_default().unwrap();
+    assert!(NoiseSchedule::linear(&ctx, 0, 0.1, 0.2).is_err());
+    assert!(NoiseSchedule::linear(&ctx, 10, 0.0, 0.2).is_err());
+    assert!(NoiseSchedule::linear(&ctx, 10, 0.1, 1.0).is_err());
+    assert!(NoiseSchedule::cosine(&ctx, 10, -0.1).is_err());
+}
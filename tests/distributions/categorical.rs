@@ -0,0 +1,115 @@
+//! Tests for `Categorical`.
+
+use approx::assert_relative_eq;
+use xnn::distributions::Categorical;
+use xnn::{Context, Tensor};
+
+#[test]
+fn test_categorical_log_prob() {
+    let ctx = Context::try_default().unwrap();
+    let logits =
+        Tensor::<f32>::from_shape_slice(&ctx, &[2, 3], &[1.0, 2.0, 3.0, 0.0, 0.0, 0.0]).unwrap();
+    let dist = Categorical::new(logits).unwrap();
+
+    let actions = Tensor::<u32>::from_slice(&ctx, &[2, 1]).unwrap();
+    let log_prob = dist.log_prob(&actions).unwrap().to_vec().unwrap();
+
+    let lse = (1.0f32.exp() + 2.0f32.exp() + 3.0f32.exp()).ln();
+    assert_relative_eq!(log_prob[0], 3.0 - lse, epsilon = 1e-5);
+    assert_relative_eq!(log_prob[1], -(3.0f32.ln()), epsilon = 1e-5);
+
+    let out_of_range = Tensor::<u32>::from_slice(&ctx, &[3, 0]).unwrap();
+    let log_prob = dist.log_prob(&out_of_range).unwrap().to_vec().unwrap();
+    assert_relative_eq!(log_prob[0], f32::MIN);
+}
+
+#[test]
+fn test_categorical_entropy() {
+    let ctx = Context::try_default().unwrap();
+    let logits = Tensor::<f32>::from_shape_slice(
+        &ctx,
+        &[3, 4],
+        &[
+            0.0,
+            0.0,
+            0.0,
+            0.0,
+            50.0,
+            0.0,
+            0.0,
+            0.0,
+            0.0,
+            0.0,
+            f32::MIN,
+            f32::MIN,
+        ],
+    )
+    .unwrap();
+    let dist = Categorical::new(logits).unwrap();
+
+    let entropy = dist.entropy().unwrap().to_vec().unwrap();
+    assert_relative_eq!(entropy[0], 4.0f32.ln(), epsilon = 1e-5);
+    assert_relative_eq!(entropy[1], 0.0, epsilon = 1e-5);
+    assert_relative_eq!(entropy[2], 2.0f32.ln(), epsilon = 1e-5);
+}
+
+#[test]
+fn test_categorical_sample() {
+    let ctx = Context::try_default().unwrap();
+    let rows = 4000;
+    let probs = [0.1f32, 0.2, 0.7];
+    let row: Vec<f32> = probs.iter().map(|p| p.ln()).collect();
+    let logits = Tensor::<f32>::from_slice(&ctx, &row)
+        .unwrap()
+        .unsqueeze(0)
+        .unwrap()
+        .broadcast_to(&[rows, 3])
+        .unwrap();
+    let dist = Categorical::new(logits).unwrap();
+
+    let samples = dist.sample(7).unwrap().to_vec().unwrap();
+    assert_eq!(samples.len(), rows);
+
+    let mut counts = [0u16; 3];
+    for &s in &samples {
+        counts[usize::try_from(s).unwrap()] += 1;
+    }
+    for (&count, &p) in counts.iter().zip(&probs) {
+        assert_relative_eq!(f32::from(count) / 4000.0, p, epsilon = 0.03);
+    }
+
+    assert_eq!(dist.sample(7).unwrap().to_vec().unwrap(), samples);
+    assert_ne!(dist.sample(8).unwrap().to_vec().unwrap(), samples);
+}
+
+#[test]
+fn test_categorical_masked() {
+    let ctx = Context::try_default().unwrap();
+    let logits = Tensor::<f32>::from_shape_slice(
+        &ctx,
+        &[2, 3],
+        &[f32::MIN, 0.0, f32::MIN, 0.0, 0.0, f32::MIN],
+    )
+    .unwrap();
+    let dist = Categorical::new(logits).unwrap();
+
+    for seed in 0..20 {
+        let samples = dist.sample(seed).unwrap().to_vec().unwrap();
+        assert_eq!(samples[0], 1);
+        assert!(samples[1] < 2);
+    }
+}
+
+#[test]
+fn test_categorical_invalid() {
+    let ctx = Context::try_default().unwrap();
+    let flat = Tensor::<f32>::from_slice(&ctx, &[1.0, 2.0]).unwrap();
+    let empty = Tensor::<f32>::from_shape_slice(&ctx, &[2, 0], &[]).unwrap();
+    assert!(Categorical::new(flat).is_err());
+    assert!(Categorical::new(empty).is_err());
+
+    let logits = Tensor::<f32>::constant(&ctx, &[2, 3], &[0.0]).unwrap();
+    let dist = Categorical::new(logits).unwrap();
+    let actions = Tensor::<u32>::from_slice(&ctx, &[0, 1, 2]).unwrap();
+    assert!(dist.log_prob(&actions).is_err());
+}
@@ -0,0 +1,4 @@
+//! Distribution integration tests.
+
+mod categorical;
+mod normal;
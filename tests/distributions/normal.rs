@@ -0,0 +1,81 @@
+//! Tests for `Normal`.
+
+use approx::assert_relative_eq;
+use xnn::distributions::Normal;
+use xnn::{Context, Tensor};
+
+const HALF_LOG_TAU: f32 = 0.918_938_5;
+
+#[test]
+fn test_normal_log_prob() {
+    let ctx = Context::try_default().unwrap();
+    let mean = Tensor::<f32>::from_slice(&ctx, &[0.0, 1.0, -2.0]).unwrap();
+    let std = Tensor::<f32>::from_slice(&ctx, &[1.0, 2.0, 0.5]).unwrap();
+    let dist = Normal::new(mean, std).unwrap();
+
+    let x = Tensor::<f32>::from_slice(&ctx, &[0.0, 3.0, -2.5]).unwrap();
+    let log_prob = dist.log_prob(&x).unwrap().to_vec().unwrap();
+    assert_relative_eq!(log_prob[0], -HALF_LOG_TAU, epsilon = 1e-5);
+    assert_relative_eq!(
+        log_prob[1],
+        -0.5 - 2.0f32.ln() - HALF_LOG_TAU,
+        epsilon = 1e-5
+    );
+    assert_relative_eq!(
+        log_prob[2],
+        -0.5 - 0.5f32.ln() - HALF_LOG_TAU,
+        epsilon = 1e-5
+    );
+}
+
+#[test]
+fn test_normal_entropy() {
+    let ctx = Context::try_default().unwrap();
+    let mean = Tensor::<f32>::from_slice(&ctx, &[5.0, 0.0]).unwrap();
+    let std = Tensor::<f32>::from_slice(&ctx, &[1.0, 3.0]).unwrap();
+    let dist = Normal::new(mean, std).unwrap();
+
+    let entropy = dist.entropy().unwrap().to_vec().unwrap();
+    assert_relative_eq!(entropy[0], 0.5 + HALF_LOG_TAU, epsilon = 1e-5);
+    assert_relative_eq!(entropy[1], 0.5 + HALF_LOG_TAU + 3.0f32.ln(), epsilon = 1e-5);
+}
+
+#[test]
+fn test_normal_sample() {
+    let ctx = Context::try_default().unwrap();
+    let n = 8192;
+    let mean = Tensor::<f32>::constant(&ctx, &[n], &[2.0]).unwrap();
+    let std = Tensor::<f32>::constant(&ctx, &[n], &[0.5]).unwrap();
+    let dist = Normal::new(mean, std).unwrap();
+
+    let samples = dist.sample(3).unwrap();
+    let m = samples.mean_reduce(&[0]).unwrap().item().unwrap();
+    let var = samples
+        .sub(&Tensor::scalar(&ctx, m).unwrap())
+        .unwrap()
+        .sqr()
+        .unwrap()
+        .mean_reduce(&[0])
+        .unwrap()
+        .item()
+        .unwrap();
+    assert_relative_eq!(m, 2.0, epsilon = 0.03);
+    assert_relative_eq!(var, 0.25, epsilon = 0.02);
+
+    let again = dist.sample(3).unwrap();
+    assert_eq!(again.to_vec().unwrap(), samples.to_vec().unwrap());
+}
+
+#[test]
+fn test_normal_invalid() {
+    let ctx = Context::try_default().unwrap();
+    let a = Tensor::<f32>::from_slice(&ctx, &[0.0, 1.0]).unwrap();
+    let b = Tensor::<f32>::from_slice(&ctx, &[1.0]).unwrap();
+    assert!(Normal::new(a, b).is_err());
+
+    let mean = Tensor::<f32>::from_slice(&ctx, &[0.0, 1.0]).unwrap();
+    let std = Tensor::<f32>::from_slice(&ctx, &[1.0, 1.0]).unwrap();
+    let dist = Normal::new(mean, std).unwrap();
+    let x = Tensor::<f32>::from_slice(&ctx, &[0.0]).unwrap();
+    assert!(dist.log_prob(&x).is_err());
+}
@@ -0,0 +1,12 @@
+//! Operator graph IR integration tests.
+
+mod registry;
+mod text;
+
+/// Asserts that `actual` matches `expected` element-wise within an absolute tolerance.
+pub(crate) fn assert_close(actual: &[f32], expected: &[f32]) {
+    assert_eq!(actual.len(), expected.len(), "length mismatch");
+    for (a, e) in actual.iter().zip(expected) {
+        assert!((a - e).abs() < 1e-5, "{actual:?} != {expected:?}");
+    }
+}
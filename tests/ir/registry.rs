@@ -0,0 +1,88 @@
+//! Tests for running a `Graph` with a `Registry`.
+
+use xnn::ir::{Attrs, Graph, Registry};
+use xnn::{Context, Tensor};
+
+#[test]
+fn test_run_builtins() {
+    let ctx = Context::try_default().unwrap();
+    let graph = Graph::parse(
+        "xnn-ir 1\n\
+         input %0 %1\n\
+         %2 = matmul %0 %1 transpose_b=true\n\
+         %3 = relu %2\n\
+         %4 = sum %3 axes=[1]\n\
+         %5 = reshape %4 shape=[-1]\n\
+         output %5 %3\n",
+    )
+    .unwrap();
+
+    let x = Tensor::<f32>::from_shape_slice(&ctx, &[2, 2], &[1.0, 2.0, 3.0, -4.0]).unwrap();
+    let w =
+        Tensor::<f32>::from_shape_slice(&ctx, &[3, 2], &[1.0, 0.0, 0.0, 1.0, 1.0, 1.0]).unwrap();
+    let outputs = graph.run(&Registry::new(), vec![x, w]).unwrap();
+
+    // x·wᵀ = [[1, 2, 3], [3, -4, -1]], rectified then summed over rows.
+    assert_eq!(outputs.len(), 2);
+    assert_eq!(outputs[0].dimensions(), &[2]);
+    crate::assert_close(&outputs[0].to_vec().unwrap(), &[6.0, 3.0]);
+    crate::assert_close(
+        &outputs[1].to_vec().unwrap(),
+        &[1.0, 2.0, 3.0, 3.0, 0.0, 0.0],
+    );
+}
+
+#[test]
+fn test_run_custom_op() {
+    let ctx = Context::try_default().unwrap();
+    let mut registry = Registry::empty();
+    let scale_ctx = ctx.clone();
+    registry.register("scale", move |x, attrs| {
+        let factor = attrs.float("factor", 1.0)?;
+        Ok(vec![x[0].mul(&Tensor::constant(
+            &scale_ctx,
+            &[1],
+            &[factor],
+        )?)?])
+    });
+    assert!(registry.contains("scale"));
+    assert!(!registry.contains("relu"));
+
+    let mut graph = Graph::new(1);
+    let y = graph
+        .node("scale", &[0], Attrs::new().with("factor", 3.0f32))
+        .unwrap();
+    graph.set_outputs(&[y]).unwrap();
+
+    let x = Tensor::<f32>::from_slice(&ctx, &[1.0, -2.0]).unwrap();
+    let outputs = graph.run(&registry, vec![x]).unwrap();
+    crate::assert_close(&outputs[0].to_vec().unwrap(), &[3.0, -6.0]);
+}
+
+#[test]
+fn test_run_invalid() {
+    let ctx = Context::try_default().unwrap();
+    let x = || Tensor::<f32>::from_slice(&ctx, &[1.0]).unwrap();
+    let registry = Registry::new();
+
+    let mut unknown = Graph::new(1);
+    let y = unknown.node("unknown", &[0], Attrs::new()).unwrap();
+    unknown.set_outputs(&[y]).unwrap();
+    assert!(unknown.run(&registry, vec![x()]).is_err());
+    assert!(unknown.run(&registry, vec![]).is_err());
+
+    let mut arity = Graph::new(1);
+    let y = arity.node("add", &[0], Attrs::new()).unwrap();
+    arity.set_outputs(&[y]).unwrap();
+    assert!(arity.run(&registry, vec![x()]).is_err());
+
+    let mut missing = Graph::new(1);
+    let y = missing.node("sum", &[0], Attrs::new()).unwrap();
+    missing.set_outputs(&[y]).unwrap();
+    assert!(missing.run(&registry, vec![x()]).is_err());
+
+    let mut outputs = Graph::new(1);
+    let y = outputs.multi_node("relu", &[0], Attrs::new(), 2).unwrap();
+    outputs.set_outputs(&[y.start]).unwrap();
+    assert!(outputs.run(&registry, vec![x()]).is_err());
+}
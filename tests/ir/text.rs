@@ -0,0 +1,102 @@
+//! Tests for the text form of `Graph`.
+
+use xnn::ir::{Attr, Attrs, Graph};
+
+fn mlp() -> Graph {
+    let mut graph = Graph::new(2);
+    let h = graph
+        .node("matmul", &[0, 1], Attrs::new().with("transpose_b", true))
+        .unwrap();
+    let h = graph.node("relu", &[h], Attrs::new()).unwrap();
+    let y = graph
+        .node(
+            "sum",
+            &[h],
+            Attrs::new().with("axes", [1]).with("scale", 0.5f32),
+        )
+        .unwrap();
+    graph.set_outputs(&[y, h]).unwrap();
+    graph
+}
+
+#[test]
+fn test_export() {
+    assert_eq!(
+        mlp().to_string(),
+        "xnn-ir 1\n\
+         input %0 %1\n\
+         %2 = matmul %0 %1 transpose_b=true\n\
+         %3 = relu %2\n\
+         %4 = sum %3 axes=[1] scale=0.5\n\
+         output %4 %3\n"
+    );
+}
+
+#[test]
+fn test_round_trip() {
+    let graph = mlp();
+    let parsed = Graph::parse(&graph.to_string()).unwrap();
+    assert_eq!(parsed, graph);
+    assert_eq!(parsed.inputs(), 2);
+    assert_eq!(parsed.outputs(), &[4, 3]);
+    assert_eq!(parsed.nodes()[0].op(), "matmul");
+    assert_eq!(
+        parsed.nodes()[2].attrs().get("axes"),
+        Some(&Attr::Ints(vec![1]))
+    );
+}
+
+#[test]
+fn test_parse_attributes() {
+    let graph = Graph::parse(
+        "# comment\n\
+         xnn-ir 1\n\
+         \n\
+         input %0\n\
+         %1 %2 = split %0 flag=false n=-3 eps=1e-5 big=inf empty=[] axes=[0,-1]\n\
+         output %2\n",
+    )
+    .unwrap();
+
+    let node = &graph.nodes()[0];
+    assert_eq!(node.outputs(), 1..3);
+    let attrs = node.attrs();
+    assert!(!attrs.bool("flag", true).unwrap());
+    assert_eq!(attrs.int("n", 0).unwrap(), -3);
+    assert!((attrs.float("eps", 0.0).unwrap() - 1e-5).abs() < 1e-12);
+    assert_eq!(attrs.get("big"), Some(&Attr::Float(f32::INFINITY)));
+    assert_eq!(attrs.ints("empty").unwrap(), &[] as &[i64]);
+    assert_eq!(attrs.ints("axes").unwrap(), &[0, -1]);
+    assert!(attrs.int("eps", 0).is_err());
+    assert!(attrs.ints("missing").is_err());
+    assert_eq!(Graph::parse(&graph.to_string()).unwrap(), graph);
+}
+
+#[test]
+fn test_parse_invalid() {
+    for text in [
+        "",
+        "xnn-ir 2\ninput %0\noutput %0\n",
+        "xnn-ir 1\noutput %0\n",
+        "xnn-ir 1\ninput %1\noutput %1\n",
+        "xnn-ir 1\ninput %0\n%1 = relu %0\n",
+        "xnn-ir 1\ninput %0\n%2 = relu %0\noutput %2\n",
+        "xnn-ir 1\ninput %0\n%1 = relu %1\noutput %1\n",
+        "xnn-ir 1\ninput %0\n%1 = relu %0 x=[1,a]\noutput %1\n",
+        "xnn-ir 1\ninput %0\n%1 = relu a=1 %0\noutput %1\n",
+        "xnn-ir 1\ninput %0\n%1 = relu %0\noutput %1 %1\n",
+        "xnn-ir 1\ninput %0\noutput %0\n%1 = relu %0\n",
+    ] {
+        assert!(Graph::parse(text).is_err(), "{text:?}");
+    }
+}
+
+#[test]
+fn test_builder_invalid() {
+    let mut graph = Graph::new(1);
+    assert!(graph.node("relu", &[1], Attrs::new()).is_err());
+    assert!(graph.node("re lu", &[0], Attrs::new()).is_err());
+    assert!(graph.set_outputs(&[1]).is_err());
+    assert!(graph.set_outputs(&[0, 0]).is_err());
+    assert!(graph.set_outputs(&[0]).is_ok());
+}
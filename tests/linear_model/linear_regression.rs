@@ -0,0 +1,58 @@
+//! Tests for `LinearRegression`.
+
+use xnn::linear_model::LinearRegression;
+use xnn::{Context, Tensor};
+
+#[test]
+fn test_linear_regression() {
+    let ctx = Context::try_default().unwrap();
+    let x = Tensor::<f32>::from_shape_slice(
+        &ctx,
+        &[6, 2],
+        &[
+            -1.0, 0.5, 0.0, -1.0, 1.0, 1.0, 0.5, 0.0, -0.5, -0.5, 1.5, -1.5,
+        ],
+    )
+    .unwrap();
+    let y = Tensor::<f32>::from_slice(&ctx, &[-1.5, 2.0, 2.0, 2.0, 0.5, 5.5]).unwrap();
+
+    let model = LinearRegression::fit(&x, &y, 0.5, 500).unwrap();
+    crate::assert_close(&model.weights().to_vec().unwrap(), &[2.0, -1.0], 1e-3);
+    crate::assert_close(&model.bias().to_vec().unwrap(), &[1.0], 1e-3);
+
+    let new_x = Tensor::<f32>::from_shape_slice(&ctx, &[2, 2], &[2.0, 1.0, 0.0, 0.0]).unwrap();
+    let pred = model.predict(&new_x).unwrap();
+    assert_eq!(pred.dimensions(), &[2]);
+    crate::assert_close(&pred.to_vec().unwrap(), &[4.0, 1.0], 1e-3);
+}
+
+#[test]
+fn test_linear_regression_no_epochs() {
+    let ctx = Context::try_default().unwrap();
+    let x = Tensor::<f32>::from_shape_slice(&ctx, &[2, 1], &[1.0, 2.0]).unwrap();
+    let y = Tensor::<f32>::from_slice(&ctx, &[1.0, 2.0]).unwrap();
+
+    let model = LinearRegression::fit(&x, &y, 0.1, 0).unwrap();
+    assert_eq!(model.predict(&x).unwrap().to_vec().unwrap(), vec![0.0, 0.0]);
+}
+
+#[test]
+fn test_linear_regression_invalid() {
+    let ctx = Context::try_default().unwrap();
+    let x = Tensor::<f32>::from_shape_slice(&ctx, &[2, 2], &[1.0, 2.0, 3.0, 4.0]).unwrap();
+    let y = Tensor::<f32>::from_slice(&ctx, &[1.0, 2.0]).unwrap();
+    let y3 = Tensor::<f32>::from_slice(&ctx, &[1.0, 2.0, 3.0]).unwrap();
+    let flat = Tensor::<f32>::from_slice(&ctx, &[1.0, 2.0]).unwrap();
+    let empty = Tensor::<f32>::from_shape_slice(&ctx, &[0, 2], &[]).unwrap();
+    let no_targets = Tensor::<f32>::from_slice(&ctx, &[]).unwrap();
+
+    assert!(LinearRegression::fit(&x, &y3, 0.1, 1).is_err());
+    assert!(LinearRegression::fit(&flat, &y, 0.1, 1).is_err());
+    assert!(LinearRegression::fit(&empty, &no_targets, 0.1, 1).is_err());
+    assert!(LinearRegression::fit(&x, &y, 0.0, 1).is_err());
+    assert!(LinearRegression::fit(&x, &y, f32::NAN, 1).is_err());
+
+    let model = LinearRegression::fit(&x, &y, 0.1, 1).unwrap();
+    let wide = Tensor::<f32>::from_shape_slice(&ctx, &[1, 3], &[1.0, 2.0, 3.0]).unwrap();
+    assert!(model.predict(&wide).is_err());
+}
@@ -0,0 +1,54 @@
+//! Tests for `LogisticRegression`.
+
+use xnn::linear_model::LogisticRegression;
+use xnn::{Context, Tensor};
+
+#[test]
+fn test_logistic_regression() {
+    let ctx = Context::try_default().unwrap();
+    let x = Tensor::<f32>::from_shape_slice(
+        &ctx,
+        &[8, 2],
+        &[
+            -2.0, -1.0, -1.5, 0.5, -1.0, -2.0, -0.5, -0.5, 0.5, 1.0, 1.0, -0.5, 1.5, 2.0, 2.0, 0.0,
+        ],
+    )
+    .unwrap();
+    let y = Tensor::<f32>::from_slice(&ctx, &[0.0, 0.0, 0.0, 0.0, 1.0, 1.0, 1.0, 1.0]).unwrap();
+
+    let model = LogisticRegression::fit(&x, &y, 1.0, 200).unwrap();
+    assert_eq!(
+        model.predict(&x).unwrap().to_vec().unwrap(),
+        vec![false, false, false, false, true, true, true, true]
+    );
+
+    let proba = model.predict_proba(&x).unwrap().to_vec().unwrap();
+    assert!(proba[..4].iter().all(|&p| p < 0.5));
+    assert!(proba[4..].iter().all(|&p| p > 0.5));
+    assert!(proba[0] < proba[3] && proba[7] > proba[4]);
+
+    let weights = model.weights().to_vec().unwrap();
+    assert!(weights[0] > 0.0);
+}
+
+#[test]
+fn test_logistic_regression_balanced() {
+    let ctx = Context::try_default().unwrap();
+    let x = Tensor::<f32>::from_shape_slice(&ctx, &[4, 1], &[-1.0, -1.0, 1.0, 1.0]).unwrap();
+    let y = Tensor::<f32>::from_slice(&ctx, &[0.0, 1.0, 0.0, 1.0]).unwrap();
+
+    let model = LogisticRegression::fit(&x, &y, 0.5, 50).unwrap();
+    for p in model.predict_proba(&x).unwrap().to_vec().unwrap() {
+        approx::assert_relative_eq!(p, 0.5, epsilon = 1e-5);
+    }
+}
+
+#[test]
+fn test_logistic_regression_invalid() {
+    let ctx = Context::try_default().unwrap();
+    let x = Tensor::<f32>::from_shape_slice(&ctx, &[2, 1], &[1.0, 2.0]).unwrap();
+    let y = Tensor::<f32>::from_shape_slice(&ctx, &[2, 1], &[0.0, 1.0]).unwrap();
+
+    assert!(LogisticRegression::fit(&x, &y, 0.1, 1).is_err());
+    assert!(LogisticRegression::fit(&x, &y.reshape(&[2]).unwrap(), -1.0, 1).is_err());
+}
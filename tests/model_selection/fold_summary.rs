@@ -0,0 +1,42 @@
+//! Tests for `fold_summary`.
+
+use approx::assert_relative_eq;
+use xnn::model_selection::fold_summary;
+use xnn::{Context, Tensor};
+
+#[test]
+fn test_fold_summary() {
+    let ctx = Context::try_default().unwrap();
+    let a = Tensor::<f32>::scalar(&ctx, 0.8).unwrap();
+    let b = Tensor::<f32>::scalar(&ctx, 0.6).unwrap();
+
+    let (mean, std) = fold_summary(&[&a, &b]).unwrap();
+    assert_eq!(mean.dimensions(), &[] as &[usize]);
+    assert_relative_eq!(mean.item().unwrap(), 0.7, epsilon = 1e-6);
+    assert_relative_eq!(std.item().unwrap(), 0.1, epsilon = 1e-6);
+}
+
+#[test]
+fn test_fold_summary_vector() {
+    let ctx = Context::try_default().unwrap();
+    let a = Tensor::<f32>::from_slice(&ctx, &[1.0, 4.0]).unwrap();
+    let b = Tensor::<f32>::from_slice(&ctx, &[3.0, 4.0]).unwrap();
+    let c = Tensor::<f32>::from_slice(&ctx, &[2.0, 4.0]).unwrap();
+
+    let (mean, std) = fold_summary(&[&a, &b, &c]).unwrap();
+    assert_eq!(mean.dimensions(), &[2]);
+    assert_eq!(mean.to_vec().unwrap(), vec![2.0, 4.0]);
+    let std = std.to_vec().unwrap();
+    assert_relative_eq!(std[0], (2.0f32 / 3.0).sqrt(), epsilon = 1e-6);
+    assert_relative_eq!(std[1], 0.0);
+}
+
+#[test]
+fn test_fold_summary_invalid() {
+    let ctx = Context::try_default().unwrap();
+    let a = Tensor::<f32>::from_slice(&ctx, &[1.0, 2.0]).unwrap();
+    let b = Tensor::<f32>::from_slice(&ctx, &[1.0]).unwrap();
+
+    assert!(fold_summary(&[]).is_err());
+    assert!(fold_summary(&[&a, &b]).is_err());
+}
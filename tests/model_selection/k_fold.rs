@@ -0,0 +1,68 @@
+//! Tests for `KFold` and `take_samples`.
+
+use xnn::model_selection::{KFold, take_samples};
+use xnn::{Context, Tensor};
+
+#[test]
+fn test_k_fold() {
+    let ctx = Context::try_default().unwrap();
+    let folds = KFold::new(&ctx, 7, 3, None).unwrap();
+    assert_eq!(folds.folds(), 3);
+
+    let expected = [
+        (vec![3, 4, 5, 6], vec![0, 1, 2]),
+        (vec![0, 1, 2, 5, 6], vec![3, 4]),
+        (vec![0, 1, 2, 3, 4], vec![5, 6]),
+    ];
+    for (fold, (train, validation)) in expected.into_iter().enumerate() {
+        let (t, v) = folds.split(fold).unwrap();
+        assert_eq!(t.to_vec().unwrap(), train);
+        assert_eq!(v.to_vec().unwrap(), validation);
+    }
+}
+
+#[test]
+fn test_k_fold_shuffled() {
+    let ctx = Context::try_default().unwrap();
+    let folds = KFold::new(&ctx, 10, 4, Some(3)).unwrap();
+
+    let mut seen = Vec::new();
+    for fold in 0..folds.folds() {
+        let (train, validation) = folds.split(fold).unwrap();
+        let train = train.to_vec().unwrap();
+        let validation = validation.to_vec().unwrap();
+        assert_eq!(train.len() + validation.len(), 10);
+        assert!(validation.iter().all(|i| !train.contains(i)));
+        seen.extend(validation);
+    }
+
+    seen.sort_unstable();
+    assert_eq!(seen, (0..10).collect::<Vec<u32>>());
+}
+
+#[test]
+fn test_k_fold_invalid() {
+    let ctx = Context::try_default().unwrap();
+    assert!(KFold::new(&ctx, 5, 1, None).is_err());
+    assert!(KFold::new(&ctx, 3, 4, None).is_err());
+
+    let folds = KFold::new(&ctx, 4, 2, None).unwrap();
+    assert!(folds.split(2).is_err());
+}
+
+#[test]
+fn test_take_samples() {
+    let ctx = Context::try_default().unwrap();
+    let x =
+        Tensor::<f32>::from_shape_slice(&ctx, &[4, 2], &[0.0, 1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0])
+            .unwrap();
+    let folds = KFold::new(&ctx, 4, 2, None).unwrap();
+    let (train, validation) = folds.split(0).unwrap();
+
+    let x_train = take_samples(&x, &train).unwrap();
+    assert_eq!(x_train.dimensions(), &[2, 2]);
+    assert_eq!(x_train.to_vec().unwrap(), vec![4.0, 5.0, 6.0, 7.0]);
+
+    let x_validation = take_samples(&x, &validation).unwrap();
+    assert_eq!(x_validation.to_vec().unwrap(), vec![0.0, 1.0, 2.0, 3.0]);
+}
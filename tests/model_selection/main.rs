@@ -0,0 +1,5 @@
+//! Model selection integration tests.
+
+mod fold_summary;
+mod k_fold;
+mod train_test_split;
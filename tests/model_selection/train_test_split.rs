@@ -0,0 +1,40 @@
+//! Tests for `train_test_split`.
+
+use xnn::Context;
+use xnn::model_selection::train_test_split;
+
+#[test]
+fn test_train_test_split() {
+    let ctx = Context::try_default().unwrap();
+
+    let (train, test) = train_test_split(&ctx, 5, 2, None).unwrap();
+    assert_eq!(train.to_vec().unwrap(), vec![2, 3, 4]);
+    assert_eq!(test.to_vec().unwrap(), vec![0, 1]);
+}
+
+#[test]
+fn test_train_test_split_shuffled() {
+    let ctx = Context::try_default().unwrap();
+
+    let (train, test) = train_test_split(&ctx, 100, 30, Some(7)).unwrap();
+    assert_eq!(train.dimensions(), &[70]);
+    assert_eq!(test.dimensions(), &[30]);
+
+    let mut all = [train.to_vec().unwrap(), test.to_vec().unwrap()].concat();
+    let in_order = all.windows(2).all(|w| w[0] < w[1]);
+    all.sort_unstable();
+    assert_eq!(all, (0..100).collect::<Vec<u32>>());
+    assert!(!in_order);
+
+    let (again, _) = train_test_split(&ctx, 100, 30, Some(7)).unwrap();
+    assert_eq!(again.to_vec().unwrap(), train.to_vec().unwrap());
+
+    let (other, _) = train_test_split(&ctx, 100, 30, Some(8)).unwrap();
+    assert_ne!(other.to_vec().unwrap(), train.to_vec().unwrap());
+}
+
+#[test]
+fn test_train_test_split_invalid() {
+    let ctx = Context::try_default().unwrap();
+    assert!(train_test_split(&ctx, 3, 4, None).is_err());
+}
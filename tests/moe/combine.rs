@@ -0,0 +1,53 @@
+//! Tests for `moe::combine`.
+
+#![allow(clippy::cast_precision_loss)]
+
+use approx::assert_relative_eq;
+use xnn::moe::{combine, grouped_matmul, top_k_gating};
+use xnn::{Context, Tensor};
+
+#[test]
+fn test_combine_weights_outputs() {
+    let ctx = Context::try_default().unwrap();
+    let logits =
+        Tensor::<f32>::from_shape_slice(&ctx, &[2, 2], &[0.0, 0.0, 0.0, 2.0_f32.ln()]).unwrap();
+    let routing = top_k_gating(&logits, 2, 2).unwrap();
+
+    // Each expert slot holds its own slot number as features.
+    let expert_out: Vec<f32> = (0..4).flat_map(|i| [i as f32, -(i as f32)]).collect();
+    let expert_out = Tensor::<f32>::from_shape_slice(&ctx, &[2, 2, 2], &expert_out).unwrap();
+
+    let y = combine(&expert_out, &routing).unwrap();
+    assert_eq!(y.dimensions(), &[2, 2]);
+
+    // Token 0 picks experts (0, 1) at slots (0, 3); token 1 picks (1, 0) at slots (2, 1).
+    let expected = [1.5, -1.5, 5.0 / 3.0, -5.0 / 3.0];
+    for (a, e) in y.to_vec().unwrap().iter().zip(&expected) {
+        assert_relative_eq!(a, e, epsilon = 1e-5);
+    }
+}
+
+#[test]
+fn test_combine_skips_dropped() {
+    let ctx = Context::try_default().unwrap();
+    let logits =
+        Tensor::<f32>::from_shape_slice(&ctx, &[3, 2], &[1.0, 0.0, 1.0, 0.0, 1.0, 0.0]).unwrap();
+    let routing = top_k_gating(&logits, 1, 2).unwrap();
+
+    let x = Tensor::<f32>::from_shape_slice(&ctx, &[3, 1], &[1.0, 2.0, 3.0]).unwrap();
+    let weights = Tensor::<f32>::from_shape_slice(&ctx, &[2, 1, 1], &[10.0, 100.0]).unwrap();
+
+    let y = combine(&grouped_matmul(&x, &routing, &weights).unwrap(), &routing).unwrap();
+    assert_eq!(y.to_vec().unwrap(), vec![10.0, 20.0, 0.0]);
+}
+
+#[test]
+fn test_combine_invalid() {
+    let ctx = Context::try_default().unwrap();
+    let logits = Tensor::<f32>::constant(&ctx, &[3, 2], &[0.0]).unwrap();
+    let routing = top_k_gating(&logits, 1, 2).unwrap();
+
+    let out = Tensor::<f32>::constant(&ctx, &[2, 3, 4], &[0.0]).unwrap();
+    assert!(combine(&out, &routing).is_err());
+    assert!(combine(&out.reshape(&[6, 4]).unwrap(), &routing).is_err());
+}
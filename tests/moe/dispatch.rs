@@ -0,0 +1,61 @@
+//! Tests for `moe::dispatch` and `moe::grouped_matmul`.
+
+use xnn::moe::{dispatch, grouped_matmul, top_k_gating};
+use xnn::{Context, Tensor};
+
+#[test]
+fn test_dispatch() {
+    let ctx = Context::try_default().unwrap();
+    let logits =
+        Tensor::<f32>::from_shape_slice(&ctx, &[3, 2], &[0.0, 1.0, 1.0, 0.0, 0.0, 1.0]).unwrap();
+    let x =
+        Tensor::<f32>::from_shape_slice(&ctx, &[3, 2], &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]).unwrap();
+
+    let routing = top_k_gating(&logits, 1, 2).unwrap();
+    let slots = dispatch(&x, &routing).unwrap();
+
+    assert_eq!(slots.dimensions(), &[2, 2, 2]);
+    assert_eq!(
+        slots.to_vec().unwrap(),
+        vec![3.0, 4.0, 0.0, 0.0, 1.0, 2.0, 5.0, 6.0]
+    );
+}
+
+#[test]
+fn test_grouped_matmul() {
+    let ctx = Context::try_default().unwrap();
+    let logits =
+        Tensor::<f32>::from_shape_slice(&ctx, &[3, 2], &[0.0, 1.0, 1.0, 0.0, 0.0, 1.0]).unwrap();
+    let x =
+        Tensor::<f32>::from_shape_slice(&ctx, &[3, 2], &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]).unwrap();
+    // Expert 0 sums the features, expert 1 swaps them.
+    let weights = Tensor::<f32>::from_shape_slice(
+        &ctx,
+        &[2, 2, 2],
+        &[1.0, 0.0, 1.0, 0.0, 0.0, 1.0, 1.0, 0.0],
+    )
+    .unwrap();
+
+    let routing = top_k_gating(&logits, 1, 2).unwrap();
+    let out = grouped_matmul(&x, &routing, &weights).unwrap();
+
+    assert_eq!(out.dimensions(), &[2, 2, 2]);
+    assert_eq!(
+        out.to_vec().unwrap(),
+        vec![7.0, 0.0, 0.0, 0.0, 2.0, 1.0, 6.0, 5.0]
+    );
+}
+
+#[test]
+fn test_dispatch_invalid() {
+    let ctx = Context::try_default().unwrap();
+    let logits = Tensor::<f32>::constant(&ctx, &[3, 2], &[0.0]).unwrap();
+    let routing = top_k_gating(&logits, 1, 2).unwrap();
+
+    let x = Tensor::<f32>::constant(&ctx, &[4, 2], &[0.0]).unwrap();
+    assert!(dispatch(&x, &routing).is_err());
+
+    let x = Tensor::<f32>::constant(&ctx, &[3, 2], &[0.0]).unwrap();
+    let weights = Tensor::<f32>::constant(&ctx, &[3, 2, 2], &[0.0]).unwrap();
+    assert!(grouped_matmul(&x, &routing, &weights).is_err());
+}
@@ -0,0 +1,5 @@
+//! Mixture-of-experts integration tests.
+
+mod combine;
+mod dispatch;
+mod top_k_gating;
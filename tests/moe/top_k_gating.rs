@@ -0,0 +1,64 @@
+//! Tests for `moe::top_k_gating`.
+
+use approx::assert_relative_eq;
+use xnn::moe::top_k_gating;
+use xnn::{Context, Tensor};
+
+#[test]
+fn test_top_k_gating() {
+    let ctx = Context::try_default().unwrap();
+    #[rustfmt::skip]
+    let logits = Tensor::<f32>::from_shape_slice(&ctx, &[3, 3], &[
+        0.0, 2.0, 1.0,
+        3.0, 0.0, 3.0_f32.ln() + 3.0,
+        1.0, 1.0, 5.0,
+    ])
+    .unwrap();
+
+    let routing = top_k_gating(&logits, 2, 4).unwrap();
+    assert_eq!(routing.tokens(), 3);
+    assert_eq!(routing.expert_count(), 3);
+    assert_eq!(routing.capacity(), 4);
+    assert_eq!(routing.experts().to_vec().unwrap(), vec![1, 2, 2, 0, 2, 0]);
+
+    let gates = routing.gates().to_vec().unwrap();
+    let e = std::f32::consts::E;
+    let expected = [e / (e + 1.0), 1.0 / (e + 1.0), 0.75, 0.25];
+    for (g, x) in gates.iter().zip(&expected) {
+        assert_relative_eq!(g, x, epsilon = 1e-5);
+    }
+
+    // First choices (1, 2, 2) are served before second choices (2, 0, 0).
+    assert_eq!(routing.slots().to_vec().unwrap(), vec![4, 10, 8, 0, 9, 1]);
+    assert_eq!(
+        routing.sources().to_vec().unwrap(),
+        vec![1, 2, 3, 3, 0, 3, 3, 3, 1, 2, 0, 3]
+    );
+    assert_eq!(routing.counts().to_vec().unwrap(), vec![2, 1, 3]);
+}
+
+#[test]
+fn test_top_k_gating_capacity() {
+    let ctx = Context::try_default().unwrap();
+    let logits =
+        Tensor::<f32>::from_shape_slice(&ctx, &[4, 2], &[1.0, 0.0, 2.0, 0.0, 3.0, 0.0, 0.0, 1.0])
+            .unwrap();
+
+    let routing = top_k_gating(&logits, 1, 2).unwrap();
+    assert_eq!(routing.experts().to_vec().unwrap(), vec![0, 0, 0, 1]);
+    assert_eq!(routing.slots().to_vec().unwrap(), vec![0, 1, 4, 2]);
+    assert_eq!(routing.gates().to_vec().unwrap(), vec![1.0, 1.0, 0.0, 1.0]);
+    assert_eq!(routing.sources().to_vec().unwrap(), vec![0, 1, 3, 4]);
+    assert_eq!(routing.counts().to_vec().unwrap(), vec![2, 1]);
+}
+
+#[test]
+fn test_top_k_gating_invalid() {
+    let ctx = Context::try_default().unwrap();
+    let logits = Tensor::<f32>::constant(&ctx, &[4, 3], &[0.0]).unwrap();
+
+    assert!(top_k_gating(&logits, 0, 2).is_err());
+    assert!(top_k_gating(&logits, 4, 2).is_err());
+    assert!(top_k_gating(&logits, 1, 0).is_err());
+    assert!(top_k_gating(&logits.reshape(&[12]).unwrap(), 1, 2).is_err());
+}
@@ -0,0 +1,52 @@
+//! Tests for `Convergence`.
+
+use xnn::optim::Convergence;
+use xnn::{Context, Tensor};
+
+#[test]
+fn test_convergence() {
+    let ctx = Context::try_default().unwrap();
+    let mut convergence = Convergence::new(&ctx, 0.1).unwrap();
+    assert!(!convergence.converged().unwrap());
+
+    let large = Tensor::<f32>::from_shape_slice(&ctx, &[2, 1], &[0.3, -0.4]).unwrap();
+    convergence.update(&large).unwrap();
+    assert!(!convergence.converged().unwrap());
+
+    let small = Tensor::<f32>::from_shape_slice(&ctx, &[2, 1], &[0.06, -0.08]).unwrap();
+    convergence.update(&small).unwrap();
+    assert!(convergence.converged().unwrap());
+
+    convergence.update(&large).unwrap();
+    assert!(convergence.converged().unwrap());
+
+    convergence.reset().unwrap();
+    assert!(!convergence.converged().unwrap());
+}
+
+#[test]
+fn test_convergence_stops_dispatches() {
+    let ctx = Context::try_default().unwrap();
+    let mut convergence = Convergence::new(&ctx, 1e-3).unwrap();
+    ctx.set_stop_flag(Some(convergence.flag())).unwrap();
+
+    let a = Tensor::<f32>::from_slice(&ctx, &[1.0, 2.0]).unwrap();
+    convergence.update(&a).unwrap();
+    assert_eq!(a.add(&a).unwrap().to_vec().unwrap(), vec![2.0, 4.0]);
+
+    convergence
+        .update(&Tensor::<f32>::from_slice(&ctx, &[0.0, 0.0]).unwrap())
+        .unwrap();
+    assert_eq!(a.add(&a).unwrap().to_vec().unwrap(), vec![0.0, 0.0]);
+    assert!(convergence.converged().unwrap());
+
+    convergence.reset().unwrap();
+    assert_eq!(a.add(&a).unwrap().to_vec().unwrap(), vec![2.0, 4.0]);
+}
+
+#[test]
+fn test_convergence_invalid() {
+    let ctx = Context::try_default().unwrap();
+    assert!(Convergence::new(&ctx, -1.0).is_err());
+    assert!(Convergence::new(&ctx, f32::NAN).is_err());
+}
@@ -0,0 +1,128 @@
+//! Tests for `Lbfgs`.
+
+use xnn::optim::Lbfgs;
+use xnn::{Context, Tensor};
+
+/// Gradient `A·x - b` of the quadratic `½xᵀAx - bᵀx`, minimized at `[1, -1, 2]`.
+fn quadratic_gradient(ctx: &Context, x: &Tensor<f32>) -> Tensor<f32> {
+    let a = Tensor::<f32>::from_shape_slice(
+        ctx,
+        &[3, 3],
+        &[4.0, 1.0, 0.0, 1.0, 3.0, 0.5, 0.0, 0.5, 2.0],
+    )
+    .unwrap();
+    let b = Tensor::<f32>::from_shape_slice(ctx, &[3, 1], &[3.0, -1.0, 3.5]).unwrap();
+    a.matmul(x, false, false).unwrap().sub(&b).unwrap()
+}
+
+#[test]
+fn test_lbfgs_first_direction() {
+    let ctx = Context::try_default().unwrap();
+    let mut optimizer = Lbfgs::new(5).unwrap();
+    let x = Tensor::<f32>::from_slice(&ctx, &[1.0, 2.0]).unwrap();
+    let g = Tensor::<f32>::from_slice(&ctx, &[0.5, -3.0]).unwrap();
+
+    let direction = optimizer.direction(&x, &g).unwrap();
+    assert_eq!(direction.to_vec().unwrap(), vec![-0.5, 3.0]);
+    assert!(optimizer.is_empty());
+}
+
+#[test]
+fn test_lbfgs_quadratic() {
+    let ctx = Context::try_default().unwrap();
+    let mut optimizer = Lbfgs::new(5).unwrap();
+    let mut x = Tensor::<f32>::constant(&ctx, &[3, 1], &[0.0]).unwrap();
+
+    let mut x_next = optimizer
+        .step(&x, &quadratic_gradient(&ctx, &x), 0.2)
+        .unwrap();
+    for _ in 0..30 {
+        x = x_next;
+        x_next = optimizer
+            .step(&x, &quadratic_gradient(&ctx, &x), 1.0)
+            .unwrap();
+    }
+
+    assert_eq!(optimizer.len(), 5);
+    crate::assert_close(&x_next.to_vec().unwrap(), &[1.0, -1.0, 2.0], 1e-4);
+}
+
+#[test]
+fn test_lbfgs_masked() {
+    let ctx = Context::try_default().unwrap();
+    let mut optimizer = Lbfgs::new(5).unwrap();
+    let mask = Tensor::<bool>::from_shape_slice(&ctx, &[3, 1], &[true, false, true]).unwrap();
+    optimizer.set_mask(Some(mask));
+    let mut x = Tensor::<f32>::constant(&ctx, &[3, 1], &[0.0]).unwrap();
+
+    for i in 0..30 {
+        let rate = if i == 0 { 0.2 } else { 1.0 };
+        x = optimizer
+            .step(&x, &quadratic_gradient(&ctx, &x), rate)
+            .unwrap();
+    }
+
+    let x = x.to_vec().unwrap();
+    assert_eq!(x[1].to_bits(), 0);
+    crate::assert_close(&x, &[0.75, 0.0, 1.75], 1e-4);
+}
+
+#[test]
+fn test_lbfgs_skips_negative_curvature() {
+    let ctx = Context::try_default().unwrap();
+    let mut optimizer = Lbfgs::new(3).unwrap();
+    let x0 = Tensor::<f32>::from_slice(&ctx, &[0.0, 0.0]).unwrap();
+    let g0 = Tensor::<f32>::from_slice(&ctx, &[1.0, 1.0]).unwrap();
+    let x1 = Tensor::<f32>::from_slice(&ctx, &[1.0, 0.0]).unwrap();
+    let g1 = Tensor::<f32>::from_slice(&ctx, &[0.0, 2.0]).unwrap();
+
+    optimizer.direction(&x0, &g0).unwrap();
+    let direction = optimizer.direction(&x1, &g1).unwrap();
+    assert_eq!(optimizer.len(), 1);
+    assert_eq!(direction.to_vec().unwrap(), vec![0.0, -2.0]);
+
+    optimizer.reset();
+    assert!(optimizer.is_empty());
+}
+
+#[test]
+fn test_lbfgs_invalid() {
+    let ctx = Context::try_default().unwrap();
+    assert!(Lbfgs::new(0).is_err());
+
+    let mut optimizer = Lbfgs::new(2).unwrap();
+    let x = Tensor::<f32>::from_slice(&ctx, &[1.0, 2.0]).unwrap();
+    let g = Tensor::<f32>::from_slice(&ctx, &[1.0, 2.0, 3.0]).unwrap();
+    assert!(optimizer.direction(&x, &g).is_err());
+    assert!(optimizer.step(&x, &x, 0.0).is_err());
+    assert!(optimizer.step(&x, &x, f32::NAN).is_err());
+
+    optimizer.direction(&x, &x).unwrap();
+    assert!(optimizer.direction(&g, &g).is_err());
+}
+
+#[test]
+fn test_lbfgs_long_diagonal() {
+    let ctx = Context::try_default().unwrap();
+    let n = 5000;
+    let scales: Vec<f32> = (0..n)
+        .map(|i| f32::from(1 + u8::try_from(i % 5).unwrap()))
+        .collect();
+    let target: Vec<f32> = (0..n)
+        .map(|i| f32::from(u8::try_from(i % 7).unwrap()) - 3.0)
+        .collect();
+    let d = Tensor::<f32>::from_slice(&ctx, &scales).unwrap();
+    let b = d
+        .mul(&Tensor::<f32>::from_slice(&ctx, &target).unwrap())
+        .unwrap();
+
+    let mut optimizer = Lbfgs::new(8).unwrap();
+    let mut x = Tensor::<f32>::constant(&ctx, &[n], &[0.0]).unwrap();
+    for i in 0..40 {
+        let gradient = d.mul(&x).unwrap().sub(&b).unwrap();
+        let rate = if i == 0 { 0.2 } else { 1.0 };
+        x = optimizer.step(&x, &gradient, rate).unwrap();
+    }
+
+    crate::assert_close(&x.to_vec().unwrap(), &target, 1e-3);
+}
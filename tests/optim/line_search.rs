@@ -0,0 +1,124 @@
+//! Tests for `LineSearch`.
+
+use xnn::optim::{Condition, LineSearch};
+use xnn::{Context, Error, Tensor};
+
+/// Loss `Σ (x - c)²` with `c = [1, 2]` and its gradient `2·(x - c)`.
+fn objective(ctx: &Context, x: &Tensor<f32>) -> Result<(Tensor<f32>, Tensor<f32>), Error> {
+    let c = Tensor::<f32>::from_slice(ctx, &[1.0, 2.0])?;
+    let r = x.sub(&c)?;
+    let two = Tensor::<f32>::from_slice(ctx, &[2.0])?;
+    Ok((r.mul(&r)?.sum_all()?, r.mul(&two)?))
+}
+
+/// Starting point `0` with its loss, gradient and the steepest descent direction `[2, 4]`,
+/// along which the loss is `5·(2t - 1)²`.
+fn start(ctx: &Context) -> [Tensor<f32>; 4] {
+    let x = Tensor::<f32>::from_slice(ctx, &[0.0, 0.0]).unwrap();
+    let (loss, gradient) = objective(ctx, &x).unwrap();
+    let direction = gradient.neg().unwrap();
+    [x, loss, gradient, direction]
+}
+
+#[test]
+fn test_line_search_armijo() {
+    let ctx = Context::try_default().unwrap();
+    let [x, loss, gradient, direction] = start(&ctx);
+    let search = LineSearch::new(Condition::Armijo, 1e-4, 10).unwrap();
+
+    let step = search
+        .search(&x, &loss, &gradient, &direction, 4.0, |x| {
+            objective(&ctx, x)
+        })
+        .unwrap();
+    assert!(step.satisfied());
+    assert_eq!(step.evaluations(), 4);
+    approx::assert_relative_eq!(step.step(), 0.5);
+    assert_eq!(step.params().to_vec().unwrap(), vec![1.0, 2.0]);
+    approx::assert_relative_eq!(step.loss().item().unwrap(), 0.0);
+
+    let (params, loss, gradient) = step.into_parts();
+    assert_eq!(params.to_vec().unwrap(), vec![1.0, 2.0]);
+    approx::assert_relative_eq!(loss.item().unwrap(), 0.0);
+    assert_eq!(gradient.to_vec().unwrap(), vec![0.0, 0.0]);
+}
+
+#[test]
+fn test_line_search_wolfe_expands() {
+    let ctx = Context::try_default().unwrap();
+    let [x, loss, gradient, direction] = start(&ctx);
+    let search = LineSearch::new(Condition::Wolfe(0.9), 1e-4, 10).unwrap();
+
+    let step = search
+        .search(&x, &loss, &gradient, &direction, 0.01, |x| {
+            objective(&ctx, x)
+        })
+        .unwrap();
+    assert!(step.satisfied());
+    assert_eq!(step.evaluations(), 4);
+    approx::assert_relative_eq!(step.step(), 0.08);
+}
+
+#[test]
+fn test_line_search_wolfe_bisects() {
+    let ctx = Context::try_default().unwrap();
+    let [x, loss, gradient, direction] = start(&ctx);
+    let search = LineSearch::new(Condition::Wolfe(0.9), 1e-4, 10).unwrap();
+
+    let step = search
+        .search(&x, &loss, &gradient, &direction, 2.0, |x| {
+            objective(&ctx, x)
+        })
+        .unwrap();
+    assert!(step.satisfied());
+    assert_eq!(step.evaluations(), 3);
+    approx::assert_relative_eq!(step.step(), 0.5);
+}
+
+#[test]
+fn test_line_search_gives_up() {
+    let ctx = Context::try_default().unwrap();
+    let [x, loss, gradient, direction] = start(&ctx);
+    let search = LineSearch::new(Condition::Armijo, 1e-4, 2).unwrap();
+
+    let step = search
+        .search(&x, &loss, &gradient, &direction, 4.0, |x| {
+            objective(&ctx, x)
+        })
+        .unwrap();
+    assert!(!step.satisfied());
+    assert_eq!(step.evaluations(), 2);
+    approx::assert_relative_eq!(step.step(), 2.0);
+}
+
+#[test]
+fn test_line_search_invalid() {
+    assert!(LineSearch::new(Condition::Armijo, 0.0, 10).is_err());
+    assert!(LineSearch::new(Condition::Armijo, 1.0, 10).is_err());
+    assert!(LineSearch::new(Condition::Armijo, 1e-4, 0).is_err());
+    assert!(LineSearch::new(Condition::Wolfe(1e-5), 1e-4, 10).is_err());
+    assert!(LineSearch::new(Condition::Wolfe(1.0), 1e-4, 10).is_err());
+
+    let ctx = Context::try_default().unwrap();
+    let [x, loss, gradient, direction] = start(&ctx);
+    let search = LineSearch::new(Condition::Armijo, 1e-4, 10).unwrap();
+    assert!(
+        search
+            .search(&x, &loss, &gradient, &gradient, 1.0, |x| objective(&ctx, x))
+            .is_err()
+    );
+    assert!(
+        search
+            .search(&x, &loss, &gradient, &direction, 0.0, |x| objective(
+                &ctx, x
+            ))
+            .is_err()
+    );
+
+    let wide = Tensor::<f32>::from_slice(&ctx, &[1.0, 1.0, 1.0]).unwrap();
+    assert!(
+        search
+            .search(&x, &loss, &gradient, &wide, 1.0, |x| objective(&ctx, x))
+            .is_err()
+    );
+}
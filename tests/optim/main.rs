@@ -0,0 +1,14 @@
+//! Optimizer integration tests.
+
+mod convergence;
+mod lbfgs;
+mod line_search;
+
+/// Asserts that `actual` matches `expected` element-wise within `epsilon`.
+#[track_caller]
+pub(crate) fn assert_close(actual: &[f32], expected: &[f32], epsilon: f32) {
+    assert_eq!(actual.len(), expected.len(), "length mismatch");
+    for (a, e) in actual.iter().zip(expected) {
+        approx::assert_relative_eq!(a, e, epsilon = epsilon);
+    }
+}
@@ -0,0 +1,14 @@
+//! Preprocessing integration tests.
+
+mod min_max_scaler;
+mod standard_scaler;
+mod target_encoder;
+
+/// Asserts that `actual` matches `expected` element-wise within a relative tolerance.
+#[track_caller]
+pub(crate) fn assert_close(actual: &[f32], expected: &[f32]) {
+    assert_eq!(actual.len(), expected.len(), "length mismatch");
+    for (a, e) in actual.iter().zip(expected) {
+        approx::assert_relative_eq!(a, e, epsilon = 1e-5);
+    }
+}
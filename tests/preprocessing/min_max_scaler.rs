@@ -0,0 +1,52 @@
+//! Tests for `MinMaxScaler`.
+
+use xnn::preprocessing::MinMaxScaler;
+use xnn::{Context, Tensor};
+
+#[test]
+fn test_min_max_scaler() {
+    let ctx = Context::try_default().unwrap();
+    let x =
+        Tensor::<f32>::from_shape_slice(&ctx, &[3, 2], &[1.0, -4.0, 3.0, 0.0, 5.0, 4.0]).unwrap();
+
+    let (scaler, y) = MinMaxScaler::fit_transform(&x, (0.0, 1.0)).unwrap();
+    crate::assert_close(&scaler.min().to_vec().unwrap(), &[1.0, -4.0]);
+    crate::assert_close(&scaler.max().to_vec().unwrap(), &[5.0, 4.0]);
+    crate::assert_close(&y.to_vec().unwrap(), &[0.0, 0.0, 0.5, 0.5, 1.0, 1.0]);
+
+    let back = scaler.inverse_transform(&y).unwrap();
+    crate::assert_close(&back.to_vec().unwrap(), &x.to_vec().unwrap());
+}
+
+#[test]
+fn test_min_max_scaler_range() {
+    let ctx = Context::try_default().unwrap();
+    let x = Tensor::<f32>::from_shape_slice(&ctx, &[3, 1], &[10.0, 15.0, 20.0]).unwrap();
+
+    let scaler = MinMaxScaler::fit(&x, (-1.0, 1.0)).unwrap();
+    let batch = Tensor::<f32>::from_shape_slice(&ctx, &[2, 1], &[12.5, 30.0]).unwrap();
+    crate::assert_close(
+        &scaler.transform(&batch).unwrap().to_vec().unwrap(),
+        &[-0.5, 3.0],
+    );
+}
+
+#[test]
+fn test_min_max_scaler_constant_feature() {
+    let ctx = Context::try_default().unwrap();
+    let x = Tensor::<f32>::from_shape_slice(&ctx, &[2, 2], &[7.0, 0.0, 7.0, 2.0]).unwrap();
+
+    let (_, y) = MinMaxScaler::fit_transform(&x, (2.0, 4.0)).unwrap();
+    crate::assert_close(&y.to_vec().unwrap(), &[2.0, 2.0, 2.0, 4.0]);
+}
+
+#[test]
+fn test_min_max_scaler_invalid() {
+    let ctx = Context::try_default().unwrap();
+    let x = Tensor::<f32>::constant(&ctx, &[2, 3], &[1.0]).unwrap();
+    assert!(MinMaxScaler::fit(&x, (1.0, 1.0)).is_err());
+    assert!(MinMaxScaler::fit(&x, (0.0, f32::NAN)).is_err());
+
+    let empty = Tensor::<f32>::constant(&ctx, &[0, 3], &[0.0]).unwrap();
+    assert!(MinMaxScaler::fit(&empty, (0.0, 1.0)).is_err());
+}
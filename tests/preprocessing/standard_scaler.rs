@@ -0,0 +1,69 @@
+//! Tests for `StandardScaler`.
+
+use xnn::preprocessing::StandardScaler;
+use xnn::{Context, Tensor};
+
+#[test]
+fn test_standard_scaler() {
+    let ctx = Context::try_default().unwrap();
+    let x = Tensor::<f32>::from_shape_slice(
+        &ctx,
+        &[4, 2],
+        &[1.0, 10.0, 2.0, 20.0, 3.0, 30.0, 6.0, 60.0],
+    )
+    .unwrap();
+
+    let (scaler, y) = StandardScaler::fit_transform(&x).unwrap();
+    assert_eq!(scaler.mean().dimensions(), &[1, 2]);
+    crate::assert_close(&scaler.mean().to_vec().unwrap(), &[3.0, 30.0]);
+
+    let std = 3.5f32.sqrt();
+    crate::assert_close(&scaler.scale().to_vec().unwrap(), &[std, 10.0 * std]);
+
+    let expected: Vec<f32> = [-2.0, -2.0, -1.0, -1.0, 0.0, 0.0, 3.0, 3.0]
+        .iter()
+        .map(|v| v / std)
+        .collect();
+    assert_eq!(y.dimensions(), &[4, 2]);
+    crate::assert_close(&y.to_vec().unwrap(), &expected);
+
+    let back = scaler.inverse_transform(&y).unwrap();
+    crate::assert_close(&back.to_vec().unwrap(), &x.to_vec().unwrap());
+}
+
+#[test]
+fn test_standard_scaler_constant_feature() {
+    let ctx = Context::try_default().unwrap();
+    let x =
+        Tensor::<f32>::from_shape_slice(&ctx, &[3, 2], &[5.0, 1.0, 5.0, 2.0, 5.0, 3.0]).unwrap();
+
+    let scaler = StandardScaler::fit(&x).unwrap();
+    let y = scaler.transform(&x).unwrap().to_vec().unwrap();
+    let std = (2.0f32 / 3.0).sqrt();
+    crate::assert_close(&y, &[0.0, -1.0 / std, 0.0, 0.0, 0.0, 1.0 / std]);
+}
+
+#[test]
+fn test_standard_scaler_new_batch() {
+    let ctx = Context::try_default().unwrap();
+    let x = Tensor::<f32>::from_shape_slice(&ctx, &[2, 1], &[0.0, 2.0]).unwrap();
+    let scaler = StandardScaler::fit(&x).unwrap();
+
+    let batch = Tensor::<f32>::from_shape_slice(&ctx, &[3, 1], &[1.0, 3.0, -1.0]).unwrap();
+    let y = scaler.transform(&batch).unwrap();
+    crate::assert_close(&y.to_vec().unwrap(), &[0.0, 2.0, -2.0]);
+}
+
+#[test]
+fn test_standard_scaler_invalid() {
+    let ctx = Context::try_default().unwrap();
+    let point = Tensor::<f32>::scalar(&ctx, 1.0).unwrap();
+    let empty = Tensor::<f32>::constant(&ctx, &[0, 3], &[0.0]).unwrap();
+    assert!(StandardScaler::fit(&point).is_err());
+    assert!(StandardScaler::fit(&empty).is_err());
+
+    let x = Tensor::<f32>::constant(&ctx, &[2, 3], &[1.0]).unwrap();
+    let scaler = StandardScaler::fit(&x).unwrap();
+    let wrong = Tensor::<f32>::constant(&ctx, &[2, 2], &[1.0]).unwrap();
+    assert!(scaler.transform(&wrong).is_err());
+}
@@ -0,0 +1,62 @@
+//! Tests for `TargetEncoder`.
+
+use xnn::preprocessing::TargetEncoder;
+use xnn::{Context, Tensor};
+
+#[test]
+fn test_target_encoder() {
+    let ctx = Context::try_default().unwrap();
+    let keys = Tensor::<u32>::from_slice(&ctx, &[0, 1, 0, 2, 1, 0]).unwrap();
+    let targets = Tensor::<f32>::from_slice(&ctx, &[1.0, 0.0, 1.0, 1.0, 1.0, 0.0]).unwrap();
+
+    let (encoder, y) = TargetEncoder::fit_transform(&keys, &targets, 4, 0.0).unwrap();
+    crate::assert_close(&encoder.prior().to_vec().unwrap(), &[4.0 / 6.0]);
+    crate::assert_close(
+        &encoder.encodings().to_vec().unwrap(),
+        &[2.0 / 3.0, 0.5, 1.0, 4.0 / 6.0],
+    );
+    crate::assert_close(
+        &y.to_vec().unwrap(),
+        &[2.0 / 3.0, 0.5, 2.0 / 3.0, 1.0, 0.5, 2.0 / 3.0],
+    );
+}
+
+#[test]
+fn test_target_encoder_smoothing() {
+    let ctx = Context::try_default().unwrap();
+    let keys = Tensor::<u32>::from_slice(&ctx, &[0, 0, 0, 1]).unwrap();
+    let targets = Tensor::<f32>::from_slice(&ctx, &[3.0, 3.0, 3.0, 7.0]).unwrap();
+
+    let encoder = TargetEncoder::fit(&keys, &targets, 2, 2.0).unwrap();
+    let prior = 4.0;
+    crate::assert_close(
+        &encoder.encodings().to_vec().unwrap(),
+        &[(9.0 + 2.0 * prior) / 5.0, (7.0 + 2.0 * prior) / 3.0],
+    );
+}
+
+#[test]
+fn test_target_encoder_unseen_keys() {
+    let ctx = Context::try_default().unwrap();
+    let keys = Tensor::<u32>::from_slice(&ctx, &[0, 1]).unwrap();
+    let targets = Tensor::<f32>::from_slice(&ctx, &[2.0, 4.0]).unwrap();
+    let encoder = TargetEncoder::fit(&keys, &targets, 2, 0.0).unwrap();
+
+    let new_keys = Tensor::<u32>::from_slice(&ctx, &[1, 9, 0]).unwrap();
+    let y = encoder.transform(&new_keys).unwrap();
+    crate::assert_close(&y.to_vec().unwrap(), &[4.0, 3.0, 2.0]);
+}
+
+#[test]
+fn test_target_encoder_invalid() {
+    let ctx = Context::try_default().unwrap();
+    let keys = Tensor::<u32>::from_slice(&ctx, &[0, 1]).unwrap();
+    let targets = Tensor::<f32>::from_slice(&ctx, &[2.0, 4.0]).unwrap();
+    let wide = Tensor::<f32>::constant(&ctx, &[2, 2], &[1.0]).unwrap();
+    let short = Tensor::<u32>::from_slice(&ctx, &[0]).unwrap();
+
+    assert!(TargetEncoder::fit(&keys, &targets, 2, -1.0).is_err());
+    assert!(TargetEncoder::fit(&keys, &targets, 2, f32::NAN).is_err());
+    assert!(TargetEncoder::fit(&keys, &wide, 2, 0.0).is_err());
+    assert!(TargetEncoder::fit(&short, &targets, 2, 0.0).is_err());
+}
@@ -0,0 +1,42 @@
+//! Tests for `prune::apply_mask`.
+
+use xnn::prune::{apply_mask, magnitude_mask};
+use xnn::{Context, Tensor};
+
+#[test]
+fn test_apply_mask_basic() {
+    let ctx = Context::try_default().unwrap();
+    let w = Tensor::<f32>::from_slice(&ctx, &[1.0, 2.0, 3.0, 4.0]).unwrap();
+    let mask = Tensor::<bool>::from_slice(&ctx, &[true, false, false, true]).unwrap();
+    let result = apply_mask(&w, &mask).unwrap();
+    assert_eq!(result.to_vec().unwrap(), vec![1.0, 0.0, 0.0, 4.0]);
+}
+
+#[test]
+fn test_apply_mask_broadcast() {
+    let ctx = Context::try_default().unwrap();
+    let w = Tensor::<f32>::from_shape_slice(&ctx, &[2, 2], &[1.0, 2.0, 3.0, 4.0]).unwrap();
+    let mask = Tensor::<bool>::from_shape_slice(&ctx, &[2, 1], &[false, true]).unwrap();
+    let result = apply_mask(&w, &mask).unwrap();
+    assert_eq!(result.to_vec().unwrap(), vec![0.0, 0.0, 3.0, 4.0]);
+}
+
+#[test]
+fn test_apply_mask_after_update() {
+    let ctx = Context::try_default().unwrap();
+    let w = Tensor::<f32>::from_slice(&ctx, &[0.1, -2.0, 0.3, 4.0]).unwrap();
+    let mask = magnitude_mask(&w, 0.5).unwrap();
+
+    let grad = Tensor::<f32>::constant(&ctx, &[4], &[1.0]).unwrap();
+    let updated = w.sub(&grad).unwrap();
+    let result = apply_mask(&updated, &mask).unwrap();
+    assert_eq!(result.to_vec().unwrap(), vec![0.0, -3.0, 0.0, 3.0]);
+}
+
+#[test]
+fn test_apply_mask_invalid_shape() {
+    let ctx = Context::try_default().unwrap();
+    let w = Tensor::<f32>::from_slice(&ctx, &[1.0, 2.0]).unwrap();
+    let mask = Tensor::<bool>::from_slice(&ctx, &[true, false, true]).unwrap();
+    assert!(apply_mask(&w, &mask).is_err());
+}
@@ -0,0 +1,57 @@
+//! Tests for `prune::magnitude_mask`.
+
+use xnn::prune::magnitude_mask;
+use xnn::{Context, Tensor};
+
+#[test]
+fn test_magnitude_mask_basic() {
+    let ctx = Context::try_default().unwrap();
+    let w =
+        Tensor::<f32>::from_shape_slice(&ctx, &[2, 3], &[0.5, -3.0, 0.1, 2.0, -0.2, 1.0]).unwrap();
+    let mask = magnitude_mask(&w, 0.5).unwrap();
+    assert_eq!(mask.dimensions(), &[2, 3]);
+    assert_eq!(
+        mask.to_vec().unwrap(),
+        vec![false, true, false, true, false, true]
+    );
+}
+
+#[test]
+fn test_magnitude_mask_ties_lowest_index_first() {
+    let ctx = Context::try_default().unwrap();
+    let w = Tensor::<f32>::from_slice(&ctx, &[1.0, -1.0, 2.0, 1.0, -1.0]).unwrap();
+    let mask = magnitude_mask(&w, 0.4).unwrap();
+    assert_eq!(mask.to_vec().unwrap(), vec![false, false, true, true, true]);
+}
+
+#[test]
+fn test_magnitude_mask_bounds() {
+    let ctx = Context::try_default().unwrap();
+    let w = Tensor::<f32>::from_slice(&ctx, &[1.0, 2.0, 3.0]).unwrap();
+    assert_eq!(
+        magnitude_mask(&w, 0.0).unwrap().to_vec().unwrap(),
+        vec![true; 3]
+    );
+    assert_eq!(
+        magnitude_mask(&w, 1.0).unwrap().to_vec().unwrap(),
+        vec![false; 3]
+    );
+}
+
+#[test]
+fn test_magnitude_mask_exact_count() {
+    let ctx = Context::try_default().unwrap();
+    let data: Vec<f32> = (0..1000u16).map(|i| f32::from(i % 37) - 18.0).collect();
+    let w = Tensor::<f32>::from_shape_slice(&ctx, &[10, 100], &data).unwrap();
+    let mask = magnitude_mask(&w, 0.7).unwrap().to_vec().unwrap();
+    assert_eq!(mask.iter().filter(|&&keep| !keep).count(), 700);
+}
+
+#[test]
+fn test_magnitude_mask_invalid_sparsity() {
+    let ctx = Context::try_default().unwrap();
+    let w = Tensor::<f32>::from_slice(&ctx, &[1.0]).unwrap();
+    assert!(magnitude_mask(&w, -0.1).is_err());
+    assert!(magnitude_mask(&w, 1.5).is_err());
+    assert!(magnitude_mask(&w, f32::NAN).is_err());
+}
@@ -0,0 +1,5 @@
+//! Pruning integration tests.
+
+mod apply_mask;
+mod magnitude_mask;
+mod snapshot;
@@ -0,0 +1,42 @@
+//! Tests for `prune::Snapshot`.
+
+use xnn::prune::{Snapshot, apply_mask, magnitude_mask};
+use xnn::{Context, Tensor};
+
+#[test]
+fn test_snapshot_is_independent_copy() {
+    let ctx = Context::try_default().unwrap();
+    let mut w = Tensor::<f32>::from_slice(&ctx, &[1.0, 2.0, 3.0]).unwrap();
+    let snapshot = Snapshot::new(&w).unwrap();
+
+    let src = Tensor::<f32>::constant(&ctx, &[3], &[9.0]).unwrap();
+    let all = Tensor::<bool>::constant(&ctx, &[3], &[true]).unwrap();
+    w.masked_copy_(&src, &all).unwrap();
+
+    assert_eq!(w.to_vec().unwrap(), vec![9.0; 3]);
+    assert_eq!(snapshot.weights().to_vec().unwrap(), vec![1.0, 2.0, 3.0]);
+}
+
+#[test]
+fn test_snapshot_rewind_by_mask() {
+    let ctx = Context::try_default().unwrap();
+    let init = Tensor::<f32>::from_slice(&ctx, &[0.1, -0.2, 0.3, -0.4]).unwrap();
+    let snapshot = Snapshot::new(&init).unwrap();
+
+    let mut trained = Tensor::<f32>::from_slice(&ctx, &[0.05, -2.0, 0.01, 3.0]).unwrap();
+    let mask = magnitude_mask(&trained, 0.5).unwrap();
+    snapshot.rewind(&mut trained, &mask).unwrap();
+    assert_eq!(trained.to_vec().unwrap(), vec![0.05, -0.2, 0.01, -0.4]);
+
+    let ticket = apply_mask(&trained, &mask).unwrap();
+    assert_eq!(ticket.to_vec().unwrap(), vec![0.0, -0.2, 0.0, -0.4]);
+}
+
+#[test]
+fn test_snapshot_rewind_invalid_shape() {
+    let ctx = Context::try_default().unwrap();
+    let snapshot = Snapshot::new(&Tensor::<f32>::from_slice(&ctx, &[1.0, 2.0]).unwrap()).unwrap();
+    let mut live = Tensor::<f32>::from_slice(&ctx, &[1.0, 2.0, 3.0]).unwrap();
+    let mask = Tensor::<bool>::from_slice(&ctx, &[true, true, true]).unwrap();
+    assert!(snapshot.rewind(&mut live, &mask).is_err());
+}
@@ -0,0 +1,27 @@
+//! Tests for `HistogramObserver`.
+
+use xnn::quant::HistogramObserver;
+use xnn::{Context, Tensor};
+
+#[test]
+fn test_histogram_observer_accumulates() {
+    let ctx = Context::try_default().unwrap();
+    let mut observer = HistogramObserver::new(&ctx, 4, 0.0, 4.0).unwrap();
+    assert_eq!(observer.range(), (0.0, 4.0));
+    assert_eq!(observer.counts().to_vec().unwrap(), vec![0, 0, 0, 0]);
+
+    let a = Tensor::<f32>::from_slice(&ctx, &[0.5, 1.5, 1.7, 3.9]).unwrap();
+    let b = Tensor::<f32>::from_shape_slice(&ctx, &[2, 2], &[-5.0, 2.0, 9.0, 1.0]).unwrap();
+    observer.observe(&a).unwrap();
+    observer.observe(&b).unwrap();
+
+    assert_eq!(observer.counts().to_vec().unwrap(), vec![2, 3, 1, 2]);
+}
+
+#[test]
+fn test_histogram_observer_invalid() {
+    let ctx = Context::try_default().unwrap();
+    assert!(HistogramObserver::new(&ctx, 0, 0.0, 1.0).is_err());
+    assert!(HistogramObserver::new(&ctx, 8, 1.0, 1.0).is_err());
+    assert!(HistogramObserver::new(&ctx, 8, f32::NAN, 1.0).is_err());
+}
@@ -0,0 +1,4 @@
+//! Quantization integration tests.
+
+mod histogram;
+mod min_max;
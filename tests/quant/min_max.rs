@@ -0,0 +1,70 @@
+//! Tests for `MinMaxObserver`.
+
+use xnn::quant::{Granularity, MinMaxObserver};
+use xnn::{Context, Tensor};
+
+#[test]
+fn test_min_max_per_tensor() {
+    let ctx = Context::try_default().unwrap();
+    let mut observer = MinMaxObserver::new(&ctx, Granularity::PerTensor);
+    assert!(observer.min().is_none());
+
+    let a = Tensor::<f32>::from_shape_slice(&ctx, &[2, 2], &[1.0, -2.0, 3.0, 0.5]).unwrap();
+    let b = Tensor::<f32>::from_shape_slice(&ctx, &[2, 2], &[4.0, -1.0, 0.0, 2.0]).unwrap();
+    observer.observe(&a).unwrap();
+    observer.observe(&b).unwrap();
+
+    let min = observer.min().unwrap();
+    assert_eq!(min.dimensions(), &[1, 1]);
+    assert_eq!(min.to_vec().unwrap(), vec![-2.0]);
+    assert_eq!(observer.max().unwrap().to_vec().unwrap(), vec![4.0]);
+}
+
+#[test]
+fn test_min_max_per_channel() {
+    let ctx = Context::try_default().unwrap();
+    let mut observer = MinMaxObserver::new(&ctx, Granularity::PerChannel(1));
+
+    let a =
+        Tensor::<f32>::from_shape_slice(&ctx, &[2, 3], &[1.0, 5.0, -3.0, 2.0, 4.0, -1.0]).unwrap();
+    let b = Tensor::<f32>::from_shape_slice(&ctx, &[1, 3], &[0.0, 6.0, -2.0]).unwrap();
+    observer.observe(&a).unwrap();
+    observer.observe(&b).unwrap();
+
+    let min = observer.min().unwrap();
+    assert_eq!(min.dimensions(), &[1, 3]);
+    assert_eq!(min.to_vec().unwrap(), vec![0.0, 4.0, -3.0]);
+    assert_eq!(
+        observer.max().unwrap().to_vec().unwrap(),
+        vec![2.0, 6.0, -1.0]
+    );
+}
+
+#[test]
+fn test_min_max_invalid_axis() {
+    let ctx = Context::try_default().unwrap();
+    let mut observer = MinMaxObserver::new(&ctx, Granularity::PerChannel(2));
+    let t = Tensor::<f32>::from_shape_slice(&ctx, &[2, 2], &[0.0; 4]).unwrap();
+    assert!(observer.observe(&t).is_err());
+}
+
+#[test]
+fn test_calculate_qparams() {
+    let ctx = Context::try_default().unwrap();
+    let mut observer = MinMaxObserver::new(&ctx, Granularity::PerChannel(0));
+    assert!(observer.calculate_qparams().is_err());
+
+    let t = Tensor::<f32>::from_shape_slice(&ctx, &[3, 2], &[-1.0, 1.55, 2.0, 5.1, -3.0, -1.0])
+        .unwrap();
+    observer.observe(&t).unwrap();
+
+    let (scale, zero_point) = observer.calculate_qparams().unwrap();
+    assert_eq!(scale.dimensions(), &[3, 1]);
+
+    let scale = scale.to_vec().unwrap();
+    let expected = [2.55 / 255.0, 5.1 / 255.0, 3.0 / 255.0];
+    for (a, b) in scale.iter().zip(expected) {
+        approx::assert_relative_eq!(*a, b, max_relative = 1e-5);
+    }
+    assert_eq!(zero_point.to_vec().unwrap(), vec![-28.0, -128.0, 127.0]);
+}
@@ -0,0 +1,62 @@
+//! Tests for `ObservationNormalizer`.
+
+use xnn::rl::ObservationNormalizer;
+use xnn::{Context, Tensor};
+
+#[test]
+fn test_observation_normalizer() {
+    let ctx = Context::try_default().unwrap();
+    let mut normalizer = ObservationNormalizer::new(None, 0.0, 10.0).unwrap();
+
+    let first = Tensor::<f32>::from_shape_slice(&ctx, &[2, 2], &[1.0, 10.0, 3.0, 10.0]).unwrap();
+    let second = Tensor::<f32>::from_shape_slice(&ctx, &[2, 2], &[5.0, 20.0, 7.0, 20.0]).unwrap();
+    normalizer.update(&first).unwrap();
+    let y = normalizer.update_normalize(&second).unwrap();
+
+    // Feature 0 has mean 4 and variance 5, feature 1 mean 15 and variance 25.
+    let stats = normalizer.stats();
+    assert_eq!(stats.count(), 4);
+    crate::assert_close(&stats.mean().unwrap().to_vec().unwrap(), &[4.0, 15.0], 1e-4);
+    crate::assert_close(
+        &stats.variance().unwrap().to_vec().unwrap(),
+        &[5.0, 25.0],
+        1e-4,
+    );
+
+    assert_eq!(y.dimensions(), &[2, 2]);
+    let s = 5.0f32.sqrt();
+    crate::assert_close(&y.to_vec().unwrap(), &[1.0 / s, 1.0, 3.0 / s, 1.0], 1e-4);
+}
+
+#[test]
+fn test_observation_normalizer_clip() {
+    let ctx = Context::try_default().unwrap();
+    let mut normalizer = ObservationNormalizer::new(None, 1e-8, 2.0).unwrap();
+
+    let obs = Tensor::<f32>::from_shape_slice(&ctx, &[2, 1, 2], &[0.0, 1.0, 2.0, 1.0]).unwrap();
+    normalizer.update(&obs).unwrap();
+
+    let far =
+        Tensor::<f32>::from_shape_slice(&ctx, &[3, 1, 2], &[100.0, 1.0, -100.0, 1.0, 1.0, 1.0])
+            .unwrap();
+    let y = normalizer.normalize(&far).unwrap();
+    assert_eq!(y.dimensions(), &[3, 1, 2]);
+    crate::assert_close(&y.to_vec().unwrap(), &[2.0, 0.0, -2.0, 0.0, 0.0, 0.0], 1e-4);
+}
+
+#[test]
+fn test_observation_normalizer_invalid() {
+    let ctx = Context::try_default().unwrap();
+    assert!(ObservationNormalizer::new(None, -1.0, 5.0).is_err());
+    assert!(ObservationNormalizer::new(None, 1e-8, 0.0).is_err());
+    assert!(ObservationNormalizer::new(Some(0.0), 1e-8, 5.0).is_err());
+
+    let mut normalizer = ObservationNormalizer::new(Some(0.1), 1e-8, 5.0).unwrap();
+    let obs = Tensor::<f32>::from_shape_slice(&ctx, &[2, 2], &[1.0, 2.0, 3.0, 4.0]).unwrap();
+    assert!(normalizer.normalize(&obs).is_err());
+
+    normalizer.update(&obs).unwrap();
+    let other = Tensor::<f32>::from_shape_slice(&ctx, &[2, 3], &[0.0; 6]).unwrap();
+    assert!(normalizer.normalize(&other).is_err());
+    assert!(normalizer.update(&other).is_err());
+}
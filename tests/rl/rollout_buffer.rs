@@ -0,0 +1,123 @@
+//! Tests for `RolloutBuffer`.
+
+use xnn::rl::RolloutBuffer;
+use xnn::{Context, Tensor};
+
+#[test]
+fn test_rollout_buffer() {
+    let ctx = Context::try_default().unwrap();
+    let mut buffer = RolloutBuffer::<u32>::new(&ctx, 3, 2, &[2], &[]).unwrap();
+    assert!(buffer.is_empty());
+    assert_eq!(buffer.capacity(), 3);
+
+    for step in 0..3u8 {
+        let s = f32::from(step);
+        let obs = Tensor::<f32>::from_shape_slice(&ctx, &[2, 2], &[s, s, -s, -s]).unwrap();
+        let action = Tensor::<u32>::from_slice(&ctx, &[u32::from(step), 1]).unwrap();
+        let log_prob = Tensor::<f32>::from_slice(&ctx, &[-0.5, -s]).unwrap();
+        let reward = Tensor::<f32>::from_slice(&ctx, &[1.0, s]).unwrap();
+        let value = Tensor::<f32>::from_slice(&ctx, &[0.5, 0.0]).unwrap();
+        let done =
+            Tensor::<f32>::from_slice(&ctx, &[0.0, if step == 1 { 1.0 } else { 0.0 }]).unwrap();
+        buffer
+            .push(&obs, &action, &log_prob, &reward, &value, &done)
+            .unwrap();
+    }
+
+    assert!(buffer.is_full());
+    assert_eq!(buffer.len(), 3);
+    assert_eq!(buffer.observations().dimensions(), &[3, 2, 2]);
+    assert_eq!(
+        buffer.observations().to_vec().unwrap(),
+        vec![
+            0.0, 0.0, -0.0, -0.0, 1.0, 1.0, -1.0, -1.0, 2.0, 2.0, -2.0, -2.0
+        ]
+    );
+    assert_eq!(buffer.actions().to_vec().unwrap(), vec![0, 1, 1, 1, 2, 1]);
+    crate::assert_close(
+        &buffer.rewards().to_vec().unwrap(),
+        &[1.0, 0.0, 1.0, 1.0, 1.0, 2.0],
+        1e-5,
+    );
+    crate::assert_close(
+        &buffer.dones().to_vec().unwrap(),
+        &[0.0, 0.0, 0.0, 1.0, 0.0, 0.0],
+        1e-5,
+    );
+
+    let last = Tensor::<f32>::from_slice(&ctx, &[0.5, 1.0]).unwrap();
+    let (advantages, returns) = buffer.compute_gae(&last, 1.0, 1.0).unwrap();
+    assert_eq!(advantages.dimensions(), &[3, 2]);
+    // Env 0 never ends: returns are the reward sums plus the bootstrap value.
+    // Env 1 ends at step 1, so steps 0 and 1 are not bootstrapped from step 2.
+    crate::assert_close(
+        &returns.to_vec().unwrap(),
+        &[3.5, 1.0, 2.5, 1.0, 1.5, 3.0],
+        1e-5,
+    );
+    crate::assert_close(
+        &advantages.to_vec().unwrap(),
+        &[3.0, 1.0, 2.0, 1.0, 1.0, 3.0],
+        1e-5,
+    );
+
+    let extra = Tensor::<f32>::from_slice(&ctx, &[0.0, 0.0]).unwrap();
+    let obs = Tensor::<f32>::constant(&ctx, &[2, 2], &[0.0]).unwrap();
+    let action = Tensor::<u32>::from_slice(&ctx, &[0, 0]).unwrap();
+    assert!(
+        buffer
+            .push(&obs, &action, &extra, &extra, &extra, &extra)
+            .is_err()
+    );
+
+    buffer.clear();
+    assert!(buffer.is_empty());
+    buffer
+        .push(&obs, &action, &extra, &extra, &extra, &extra)
+        .unwrap();
+    assert_eq!(buffer.len(), 1);
+}
+
+#[test]
+fn test_rollout_buffer_partial_gae() {
+    let ctx = Context::try_default().unwrap();
+    let mut buffer = RolloutBuffer::<f32>::new(&ctx, 4, 1, &[1], &[1]).unwrap();
+    let x = Tensor::<f32>::from_shape_slice(&ctx, &[1, 1], &[0.0]).unwrap();
+    let one = Tensor::<f32>::from_slice(&ctx, &[1.0]).unwrap();
+    let zero = Tensor::<f32>::from_slice(&ctx, &[0.0]).unwrap();
+
+    for _ in 0..2 {
+        buffer.push(&x, &x, &zero, &one, &zero, &zero).unwrap();
+    }
+
+    let (advantages, returns) = buffer.compute_gae(&zero, 0.5, 1.0).unwrap();
+    assert_eq!(advantages.dimensions(), &[2, 1]);
+    crate::assert_close(&returns.to_vec().unwrap(), &[1.5, 1.0], 1e-5);
+}
+
+#[test]
+fn test_rollout_buffer_invalid() {
+    let ctx = Context::try_default().unwrap();
+    let mut buffer = RolloutBuffer::<f32>::new(&ctx, 2, 2, &[3], &[]).unwrap();
+    let obs = Tensor::<f32>::constant(&ctx, &[2, 3], &[0.0]).unwrap();
+    let bad_obs = Tensor::<f32>::constant(&ctx, &[2, 2], &[0.0]).unwrap();
+    let per_env = Tensor::<f32>::constant(&ctx, &[2], &[0.0]).unwrap();
+    let bad = Tensor::<f32>::constant(&ctx, &[3], &[0.0]).unwrap();
+
+    assert!(
+        buffer
+            .push(&bad_obs, &per_env, &per_env, &per_env, &per_env, &per_env)
+            .is_err()
+    );
+    assert!(
+        buffer
+            .push(&obs, &per_env, &per_env, &bad, &per_env, &per_env)
+            .is_err()
+    );
+    assert!(buffer.is_empty());
+
+    buffer
+        .push(&obs, &per_env, &per_env, &per_env, &per_env, &per_env)
+        .unwrap();
+    assert!(buffer.compute_gae(&bad, 0.9, 0.9).is_err());
+}
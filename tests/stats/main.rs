@@ -0,0 +1,12 @@
+//! Running statistics integration tests.
+
+mod running_stats;
+
+/// Asserts that `actual` matches `expected` element-wise within a relative tolerance.
+#[track_caller]
+pub(crate) fn assert_close(actual: &[f32], expected: &[f32]) {
+    assert_eq!(actual.len(), expected.len(), "length mismatch");
+    for (a, e) in actual.iter().zip(expected) {
+        approx::assert_relative_eq!(a, e, epsilon = 1e-5);
+    }
+}
@@ -0,0 +1,69 @@
+//! Tests for `RunningStats`.
+
+use xnn::stats::RunningStats;
+use xnn::{Context, Tensor};
+
+#[test]
+fn test_running_stats_cumulative() {
+    let ctx = Context::try_default().unwrap();
+    let mut stats = RunningStats::new(&[0], None).unwrap();
+    assert!(stats.mean().is_none());
+
+    let a =
+        Tensor::<f32>::from_shape_slice(&ctx, &[2, 3], &[1.0, 2.0, 3.0, 3.0, 6.0, -1.0]).unwrap();
+    let b = Tensor::<f32>::from_shape_slice(&ctx, &[1, 3], &[5.0, 1.0, 4.0]).unwrap();
+    stats.observe(&a).unwrap();
+    stats.observe(&b).unwrap();
+
+    assert_eq!(stats.count(), 3);
+    let mean = stats.mean().unwrap();
+    assert_eq!(mean.dimensions(), &[1, 3]);
+    crate::assert_close(&mean.to_vec().unwrap(), &[3.0, 3.0, 2.0]);
+
+    let expected_var = [8.0 / 3.0, 14.0 / 3.0, 14.0 / 3.0];
+    crate::assert_close(&stats.variance().unwrap().to_vec().unwrap(), &expected_var);
+}
+
+#[test]
+fn test_running_stats_momentum() {
+    let ctx = Context::try_default().unwrap();
+    let mut stats = RunningStats::new(&[0], Some(0.25)).unwrap();
+
+    let a = Tensor::<f32>::from_shape_slice(&ctx, &[2, 2], &[0.0, 2.0, 2.0, 2.0]).unwrap();
+    let b = Tensor::<f32>::from_shape_slice(&ctx, &[2, 2], &[4.0, 6.0, 8.0, 6.0]).unwrap();
+    stats.observe(&a).unwrap();
+    stats.observe(&b).unwrap();
+
+    crate::assert_close(&stats.mean().unwrap().to_vec().unwrap(), &[2.25, 3.0]);
+    crate::assert_close(&stats.variance().unwrap().to_vec().unwrap(), &[1.75, 0.0]);
+}
+
+#[test]
+fn test_running_stats_normalize() {
+    let ctx = Context::try_default().unwrap();
+    let mut stats = RunningStats::new(&[0, 1], None).unwrap();
+
+    let x = Tensor::<f32>::from_shape_slice(&ctx, &[2, 2], &[1.0, 3.0, 1.0, 3.0]).unwrap();
+    assert!(stats.normalize(&x, 0.0).is_err());
+    stats.observe(&x).unwrap();
+
+    let result = stats.normalize(&x, 0.0).unwrap();
+    crate::assert_close(&result.to_vec().unwrap(), &[-1.0, 1.0, -1.0, 1.0]);
+}
+
+#[test]
+fn test_running_stats_invalid() {
+    let ctx = Context::try_default().unwrap();
+    assert!(RunningStats::new(&[0], Some(0.0)).is_err());
+    assert!(RunningStats::new(&[0], Some(1.5)).is_err());
+
+    let mut stats = RunningStats::new(&[0], None).unwrap();
+    let a = Tensor::<f32>::from_shape_slice(&ctx, &[2, 2], &[1.0; 4]).unwrap();
+    let b = Tensor::<f32>::from_shape_slice(&ctx, &[2, 3], &[1.0; 6]).unwrap();
+    stats.observe(&a).unwrap();
+    assert!(stats.observe(&b).is_err());
+    assert_eq!(stats.count(), 2);
+
+    let mut stats = RunningStats::new(&[2], None).unwrap();
+    assert!(stats.observe(&a).is_err());
+}
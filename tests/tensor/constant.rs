@@ -100,10 +100,11 @@ fn test_constant_empty_value_error() {
 }
 
 #[test]
-fn test_constant_zero_dimension_error() {
+fn test_constant_zero_dimension() {
     let ctx = Context::try_default().unwrap();
-    let result = Tensor::<f32>::constant(&ctx, &[0], &[1.0]);
-    assert!(result.is_err());
+    let t = Tensor::<f32>::constant(&ctx, &[0], &[1.0]).unwrap();
+    assert_eq!(t.dimensions(), &[0]);
+    assert!(t.to_vec().unwrap().is_empty());
 }
 
 #[test]
@@ -0,0 +1,85 @@
+//! Tests for `Tensor::box_iou` operation.
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use xnn::{Context, Tensor};
+
+fn iou(a: &[f32], b: &[f32]) -> f32 {
+    let area = |r: &[f32]| (r[2] - r[0]).max(0.0) * (r[3] - r[1]).max(0.0);
+    let w = (a[2].min(b[2]) - a[0].max(b[0])).max(0.0);
+    let h = (a[3].min(b[3]) - a[1].max(b[1])).max(0.0);
+    let inter = w * h;
+    let union = area(a) + area(b) - inter;
+    if union > 0.0 { inter / union } else { 0.0 }
+}
+
+fn random_boxes(rng: &mut StdRng, count: usize) -> Vec<f32> {
+    (0..count)
+        .flat_map(|_| {
+            let (x, y) = (rng.random_range(0.0..50.0), rng.random_range(0.0..50.0));
+            let (w, h) = (rng.random_range(1.0..30.0), rng.random_range(1.0..30.0));
+            [x, y, x + w, y + h]
+        })
+        .collect()
+}
+
+#[test]
+fn test_box_iou_basic() {
+    let ctx = Context::try_default().unwrap();
+    let a =
+        Tensor::<f32>::from_shape_slice(&ctx, &[2, 4], &[0.0, 0.0, 2.0, 2.0, 0.0, 0.0, 1.0, 1.0])
+            .unwrap();
+    let b = Tensor::<f32>::from_shape_slice(
+        &ctx,
+        &[3, 4],
+        &[1.0, 1.0, 3.0, 3.0, 0.0, 0.0, 2.0, 2.0, 5.0, 5.0, 5.0, 5.0],
+    )
+    .unwrap();
+
+    let y = a.box_iou(&b).unwrap();
+    assert_eq!(y.dimensions(), &[2, 3]);
+    crate::assert_vec_relative_eq(
+        &y.to_vec().unwrap(),
+        &[1.0 / 7.0, 1.0, 0.0, 0.0, 0.25, 0.0],
+        1e-6,
+    );
+}
+
+#[test]
+fn test_box_iou_batched() {
+    let ctx = Context::try_default().unwrap();
+    let mut rng = StdRng::seed_from_u64(3);
+    let (batch, n, m) = (3, 17, 29);
+    let a = random_boxes(&mut rng, batch * n);
+    let b = random_boxes(&mut rng, batch * m);
+
+    let ta = Tensor::<f32>::from_shape_slice(&ctx, &[batch, n, 4], &a).unwrap();
+    let tb = Tensor::<f32>::from_shape_slice(&ctx, &[batch, m, 4], &b).unwrap();
+    let y = ta.box_iou(&tb).unwrap();
+    assert_eq!(y.dimensions(), &[batch, n, m]);
+
+    let mut expected = Vec::new();
+    for k in 0..batch {
+        for i in 0..n {
+            for j in 0..m {
+                let ia = (k * n + i) * 4;
+                let ib = (k * m + j) * 4;
+                expected.push(iou(&a[ia..ia + 4], &b[ib..ib + 4]));
+            }
+        }
+    }
+    crate::assert_vec_relative_eq(&y.to_vec().unwrap(), &expected, 1e-5);
+}
+
+#[test]
+fn test_box_iou_invalid() {
+    let ctx = Context::try_default().unwrap();
+    let a = Tensor::<f32>::constant(&ctx, &[2, 3, 4], &[0.0]).unwrap();
+    let b = Tensor::<f32>::constant(&ctx, &[4, 3, 4], &[0.0]).unwrap();
+    let c = Tensor::<f32>::constant(&ctx, &[3, 5], &[0.0]).unwrap();
+    let d = Tensor::<f32>::constant(&ctx, &[4], &[0.0]).unwrap();
+
+    assert!(a.box_iou(&b).is_err());
+    assert!(c.box_iou(&c).is_err());
+    assert!(d.box_iou(&d).is_err());
+}
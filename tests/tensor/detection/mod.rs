@@ -0,0 +1,5 @@
+//! Object detection operation tests.
+
+mod box_iou;
+mod nms;
+mod roi_align;
@@ -0,0 +1,96 @@
+//! Tests for `Tensor::nms` operation.
+
+#![allow(clippy::cast_possible_truncation, clippy::cast_precision_loss)]
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use xnn::{Context, Tensor};
+
+fn iou(a: &[f32], b: &[f32]) -> f32 {
+    let area = |r: &[f32]| (r[2] - r[0]).max(0.0) * (r[3] - r[1]).max(0.0);
+    let w = (a[2].min(b[2]) - a[0].max(b[0])).max(0.0);
+    let h = (a[3].min(b[3]) - a[1].max(b[1])).max(0.0);
+    let inter = w * h;
+    let union = area(a) + area(b) - inter;
+    if union > 0.0 { inter / union } else { 0.0 }
+}
+
+fn cpu_nms(boxes: &[f32], scores: &[f32], threshold: f32) -> Vec<u32> {
+    let mut order: Vec<usize> = (0..scores.len()).collect();
+    order.sort_by(|&a, &b| scores[b].total_cmp(&scores[a]).then(a.cmp(&b)));
+
+    let mut keep: Vec<usize> = Vec::new();
+    for &i in &order {
+        let candidate = &boxes[i * 4..i * 4 + 4];
+        if keep
+            .iter()
+            .all(|&k| iou(&boxes[k * 4..k * 4 + 4], candidate) <= threshold)
+        {
+            keep.push(i);
+        }
+    }
+    keep.into_iter().map(|i| i as u32).collect()
+}
+
+#[test]
+fn test_nms_basic() {
+    let ctx = Context::try_default().unwrap();
+    let boxes = Tensor::<f32>::from_shape_slice(
+        &ctx,
+        &[4, 4],
+        &[
+            0.0, 0.0, 10.0, 10.0, // kept
+            1.0, 1.0, 11.0, 11.0, // overlaps box 0 heavily
+            20.0, 20.0, 30.0, 30.0, // disjoint
+            0.0, 0.0, 10.0, 5.0, // IoU 0.5 with box 0
+        ],
+    )
+    .unwrap();
+    let scores = Tensor::<f32>::from_slice(&ctx, &[0.9, 0.8, 0.7, 0.95]).unwrap();
+
+    let (keep, count) = boxes.nms(&scores, 0.5).unwrap();
+    assert_eq!(count.to_vec().unwrap(), vec![3]);
+    assert_eq!(keep.to_vec().unwrap(), vec![3, 0, 2, u32::MAX]);
+
+    // Box 0 is now suppressed by box 3, which frees box 1.
+    let (keep, count) = boxes.nms(&scores, 0.4).unwrap();
+    assert_eq!(count.to_vec().unwrap(), vec![3]);
+    assert_eq!(keep.to_vec().unwrap(), vec![3, 1, 2, u32::MAX]);
+}
+
+#[test]
+fn test_nms_matches_cpu() {
+    let ctx = Context::try_default().unwrap();
+    let mut rng = StdRng::seed_from_u64(7);
+    let n = 700;
+
+    let mut boxes = Vec::with_capacity(n * 4);
+    for _ in 0..n {
+        let (x, y) = (rng.random_range(0.0..100.0), rng.random_range(0.0..100.0));
+        let (w, h) = (rng.random_range(5.0..30.0), rng.random_range(5.0..30.0));
+        boxes.extend([x, y, x + w, y + h]);
+    }
+    let scores: Vec<f32> = (0..n).map(|_| rng.random_range(0.0..1.0)).collect();
+
+    let boxes_t = Tensor::<f32>::from_shape_slice(&ctx, &[n, 4], &boxes).unwrap();
+    let scores_t = Tensor::<f32>::from_slice(&ctx, &scores).unwrap();
+    let (keep, count) = boxes_t.nms(&scores_t, 0.3).unwrap();
+
+    let expected = cpu_nms(&boxes, &scores, 0.3);
+    let count = count.to_vec().unwrap()[0] as usize;
+    let keep = keep.to_vec().unwrap();
+    assert_eq!(count, expected.len());
+    assert_eq!(&keep[..count], expected.as_slice());
+    assert!(keep[count..].iter().all(|&i| i == u32::MAX));
+}
+
+#[test]
+fn test_nms_invalid() {
+    let ctx = Context::try_default().unwrap();
+    let boxes = Tensor::<f32>::from_shape_slice(&ctx, &[2, 4], &[0.0; 8]).unwrap();
+    let scores = Tensor::<f32>::from_slice(&ctx, &[1.0, 2.0]).unwrap();
+    let wrong = Tensor::<f32>::from_slice(&ctx, &[1.0, 2.0, 3.0]).unwrap();
+    assert!(boxes.nms(&wrong, 0.5).is_err());
+    assert!(scores.nms(&scores, 0.5).is_err());
+    assert!(boxes.nms(&scores, f32::NAN).is_err());
+}
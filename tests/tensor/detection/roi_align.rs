@@ -0,0 +1,126 @@
+//! Tests for `Tensor::roi_align` operation.
+
+#![allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_precision_loss,
+    clippy::cast_sign_loss
+)]
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use xnn::{Context, Tensor};
+
+fn bilinear(plane: &[f32], h: usize, w: usize, y: f32, x: f32) -> f32 {
+    if y < -1.0 || y > h as f32 || x < -1.0 || x > w as f32 {
+        return 0.0;
+    }
+    let (mut y, mut x) = (y.max(0.0), x.max(0.0));
+    let (mut y0, mut x0) = (y as usize, x as usize);
+    let (mut y1, mut x1) = (y0 + 1, x0 + 1);
+    if y0 >= h - 1 {
+        (y0, y1, y) = (h - 1, h - 1, (h - 1) as f32);
+    }
+    if x0 >= w - 1 {
+        (x0, x1, x) = (w - 1, w - 1, (w - 1) as f32);
+    }
+    let (ly, lx) = (y - y0 as f32, x - x0 as f32);
+    let at = |r: usize, c: usize| plane[r * w + c];
+    let top = at(y0, x0) * (1.0 - lx) + at(y0, x1) * lx;
+    let bottom = at(y1, x0) * (1.0 - lx) + at(y1, x1) * lx;
+    top * (1.0 - ly) + bottom * ly
+}
+
+fn cpu_roi_align(
+    features: &[f32],
+    [n, c, h, w]: [usize; 4],
+    boxes: &[f32],
+    (oh, ow): (usize, usize),
+    scale: f32,
+    ratio: usize,
+) -> Vec<f32> {
+    let mut out = Vec::new();
+    for roi in boxes.chunks(5) {
+        let b = roi[0] as usize;
+        let (sx, sy) = (roi[1] * scale - 0.5, roi[2] * scale - 0.5);
+        let (bw, bh) = (
+            (roi[3] * scale - 0.5 - sx) / ow as f32,
+            (roi[4] * scale - 0.5 - sy) / oh as f32,
+        );
+        let (gw, gh) = if ratio > 0 {
+            (ratio, ratio)
+        } else {
+            ((bw.ceil() as usize).max(1), (bh.ceil() as usize).max(1))
+        };
+        for ch in 0..c {
+            for ph in 0..oh {
+                for pw in 0..ow {
+                    if b >= n {
+                        out.push(0.0);
+                        continue;
+                    }
+                    let plane = &features[(b * c + ch) * h * w..(b * c + ch + 1) * h * w];
+                    let mut sum = 0.0;
+                    for iy in 0..gh {
+                        let y = sy + ph as f32 * bh + (iy as f32 + 0.5) * bh / gh as f32;
+                        for ix in 0..gw {
+                            let x = sx + pw as f32 * bw + (ix as f32 + 0.5) * bw / gw as f32;
+                            sum += bilinear(plane, h, w, y, x);
+                        }
+                    }
+                    out.push(sum / (gh * gw) as f32);
+                }
+            }
+        }
+    }
+    out
+}
+
+#[test]
+fn test_roi_align_constant_feature() {
+    let ctx = Context::try_default().unwrap();
+    let features = Tensor::<f32>::constant(&ctx, &[1, 2, 8, 8], &[3.0]).unwrap();
+    let boxes = Tensor::<f32>::from_shape_slice(&ctx, &[1, 5], &[0.0, 1.0, 1.0, 5.0, 6.0]).unwrap();
+    let result = features.roi_align(&boxes, (2, 3), 1.0, 2).unwrap();
+    assert_eq!(result.dimensions(), &[1, 2, 2, 3]);
+    crate::assert_vec_relative_eq(&result.to_vec().unwrap(), &[3.0; 12], 1e-6);
+}
+
+#[test]
+fn test_roi_align_matches_cpu() {
+    let ctx = Context::try_default().unwrap();
+    let mut rng = StdRng::seed_from_u64(11);
+    let dims = [2, 3, 10, 12];
+    let features: Vec<f32> = (0..dims.iter().product::<usize>())
+        .map(|_| rng.random_range(-1.0..1.0))
+        .collect();
+    let boxes = [
+        0.0, 2.0, 3.0, 15.0, 11.0, //
+        1.0, -4.0, -2.0, 9.0, 25.0, //
+        1.0, 10.0, 10.0, 10.5, 11.0, //
+        5.0, 0.0, 0.0, 4.0, 4.0, // out-of-range batch
+    ];
+
+    let features_t = Tensor::<f32>::from_shape_slice(&ctx, &dims, &features).unwrap();
+    let boxes_t = Tensor::<f32>::from_shape_slice(&ctx, &[4, 5], &boxes).unwrap();
+
+    for ratio in [0, 2] {
+        let result = features_t.roi_align(&boxes_t, (3, 4), 0.5, ratio).unwrap();
+        let expected = cpu_roi_align(&features, dims, &boxes, (3, 4), 0.5, ratio);
+        assert_eq!(result.dimensions(), &[4, 3, 3, 4]);
+        crate::assert_vec_relative_eq(&result.to_vec().unwrap(), &expected, 1e-5);
+    }
+}
+
+#[test]
+fn test_roi_align_invalid() {
+    let ctx = Context::try_default().unwrap();
+    let features = Tensor::<f32>::constant(&ctx, &[1, 1, 4, 4], &[0.0]).unwrap();
+    let boxes = Tensor::<f32>::constant(&ctx, &[2, 5], &[0.0]).unwrap();
+    let wrong = Tensor::<f32>::constant(&ctx, &[2, 4], &[0.0]).unwrap();
+    let flat = Tensor::<f32>::constant(&ctx, &[4, 4], &[0.0]).unwrap();
+
+    assert!(features.roi_align(&wrong, (2, 2), 1.0, 0).is_err());
+    assert!(flat.roi_align(&boxes, (2, 2), 1.0, 0).is_err());
+    assert!(features.roi_align(&boxes, (0, 2), 1.0, 0).is_err());
+    assert!(features.roi_align(&boxes, (2, 2), 0.0, 0).is_err());
+}
@@ -0,0 +1,52 @@
+//! Tests for `Tensor<Df64>::add` operation.
+
+use xnn::Context;
+
+use super::{assert_df64_relative_eq, df64_tensor};
+
+#[test]
+fn test_add_df64_vector() {
+    let ctx = Context::try_default().unwrap();
+    let a = df64_tensor(&ctx, &[4], &[1.0, 2.0, 3.0, 4.0]);
+    let b = df64_tensor(&ctx, &[4], &[5.0, 6.0, 7.0, 8.0]);
+    let result = a.add(&b).unwrap();
+    assert_df64_relative_eq(&result, &[4], &[6.0, 8.0, 10.0, 12.0], 1e-12);
+}
+
+#[test]
+fn test_add_df64_beyond_f32_precision() {
+    let ctx = Context::try_default().unwrap();
+    let a = df64_tensor(&ctx, &[3], &[1.0, 1.0, 1e8]);
+    let b = df64_tensor(&ctx, &[3], &[1e-10, -1e-12, 1.5]);
+    let result = a.add(&b).unwrap();
+    assert_df64_relative_eq(&result, &[3], &[1.0 + 1e-10, 1.0 - 1e-12, 1e8 + 1.5], 1e-13);
+}
+
+#[test]
+fn test_add_df64_broadcast() {
+    let ctx = Context::try_default().unwrap();
+    let a = df64_tensor(&ctx, &[2, 3], &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+    let b = df64_tensor(&ctx, &[3], &[1e-9, 2e-9, 3e-9]);
+    let result = a.add(&b).unwrap();
+    assert_df64_relative_eq(
+        &result,
+        &[2, 3],
+        &[
+            1.0 + 1e-9,
+            2.0 + 2e-9,
+            3.0 + 3e-9,
+            4.0 + 1e-9,
+            5.0 + 2e-9,
+            6.0 + 3e-9,
+        ],
+        1e-13,
+    );
+}
+
+#[test]
+fn test_add_df64_incompatible_shapes() {
+    let ctx = Context::try_default().unwrap();
+    let a = df64_tensor(&ctx, &[3], &[1.0, 2.0, 3.0]);
+    let b = df64_tensor(&ctx, &[2], &[1.0, 2.0]);
+    assert!(a.add(&b).is_err());
+}
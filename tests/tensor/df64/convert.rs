@@ -0,0 +1,33 @@
+//! Tests for `Tensor::to_df64` and `Tensor<Df64>::to_f32` conversions.
+
+use xnn::{Context, Df64, Tensor};
+
+use super::{assert_df64_relative_eq, df64_tensor};
+
+#[test]
+fn test_to_df64_round_trip() {
+    let ctx = Context::try_default().unwrap();
+    let data = [1.5f32, -2.25, 3.0, 1e-3, 7.0];
+    let a = Tensor::<f32>::from_shape_slice(&ctx, &[5], &data).unwrap();
+    let wide = a.to_df64().unwrap();
+    let expected: Vec<f64> = data.iter().copied().map(f64::from).collect();
+    assert_df64_relative_eq(&wide, &[5], &expected, 0.0);
+    assert_eq!(wide.to_f32().unwrap().to_vec().unwrap(), data);
+}
+
+#[test]
+fn test_to_f32_rounds() {
+    let ctx = Context::try_default().unwrap();
+    let a = df64_tensor(&ctx, &[2], &[1.0 + 1e-10, 0.1]);
+    #[allow(clippy::cast_possible_truncation)]
+    let expected = [(1.0f64 + 1e-10) as f32, 0.1f64 as f32];
+    assert_eq!(a.to_f32().unwrap().to_vec().unwrap(), expected);
+}
+
+#[test]
+fn test_df64_split_from_f64() {
+    let x = core::f64::consts::PI;
+    let d = Df64::from(x);
+    assert_eq!(d.hi().to_bits(), core::f32::consts::PI.to_bits());
+    approx::assert_relative_eq!(f64::from(d), x, max_relative = 1e-14);
+}
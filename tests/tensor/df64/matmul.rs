@@ -0,0 +1,95 @@
+//! Tests for `Tensor<Df64>::matmul` operation.
+
+#![allow(clippy::cast_precision_loss)]
+
+use xnn::Context;
+
+use super::{assert_df64_relative_eq, df64_tensor};
+
+fn cpu_matmul(a: &[f64], b: &[f64], m: usize, k: usize, n: usize) -> Vec<f64> {
+    let mut c = vec![0.0; m * n];
+    for i in 0..m {
+        for j in 0..n {
+            let mut sum = 0.0;
+            for l in 0..k {
+                sum += a[i * k + l] * b[l * n + j];
+            }
+            c[i * n + j] = sum;
+        }
+    }
+    c
+}
+
+fn transpose(x: &[f64], rows: usize, cols: usize) -> Vec<f64> {
+    let mut y = vec![0.0; rows * cols];
+    for i in 0..rows {
+        for j in 0..cols {
+            y[j * rows + i] = x[i * cols + j];
+        }
+    }
+    y
+}
+
+#[test]
+fn test_matmul_df64_2x2() {
+    let ctx = Context::try_default().unwrap();
+    let a = df64_tensor(&ctx, &[2, 2], &[1.0, 2.0, 3.0, 4.0]);
+    let b = df64_tensor(&ctx, &[2, 2], &[5.0, 6.0, 7.0, 8.0]);
+    let result = a.matmul(&b, false, false).unwrap();
+    assert_df64_relative_eq(&result, &[2, 2], &[19.0, 22.0, 43.0, 50.0], 1e-12);
+}
+
+#[test]
+fn test_matmul_df64_long_accumulation() {
+    let ctx = Context::try_default().unwrap();
+    let (m, k, n) = (3, 1000, 2);
+    let a: Vec<f64> = (0..m * k).map(|i| 1.0 + (i as f64) * 1e-7).collect();
+    let b: Vec<f64> = (0..k * n).map(|i| 1.0 / (1.0 + i as f64)).collect();
+    let ta = df64_tensor(&ctx, &[m, k], &a);
+    let tb = df64_tensor(&ctx, &[k, n], &b);
+    let result = ta.matmul(&tb, false, false).unwrap();
+    assert_df64_relative_eq(&result, &[m, n], &cpu_matmul(&a, &b, m, k, n), 1e-12);
+}
+
+#[test]
+fn test_matmul_df64_transpose() {
+    let ctx = Context::try_default().unwrap();
+    let (m, k, n) = (2, 3, 4);
+    let a: Vec<f64> = (0..m * k).map(|i| (i as f64) * 0.1).collect();
+    let b: Vec<f64> = (0..k * n).map(|i| (i as f64) * 0.01).collect();
+    let expected = cpu_matmul(&a, &b, m, k, n);
+
+    let at = df64_tensor(&ctx, &[k, m], &transpose(&a, m, k));
+    let bt = df64_tensor(&ctx, &[n, k], &transpose(&b, k, n));
+    let ta = df64_tensor(&ctx, &[m, k], &a);
+    let tb = df64_tensor(&ctx, &[k, n], &b);
+
+    let result = at.matmul(&tb, true, false).unwrap();
+    assert_df64_relative_eq(&result, &[m, n], &expected, 1e-12);
+    let result = ta.matmul(&bt, false, true).unwrap();
+    assert_df64_relative_eq(&result, &[m, n], &expected, 1e-12);
+    let result = at.matmul(&bt, true, true).unwrap();
+    assert_df64_relative_eq(&result, &[m, n], &expected, 1e-12);
+}
+
+#[test]
+fn test_matmul_df64_batched_broadcast() {
+    let ctx = Context::try_default().unwrap();
+    let (m, k, n) = (2, 3, 2);
+    let a: Vec<f64> = (0..2 * m * k).map(|i| (i as f64) * 0.5).collect();
+    let b: Vec<f64> = (0..k * n).map(|i| (i as f64) + 0.25).collect();
+    let ta = df64_tensor(&ctx, &[2, m, k], &a);
+    let tb = df64_tensor(&ctx, &[1, k, n], &b);
+    let result = ta.matmul(&tb, false, false).unwrap();
+    let mut expected = cpu_matmul(&a[..m * k], &b, m, k, n);
+    expected.extend(cpu_matmul(&a[m * k..], &b, m, k, n));
+    assert_df64_relative_eq(&result, &[2, m, n], &expected, 1e-12);
+}
+
+#[test]
+fn test_matmul_df64_dimension_mismatch() {
+    let ctx = Context::try_default().unwrap();
+    let a = df64_tensor(&ctx, &[2, 3], &[0.0; 6]);
+    let b = df64_tensor(&ctx, &[2, 2], &[0.0; 4]);
+    assert!(a.matmul(&b, false, false).is_err());
+}
@@ -0,0 +1,37 @@
+//! Emulated double-precision tests.
+
+mod add;
+mod convert;
+mod matmul;
+mod mul;
+mod sub;
+mod sum;
+
+use xnn::{Context, Df64, Tensor};
+
+/// Uploads `f64` values as a df64 tensor.
+pub(crate) fn df64_tensor(ctx: &Context, dimensions: &[usize], values: &[f64]) -> Tensor<Df64> {
+    let data: Vec<Df64> = values.iter().copied().map(Df64::from).collect();
+    Tensor::from_shape_slice(ctx, dimensions, &data).unwrap()
+}
+
+/// Asserts a df64 tensor matches `expected` within `epsilon` relative error.
+#[track_caller]
+pub(crate) fn assert_df64_relative_eq(
+    result: &Tensor<Df64>,
+    dimensions: &[usize],
+    expected: &[f64],
+    epsilon: f64,
+) {
+    assert_eq!(result.dimensions(), dimensions);
+    let actual: Vec<f64> = result
+        .to_vec()
+        .unwrap()
+        .into_iter()
+        .map(f64::from)
+        .collect();
+    assert_eq!(actual.len(), expected.len(), "length mismatch");
+    for (a, e) in actual.iter().zip(expected.iter()) {
+        approx::assert_relative_eq!(a, e, epsilon = epsilon, max_relative = epsilon);
+    }
+}
@@ -0,0 +1,34 @@
+//! Tests for `Tensor<Df64>::mul` operation.
+
+use xnn::Context;
+
+use super::{assert_df64_relative_eq, df64_tensor};
+
+#[test]
+fn test_mul_df64_vector() {
+    let ctx = Context::try_default().unwrap();
+    let a = df64_tensor(&ctx, &[4], &[1.0, 2.0, 3.0, 4.0]);
+    let b = df64_tensor(&ctx, &[4], &[5.0, 6.0, 7.0, 8.0]);
+    let result = a.mul(&b).unwrap();
+    assert_df64_relative_eq(&result, &[4], &[5.0, 12.0, 21.0, 32.0], 1e-12);
+}
+
+#[test]
+fn test_mul_df64_beyond_f32_precision() {
+    let ctx = Context::try_default().unwrap();
+    let values = [1.0 / 3.0, core::f64::consts::PI, 1.000_000_1];
+    let a = df64_tensor(&ctx, &[3], &values);
+    let b = df64_tensor(&ctx, &[3], &values);
+    let result = a.mul(&b).unwrap();
+    let expected: Vec<f64> = values.iter().map(|x| x * x).collect();
+    assert_df64_relative_eq(&result, &[3], &expected, 1e-13);
+}
+
+#[test]
+fn test_mul_df64_broadcast_scalar() {
+    let ctx = Context::try_default().unwrap();
+    let a = df64_tensor(&ctx, &[2, 2], &[1.0, 2.0, 3.0, 4.0]);
+    let b = df64_tensor(&ctx, &[1], &[0.1]);
+    let result = a.mul(&b).unwrap();
+    assert_df64_relative_eq(&result, &[2, 2], &[0.1, 0.2, 0.3, 0.4], 1e-13);
+}
@@ -0,0 +1,24 @@
+//! Tests for `Tensor<Df64>::sub` operation.
+
+use xnn::Context;
+
+use super::{assert_df64_relative_eq, df64_tensor};
+
+#[test]
+fn test_sub_df64_vector() {
+    let ctx = Context::try_default().unwrap();
+    let a = df64_tensor(&ctx, &[4], &[5.0, 6.0, 7.0, 8.0]);
+    let b = df64_tensor(&ctx, &[4], &[1.0, 2.0, 3.0, 4.0]);
+    let result = a.sub(&b).unwrap();
+    assert_df64_relative_eq(&result, &[4], &[4.0, 4.0, 4.0, 4.0], 1e-12);
+}
+
+#[test]
+fn test_sub_df64_catastrophic_cancellation() {
+    let ctx = Context::try_default().unwrap();
+    let a = df64_tensor(&ctx, &[2], &[1.0 + 1e-10, 12_345_678.9]);
+    let b = df64_tensor(&ctx, &[2], &[1.0, 12_345_678.0]);
+    let result = a.sub(&b).unwrap();
+    let expected = [(1.0 + 1e-10) - 1.0, 12_345_678.9 - 12_345_678.0];
+    assert_df64_relative_eq(&result, &[2], &expected, 1e-6);
+}
@@ -0,0 +1,63 @@
+//! Tests for `Tensor<Df64>::sum_reduce` operation.
+
+use xnn::Context;
+
+use super::{assert_df64_relative_eq, df64_tensor};
+
+#[test]
+fn test_sum_df64_axes() {
+    let ctx = Context::try_default().unwrap();
+    let t = df64_tensor(&ctx, &[2, 3], &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+
+    let rows = t.sum_reduce(&[1], false).unwrap();
+    assert_df64_relative_eq(&rows, &[2, 1], &[6.0, 15.0], 1e-12);
+
+    let cols = t.sum_reduce(&[0], false).unwrap();
+    assert_df64_relative_eq(&cols, &[1, 3], &[5.0, 7.0, 9.0], 1e-12);
+
+    let all = t.sum_reduce(&[0, 1], false).unwrap();
+    assert_df64_relative_eq(&all, &[1, 1], &[21.0], 1e-12);
+}
+
+#[test]
+fn test_sum_df64_beyond_f32_precision() {
+    let ctx = Context::try_default().unwrap();
+    let mut values = vec![1e8];
+    values.extend(std::iter::repeat_n(0.1, 1000));
+    let t = df64_tensor(&ctx, &[values.len()], &values);
+
+    let sum = t.sum_reduce(&[0], false).unwrap();
+    assert_df64_relative_eq(&sum, &[1], &[1e8 + 100.0], 1e-12);
+}
+
+#[test]
+fn test_sum_df64_normalize() {
+    let ctx = Context::try_default().unwrap();
+    let values: Vec<f64> = (0..300).map(|i| 1.0 + f64::from(i) * 1e-9).collect();
+    let t = df64_tensor(&ctx, &[3, 100], &values);
+
+    let mean = t.sum_reduce(&[1], true).unwrap();
+    let expected: Vec<f64> = values
+        .chunks(100)
+        .map(|row| row.iter().sum::<f64>() / 100.0)
+        .collect();
+    assert_df64_relative_eq(&mean, &[3, 1], &expected, 1e-13);
+}
+
+#[test]
+fn test_sum_df64_strided() {
+    let ctx = Context::try_default().unwrap();
+    let t = df64_tensor(&ctx, &[2, 3], &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+    let transposed = t.transpose(0, 1).unwrap();
+
+    let sum = transposed.sum_reduce(&[1], false).unwrap();
+    assert_df64_relative_eq(&sum, &[3, 1], &[5.0, 7.0, 9.0], 1e-12);
+}
+
+#[test]
+fn test_sum_df64_invalid_axes() {
+    let ctx = Context::try_default().unwrap();
+    let t = df64_tensor(&ctx, &[2, 3], &[0.0; 6]);
+    assert!(t.sum_reduce(&[2], false).is_err());
+    assert!(t.sum_reduce(&[0, 0], false).is_err());
+}
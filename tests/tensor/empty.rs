@@ -0,0 +1,184 @@
+//! Tests for tensors with zero-sized dimensions.
+
+use xnn::{Context, PadMode, Tensor};
+
+#[test]
+fn test_empty_constant() {
+    let ctx = Context::try_default().unwrap();
+
+    let t = Tensor::<f32>::constant(&ctx, &[0, 3], &[1.0]).unwrap();
+    assert_eq!(t.dimensions(), &[0, 3]);
+    assert!(t.to_vec().unwrap().is_empty());
+
+    let t = Tensor::<u32>::from_slice(&ctx, &[]).unwrap();
+    assert_eq!(t.dimensions(), &[0]);
+    assert!(t.to_vec().unwrap().is_empty());
+
+    let t = Tensor::<f32>::from_shape_slice(&ctx, &[2, 0, 4], &[]).unwrap();
+    assert_eq!(t.dimensions(), &[2, 0, 4]);
+    assert!(t.to_vec().unwrap().is_empty());
+
+    assert!(Tensor::<f32>::from_shape_slice(&ctx, &[2, 1], &[]).is_err());
+}
+
+#[test]
+fn test_empty_elementwise() {
+    let ctx = Context::try_default().unwrap();
+    let a = Tensor::<f32>::constant(&ctx, &[0, 3], &[1.0]).unwrap();
+    let b = Tensor::<f32>::constant(&ctx, &[1, 3], &[2.0]).unwrap();
+
+    let c = a.add(&b).unwrap();
+    assert_eq!(c.dimensions(), &[0, 3]);
+    assert!(c.to_vec().unwrap().is_empty());
+
+    let e = a.exp().unwrap().relu().unwrap();
+    assert_eq!(e.dimensions(), &[0, 3]);
+    assert!(e.to_vec().unwrap().is_empty());
+
+    let m = a.lt(&b).unwrap();
+    assert_eq!(m.dimensions(), &[0, 3]);
+    assert!(m.to_vec().unwrap().is_empty());
+}
+
+#[test]
+fn test_empty_shape() {
+    let ctx = Context::try_default().unwrap();
+    let t = Tensor::<f32>::constant(&ctx, &[2, 0, 3], &[1.0]).unwrap();
+
+    assert_eq!(t.reshape(&[0, 6]).unwrap().dimensions(), &[0, 6]);
+    assert!(t.reshape(&[1]).is_err());
+
+    let p = t.permute(&[2, 0, 1]).unwrap();
+    assert_eq!(p.dimensions(), &[3, 2, 0]);
+    assert!(p.copy().unwrap().to_vec().unwrap().is_empty());
+    assert!(p.contiguous().unwrap().to_vec().unwrap().is_empty());
+
+    let s = Tensor::stack(&[&t, &t], 1).unwrap();
+    assert_eq!(s.dimensions(), &[2, 2, 0, 3]);
+    assert!(s.to_vec().unwrap().is_empty());
+
+    let r = t.repeat(&[2, 2, 2]).unwrap();
+    assert_eq!(r.dimensions(), &[4, 0, 6]);
+
+    let b = t.broadcast_to(&[4, 2, 0, 3]).unwrap();
+    assert!(b.to_vec().unwrap().is_empty());
+}
+
+#[test]
+fn test_empty_narrow() {
+    let ctx = Context::try_default().unwrap();
+    let t = Tensor::<u32>::from_shape_slice(&ctx, &[2, 3], &[1, 2, 3, 4, 5, 6]).unwrap();
+
+    let n = t.narrow(1, 3, 0).unwrap();
+    assert_eq!(n.dimensions(), &[2, 0]);
+    assert!(n.to_vec().unwrap().is_empty());
+    assert!(t.narrow(1, 4, 0).is_err());
+
+    let parts = t.split(&[0, 3], 1).unwrap();
+    assert_eq!(parts[0].dimensions(), &[2, 0]);
+    assert_eq!(parts[1].to_vec().unwrap(), vec![1, 2, 3, 4, 5, 6]);
+
+    let parts = n.chunk(2, 1).unwrap();
+    assert_eq!(parts.len(), 2);
+    assert_eq!(parts[1].dimensions(), &[2, 0]);
+    assert!(parts[1].to_vec().unwrap().is_empty());
+}
+
+#[test]
+fn test_empty_pad() {
+    let ctx = Context::try_default().unwrap();
+    let t = Tensor::<f32>::constant(&ctx, &[0, 2], &[1.0]).unwrap();
+
+    let p = t.pad(&[(1, 1), (0, 0)], PadMode::Constant(7.0)).unwrap();
+    assert_eq!(p.dimensions(), &[2, 2]);
+    assert_eq!(p.to_vec().unwrap(), vec![7.0; 4]);
+}
+
+#[test]
+fn test_empty_reduction() {
+    let ctx = Context::try_default().unwrap();
+    let t = Tensor::<f32>::constant(&ctx, &[0, 3], &[1.0]).unwrap();
+
+    let s = t.sum_reduce(&[0], false).unwrap();
+    assert_eq!(s.dimensions(), &[1, 3]);
+    assert_eq!(s.to_vec().unwrap(), vec![0.0; 3]);
+
+    let s = t.sum_reduce(&[1], false).unwrap();
+    assert_eq!(s.dimensions(), &[0, 1]);
+    assert!(s.to_vec().unwrap().is_empty());
+}
+
+#[test]
+fn test_empty_reduction_undefined() {
+    let ctx = Context::try_default().unwrap();
+    let t = Tensor::<f32>::constant(&ctx, &[0, 3], &[1.0]).unwrap();
+
+    assert!(t.max_reduce(&[0]).is_err());
+    assert!(t.min_reduce(&[0]).is_err());
+    assert!(t.mean_reduce(&[0]).is_err());
+    assert!(t.sum_reduce(&[0], true).is_err());
+    assert!(t.mean_reduce(&[0, 1]).is_err());
+
+    let p = t.prod_reduce(&[0]).unwrap();
+    assert_eq!(p.dimensions(), &[1, 3]);
+    assert_eq!(p.to_vec().unwrap(), vec![1.0; 3]);
+
+    let m = t.max_reduce(&[1]).unwrap();
+    assert_eq!(m.dimensions(), &[0, 1]);
+    assert!(m.to_vec().unwrap().is_empty());
+}
+
+#[test]
+fn test_empty_matmul() {
+    let ctx = Context::try_default().unwrap();
+
+    let a = Tensor::<f32>::constant(&ctx, &[0, 4], &[1.0]).unwrap();
+    let b = Tensor::<f32>::constant(&ctx, &[4, 3], &[1.0]).unwrap();
+    let c = a.matmul(&b, false, false).unwrap();
+    assert_eq!(c.dimensions(), &[0, 3]);
+    assert!(c.to_vec().unwrap().is_empty());
+
+    let a = Tensor::<f32>::constant(&ctx, &[2, 0], &[1.0]).unwrap();
+    let b = Tensor::<f32>::constant(&ctx, &[0, 3], &[1.0]).unwrap();
+    let c = a.matmul(&b, false, false).unwrap();
+    assert_eq!(c.dimensions(), &[2, 3]);
+    assert_eq!(c.to_vec().unwrap(), vec![0.0; 6]);
+
+    let a = Tensor::<f32>::constant(&ctx, &[16, 0], &[1.0]).unwrap();
+    let b = Tensor::<f32>::constant(&ctx, &[0, 16], &[1.0]).unwrap();
+    let c = a.matmul(&b, false, false).unwrap();
+    assert_eq!(c.to_vec().unwrap(), vec![0.0; 256]);
+}
+
+#[test]
+fn test_empty_rows() {
+    let ctx = Context::try_default().unwrap();
+    let t = Tensor::<f32>::constant(&ctx, &[0, 4], &[1.0]).unwrap();
+
+    let s = t.softmax().unwrap();
+    assert_eq!(s.dimensions(), &[0, 4]);
+    assert!(s.to_vec().unwrap().is_empty());
+
+    let t = Tensor::<f32>::constant(&ctx, &[0, 3], &[1.0]).unwrap();
+    let s = t.softmax().unwrap();
+    assert_eq!(s.dimensions(), &[0, 3]);
+    assert!(s.to_vec().unwrap().is_empty());
+
+    let w = Tensor::<f32>::constant(&ctx, &[2, 0], &[1.0]).unwrap();
+    let s = w.softmax().unwrap();
+    assert_eq!(s.dimensions(), &[2, 0]);
+    assert!(s.to_vec().unwrap().is_empty());
+
+    let mask = Tensor::<bool>::constant(&ctx, &[1, 0], &[true]).unwrap();
+    let s = w.masked_softmax(&mask).unwrap();
+    assert_eq!(s.dimensions(), &[2, 0]);
+    assert!(s.to_vec().unwrap().is_empty());
+
+    let t = Tensor::<f32>::constant(&ctx, &[0, 4], &[1.0]).unwrap();
+    let (values, indices) = t.sort(false, true).unwrap();
+    assert_eq!(values.dimensions(), &[0, 4]);
+    assert!(indices.to_vec().unwrap().is_empty());
+
+    let (values, _) = t.topk(2, true).unwrap();
+    assert_eq!(values.dimensions(), &[0, 2]);
+}
@@ -0,0 +1,261 @@
+//! Tests for `Tensor::from_texture` operation.
+
+use xnn::{Context, Normalization, Tensor};
+
+/// Creates a context together with its wgpu device and queue.
+fn device() -> (Context, wgpu::Device, wgpu::Queue) {
+    let instance = wgpu::Instance::default();
+    let adapter =
+        pollster::block_on(instance.request_adapter(&wgpu::RequestAdapterOptions::default()))
+            .unwrap();
+    let (device, queue) =
+        pollster::block_on(adapter.request_device(&wgpu::DeviceDescriptor::default())).unwrap();
+    let ctx = Context::from_device_queue(&device, &queue);
+    (ctx, device, queue)
+}
+
+/// Creates a `width × height` texture of `format` filled with `texels`.
+fn texture(
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    format: wgpu::TextureFormat,
+    usage: wgpu::TextureUsages,
+    (width, height): (u32, u32),
+    texels: &[u8],
+) -> wgpu::Texture {
+    let size = wgpu::Extent3d {
+        width,
+        height,
+        depth_or_array_layers: 1,
+    };
+    let texture = device.create_texture(&wgpu::TextureDescriptor {
+        label: None,
+        size,
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format,
+        usage: usage | wgpu::TextureUsages::COPY_DST,
+        view_formats: &[],
+    });
+    queue.write_texture(
+        texture.as_image_copy(),
+        texels,
+        wgpu::TexelCopyBufferLayout {
+            offset: 0,
+            bytes_per_row: Some(4 * width),
+            rows_per_image: Some(height),
+        },
+        size,
+    );
+    texture
+}
+
+#[test]
+fn test_from_texture_rgba8() {
+    let (ctx, device, queue) = device();
+    let texels = [
+        255, 0, 0, 255, 0, 255, 0, 255, 0, 0, 255, 255, //
+        51, 102, 153, 0, 0, 0, 0, 0, 255, 255, 255, 255,
+    ];
+    let texture = texture(
+        &device,
+        &queue,
+        wgpu::TextureFormat::Rgba8Unorm,
+        wgpu::TextureUsages::TEXTURE_BINDING,
+        (3, 2),
+        &texels,
+    );
+
+    let result = Tensor::from_texture(&ctx, &texture, Normalization::IDENTITY).unwrap();
+    assert_eq!(result.dimensions(), &[1, 3, 2, 3]);
+    crate::assert_vec_relative_eq(
+        &result.to_vec().unwrap(),
+        &[
+            1.0, 0.0, 0.0, 0.2, 0.0, 1.0, // red
+            0.0, 1.0, 0.0, 0.4, 0.0, 1.0, // green
+            0.0, 0.0, 1.0, 0.6, 0.0, 1.0, // blue
+        ],
+        1e-6,
+    );
+}
+
+#[test]
+fn test_from_texture_bgra8_normalized() {
+    let (ctx, device, queue) = device();
+    let texels = [0, 0, 255, 255, 255, 255, 255, 255];
+    let texture = texture(
+        &device,
+        &queue,
+        wgpu::TextureFormat::Bgra8Unorm,
+        wgpu::TextureUsages::TEXTURE_BINDING,
+        (2, 1),
+        &texels,
+    );
+
+    let normalization = Normalization {
+        mean: [0.5; 3],
+        std: [0.5; 3],
+    };
+    let result = Tensor::from_texture(&ctx, &texture, normalization).unwrap();
+    crate::assert_vec_relative_eq(
+        &result.to_vec().unwrap(),
+        &[1.0, 1.0, -1.0, 1.0, -1.0, 1.0],
+        1e-6,
+    );
+}
+
+#[test]
+fn test_from_texture_invalid() {
+    let (ctx, device, queue) = device();
+    let unbound = texture(
+        &device,
+        &queue,
+        wgpu::TextureFormat::Rgba8Unorm,
+        wgpu::TextureUsages::COPY_SRC,
+        (1, 1),
+        &[0; 4],
+    );
+    assert!(Tensor::from_texture(&ctx, &unbound, Normalization::IDENTITY).is_err());
+
+    let integer = texture(
+        &device,
+        &queue,
+        wgpu::TextureFormat::Rgba8Uint,
+        wgpu::TextureUsages::TEXTURE_BINDING,
+        (1, 1),
+        &[0; 4],
+    );
+    assert!(Tensor::from_texture(&ctx, &integer, Normalization::IDENTITY).is_err());
+
+    let valid = texture(
+        &device,
+        &queue,
+        wgpu::TextureFormat::Rgba8Unorm,
+        wgpu::TextureUsages::TEXTURE_BINDING,
+        (1, 1),
+        &[0; 4],
+    );
+    let normalization = Normalization {
+        mean: [0.0; 3],
+        std: [1.0, 0.0, 1.0],
+    };
+    assert!(Tensor::from_texture(&ctx, &valid, normalization).is_err());
+}
+
+#[test]
+fn test_from_texture_multisampled() {
+    let (ctx, device, _) = device();
+    let multisampled = device.create_texture(&wgpu::TextureDescriptor {
+        label: None,
+        size: wgpu::Extent3d {
+            width: 2,
+            height: 2,
+            depth_or_array_layers: 1,
+        },
+        mip_level_count: 1,
+        sample_count: 4,
+        dimension: wgpu::TextureDimension::D2,
+        format: wgpu::TextureFormat::Rgba8Unorm,
+        usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::RENDER_ATTACHMENT,
+        view_formats: &[],
+    });
+
+    let identity = Normalization::IDENTITY;
+    assert!(Tensor::from_texture(&ctx, &multisampled, identity).is_err());
+    assert!(
+        Tensor::from_textures_letterboxed(&ctx, &[&multisampled], 4, 4, 0.0, identity).is_err()
+    );
+}
+
+#[test]
+fn test_from_textures_letterboxed_pads() {
+    let (ctx, device, queue) = device();
+    let texels: Vec<u8> = (0..8u8).flat_map(|i| [i * 30, 0, 255, 255]).collect();
+    let wide = texture(
+        &device,
+        &queue,
+        wgpu::TextureFormat::Rgba8Unorm,
+        wgpu::TextureUsages::TEXTURE_BINDING,
+        (4, 2),
+        &texels,
+    );
+
+    let (result, placements) =
+        Tensor::from_textures_letterboxed(&ctx, &[&wide], 4, 4, 0.5, Normalization::IDENTITY)
+            .unwrap();
+    assert_eq!(result.dimensions(), &[1, 3, 4, 4]);
+    assert_eq!(placements[0].left, 0);
+    assert_eq!(placements[0].top, 1);
+    approx::assert_relative_eq!(placements[0].scale, 1.0);
+    assert_eq!(placements[0].to_source(2.0, 3.0), (2.0, 2.0));
+
+    let values = result.to_vec().unwrap();
+    let red: Vec<f32> = (0..8u8).map(|i| f32::from(i * 30) / 255.0).collect();
+    crate::assert_vec_relative_eq(&values[..4], &[0.5; 4], 1e-6);
+    crate::assert_vec_relative_eq(&values[4..12], &red, 1e-6);
+    crate::assert_vec_relative_eq(&values[12..16], &[0.5; 4], 1e-6);
+    crate::assert_vec_relative_eq(&values[36..40], &[1.0; 4], 1e-6);
+}
+
+#[test]
+fn test_from_textures_letterboxed_batch_resizes() {
+    let (ctx, device, queue) = device();
+    let small = texture(
+        &device,
+        &queue,
+        wgpu::TextureFormat::Rgba8Unorm,
+        wgpu::TextureUsages::TEXTURE_BINDING,
+        (2, 2),
+        &[0, 0, 0, 255, 255, 0, 0, 255, 0, 0, 0, 255, 255, 0, 0, 255],
+    );
+    let tall = texture(
+        &device,
+        &queue,
+        wgpu::TextureFormat::Rgba8Unorm,
+        wgpu::TextureUsages::TEXTURE_BINDING,
+        (1, 2),
+        &[0, 255, 0, 255, 0, 255, 0, 255],
+    );
+
+    let normalization = Normalization {
+        mean: [0.5; 3],
+        std: [0.5; 3],
+    };
+    let (result, placements) =
+        Tensor::from_textures_letterboxed(&ctx, &[&small, &tall], 4, 4, 0.0, normalization)
+            .unwrap();
+    assert_eq!(result.dimensions(), &[2, 3, 4, 4]);
+    approx::assert_relative_eq!(placements[0].scale, 2.0);
+    assert_eq!((placements[0].left, placements[0].top), (0, 0));
+    assert_eq!((placements[1].left, placements[1].top), (1, 0));
+
+    // Red ramps from 0 to 1 across pixel centers: sources at -0.25, 0.25, 0.75, 1.25.
+    let values = result.to_vec().unwrap();
+    let ramp = [0.0, 0.25, 0.75, 1.0].map(|v: f32| v * 2.0 - 1.0);
+    for row in 0..4 {
+        crate::assert_vec_relative_eq(&values[row * 4..row * 4 + 4], &ramp, 1e-5);
+    }
+
+    // Second image is 2 × 4 centered, green inside, padding normalizes to -1.
+    let green = &values[48 + 16..48 + 32];
+    for row in 0..4 {
+        crate::assert_vec_relative_eq(&green[row * 4..row * 4 + 4], &[-1.0, 1.0, 1.0, -1.0], 1e-5);
+    }
+}
+
+#[test]
+fn test_from_textures_letterboxed_invalid() {
+    let (ctx, device, queue) = device();
+    let valid = texture(
+        &device,
+        &queue,
+        wgpu::TextureFormat::Rgba8Unorm,
+        wgpu::TextureUsages::TEXTURE_BINDING,
+        (1, 1),
+        &[0; 4],
+    );
+    let identity = Normalization::IDENTITY;
+    assert!(Tensor::from_textures_letterboxed(&ctx, &[], 4, 4, 0.0, identity).is_err());
+    assert!(Tensor::from_textures_letterboxed(&ctx, &[&valid], 0, 4, 0.0, identity).is_err());
+}
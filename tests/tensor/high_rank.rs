@@ -0,0 +1,47 @@
+//! Tests for operations on tensors with rank above 8.
+
+#![allow(clippy::cast_precision_loss)]
+
+use xnn::{Context, Tensor};
+
+const DIMS: [usize; 10] = [2, 1, 2, 1, 2, 1, 1, 2, 1, 3];
+
+fn ramp(ctx: &Context) -> Tensor<f32> {
+    let len = DIMS.iter().product();
+    let data: Vec<f32> = (0..len).map(|i| i as f32).collect();
+    Tensor::<f32>::from_shape_slice(ctx, &DIMS, &data).unwrap()
+}
+
+#[test]
+fn test_high_rank_binary_broadcast() {
+    let ctx = Context::try_default().unwrap();
+    let x = ramp(&ctx);
+    let bias = Tensor::<f32>::from_slice(&ctx, &[10.0, 20.0, 30.0]).unwrap();
+
+    let y = x.add(&bias).unwrap();
+    assert_eq!(y.dimensions(), &DIMS);
+    assert_eq!(
+        &y.to_vec().unwrap()[..6],
+        &[10.0, 21.0, 32.0, 13.0, 24.0, 35.0]
+    );
+}
+
+#[test]
+fn test_high_rank_reduce() {
+    let ctx = Context::try_default().unwrap();
+    let x = ramp(&ctx);
+
+    let y = x.sum_reduce(&[0, 2, 4, 7], false).unwrap();
+    assert_eq!(y.dimensions(), &[1, 1, 1, 1, 1, 1, 1, 1, 1, 3]);
+    assert_eq!(y.to_vec().unwrap(), vec![360.0, 376.0, 392.0]);
+}
+
+#[test]
+fn test_high_rank_transpose() {
+    let ctx = Context::try_default().unwrap();
+    let x = ramp(&ctx);
+
+    let y = x.transpose(0, 9).unwrap().contiguous().unwrap();
+    assert_eq!(y.dimensions(), &[3, 1, 2, 1, 2, 1, 1, 2, 1, 2]);
+    assert_eq!(&y.to_vec().unwrap()[..4], &[0.0, 24.0, 3.0, 27.0]);
+}
@@ -0,0 +1,86 @@
+//! Tests for `Tensor::gather` operation.
+
+use xnn::{Context, Tensor};
+
+#[test]
+fn test_gather_last_axis() {
+    let ctx = Context::try_default().unwrap();
+    let logits =
+        Tensor::<f32>::from_shape_slice(&ctx, &[2, 3], &[0.1, 0.2, 0.7, 0.5, 0.3, 0.2]).unwrap();
+    let labels = Tensor::<u32>::from_shape_slice(&ctx, &[2, 1], &[2, 0]).unwrap();
+
+    let result = logits.gather(1, &labels).unwrap();
+    assert_eq!(result.dimensions(), &[2, 1]);
+    assert_eq!(result.to_vec().unwrap(), vec![0.7, 0.5]);
+}
+
+#[test]
+fn test_gather_first_axis() {
+    let ctx = Context::try_default().unwrap();
+    let data: Vec<u32> = (0..6).collect();
+    let t = Tensor::<u32>::from_shape_slice(&ctx, &[3, 2], &data).unwrap();
+    let indices =
+        Tensor::<u32>::from_shape_slice(&ctx, &[4, 2], &[2, 0, 1, 1, 0, 2, 2, 2]).unwrap();
+
+    let result = t.gather(0, &indices).unwrap();
+    assert_eq!(result.dimensions(), &[4, 2]);
+    assert_eq!(result.to_vec().unwrap(), vec![4, 1, 2, 3, 0, 5, 4, 5]);
+}
+
+#[test]
+fn test_gather_beam_reorder() {
+    let ctx = Context::try_default().unwrap();
+    let data: Vec<i32> = (0..12).collect();
+    let beams = Tensor::<i32>::from_shape_slice(&ctx, &[1, 3, 4], &data).unwrap();
+    let parents = Tensor::<u32>::from_slice(&ctx, &[2, 2, 0])
+        .unwrap()
+        .reshape(&[1, 3, 1])
+        .unwrap()
+        .broadcast_to(&[1, 3, 4])
+        .unwrap();
+
+    let result = beams.gather(1, &parents).unwrap();
+    assert_eq!(
+        result.to_vec().unwrap(),
+        vec![8, 9, 10, 11, 8, 9, 10, 11, 0, 1, 2, 3]
+    );
+}
+
+#[test]
+fn test_gather_strided() {
+    let ctx = Context::try_default().unwrap();
+    let data: Vec<u32> = (0..6).collect();
+    let t = Tensor::<u32>::from_shape_slice(&ctx, &[2, 3], &data)
+        .unwrap()
+        .transpose(0, 1)
+        .unwrap()
+        .narrow(0, 1, 2)
+        .unwrap();
+    let indices = Tensor::<u32>::from_shape_slice(&ctx, &[2, 1], &[1, 0]).unwrap();
+
+    let result = t.gather(1, &indices).unwrap();
+    assert_eq!(result.to_vec().unwrap(), vec![4, 2]);
+}
+
+#[test]
+fn test_gather_out_of_bounds() {
+    let ctx = Context::try_default().unwrap();
+    let t = Tensor::<f32>::from_slice(&ctx, &[1.0, 2.0, 3.0]).unwrap();
+    let indices = Tensor::<u32>::from_slice(&ctx, &[2, 3, 0, 9]).unwrap();
+
+    let result = t.gather(0, &indices).unwrap();
+    assert_eq!(result.to_vec().unwrap(), vec![3.0, 0.0, 1.0, 0.0]);
+}
+
+#[test]
+fn test_gather_invalid() {
+    let ctx = Context::try_default().unwrap();
+    let t = Tensor::<f32>::constant(&ctx, &[2, 3], &[0.0]).unwrap();
+    let indices = Tensor::<u32>::constant(&ctx, &[2, 1], &[0]).unwrap();
+    assert!(t.gather(2, &indices).is_err());
+    assert!(t.gather(0, &indices.reshape(&[2]).unwrap()).is_err());
+    assert!(
+        t.gather(1, &Tensor::<u32>::constant(&ctx, &[3, 1], &[0]).unwrap())
+            .is_err()
+    );
+}
@@ -0,0 +1,79 @@
+//! Tests for `Tensor::index_put_` operation.
+
+use xnn::{Context, Tensor};
+
+#[test]
+fn test_index_put_rows() {
+    let ctx = Context::try_default().unwrap();
+    let mut t = Tensor::<f32>::constant(&ctx, &[4, 3], &[0.0]).unwrap();
+    let indices = Tensor::<u32>::from_slice(&ctx, &[2, 0]).unwrap();
+    let values =
+        Tensor::<f32>::from_shape_slice(&ctx, &[2, 3], &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]).unwrap();
+    t.index_put_(&indices, &values).unwrap();
+    assert_eq!(t.dimensions(), &[4, 3]);
+    assert_eq!(
+        t.to_vec().unwrap(),
+        vec![4.0, 5.0, 6.0, 0.0, 0.0, 0.0, 1.0, 2.0, 3.0, 0.0, 0.0, 0.0]
+    );
+}
+
+#[test]
+fn test_index_put_elements() {
+    let ctx = Context::try_default().unwrap();
+    let mut t = Tensor::<i32>::from_slice(&ctx, &[1, 2, 3, 4, 5]).unwrap();
+    let indices = Tensor::<u32>::from_slice(&ctx, &[4, 1]).unwrap();
+    let values = Tensor::<i32>::from_slice(&ctx, &[-5, -2]).unwrap();
+    t.index_put_(&indices, &values).unwrap();
+    assert_eq!(t.to_vec().unwrap(), vec![1, -2, 3, 4, -5]);
+}
+
+#[test]
+fn test_index_put_kv_cache_slot() {
+    let ctx = Context::try_default().unwrap();
+    let mut cache = Tensor::<f32>::constant(&ctx, &[3, 2, 2], &[0.0]).unwrap();
+    let indices = Tensor::<u32>::from_slice(&ctx, &[1]).unwrap();
+    let values = Tensor::<f32>::from_shape_slice(&ctx, &[1, 2, 2], &[1.0, 2.0, 3.0, 4.0]).unwrap();
+    cache.index_put_(&indices, &values).unwrap();
+    let out = cache.to_vec().unwrap();
+    assert_eq!(&out[4..8], &[1.0, 2.0, 3.0, 4.0]);
+    assert!(out[..4].iter().chain(&out[8..]).all(|&x| x == 0.0));
+}
+
+#[test]
+fn test_index_put_out_of_bounds_skipped() {
+    let ctx = Context::try_default().unwrap();
+    let mut t = Tensor::<u32>::from_slice(&ctx, &[1, 2, 3]).unwrap();
+    let indices = Tensor::<u32>::from_slice(&ctx, &[7, 0]).unwrap();
+    let values = Tensor::<u32>::from_slice(&ctx, &[9, 8]).unwrap();
+    t.index_put_(&indices, &values).unwrap();
+    assert_eq!(t.to_vec().unwrap(), vec![8, 2, 3]);
+}
+
+#[test]
+fn test_index_put_aliased() {
+    let ctx = Context::try_default().unwrap();
+    let mut t = Tensor::<f32>::from_shape_slice(&ctx, &[2, 2], &[1.0, 2.0, 3.0, 4.0]).unwrap();
+    let indices = Tensor::<u32>::from_slice(&ctx, &[1, 0]).unwrap();
+    let values = t.view(&[2, 2]).unwrap();
+    t.index_put_(&indices, &values).unwrap();
+    assert_eq!(t.to_vec().unwrap(), vec![3.0, 4.0, 1.0, 2.0]);
+
+    let mut u = Tensor::<u32>::from_slice(&ctx, &[1, 0]).unwrap();
+    let indices = u.view(&[2]).unwrap();
+    let values = Tensor::<u32>::from_slice(&ctx, &[5, 6]).unwrap();
+    u.index_put_(&indices, &values).unwrap();
+    assert_eq!(u.to_vec().unwrap(), vec![6, 5]);
+}
+
+#[test]
+fn test_index_put_invalid() {
+    let ctx = Context::try_default().unwrap();
+    let mut t = Tensor::<f32>::constant(&ctx, &[2, 3], &[0.0]).unwrap();
+    let indices = Tensor::<u32>::from_slice(&ctx, &[0]).unwrap();
+    let wide = Tensor::<f32>::constant(&ctx, &[1, 4], &[1.0]).unwrap();
+    let many = Tensor::<f32>::constant(&ctx, &[2, 3], &[1.0]).unwrap();
+    let nested = Tensor::<u32>::from_shape_slice(&ctx, &[1, 1], &[0]).unwrap();
+    assert!(t.index_put_(&indices, &wide).is_err());
+    assert!(t.index_put_(&indices, &many).is_err());
+    assert!(t.index_put_(&nested, &many).is_err());
+}
@@ -0,0 +1,52 @@
+//! Tests for `Tensor::masked_copy_` operation.
+
+use xnn::{Context, Tensor};
+
+#[test]
+fn test_masked_copy_basic() {
+    let ctx = Context::try_default().unwrap();
+    let mut dst = Tensor::<f32>::from_slice(&ctx, &[1.0, 2.0, 3.0, 4.0]).unwrap();
+    let src = Tensor::<f32>::from_slice(&ctx, &[10.0, 20.0, 30.0, 40.0]).unwrap();
+    let mask = Tensor::<bool>::from_slice(&ctx, &[true, false, false, true]).unwrap();
+    dst.masked_copy_(&src, &mask).unwrap();
+    assert_eq!(dst.to_vec().unwrap(), vec![10.0, 2.0, 3.0, 40.0]);
+}
+
+#[test]
+fn test_masked_copy_broadcast() {
+    let ctx = Context::try_default().unwrap();
+    let mut dst = Tensor::<i32>::constant(&ctx, &[2, 3], &[0]).unwrap();
+    let src = Tensor::<i32>::from_slice(&ctx, &[1, 2, 3]).unwrap();
+    let mask = Tensor::<bool>::from_shape_slice(&ctx, &[2, 1], &[false, true]).unwrap();
+    dst.masked_copy_(&src, &mask).unwrap();
+    assert_eq!(dst.to_vec().unwrap(), vec![0, 0, 0, 1, 2, 3]);
+}
+
+#[test]
+fn test_masked_copy_aliased() {
+    let ctx = Context::try_default().unwrap();
+    let mut dst = Tensor::<f32>::from_shape_slice(&ctx, &[2, 2], &[1.0, 2.0, 3.0, 4.0]).unwrap();
+    let src = dst.transpose(0, 1).unwrap();
+    let mask =
+        Tensor::<bool>::from_shape_slice(&ctx, &[2, 2], &[false, true, true, false]).unwrap();
+    dst.masked_copy_(&src, &mask).unwrap();
+    assert_eq!(dst.to_vec().unwrap(), vec![1.0, 3.0, 2.0, 4.0]);
+
+    let src = dst.reshape(&[2, 2]).unwrap();
+    let mask = Tensor::<bool>::from_slice(&ctx, &[true, false]).unwrap();
+    dst.masked_copy_(&src, &mask).unwrap();
+    assert_eq!(dst.to_vec().unwrap(), vec![1.0, 3.0, 2.0, 4.0]);
+}
+
+#[test]
+fn test_masked_copy_invalid_shape() {
+    let ctx = Context::try_default().unwrap();
+    let mut dst = Tensor::<f32>::from_slice(&ctx, &[1.0, 2.0]).unwrap();
+    let src = Tensor::<f32>::from_shape_slice(&ctx, &[2, 2], &[0.0; 4]).unwrap();
+    let mask = Tensor::<bool>::from_slice(&ctx, &[true, true]).unwrap();
+    assert!(dst.masked_copy_(&src, &mask).is_err());
+
+    let src = Tensor::<f32>::from_slice(&ctx, &[0.0, 0.0]).unwrap();
+    let mask = Tensor::<bool>::from_slice(&ctx, &[true, true, true]).unwrap();
+    assert!(dst.masked_copy_(&src, &mask).is_err());
+}
@@ -0,0 +1,7 @@
+//! Indexing operation tests.
+
+mod gather;
+mod index_put;
+mod masked_copy;
+mod nonzero;
+mod take_rows;
@@ -0,0 +1,96 @@
+//! Tests for `Tensor::nonzero` operation.
+
+use xnn::{Context, Tensor};
+
+#[test]
+fn test_nonzero() {
+    let ctx = Context::try_default().unwrap();
+    let t =
+        Tensor::<f32>::from_shape_slice(&ctx, &[2, 3], &[0.0, 1.5, 0.0, -2.0, 0.0, 3.0]).unwrap();
+
+    let (indices, count) = t.nonzero().unwrap();
+    assert_eq!(indices.dimensions(), &[6, 2]);
+    assert_eq!(count.to_vec().unwrap(), vec![3]);
+    assert_eq!(
+        indices.to_vec().unwrap(),
+        vec![
+            0,
+            1,
+            1,
+            0,
+            1,
+            2,
+            u32::MAX,
+            u32::MAX,
+            u32::MAX,
+            u32::MAX,
+            u32::MAX,
+            u32::MAX
+        ]
+    );
+}
+
+#[test]
+fn test_nonzero_mask_strided() {
+    let ctx = Context::try_default().unwrap();
+    let mask = Tensor::<bool>::from_shape_slice(&ctx, &[2, 2], &[true, false, true, true])
+        .unwrap()
+        .transpose(0, 1)
+        .unwrap();
+
+    let (indices, count) = mask.nonzero().unwrap();
+    assert_eq!(count.to_vec().unwrap(), vec![3]);
+    assert_eq!(indices.to_vec().unwrap()[..6], [0, 0, 0, 1, 1, 1]);
+}
+
+#[test]
+fn test_nonzero_long() {
+    let ctx = Context::try_default().unwrap();
+    let data: Vec<i32> = (0..50_000)
+        .map(|i| i32::from(i % 7 == 3 || i % 11 == 0))
+        .collect();
+    let t = Tensor::<i32>::from_slice(&ctx, &data).unwrap();
+
+    let (indices, count) = t.nonzero().unwrap();
+    let expected: Vec<u32> = (0..50_000u32).filter(|&i| data[i as usize] != 0).collect();
+    assert_eq!(
+        count.to_vec().unwrap(),
+        vec![u32::try_from(expected.len()).unwrap()]
+    );
+    assert_eq!(indices.to_vec().unwrap()[..expected.len()], expected);
+}
+
+#[test]
+fn test_nonzero_empty() {
+    let ctx = Context::try_default().unwrap();
+    let t = Tensor::<f32>::constant(&ctx, &[0, 3], &[0.0]).unwrap();
+
+    let (indices, count) = t.nonzero().unwrap();
+    assert_eq!(indices.dimensions(), &[0, 2]);
+    assert_eq!(count.to_vec().unwrap(), vec![0]);
+
+    let scalar = Tensor::<f32>::from_slice(&ctx, &[1.0])
+        .unwrap()
+        .reshape(&[])
+        .unwrap();
+    assert!(scalar.nonzero().is_err());
+}
+
+#[test]
+fn test_nonzero_take_rows() {
+    let ctx = Context::try_default().unwrap();
+    let scores = Tensor::<f32>::from_slice(&ctx, &[0.2, 0.9, 0.1, 0.7]).unwrap();
+    let threshold = Tensor::<f32>::from_slice(&ctx, &[0.5]).unwrap();
+    let values =
+        Tensor::<f32>::from_shape_slice(&ctx, &[4, 2], &[0.0, 1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0])
+            .unwrap();
+
+    let (indices, count) = scores.gt(&threshold).unwrap().nonzero().unwrap();
+    let selected = values
+        .take_rows(&indices.reshape(&[4]).unwrap(), &count)
+        .unwrap();
+    assert_eq!(
+        selected.to_vec().unwrap(),
+        vec![2.0, 3.0, 6.0, 7.0, 0.0, 0.0, 0.0, 0.0]
+    );
+}
@@ -0,0 +1,74 @@
+//! Tests for `Tensor::take_rows` operation.
+
+use xnn::{Context, Tensor};
+
+#[test]
+fn test_take_rows() {
+    let ctx = Context::try_default().unwrap();
+    let data: Vec<u32> = (0..8).collect();
+    let t = Tensor::<u32>::from_shape_slice(&ctx, &[4, 2], &data).unwrap();
+    let indices = Tensor::<u32>::from_slice(&ctx, &[3, 1, 0]).unwrap();
+
+    let count = Tensor::<u32>::from_slice(&ctx, &[2]).unwrap();
+    let result = t.take_rows(&indices, &count).unwrap();
+    assert_eq!(result.dimensions(), &[3, 2]);
+    assert_eq!(result.to_vec().unwrap(), vec![6, 7, 2, 3, 0, 0]);
+
+    let count = Tensor::<u32>::from_slice(&ctx, &[0]).unwrap();
+    let result = t.take_rows(&indices, &count).unwrap();
+    assert_eq!(result.to_vec().unwrap(), vec![0; 6]);
+}
+
+#[test]
+fn test_take_rows_count_exceeds_indices() {
+    let ctx = Context::try_default().unwrap();
+    let t = Tensor::<f32>::from_slice(&ctx, &[1.0, 2.0, 3.0]).unwrap();
+    let indices = Tensor::<u32>::from_slice(&ctx, &[2, 7]).unwrap();
+    let count = Tensor::<u32>::from_slice(&ctx, &[100]).unwrap();
+
+    let result = t.take_rows(&indices, &count).unwrap();
+    assert_eq!(result.to_vec().unwrap(), vec![3.0, 0.0]);
+}
+
+#[test]
+fn test_take_rows_after_nms() {
+    let ctx = Context::try_default().unwrap();
+    let boxes = Tensor::<f32>::from_shape_slice(
+        &ctx,
+        &[3, 4],
+        &[
+            0.0, 0.0, 10.0, 10.0, //
+            1.0, 1.0, 11.0, 11.0, //
+            20.0, 20.0, 30.0, 30.0,
+        ],
+    )
+    .unwrap();
+    let scores = Tensor::<f32>::from_slice(&ctx, &[0.9, 0.8, 0.7]).unwrap();
+
+    let (keep, count) = boxes.nms(&scores, 0.5).unwrap();
+    let kept = boxes.take_rows(&keep, &count).unwrap();
+    assert_eq!(kept.dimensions(), &[3, 4]);
+    assert_eq!(
+        kept.to_vec().unwrap(),
+        vec![
+            0.0, 0.0, 10.0, 10.0, //
+            20.0, 20.0, 30.0, 30.0, //
+            0.0, 0.0, 0.0, 0.0,
+        ]
+    );
+}
+
+#[test]
+fn test_take_rows_invalid() {
+    let ctx = Context::try_default().unwrap();
+    let t = Tensor::<f32>::constant(&ctx, &[4, 2], &[0.0]).unwrap();
+    let indices = Tensor::<u32>::from_slice(&ctx, &[0, 1]).unwrap();
+    let count = Tensor::<u32>::from_slice(&ctx, &[1]).unwrap();
+
+    let matrix = Tensor::<u32>::constant(&ctx, &[2, 1], &[0]).unwrap();
+    assert!(t.take_rows(&matrix, &count).is_err());
+    assert!(t.take_rows(&indices, &indices).is_err());
+
+    let scalar = Tensor::<f32>::constant(&ctx, &[], &[0.0]).unwrap();
+    assert!(scalar.take_rows(&indices, &count).is_err());
+}
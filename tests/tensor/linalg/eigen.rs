@@ -0,0 +1,159 @@
+//! Tests for `Tensor::eigh`.
+
+#![allow(clippy::cast_precision_loss)]
+
+use approx::assert_relative_eq;
+use xnn::{Context, Tensor};
+
+/// Batch of symmetric `n × n` matrices with deterministic pseudo-random entries.
+fn symmetric(batch: usize, n: usize) -> Vec<f32> {
+    let mut data = vec![0.0; batch * n * n];
+    for p in 0..batch {
+        for i in 0..n {
+            for j in 0..=i {
+                let value = ((p * 31 + i * 7 + j * 13) as f32 * 0.7).sin();
+                data[p * n * n + i * n + j] = value;
+                data[p * n * n + j * n + i] = value;
+            }
+        }
+    }
+    data
+}
+
+/// Checks that `values` ascend and that `vectors · diag(values) · vectorsᵀ` reproduces
+/// `matrices` with orthonormal `vectors`.
+fn check(matrices: &[f32], values: &[f32], vectors: &[f32], n: usize, epsilon: f32) {
+    for p in 0..values.len() / n {
+        let values = &values[p * n..(p + 1) * n];
+        let vectors = &vectors[p * n * n..(p + 1) * n * n];
+        let matrix = &matrices[p * n * n..(p + 1) * n * n];
+
+        assert!(values.windows(2).all(|pair| pair[0] <= pair[1]));
+        for i in 0..n {
+            for k in 0..n {
+                let (mut product, mut gram) = (0.0, 0.0);
+                for j in 0..n {
+                    product += vectors[i * n + j] * values[j] * vectors[k * n + j];
+                    gram += vectors[j * n + i] * vectors[j * n + k];
+                }
+                assert_relative_eq!(product, matrix[i * n + k], epsilon = epsilon);
+                assert_relative_eq!(gram, if i == k { 1.0 } else { 0.0 }, epsilon = epsilon);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_eigh_2x2() {
+    let ctx = Context::try_default().unwrap();
+    let a = Tensor::<f32>::from_shape_slice(&ctx, &[2, 2], &[2.0, 1.0, 1.0, 2.0]).unwrap();
+
+    let (values, vectors) = a.eigh().unwrap();
+    assert_eq!(values.dimensions(), &[2]);
+    assert_eq!(vectors.dimensions(), &[2, 2]);
+
+    let values = values.to_vec().unwrap();
+    assert_relative_eq!(values[0], 1.0, epsilon = 1e-5);
+    assert_relative_eq!(values[1], 3.0, epsilon = 1e-5);
+    check(
+        &[2.0, 1.0, 1.0, 2.0],
+        &values,
+        &vectors.to_vec().unwrap(),
+        2,
+        1e-5,
+    );
+}
+
+#[test]
+fn test_eigh_diagonal() {
+    let ctx = Context::try_default().unwrap();
+    let data = [3.0, 0.0, 0.0, 0.0, -1.0, 0.0, 0.0, 0.0, 2.0];
+    let a = Tensor::<f32>::from_shape_slice(&ctx, &[3, 3], &data).unwrap();
+
+    let (values, vectors) = a.eigh().unwrap();
+    assert_eq!(values.to_vec().unwrap(), vec![-1.0, 2.0, 3.0]);
+    assert_eq!(
+        vectors.to_vec().unwrap(),
+        vec![0.0, 0.0, 1.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0]
+    );
+}
+
+#[test]
+fn test_eigh_batched() {
+    let ctx = Context::try_default().unwrap();
+    let (batch, n) = (5, 8);
+    let data = symmetric(batch, n);
+    let a = Tensor::<f32>::from_shape_slice(&ctx, &[batch, n, n], &data).unwrap();
+
+    let (values, vectors) = a.eigh().unwrap();
+    assert_eq!(values.dimensions(), &[batch, n]);
+    assert_eq!(vectors.dimensions(), &[batch, n, n]);
+    check(
+        &data,
+        &values.to_vec().unwrap(),
+        &vectors.to_vec().unwrap(),
+        n,
+        1e-4,
+    );
+}
+
+#[test]
+fn test_eigh_largest() {
+    let ctx = Context::try_default().unwrap();
+    let n = 32;
+    let data = symmetric(2, n);
+    let a = Tensor::<f32>::from_shape_slice(&ctx, &[2, 1, n, n], &data).unwrap();
+
+    let (values, vectors) = a.eigh().unwrap();
+    assert_eq!(values.dimensions(), &[2, 1, n]);
+    check(
+        &data,
+        &values.to_vec().unwrap(),
+        &vectors.to_vec().unwrap(),
+        n,
+        1e-3,
+    );
+}
+
+#[test]
+fn test_eigh_reads_lower_triangle() {
+    let ctx = Context::try_default().unwrap();
+    let a = Tensor::<f32>::from_shape_slice(&ctx, &[2, 2], &[2.0, 9.0, 1.0, 2.0]).unwrap();
+
+    let (values, _) = a.eigh().unwrap();
+    let values = values.to_vec().unwrap();
+    assert_relative_eq!(values[0], 1.0, epsilon = 1e-5);
+    assert_relative_eq!(values[1], 3.0, epsilon = 1e-5);
+}
+
+#[test]
+fn test_eigh_transposed_view() {
+    let ctx = Context::try_default().unwrap();
+    let n = 4;
+    let data = symmetric(1, n);
+    let a = Tensor::<f32>::from_shape_slice(&ctx, &[n, n], &data).unwrap();
+
+    let (values, _) = a.eigh().unwrap();
+    let (transposed, _) = a.permute(&[1, 0]).unwrap().eigh().unwrap();
+    for (a, b) in values
+        .to_vec()
+        .unwrap()
+        .iter()
+        .zip(&transposed.to_vec().unwrap())
+    {
+        assert_relative_eq!(a, b, epsilon = 1e-5);
+    }
+}
+
+#[test]
+fn test_eigh_invalid() {
+    let ctx = Context::try_default().unwrap();
+    let vector = Tensor::<f32>::from_slice(&ctx, &[1.0, 2.0]).unwrap();
+    assert!(vector.eigh().is_err());
+
+    let wide = Tensor::<f32>::from_shape_slice(&ctx, &[2, 3], &[0.0; 6]).unwrap();
+    assert!(wide.eigh().is_err());
+
+    let large = Tensor::<f32>::constant(&ctx, &[33, 33], &[0.0]).unwrap();
+    assert!(large.eigh().is_err());
+}
@@ -296,3 +296,32 @@ fn test_matmul_error_batch_incompatible() {
 
     assert!(a.matmul(&b, false, false).is_err());
 }
+
+#[test]
+fn test_matmul_high_rank_broadcast() {
+    let ctx = Context::try_default().unwrap();
+
+    for (m, k, n) in [(2, 3, 2), (9, 10, 11)] {
+        let a_shape = [2, 1, 1, 1, 1, 1, 1, 2, m, k];
+        let b_shape = [1, 1, 1, 1, 1, 1, 1, 2, k, n];
+        let a_len: usize = a_shape.iter().product();
+        let b_len: usize = b_shape.iter().product();
+        let a_data: Vec<f32> = (0..a_len).map(|i| (i % 7) as f32 - 3.0).collect();
+        let b_data: Vec<f32> = (0..b_len).map(|i| (i % 5) as f32 * 0.5).collect();
+
+        let a = Tensor::<f32>::from_shape_slice(&ctx, &a_shape, &a_data).unwrap();
+        let b = Tensor::<f32>::from_shape_slice(&ctx, &b_shape, &b_data).unwrap();
+        let c = a.matmul(&b, false, false).unwrap();
+        assert_eq!(c.dimensions(), &[2, 1, 1, 1, 1, 1, 1, 2, m, n]);
+
+        let mut expected = Vec::new();
+        for outer in 0..2 {
+            for inner in 0..2 {
+                let a_mat = &a_data[(outer * 2 + inner) * m * k..][..m * k];
+                let b_mat = &b_data[inner * k * n..][..k * n];
+                expected.extend(cpu_matmul(a_mat, b_mat, m, k, n));
+            }
+        }
+        crate::assert_vec_relative_eq(&c.to_vec().unwrap(), &expected, 1e-4);
+    }
+}
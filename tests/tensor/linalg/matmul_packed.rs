@@ -0,0 +1,86 @@
+//! Tests for `Tensor::matmul_packed` operation.
+
+#![allow(clippy::cast_precision_loss)]
+
+use xnn::{Context, Tensor};
+
+/// Checks each packed part against the matching column group of a plain matmul.
+#[track_caller]
+fn check_packed(
+    a_shape: &[usize],
+    b_shape: &[usize],
+    transpose_a: bool,
+    transpose_b: bool,
+    parts: usize,
+) {
+    let ctx = Context::try_default().unwrap();
+
+    let a_len: usize = a_shape.iter().product();
+    let b_len: usize = b_shape.iter().product();
+    let a_data: Vec<f32> = (0..a_len).map(|i| (i % 7) as f32 - 3.0).collect();
+    let b_data: Vec<f32> = (0..b_len).map(|i| (i % 5) as f32 * 0.5).collect();
+
+    let a = Tensor::<f32>::from_shape_slice(&ctx, a_shape, &a_data).unwrap();
+    let b = Tensor::<f32>::from_shape_slice(&ctx, b_shape, &b_data).unwrap();
+
+    let full = a.matmul(&b, transpose_a, transpose_b).unwrap();
+    let packed = a
+        .matmul_packed(&b, transpose_a, transpose_b, parts)
+        .unwrap();
+
+    let rank = full.dimensions().len();
+    let mut expected_dims = vec![parts];
+    expected_dims.extend(&full.dimensions()[..rank - 1]);
+    expected_dims.push(full.dimensions()[rank - 1] / parts);
+    assert_eq!(packed.dimensions(), expected_dims.as_slice());
+
+    let expected = full.shard(rank - 1, parts).unwrap();
+    let actual = packed.shard(0, parts).unwrap();
+    for (a, e) in actual.iter().zip(&expected) {
+        crate::assert_vec_relative_eq(&a.to_vec().unwrap(), &e.to_vec().unwrap(), 1e-4);
+    }
+}
+
+#[test]
+fn test_matmul_packed_qkv() {
+    check_packed(&[4, 8], &[8, 12], false, false, 3);
+}
+
+#[test]
+fn test_matmul_packed_single_part() {
+    check_packed(&[3, 5], &[5, 7], false, false, 1);
+}
+
+#[test]
+fn test_matmul_packed_transpose_b() {
+    check_packed(&[5, 6], &[9, 6], false, true, 3);
+}
+
+#[test]
+fn test_matmul_packed_transpose_a() {
+    check_packed(&[6, 5], &[6, 4], true, false, 2);
+}
+
+#[test]
+fn test_matmul_packed_batched() {
+    check_packed(&[2, 3, 4], &[2, 4, 6], false, false, 3);
+}
+
+#[test]
+fn test_matmul_packed_batched_broadcast() {
+    check_packed(&[3, 5, 4], &[1, 4, 6], false, false, 2);
+}
+
+#[test]
+fn test_matmul_packed_large() {
+    check_packed(&[70, 33], &[33, 210], false, false, 3);
+}
+
+#[test]
+fn test_matmul_packed_indivisible() {
+    let ctx = Context::try_default().unwrap();
+    let a = Tensor::<f32>::from_shape_slice(&ctx, &[2, 2], &[1.0; 4]).unwrap();
+    let b = Tensor::<f32>::from_shape_slice(&ctx, &[2, 5], &[1.0; 10]).unwrap();
+    assert!(a.matmul_packed(&b, false, false, 3).is_err());
+    assert!(a.matmul_packed(&b, false, false, 0).is_err());
+}
@@ -0,0 +1,135 @@
+//! Tests for `Tensor::matmul_scaled_` operation.
+
+#![allow(clippy::cast_precision_loss)]
+
+use xnn::{Context, Tensor};
+
+/// Checks `c[i] = alpha[i] · a[i] × b[i] + beta[i] · c[i]` over a batch of square matrices.
+#[track_caller]
+fn check_scaled(batch: usize, size: usize) {
+    let ctx = Context::try_default().unwrap();
+    let len = batch * size * size;
+
+    let a_data: Vec<f32> = (0..len).map(|i| (i % 7) as f32 - 3.0).collect();
+    let b_data: Vec<f32> = (0..len).map(|i| (i % 5) as f32 * 0.5).collect();
+    let c_data: Vec<f32> = (0..len).map(|i| (i % 3) as f32).collect();
+    let alpha: Vec<f32> = (0..batch).map(|i| i as f32 * 0.5).collect();
+    let beta: Vec<f32> = (0..batch).map(|i| 1.0 - i as f32).collect();
+
+    let dims = [batch, size, size];
+    let a = Tensor::<f32>::from_shape_slice(&ctx, &dims, &a_data).unwrap();
+    let b = Tensor::<f32>::from_shape_slice(&ctx, &dims, &b_data).unwrap();
+    let mut c = Tensor::<f32>::from_shape_slice(&ctx, &dims, &c_data).unwrap();
+    let alpha_t = Tensor::<f32>::from_slice(&ctx, &alpha).unwrap();
+    let beta_t = Tensor::<f32>::from_slice(&ctx, &beta).unwrap();
+
+    c.matmul_scaled_(&a, &b, false, false, &alpha_t, &beta_t)
+        .unwrap();
+
+    let mut expected = vec![0.0; len];
+    for p in 0..batch {
+        for i in 0..size {
+            for j in 0..size {
+                let base = p * size * size;
+                let mut sum = 0.0;
+                for l in 0..size {
+                    sum += a_data[base + i * size + l] * b_data[base + l * size + j];
+                }
+                let idx = base + i * size + j;
+                expected[idx] = alpha[p] * sum + beta[p] * c_data[idx];
+            }
+        }
+    }
+
+    crate::assert_vec_relative_eq(&c.to_vec().unwrap(), &expected, 1e-4);
+}
+
+#[test]
+fn test_matmul_scaled_small() {
+    check_scaled(5, 4);
+}
+
+#[test]
+fn test_matmul_scaled_tiled() {
+    check_scaled(3, 20);
+}
+
+#[test]
+fn test_matmul_scaled_zero_beta_ignores_output() {
+    let ctx = Context::try_default().unwrap();
+    let a = Tensor::<f32>::from_shape_slice(&ctx, &[2, 2], &[1.0, 2.0, 3.0, 4.0]).unwrap();
+    let b = Tensor::<f32>::from_shape_slice(&ctx, &[2, 2], &[1.0, 0.0, 0.0, 1.0]).unwrap();
+    let mut c = Tensor::<f32>::constant(&ctx, &[2, 2], &[f32::NAN]).unwrap();
+    let alpha = Tensor::<f32>::from_slice(&ctx, &[2.0]).unwrap();
+    let beta = Tensor::<f32>::from_slice(&ctx, &[0.0]).unwrap();
+
+    c.matmul_scaled_(&a, &b, false, false, &alpha, &beta)
+        .unwrap();
+    assert_eq!(c.to_vec().unwrap(), vec![2.0, 4.0, 6.0, 8.0]);
+}
+
+#[test]
+fn test_matmul_scaled_aliased() {
+    let ctx = Context::try_default().unwrap();
+    let mut c = Tensor::<f32>::from_shape_slice(&ctx, &[2, 2], &[1.0, 2.0, 3.0, 4.0]).unwrap();
+    let b = Tensor::<f32>::from_shape_slice(&ctx, &[2, 2], &[0.0, 1.0, 1.0, 0.0]).unwrap();
+    let alpha = Tensor::<f32>::from_slice(&ctx, &[1.0]).unwrap();
+    let beta = Tensor::<f32>::from_slice(&ctx, &[1.0]).unwrap();
+
+    let a = c.reshape(&[2, 2]).unwrap();
+    c.matmul_scaled_(&a, &b, false, false, &alpha, &beta)
+        .unwrap();
+    assert_eq!(c.to_vec().unwrap(), vec![3.0, 3.0, 7.0, 7.0]);
+
+    let b = c.transpose(0, 1).unwrap();
+    let identity = Tensor::<f32>::from_shape_slice(&ctx, &[2, 2], &[1.0, 0.0, 0.0, 1.0]).unwrap();
+    let beta = Tensor::<f32>::from_slice(&ctx, &[0.0]).unwrap();
+    c.matmul_scaled_(&identity, &b, false, false, &alpha, &beta)
+        .unwrap();
+    assert_eq!(c.to_vec().unwrap(), vec![3.0, 7.0, 3.0, 7.0]);
+}
+
+#[test]
+fn test_matmul_scaled_broadcast_batch() {
+    let ctx = Context::try_default().unwrap();
+    let x = Tensor::<f32>::from_shape_slice(&ctx, &[1, 1, 2], &[1.0, 2.0]).unwrap();
+    let experts =
+        Tensor::<f32>::from_shape_slice(&ctx, &[2, 2, 1], &[1.0, 1.0, 10.0, 0.0]).unwrap();
+    let mut y = Tensor::<f32>::constant(&ctx, &[2, 1, 1], &[1.0]).unwrap();
+    let gates = Tensor::<f32>::from_slice(&ctx, &[0.25, 0.75]).unwrap();
+    let beta = Tensor::<f32>::from_slice(&ctx, &[1.0, 0.0]).unwrap();
+
+    y.matmul_scaled_(&x, &experts, false, false, &gates, &beta)
+        .unwrap();
+    assert_eq!(y.to_vec().unwrap(), vec![1.75, 7.5]);
+}
+
+#[test]
+fn test_matmul_scaled_invalid() {
+    let ctx = Context::try_default().unwrap();
+    let a = Tensor::<f32>::constant(&ctx, &[3, 2, 2], &[1.0]).unwrap();
+    let mut c = Tensor::<f32>::constant(&ctx, &[3, 2, 2], &[0.0]).unwrap();
+    let ones = Tensor::<f32>::constant(&ctx, &[3], &[1.0]).unwrap();
+    let short = Tensor::<f32>::constant(&ctx, &[2], &[1.0]).unwrap();
+
+    assert!(
+        c.matmul_scaled_(&a, &a, false, false, &short, &ones)
+            .is_err()
+    );
+    assert!(
+        c.matmul_scaled_(&a, &a, false, false, &ones, &short)
+            .is_err()
+    );
+
+    let b = Tensor::<f32>::constant(&ctx, &[3, 2, 3], &[1.0]).unwrap();
+    assert!(
+        c.matmul_scaled_(&a, &b, false, false, &ones, &ones)
+            .is_err()
+    );
+
+    let mut view = c.transpose(1, 2).unwrap();
+    assert!(
+        view.matmul_scaled_(&a, &a, false, false, &ones, &ones)
+            .is_err()
+    );
+}
@@ -1,3 +1,9 @@
 //! Linear algebra operation tests.
 
+mod eigen;
 mod matmul;
+mod matmul_packed;
+mod matmul_scaled;
+mod sharded_matmul;
+mod small_matmul;
+mod spectral_norm;
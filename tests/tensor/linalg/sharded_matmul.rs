@@ -0,0 +1,75 @@
+//! Tests for `ShardedMatrix::matmul` operation.
+
+#![allow(clippy::cast_precision_loss)]
+
+use xnn::{Context, ShardedMatrix, Tensor};
+
+fn values(len: usize, scale: f32) -> Vec<f32> {
+    (0..len).map(|i| ((i % 7) as f32 - 3.0) * scale).collect()
+}
+
+#[test]
+fn test_sharded_matmul_matches_matmul() {
+    let ctx = Context::try_default().unwrap();
+    let (m, k, n) = (4, 8, 5);
+    let x_data = values(m * k, 0.5);
+    let w_data = values(k * n, 0.25);
+
+    let w = ShardedMatrix::from_shape_slice_with_rows(&ctx, &[k, n], &w_data, 3).unwrap();
+    assert_eq!(w.dimensions(), &[k, n]);
+    let rows: Vec<usize> = w.shards().iter().map(|s| s.dimensions()[0]).collect();
+    assert_eq!(rows, vec![3, 3, 2]);
+
+    let x = Tensor::<f32>::from_shape_slice(&ctx, &[m, k], &x_data).unwrap();
+    let dense = Tensor::<f32>::from_shape_slice(&ctx, &[k, n], &w_data).unwrap();
+    let expected = x.matmul(&dense, false, false).unwrap();
+    let result = w.matmul(&x).unwrap();
+
+    assert_eq!(result.dimensions(), &[m, n]);
+    crate::assert_vec_relative_eq(&result.to_vec().unwrap(), &expected.to_vec().unwrap(), 1e-5);
+}
+
+#[test]
+fn test_sharded_matmul_batched() {
+    let ctx = Context::try_default().unwrap();
+    let (b, m, k, n) = (2, 3, 6, 4);
+    let x_data = values(b * m * k, 1.0);
+    let w_data = values(k * n, 0.5);
+
+    let w = ShardedMatrix::from_shape_slice_with_rows(&ctx, &[k, n], &w_data, 4).unwrap();
+    let x = Tensor::<f32>::from_shape_slice(&ctx, &[b, m, k], &x_data).unwrap();
+    let dense = Tensor::<f32>::from_shape_slice(&ctx, &[1, k, n], &w_data).unwrap();
+    let expected = x.matmul(&dense, false, false).unwrap();
+    let result = w.matmul(&x).unwrap();
+
+    assert_eq!(result.dimensions(), &[b, m, n]);
+    crate::assert_vec_relative_eq(&result.to_vec().unwrap(), &expected.to_vec().unwrap(), 1e-5);
+}
+
+#[test]
+fn test_sharded_matmul_single_shard() {
+    let ctx = Context::try_default().unwrap();
+    let w_data = values(12, 1.0);
+    let w = ShardedMatrix::from_shape_slice(&ctx, &[3, 4], &w_data).unwrap();
+    assert_eq!(w.shards().len(), 1);
+
+    let x = Tensor::<f32>::from_shape_slice(&ctx, &[1, 3], &[1.0, 0.0, -1.0]).unwrap();
+    let result = w.matmul(&x).unwrap().to_vec().unwrap();
+    let expected: Vec<f32> = (0..4).map(|j| w_data[j] - w_data[8 + j]).collect();
+    crate::assert_vec_relative_eq(&result, &expected, 1e-6);
+}
+
+#[test]
+fn test_sharded_matmul_invalid() {
+    let ctx = Context::try_default().unwrap();
+    let data = values(6, 1.0);
+    assert!(ShardedMatrix::from_shape_slice(&ctx, &[6], &data).is_err());
+    assert!(ShardedMatrix::from_shape_slice(&ctx, &[2, 2], &data).is_err());
+    assert!(ShardedMatrix::from_shape_slice_with_rows(&ctx, &[2, 3], &data, 0).is_err());
+
+    let w = ShardedMatrix::from_shape_slice_with_rows(&ctx, &[2, 3], &data, 1).unwrap();
+    let x = Tensor::<f32>::from_shape_slice(&ctx, &[1, 3], &[1.0; 3]).unwrap();
+    assert!(w.matmul(&x).is_err());
+    let x = Tensor::<f32>::from_slice(&ctx, &[1.0; 2]).unwrap();
+    assert!(w.matmul(&x).is_err());
+}
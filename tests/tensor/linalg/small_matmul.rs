@@ -0,0 +1,131 @@
+//! Tests for `Tensor::matmul` on batches of small matrices.
+
+#![allow(clippy::cast_precision_loss)]
+
+use xnn::{Context, Tensor};
+
+/// Reference batched matmul over contiguous `[batch, m, k] × [batch, k, n]` data.
+fn cpu_bmm(a: &[f32], b: &[f32], batch: usize, m: usize, k: usize, n: usize) -> Vec<f32> {
+    let mut c = vec![0.0; batch * m * n];
+    for p in 0..batch {
+        for i in 0..m {
+            for j in 0..n {
+                let mut sum = 0.0;
+                for l in 0..k {
+                    sum += a[p * m * k + i * k + l] * b[p * k * n + l * n + j];
+                }
+                c[p * m * n + i * n + j] = sum;
+            }
+        }
+    }
+    c
+}
+
+/// Transposes each `[rows, cols]` matrix of a contiguous batch.
+fn transpose(data: &[f32], batch: usize, rows: usize, cols: usize) -> Vec<f32> {
+    let mut out = vec![0.0; data.len()];
+    for p in 0..batch {
+        for i in 0..rows {
+            for j in 0..cols {
+                out[p * rows * cols + j * rows + i] = data[p * rows * cols + i * cols + j];
+            }
+        }
+    }
+    out
+}
+
+#[test]
+fn test_small_matmul_many_4x4() {
+    let ctx = Context::try_default().unwrap();
+    let batch = 70_000;
+
+    let a_data: Vec<f32> = (0..batch * 16).map(|i| (i % 7) as f32 - 3.0).collect();
+    let b_data: Vec<f32> = (0..batch * 16).map(|i| (i % 5) as f32 * 0.5).collect();
+
+    let a = Tensor::<f32>::from_shape_slice(&ctx, &[batch, 4, 4], &a_data).unwrap();
+    let b = Tensor::<f32>::from_shape_slice(&ctx, &[batch, 4, 4], &b_data).unwrap();
+    let c = a.matmul(&b, false, false).unwrap();
+
+    assert_eq!(c.dimensions(), &[batch, 4, 4]);
+    crate::assert_vec_relative_eq(
+        &c.to_vec().unwrap(),
+        &cpu_bmm(&a_data, &b_data, batch, 4, 4, 4),
+        1e-4,
+    );
+}
+
+#[test]
+fn test_small_matmul_transforms_points() {
+    let ctx = Context::try_default().unwrap();
+
+    // Two poses applied to the same homogeneous point.
+    #[rustfmt::skip]
+    let poses = [
+        1.0, 0.0, 0.0, 2.0,
+        0.0, 1.0, 0.0, 3.0,
+        0.0, 0.0, 1.0, 4.0,
+        0.0, 0.0, 0.0, 1.0,
+
+        0.0, -1.0, 0.0, 0.0,
+        1.0, 0.0, 0.0, 0.0,
+        0.0, 0.0, 1.0, 0.0,
+        0.0, 0.0, 0.0, 1.0,
+    ];
+    let poses = Tensor::<f32>::from_shape_slice(&ctx, &[2, 4, 4], &poses).unwrap();
+    let point = Tensor::<f32>::from_shape_slice(&ctx, &[1, 4, 1], &[1.0, 2.0, 3.0, 1.0]).unwrap();
+
+    let moved = poses.matmul(&point, false, false).unwrap();
+    assert_eq!(moved.dimensions(), &[2, 4, 1]);
+    assert_eq!(
+        moved.to_vec().unwrap(),
+        vec![3.0, 5.0, 7.0, 1.0, -2.0, 1.0, 3.0, 1.0]
+    );
+}
+
+#[test]
+fn test_small_matmul_transposed() {
+    let ctx = Context::try_default().unwrap();
+    let (batch, m, k, n) = (6, 3, 8, 5);
+
+    let a_data: Vec<f32> = (0..batch * m * k).map(|i| (i % 11) as f32 - 5.0).collect();
+    let b_data: Vec<f32> = (0..batch * k * n).map(|i| (i % 3) as f32).collect();
+    let a = Tensor::<f32>::from_shape_slice(&ctx, &[batch, m, k], &a_data).unwrap();
+    let expected = cpu_bmm(&a_data, &b_data, batch, m, k, n);
+
+    let at =
+        Tensor::<f32>::from_shape_slice(&ctx, &[batch, k, m], &transpose(&a_data, batch, m, k))
+            .unwrap();
+    let bt =
+        Tensor::<f32>::from_shape_slice(&ctx, &[batch, n, k], &transpose(&b_data, batch, k, n))
+            .unwrap();
+    let c = at.matmul(&bt, true, true).unwrap();
+    assert_eq!(c.dimensions(), &[batch, m, n]);
+    crate::assert_vec_relative_eq(&c.to_vec().unwrap(), &expected, 1e-4);
+
+    let c = a.matmul(&bt, false, true).unwrap();
+
+    assert_eq!(c.dimensions(), &[batch, m, n]);
+    crate::assert_vec_relative_eq(&c.to_vec().unwrap(), &expected, 1e-4);
+}
+
+#[test]
+fn test_small_matmul_matches_tiled() {
+    let ctx = Context::try_default().unwrap();
+
+    for size in [8, 9] {
+        let batch = 3;
+        let len = batch * size * size;
+        let a_data: Vec<f32> = (0..len).map(|i| (i % 13) as f32 * 0.25).collect();
+        let b_data: Vec<f32> = (0..len).map(|i| (i % 4) as f32 - 1.5).collect();
+
+        let a = Tensor::<f32>::from_shape_slice(&ctx, &[batch, size, size], &a_data).unwrap();
+        let b = Tensor::<f32>::from_shape_slice(&ctx, &[batch, size, size], &b_data).unwrap();
+        let c = a.matmul(&b, false, false).unwrap();
+
+        crate::assert_vec_relative_eq(
+            &c.to_vec().unwrap(),
+            &cpu_bmm(&a_data, &b_data, batch, size, size, size),
+            1e-4,
+        );
+    }
+}
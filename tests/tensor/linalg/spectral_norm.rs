@@ -0,0 +1,50 @@
+//! Tests for `Tensor::spectral_norm` operation.
+
+use xnn::{Context, Tensor};
+
+#[test]
+fn test_spectral_norm_diagonal() {
+    let ctx = Context::try_default().unwrap();
+    let w =
+        Tensor::<f32>::from_shape_slice(&ctx, &[2, 3], &[3.0, 0.0, 0.0, 0.0, 1.0, 0.0]).unwrap();
+    let u = Tensor::<f32>::from_slice(&ctx, &[1.0, 1.0]).unwrap();
+
+    let (weight, u, sigma) = w.spectral_norm(&u, 10).unwrap();
+    assert_eq!(weight.dimensions(), &[2, 3]);
+    assert_eq!(u.dimensions(), &[2]);
+    assert_eq!(sigma.dimensions(), &[1]);
+
+    crate::assert_vec_relative_eq(&sigma.to_vec().unwrap(), &[3.0], 1e-4);
+    crate::assert_vec_relative_eq(
+        &weight.to_vec().unwrap(),
+        &[1.0, 0.0, 0.0, 0.0, 1.0 / 3.0, 0.0],
+        1e-4,
+    );
+    crate::assert_vec_relative_eq(&u.to_vec().unwrap(), &[1.0, 0.0], 1e-4);
+}
+
+#[test]
+fn test_spectral_norm_converges_across_calls() {
+    let ctx = Context::try_default().unwrap();
+    let w = Tensor::<f32>::from_shape_slice(&ctx, &[2, 2], &[2.0, 1.0, 1.0, 2.0]).unwrap();
+    let mut u = Tensor::<f32>::from_slice(&ctx, &[1.0, 0.0]).unwrap();
+
+    let mut sigma = Vec::new();
+    for _ in 0..20 {
+        let (_, next, s) = w.spectral_norm(&u, 1).unwrap();
+        u = next;
+        sigma = s.to_vec().unwrap();
+    }
+    crate::assert_vec_relative_eq(&sigma, &[3.0], 1e-4);
+}
+
+#[test]
+fn test_spectral_norm_invalid() {
+    let ctx = Context::try_default().unwrap();
+    let w = Tensor::<f32>::from_shape_slice(&ctx, &[2, 2], &[1.0; 4]).unwrap();
+    let u = Tensor::<f32>::from_slice(&ctx, &[1.0, 0.0, 0.0]).unwrap();
+    let v = Tensor::<f32>::from_slice(&ctx, &[1.0, 0.0]).unwrap();
+    assert!(w.spectral_norm(&u, 1).is_err());
+    assert!(w.spectral_norm(&v, 0).is_err());
+    assert!(v.spectral_norm(&v, 1).is_err());
+}
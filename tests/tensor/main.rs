@@ -2,12 +2,27 @@
 
 mod constant;
 mod copy;
+mod detection;
+mod df64;
+mod empty;
 mod from_shape_slice;
 mod from_slice;
+mod from_texture;
+mod high_rank;
+mod index;
 mod linalg;
 mod math;
 mod nn;
+mod random;
+mod read_region;
 mod reduction;
+mod rl;
+mod scalar;
+mod series;
+mod shape;
+mod sorting;
+mod stop_flag;
+mod write_slice;
 
 use core::fmt::Debug;
 
@@ -0,0 +1,82 @@
+//! Tests for `Tensor::bias_dropout_residual` operation.
+
+#![allow(clippy::cast_precision_loss)]
+
+use approx::assert_relative_eq;
+use xnn::{Context, Tensor};
+
+#[test]
+fn test_bias_dropout_residual_no_drop() {
+    let ctx = Context::try_default().unwrap();
+    let x =
+        Tensor::<f32>::from_shape_slice(&ctx, &[2, 3], &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]).unwrap();
+    let bias = Tensor::<f32>::from_slice(&ctx, &[0.5, -0.5, 1.0]).unwrap();
+    let residual = Tensor::<f32>::from_shape_slice(&ctx, &[2, 3], &[10.0; 6]).unwrap();
+    let result = x.bias_dropout_residual(&bias, &residual, 0.0, 7).unwrap();
+    assert_eq!(result.dimensions(), &[2, 3]);
+    crate::assert_vec_relative_eq(
+        &result.to_vec().unwrap(),
+        &[11.5, 11.5, 14.0, 14.5, 14.5, 17.0],
+        1e-6,
+    );
+}
+
+#[test]
+fn test_bias_dropout_residual_mask() {
+    let ctx = Context::try_default().unwrap();
+    let n = 4096;
+    let p = 0.25;
+    let x = Tensor::<f32>::constant(&ctx, &[n], &[1.0]).unwrap();
+    let bias = Tensor::<f32>::from_slice(&ctx, &[0.5]).unwrap();
+    let residual = Tensor::<f32>::constant(&ctx, &[n], &[2.0]).unwrap();
+    let out = x
+        .bias_dropout_residual(&bias, &residual, p, 42)
+        .unwrap()
+        .to_vec()
+        .unwrap();
+
+    let kept_value = 1.5 / (1.0 - p) + 2.0;
+    let mut dropped = 0;
+    for &v in &out {
+        if (v - 2.0).abs() < 1e-6 {
+            dropped += 1;
+        } else {
+            assert_relative_eq!(v, kept_value, epsilon = 1e-5);
+        }
+    }
+
+    let rate = dropped as f32 / n as f32;
+    assert!((rate - p).abs() < 0.05, "drop rate {rate} too far from {p}");
+}
+
+#[test]
+fn test_bias_dropout_residual_seed() {
+    let ctx = Context::try_default().unwrap();
+    let x = Tensor::<f32>::constant(&ctx, &[256], &[1.0]).unwrap();
+    let bias = Tensor::<f32>::from_slice(&ctx, &[0.0]).unwrap();
+    let residual = Tensor::<f32>::from_slice(&ctx, &[0.0]).unwrap();
+
+    let a = x.bias_dropout_residual(&bias, &residual, 0.5, 1).unwrap();
+    let b = x.bias_dropout_residual(&bias, &residual, 0.5, 1).unwrap();
+    let c = x.bias_dropout_residual(&bias, &residual, 0.5, 2).unwrap();
+
+    assert_eq!(a.to_vec().unwrap(), b.to_vec().unwrap());
+    assert_ne!(a.to_vec().unwrap(), c.to_vec().unwrap());
+}
+
+#[test]
+fn test_bias_dropout_residual_invalid() {
+    let ctx = Context::try_default().unwrap();
+    let x = Tensor::<f32>::from_slice(&ctx, &[1.0, 2.0, 3.0]).unwrap();
+    let bias = Tensor::<f32>::from_slice(&ctx, &[1.0, 2.0]).unwrap();
+    let residual = Tensor::<f32>::from_slice(&ctx, &[1.0, 2.0, 3.0]).unwrap();
+    assert!(x.bias_dropout_residual(&bias, &residual, 0.1, 0).is_err());
+    assert!(
+        x.bias_dropout_residual(&residual, &residual, 1.0, 0)
+            .is_err()
+    );
+    assert!(
+        x.bias_dropout_residual(&residual, &residual, -0.1, 0)
+            .is_err()
+    );
+}
@@ -0,0 +1,177 @@
+//! Tests for `Tensor::conv1d` and `Tensor::causal_conv1d` operations.
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use xnn::{Context, Tensor};
+
+#[allow(clippy::too_many_arguments)]
+fn cpu_conv1d(
+    x: &[f32],
+    weight: &[f32],
+    bias: Option<&[f32]>,
+    [n, c, l]: [usize; 3],
+    [k, out_c]: [usize; 2],
+    stride: usize,
+    dilation: usize,
+    (before, after): (usize, usize),
+) -> Vec<f32> {
+    let out_l = (l + before + after - dilation * (k - 1) - 1) / stride + 1;
+    let mut out = Vec::with_capacity(n * out_c * out_l);
+    for b in 0..n {
+        for o in 0..out_c {
+            for t in 0..out_l {
+                let mut acc = bias.map_or(0.0, |bias| bias[o]);
+                for j in 0..k {
+                    let Some(s) = (t * stride + j * dilation).checked_sub(before) else {
+                        continue;
+                    };
+                    if s >= l {
+                        continue;
+                    }
+                    for i in 0..c {
+                        acc += x[(b * c + i) * l + s] * weight[(j * c + i) * out_c + o];
+                    }
+                }
+                out.push(acc);
+            }
+        }
+    }
+    out
+}
+
+#[test]
+fn test_conv1d_matches_cpu() {
+    let ctx = Context::try_default().unwrap();
+    let mut rng = StdRng::seed_from_u64(41);
+
+    for (dims, (k, out_c), stride, dilation, padding, biased) in [
+        ([2, 3, 17], (3, 4), 1, 1, (1, 1), true),
+        ([1, 5, 32], (2, 2), 1, 4, (4, 0), false),
+        ([3, 2, 20], (5, 3), 2, 2, (0, 3), true),
+        ([1, 1, 6], (1, 1), 3, 1, (0, 0), false),
+    ] {
+        let [_, c, l] = dims;
+        let len = dims.iter().product();
+        let x: Vec<f32> = (0..len).map(|_| rng.random_range(-1.0..1.0)).collect();
+        let weight: Vec<f32> = (0..k * c * out_c)
+            .map(|_| rng.random_range(-1.0..1.0))
+            .collect();
+        let bias: Vec<f32> = (0..out_c).map(|_| rng.random_range(-1.0..1.0)).collect();
+
+        let tx = Tensor::<f32>::from_shape_slice(&ctx, &dims, &x).unwrap();
+        let tw = Tensor::<f32>::from_shape_slice(&ctx, &[k, c, out_c], &weight).unwrap();
+        let tb = Tensor::<f32>::from_slice(&ctx, &bias).unwrap();
+
+        let y = tx
+            .conv1d(&tw, biased.then_some(&tb), stride, dilation, padding)
+            .unwrap();
+
+        let out_l = (l + padding.0 + padding.1 - dilation * (k - 1) - 1) / stride + 1;
+        assert_eq!(y.dimensions(), &[dims[0], out_c, out_l]);
+        crate::assert_vec_relative_eq(
+            &y.to_vec().unwrap(),
+            &cpu_conv1d(
+                &x,
+                &weight,
+                biased.then_some(&bias[..]),
+                dims,
+                [k, out_c],
+                stride,
+                dilation,
+                padding,
+            ),
+            1e-5,
+        );
+    }
+}
+
+#[test]
+fn test_conv1d_strided_input() {
+    let ctx = Context::try_default().unwrap();
+    // `[n, l, c]` transposed to `[n, c, l]`; the input is materialized before the kernel.
+    let x = Tensor::<f32>::from_shape_slice(&ctx, &[1, 3, 2], &[1.0, 10.0, 2.0, 20.0, 3.0, 30.0])
+        .unwrap()
+        .permute(&[0, 2, 1])
+        .unwrap();
+    let weight = Tensor::<f32>::from_shape_slice(&ctx, &[2, 2, 1], &[1.0, 0.0, 0.0, 1.0]).unwrap();
+
+    // y[t] = x[0, t] + x[1, t + 1].
+    let y = x.conv1d(&weight, None, 1, 1, (0, 0)).unwrap();
+    assert_eq!(y.dimensions(), &[1, 1, 2]);
+    crate::assert_vec_relative_eq(&y.to_vec().unwrap(), &[21.0, 32.0], 1e-6);
+}
+
+#[test]
+fn test_causal_conv1d() {
+    let ctx = Context::try_default().unwrap();
+    let x =
+        Tensor::<f32>::from_shape_slice(&ctx, &[1, 1, 6], &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]).unwrap();
+    let weight = Tensor::<f32>::from_shape_slice(&ctx, &[3, 1, 1], &[1.0, 10.0, 100.0]).unwrap();
+    let bias = Tensor::<f32>::from_slice(&ctx, &[0.5]).unwrap();
+
+    // y[t] = x[t - 4] + 10·x[t - 2] + 100·x[t] + 0.5, with zeros before the sequence.
+    let y = x.causal_conv1d(&weight, Some(&bias), 2).unwrap();
+    assert_eq!(y.dimensions(), &[1, 1, 6]);
+    crate::assert_vec_relative_eq(
+        &y.to_vec().unwrap(),
+        &[100.5, 200.5, 310.5, 420.5, 531.5, 642.5],
+        1e-6,
+    );
+}
+
+#[test]
+fn test_causal_conv1d_no_future_leak() {
+    let ctx = Context::try_default().unwrap();
+    let mut rng = StdRng::seed_from_u64(42);
+    let (c, l, cut) = (3, 24, 10);
+
+    let x: Vec<f32> = (0..c * l).map(|_| rng.random_range(-1.0..1.0)).collect();
+    // Same sequence with every step from `cut` on shifted.
+    let mut perturbed = x.clone();
+    for i in 0..c {
+        for value in &mut perturbed[i * l + cut..(i + 1) * l] {
+            *value += 1.0;
+        }
+    }
+    let weight: Vec<f32> = (0..2 * c * 2)
+        .map(|_| rng.random_range(-1.0..1.0))
+        .collect();
+
+    let tw = Tensor::<f32>::from_shape_slice(&ctx, &[2, c, 2], &weight).unwrap();
+    let run = |x: &[f32]| {
+        Tensor::<f32>::from_shape_slice(&ctx, &[1, c, l], x)
+            .unwrap()
+            .causal_conv1d(&tw, None, 3)
+            .unwrap()
+            .to_vec()
+            .unwrap()
+    };
+
+    let (y, z) = (run(&x), run(&perturbed));
+    for o in 0..2 {
+        assert_eq!(y[o * l..o * l + cut], z[o * l..o * l + cut]);
+        assert!((y[o * l + cut] - z[o * l + cut]).abs() > 1e-6);
+    }
+}
+
+#[test]
+fn test_conv1d_invalid() {
+    let ctx = Context::try_default().unwrap();
+    let x = Tensor::<f32>::constant(&ctx, &[1, 3, 8], &[0.0]).unwrap();
+    let weight = Tensor::<f32>::constant(&ctx, &[3, 3, 2], &[0.0]).unwrap();
+    let wrong_channels = Tensor::<f32>::constant(&ctx, &[3, 2, 2], &[0.0]).unwrap();
+    let empty = Tensor::<f32>::constant(&ctx, &[0, 3, 2], &[0.0]).unwrap();
+    let bias = Tensor::<f32>::constant(&ctx, &[3], &[0.0]).unwrap();
+    let flat = Tensor::<f32>::constant(&ctx, &[3, 8], &[0.0]).unwrap();
+
+    assert!(flat.conv1d(&weight, None, 1, 1, (0, 0)).is_err());
+    assert!(x.conv1d(&wrong_channels, None, 1, 1, (0, 0)).is_err());
+    assert!(x.conv1d(&empty, None, 1, 1, (0, 0)).is_err());
+    assert!(x.conv1d(&weight, Some(&bias), 1, 1, (0, 0)).is_err());
+    assert!(x.conv1d(&weight, None, 0, 1, (0, 0)).is_err());
+    assert!(x.conv1d(&weight, None, 1, 0, (0, 0)).is_err());
+    assert!(x.conv1d(&weight, None, 1, 4, (0, 0)).is_err());
+    assert!(x.conv1d(&weight, None, 1, 4, (1, 0)).is_ok());
+    assert!(x.causal_conv1d(&weight, None, 0).is_err());
+    assert!(x.causal_conv1d(&weight, None, 100).is_ok());
+}
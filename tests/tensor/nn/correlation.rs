@@ -0,0 +1,95 @@
+//! Tests for `Tensor::correlation` operation.
+
+#![allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_possible_wrap,
+    clippy::cast_precision_loss,
+    clippy::cast_sign_loss
+)]
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use xnn::{Context, Tensor};
+
+fn cpu_correlation(a: &[f32], b: &[f32], [n, c, h, w]: [usize; 4], d: usize) -> Vec<f32> {
+    let span = 2 * d + 1;
+    let mut out = vec![0.0; n * span * span * h * w];
+    for k in 0..n {
+        for dy in 0..span {
+            for dx in 0..span {
+                for y in 0..h {
+                    for x in 0..w {
+                        let (sy, sx) = (y as i64 + dy as i64 - d as i64, x as i64 + dx as i64);
+                        let sx = sx - d as i64;
+                        if sy < 0 || sx < 0 || sy >= h as i64 || sx >= w as i64 {
+                            continue;
+                        }
+                        let sum: f32 = (0..c)
+                            .map(|ch| {
+                                let base = (k * c + ch) * h * w;
+                                a[base + y * w + x] * b[base + sy as usize * w + sx as usize]
+                            })
+                            .sum();
+                        let channel = (k * span + dy) * span + dx;
+                        out[(channel * h + y) * w + x] = sum / c as f32;
+                    }
+                }
+            }
+        }
+    }
+    out
+}
+
+#[test]
+fn test_correlation_matches_cpu() {
+    let ctx = Context::try_default().unwrap();
+    let mut rng = StdRng::seed_from_u64(9);
+
+    for (dims, d) in [([2, 3, 19, 21], 3), ([1, 5, 8, 35], 0), ([1, 2, 6, 5], 15)] {
+        let len = dims.iter().product();
+        let a: Vec<f32> = (0..len).map(|_| rng.random_range(-1.0..1.0)).collect();
+        let b: Vec<f32> = (0..len).map(|_| rng.random_range(-1.0..1.0)).collect();
+
+        let ta = Tensor::<f32>::from_shape_slice(&ctx, &dims, &a).unwrap();
+        let tb = Tensor::<f32>::from_shape_slice(&ctx, &dims, &b).unwrap();
+        let y = ta.correlation(&tb, d).unwrap();
+
+        let span = 2 * d + 1;
+        assert_eq!(y.dimensions(), &[dims[0], span * span, dims[2], dims[3]]);
+        crate::assert_vec_relative_eq(
+            &y.to_vec().unwrap(),
+            &cpu_correlation(&a, &b, dims, d),
+            1e-5,
+        );
+    }
+}
+
+#[test]
+fn test_correlation_shift() {
+    let ctx = Context::try_default().unwrap();
+    let mut data = vec![0.0; 16];
+    data[5] = 2.0;
+    let a = Tensor::<f32>::from_shape_slice(&ctx, &[1, 1, 4, 4], &data).unwrap();
+    data[5] = 0.0;
+    data[6] = 3.0;
+    let b = Tensor::<f32>::from_shape_slice(&ctx, &[1, 1, 4, 4], &data).unwrap();
+
+    // The feature moves one pixel right: displacement (dy, dx) = (0, 1) at pixel (1, 1).
+    let y = a.correlation(&b, 1).unwrap().to_vec().unwrap();
+    let peak = y.iter().position(|&v| v != 0.0).unwrap();
+    assert_eq!(peak, 5 * 16 + 5);
+    crate::assert_vec_relative_eq(&y[peak..=peak], &[6.0], 1e-6);
+    assert_eq!(y.iter().filter(|&&v| v != 0.0).count(), 1);
+}
+
+#[test]
+fn test_correlation_invalid() {
+    let ctx = Context::try_default().unwrap();
+    let a = Tensor::<f32>::constant(&ctx, &[1, 2, 4, 4], &[0.0]).unwrap();
+    let b = Tensor::<f32>::constant(&ctx, &[1, 2, 4, 5], &[0.0]).unwrap();
+    let flat = Tensor::<f32>::constant(&ctx, &[2, 4, 4], &[0.0]).unwrap();
+
+    assert!(a.correlation(&b, 1).is_err());
+    assert!(flat.correlation(&flat, 1).is_err());
+    assert!(a.correlation(&a, 16).is_err());
+}
@@ -0,0 +1,135 @@
+//! Tests for `Tensor::cross_entropy` and `Tensor::cross_entropy_chunked` operations.
+
+#![allow(clippy::cast_precision_loss)]
+
+use xnn::{Context, Tensor};
+
+/// Returns per-row losses and the gradient of their sum.
+fn cross_entropy_ref(logits: &[f32], targets: &[u32], classes: usize) -> (Vec<f32>, Vec<f32>) {
+    let mut loss = Vec::new();
+    let mut grad = Vec::new();
+    for (row, &target) in logits.chunks(classes).zip(targets) {
+        let max = row.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+        let sum: f32 = row.iter().map(|x| (x - max).exp()).sum();
+        loss.push(sum.ln() + max - row[target as usize]);
+        for (j, x) in row.iter().enumerate() {
+            let p = (x - max).exp() / sum;
+            grad.push(if j == target as usize { p - 1.0 } else { p });
+        }
+    }
+    (loss, grad)
+}
+
+fn logits(rows: usize, classes: usize) -> Vec<f32> {
+    (0..rows * classes)
+        .map(|i| ((i * 37) % 23) as f32 * 0.25 - 2.0)
+        .collect()
+}
+
+#[test]
+fn test_cross_entropy_basic() {
+    let ctx = Context::try_default().unwrap();
+    let data = logits(3, 5);
+    let targets = [0u32, 4, 2];
+    let x = Tensor::<f32>::from_shape_slice(&ctx, &[3, 5], &data).unwrap();
+    let t = Tensor::<u32>::from_slice(&ctx, &targets).unwrap();
+
+    let (loss, grad) = x.cross_entropy(&t).unwrap();
+    let (loss_ref, grad_ref) = cross_entropy_ref(&data, &targets, 5);
+
+    assert_eq!(loss.dimensions(), &[3]);
+    assert_eq!(grad.dimensions(), &[3, 5]);
+    crate::assert_vec_relative_eq(&loss.to_vec().unwrap(), &loss_ref, 1e-5);
+    crate::assert_vec_relative_eq(&grad.to_vec().unwrap(), &grad_ref, 1e-5);
+}
+
+#[test]
+fn test_cross_entropy_large_logits() {
+    let ctx = Context::try_default().unwrap();
+    let data = [1000.0f32, 0.0, -1000.0, 50.0, 51.0, 52.0];
+    let targets = [0u32, 1];
+    let x = Tensor::<f32>::from_shape_slice(&ctx, &[2, 3], &data).unwrap();
+    let t = Tensor::<u32>::from_slice(&ctx, &targets).unwrap();
+
+    let (loss, grad) = x.cross_entropy(&t).unwrap();
+    let (loss_ref, grad_ref) = cross_entropy_ref(&data, &targets, 3);
+
+    crate::assert_vec_relative_eq(&loss.to_vec().unwrap(), &loss_ref, 1e-5);
+    crate::assert_vec_relative_eq(&grad.to_vec().unwrap(), &grad_ref, 1e-5);
+}
+
+#[test]
+fn test_cross_entropy_chunked_matches_full() {
+    let ctx = Context::try_default().unwrap();
+    let (rows, classes) = (4, 1000);
+    let data = logits(rows, classes);
+    let targets = [3u32, 999, 400, 650];
+    let splits = [300, 256, 444];
+
+    let chunks: Vec<Tensor<f32>> = {
+        let mut start = 0;
+        splits
+            .iter()
+            .map(|&cols| {
+                let chunk: Vec<f32> = data
+                    .chunks(classes)
+                    .flat_map(|row| row[start..start + cols].to_vec())
+                    .collect();
+                start += cols;
+                Tensor::<f32>::from_shape_slice(&ctx, &[rows, cols], &chunk).unwrap()
+            })
+            .collect()
+    };
+    let t = Tensor::<u32>::from_slice(&ctx, &targets).unwrap();
+
+    let (loss, grads) = Tensor::cross_entropy_chunked(&chunks, &t).unwrap();
+    let (loss_ref, grad_ref) = cross_entropy_ref(&data, &targets, classes);
+
+    crate::assert_vec_relative_eq(&loss.to_vec().unwrap(), &loss_ref, 1e-4);
+
+    let mut start = 0;
+    for (grad, &cols) in grads.iter().zip(&splits) {
+        assert_eq!(grad.dimensions(), &[rows, cols]);
+        let expected: Vec<f32> = grad_ref
+            .chunks(classes)
+            .flat_map(|row| row[start..start + cols].to_vec())
+            .collect();
+        crate::assert_vec_relative_eq(&grad.to_vec().unwrap(), &expected, 1e-5);
+        start += cols;
+    }
+}
+
+#[test]
+fn test_cross_entropy_target_out_of_bounds() {
+    let ctx = Context::try_default().unwrap();
+    let data = [1.0f32, 2.0, 3.0];
+    let x = Tensor::<f32>::from_shape_slice(&ctx, &[1, 3], &data).unwrap();
+    let t = Tensor::<u32>::from_slice(&ctx, &[7]).unwrap();
+
+    let (loss, grad) = x.cross_entropy(&t).unwrap();
+    let sum: f32 = data.iter().map(|x| (x - 3.0).exp()).sum();
+    let softmax: Vec<f32> = data.iter().map(|x| (x - 3.0).exp() / sum).collect();
+
+    crate::assert_vec_relative_eq(&loss.to_vec().unwrap(), &[sum.ln() + 3.0], 1e-5);
+    crate::assert_vec_relative_eq(&grad.to_vec().unwrap(), &softmax, 1e-5);
+}
+
+#[test]
+fn test_cross_entropy_invalid() {
+    let ctx = Context::try_default().unwrap();
+    let x = Tensor::<f32>::from_shape_slice(&ctx, &[2, 3], &[0.0; 6]).unwrap();
+    let y = Tensor::<f32>::from_shape_slice(&ctx, &[3, 3], &[0.0; 9]).unwrap();
+    let v = Tensor::<f32>::from_slice(&ctx, &[0.0; 3]).unwrap();
+    let t = Tensor::<u32>::from_slice(&ctx, &[0, 1]).unwrap();
+    let t3 = Tensor::<u32>::from_slice(&ctx, &[0, 1, 2]).unwrap();
+
+    assert!(x.cross_entropy(&t3).is_err());
+    assert!(v.cross_entropy(&t).is_err());
+    assert!(Tensor::<f32>::cross_entropy_chunked(&[], &t).is_err());
+    assert!(Tensor::cross_entropy_chunked(&[x, y], &t).is_err());
+
+    let empty = Tensor::<f32>::constant(&ctx, &[2, 0], &[0.0]).unwrap();
+    let also_empty = Tensor::<f32>::constant(&ctx, &[2, 0], &[0.0]).unwrap();
+    assert!(empty.cross_entropy(&t).is_err());
+    assert!(Tensor::cross_entropy_chunked(&[empty, also_empty], &t).is_err());
+}
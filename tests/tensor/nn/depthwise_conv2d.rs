@@ -0,0 +1,148 @@
+//! Tests for `Tensor::depthwise_conv2d` operation.
+
+#![allow(
+    clippy::cast_possible_wrap,
+    clippy::cast_sign_loss,
+    clippy::too_many_arguments
+)]
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use xnn::{Context, MemoryFormat, Tensor};
+
+fn cpu_depthwise(
+    x: &[f32],
+    weight: &[f32],
+    bias: Option<&[f32]>,
+    [n, h, w, c]: [usize; 4],
+    (kh, kw): (usize, usize),
+    (sh, sw): (usize, usize),
+    (ph, pw): (usize, usize),
+) -> Vec<f32> {
+    let out_h = (h + 2 * ph - kh) / sh + 1;
+    let out_w = (w + 2 * pw - kw) / sw + 1;
+    let mut out = Vec::with_capacity(n * out_h * out_w * c);
+    for b in 0..n {
+        for oy in 0..out_h {
+            for ox in 0..out_w {
+                for ch in 0..c {
+                    let mut acc = bias.map_or(0.0, |bias| bias[ch]);
+                    for ky in 0..kh {
+                        for kx in 0..kw {
+                            let (Some(iy), Some(ix)) = (
+                                (oy * sh + ky).checked_sub(ph),
+                                (ox * sw + kx).checked_sub(pw),
+                            ) else {
+                                continue;
+                            };
+                            if iy >= h || ix >= w {
+                                continue;
+                            }
+                            let src = ((b * h + iy) * w + ix) * c + ch;
+                            acc += x[src] * weight[(ky * kw + kx) * c + ch];
+                        }
+                    }
+                    out.push(acc);
+                }
+            }
+        }
+    }
+    out
+}
+
+#[test]
+fn test_depthwise_conv2d_matches_cpu() {
+    let ctx = Context::try_default().unwrap();
+    let mut rng = StdRng::seed_from_u64(38);
+
+    for (dims, kernel, stride, padding, biased) in [
+        ([2, 9, 11, 5], (3, 3), (1, 1), (1, 1), true),
+        ([1, 16, 13, 32], (3, 3), (2, 2), (1, 1), false),
+        ([3, 7, 6, 3], (5, 3), (1, 2), (2, 0), true),
+        ([1, 4, 4, 8], (1, 1), (1, 1), (0, 0), false),
+    ] {
+        let [_, _, _, c] = dims;
+        let len = dims.iter().product();
+        let x: Vec<f32> = (0..len).map(|_| rng.random_range(-1.0..1.0)).collect();
+        let weight: Vec<f32> = (0..kernel.0 * kernel.1 * c)
+            .map(|_| rng.random_range(-1.0..1.0))
+            .collect();
+        let bias: Vec<f32> = (0..c).map(|_| rng.random_range(-1.0..1.0)).collect();
+
+        // `x` is generated in NHWC order; the tensors are its logical NCHW view.
+        let nhwc = Tensor::<f32>::from_shape_slice(&ctx, &dims, &x).unwrap();
+        let channels_last = nhwc.permute(&[0, 3, 1, 2]).unwrap();
+        let tw = Tensor::<f32>::from_shape_slice(&ctx, &[kernel.0, kernel.1, c], &weight).unwrap();
+        let tb = Tensor::<f32>::from_slice(&ctx, &bias).unwrap();
+
+        let out_h = (dims[1] + 2 * padding.0 - kernel.0) / stride.0 + 1;
+        let out_w = (dims[2] + 2 * padding.1 - kernel.1) / stride.1 + 1;
+        let expected = cpu_depthwise(
+            &x,
+            &weight,
+            biased.then_some(&bias[..]),
+            dims,
+            kernel,
+            stride,
+            padding,
+        );
+
+        for format in [MemoryFormat::ChannelsLast, MemoryFormat::ChannelsFirst] {
+            let tx = channels_last.to_memory_format(format).unwrap();
+            let y = tx
+                .depthwise_conv2d(&tw, biased.then_some(&tb), stride, padding)
+                .unwrap();
+
+            assert_eq!(y.dimensions(), &[dims[0], c, out_h, out_w]);
+            assert_eq!(y.memory_format(), tx.memory_format());
+            crate::assert_vec_relative_eq(
+                &y.permute(&[0, 2, 3, 1]).unwrap().to_vec().unwrap(),
+                &expected,
+                1e-5,
+            );
+        }
+    }
+}
+
+#[test]
+fn test_depthwise_conv2d_channels_independent() {
+    let ctx = Context::try_default().unwrap();
+    // Two channels of ones; the second filter is zero, so only the first channel sums.
+    let x = Tensor::<f32>::constant(&ctx, &[1, 2, 3, 3], &[1.0]).unwrap();
+    let weight = Tensor::<f32>::from_shape_slice(&ctx, &[3, 3, 2], &[1.0, 0.0].repeat(9)).unwrap();
+
+    let y = x.depthwise_conv2d(&weight, None, (1, 1), (1, 1)).unwrap();
+    assert_eq!(y.dimensions(), &[1, 2, 3, 3]);
+    assert_eq!(y.memory_format(), MemoryFormat::ChannelsFirst);
+
+    let mut expected = vec![4.0, 6.0, 4.0, 6.0, 9.0, 6.0, 4.0, 6.0, 4.0];
+    expected.extend([0.0; 9]);
+    crate::assert_vec_relative_eq(&y.to_vec().unwrap(), &expected, 1e-6);
+}
+
+#[test]
+fn test_depthwise_conv2d_invalid() {
+    let ctx = Context::try_default().unwrap();
+    let x = Tensor::<f32>::constant(&ctx, &[1, 3, 4, 4], &[0.0]).unwrap();
+    let weight = Tensor::<f32>::constant(&ctx, &[3, 3, 3], &[0.0]).unwrap();
+    let wrong_channels = Tensor::<f32>::constant(&ctx, &[3, 3, 2], &[0.0]).unwrap();
+    let large = Tensor::<f32>::constant(&ctx, &[5, 5, 3], &[0.0]).unwrap();
+    let bias = Tensor::<f32>::constant(&ctx, &[2], &[0.0]).unwrap();
+    let flat = Tensor::<f32>::constant(&ctx, &[3, 4, 4], &[0.0]).unwrap();
+
+    assert!(
+        flat.depthwise_conv2d(&weight, None, (1, 1), (0, 0))
+            .is_err()
+    );
+    assert!(
+        x.depthwise_conv2d(&wrong_channels, None, (1, 1), (0, 0))
+            .is_err()
+    );
+    assert!(
+        x.depthwise_conv2d(&weight, Some(&bias), (1, 1), (0, 0))
+            .is_err()
+    );
+    assert!(x.depthwise_conv2d(&weight, None, (0, 1), (0, 0)).is_err());
+    assert!(x.depthwise_conv2d(&large, None, (1, 1), (0, 0)).is_err());
+    assert!(x.depthwise_conv2d(&large, None, (1, 1), (1, 1)).is_ok());
+}
@@ -0,0 +1,45 @@
+//! Tests for `Tensor::diffusion_step` operation.
+
+use xnn::{Context, Tensor};
+
+#[test]
+fn test_diffusion_step_without_noise() {
+    let ctx = Context::try_default().unwrap();
+    let x = Tensor::<f32>::from_shape_slice(&ctx, &[2, 2], &[1.0, 2.0, 3.0, 4.0]).unwrap();
+    let eps = Tensor::<f32>::from_shape_slice(&ctx, &[2, 2], &[0.5, 0.0, -1.0, 2.0]).unwrap();
+    let result = x.diffusion_step(&eps, 2.0, -1.0, 0.0, 0).unwrap();
+    assert_eq!(result.dimensions(), &[2, 2]);
+    assert_eq!(result.to_vec().unwrap(), vec![1.5, 4.0, 7.0, 6.0]);
+}
+
+#[test]
+fn test_diffusion_step_noise_is_seeded() {
+    let ctx = Context::try_default().unwrap();
+    let x = Tensor::<f32>::constant(&ctx, &[1024], &[0.0]).unwrap();
+    let a = x
+        .diffusion_step(&x, 1.0, 1.0, 1.0, 3)
+        .unwrap()
+        .to_vec()
+        .unwrap();
+    let b = x
+        .diffusion_step(&x, 1.0, 1.0, 1.0, 3)
+        .unwrap()
+        .to_vec()
+        .unwrap();
+    let c = x
+        .diffusion_step(&x, 1.0, 1.0, 1.0, 4)
+        .unwrap()
+        .to_vec()
+        .unwrap();
+    assert_eq!(a, b);
+    assert_ne!(a, c);
+    assert!(a.iter().all(|v| v.is_finite()));
+}
+
+#[test]
+fn test_diffusion_step_invalid_shape() {
+    let ctx = Context::try_default().unwrap();
+    let x = Tensor::<f32>::from_slice(&ctx, &[1.0, 2.0]).unwrap();
+    let eps = Tensor::<f32>::from_shape_slice(&ctx, &[1, 2], &[1.0, 2.0]).unwrap();
+    assert!(x.diffusion_step(&eps, 1.0, 1.0, 0.0, 0).is_err());
+}
@@ -0,0 +1,90 @@
+//! Tests for `Tensor::embedding_bag` operation.
+
+use xnn::{BagMode, Context, Tensor};
+
+fn table(ctx: &Context) -> Tensor<f32> {
+    Tensor::<f32>::from_shape_slice(ctx, &[4, 2], &[1.0, 10.0, 2.0, 20.0, 3.0, 30.0, 4.0, 40.0])
+        .unwrap()
+}
+
+#[test]
+fn test_embedding_bag_sum() {
+    let ctx = Context::try_default().unwrap();
+    let indices = Tensor::<u32>::from_slice(&ctx, &[0, 2, 1, 3, 3, 0]).unwrap();
+    let offsets = Tensor::<u32>::from_slice(&ctx, &[0, 2, 3]).unwrap();
+
+    let y = table(&ctx)
+        .embedding_bag(&indices, &offsets, BagMode::Sum)
+        .unwrap();
+    assert_eq!(y.dimensions(), &[3, 2]);
+    assert_eq!(y.to_vec().unwrap(), vec![4.0, 40.0, 2.0, 20.0, 9.0, 90.0]);
+}
+
+#[test]
+fn test_embedding_bag_mean() {
+    let ctx = Context::try_default().unwrap();
+    let indices = Tensor::<u32>::from_slice(&ctx, &[0, 2, 1, 3, 3]).unwrap();
+    let offsets = Tensor::<u32>::from_slice(&ctx, &[0, 2, 2]).unwrap();
+
+    let y = table(&ctx)
+        .embedding_bag(&indices, &offsets, BagMode::Mean)
+        .unwrap();
+    assert_eq!(
+        y.to_vec().unwrap(),
+        vec![2.0, 20.0, 0.0, 0.0, 10.0 / 3.0, 100.0 / 3.0]
+    );
+}
+
+#[test]
+fn test_embedding_bag_skips_out_of_bounds() {
+    let ctx = Context::try_default().unwrap();
+    let indices = Tensor::<u32>::from_slice(&ctx, &[1, 7, 2]).unwrap();
+    let offsets = Tensor::<u32>::from_slice(&ctx, &[0]).unwrap();
+
+    let y = table(&ctx)
+        .embedding_bag(&indices, &offsets, BagMode::Sum)
+        .unwrap();
+    assert_eq!(y.to_vec().unwrap(), vec![5.0, 50.0]);
+}
+
+#[test]
+fn test_embedding_bag_strided_table() {
+    let ctx = Context::try_default().unwrap();
+    let table = table(&ctx)
+        .transpose(0, 1)
+        .unwrap()
+        .transpose(0, 1)
+        .unwrap();
+    let table = table.narrow(1, 1, 1).unwrap();
+    let indices = Tensor::<u32>::from_slice(&ctx, &[3, 0]).unwrap();
+    let offsets = Tensor::<u32>::from_slice(&ctx, &[0, 1]).unwrap();
+
+    let y = table
+        .embedding_bag(&indices, &offsets, BagMode::Sum)
+        .unwrap();
+    assert_eq!(y.to_vec().unwrap(), vec![40.0, 10.0]);
+}
+
+#[test]
+fn test_embedding_bag_invalid() {
+    let ctx = Context::try_default().unwrap();
+    let indices = Tensor::<u32>::from_slice(&ctx, &[0, 1]).unwrap();
+    let offsets = Tensor::<u32>::from_slice(&ctx, &[0]).unwrap();
+    let grid = Tensor::<u32>::constant(&ctx, &[1, 2], &[0]).unwrap();
+
+    let flat = table(&ctx).reshape(&[8]).unwrap();
+    assert!(
+        flat.embedding_bag(&indices, &offsets, BagMode::Sum)
+            .is_err()
+    );
+    assert!(
+        table(&ctx)
+            .embedding_bag(&grid, &offsets, BagMode::Sum)
+            .is_err()
+    );
+    assert!(
+        table(&ctx)
+            .embedding_bag(&indices, &grid, BagMode::Sum)
+            .is_err()
+    );
+}
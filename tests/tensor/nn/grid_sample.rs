@@ -0,0 +1,139 @@
+//! Tests for `Tensor::grid_sample` operation.
+
+#![allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_possible_wrap,
+    clippy::cast_precision_loss,
+    clippy::cast_sign_loss
+)]
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use xnn::{Context, Interpolation, Tensor};
+
+fn cpu_grid_sample(
+    x: &[f32],
+    [n, c, h, w]: [usize; 4],
+    grid: &[f32],
+    (oh, ow): (usize, usize),
+    mode: Interpolation,
+) -> Vec<f32> {
+    let texel = |base: usize, px: i64, py: i64| {
+        if px < 0 || py < 0 || px >= w as i64 || py >= h as i64 {
+            0.0
+        } else {
+            x[base + py as usize * w + px as usize]
+        }
+    };
+
+    let mut out = Vec::new();
+    for b in 0..n {
+        for ch in 0..c {
+            let base = (b * c + ch) * h * w;
+            for p in 0..oh * ow {
+                let g = &grid[(b * oh * ow + p) * 2..][..2];
+                let sx = ((g[0] + 1.0) * w as f32 - 1.0) * 0.5;
+                let sy = ((g[1] + 1.0) * h as f32 - 1.0) * 0.5;
+                out.push(match mode {
+                    Interpolation::Nearest => texel(
+                        base,
+                        sx.round_ties_even() as i64,
+                        sy.round_ties_even() as i64,
+                    ),
+                    Interpolation::Bilinear => {
+                        let (x0, y0) = (sx.floor() as i64, sy.floor() as i64);
+                        let (lx, ly) = (sx - sx.floor(), sy - sy.floor());
+                        let top = texel(base, x0, y0) * (1.0 - lx) + texel(base, x0 + 1, y0) * lx;
+                        let bottom =
+                            texel(base, x0, y0 + 1) * (1.0 - lx) + texel(base, x0 + 1, y0 + 1) * lx;
+                        top * (1.0 - ly) + bottom * ly
+                    }
+                });
+            }
+        }
+    }
+    out
+}
+
+/// Grid sampling every input pixel center of an `h × w` image.
+fn identity_grid(n: usize, h: usize, w: usize) -> Vec<f32> {
+    let mut grid = Vec::new();
+    for _ in 0..n {
+        for i in 0..h {
+            for j in 0..w {
+                grid.push((2 * j + 1) as f32 / w as f32 - 1.0);
+                grid.push((2 * i + 1) as f32 / h as f32 - 1.0);
+            }
+        }
+    }
+    grid
+}
+
+#[test]
+fn test_grid_sample_identity() {
+    let ctx = Context::try_default().unwrap();
+    let data: Vec<f32> = (0..2 * 3 * 4 * 5).map(|i| i as f32).collect();
+    let x = Tensor::<f32>::from_shape_slice(&ctx, &[2, 3, 4, 5], &data).unwrap();
+    let grid =
+        Tensor::<f32>::from_shape_slice(&ctx, &[2, 4, 5, 2], &identity_grid(2, 4, 5)).unwrap();
+
+    for mode in [Interpolation::Bilinear, Interpolation::Nearest] {
+        let y = x.grid_sample(&grid, mode).unwrap();
+        assert_eq!(y.dimensions(), &[2, 3, 4, 5]);
+        crate::assert_vec_relative_eq(&y.to_vec().unwrap(), &data, 1e-5);
+    }
+}
+
+#[test]
+fn test_grid_sample_matches_cpu() {
+    let ctx = Context::try_default().unwrap();
+    let mut rng = StdRng::seed_from_u64(5);
+    let dims = [2, 3, 6, 7];
+    let data: Vec<f32> = (0..dims.iter().product::<usize>())
+        .map(|_| rng.random_range(-1.0..1.0))
+        .collect();
+    let grid: Vec<f32> = (0..2 * 4 * 9 * 2)
+        .map(|_| rng.random_range(-1.3..1.3))
+        .collect();
+
+    let x = Tensor::<f32>::from_shape_slice(&ctx, &dims, &data).unwrap();
+    let g = Tensor::<f32>::from_shape_slice(&ctx, &[2, 4, 9, 2], &grid).unwrap();
+
+    for mode in [Interpolation::Bilinear, Interpolation::Nearest] {
+        let y = x.grid_sample(&g, mode).unwrap();
+        assert_eq!(y.dimensions(), &[2, 3, 4, 9]);
+        let expected = cpu_grid_sample(&data, dims, &grid, (4, 9), mode);
+        crate::assert_vec_relative_eq(&y.to_vec().unwrap(), &expected, 1e-5);
+    }
+}
+
+#[test]
+fn test_grid_sample_shift() {
+    let ctx = Context::try_default().unwrap();
+    let x = Tensor::<f32>::from_shape_slice(&ctx, &[1, 1, 1, 4], &[1.0, 2.0, 3.0, 4.0]).unwrap();
+    // Half a pixel to the right of each center; the last sample blends with zero padding.
+    let grid: Vec<f32> = (0..4)
+        .flat_map(|j| [(2 * j + 2) as f32 / 4.0 - 1.0, 0.0])
+        .collect();
+    let g = Tensor::<f32>::from_shape_slice(&ctx, &[1, 1, 4, 2], &grid).unwrap();
+
+    let y = x.grid_sample(&g, Interpolation::Bilinear).unwrap();
+    crate::assert_vec_relative_eq(&y.to_vec().unwrap(), &[1.5, 2.5, 3.5, 2.0], 1e-6);
+}
+
+#[test]
+fn test_grid_sample_invalid() {
+    let ctx = Context::try_default().unwrap();
+    let x = Tensor::<f32>::constant(&ctx, &[1, 1, 4, 4], &[0.0]).unwrap();
+    let flat = Tensor::<f32>::constant(&ctx, &[4, 4], &[0.0]).unwrap();
+    let grid = Tensor::<f32>::constant(&ctx, &[1, 2, 2, 2], &[0.0]).unwrap();
+    let wrong_batch = Tensor::<f32>::constant(&ctx, &[2, 2, 2, 2], &[0.0]).unwrap();
+    let wrong_last = Tensor::<f32>::constant(&ctx, &[1, 2, 2, 3], &[0.0]).unwrap();
+
+    assert!(flat.grid_sample(&grid, Interpolation::Bilinear).is_err());
+    assert!(
+        x.grid_sample(&wrong_batch, Interpolation::Bilinear)
+            .is_err()
+    );
+    assert!(x.grid_sample(&wrong_last, Interpolation::Nearest).is_err());
+}
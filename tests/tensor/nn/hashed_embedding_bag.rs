@@ -0,0 +1,112 @@
+//! Tests for `Tensor::hashed_embedding_bag` operation.
+
+#![allow(clippy::cast_precision_loss)]
+
+use approx::assert_relative_eq;
+use xnn::{BagMode, Context, Tensor};
+
+/// Table whose row `r` is `[r + 1, -(r + 1) * 10]`.
+fn table(ctx: &Context, rows: u32) -> Tensor<f32> {
+    let data: Vec<f32> = (1..=rows)
+        .flat_map(|r| [r as f32, -(r as f32) * 10.0])
+        .collect();
+    Tensor::<f32>::from_shape_slice(ctx, &[rows as usize, 2], &data).unwrap()
+}
+
+#[test]
+fn test_hashed_embedding_bag_single_ids() {
+    let ctx = Context::try_default().unwrap();
+    let rows = 16;
+    let ids: Vec<u32> = vec![0, 7, 1_000_000, u32::MAX, 42];
+    let offsets: Vec<u32> = (0..5).collect();
+
+    let ids = Tensor::<u32>::from_slice(&ctx, &ids).unwrap();
+    let offsets = Tensor::<u32>::from_slice(&ctx, &offsets).unwrap();
+    let y = table(&ctx, rows)
+        .hashed_embedding_bag(&ids, &offsets, BagMode::Sum, 3)
+        .unwrap();
+
+    assert_eq!(y.dimensions(), &[5, 2]);
+    for pair in y.to_vec().unwrap().chunks(2) {
+        let row = pair[0].abs();
+        assert!(row >= 1.0 && row <= rows as f32 && row.fract() == 0.0);
+        assert_relative_eq!(pair[1], -pair[0] * 10.0);
+    }
+}
+
+#[test]
+fn test_hashed_embedding_bag_reduces_bags() {
+    let ctx = Context::try_default().unwrap();
+    let weights = table(&ctx, 64);
+    let ids = Tensor::<u32>::from_slice(&ctx, &[11, 2_024, 99_999]).unwrap();
+
+    let singles = weights
+        .hashed_embedding_bag(
+            &ids,
+            &Tensor::<u32>::from_slice(&ctx, &[0, 1, 2]).unwrap(),
+            BagMode::Sum,
+            7,
+        )
+        .unwrap()
+        .to_vec()
+        .unwrap();
+    let whole = Tensor::<u32>::from_slice(&ctx, &[0]).unwrap();
+
+    let sum = weights
+        .hashed_embedding_bag(&ids, &whole, BagMode::Sum, 7)
+        .unwrap()
+        .to_vec()
+        .unwrap();
+    let expected = [
+        singles[0] + singles[2] + singles[4],
+        singles[1] + singles[3] + singles[5],
+    ];
+    assert_eq!(sum, expected);
+
+    let mean = weights
+        .hashed_embedding_bag(&ids, &whole, BagMode::Mean, 7)
+        .unwrap()
+        .to_vec()
+        .unwrap();
+    crate::assert_vec_relative_eq(&mean, &[expected[0] / 3.0, expected[1] / 3.0], 1e-5);
+}
+
+#[test]
+fn test_hashed_embedding_bag_seed() {
+    let ctx = Context::try_default().unwrap();
+    let weights = table(&ctx, 1024);
+    let ids: Vec<u32> = (0..32).collect();
+    let ids = Tensor::<u32>::from_slice(&ctx, &ids).unwrap();
+    let offsets: Vec<u32> = (0..32).collect();
+    let offsets = Tensor::<u32>::from_slice(&ctx, &offsets).unwrap();
+
+    let lookup = |seed| {
+        weights
+            .hashed_embedding_bag(&ids, &offsets, BagMode::Sum, seed)
+            .unwrap()
+            .to_vec()
+            .unwrap()
+    };
+
+    assert_eq!(lookup(1), lookup(1));
+    assert_ne!(lookup(1), lookup(2));
+}
+
+#[test]
+fn test_hashed_embedding_bag_invalid() {
+    let ctx = Context::try_default().unwrap();
+    let ids = Tensor::<u32>::from_slice(&ctx, &[5, 6]).unwrap();
+    let offsets = Tensor::<u32>::from_slice(&ctx, &[0]).unwrap();
+    let grid = Tensor::<u32>::constant(&ctx, &[1, 2], &[0]).unwrap();
+
+    let flat = table(&ctx, 4).reshape(&[8]).unwrap();
+    assert!(
+        flat.hashed_embedding_bag(&ids, &offsets, BagMode::Sum, 0)
+            .is_err()
+    );
+    assert!(
+        table(&ctx, 4)
+            .hashed_embedding_bag(&grid, &offsets, BagMode::Sum, 0)
+            .is_err()
+    );
+}
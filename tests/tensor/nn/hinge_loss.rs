@@ -0,0 +1,22 @@
+//! Tests for `Tensor::hinge_loss` operation.
+
+use xnn::{Context, Tensor};
+
+#[test]
+fn test_hinge_loss_real() {
+    let ctx = Context::try_default().unwrap();
+    let scores = Tensor::<f32>::from_slice(&ctx, &[-1.0, 0.5, 1.0, 2.0]).unwrap();
+    let (loss, grad) = scores.hinge_loss(true).unwrap();
+    assert_eq!(loss.to_vec().unwrap(), vec![2.0, 0.5, 0.0, 0.0]);
+    assert_eq!(grad.to_vec().unwrap(), vec![-1.0, -1.0, 0.0, 0.0]);
+}
+
+#[test]
+fn test_hinge_loss_fake() {
+    let ctx = Context::try_default().unwrap();
+    let scores = Tensor::<f32>::from_shape_slice(&ctx, &[2, 2], &[-2.0, -1.0, 0.0, 1.5]).unwrap();
+    let (loss, grad) = scores.hinge_loss(false).unwrap();
+    assert_eq!(loss.dimensions(), &[2, 2]);
+    assert_eq!(loss.to_vec().unwrap(), vec![0.0, 0.0, 1.0, 2.5]);
+    assert_eq!(grad.to_vec().unwrap(), vec![0.0, 0.0, 1.0, 1.0]);
+}
@@ -0,0 +1,91 @@
+//! Tests for `Tensor::info_nce` operation.
+
+#![allow(clippy::cast_possible_truncation, clippy::cast_precision_loss)]
+
+use xnn::{Context, Tensor};
+
+fn embeddings(n: usize, d: usize, seed: usize) -> Vec<f32> {
+    (0..n * d)
+        .map(|i| ((i * 29 + seed * 13) % 17) as f32 * 0.1 - 0.8)
+        .collect()
+}
+
+fn info_nce_ref(a: &[f64], p: &[f64], n: usize, d: usize, temperature: f64) -> Vec<f64> {
+    (0..n)
+        .map(|i| {
+            let logits: Vec<f64> = (0..n)
+                .map(|j| (0..d).map(|k| a[i * d + k] * p[j * d + k]).sum::<f64>() / temperature)
+                .collect();
+            let max = logits.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+            let lse = logits.iter().map(|x| (x - max).exp()).sum::<f64>().ln() + max;
+            lse - logits[i]
+        })
+        .collect()
+}
+
+#[test]
+fn test_info_nce_loss() {
+    let ctx = Context::try_default().unwrap();
+    let (n, d) = (4, 3);
+    let a_data = embeddings(n, d, 0);
+    let p_data = embeddings(n, d, 1);
+    let a = Tensor::<f32>::from_shape_slice(&ctx, &[n, d], &a_data).unwrap();
+    let p = Tensor::<f32>::from_shape_slice(&ctx, &[n, d], &p_data).unwrap();
+
+    let (loss, grad_a, grad_p) = a.info_nce(&p, 0.5).unwrap();
+    assert_eq!(loss.dimensions(), &[n]);
+    assert_eq!(grad_a.dimensions(), &[n, d]);
+    assert_eq!(grad_p.dimensions(), &[n, d]);
+
+    let a64: Vec<f64> = a_data.iter().copied().map(f64::from).collect();
+    let p64: Vec<f64> = p_data.iter().copied().map(f64::from).collect();
+    let expected: Vec<f32> = info_nce_ref(&a64, &p64, n, d, 0.5)
+        .into_iter()
+        .map(|x| x as f32)
+        .collect();
+    crate::assert_vec_relative_eq(&loss.to_vec().unwrap(), &expected, 1e-5);
+}
+
+#[test]
+fn test_info_nce_gradients_match_finite_difference() {
+    let ctx = Context::try_default().unwrap();
+    let (n, d, temperature) = (3, 4, 0.7);
+    let a_data = embeddings(n, d, 2);
+    let p_data = embeddings(n, d, 5);
+    let a = Tensor::<f32>::from_shape_slice(&ctx, &[n, d], &a_data).unwrap();
+    let p = Tensor::<f32>::from_shape_slice(&ctx, &[n, d], &p_data).unwrap();
+
+    let (_, grad_a, grad_p) = a.info_nce(&p, temperature as f32).unwrap();
+    let grad_a = grad_a.to_vec().unwrap();
+    let grad_p = grad_p.to_vec().unwrap();
+
+    let a64: Vec<f64> = a_data.iter().copied().map(f64::from).collect();
+    let p64: Vec<f64> = p_data.iter().copied().map(f64::from).collect();
+    let total = |a: &[f64], p: &[f64]| info_nce_ref(a, p, n, d, temperature).iter().sum::<f64>();
+
+    let eps = 1e-5;
+    for j in 0..n * d {
+        let (mut plus, mut minus) = (a64.clone(), a64.clone());
+        plus[j] += eps;
+        minus[j] -= eps;
+        let numeric = (total(&plus, &p64) - total(&minus, &p64)) / (2.0 * eps);
+        assert!((f64::from(grad_a[j]) - numeric).abs() < 1e-4);
+
+        let (mut plus, mut minus) = (p64.clone(), p64.clone());
+        plus[j] += eps;
+        minus[j] -= eps;
+        let numeric = (total(&a64, &plus) - total(&a64, &minus)) / (2.0 * eps);
+        assert!((f64::from(grad_p[j]) - numeric).abs() < 1e-4);
+    }
+}
+
+#[test]
+fn test_info_nce_invalid() {
+    let ctx = Context::try_default().unwrap();
+    let a = Tensor::<f32>::from_shape_slice(&ctx, &[2, 2], &[0.0; 4]).unwrap();
+    let p = Tensor::<f32>::from_shape_slice(&ctx, &[2, 3], &[0.0; 6]).unwrap();
+    let v = Tensor::<f32>::from_slice(&ctx, &[0.0; 4]).unwrap();
+    assert!(a.info_nce(&p, 1.0).is_err());
+    assert!(v.info_nce(&v, 1.0).is_err());
+    assert!(a.info_nce(&a, 0.0).is_err());
+}
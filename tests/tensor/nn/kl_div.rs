@@ -0,0 +1,143 @@
+//! Tests for `Tensor::kl_div` operation.
+
+#![allow(clippy::cast_possible_truncation, clippy::cast_precision_loss)]
+
+use xnn::{Context, Tensor};
+
+fn log_softmax(row: &[f64], temperature: f64) -> Vec<f64> {
+    let max = row.iter().copied().fold(f64::NEG_INFINITY, f64::max) / temperature;
+    let lse = row
+        .iter()
+        .map(|x| (x / temperature - max).exp())
+        .sum::<f64>()
+        .ln()
+        + max;
+    row.iter().map(|x| x / temperature - lse).collect()
+}
+
+fn kl_ref(student: &[f64], teacher: &[f64], temperature: f64) -> f64 {
+    let log_q = log_softmax(student, temperature);
+    let log_p = log_softmax(teacher, temperature);
+    let kl: f64 = log_p
+        .iter()
+        .zip(&log_q)
+        .map(|(lp, lq)| lp.exp() * (lp - lq))
+        .sum();
+    temperature * temperature * kl
+}
+
+/// Returns per-row losses and the gradient of their sum.
+fn kl_div_ref(
+    student: &[f32],
+    teacher: &[f32],
+    cols: usize,
+    temperature: f32,
+) -> (Vec<f32>, Vec<f32>) {
+    let t = f64::from(temperature);
+    let mut loss = Vec::new();
+    let mut grad = Vec::new();
+    for (s, p) in student.chunks(cols).zip(teacher.chunks(cols)) {
+        let s: Vec<f64> = s.iter().copied().map(f64::from).collect();
+        let p: Vec<f64> = p.iter().copied().map(f64::from).collect();
+        loss.push(kl_ref(&s, &p, t) as f32);
+        let log_q = log_softmax(&s, t);
+        let log_p = log_softmax(&p, t);
+        for (lq, lp) in log_q.iter().zip(&log_p) {
+            grad.push((t * (lq.exp() - lp.exp())) as f32);
+        }
+    }
+    (loss, grad)
+}
+
+fn logits(rows: usize, cols: usize, seed: usize) -> Vec<f32> {
+    (0..rows * cols)
+        .map(|i| ((i * 37 + seed * 11) % 23) as f32 * 0.25 - 2.0)
+        .collect()
+}
+
+#[test]
+fn test_kl_div_basic() {
+    let ctx = Context::try_default().unwrap();
+    let s_data = logits(3, 5, 0);
+    let t_data = logits(3, 5, 1);
+    let s = Tensor::<f32>::from_shape_slice(&ctx, &[3, 5], &s_data).unwrap();
+    let t = Tensor::<f32>::from_shape_slice(&ctx, &[3, 5], &t_data).unwrap();
+
+    for temperature in [1.0, 2.0, 4.0] {
+        let (loss, grad) = s.kl_div(&t, temperature).unwrap();
+        let (loss_ref, grad_ref) = kl_div_ref(&s_data, &t_data, 5, temperature);
+
+        assert_eq!(loss.dimensions(), &[3]);
+        assert_eq!(grad.dimensions(), &[3, 5]);
+        crate::assert_vec_relative_eq(&loss.to_vec().unwrap(), &loss_ref, 1e-4);
+        crate::assert_vec_relative_eq(&grad.to_vec().unwrap(), &grad_ref, 1e-4);
+    }
+}
+
+#[test]
+fn test_kl_div_identical_is_zero() {
+    let ctx = Context::try_default().unwrap();
+    let data = logits(2, 7, 3);
+    let s = Tensor::<f32>::from_shape_slice(&ctx, &[2, 7], &data).unwrap();
+    let (loss, grad) = s.kl_div(&s.copy().unwrap(), 3.0).unwrap();
+    for v in loss
+        .to_vec()
+        .unwrap()
+        .into_iter()
+        .chain(grad.to_vec().unwrap())
+    {
+        assert!(v.abs() < 1e-5, "expected zero, got {v}");
+    }
+}
+
+#[test]
+fn test_kl_div_gradient_matches_finite_difference() {
+    let s_data = logits(1, 6, 2);
+    let t_data = logits(1, 6, 5);
+    let temperature = 2.0;
+
+    let ctx = Context::try_default().unwrap();
+    let s = Tensor::<f32>::from_shape_slice(&ctx, &[1, 6], &s_data).unwrap();
+    let t = Tensor::<f32>::from_shape_slice(&ctx, &[1, 6], &t_data).unwrap();
+    let grad = s.kl_div(&t, temperature).unwrap().1.to_vec().unwrap();
+
+    let s64: Vec<f64> = s_data.iter().copied().map(f64::from).collect();
+    let t64: Vec<f64> = t_data.iter().copied().map(f64::from).collect();
+    let eps = 1e-5;
+    for (j, g) in grad.iter().enumerate() {
+        let mut plus = s64.clone();
+        let mut minus = s64.clone();
+        plus[j] += eps;
+        minus[j] -= eps;
+        let numeric = (kl_ref(&plus, &t64, f64::from(temperature))
+            - kl_ref(&minus, &t64, f64::from(temperature)))
+            / (2.0 * eps);
+        assert!((f64::from(*g) - numeric).abs() < 1e-4, "{g} vs {numeric}");
+    }
+}
+
+#[test]
+fn test_kl_div_long_rows() {
+    let ctx = Context::try_default().unwrap();
+    let s_data = logits(2, 1000, 0);
+    let t_data = logits(2, 1000, 4);
+    let s = Tensor::<f32>::from_shape_slice(&ctx, &[2, 1000], &s_data).unwrap();
+    let t = Tensor::<f32>::from_shape_slice(&ctx, &[2, 1000], &t_data).unwrap();
+
+    let (loss, grad) = s.kl_div(&t, 1.5).unwrap();
+    let (loss_ref, grad_ref) = kl_div_ref(&s_data, &t_data, 1000, 1.5);
+    crate::assert_vec_relative_eq(&loss.to_vec().unwrap(), &loss_ref, 1e-4);
+    crate::assert_vec_relative_eq(&grad.to_vec().unwrap(), &grad_ref, 1e-4);
+}
+
+#[test]
+fn test_kl_div_invalid() {
+    let ctx = Context::try_default().unwrap();
+    let s = Tensor::<f32>::from_shape_slice(&ctx, &[2, 2], &[0.0; 4]).unwrap();
+    let t = Tensor::<f32>::from_shape_slice(&ctx, &[1, 4], &[0.0; 4]).unwrap();
+    let v = Tensor::<f32>::from_slice(&ctx, &[0.0; 4]).unwrap();
+    assert!(s.kl_div(&t, 1.0).is_err());
+    assert!(v.kl_div(&v, 1.0).is_err());
+    assert!(s.kl_div(&s, 0.0).is_err());
+    assert!(s.kl_div(&s, f32::NAN).is_err());
+}
@@ -0,0 +1,63 @@
+//! Tests for `Tensor::masked_softmax` operation.
+
+use xnn::{Context, Tensor};
+
+#[test]
+fn test_masked_softmax_basic() {
+    let ctx = Context::try_default().unwrap();
+    let t = Tensor::<f32>::from_slice(&ctx, &[1.0, 2.0, 3.0, 4.0]).unwrap();
+    let mask = Tensor::<bool>::from_slice(&ctx, &[true, false, true, false]).unwrap();
+    let result = t.masked_softmax(&mask).unwrap();
+
+    let e = 2.0f32.exp();
+    crate::assert_vec_relative_eq(
+        &result.to_vec().unwrap(),
+        &[1.0 / (1.0 + e), 0.0, e / (1.0 + e), 0.0],
+        1e-6,
+    );
+}
+
+#[test]
+fn test_masked_softmax_causal_broadcast() {
+    let ctx = Context::try_default().unwrap();
+    let scores = Tensor::<f32>::constant(&ctx, &[2, 3, 3], &[0.5]).unwrap();
+    let causal = [true, false, false, true, true, false, true, true, true];
+    let mask = Tensor::<bool>::from_shape_slice(&ctx, &[3, 3], &causal).unwrap();
+    let result = scores.masked_softmax(&mask).unwrap();
+
+    let row = [
+        1.0,
+        0.0,
+        0.0,
+        0.5,
+        0.5,
+        0.0,
+        1.0 / 3.0,
+        1.0 / 3.0,
+        1.0 / 3.0,
+    ];
+    let expected: Vec<f32> = row.iter().chain(&row).copied().collect();
+    assert_eq!(result.dimensions(), &[2, 3, 3]);
+    crate::assert_vec_relative_eq(&result.to_vec().unwrap(), &expected, 1e-6);
+}
+
+#[test]
+fn test_masked_softmax_fully_masked_row() {
+    let ctx = Context::try_default().unwrap();
+    let t = Tensor::<f32>::from_shape_slice(&ctx, &[2, 2], &[1.0, 2.0, 3.0, 4.0]).unwrap();
+    let mask = Tensor::<bool>::from_shape_slice(&ctx, &[2, 1], &[false, true]).unwrap();
+    let out = t.masked_softmax(&mask).unwrap().to_vec().unwrap();
+    assert!(out.iter().all(|v| v.is_finite()));
+    crate::assert_vec_relative_eq(&out[..2], &[0.0, 0.0], 1e-6);
+    crate::assert_vec_relative_eq(&out[2..], &[0.268_941_43, 0.731_058_6], 1e-6);
+}
+
+#[test]
+fn test_masked_softmax_invalid_mask() {
+    let ctx = Context::try_default().unwrap();
+    let t = Tensor::<f32>::from_shape_slice(&ctx, &[2, 2], &[0.0; 4]).unwrap();
+    let wider = Tensor::<bool>::from_shape_slice(&ctx, &[3, 2, 2], &[true; 12]).unwrap();
+    let wrong = Tensor::<bool>::from_slice(&ctx, &[true, true, true]).unwrap();
+    assert!(t.masked_softmax(&wider).is_err());
+    assert!(t.masked_softmax(&wrong).is_err());
+}
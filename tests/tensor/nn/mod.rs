@@ -1,11 +1,29 @@
 //! Neural network operation tests.
 
+mod bias_dropout_residual;
+mod conv1d;
+mod correlation;
+mod cross_entropy;
+mod depthwise_conv2d;
+mod diffusion_step;
 mod elu;
+mod embedding_bag;
 mod gelu;
+mod grid_sample;
+mod hashed_embedding_bag;
+mod hinge_loss;
+mod info_nce;
+mod kl_div;
 mod leaky_relu;
+mod masked_softmax;
+mod pointwise_conv2d;
 mod prelu;
 mod relu;
+mod selective_scan;
 mod selu;
 mod sigmoid;
 mod silu;
+mod softmax;
 mod softplus;
+mod triplet_margin_loss;
+mod wasserstein_loss;
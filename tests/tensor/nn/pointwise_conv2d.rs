@@ -0,0 +1,92 @@
+//! Tests for `Tensor::pointwise_conv2d` operation.
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use xnn::{Context, MemoryFormat, Tensor};
+
+fn cpu_pointwise(
+    x: &[f32],
+    weight: &[f32],
+    bias: Option<&[f32]>,
+    c_in: usize,
+    c_out: usize,
+) -> Vec<f32> {
+    x.chunks(c_in)
+        .flat_map(|pixel| {
+            (0..c_out).map(move |o| {
+                let sum: f32 = (0..c_in).map(|i| pixel[i] * weight[i * c_out + o]).sum();
+                sum + bias.map_or(0.0, |bias| bias[o])
+            })
+        })
+        .collect()
+}
+
+#[test]
+fn test_pointwise_conv2d_matches_cpu() {
+    let ctx = Context::try_default().unwrap();
+    let mut rng = StdRng::seed_from_u64(39);
+
+    for (dims, c_out, biased) in [([2, 5, 7, 16], 24, true), ([1, 3, 3, 3], 2, false)] {
+        let c_in = dims[3];
+        let len = dims.iter().product();
+        let x: Vec<f32> = (0..len).map(|_| rng.random_range(-1.0..1.0)).collect();
+        let weight: Vec<f32> = (0..c_in * c_out)
+            .map(|_| rng.random_range(-1.0..1.0))
+            .collect();
+        let bias: Vec<f32> = (0..c_out).map(|_| rng.random_range(-1.0..1.0)).collect();
+
+        // `x` is generated in NHWC order; the tensors are its logical NCHW view.
+        let nhwc = Tensor::<f32>::from_shape_slice(&ctx, &dims, &x).unwrap();
+        let channels_last = nhwc.permute(&[0, 3, 1, 2]).unwrap();
+        let tw = Tensor::<f32>::from_shape_slice(&ctx, &[c_in, c_out], &weight).unwrap();
+        let tb = Tensor::<f32>::from_slice(&ctx, &bias).unwrap();
+        let expected = cpu_pointwise(&x, &weight, biased.then_some(&bias[..]), c_in, c_out);
+
+        for format in [MemoryFormat::ChannelsLast, MemoryFormat::ChannelsFirst] {
+            let tx = channels_last.to_memory_format(format).unwrap();
+            let y = tx.pointwise_conv2d(&tw, biased.then_some(&tb)).unwrap();
+
+            assert_eq!(y.dimensions(), &[dims[0], c_out, dims[1], dims[2]]);
+            assert_eq!(y.memory_format(), tx.memory_format());
+            crate::assert_vec_relative_eq(
+                &y.permute(&[0, 2, 3, 1]).unwrap().to_vec().unwrap(),
+                &expected,
+                1e-4,
+            );
+        }
+    }
+}
+
+#[test]
+fn test_pointwise_conv2d_after_depthwise() {
+    let ctx = Context::try_default().unwrap();
+    let x = Tensor::<f32>::constant(&ctx, &[1, 2, 4, 4], &[1.0])
+        .unwrap()
+        .to_memory_format(MemoryFormat::ChannelsLast)
+        .unwrap();
+    let depthwise = Tensor::<f32>::constant(&ctx, &[3, 3, 2], &[1.0]).unwrap();
+    let pointwise = Tensor::<f32>::from_shape_slice(&ctx, &[2, 1], &[0.5, 0.5]).unwrap();
+
+    let y = x
+        .depthwise_conv2d(&depthwise, None, (1, 1), (0, 0))
+        .unwrap()
+        .pointwise_conv2d(&pointwise, None)
+        .unwrap();
+
+    assert_eq!(y.dimensions(), &[1, 1, 2, 2]);
+    crate::assert_vec_relative_eq(&y.to_vec().unwrap(), &[9.0; 4], 1e-6);
+}
+
+#[test]
+fn test_pointwise_conv2d_invalid() {
+    let ctx = Context::try_default().unwrap();
+    let x = Tensor::<f32>::constant(&ctx, &[1, 3, 2, 2], &[0.0]).unwrap();
+    let weight = Tensor::<f32>::constant(&ctx, &[3, 4], &[0.0]).unwrap();
+    let wrong = Tensor::<f32>::constant(&ctx, &[2, 4], &[0.0]).unwrap();
+    let bias = Tensor::<f32>::constant(&ctx, &[3], &[0.0]).unwrap();
+    let flat = Tensor::<f32>::constant(&ctx, &[3, 4], &[0.0]).unwrap();
+
+    assert!(flat.pointwise_conv2d(&weight, None).is_err());
+    assert!(x.pointwise_conv2d(&wrong, None).is_err());
+    assert!(x.pointwise_conv2d(&weight, Some(&bias)).is_err());
+}
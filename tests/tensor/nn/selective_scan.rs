@@ -0,0 +1,210 @@
+//! Tests for `Tensor::selective_scan` operation.
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use xnn::{Context, Tensor};
+
+struct Inputs {
+    x: Vec<f32>,
+    delta: Vec<f32>,
+    a: Vec<f32>,
+    b: Vec<f32>,
+    c: Vec<f32>,
+    d: Vec<f32>,
+}
+
+impl Inputs {
+    fn random(rng: &mut StdRng, [batch, channels, length, states]: [usize; 4]) -> Self {
+        let mut random = |len: usize, range: core::ops::Range<f32>| -> Vec<f32> {
+            (0..len).map(|_| rng.random_range(range.clone())).collect()
+        };
+        Self {
+            x: random(batch * channels * length, -1.0..1.0),
+            delta: random(batch * channels * length, 0.01..0.5),
+            a: random(channels * states, -2.0..-0.1),
+            b: random(batch * states * length, -1.0..1.0),
+            c: random(batch * states * length, -1.0..1.0),
+            d: random(channels, -1.0..1.0),
+        }
+    }
+}
+
+/// Returns the output and final states of the sequential recurrence.
+fn cpu_scan(
+    inputs: &Inputs,
+    skip: bool,
+    initial: Option<&[f32]>,
+    [batch, channels, length, states]: [usize; 4],
+) -> (Vec<f32>, Vec<f32>) {
+    let mut y = vec![0.0; batch * channels * length];
+    let mut h = initial.map_or_else(|| vec![0.0; batch * channels * states], <[f32]>::to_vec);
+
+    for b in 0..batch {
+        for ch in 0..channels {
+            let row = (b * channels + ch) * length;
+            for t in 0..length {
+                let (x, dt) = (inputs.x[row + t], inputs.delta[row + t]);
+                let mut acc = if skip { inputs.d[ch] * x } else { 0.0 };
+                for s in 0..states {
+                    let bc = (b * states + s) * length + t;
+                    let h = &mut h[(b * channels + ch) * states + s];
+                    *h = (dt * inputs.a[ch * states + s]).exp() * *h + dt * inputs.b[bc] * x;
+                    acc += inputs.c[bc] * *h;
+                }
+                y[row + t] = acc;
+            }
+        }
+    }
+
+    (y, h)
+}
+
+#[test]
+fn test_selective_scan_matches_cpu() {
+    let ctx = Context::try_default().unwrap();
+    let mut rng = StdRng::seed_from_u64(45);
+
+    // Lengths below, at and above one step per thread, and spanning several steps per thread.
+    for (dims, skip) in [
+        ([2, 3, 7, 4], true),
+        ([1, 2, 256, 3], false),
+        ([2, 2, 1000, 5], true),
+        ([1, 1, 1, 1], false),
+    ] {
+        let [batch, channels, length, states] = dims;
+        let inputs = Inputs::random(&mut rng, dims);
+        let tensor = |dims: &[usize], data: &[f32]| Tensor::from_shape_slice(&ctx, dims, data);
+
+        let x = tensor(&[batch, channels, length], &inputs.x).unwrap();
+        let delta = tensor(&[batch, channels, length], &inputs.delta).unwrap();
+        let a = tensor(&[channels, states], &inputs.a).unwrap();
+        let b = tensor(&[batch, states, length], &inputs.b).unwrap();
+        let c = tensor(&[batch, states, length], &inputs.c).unwrap();
+        let d = Tensor::from_slice(&ctx, &inputs.d).unwrap();
+
+        let (y, state) = x
+            .selective_scan(&delta, &a, &b, &c, skip.then_some(&d), None)
+            .unwrap();
+        let (expected_y, expected_state) = cpu_scan(&inputs, skip, None, dims);
+
+        assert_eq!(y.dimensions(), &[batch, channels, length]);
+        assert_eq!(state.dimensions(), &[batch, channels, states]);
+        crate::assert_vec_relative_eq(&y.to_vec().unwrap(), &expected_y, 1e-4);
+        crate::assert_vec_relative_eq(&state.to_vec().unwrap(), &expected_state, 1e-4);
+    }
+}
+
+#[test]
+fn test_selective_scan_chunked() {
+    let ctx = Context::try_default().unwrap();
+    let mut rng = StdRng::seed_from_u64(46);
+    let dims = [2, 3, 40, 4];
+    let [batch, channels, length, states] = dims;
+    let inputs = Inputs::random(&mut rng, dims);
+    let tensor = |dims: &[usize], data: &[f32]| Tensor::from_shape_slice(&ctx, dims, data).unwrap();
+
+    let x = tensor(&[batch, channels, length], &inputs.x);
+    let delta = tensor(&[batch, channels, length], &inputs.delta);
+    let a = tensor(&[channels, states], &inputs.a);
+    let b = tensor(&[batch, states, length], &inputs.b);
+    let c = tensor(&[batch, states, length], &inputs.c);
+    let d = Tensor::from_slice(&ctx, &inputs.d).unwrap();
+
+    // Scanning two halves, carrying the state across, matches one scan of the whole.
+    let half = |t: &Tensor<f32>, start: usize| t.narrow(2, start, length / 2).unwrap();
+    let (first, state) = half(&x, 0)
+        .selective_scan(
+            &half(&delta, 0),
+            &a,
+            &half(&b, 0),
+            &half(&c, 0),
+            Some(&d),
+            None,
+        )
+        .unwrap();
+    let (second, state) = half(&x, length / 2)
+        .selective_scan(
+            &half(&delta, length / 2),
+            &a,
+            &half(&b, length / 2),
+            &half(&c, length / 2),
+            Some(&d),
+            Some(&state),
+        )
+        .unwrap();
+
+    let (expected_y, expected_state) = cpu_scan(&inputs, true, None, dims);
+    let (first, second) = (first.to_vec().unwrap(), second.to_vec().unwrap());
+    let y: Vec<f32> = first
+        .chunks(length / 2)
+        .zip(second.chunks(length / 2))
+        .flat_map(|(first, second)| first.iter().chain(second))
+        .copied()
+        .collect();
+    crate::assert_vec_relative_eq(&y, &expected_y, 1e-4);
+    crate::assert_vec_relative_eq(&state.to_vec().unwrap(), &expected_state, 1e-4);
+}
+
+#[test]
+fn test_selective_scan_initial_state() {
+    let ctx = Context::try_default().unwrap();
+    // With zero input the state only decays: h_t = exp(Δ·A)^t·h_0 and y_t = C·h_t.
+    let x = Tensor::<f32>::constant(&ctx, &[1, 1, 3], &[0.0]).unwrap();
+    let delta = Tensor::<f32>::constant(&ctx, &[1, 1, 3], &[1.0]).unwrap();
+    let a = Tensor::<f32>::from_shape_slice(&ctx, &[1, 1], &[-core::f32::consts::LN_2]).unwrap();
+    let b = Tensor::<f32>::constant(&ctx, &[1, 1, 3], &[1.0]).unwrap();
+    let c = Tensor::<f32>::constant(&ctx, &[1, 1, 3], &[2.0]).unwrap();
+    let initial = Tensor::<f32>::from_shape_slice(&ctx, &[1, 1, 1], &[8.0]).unwrap();
+
+    let (y, state) = x
+        .selective_scan(&delta, &a, &b, &c, None, Some(&initial))
+        .unwrap();
+    crate::assert_vec_relative_eq(&y.to_vec().unwrap(), &[8.0, 4.0, 2.0], 1e-4);
+    crate::assert_vec_relative_eq(&state.to_vec().unwrap(), &[1.0], 1e-4);
+}
+
+#[test]
+fn test_selective_scan_invalid() {
+    let ctx = Context::try_default().unwrap();
+    let zeros = |dims: &[usize]| Tensor::<f32>::constant(&ctx, dims, &[0.0]).unwrap();
+    let x = zeros(&[2, 3, 5]);
+    let a = zeros(&[3, 4]);
+    let bc = zeros(&[2, 4, 5]);
+
+    assert!(
+        zeros(&[3, 5])
+            .selective_scan(&zeros(&[3, 5]), &a, &bc, &bc, None, None)
+            .is_err()
+    );
+    assert!(
+        x.selective_scan(&zeros(&[2, 3, 4]), &a, &bc, &bc, None, None)
+            .is_err()
+    );
+    assert!(
+        x.selective_scan(&x, &zeros(&[2, 4]), &bc, &bc, None, None)
+            .is_err()
+    );
+    assert!(
+        x.selective_scan(&x, &a, &zeros(&[2, 3, 5]), &bc, None, None)
+            .is_err()
+    );
+    assert!(
+        x.selective_scan(&x, &a, &bc, &bc, Some(&zeros(&[4])), None)
+            .is_err()
+    );
+    assert!(
+        x.selective_scan(&x, &a, &bc, &bc, None, Some(&zeros(&[2, 3, 3])))
+            .is_err()
+    );
+    assert!(
+        x.selective_scan(
+            &x,
+            &a,
+            &bc,
+            &bc,
+            Some(&zeros(&[3])),
+            Some(&zeros(&[2, 3, 4]))
+        )
+        .is_ok()
+    );
+}
@@ -0,0 +1,51 @@
+//! Tests for `Tensor::softmax` operation.
+
+#![allow(clippy::cast_precision_loss)]
+
+use xnn::{Context, Tensor};
+
+fn softmax_ref(x: &[f32], cols: usize) -> Vec<f32> {
+    x.chunks(cols)
+        .flat_map(|row| {
+            let max = row.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+            let sum: f32 = row.iter().map(|v| (v - max).exp()).sum();
+            row.iter().map(move |v| (v - max).exp() / sum)
+        })
+        .collect()
+}
+
+#[test]
+fn test_softmax_vector() {
+    let ctx = Context::try_default().unwrap();
+    let data = [1.0f32, 2.0, 3.0, 4.0];
+    let t = Tensor::<f32>::from_slice(&ctx, &data).unwrap();
+    let result = t.softmax().unwrap();
+    assert_eq!(result.dimensions(), &[4]);
+    crate::assert_vec_relative_eq(&result.to_vec().unwrap(), &softmax_ref(&data, 4), 1e-6);
+}
+
+#[test]
+fn test_softmax_matrix() {
+    let ctx = Context::try_default().unwrap();
+    let data = [1.0f32, 2.0, 3.0, -1.0, 0.0, 1000.0];
+    let t = Tensor::<f32>::from_shape_slice(&ctx, &[2, 3], &data).unwrap();
+    let result = t.softmax().unwrap();
+    crate::assert_vec_relative_eq(&result.to_vec().unwrap(), &softmax_ref(&data, 3), 1e-6);
+}
+
+#[test]
+fn test_softmax_long_rows() {
+    let ctx = Context::try_default().unwrap();
+    let cols = 1000;
+    let data: Vec<f32> = (0..3 * cols).map(|i| (i % 17) as f32 * 0.3).collect();
+    let t = Tensor::<f32>::from_shape_slice(&ctx, &[3, cols], &data).unwrap();
+    let result = t.softmax().unwrap();
+    crate::assert_vec_relative_eq(&result.to_vec().unwrap(), &softmax_ref(&data, cols), 1e-5);
+}
+
+#[test]
+fn test_softmax_scalar() {
+    let ctx = Context::try_default().unwrap();
+    let t = Tensor::<f32>::from_shape_slice(&ctx, &[], &[1.0]).unwrap();
+    assert!(t.softmax().is_err());
+}
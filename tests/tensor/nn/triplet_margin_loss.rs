@@ -0,0 +1,40 @@
+//! Tests for `Tensor::triplet_margin_loss` operation.
+
+use xnn::{Context, Tensor};
+
+#[test]
+fn test_triplet_margin_loss_basic() {
+    let ctx = Context::try_default().unwrap();
+    let a = Tensor::<f32>::from_shape_slice(&ctx, &[2, 2], &[0.0, 0.0, 1.0, 1.0]).unwrap();
+    let p = Tensor::<f32>::from_shape_slice(&ctx, &[2, 2], &[3.0, 4.0, 1.0, 2.0]).unwrap();
+    let n = Tensor::<f32>::from_shape_slice(&ctx, &[2, 2], &[0.0, 1.0, 1.0, 9.0]).unwrap();
+
+    let (loss, grad_a, grad_p, grad_n) = a.triplet_margin_loss(&p, &n, 1.0).unwrap();
+    assert_eq!(loss.dimensions(), &[2]);
+    crate::assert_vec_relative_eq(&loss.to_vec().unwrap(), &[5.0, 0.0], 1e-6);
+
+    crate::assert_vec_relative_eq(&grad_p.to_vec().unwrap(), &[0.6, 0.8, 0.0, 0.0], 1e-6);
+    crate::assert_vec_relative_eq(&grad_n.to_vec().unwrap(), &[0.0, -1.0, 0.0, 0.0], 1e-6);
+    crate::assert_vec_relative_eq(&grad_a.to_vec().unwrap(), &[-0.6, 0.2, 0.0, 0.0], 1e-6);
+}
+
+#[test]
+fn test_triplet_margin_loss_identical_positive() {
+    let ctx = Context::try_default().unwrap();
+    let a = Tensor::<f32>::from_shape_slice(&ctx, &[1, 3], &[1.0, 2.0, 3.0]).unwrap();
+    let n = Tensor::<f32>::from_shape_slice(&ctx, &[1, 3], &[1.0, 2.0, 3.5]).unwrap();
+
+    let (loss, grad_a, grad_p, _) = a.triplet_margin_loss(&a, &n, 1.0).unwrap();
+    crate::assert_vec_relative_eq(&loss.to_vec().unwrap(), &[0.5], 1e-6);
+    assert!(grad_p.to_vec().unwrap().iter().all(|g| g.is_finite()));
+    crate::assert_vec_relative_eq(&grad_a.to_vec().unwrap(), &[0.0, 0.0, 1.0], 1e-6);
+}
+
+#[test]
+fn test_triplet_margin_loss_invalid() {
+    let ctx = Context::try_default().unwrap();
+    let a = Tensor::<f32>::from_shape_slice(&ctx, &[2, 2], &[0.0; 4]).unwrap();
+    let b = Tensor::<f32>::from_shape_slice(&ctx, &[1, 4], &[0.0; 4]).unwrap();
+    assert!(a.triplet_margin_loss(&a, &b, 1.0).is_err());
+    assert!(a.triplet_margin_loss(&a, &a, -1.0).is_err());
+}
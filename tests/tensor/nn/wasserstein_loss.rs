@@ -0,0 +1,18 @@
+//! Tests for `Tensor::wasserstein_loss` operation.
+
+use xnn::{Context, Tensor};
+
+#[test]
+fn test_wasserstein_loss() {
+    let ctx = Context::try_default().unwrap();
+    let scores = Tensor::<f32>::from_shape_slice(&ctx, &[3, 1], &[-1.0, 0.5, 2.0]).unwrap();
+
+    let (loss, grad) = scores.wasserstein_loss(true).unwrap();
+    assert_eq!(grad.dimensions(), &[3, 1]);
+    assert_eq!(loss.to_vec().unwrap(), vec![1.0, -0.5, -2.0]);
+    assert_eq!(grad.to_vec().unwrap(), vec![-1.0; 3]);
+
+    let (loss, grad) = scores.wasserstein_loss(false).unwrap();
+    assert_eq!(loss.to_vec().unwrap(), vec![-1.0, 0.5, 2.0]);
+    assert_eq!(grad.to_vec().unwrap(), vec![1.0; 3]);
+}
@@ -0,0 +1,5 @@
+//! Random generation tests.
+
+mod multinomial;
+mod random_matrix;
+mod randperm;
@@ -0,0 +1,98 @@
+//! Tests for `Tensor::multinomial`.
+
+#![allow(clippy::cast_precision_loss)]
+
+use xnn::{Context, Tensor};
+
+#[test]
+fn test_multinomial_frequencies() {
+    let ctx = Context::try_default().unwrap();
+    let weights = Tensor::<f32>::from_shape_slice(&ctx, &[1, 3], &[1.0, 0.0, 3.0]).unwrap();
+
+    let samples = weights.multinomial(4000, 7).unwrap();
+    assert_eq!(samples.dimensions(), &[1, 4000]);
+
+    let samples = samples.to_vec().unwrap();
+    let mut counts = [0usize; 3];
+    for &class in &samples {
+        counts[class as usize] += 1;
+    }
+    assert_eq!(counts[1], 0);
+    let frequency = counts[2] as f32 / samples.len() as f32;
+    assert!((frequency - 0.75).abs() < 0.03, "frequency {frequency}");
+}
+
+#[test]
+fn test_multinomial_many_classes() {
+    let ctx = Context::try_default().unwrap();
+    let mut data = vec![0.0; 2 * 1000];
+    data[700] = 1.0;
+    data[999] = 1.0;
+    data[1000] = 2.0;
+    let weights = Tensor::<f32>::from_shape_slice(&ctx, &[2, 1000], &data).unwrap();
+
+    let samples = weights.multinomial(500, 3).unwrap().to_vec().unwrap();
+    assert!(
+        samples[..500]
+            .iter()
+            .all(|&class| class == 700 || class == 999)
+    );
+    assert!(samples[..500].contains(&700) && samples[..500].contains(&999));
+    assert!(samples[500..].iter().all(|&class| class == 0));
+}
+
+#[test]
+fn test_multinomial_batched() {
+    let ctx = Context::try_default().unwrap();
+    let data: Vec<f32> = (0..30u8).map(|i| f32::from(i % 5 + 1)).collect();
+    let weights = Tensor::<f32>::from_shape_slice(&ctx, &[2, 3, 5], &data).unwrap();
+
+    let samples = weights.multinomial(4, 11).unwrap();
+    assert_eq!(samples.dimensions(), &[2, 3, 4]);
+    assert!(samples.to_vec().unwrap().iter().all(|&class| class < 5));
+
+    let again = weights.multinomial(4, 11).unwrap();
+    assert_eq!(again.to_vec().unwrap(), samples.to_vec().unwrap());
+}
+
+#[test]
+fn test_multinomial_seed() {
+    let ctx = Context::try_default().unwrap();
+    let weights = Tensor::<f32>::constant(&ctx, &[64], &[1.0]).unwrap();
+
+    let a = weights.multinomial(100, 1).unwrap().to_vec().unwrap();
+    let b = weights.multinomial(100, 2).unwrap().to_vec().unwrap();
+    assert_ne!(a, b);
+}
+
+#[test]
+fn test_multinomial_zero_row() {
+    let ctx = Context::try_default().unwrap();
+    let weights = Tensor::<f32>::from_shape_slice(&ctx, &[2, 2], &[0.0, 0.0, 0.0, 1.0]).unwrap();
+
+    let samples = weights.multinomial(3, 5).unwrap();
+    assert_eq!(
+        samples.to_vec().unwrap(),
+        vec![u32::MAX, u32::MAX, u32::MAX, 1, 1, 1]
+    );
+}
+
+#[test]
+fn test_multinomial_no_samples() {
+    let ctx = Context::try_default().unwrap();
+    let weights = Tensor::<f32>::from_slice(&ctx, &[1.0, 2.0]).unwrap();
+
+    let samples = weights.multinomial(0, 5).unwrap();
+    assert_eq!(samples.dimensions(), &[0]);
+    assert!(samples.to_vec().unwrap().is_empty());
+}
+
+#[test]
+fn test_multinomial_invalid() {
+    let ctx = Context::try_default().unwrap();
+    let scalar = Tensor::<f32>::scalar(&ctx, 1.0).unwrap();
+    assert!(scalar.multinomial(1, 0).is_err());
+
+    let empty = Tensor::<f32>::constant(&ctx, &[2, 0], &[0.0]).unwrap();
+    assert!(empty.multinomial(1, 0).is_err());
+}
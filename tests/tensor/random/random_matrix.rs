@@ -0,0 +1,93 @@
+//! Tests for random matrix generators.
+
+#![allow(clippy::cast_precision_loss)]
+
+use approx::assert_relative_eq;
+use xnn::{Context, Tensor};
+
+#[test]
+fn test_random_well_conditioned() {
+    let ctx = Context::try_default().unwrap();
+    let n = 16;
+    let a = Tensor::<f32>::random_well_conditioned(&ctx, &[3, n, n], 5).unwrap();
+    assert_eq!(a.dimensions(), &[3, n, n]);
+
+    let values = a.to_vec().unwrap();
+    for (index, &value) in values.iter().enumerate() {
+        let (i, j) = (index / n % n, index % n);
+        if i == j {
+            assert_relative_eq!(value, 2.0);
+        } else {
+            assert!(value.abs() < 1.0 / n as f32);
+        }
+    }
+
+    let again = Tensor::<f32>::random_well_conditioned(&ctx, &[3, n, n], 5).unwrap();
+    assert_eq!(again.to_vec().unwrap(), values);
+    let other = Tensor::<f32>::random_well_conditioned(&ctx, &[3, n, n], 6).unwrap();
+    assert_ne!(other.to_vec().unwrap(), values);
+}
+
+#[test]
+fn test_random_orthogonal() {
+    let ctx = Context::try_default().unwrap();
+    let n = 24;
+    let q = Tensor::<f32>::random_orthogonal(&ctx, &[2, n, n], 9).unwrap();
+    assert_eq!(q.dimensions(), &[2, n, n]);
+
+    let values = q.to_vec().unwrap();
+    for matrix in values.chunks(n * n) {
+        for i in 0..n {
+            for k in 0..n {
+                let gram: f32 = (0..n).map(|j| matrix[j * n + i] * matrix[j * n + k]).sum();
+                assert_relative_eq!(gram, if i == k { 1.0 } else { 0.0 }, epsilon = 1e-4);
+            }
+        }
+    }
+    assert_ne!(values[..n * n], values[n * n..]);
+    assert!(values.iter().filter(|value| value.abs() > 1e-3).count() > values.len() / 2);
+}
+
+#[test]
+fn test_random_banded() {
+    let ctx = Context::try_default().unwrap();
+    let (rows, cols) = (6, 9);
+    let a = Tensor::<f32>::random_banded(&ctx, &[2, rows, cols], 1, 2, 3).unwrap();
+
+    let values = a.to_vec().unwrap();
+    for (index, &value) in values.iter().enumerate() {
+        let (i, j) = (index / cols % rows, index % cols);
+        if j + 1 >= i && j <= i + 2 {
+            assert!(value != 0.0);
+        } else {
+            assert!(value == 0.0);
+        }
+    }
+}
+
+#[test]
+fn test_random_sparse() {
+    let ctx = Context::try_default().unwrap();
+    let shape = [100, 100];
+
+    let a = Tensor::<f32>::random_sparse(&ctx, &shape, 0.1, 4).unwrap();
+    let nonzero = a.to_vec().unwrap().iter().filter(|&&v| v != 0.0).count();
+    assert!((800..1200).contains(&nonzero), "{nonzero} nonzero entries");
+
+    let empty = Tensor::<f32>::random_sparse(&ctx, &shape, 0.0, 4).unwrap();
+    assert!(empty.to_vec().unwrap().iter().all(|&v| v == 0.0));
+
+    let dense = Tensor::<f32>::random_sparse(&ctx, &shape, 1.0, 4).unwrap();
+    assert!(dense.to_vec().unwrap().iter().all(|&v| v != 0.0));
+}
+
+#[test]
+fn test_random_matrix_invalid() {
+    let ctx = Context::try_default().unwrap();
+    assert!(Tensor::<f32>::random_well_conditioned(&ctx, &[4], 0).is_err());
+    assert!(Tensor::<f32>::random_well_conditioned(&ctx, &[3, 4], 0).is_err());
+    assert!(Tensor::<f32>::random_orthogonal(&ctx, &[3, 4], 0).is_err());
+    assert!(Tensor::<f32>::random_banded(&ctx, &[4], 1, 1, 0).is_err());
+    assert!(Tensor::<f32>::random_sparse(&ctx, &[4, 4], 1.5, 0).is_err());
+    assert!(Tensor::<f32>::random_sparse(&ctx, &[4, 4], f32::NAN, 0).is_err());
+}
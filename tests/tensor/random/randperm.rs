@@ -0,0 +1,30 @@
+//! Tests for `Tensor::randperm`.
+
+use xnn::{Context, Tensor};
+
+#[test]
+fn test_randperm() {
+    let ctx = Context::try_default().unwrap();
+    let p = Tensor::<u32>::randperm(&ctx, 1000, 42).unwrap();
+    assert_eq!(p.dimensions(), &[1000]);
+
+    let values = p.to_vec().unwrap();
+    let mut sorted = values.clone();
+    sorted.sort_unstable();
+    assert_eq!(sorted, (0..1000).collect::<Vec<u32>>());
+    assert_ne!(values, sorted);
+
+    let again = Tensor::<u32>::randperm(&ctx, 1000, 42).unwrap();
+    assert_eq!(again.to_vec().unwrap(), values);
+
+    let other = Tensor::<u32>::randperm(&ctx, 1000, 43).unwrap();
+    assert_ne!(other.to_vec().unwrap(), values);
+}
+
+#[test]
+fn test_randperm_empty() {
+    let ctx = Context::try_default().unwrap();
+    let p = Tensor::<u32>::randperm(&ctx, 0, 1).unwrap();
+    assert_eq!(p.dimensions(), &[0]);
+    assert!(p.to_vec().unwrap().is_empty());
+}
@@ -0,0 +1,90 @@
+//! Tests for `Tensor::get` and `Tensor::read_region`.
+
+use xnn::{Context, Tensor};
+
+fn iota(ctx: &Context, dimensions: &[usize]) -> Tensor<u32> {
+    let len = u32::try_from(dimensions.iter().product::<usize>()).unwrap();
+    let data: Vec<u32> = (0..len).collect();
+    Tensor::from_shape_slice(ctx, dimensions, &data).unwrap()
+}
+
+#[test]
+fn test_get() {
+    let ctx = Context::try_default().unwrap();
+    let t = iota(&ctx, &[2, 3, 4]);
+
+    assert_eq!(t.get(&[0, 0, 0]).unwrap(), 0);
+    assert_eq!(t.get(&[1, 2, 3]).unwrap(), 23);
+    assert_eq!(t.get(&[1, 0, 2]).unwrap(), 14);
+
+    let s = Tensor::<u32>::scalar(&ctx, 9).unwrap();
+    assert_eq!(s.get(&[]).unwrap(), 9);
+}
+
+#[test]
+fn test_get_view() {
+    let ctx = Context::try_default().unwrap();
+    let t = iota(&ctx, &[3, 4]);
+
+    let v = t.transpose(0, 1).unwrap();
+    assert_eq!(v.get(&[3, 1]).unwrap(), 7);
+
+    let v = t.narrow(0, 1, 2).unwrap().narrow(1, 1, 2).unwrap();
+    assert_eq!(v.get(&[1, 1]).unwrap(), 10);
+
+    let v = t.narrow(0, 2, 1).unwrap().broadcast_to(&[5, 4]).unwrap();
+    assert_eq!(v.get(&[4, 3]).unwrap(), 11);
+}
+
+#[test]
+fn test_get_invalid() {
+    let ctx = Context::try_default().unwrap();
+    let t = iota(&ctx, &[2, 3]);
+
+    assert!(t.get(&[2, 0]).is_err());
+    assert!(t.get(&[0, 3]).is_err());
+    assert!(t.get(&[0]).is_err());
+    assert!(t.get(&[0, 0, 0]).is_err());
+}
+
+#[test]
+fn test_read_region_contiguous() {
+    let ctx = Context::try_default().unwrap();
+    let t = iota(&ctx, &[4, 3]);
+
+    assert_eq!(
+        t.read_region(&[1, 0], &[2, 3]).unwrap(),
+        vec![3, 4, 5, 6, 7, 8]
+    );
+    assert_eq!(t.read_region(&[2, 1], &[1, 2]).unwrap(), vec![7, 8]);
+    assert_eq!(
+        t.read_region(&[0, 0], &[4, 3]).unwrap(),
+        t.to_vec().unwrap()
+    );
+    assert!(t.read_region(&[1, 1], &[0, 2]).unwrap().is_empty());
+}
+
+#[test]
+fn test_read_region_strided() {
+    let ctx = Context::try_default().unwrap();
+    let t = iota(&ctx, &[3, 4]);
+
+    assert_eq!(
+        t.read_region(&[0, 1], &[3, 2]).unwrap(),
+        vec![1, 2, 5, 6, 9, 10]
+    );
+
+    let v = t.transpose(0, 1).unwrap();
+    assert_eq!(v.read_region(&[2, 0], &[2, 2]).unwrap(), vec![2, 6, 3, 7]);
+}
+
+#[test]
+fn test_read_region_invalid() {
+    let ctx = Context::try_default().unwrap();
+    let t = iota(&ctx, &[2, 3]);
+
+    assert!(t.read_region(&[0, 0], &[3, 1]).is_err());
+    assert!(t.read_region(&[1, 2], &[1, 2]).is_err());
+    assert!(t.read_region(&[0], &[1]).is_err());
+    assert!(t.read_region(&[0, 0], &[1]).is_err());
+}
@@ -0,0 +1,77 @@
+//! Whole-tensor reduction tests.
+
+use approx::assert_relative_eq;
+use xnn::{Context, Tensor};
+
+#[test]
+fn test_all_reductions_f32() {
+    let ctx = Context::try_default().unwrap();
+
+    let a =
+        Tensor::<f32>::from_shape_slice(&ctx, &[2, 3], &[1.0, -2.0, 3.0, 4.0, 5.0, -6.0]).unwrap();
+
+    let sum = a.sum_all().unwrap();
+    assert!(sum.dimensions().is_empty());
+    assert_relative_eq!(sum.item().unwrap(), 5.0);
+    assert_relative_eq!(a.mean_all().unwrap().item().unwrap(), 5.0 / 6.0);
+    assert_relative_eq!(a.max_all().unwrap().item().unwrap(), 5.0);
+    assert_relative_eq!(a.min_all().unwrap().item().unwrap(), -6.0);
+}
+
+#[test]
+fn test_all_reductions_multi_pass() {
+    let ctx = Context::try_default().unwrap();
+
+    // Enough elements for several passes, with a partial last chunk in each.
+    let len = 3 * 2048 * 2048 + 77;
+    let data: Vec<u32> = (0..1000).cycle().take(len).collect();
+    let a = Tensor::<u32>::from_slice(&ctx, &data).unwrap();
+
+    let expected: u64 = data.iter().map(|&v| u64::from(v)).sum();
+    assert_eq!(
+        u64::from(a.sum_all().unwrap().item().unwrap()),
+        expected % (1 << 32)
+    );
+    assert_eq!(a.max_all().unwrap().item().unwrap(), 999);
+    assert_eq!(a.min_all().unwrap().item().unwrap(), 0);
+
+    let ones = Tensor::<f32>::constant(&ctx, &[len], &[1.0]).unwrap();
+    assert_relative_eq!(
+        ones.mean_all().unwrap().item().unwrap(),
+        1.0,
+        epsilon = 1e-4
+    );
+}
+
+#[test]
+fn test_all_reductions_strided() {
+    let ctx = Context::try_default().unwrap();
+
+    let a = Tensor::<i32>::from_shape_slice(&ctx, &[2, 3], &[1, -2, 3, 4, 5, -6])
+        .unwrap()
+        .permute(&[1, 0])
+        .unwrap();
+    assert_eq!(a.sum_all().unwrap().item().unwrap(), 5);
+    assert_eq!(a.max_all().unwrap().item().unwrap(), 5);
+
+    let narrowed = a.narrow(0, 0, 2).unwrap();
+    assert_eq!(narrowed.sum_all().unwrap().item().unwrap(), 8);
+    assert_eq!(narrowed.min_all().unwrap().item().unwrap(), -2);
+
+    let broadcast = Tensor::<i32>::scalar(&ctx, 7)
+        .unwrap()
+        .broadcast_to(&[4, 5])
+        .unwrap();
+    assert_eq!(broadcast.sum_all().unwrap().item().unwrap(), 140);
+}
+
+#[test]
+fn test_all_reductions_empty() {
+    let ctx = Context::try_default().unwrap();
+
+    let a = Tensor::<f32>::constant(&ctx, &[0, 3], &[0.0]).unwrap();
+    assert_relative_eq!(a.sum_all().unwrap().item().unwrap(), 0.0);
+    assert!(a.mean_all().is_err());
+    assert!(a.max_all().is_err());
+    assert!(a.min_all().is_err());
+}
@@ -0,0 +1,104 @@
+//! Argmax and argmin reduction tests.
+
+use xnn::{Context, Tensor};
+
+#[test]
+fn test_argmax_2d() {
+    let ctx = Context::try_default().unwrap();
+
+    let a =
+        Tensor::<f32>::from_shape_slice(&ctx, &[2, 3], &[1.0, 5.0, 3.0, 9.0, 2.0, 4.0]).unwrap();
+
+    let rows = a.argmax(1).unwrap();
+    assert_eq!(rows.dimensions(), &[2, 1]);
+    assert_eq!(rows.to_vec().unwrap(), vec![1, 0]);
+
+    let cols = a.argmax(0).unwrap();
+    assert_eq!(cols.dimensions(), &[1, 3]);
+    assert_eq!(cols.to_vec().unwrap(), vec![1, 0, 1]);
+}
+
+#[test]
+fn test_argmin_2d() {
+    let ctx = Context::try_default().unwrap();
+
+    let a = Tensor::<i32>::from_shape_slice(&ctx, &[2, 3], &[1, -5, 3, 9, 2, -4]).unwrap();
+
+    assert_eq!(a.argmin(1).unwrap().to_vec().unwrap(), vec![1, 2]);
+    assert_eq!(a.argmin(0).unwrap().to_vec().unwrap(), vec![0, 0, 1]);
+}
+
+#[test]
+fn test_argmax_ties() {
+    let ctx = Context::try_default().unwrap();
+
+    let a = Tensor::<u32>::from_shape_slice(&ctx, &[2, 4], &[1, 7, 7, 0, 3, 3, 3, 3]).unwrap();
+
+    assert_eq!(a.argmax(1).unwrap().to_vec().unwrap(), vec![1, 0]);
+    assert_eq!(a.argmin(1).unwrap().to_vec().unwrap(), vec![3, 0]);
+}
+
+#[test]
+fn test_argmax_3d_middle_axis() {
+    let ctx = Context::try_default().unwrap();
+
+    let data = [1.0, 8.0, 3.0, 4.0, 5.0, 6.0, 7.0, 2.0, 9.0, 10.0, 11.0, 0.0];
+    let a = Tensor::<f32>::from_shape_slice(&ctx, &[2, 2, 3], &data).unwrap();
+
+    let y = a.argmax(1).unwrap();
+    assert_eq!(y.dimensions(), &[2, 1, 3]);
+    assert_eq!(y.to_vec().unwrap(), vec![1, 0, 1, 1, 1, 0]);
+}
+
+#[test]
+fn test_argmax_strided() {
+    let ctx = Context::try_default().unwrap();
+
+    let a = Tensor::<f32>::from_shape_slice(&ctx, &[2, 3], &[1.0, 5.0, 3.0, 9.0, 2.0, 4.0])
+        .unwrap()
+        .transpose(0, 1)
+        .unwrap();
+
+    assert_eq!(a.argmax(1).unwrap().to_vec().unwrap(), vec![1, 0, 1]);
+}
+
+#[test]
+fn test_argmax_long_axis() {
+    let ctx = Context::try_default().unwrap();
+
+    let mut data: Vec<i32> = (0..5000).map(|i| (i * 7919) % 4999).collect();
+    data[4321] = 10_000;
+    data[123] = -10_000;
+    let a = Tensor::<i32>::from_shape_slice(&ctx, &[1, 5000], &data).unwrap();
+
+    assert_eq!(a.argmax(1).unwrap().to_vec().unwrap(), vec![4321]);
+    assert_eq!(a.argmin(1).unwrap().to_vec().unwrap(), vec![123]);
+}
+
+#[test]
+fn test_argmax_many_rows() {
+    let ctx = Context::try_default().unwrap();
+
+    let rows = 70_000u32;
+    let data: Vec<u32> = (0..rows)
+        .flat_map(|r| [r % 3, (r + 1) % 3, (r + 2) % 3])
+        .collect();
+    let a = Tensor::<u32>::from_shape_slice(&ctx, &[rows as usize, 3], &data).unwrap();
+
+    let y = a.argmax(1).unwrap().to_vec().unwrap();
+    assert_eq!(y.len(), 70_000);
+    for (r, &index) in (0..rows).zip(&y) {
+        assert_eq!(index, (2 + 3 - r % 3) % 3);
+    }
+}
+
+#[test]
+fn test_argmax_invalid() {
+    let ctx = Context::try_default().unwrap();
+
+    let a = Tensor::<f32>::from_slice(&ctx, &[1.0, 2.0]).unwrap();
+    assert!(a.argmax(1).is_err());
+
+    let empty = Tensor::<f32>::from_shape_slice(&ctx, &[2, 0], &[]).unwrap();
+    assert!(empty.argmin(1).is_err());
+}
@@ -0,0 +1,157 @@
+//! Tests for `Tensor::cumsum` and `Tensor::cumprod` operations.
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use xnn::{Context, Tensor};
+
+/// CPU running sum along the middle axis of `[outer, size, inner]` `x`.
+fn cpu_cumsum(x: &[f32], [outer, size, inner]: [usize; 3], exclusive: bool) -> Vec<f32> {
+    let mut y = vec![0.0; x.len()];
+    for o in 0..outer {
+        for i in 0..inner {
+            let mut acc = 0.0;
+            for k in 0..size {
+                let idx = (o * size + k) * inner + i;
+                if exclusive {
+                    y[idx] = acc;
+                    acc += x[idx];
+                } else {
+                    acc += x[idx];
+                    y[idx] = acc;
+                }
+            }
+        }
+    }
+    y
+}
+
+#[test]
+fn test_cumsum() {
+    let ctx = Context::try_default().unwrap();
+    let a =
+        Tensor::<f32>::from_shape_slice(&ctx, &[2, 3], &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]).unwrap();
+
+    let rows = a.cumsum(1, false).unwrap();
+    assert_eq!(rows.dimensions(), &[2, 3]);
+    crate::assert_vec_relative_eq(
+        &rows.to_vec().unwrap(),
+        &[1.0, 3.0, 6.0, 4.0, 9.0, 15.0],
+        1e-6,
+    );
+
+    let columns = a.cumsum(0, false).unwrap();
+    crate::assert_vec_relative_eq(
+        &columns.to_vec().unwrap(),
+        &[1.0, 2.0, 3.0, 5.0, 7.0, 9.0],
+        1e-6,
+    );
+
+    let exclusive = a.cumsum(1, true).unwrap();
+    crate::assert_vec_relative_eq(
+        &exclusive.to_vec().unwrap(),
+        &[0.0, 1.0, 3.0, 0.0, 4.0, 9.0],
+        1e-6,
+    );
+}
+
+#[test]
+fn test_cumsum_matches_cpu() {
+    let ctx = Context::try_default().unwrap();
+    let mut rng = StdRng::seed_from_u64(84);
+
+    // Lines shorter than, close to and far longer than a workgroup.
+    for dims in [[3, 7, 2], [1, 256, 1], [2, 1000, 3], [1, 5000, 1]] {
+        let x: Vec<f32> = (0..dims.iter().product())
+            .map(|_| rng.random_range(-1.0..1.0))
+            .collect();
+        let a = Tensor::<f32>::from_shape_slice(&ctx, &dims, &x).unwrap();
+
+        for exclusive in [false, true] {
+            let y = a.cumsum(1, exclusive).unwrap();
+            crate::assert_vec_relative_eq(
+                &y.to_vec().unwrap(),
+                &cpu_cumsum(&x, dims, exclusive),
+                1e-3,
+            );
+        }
+    }
+}
+
+#[test]
+fn test_cumsum_integer() {
+    let ctx = Context::try_default().unwrap();
+    let lengths = Tensor::<u32>::from_slice(&ctx, &[3, 1, 4, 2]).unwrap();
+
+    // Exclusive sums of lengths are offsets into a packed buffer.
+    assert_eq!(
+        lengths.cumsum(0, true).unwrap().to_vec().unwrap(),
+        [0, 3, 4, 8]
+    );
+    assert_eq!(
+        lengths.cumsum(0, false).unwrap().to_vec().unwrap(),
+        [3, 4, 8, 10]
+    );
+
+    let signed = Tensor::<i32>::from_slice(&ctx, &[5, -2, -7]).unwrap();
+    assert_eq!(
+        signed.cumsum(0, false).unwrap().to_vec().unwrap(),
+        [5, 3, -4]
+    );
+}
+
+#[test]
+fn test_cumprod() {
+    let ctx = Context::try_default().unwrap();
+    let a =
+        Tensor::<f32>::from_shape_slice(&ctx, &[2, 3], &[1.0, 2.0, 3.0, -1.0, 0.5, 4.0]).unwrap();
+
+    crate::assert_vec_relative_eq(
+        &a.cumprod(1, false).unwrap().to_vec().unwrap(),
+        &[1.0, 2.0, 6.0, -1.0, -0.5, -2.0],
+        1e-6,
+    );
+    crate::assert_vec_relative_eq(
+        &a.cumprod(1, true).unwrap().to_vec().unwrap(),
+        &[1.0, 1.0, 2.0, 1.0, -1.0, -0.5],
+        1e-6,
+    );
+
+    let long = Tensor::<i32>::constant(&ctx, &[1000], &[1]).unwrap();
+    assert!(
+        long.cumprod(0, false)
+            .unwrap()
+            .to_vec()
+            .unwrap()
+            .iter()
+            .all(|&v| v == 1)
+    );
+}
+
+#[test]
+fn test_cumsum_strided_input() {
+    let ctx = Context::try_default().unwrap();
+    // `[3, 2]` transposed to `[2, 3]`; the input is materialized before the kernel.
+    let a = Tensor::<f32>::from_shape_slice(&ctx, &[3, 2], &[1.0, 10.0, 2.0, 20.0, 3.0, 30.0])
+        .unwrap()
+        .permute(&[1, 0])
+        .unwrap();
+
+    let y = a.cumsum(1, false).unwrap();
+    assert_eq!(y.dimensions(), &[2, 3]);
+    crate::assert_vec_relative_eq(
+        &y.to_vec().unwrap(),
+        &[1.0, 3.0, 6.0, 10.0, 30.0, 60.0],
+        1e-6,
+    );
+}
+
+#[test]
+fn test_cumsum_invalid() {
+    let ctx = Context::try_default().unwrap();
+    let a = Tensor::<f32>::from_slice(&ctx, &[1.0, 2.0]).unwrap();
+    let empty = Tensor::<f32>::constant(&ctx, &[2, 0], &[0.0]).unwrap();
+
+    assert!(a.cumsum(1, false).is_err());
+    assert!(a.cumprod(1, true).is_err());
+    assert_eq!(empty.cumsum(1, false).unwrap().dimensions(), &[2, 0]);
+}
@@ -0,0 +1,85 @@
+//! Tests for `Tensor::gradient_histogram`.
+
+use xnn::{Context, Tensor};
+
+/// Reference histogram of `[rows, features]` bins.
+fn reference(bins: &[u32], features: usize, grad: &[f32], hess: &[f32], n: usize) -> Vec<f32> {
+    let mut hist = vec![0.0f32; features * n * 2];
+    for (row, (g, h)) in grad.iter().zip(hess).enumerate() {
+        for f in 0..features {
+            let bin = usize::try_from(bins[row * features + f]).unwrap();
+            if bin < n {
+                hist[(f * n + bin) * 2] += g;
+                hist[(f * n + bin) * 2 + 1] += h;
+            }
+        }
+    }
+    hist
+}
+
+#[test]
+fn test_gradient_histogram() {
+    let ctx = Context::try_default().unwrap();
+    let bins = [0, 2, 1, 2, 0, 0, 1, 1];
+    let grad = [0.5, -1.0, 2.0, 0.25];
+    let hess = [1.0, 2.0, 3.0, 4.0];
+
+    let binned = Tensor::<u32>::from_shape_slice(&ctx, &[4, 2], &bins).unwrap();
+    let g = Tensor::<f32>::from_slice(&ctx, &grad).unwrap();
+    let h = Tensor::<f32>::from_slice(&ctx, &hess).unwrap();
+
+    let hist = binned.gradient_histogram(&g, &h, 3).unwrap();
+    assert_eq!(hist.dimensions(), &[2, 3, 2]);
+    assert_eq!(
+        hist.to_vec().unwrap(),
+        vec![
+            2.5, 4.0, -0.75, 6.0, 0.0, 0.0, //
+            2.0, 3.0, 0.25, 4.0, -0.5, 3.0,
+        ]
+    );
+}
+
+#[test]
+fn test_gradient_histogram_ignores_out_of_range_bins() {
+    let ctx = Context::try_default().unwrap();
+    let binned = Tensor::<u32>::from_shape_slice(&ctx, &[3, 1], &[0, 7, 1]).unwrap();
+    let g = Tensor::<f32>::from_slice(&ctx, &[1.0, 2.0, 4.0]).unwrap();
+    let h = Tensor::<f32>::constant(&ctx, &[3], &[1.0]).unwrap();
+
+    let hist = binned.gradient_histogram(&g, &h, 2).unwrap();
+    assert_eq!(hist.to_vec().unwrap(), vec![1.0, 1.0, 4.0, 1.0]);
+}
+
+#[test]
+fn test_gradient_histogram_large() {
+    let ctx = Context::try_default().unwrap();
+    let (rows, features, n) = (3000, 5, 16);
+    let bins: Vec<u32> = (0..rows * features)
+        .map(|i| u32::try_from((i * 31 + i / 7) % 17).unwrap())
+        .collect();
+    let grad: Vec<f32> = (0..3000u16).map(|i| f32::from(i % 13) - 6.0).collect();
+    let hess: Vec<f32> = (0..3000u16).map(|i| f32::from(i % 5)).collect();
+
+    let binned = Tensor::<u32>::from_shape_slice(&ctx, &[rows, features], &bins).unwrap();
+    let g = Tensor::<f32>::from_slice(&ctx, &grad).unwrap();
+    let h = Tensor::<f32>::from_slice(&ctx, &hess).unwrap();
+
+    let hist = binned.gradient_histogram(&g, &h, n).unwrap();
+    assert_eq!(
+        hist.to_vec().unwrap(),
+        reference(&bins, features, &grad, &hess, n)
+    );
+}
+
+#[test]
+fn test_gradient_histogram_invalid() {
+    let ctx = Context::try_default().unwrap();
+    let binned = Tensor::<u32>::constant(&ctx, &[4, 2], &[0]).unwrap();
+    let flat = Tensor::<u32>::constant(&ctx, &[4], &[0]).unwrap();
+    let g = Tensor::<f32>::constant(&ctx, &[4], &[1.0]).unwrap();
+    let short = Tensor::<f32>::constant(&ctx, &[3], &[1.0]).unwrap();
+
+    assert!(flat.gradient_histogram(&g, &g, 2).is_err());
+    assert!(binned.gradient_histogram(&short, &g, 2).is_err());
+    assert!(binned.gradient_histogram(&g, &short, 2).is_err());
+}
@@ -0,0 +1,101 @@
+//! Tests for `Tensor::group_sum` and `Tensor::group_mean`.
+
+use xnn::{Context, Tensor};
+
+#[test]
+fn test_group_sum() {
+    let ctx = Context::try_default().unwrap();
+    let values = Tensor::<f32>::from_slice(&ctx, &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]).unwrap();
+    let keys = Tensor::<u32>::from_slice(&ctx, &[2, 0, 2, 1, 0, 2]).unwrap();
+
+    let (sums, counts) = values.group_sum(&keys, 4).unwrap();
+    assert_eq!(sums.dimensions(), &[4]);
+    assert_eq!(sums.to_vec().unwrap(), vec![7.0, 4.0, 10.0, 0.0]);
+    assert_eq!(counts.to_vec().unwrap(), vec![2, 1, 3, 0]);
+}
+
+#[test]
+fn test_group_mean_rows() {
+    let ctx = Context::try_default().unwrap();
+    let values = Tensor::<f32>::from_shape_slice(
+        &ctx,
+        &[4, 2],
+        &[1.0, 10.0, 2.0, 20.0, 3.0, 30.0, 5.0, 50.0],
+    )
+    .unwrap();
+    let keys = Tensor::<u32>::from_slice(&ctx, &[1, 0, 1, 1]).unwrap();
+
+    let (means, counts) = values.group_mean(&keys, 3).unwrap();
+    assert_eq!(means.dimensions(), &[3, 2]);
+    assert_eq!(
+        means.to_vec().unwrap(),
+        vec![2.0, 20.0, 3.0, 30.0, 0.0, 0.0]
+    );
+    assert_eq!(counts.to_vec().unwrap(), vec![1, 3, 0]);
+}
+
+#[test]
+fn test_group_ignores_out_of_range_keys() {
+    let ctx = Context::try_default().unwrap();
+    let values = Tensor::<f32>::from_slice(&ctx, &[1.0, 2.0, 4.0]).unwrap();
+    let keys = Tensor::<u32>::from_slice(&ctx, &[0, 5, u32::MAX]).unwrap();
+
+    let (sums, counts) = values.group_sum(&keys, 2).unwrap();
+    assert_eq!(sums.to_vec().unwrap(), vec![1.0, 0.0]);
+    assert_eq!(counts.to_vec().unwrap(), vec![1, 0]);
+}
+
+#[test]
+fn test_group_strided() {
+    let ctx = Context::try_default().unwrap();
+    let t =
+        Tensor::<f32>::from_shape_slice(&ctx, &[2, 3], &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]).unwrap();
+    let keys = Tensor::<u32>::from_slice(&ctx, &[0, 1, 0]).unwrap();
+
+    let (sums, _) = t.transpose(0, 1).unwrap().group_sum(&keys, 2).unwrap();
+    assert_eq!(sums.to_vec().unwrap(), vec![4.0, 10.0, 2.0, 5.0]);
+}
+
+#[test]
+fn test_group_large() {
+    let ctx = Context::try_default().unwrap();
+    let groups = 37;
+    let data: Vec<f32> = (0..5000u16).map(|i| f32::from(i % 11)).collect();
+    let ids: Vec<u32> = (0..5000u32).map(|i| (i * 7) % 37).collect();
+
+    let values = Tensor::<f32>::from_slice(&ctx, &data).unwrap();
+    let keys = Tensor::<u32>::from_slice(&ctx, &ids).unwrap();
+    let (sums, counts) = values.group_sum(&keys, groups).unwrap();
+
+    let mut expected = vec![0.0f32; groups];
+    let mut expected_counts = vec![0u32; groups];
+    for (v, &k) in data.iter().zip(&ids) {
+        let k = usize::try_from(k).unwrap();
+        expected[k] += v;
+        expected_counts[k] += 1;
+    }
+    assert_eq!(sums.to_vec().unwrap(), expected);
+    assert_eq!(counts.to_vec().unwrap(), expected_counts);
+}
+
+#[test]
+fn test_group_empty() {
+    let ctx = Context::try_default().unwrap();
+    let values = Tensor::<f32>::from_slice(&ctx, &[]).unwrap();
+    let keys = Tensor::<u32>::from_slice(&ctx, &[]).unwrap();
+
+    let (means, counts) = values.group_mean(&keys, 2).unwrap();
+    assert_eq!(means.to_vec().unwrap(), vec![0.0, 0.0]);
+    assert_eq!(counts.to_vec().unwrap(), vec![0, 0]);
+}
+
+#[test]
+fn test_group_invalid() {
+    let ctx = Context::try_default().unwrap();
+    let values = Tensor::<f32>::from_slice(&ctx, &[1.0, 2.0]).unwrap();
+    let keys = Tensor::<u32>::from_slice(&ctx, &[0, 1, 0]).unwrap();
+    let scalar = Tensor::<f32>::scalar(&ctx, 1.0).unwrap();
+
+    assert!(values.group_sum(&keys, 2).is_err());
+    assert!(scalar.group_sum(&keys, 2).is_err());
+}
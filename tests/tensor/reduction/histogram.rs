@@ -0,0 +1,38 @@
+//! Tests for `Tensor::histogram` operation.
+
+use xnn::{Context, Tensor};
+
+#[test]
+fn test_histogram_basic() {
+    let ctx = Context::try_default().unwrap();
+    let t = Tensor::<f32>::from_slice(&ctx, &[0.0, 0.1, 0.5, 0.75, 0.99, 1.0]).unwrap();
+    let result = t.histogram(4, 0.0, 1.0).unwrap();
+    assert_eq!(result.dimensions(), &[4]);
+    assert_eq!(result.to_vec().unwrap(), vec![2, 0, 1, 3]);
+}
+
+#[test]
+fn test_histogram_out_of_range_and_nan() {
+    let ctx = Context::try_default().unwrap();
+    let data = [-10.0, f32::NAN, 3.0, f32::INFINITY, f32::NEG_INFINITY, -1.0];
+    let t = Tensor::<f32>::from_shape_slice(&ctx, &[2, 3], &data).unwrap();
+    let result = t.histogram(2, -1.0, 1.0).unwrap();
+    assert_eq!(result.to_vec().unwrap(), vec![3, 2]);
+}
+
+#[test]
+fn test_histogram_large() {
+    let ctx = Context::try_default().unwrap();
+    let data: Vec<f32> = (0..10_000u16).map(|i| f32::from(i % 10)).collect();
+    let t = Tensor::<f32>::from_slice(&ctx, &data).unwrap();
+    let result = t.histogram(10, 0.0, 10.0).unwrap();
+    assert_eq!(result.to_vec().unwrap(), vec![1000; 10]);
+}
+
+#[test]
+fn test_histogram_invalid() {
+    let ctx = Context::try_default().unwrap();
+    let t = Tensor::<f32>::from_slice(&ctx, &[1.0]).unwrap();
+    assert!(t.histogram(0, 0.0, 1.0).is_err());
+    assert!(t.histogram(4, 2.0, 1.0).is_err());
+}
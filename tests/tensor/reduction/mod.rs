@@ -1,6 +1,19 @@
 //! Reduction operation tests.
 
+mod all;
+mod arg;
+mod cumulative;
+mod gradient_histogram;
+mod group;
+mod histogram;
 mod max;
 mod mean;
 mod min;
+mod moments;
+mod norm;
+mod prod;
+mod segment;
+mod std;
 mod sum;
+mod tiled;
+mod var;
@@ -0,0 +1,100 @@
+//! Single-pass mean and variance tests.
+
+use approx::assert_relative_eq;
+use xnn::{Context, Tensor};
+
+fn assert_approx(actual: &[f32], expected: &[f32], epsilon: f32) {
+    assert_eq!(actual.len(), expected.len(), "length mismatch");
+    for (a, e) in actual.iter().zip(expected.iter()) {
+        assert_relative_eq!(a, e, epsilon = epsilon);
+    }
+}
+
+#[test]
+fn test_moments_rows() {
+    let ctx = Context::try_default().unwrap();
+
+    let a =
+        Tensor::<f32>::from_shape_slice(&ctx, &[2, 3], &[1.0, 2.0, 3.0, 2.0, 4.0, 9.0]).unwrap();
+    let (mean, var) = a.moments(&[1], false).unwrap();
+
+    assert_eq!(mean.dimensions(), &[2, 1]);
+    assert_eq!(var.dimensions(), &[2, 1]);
+    assert_approx(&mean.to_vec().unwrap(), &[2.0, 5.0], 1e-5);
+    assert_approx(&var.to_vec().unwrap(), &[2.0 / 3.0, 26.0 / 3.0], 1e-5);
+
+    let (_, var) = a.moments(&[1], true).unwrap();
+    assert_approx(&var.to_vec().unwrap(), &[1.0, 13.0], 1e-5);
+}
+
+#[test]
+fn test_moments_multiple_axes() {
+    let ctx = Context::try_default().unwrap();
+
+    let data: Vec<f32> = (1..=12u8).map(f32::from).collect();
+    let a = Tensor::<f32>::from_shape_slice(&ctx, &[2, 2, 3], &data).unwrap();
+    let (mean, var) = a.moments(&[0, 2], true).unwrap();
+
+    assert_eq!(mean.dimensions(), &[1, 2, 1]);
+    assert_approx(&mean.to_vec().unwrap(), &[5.0, 8.0], 1e-5);
+    assert_approx(&var.to_vec().unwrap(), &[11.6, 11.6], 1e-5);
+}
+
+#[test]
+fn test_moments_matches_var_reduce_on_view() {
+    let ctx = Context::try_default().unwrap();
+
+    let data: Vec<f32> = (0..24u8).map(|i| f32::from(i * 7 % 11)).collect();
+    let a = Tensor::<f32>::from_shape_slice(&ctx, &[4, 6], &data)
+        .unwrap()
+        .permute(&[1, 0])
+        .unwrap();
+    let (mean, var) = a.moments(&[1], false).unwrap();
+
+    assert_eq!(mean.dimensions(), &[6, 1]);
+    assert_approx(
+        &mean.to_vec().unwrap(),
+        &a.mean_reduce(&[1]).unwrap().to_vec().unwrap(),
+        1e-5,
+    );
+    assert_approx(
+        &var.to_vec().unwrap(),
+        &a.var_reduce(&[1], false).unwrap().to_vec().unwrap(),
+        1e-5,
+    );
+}
+
+#[test]
+fn test_moments_long_rows_with_offset() {
+    let ctx = Context::try_default().unwrap();
+
+    // Large mean relative to the spread, where `E[x²] - E[x]²` would cancel catastrophically.
+    let data: Vec<f32> = (0..2 * 5000u16)
+        .map(|i| 10_000.0 + if i % 2 == 0 { 1.0 } else { -1.0 })
+        .collect();
+    let a = Tensor::<f32>::from_shape_slice(&ctx, &[2, 5000], &data).unwrap();
+    let (mean, var) = a.moments(&[1], false).unwrap();
+
+    assert_approx(&mean.to_vec().unwrap(), &[10_000.0, 10_000.0], 1e-3);
+    assert_approx(&var.to_vec().unwrap(), &[1.0, 1.0], 1e-4);
+}
+
+#[test]
+fn test_moments_no_axes() {
+    let ctx = Context::try_default().unwrap();
+
+    let a = Tensor::<f32>::from_slice(&ctx, &[3.0, -1.0]).unwrap();
+    let (mean, var) = a.moments(&[], false).unwrap();
+
+    assert_eq!(mean.to_vec().unwrap(), vec![3.0, -1.0]);
+    assert_eq!(var.to_vec().unwrap(), vec![0.0, 0.0]);
+}
+
+#[test]
+fn test_moments_invalid_axes() {
+    let ctx = Context::try_default().unwrap();
+
+    let a = Tensor::<f32>::from_shape_slice(&ctx, &[2, 3], &[0.0; 6]).unwrap();
+    assert!(a.moments(&[2], false).is_err());
+    assert!(a.moments(&[0, 0], false).is_err());
+}
@@ -0,0 +1,72 @@
+//! Vector norm and normalization tests.
+
+use xnn::{Context, Tensor};
+
+#[test]
+fn test_norm_orders() {
+    let ctx = Context::try_default().unwrap();
+    let a =
+        Tensor::<f32>::from_shape_slice(&ctx, &[2, 3], &[3.0, -4.0, 0.0, 1.0, 2.0, -2.0]).unwrap();
+
+    for (p, expected) in [
+        (1.0, [7.0, 5.0]),
+        (2.0, [5.0, 3.0]),
+        (f32::INFINITY, [4.0, 2.0]),
+    ] {
+        let result = a.norm(p, &[1]).unwrap();
+        assert_eq!(result.dimensions(), &[2, 1]);
+        crate::assert_vec_relative_eq(&result.to_vec().unwrap(), &expected, 1e-5);
+    }
+
+    let columns = a.norm(1.0, &[0]).unwrap();
+    assert_eq!(columns.dimensions(), &[1, 3]);
+    crate::assert_vec_relative_eq(&columns.to_vec().unwrap(), &[4.0, 6.0, 2.0], 1e-5);
+
+    let total = a.norm(2.0, &[0, 1]).unwrap();
+    crate::assert_vec_relative_eq(&total.to_vec().unwrap(), &[34.0f32.sqrt()], 1e-5);
+}
+
+#[test]
+fn test_norm_general_order() {
+    let ctx = Context::try_default().unwrap();
+    let a = Tensor::<f32>::from_slice(&ctx, &[1.0, -2.0, 3.0]).unwrap();
+
+    let result = a.norm(3.0, &[0]).unwrap();
+    crate::assert_vec_relative_eq(&result.to_vec().unwrap(), &[36.0f32.cbrt()], 1e-4);
+}
+
+#[test]
+fn test_norm_invalid() {
+    let ctx = Context::try_default().unwrap();
+    let a = Tensor::<f32>::from_slice(&ctx, &[1.0, 2.0]).unwrap();
+
+    assert!(a.norm(0.0, &[0]).is_err());
+    assert!(a.norm(-1.0, &[0]).is_err());
+    assert!(a.norm(f32::NAN, &[0]).is_err());
+    assert!(a.norm(2.0, &[1]).is_err());
+}
+
+#[test]
+fn test_normalize() {
+    let ctx = Context::try_default().unwrap();
+    let a =
+        Tensor::<f32>::from_shape_slice(&ctx, &[3, 2], &[3.0, 4.0, 0.0, 0.0, -1.0, 0.0]).unwrap();
+
+    // The all-zero row stays zero instead of dividing by zero.
+    let result = a.normalize(&[1], 1e-12).unwrap();
+    assert_eq!(result.dimensions(), &[3, 2]);
+    crate::assert_vec_relative_eq(
+        &result.to_vec().unwrap(),
+        &[0.6, 0.8, 0.0, 0.0, -1.0, 0.0],
+        1e-5,
+    );
+
+    // Slices smaller than eps are scaled by 1/eps instead.
+    let small = Tensor::<f32>::from_slice(&ctx, &[0.03, 0.04]).unwrap();
+    let result = small.normalize(&[0], 0.1).unwrap();
+    crate::assert_vec_relative_eq(&result.to_vec().unwrap(), &[0.3, 0.4], 1e-5);
+
+    assert!(a.normalize(&[1], -1.0).is_err());
+    assert!(a.normalize(&[1], f32::INFINITY).is_err());
+    assert!(a.normalize(&[2], 1e-12).is_err());
+}
@@ -0,0 +1,103 @@
+//! Product reduction tests.
+
+use approx::assert_relative_eq;
+use xnn::{Context, Tensor};
+
+fn assert_approx(actual: &[f32], expected: &[f32], epsilon: f32) {
+    assert_eq!(actual.len(), expected.len(), "length mismatch");
+    for (a, e) in actual.iter().zip(expected.iter()) {
+        assert_relative_eq!(a, e, epsilon = epsilon);
+    }
+}
+
+#[test]
+fn test_prod_reduce_2d_axis0() {
+    let ctx = Context::try_default().unwrap();
+
+    let a =
+        Tensor::<f32>::from_shape_slice(&ctx, &[2, 3], &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]).unwrap();
+    let result = a.prod_reduce(&[0]).unwrap();
+
+    assert_eq!(result.dimensions(), &[1, 3]);
+    assert_approx(&result.to_vec().unwrap(), &[4.0, 10.0, 18.0], 1e-4);
+}
+
+#[test]
+fn test_prod_reduce_2d_axis1() {
+    let ctx = Context::try_default().unwrap();
+
+    let a =
+        Tensor::<f32>::from_shape_slice(&ctx, &[2, 3], &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]).unwrap();
+    let result = a.prod_reduce(&[1]).unwrap();
+
+    assert_eq!(result.dimensions(), &[2, 1]);
+    assert_approx(&result.to_vec().unwrap(), &[6.0, 120.0], 1e-4);
+}
+
+#[test]
+fn test_prod_reduce_zeros_and_negatives() {
+    let ctx = Context::try_default().unwrap();
+
+    let a =
+        Tensor::<f32>::from_shape_slice(&ctx, &[3, 2], &[-2.0, 3.0, 0.0, -1.0, -0.5, 4.0]).unwrap();
+    let result = a.prod_reduce(&[1]).unwrap();
+
+    assert_eq!(result.dimensions(), &[3, 1]);
+    assert_approx(&result.to_vec().unwrap(), &[-6.0, 0.0, -2.0], 1e-4);
+}
+
+#[test]
+fn test_prod_reduce_3d_multiple_axes() {
+    let ctx = Context::try_default().unwrap();
+
+    let data = [
+        1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0, 11.0, 12.0,
+    ];
+    let a = Tensor::<f32>::from_shape_slice(&ctx, &[2, 2, 3], &data).unwrap();
+    let result = a.prod_reduce(&[0, 2]).unwrap();
+
+    assert_eq!(result.dimensions(), &[1, 2, 1]);
+    assert_approx(
+        &result.to_vec().unwrap(),
+        &[
+            1.0 * 2.0 * 3.0 * 7.0 * 8.0 * 9.0,
+            4.0 * 5.0 * 6.0 * 10.0 * 11.0 * 12.0,
+        ],
+        1e-2,
+    );
+}
+
+#[test]
+fn test_prod_reduce_large() {
+    let ctx = Context::try_default().unwrap();
+
+    let mut data = vec![1.0f32; 1000];
+    data[17] = 2.0;
+    data[500] = -3.0;
+    data[999] = 0.5;
+    let a = Tensor::<f32>::from_slice(&ctx, &data).unwrap();
+    let result = a.prod_reduce(&[0]).unwrap();
+
+    assert_approx(&result.to_vec().unwrap(), &[-3.0], 1e-5);
+}
+
+#[test]
+fn test_prod_reduce_integer() {
+    let ctx = Context::try_default().unwrap();
+
+    let a = Tensor::<i32>::from_shape_slice(&ctx, &[2, 3], &[1, -2, 3, 4, 5, 0]).unwrap();
+    let result = a.prod_reduce(&[1]).unwrap();
+
+    assert_eq!(result.to_vec().unwrap(), vec![-6, 0]);
+}
+
+#[test]
+fn test_prod_reduce_invalid_axis() {
+    let ctx = Context::try_default().unwrap();
+
+    let a =
+        Tensor::<f32>::from_shape_slice(&ctx, &[2, 3], &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]).unwrap();
+
+    assert!(a.prod_reduce(&[5]).is_err());
+    assert!(a.prod_reduce(&[1, 1]).is_err());
+}
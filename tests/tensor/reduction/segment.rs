@@ -0,0 +1,68 @@
+//! Tests for `Tensor::segment_sum` and `Tensor::segment_mean`.
+
+use xnn::{Context, Tensor};
+
+#[test]
+fn test_segment_sum() {
+    let ctx = Context::try_default().unwrap();
+    let values = Tensor::<f32>::from_slice(&ctx, &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]).unwrap();
+    let ids = Tensor::<u32>::from_slice(&ctx, &[0, 0, 2, 2, 2, 3]).unwrap();
+
+    let sums = values.segment_sum(&ids, 4).unwrap();
+    assert_eq!(sums.dimensions(), &[4]);
+    assert_eq!(sums.to_vec().unwrap(), vec![3.0, 0.0, 12.0, 6.0]);
+}
+
+#[test]
+fn test_segment_mean_packed_sequences() {
+    let ctx = Context::try_default().unwrap();
+    let tokens = Tensor::<f32>::from_shape_slice(
+        &ctx,
+        &[5, 2],
+        &[1.0, 10.0, 3.0, 30.0, 2.0, 20.0, 4.0, 40.0, 9.0, 90.0],
+    )
+    .unwrap();
+    let ids = Tensor::<u32>::from_slice(&ctx, &[0, 0, 1, 1, 1]).unwrap();
+
+    let pooled = tokens.segment_mean(&ids, 3).unwrap();
+    assert_eq!(pooled.dimensions(), &[3, 2]);
+    assert_eq!(
+        pooled.to_vec().unwrap(),
+        vec![2.0, 20.0, 5.0, 50.0, 0.0, 0.0]
+    );
+}
+
+#[test]
+fn test_segment_ignores_out_of_range_ids() {
+    let ctx = Context::try_default().unwrap();
+    let values = Tensor::<f32>::from_slice(&ctx, &[1.0, 2.0, 4.0]).unwrap();
+    let ids = Tensor::<u32>::from_slice(&ctx, &[1, 1, 7]).unwrap();
+
+    let sums = values.segment_sum(&ids, 2).unwrap();
+    assert_eq!(sums.to_vec().unwrap(), vec![0.0, 3.0]);
+}
+
+#[test]
+fn test_segment_matches_group() {
+    let ctx = Context::try_default().unwrap();
+    let data: Vec<f32> = (0..3000)
+        .map(|i| f32::from(u16::try_from(i % 17).unwrap()))
+        .collect();
+    let ids: Vec<u32> = (0..1000u32).map(|i| i / 7).collect();
+    let values = Tensor::<f32>::from_shape_slice(&ctx, &[1000, 3], &data).unwrap();
+    let ids = Tensor::<u32>::from_slice(&ctx, &ids).unwrap();
+
+    let segments = values.segment_sum(&ids, 143).unwrap();
+    let (groups, _) = values.group_sum(&ids, 143).unwrap();
+    assert_eq!(segments.to_vec().unwrap(), groups.to_vec().unwrap());
+}
+
+#[test]
+fn test_segment_invalid_shape() {
+    let ctx = Context::try_default().unwrap();
+    let values = Tensor::<f32>::from_slice(&ctx, &[1.0, 2.0, 3.0]).unwrap();
+    let ids = Tensor::<u32>::from_slice(&ctx, &[0, 1]).unwrap();
+
+    assert!(values.segment_sum(&ids, 2).is_err());
+    assert!(values.segment_mean(&ids, 2).is_err());
+}
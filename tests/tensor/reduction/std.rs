@@ -0,0 +1,43 @@
+//! Standard deviation reduction tests.
+
+use approx::assert_relative_eq;
+use xnn::{Context, Tensor};
+
+fn assert_approx(actual: &[f32], expected: &[f32], epsilon: f32) {
+    assert_eq!(actual.len(), expected.len(), "length mismatch");
+    for (a, e) in actual.iter().zip(expected.iter()) {
+        assert_relative_eq!(a, e, epsilon = epsilon);
+    }
+}
+
+#[test]
+fn test_std_reduce() {
+    let ctx = Context::try_default().unwrap();
+
+    let a =
+        Tensor::<f32>::from_shape_slice(&ctx, &[2, 3], &[1.0, 2.0, 3.0, 5.0, 8.0, 3.0]).unwrap();
+    let result = a.std_reduce(&[0], false).unwrap();
+
+    assert_eq!(result.dimensions(), &[1, 3]);
+    assert_approx(&result.to_vec().unwrap(), &[2.0, 3.0, 0.0], 1e-4);
+}
+
+#[test]
+fn test_std_reduce_correction() {
+    let ctx = Context::try_default().unwrap();
+
+    let a = Tensor::<f32>::from_shape_slice(&ctx, &[1, 4], &[2.0, 4.0, 4.0, 6.0]).unwrap();
+    let result = a.std_reduce(&[1], true).unwrap();
+
+    assert_eq!(result.dimensions(), &[1, 1]);
+    assert_approx(&result.to_vec().unwrap(), &[(8.0f32 / 3.0).sqrt()], 1e-4);
+}
+
+#[test]
+fn test_std_reduce_invalid_axis() {
+    let ctx = Context::try_default().unwrap();
+
+    let a = Tensor::<f32>::from_slice(&ctx, &[1.0, 2.0]).unwrap();
+
+    assert!(a.std_reduce(&[1], false).is_err());
+}
@@ -0,0 +1,134 @@
+//! Tests for reductions over long axes, which run as chained multi-pass reductions.
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use xnn::{Context, Tensor};
+
+/// CPU reduction of the middle axis of `[outer, size, inner]` `x` by `op` from `init`.
+fn cpu_reduce(
+    x: &[f32],
+    [outer, size, inner]: [usize; 3],
+    init: f32,
+    op: impl Fn(f32, f32) -> f32,
+) -> Vec<f32> {
+    let mut y = Vec::with_capacity(outer * inner);
+    for o in 0..outer {
+        for i in 0..inner {
+            y.push((0..size).fold(init, |acc, k| op(acc, x[(o * size + k) * inner + i])));
+        }
+    }
+    y
+}
+
+#[test]
+fn test_long_axis_matches_cpu() {
+    let ctx = Context::try_default().unwrap();
+    let mut rng = StdRng::seed_from_u64(86);
+
+    // Last and middle axes, one to three passes per row.
+    for dims in [[3, 5000, 1], [2, 3000, 3], [1, 2049, 2]] {
+        let x: Vec<f32> = (0..dims.iter().product())
+            .map(|_| rng.random_range(-1.0..1.0))
+            .collect();
+        let a = Tensor::<f32>::from_shape_slice(&ctx, &dims, &x).unwrap();
+        let n = f32::from(u16::try_from(dims[1]).unwrap());
+
+        let sum = a.sum_reduce(&[1], false).unwrap();
+        assert_eq!(sum.dimensions(), &[dims[0], 1, dims[2]]);
+        let expected = cpu_reduce(&x, dims, 0.0, |a, b| a + b);
+        crate::assert_vec_relative_eq(&sum.to_vec().unwrap(), &expected, 1e-3);
+
+        let mean: Vec<f32> = expected.iter().map(|s| s / n).collect();
+        crate::assert_vec_relative_eq(&a.mean_reduce(&[1]).unwrap().to_vec().unwrap(), &mean, 1e-4);
+
+        crate::assert_vec_relative_eq(
+            &a.max_reduce(&[1]).unwrap().to_vec().unwrap(),
+            &cpu_reduce(&x, dims, f32::MIN, f32::max),
+            1e-6,
+        );
+        crate::assert_vec_relative_eq(
+            &a.min_reduce(&[1]).unwrap().to_vec().unwrap(),
+            &cpu_reduce(&x, dims, f32::MAX, f32::min),
+            1e-6,
+        );
+
+        let var = a.var_reduce(&[1], true).unwrap().to_vec().unwrap();
+        let squares = cpu_reduce(&x, dims, 0.0, |a, b| a + b * b);
+        for ((v, s), q) in var.iter().zip(&expected).zip(&squares) {
+            let unbiased = (q - s * s / n) / (n - 1.0);
+            assert!((v - unbiased).abs() < 1e-3, "{v} != {unbiased}");
+        }
+    }
+}
+
+#[test]
+fn test_long_axis_prod() {
+    let ctx = Context::try_default().unwrap();
+    let mut x = vec![1.0f32; 4096];
+    x[100] = 2.0;
+    x[3000] = -0.5;
+    x[4095] = 3.0;
+    let a = Tensor::<f32>::from_shape_slice(&ctx, &[2, 2048], &x).unwrap();
+
+    let result = a.prod_reduce(&[0, 1]).unwrap();
+    assert_eq!(result.dimensions(), &[1, 1]);
+    crate::assert_vec_relative_eq(&result.to_vec().unwrap(), &[-3.0], 1e-6);
+}
+
+#[test]
+fn test_long_axes_non_adjacent() {
+    let ctx = Context::try_default().unwrap();
+    // Reducing axes 1 and 3 of `[2, 100, 3, 50]` covers 5000 elements per output.
+    let dims = [2, 100, 3, 50];
+    let x: Vec<f32> = (0..dims.iter().product::<usize>())
+        .map(|i| f32::from(u8::try_from(i * 7 % 13).unwrap()))
+        .collect();
+    let a = Tensor::<f32>::from_shape_slice(&ctx, &dims, &x).unwrap();
+
+    let result = a.sum_reduce(&[3, 1], false).unwrap();
+    assert_eq!(result.dimensions(), &[2, 1, 3, 1]);
+
+    let mut expected = [0.0f32; 6];
+    for (i, v) in x.iter().enumerate() {
+        let (n, c) = (i / (100 * 3 * 50), i / 50 % 3);
+        expected[n * 3 + c] += v;
+    }
+    crate::assert_vec_relative_eq(&result.to_vec().unwrap(), &expected, 1e-5);
+}
+
+#[test]
+fn test_long_axis_integer() {
+    let ctx = Context::try_default().unwrap();
+    // Above `CHUNK²` elements, so three passes chain.
+    let a = Tensor::<i32>::constant(&ctx, &[1, 5_000_000], &[1]).unwrap();
+
+    assert_eq!(
+        a.sum_reduce(&[1], false).unwrap().to_vec().unwrap(),
+        [5_000_000]
+    );
+    assert_eq!(a.max_reduce(&[1]).unwrap().to_vec().unwrap(), [1]);
+}
+
+#[test]
+fn test_many_outputs() {
+    let ctx = Context::try_default().unwrap();
+    // More outputs than workgroups in one grid dimension.
+    let rows = 70_000;
+    let x: Vec<f32> = (0..rows * 2)
+        .map(|i| f32::from(u8::try_from(i % 5).unwrap()))
+        .collect();
+    let a = Tensor::<f32>::from_shape_slice(&ctx, &[rows, 2], &x).unwrap();
+
+    let expected: Vec<f32> = x.chunks(2).map(|pair| pair[0] + pair[1]).collect();
+    crate::assert_vec_relative_eq(
+        &a.sum_reduce(&[1], false).unwrap().to_vec().unwrap(),
+        &expected,
+        1e-6,
+    );
+    let expected: Vec<f32> = x.chunks(2).map(|pair| pair[0].max(pair[1])).collect();
+    crate::assert_vec_relative_eq(
+        &a.max_reduce(&[1]).unwrap().to_vec().unwrap(),
+        &expected,
+        1e-6,
+    );
+}
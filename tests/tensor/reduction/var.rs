@@ -0,0 +1,86 @@
+//! Variance reduction tests.
+
+use approx::assert_relative_eq;
+use xnn::{Context, Tensor};
+
+fn assert_approx(actual: &[f32], expected: &[f32], epsilon: f32) {
+    assert_eq!(actual.len(), expected.len(), "length mismatch");
+    for (a, e) in actual.iter().zip(expected.iter()) {
+        assert_relative_eq!(a, e, epsilon = epsilon);
+    }
+}
+
+#[test]
+fn test_var_reduce_2d_axis0() {
+    let ctx = Context::try_default().unwrap();
+
+    let a =
+        Tensor::<f32>::from_shape_slice(&ctx, &[2, 3], &[1.0, 2.0, 3.0, 5.0, 8.0, 3.0]).unwrap();
+    let result = a.var_reduce(&[0], false).unwrap();
+
+    assert_eq!(result.dimensions(), &[1, 3]);
+    assert_approx(&result.to_vec().unwrap(), &[4.0, 9.0, 0.0], 1e-4);
+}
+
+#[test]
+fn test_var_reduce_2d_axis1() {
+    let ctx = Context::try_default().unwrap();
+
+    let a =
+        Tensor::<f32>::from_shape_slice(&ctx, &[2, 3], &[1.0, 2.0, 3.0, 2.0, 4.0, 9.0]).unwrap();
+    let result = a.var_reduce(&[1], false).unwrap();
+
+    assert_eq!(result.dimensions(), &[2, 1]);
+    assert_approx(&result.to_vec().unwrap(), &[2.0 / 3.0, 26.0 / 3.0], 1e-4);
+}
+
+#[test]
+fn test_var_reduce_correction() {
+    let ctx = Context::try_default().unwrap();
+
+    let a =
+        Tensor::<f32>::from_shape_slice(&ctx, &[2, 3], &[1.0, 2.0, 3.0, 2.0, 4.0, 9.0]).unwrap();
+    let result = a.var_reduce(&[1], true).unwrap();
+
+    assert_eq!(result.dimensions(), &[2, 1]);
+    assert_approx(&result.to_vec().unwrap(), &[1.0, 13.0], 1e-4);
+}
+
+#[test]
+fn test_var_reduce_3d_multiple_axes() {
+    let ctx = Context::try_default().unwrap();
+
+    let data = [
+        1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0, 11.0, 12.0,
+    ];
+    let a = Tensor::<f32>::from_shape_slice(&ctx, &[2, 2, 3], &data).unwrap();
+    let result = a.var_reduce(&[0, 2], true).unwrap();
+
+    assert_eq!(result.dimensions(), &[1, 2, 1]);
+    assert_approx(&result.to_vec().unwrap(), &[11.6, 11.6], 1e-4);
+}
+
+#[test]
+fn test_var_reduce_strided() {
+    let ctx = Context::try_default().unwrap();
+
+    let a = Tensor::<f32>::from_shape_slice(&ctx, &[2, 3], &[1.0, 2.0, 3.0, 5.0, 8.0, 3.0])
+        .unwrap()
+        .transpose(0, 1)
+        .unwrap();
+    let result = a.var_reduce(&[1], false).unwrap();
+
+    assert_eq!(result.dimensions(), &[3, 1]);
+    assert_approx(&result.to_vec().unwrap(), &[4.0, 9.0, 0.0], 1e-4);
+}
+
+#[test]
+fn test_var_reduce_invalid_axis() {
+    let ctx = Context::try_default().unwrap();
+
+    let a =
+        Tensor::<f32>::from_shape_slice(&ctx, &[2, 3], &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]).unwrap();
+
+    assert!(a.var_reduce(&[5], false).is_err());
+    assert!(a.var_reduce(&[1, 1], true).is_err());
+}
@@ -0,0 +1,110 @@
+//! Tests for `Tensor::gae`.
+
+use xnn::{Context, Tensor};
+
+use crate::assert_vec_relative_eq;
+
+/// Reference advantages and returns of `[steps, envs]` rollouts.
+fn gae_reference(
+    rewards: &[f32],
+    values: &[f32],
+    dones: &[f32],
+    last_values: &[f32],
+    gamma: f32,
+    lambda: f32,
+) -> (Vec<f32>, Vec<f32>) {
+    let envs = last_values.len();
+    let steps = rewards.len() / envs;
+    let mut advantages = vec![0.0; rewards.len()];
+
+    for (env, &last_value) in last_values.iter().enumerate() {
+        let mut next_value = last_value;
+        let mut gae = 0.0;
+        for t in (0..steps).rev() {
+            let k = t * envs + env;
+            let live = 1.0 - dones[k];
+            let delta = rewards[k] + gamma * live * next_value - values[k];
+            gae = delta + gamma * lambda * live * gae;
+            advantages[k] = gae;
+            next_value = values[k];
+        }
+    }
+
+    let returns = advantages.iter().zip(values).map(|(a, v)| a + v).collect();
+    (advantages, returns)
+}
+
+#[test]
+fn test_gae() {
+    let ctx = Context::try_default().unwrap();
+    let rewards = [1.0, 0.0, 0.5, 1.0, -1.0, 2.0, 0.0, 0.5];
+    let values = [0.5, 0.2, 0.1, 0.4, -0.3, 0.8, 0.6, 0.0];
+    let dones = [0.0, 0.0, 0.0, 1.0, 1.0, 0.0, 0.0, 0.0];
+    let last_values = [0.7, -0.2];
+
+    let r = Tensor::<f32>::from_shape_slice(&ctx, &[4, 2], &rewards).unwrap();
+    let v = Tensor::<f32>::from_shape_slice(&ctx, &[4, 2], &values).unwrap();
+    let d = Tensor::<f32>::from_shape_slice(&ctx, &[4, 2], &dones).unwrap();
+    let last = Tensor::<f32>::from_slice(&ctx, &last_values).unwrap();
+
+    let (advantages, returns) = r.gae(&v, &d, &last, 0.99, 0.95).unwrap();
+    assert_eq!(advantages.dimensions(), &[4, 2]);
+    assert_eq!(returns.dimensions(), &[4, 2]);
+
+    let (expected_adv, expected_ret) =
+        gae_reference(&rewards, &values, &dones, &last_values, 0.99, 0.95);
+    assert_vec_relative_eq(&advantages.to_vec().unwrap(), &expected_adv, 1e-5);
+    assert_vec_relative_eq(&returns.to_vec().unwrap(), &expected_ret, 1e-5);
+}
+
+#[test]
+fn test_gae_one_step_td() {
+    let ctx = Context::try_default().unwrap();
+    let r = Tensor::<f32>::from_shape_slice(&ctx, &[2, 1], &[1.0, 2.0]).unwrap();
+    let v = Tensor::<f32>::from_shape_slice(&ctx, &[2, 1], &[0.5, 1.0]).unwrap();
+    let d = Tensor::<f32>::from_shape_slice(&ctx, &[2, 1], &[0.0, 0.0]).unwrap();
+    let last = Tensor::<f32>::from_slice(&ctx, &[3.0]).unwrap();
+
+    let (advantages, returns) = r.gae(&v, &d, &last, 0.5, 0.0).unwrap();
+    assert_vec_relative_eq(&advantages.to_vec().unwrap(), &[1.0, 2.5], 1e-6);
+    assert_vec_relative_eq(&returns.to_vec().unwrap(), &[1.5, 3.5], 1e-6);
+}
+
+#[test]
+fn test_gae_transposed() {
+    let ctx = Context::try_default().unwrap();
+    let r = Tensor::<f32>::from_shape_slice(&ctx, &[2, 3], &[1.0, 0.0, 2.0, 0.5, 1.0, 0.0])
+        .unwrap()
+        .transpose(0, 1)
+        .unwrap();
+    let v = Tensor::<f32>::constant(&ctx, &[3, 2], &[0.5]).unwrap();
+    let d = Tensor::<f32>::constant(&ctx, &[3, 2], &[0.0]).unwrap();
+    let last = Tensor::<f32>::from_slice(&ctx, &[1.0, 0.0]).unwrap();
+
+    let (advantages, _) = r.gae(&v, &d, &last, 0.9, 0.8).unwrap();
+    let (expected, _) = gae_reference(
+        &r.to_vec().unwrap(),
+        &[0.5; 6],
+        &[0.0; 6],
+        &[1.0, 0.0],
+        0.9,
+        0.8,
+    );
+    assert_vec_relative_eq(&advantages.to_vec().unwrap(), &expected, 1e-5);
+}
+
+#[test]
+fn test_gae_invalid() {
+    let ctx = Context::try_default().unwrap();
+    let r = Tensor::<f32>::constant(&ctx, &[3, 2], &[0.0]).unwrap();
+    let last = Tensor::<f32>::constant(&ctx, &[2], &[0.0]).unwrap();
+    let flat = Tensor::<f32>::constant(&ctx, &[6], &[0.0]).unwrap();
+    let short = Tensor::<f32>::constant(&ctx, &[2, 2], &[0.0]).unwrap();
+
+    assert!(flat.gae(&flat, &flat, &last, 0.9, 0.9).is_err());
+    assert!(r.gae(&short, &r, &last, 0.9, 0.9).is_err());
+    assert!(r.gae(&r, &short, &last, 0.9, 0.9).is_err());
+    assert!(r.gae(&r, &r, &flat, 0.9, 0.9).is_err());
+    assert!(r.gae(&r, &r, &last, 1.5, 0.9).is_err());
+    assert!(r.gae(&r, &r, &last, 0.9, -0.1).is_err());
+}
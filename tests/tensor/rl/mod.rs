@@ -0,0 +1,4 @@
+//! Reinforcement learning operation tests.
+
+mod gae;
+mod ppo_clip;
@@ -0,0 +1,54 @@
+//! Tests for `Tensor::ppo_clip`.
+
+use xnn::{Context, Tensor};
+
+use crate::assert_vec_relative_eq;
+
+#[test]
+fn test_ppo_clip() {
+    let ctx = Context::try_default().unwrap();
+    let ratio = [1.0, 1.5, 1.5, 0.5, 0.5, 1.1, 0.9, 1.3];
+    let advantages = [2.0, 1.0, -1.0, 1.0, -2.0, -0.5, 0.0, 3.0];
+    let epsilon = 0.2;
+
+    let r = Tensor::<f32>::from_shape_slice(&ctx, &[2, 4], &ratio).unwrap();
+    let a = Tensor::<f32>::from_shape_slice(&ctx, &[2, 4], &advantages).unwrap();
+    let (loss, grad) = r.ppo_clip(&a, epsilon).unwrap();
+    assert_eq!(loss.dimensions(), &[2, 4]);
+    assert_eq!(grad.dimensions(), &[2, 4]);
+
+    let mut expected_loss = Vec::new();
+    let mut expected_grad = Vec::new();
+    for (&r, &a) in ratio.iter().zip(&advantages) {
+        let unclipped = r * a;
+        let clipped = r.clamp(1.0 - epsilon, 1.0 + epsilon) * a;
+        expected_loss.push(-unclipped.min(clipped));
+        expected_grad.push(if unclipped <= clipped { -a } else { 0.0 });
+    }
+
+    assert_vec_relative_eq(&loss.to_vec().unwrap(), &expected_loss, 1e-6);
+    assert_vec_relative_eq(&grad.to_vec().unwrap(), &expected_grad, 1e-6);
+}
+
+#[test]
+fn test_ppo_clip_gradient_cutoff() {
+    let ctx = Context::try_default().unwrap();
+    let r = Tensor::<f32>::from_slice(&ctx, &[1.5, 0.5, 1.5, 0.5]).unwrap();
+    let a = Tensor::<f32>::from_slice(&ctx, &[1.0, -1.0, -1.0, 1.0]).unwrap();
+    let (loss, grad) = r.ppo_clip(&a, 0.2).unwrap();
+
+    assert_vec_relative_eq(&loss.to_vec().unwrap(), &[-1.2, 0.8, 1.5, -0.5], 1e-6);
+    assert_vec_relative_eq(&grad.to_vec().unwrap(), &[0.0, 0.0, 1.0, -1.0], 1e-6);
+}
+
+#[test]
+fn test_ppo_clip_invalid() {
+    let ctx = Context::try_default().unwrap();
+    let r = Tensor::<f32>::from_slice(&ctx, &[1.0, 1.0]).unwrap();
+    let a = Tensor::<f32>::from_slice(&ctx, &[1.0]).unwrap();
+    assert!(r.ppo_clip(&a, 0.2).is_err());
+
+    let a = Tensor::<f32>::from_slice(&ctx, &[1.0, 1.0]).unwrap();
+    assert!(r.ppo_clip(&a, -0.1).is_err());
+    assert!(r.ppo_clip(&a, f32::NAN).is_err());
+}
@@ -0,0 +1,82 @@
+//! Tests for rank-0 scalar tensors and `Tensor::item`.
+
+use approx::assert_relative_eq;
+use xnn::{Context, Tensor};
+
+#[test]
+fn test_scalar() {
+    let ctx = Context::try_default().unwrap();
+    let s = Tensor::<f32>::scalar(&ctx, 2.5).unwrap();
+    assert_eq!(s.dimensions(), &[] as &[usize]);
+    assert_eq!(s.to_vec().unwrap(), vec![2.5]);
+    assert_relative_eq!(s.item().unwrap(), 2.5);
+
+    let u = Tensor::<u32>::scalar(&ctx, 7).unwrap();
+    assert_eq!(u.item().unwrap(), 7);
+}
+
+#[test]
+fn test_scalar_broadcast() {
+    let ctx = Context::try_default().unwrap();
+    let s = Tensor::<f32>::scalar(&ctx, 2.0).unwrap();
+    let t = Tensor::<f32>::from_shape_slice(&ctx, &[2, 2], &[1.0, 2.0, 3.0, 4.0]).unwrap();
+
+    let r = t.mul(&s).unwrap();
+    assert_eq!(r.dimensions(), &[2, 2]);
+    assert_eq!(r.to_vec().unwrap(), vec![2.0, 4.0, 6.0, 8.0]);
+
+    let r = s.add(&s).unwrap();
+    assert_eq!(r.dimensions(), &[] as &[usize]);
+    assert_relative_eq!(r.item().unwrap(), 4.0);
+}
+
+#[test]
+fn test_scalar_reshape() {
+    let ctx = Context::try_default().unwrap();
+    let t = Tensor::<f32>::from_shape_slice(&ctx, &[1, 1], &[3.0]).unwrap();
+
+    let s = t.reshape(&[]).unwrap();
+    assert_eq!(s.dimensions(), &[] as &[usize]);
+    assert_eq!(s.unsqueeze(0).unwrap().dimensions(), &[1]);
+    assert_eq!(t.squeeze().dimensions(), &[] as &[usize]);
+}
+
+#[test]
+fn test_item_reduction() {
+    let ctx = Context::try_default().unwrap();
+    let t =
+        Tensor::<f32>::from_shape_slice(&ctx, &[2, 3], &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]).unwrap();
+
+    let sum = t.sum_reduce(&[0, 1], false).unwrap();
+    assert_eq!(sum.dimensions(), &[1, 1]);
+    assert_relative_eq!(sum.item().unwrap(), 21.0);
+}
+
+#[test]
+fn test_item_view() {
+    let ctx = Context::try_default().unwrap();
+    let t = Tensor::<u32>::from_shape_slice(&ctx, &[2, 3], &[1, 2, 3, 4, 5, 6]).unwrap();
+
+    let element = t.narrow(0, 1, 1).unwrap().narrow(1, 2, 1).unwrap();
+    assert_eq!(element.item().unwrap(), 6);
+
+    let column = t.transpose(0, 1).unwrap().narrow(0, 1, 1).unwrap();
+    assert_eq!(column.narrow(1, 1, 1).unwrap().item().unwrap(), 5);
+}
+
+#[test]
+fn test_item_async() {
+    let ctx = Context::try_default().unwrap();
+    let s = Tensor::<i32>::scalar(&ctx, -4).unwrap();
+    assert_eq!(pollster::block_on(s.item_async()).unwrap(), -4);
+}
+
+#[test]
+fn test_item_invalid() {
+    let ctx = Context::try_default().unwrap();
+    let t = Tensor::<f32>::from_slice(&ctx, &[1.0, 2.0]).unwrap();
+    assert!(t.item().is_err());
+
+    let e = Tensor::<f32>::from_slice(&ctx, &[]).unwrap();
+    assert!(e.item().is_err());
+}
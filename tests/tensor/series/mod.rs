@@ -0,0 +1,4 @@
+//! Time series operation tests.
+
+mod rolling;
+mod shift;
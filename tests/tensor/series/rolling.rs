@@ -0,0 +1,117 @@
+//! Tests for `Tensor::rolling_mean` and `Tensor::rolling_std`.
+
+use xnn::{Context, Tensor};
+
+/// Asserts `actual` matches `expected`, where `None` expects `NaN`.
+fn assert_rolling(actual: &[f32], expected: &[Option<f32>]) {
+    assert_eq!(actual.len(), expected.len(), "length mismatch");
+    for (a, e) in actual.iter().zip(expected) {
+        match e {
+            Some(e) => approx::assert_relative_eq!(a, e, epsilon = 1e-5),
+            None => assert!(a.is_nan(), "expected NaN, got {a}"),
+        }
+    }
+}
+
+#[test]
+fn test_rolling_mean() {
+    let ctx = Context::try_default().unwrap();
+    let x = Tensor::<f32>::from_slice(&ctx, &[1.0, 2.0, 3.0, 4.0, 10.0]).unwrap();
+
+    let y = x.rolling_mean(3).unwrap();
+    assert_eq!(y.dimensions(), &[5]);
+    assert_rolling(
+        &y.to_vec().unwrap(),
+        &[None, None, Some(2.0), Some(3.0), Some(17.0 / 3.0)],
+    );
+
+    let y = x.rolling_mean(1).unwrap();
+    assert_rolling(
+        &y.to_vec().unwrap(),
+        &[Some(1.0), Some(2.0), Some(3.0), Some(4.0), Some(10.0)],
+    );
+}
+
+#[test]
+fn test_rolling_mean_columns() {
+    let ctx = Context::try_default().unwrap();
+    let x =
+        Tensor::<f32>::from_shape_slice(&ctx, &[4, 2], &[1.0, 0.0, 3.0, 2.0, 5.0, 4.0, 7.0, 8.0])
+            .unwrap();
+
+    let y = x.rolling_mean(2).unwrap();
+    assert_eq!(y.dimensions(), &[4, 2]);
+    assert_rolling(
+        &y.to_vec().unwrap(),
+        &[
+            None,
+            None,
+            Some(2.0),
+            Some(1.0),
+            Some(4.0),
+            Some(3.0),
+            Some(6.0),
+            Some(6.0),
+        ],
+    );
+
+    let strided = x.transpose(0, 1).unwrap().rolling_mean(2).unwrap();
+    assert_eq!(strided.dimensions(), &[2, 4]);
+    assert_rolling(
+        &strided.to_vec().unwrap(),
+        &[
+            None,
+            None,
+            None,
+            None,
+            Some(0.5),
+            Some(2.5),
+            Some(4.5),
+            Some(7.5),
+        ],
+    );
+}
+
+#[test]
+fn test_rolling_std() {
+    let ctx = Context::try_default().unwrap();
+    let x = Tensor::<f32>::from_slice(&ctx, &[2.0, 4.0, 4.0, 6.0, 6.0]).unwrap();
+
+    let population = x.rolling_std(2, false).unwrap();
+    assert_rolling(
+        &population.to_vec().unwrap(),
+        &[None, Some(1.0), Some(0.0), Some(1.0), Some(0.0)],
+    );
+
+    let sample = x.rolling_std(4, true).unwrap();
+    assert_rolling(
+        &sample.to_vec().unwrap(),
+        &[
+            None,
+            None,
+            None,
+            Some((8.0f32 / 3.0).sqrt()),
+            Some((4.0f32 / 3.0).sqrt()),
+        ],
+    );
+}
+
+#[test]
+fn test_rolling_window_exceeds_steps() {
+    let ctx = Context::try_default().unwrap();
+    let x = Tensor::<f32>::from_slice(&ctx, &[1.0, 2.0]).unwrap();
+
+    let y = x.rolling_mean(5).unwrap();
+    assert_rolling(&y.to_vec().unwrap(), &[None, None]);
+}
+
+#[test]
+fn test_rolling_invalid() {
+    let ctx = Context::try_default().unwrap();
+    let scalar = Tensor::<f32>::scalar(&ctx, 1.0).unwrap();
+    assert!(scalar.rolling_mean(1).is_err());
+
+    let x = Tensor::<f32>::from_slice(&ctx, &[1.0, 2.0]).unwrap();
+    assert!(x.rolling_mean(0).is_err());
+    assert!(x.rolling_std(0, false).is_err());
+}
@@ -0,0 +1,48 @@
+//! Tests for `Tensor::shift` and `Tensor::lags`.
+
+use xnn::{Context, Tensor};
+
+#[test]
+fn test_shift() {
+    let ctx = Context::try_default().unwrap();
+    let x = Tensor::<u32>::from_slice(&ctx, &[1, 2, 3, 4]).unwrap();
+
+    assert_eq!(x.shift(1, 0).unwrap().to_vec().unwrap(), vec![0, 1, 2, 3]);
+    assert_eq!(x.shift(-2, 9).unwrap().to_vec().unwrap(), vec![3, 4, 9, 9]);
+    assert_eq!(x.shift(0, 0).unwrap().to_vec().unwrap(), vec![1, 2, 3, 4]);
+    assert_eq!(x.shift(7, 5).unwrap().to_vec().unwrap(), vec![5, 5, 5, 5]);
+}
+
+#[test]
+fn test_shift_columns() {
+    let ctx = Context::try_default().unwrap();
+    let x =
+        Tensor::<f32>::from_shape_slice(&ctx, &[3, 2], &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]).unwrap();
+
+    let y = x.shift(1, -1.0).unwrap();
+    assert_eq!(y.dimensions(), &[3, 2]);
+    assert_eq!(y.to_vec().unwrap(), vec![-1.0, -1.0, 1.0, 2.0, 3.0, 4.0]);
+}
+
+#[test]
+fn test_lags() {
+    let ctx = Context::try_default().unwrap();
+    let x = Tensor::<i32>::from_slice(&ctx, &[1, 2, 3, 4]).unwrap();
+
+    let y = x.lags(&[0, 1, 2], -1).unwrap();
+    assert_eq!(y.dimensions(), &[4, 3]);
+    assert_eq!(
+        y.to_vec().unwrap(),
+        vec![1, -1, -1, 2, 1, -1, 3, 2, 1, 4, 3, 2]
+    );
+}
+
+#[test]
+fn test_shift_invalid() {
+    let ctx = Context::try_default().unwrap();
+    let scalar = Tensor::<f32>::scalar(&ctx, 1.0).unwrap();
+    assert!(scalar.shift(1, 0.0).is_err());
+
+    let x = Tensor::<f32>::from_slice(&ctx, &[1.0, 2.0]).unwrap();
+    assert!(x.lags(&[], 0.0).is_err());
+}
@@ -0,0 +1,80 @@
+//! Tests for `Tensor::broadcast_to` operation.
+
+use xnn::{Context, Tensor};
+
+#[test]
+fn test_broadcast_to() {
+    let ctx = Context::try_default().unwrap();
+    let t = Tensor::<u32>::from_shape_slice(&ctx, &[3, 1], &[1, 2, 3]).unwrap();
+
+    let b = t.broadcast_to(&[2, 3, 2]).unwrap();
+    assert_eq!(b.dimensions(), &[2, 3, 2]);
+    assert_eq!(
+        b.to_vec().unwrap(),
+        vec![1, 1, 2, 2, 3, 3, 1, 1, 2, 2, 3, 3]
+    );
+
+    let same = t.broadcast_to(&[3, 1]).unwrap();
+    assert_eq!(same.to_vec().unwrap(), vec![1, 2, 3]);
+}
+
+#[test]
+fn test_broadcast_to_ops() {
+    let ctx = Context::try_default().unwrap();
+    let row = Tensor::<f32>::from_slice(&ctx, &[1.0, 2.0]).unwrap();
+    let x =
+        Tensor::<f32>::from_shape_slice(&ctx, &[3, 2], &[1.0, 0.0, 0.0, 1.0, 1.0, 1.0]).unwrap();
+    let rows = row.broadcast_to(&[3, 2]).unwrap();
+
+    assert_eq!(
+        rows.mul(&x).unwrap().to_vec().unwrap(),
+        vec![1.0, 0.0, 0.0, 2.0, 1.0, 2.0]
+    );
+    assert_eq!(
+        rows.sum_reduce(&[0], false).unwrap().to_vec().unwrap(),
+        vec![3.0, 6.0]
+    );
+    crate::assert_vec_relative_eq(
+        &rows.exp().unwrap().to_vec().unwrap(),
+        &[1.0f32, 2.0, 1.0, 2.0, 1.0, 2.0].map(f32::exp),
+        1e-6,
+    );
+}
+
+#[test]
+fn test_broadcast_to_matmul_batch() {
+    let ctx = Context::try_default().unwrap();
+    let a = Tensor::<f32>::from_shape_slice(&ctx, &[2, 2], &[1.0, 2.0, 3.0, 4.0]).unwrap();
+    let b =
+        Tensor::<f32>::from_shape_slice(&ctx, &[3, 2, 1], &[1.0, 0.0, 0.0, 1.0, 1.0, 1.0]).unwrap();
+
+    let y = a
+        .broadcast_to(&[3, 2, 2])
+        .unwrap()
+        .matmul(&b, false, false)
+        .unwrap();
+    assert_eq!(y.dimensions(), &[3, 2, 1]);
+    assert_eq!(y.to_vec().unwrap(), vec![1.0, 3.0, 2.0, 4.0, 3.0, 7.0]);
+}
+
+#[test]
+fn test_broadcast_to_read_only() {
+    let ctx = Context::try_default().unwrap();
+    let t = Tensor::<f32>::constant(&ctx, &[1, 2], &[0.0]).unwrap();
+    let mut b = t.broadcast_to(&[3, 2]).unwrap();
+    assert!(b.write_slice(0, &[1.0]).is_err());
+}
+
+#[test]
+fn test_broadcast_to_invalid() {
+    let ctx = Context::try_default().unwrap();
+    let t = Tensor::<f32>::constant(&ctx, &[2, 3], &[0.0]).unwrap();
+    assert!(t.broadcast_to(&[3]).is_err());
+    assert!(t.broadcast_to(&[2, 4]).is_err());
+    assert!(t.broadcast_to(&[4, 3]).is_err());
+    assert!(t.broadcast_to(&[2, 0]).is_err());
+
+    let s = Tensor::<f32>::from_slice(&ctx, &[1.0]).unwrap();
+    assert!(s.broadcast_to(&[usize::MAX, usize::MAX, 2]).is_err());
+    assert!(s.broadcast_to(&[usize::MAX, 2, 0]).is_err());
+}
@@ -0,0 +1,63 @@
+//! Tests for `Tensor::contiguous` and `Tensor::is_contiguous` operations.
+
+use xnn::{Context, Tensor};
+
+#[test]
+fn test_contiguous_fresh() {
+    let ctx = Context::try_default().unwrap();
+    let t = Tensor::<u32>::from_shape_slice(&ctx, &[2, 3], &[0, 1, 2, 3, 4, 5]).unwrap();
+    assert!(t.is_contiguous());
+
+    let c = t.contiguous().unwrap();
+    assert!(c.is_contiguous());
+    assert_eq!(c.to_vec().unwrap(), t.to_vec().unwrap());
+}
+
+#[test]
+fn test_contiguous_transpose() {
+    let ctx = Context::try_default().unwrap();
+    let t = Tensor::<u32>::from_shape_slice(&ctx, &[2, 3], &[0, 1, 2, 3, 4, 5])
+        .unwrap()
+        .transpose(0, 1)
+        .unwrap();
+    assert!(!t.is_contiguous());
+
+    let c = t.contiguous().unwrap();
+    assert!(c.is_contiguous());
+    assert_eq!(c.dimensions(), &[3, 2]);
+    assert_eq!(c.to_vec().unwrap(), vec![0, 3, 1, 4, 2, 5]);
+}
+
+#[test]
+fn test_contiguous_offset_and_strides() {
+    let ctx = Context::try_default().unwrap();
+    let data: Vec<i32> = (0..24).collect();
+    let t = Tensor::<i32>::from_shape_slice(&ctx, &[2, 3, 4], &data)
+        .unwrap()
+        .narrow(2, 1, 2)
+        .unwrap()
+        .narrow(1, 1, 2)
+        .unwrap()
+        .permute(&[2, 0, 1])
+        .unwrap();
+    assert!(!t.is_contiguous());
+
+    let c = t.contiguous().unwrap();
+    assert!(c.is_contiguous());
+    assert_eq!(c.dimensions(), &[2, 2, 2]);
+    assert_eq!(c.to_vec().unwrap(), vec![5, 9, 17, 21, 6, 10, 18, 22]);
+}
+
+#[test]
+fn test_contiguous_broadcast() {
+    let ctx = Context::try_default().unwrap();
+    let t = Tensor::<f32>::from_slice(&ctx, &[1.0, 2.0])
+        .unwrap()
+        .broadcast_to(&[3, 2])
+        .unwrap();
+    assert!(!t.is_contiguous());
+
+    let c = t.contiguous().unwrap();
+    assert!(c.is_contiguous());
+    assert_eq!(c.to_vec().unwrap(), vec![1.0, 2.0, 1.0, 2.0, 1.0, 2.0]);
+}
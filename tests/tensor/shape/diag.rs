@@ -0,0 +1,83 @@
+//! Tests for `Tensor::diag` and `Tensor::diagflat` operations.
+
+use xnn::{Context, Tensor};
+
+#[test]
+fn test_diag() {
+    let ctx = Context::try_default().unwrap();
+    let t = Tensor::<f32>::from_shape_slice(
+        &ctx,
+        &[3, 3],
+        &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0],
+    )
+    .unwrap();
+
+    let d = t.diag().unwrap();
+    assert_eq!(d.dimensions(), &[3]);
+    assert_eq!(d.to_vec().unwrap(), vec![1.0, 5.0, 9.0]);
+}
+
+#[test]
+fn test_diag_rectangular() {
+    let ctx = Context::try_default().unwrap();
+    let data: Vec<u32> = (0..6).collect();
+    let t = Tensor::<u32>::from_shape_slice(&ctx, &[2, 3], &data).unwrap();
+
+    assert_eq!(t.diag().unwrap().to_vec().unwrap(), vec![0, 4]);
+    assert_eq!(
+        t.transpose(0, 1).unwrap().diag().unwrap().to_vec().unwrap(),
+        vec![0, 4]
+    );
+    assert_eq!(
+        t.narrow(1, 1, 2).unwrap().diag().unwrap().to_vec().unwrap(),
+        vec![1, 5]
+    );
+}
+
+#[test]
+fn test_diag_batched() {
+    let ctx = Context::try_default().unwrap();
+    let data: Vec<u32> = (0..8).collect();
+    let t = Tensor::<u32>::from_shape_slice(&ctx, &[2, 2, 2], &data).unwrap();
+
+    let d = t.diag().unwrap();
+    assert_eq!(d.dimensions(), &[2, 2]);
+    assert_eq!(d.to_vec().unwrap(), vec![0, 3, 4, 7]);
+}
+
+#[test]
+fn test_diag_invalid() {
+    let ctx = Context::try_default().unwrap();
+    let t = Tensor::<f32>::from_slice(&ctx, &[1.0, 2.0]).unwrap();
+    assert!(t.diag().is_err());
+}
+
+#[test]
+fn test_diagflat() {
+    let ctx = Context::try_default().unwrap();
+    let v = Tensor::<f32>::from_slice(&ctx, &[1.0, 2.0, 3.0]).unwrap();
+
+    let m = v.diagflat().unwrap();
+    assert_eq!(m.dimensions(), &[3, 3]);
+    assert_eq!(
+        m.to_vec().unwrap(),
+        vec![1.0, 0.0, 0.0, 0.0, 2.0, 0.0, 0.0, 0.0, 3.0]
+    );
+    assert_eq!(m.diag().unwrap().to_vec().unwrap(), v.to_vec().unwrap());
+}
+
+#[test]
+fn test_diagflat_flattens() {
+    let ctx = Context::try_default().unwrap();
+    let t = Tensor::<i32>::from_shape_slice(&ctx, &[2, 2], &[1, -2, 3, -4]).unwrap();
+
+    let m = Tensor::diagflat(&t.transpose(0, 1).unwrap()).unwrap();
+    assert_eq!(m.dimensions(), &[4, 4]);
+    assert_eq!(m.diag().unwrap().to_vec().unwrap(), vec![1, 3, -2, -4]);
+
+    let s = Tensor::<f32>::scalar(&ctx, 5.0).unwrap();
+    assert_eq!(s.diagflat().unwrap().to_vec().unwrap(), vec![5.0]);
+
+    let e = Tensor::<f32>::from_slice(&ctx, &[]).unwrap();
+    assert_eq!(e.diagflat().unwrap().dimensions(), &[0, 0]);
+}
@@ -0,0 +1,45 @@
+//! Tests for `Tensor::interleaved_to_sharded` operation.
+
+use xnn::{Context, Tensor};
+
+#[test]
+fn test_interleaved_to_sharded_rows() {
+    let ctx = Context::try_default().unwrap();
+    // Rows interleaved per head as [q0, k0, v0, q1, k1, v1].
+    let data = [0, 1, 10, 11, 20, 21, 2, 3, 12, 13, 22, 23];
+    let t = Tensor::<i32>::from_shape_slice(&ctx, &[6, 2], &data).unwrap();
+    let result = t.interleaved_to_sharded(0, 3, 1).unwrap();
+    assert_eq!(result.dimensions(), &[6, 2]);
+    assert_eq!(
+        result.to_vec().unwrap(),
+        vec![0, 1, 2, 3, 10, 11, 12, 13, 20, 21, 22, 23]
+    );
+}
+
+#[test]
+fn test_interleaved_to_sharded_blocks() {
+    let ctx = Context::try_default().unwrap();
+    let data: Vec<u32> = (0..8).collect();
+    let t = Tensor::<u32>::from_shape_slice(&ctx, &[1, 8], &data).unwrap();
+    let result = t.interleaved_to_sharded(1, 2, 2).unwrap();
+    assert_eq!(result.to_vec().unwrap(), vec![0, 1, 4, 5, 2, 3, 6, 7]);
+}
+
+#[test]
+fn test_interleaved_to_sharded_round_trip() {
+    let ctx = Context::try_default().unwrap();
+    let data: Vec<f32> = (0..48u8).map(f32::from).collect();
+    let t = Tensor::<f32>::from_shape_slice(&ctx, &[2, 12, 2], &data).unwrap();
+    let sharded = t.interleaved_to_sharded(1, 3, 2).unwrap();
+    let restored = sharded.sharded_to_interleaved(1, 3, 2).unwrap();
+    assert_eq!(restored.to_vec().unwrap(), data);
+}
+
+#[test]
+fn test_interleaved_to_sharded_invalid() {
+    let ctx = Context::try_default().unwrap();
+    let t = Tensor::<f32>::from_shape_slice(&ctx, &[6, 2], &[0.0; 12]).unwrap();
+    assert!(t.interleaved_to_sharded(0, 4, 1).is_err());
+    assert!(t.interleaved_to_sharded(0, 3, 0).is_err());
+    assert!(t.interleaved_to_sharded(2, 1, 1).is_err());
+}
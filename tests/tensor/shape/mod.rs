@@ -0,0 +1,22 @@
+//! Shape manipulation tests.
+
+mod broadcast_to;
+mod contiguous;
+mod diag;
+mod interleaved_to_sharded;
+mod narrow;
+mod pad;
+mod permute;
+mod rearrange;
+mod repeat;
+mod reshape;
+mod shard;
+mod sharded_to_interleaved;
+mod split;
+mod squeeze;
+mod stack;
+mod to_memory_format;
+mod transpose;
+mod triangle;
+mod unsqueeze;
+mod view;
@@ -0,0 +1,82 @@
+//! Tests for `Tensor::narrow` operation.
+
+use xnn::{Context, Tensor};
+
+#[test]
+fn test_narrow() {
+    let ctx = Context::try_default().unwrap();
+    let data: Vec<u32> = (0..12).collect();
+    let t = Tensor::<u32>::from_shape_slice(&ctx, &[4, 3], &data).unwrap();
+
+    let rows = t.narrow(0, 1, 2).unwrap();
+    assert_eq!(rows.dimensions(), &[2, 3]);
+    assert_eq!(rows.to_vec().unwrap(), vec![3, 4, 5, 6, 7, 8]);
+
+    let cols = t.narrow(1, 1, 2).unwrap();
+    assert_eq!(cols.dimensions(), &[4, 2]);
+    assert_eq!(cols.to_vec().unwrap(), vec![1, 2, 4, 5, 7, 8, 10, 11]);
+
+    let block = rows.narrow(1, 2, 1).unwrap();
+    assert_eq!(block.to_vec().unwrap(), vec![5, 8]);
+}
+
+#[test]
+fn test_narrow_ops() {
+    let ctx = Context::try_default().unwrap();
+    let data: Vec<f32> = (0..12u8).map(f32::from).collect();
+    let t = Tensor::<f32>::from_shape_slice(&ctx, &[4, 3], &data).unwrap();
+    let batch = t.narrow(0, 2, 2).unwrap();
+    let ones = Tensor::<f32>::constant(&ctx, &[1, 3], &[1.0]).unwrap();
+
+    assert_eq!(
+        batch.add(&ones).unwrap().to_vec().unwrap(),
+        vec![7.0, 8.0, 9.0, 10.0, 11.0, 12.0]
+    );
+    assert_eq!(
+        batch.neg().unwrap().to_vec().unwrap(),
+        vec![-6.0, -7.0, -8.0, -9.0, -10.0, -11.0]
+    );
+    assert_eq!(
+        batch.sum_reduce(&[1], false).unwrap().to_vec().unwrap(),
+        vec![21.0, 30.0]
+    );
+
+    let w = Tensor::<f32>::constant(&ctx, &[3, 1], &[1.0]).unwrap();
+    assert_eq!(
+        batch.matmul(&w, false, false).unwrap().to_vec().unwrap(),
+        vec![21.0, 30.0]
+    );
+    assert_eq!(
+        t.narrow(1, 0, 2)
+            .unwrap()
+            .matmul(&w.narrow(0, 1, 2).unwrap(), false, false)
+            .unwrap()
+            .to_vec()
+            .unwrap(),
+        vec![1.0, 7.0, 13.0, 19.0]
+    );
+}
+
+#[test]
+fn test_narrow_write_slice() {
+    let ctx = Context::try_default().unwrap();
+    let t = Tensor::<u32>::constant(&ctx, &[3, 2], &[0]).unwrap();
+
+    let mut row = t.narrow(0, 1, 1).unwrap();
+    row.write_slice(0, &[7, 8]).unwrap();
+    assert!(row.write_slice(1, &[9, 9]).is_err());
+    assert_eq!(t.to_vec().unwrap(), vec![0, 0, 7, 8, 0, 0]);
+
+    let mut col = t.narrow(1, 1, 1).unwrap();
+    assert!(col.write_slice(0, &[1]).is_err());
+}
+
+#[test]
+fn test_narrow_invalid() {
+    let ctx = Context::try_default().unwrap();
+    let t = Tensor::<f32>::constant(&ctx, &[4, 3], &[0.0]).unwrap();
+    assert!(t.narrow(2, 0, 1).is_err());
+    assert!(t.narrow(0, 5, 0).is_err());
+    assert!(t.narrow(0, 3, 2).is_err());
+    assert!(t.narrow(1, usize::MAX, 2).is_err());
+}
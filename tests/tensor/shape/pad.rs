@@ -0,0 +1,89 @@
+//! Tests for `Tensor::pad` operation.
+
+use xnn::{Context, PadMode, Tensor};
+
+#[test]
+fn test_pad_constant() {
+    let ctx = Context::try_default().unwrap();
+    let t = Tensor::<f32>::from_shape_slice(&ctx, &[2, 2], &[1.0, 2.0, 3.0, 4.0]).unwrap();
+
+    let result = t.pad(&[(1, 0), (1, 2)], PadMode::Constant(9.0)).unwrap();
+    assert_eq!(result.dimensions(), &[3, 5]);
+    assert_eq!(
+        result.to_vec().unwrap(),
+        vec![
+            9.0, 9.0, 9.0, 9.0, 9.0, //
+            9.0, 1.0, 2.0, 9.0, 9.0, //
+            9.0, 3.0, 4.0, 9.0, 9.0,
+        ]
+    );
+}
+
+#[test]
+fn test_pad_reflect() {
+    let ctx = Context::try_default().unwrap();
+    let t = Tensor::<u32>::from_slice(&ctx, &[1, 2, 3]).unwrap();
+
+    let result = t.pad(&[(2, 2)], PadMode::Reflect).unwrap();
+    assert_eq!(result.to_vec().unwrap(), vec![3, 2, 1, 2, 3, 2, 1]);
+
+    assert!(t.pad(&[(3, 0)], PadMode::Reflect).is_err());
+    assert!(t.pad(&[(0, 3)], PadMode::Reflect).is_err());
+}
+
+#[test]
+fn test_pad_replicate() {
+    let ctx = Context::try_default().unwrap();
+    let t = Tensor::<i32>::from_shape_slice(&ctx, &[2, 2], &[1, 2, 3, 4]).unwrap();
+
+    let result = t.pad(&[(0, 1), (2, 1)], PadMode::Replicate).unwrap();
+    assert_eq!(result.dimensions(), &[3, 5]);
+    assert_eq!(
+        result.to_vec().unwrap(),
+        vec![1, 1, 1, 2, 2, 3, 3, 3, 4, 4, 3, 3, 3, 4, 4]
+    );
+}
+
+#[test]
+fn test_pad_image_batch() {
+    let ctx = Context::try_default().unwrap();
+    let data: Vec<f32> = (0..8u8).map(f32::from).collect();
+    let t = Tensor::<f32>::from_shape_slice(&ctx, &[1, 2, 2, 2], &data).unwrap();
+
+    let result = t
+        .pad(&[(0, 0), (0, 0), (1, 1), (1, 1)], PadMode::Constant(0.0))
+        .unwrap();
+    assert_eq!(result.dimensions(), &[1, 2, 4, 4]);
+
+    let out = result.to_vec().unwrap();
+    assert_eq!(&out[4..8], &[0.0, 0.0, 1.0, 0.0]);
+    assert_eq!(&out[24..28], &[0.0, 6.0, 7.0, 0.0]);
+}
+
+#[test]
+fn test_pad_strided() {
+    let ctx = Context::try_default().unwrap();
+    let data: Vec<u32> = (0..6).collect();
+    let t = Tensor::<u32>::from_shape_slice(&ctx, &[2, 3], &data).unwrap();
+
+    let result = t
+        .transpose(0, 1)
+        .unwrap()
+        .narrow(0, 1, 2)
+        .unwrap()
+        .pad(&[(0, 0), (1, 1)], PadMode::Replicate)
+        .unwrap();
+    assert_eq!(result.dimensions(), &[2, 4]);
+    assert_eq!(result.to_vec().unwrap(), vec![1, 1, 4, 4, 2, 2, 5, 5]);
+}
+
+#[test]
+fn test_pad_invalid() {
+    let ctx = Context::try_default().unwrap();
+    let t = Tensor::<f32>::constant(&ctx, &[2, 3], &[0.0]).unwrap();
+    assert!(t.pad(&[(1, 1)], PadMode::Constant(0.0)).is_err());
+    assert!(
+        t.pad(&[(0, 0), (usize::MAX, 1)], PadMode::Replicate)
+            .is_err()
+    );
+}
@@ -0,0 +1,84 @@
+//! Tests for `Tensor::permute` operation.
+
+use xnn::{Context, Tensor};
+
+/// Permutes a row-major `[2, 3, 4]` array on the host.
+fn cpu_permute(data: &[u32], dims: [usize; 3], axes: [usize; 3]) -> Vec<u32> {
+    let strides = [dims[1] * dims[2], dims[2], 1];
+    let out = axes.map(|axis| dims[axis]);
+    let mut result = Vec::new();
+    for i in 0..out[0] {
+        for j in 0..out[1] {
+            for k in 0..out[2] {
+                let coord = [i, j, k];
+                let idx: usize = (0..3).map(|d| coord[d] * strides[axes[d]]).sum();
+                result.push(data[idx]);
+            }
+        }
+    }
+    result
+}
+
+#[test]
+fn test_permute() {
+    let ctx = Context::try_default().unwrap();
+    let data: Vec<u32> = (0..24).collect();
+    let t = Tensor::<u32>::from_shape_slice(&ctx, &[2, 3, 4], &data).unwrap();
+
+    for axes in [[0, 1, 2], [2, 0, 1], [1, 2, 0], [2, 1, 0], [0, 2, 1]] {
+        let p = t.permute(&axes).unwrap();
+        assert_eq!(p.dimensions(), axes.map(|axis| [2, 3, 4][axis]));
+        assert_eq!(p.to_vec().unwrap(), cpu_permute(&data, [2, 3, 4], axes));
+    }
+}
+
+#[test]
+fn test_permute_elementwise() {
+    let ctx = Context::try_default().unwrap();
+    let data: Vec<u32> = (0..24).collect();
+    let t = Tensor::<u32>::from_shape_slice(&ctx, &[2, 3, 4], &data).unwrap();
+    let p = t.permute(&[2, 0, 1]).unwrap();
+    let q = t.permute(&[1, 2, 0]).unwrap().permute(&[1, 2, 0]).unwrap();
+
+    let expected: Vec<u32> = cpu_permute(&data, [2, 3, 4], [2, 0, 1])
+        .iter()
+        .map(|x| x * 2)
+        .collect();
+    assert_eq!(p.add(&q).unwrap().to_vec().unwrap(), expected);
+    assert_eq!(p.copy().unwrap().to_vec().unwrap(), q.to_vec().unwrap());
+}
+
+#[test]
+fn test_permute_batched_matmul() {
+    let ctx = Context::try_default().unwrap();
+    let data: Vec<f32> = (0..24u8).map(f32::from).collect();
+    let t = Tensor::<f32>::from_shape_slice(&ctx, &[2, 3, 4], &data).unwrap();
+
+    let expected = t.matmul(&t, false, true).unwrap().to_vec().unwrap();
+    let p = t.permute(&[0, 2, 1]).unwrap();
+    assert_eq!(
+        t.matmul(&p, false, false).unwrap().to_vec().unwrap(),
+        expected
+    );
+
+    let expected = t.matmul(&t, true, false).unwrap().to_vec().unwrap();
+    let batch_last = t.permute(&[1, 2, 0]).unwrap().permute(&[2, 0, 1]).unwrap();
+    assert_eq!(
+        batch_last
+            .matmul(&t, true, false)
+            .unwrap()
+            .to_vec()
+            .unwrap(),
+        expected
+    );
+}
+
+#[test]
+fn test_permute_invalid() {
+    let ctx = Context::try_default().unwrap();
+    let t = Tensor::<f32>::constant(&ctx, &[2, 3, 4], &[0.0]).unwrap();
+    assert!(t.permute(&[0, 1]).is_err());
+    assert!(t.permute(&[0, 1, 1]).is_err());
+    assert!(t.permute(&[0, 1, 3]).is_err());
+    assert!(t.permute(&[0, 1, 2, 3]).is_err());
+}
@@ -0,0 +1,94 @@
+//! Tests for `Tensor::rearrange` operation.
+
+use xnn::{Context, Tensor};
+
+fn iota(ctx: &Context, dimensions: &[usize]) -> Tensor<u32> {
+    let len = u32::try_from(dimensions.iter().product::<usize>()).unwrap();
+    let data: Vec<u32> = (0..len).collect();
+    Tensor::from_shape_slice(ctx, dimensions, &data).unwrap()
+}
+
+#[test]
+fn test_rearrange_permute() {
+    let ctx = Context::try_default().unwrap();
+    let t = iota(&ctx, &[2, 3, 4, 5]);
+
+    let r = t.rearrange("b h w c -> b c h w", &[]).unwrap();
+    let p = t.permute(&[0, 3, 1, 2]).unwrap();
+    assert_eq!(r.dimensions(), &[2, 5, 3, 4]);
+    assert_eq!(r.to_vec().unwrap(), p.to_vec().unwrap());
+}
+
+#[test]
+fn test_rearrange_merge() {
+    let ctx = Context::try_default().unwrap();
+    let t = iota(&ctx, &[2, 3, 4]);
+
+    let r = t.rearrange("b c hw -> b (c hw)", &[]).unwrap();
+    assert_eq!(r.dimensions(), &[2, 12]);
+    assert_eq!(r.to_vec().unwrap(), t.to_vec().unwrap());
+
+    let r = t.rearrange("a b c -> (c a) b", &[]).unwrap();
+    let expected = t.permute(&[2, 0, 1]).unwrap().reshape(&[8, 3]).unwrap();
+    assert_eq!(r.dimensions(), &[8, 3]);
+    assert_eq!(r.to_vec().unwrap(), expected.to_vec().unwrap());
+}
+
+#[test]
+fn test_rearrange_split() {
+    let ctx = Context::try_default().unwrap();
+    let t = iota(&ctx, &[2, 12, 5]);
+
+    let r = t.rearrange("b (h w) c -> b c h w", &[("h", 3)]).unwrap();
+    let expected = t
+        .reshape(&[2, 3, 4, 5])
+        .unwrap()
+        .permute(&[0, 3, 1, 2])
+        .unwrap();
+    assert_eq!(r.dimensions(), &[2, 5, 3, 4]);
+    assert_eq!(r.to_vec().unwrap(), expected.to_vec().unwrap());
+
+    let r = t
+        .rearrange("b (heads d) c -> (b heads) c d", &[("heads", 2), ("d", 6)])
+        .unwrap();
+    let expected = t
+        .reshape(&[2, 2, 6, 5])
+        .unwrap()
+        .permute(&[0, 1, 3, 2])
+        .unwrap()
+        .reshape(&[4, 5, 6])
+        .unwrap();
+    assert_eq!(r.to_vec().unwrap(), expected.to_vec().unwrap());
+}
+
+#[test]
+fn test_rearrange_unit_axes() {
+    let ctx = Context::try_default().unwrap();
+    let t = iota(&ctx, &[3, 1, 4]);
+
+    let r = t.rearrange("a () b -> b a", &[]).unwrap();
+    assert_eq!(r.dimensions(), &[4, 3]);
+
+    let r = t.rearrange("a one b -> a b () one", &[]).unwrap();
+    assert_eq!(r.dimensions(), &[3, 4, 1, 1]);
+}
+
+#[test]
+fn test_rearrange_invalid() {
+    let ctx = Context::try_default().unwrap();
+    let t = iota(&ctx, &[2, 6]);
+
+    assert!(t.rearrange("a b", &[]).is_err());
+    assert!(t.rearrange("a b c -> a b c", &[]).is_err());
+    assert!(t.rearrange("a b -> a", &[]).is_err());
+    assert!(t.rearrange("a b -> a b c", &[]).is_err());
+    assert!(t.rearrange("a a -> a a", &[]).is_err());
+    assert!(t.rearrange("a (b -> a b", &[]).is_err());
+    assert!(t.rearrange("a b) -> a b", &[]).is_err());
+    assert!(t.rearrange("a ((b c)) -> a b c", &[]).is_err());
+    assert!(t.rearrange("a 1b -> a 1b", &[]).is_err());
+    assert!(t.rearrange("a b -> a b", &[("c", 2)]).is_err());
+    assert!(t.rearrange("a b -> b a", &[("a", 3)]).is_err());
+    assert!(t.rearrange("a (b c) -> a b c", &[]).is_err());
+    assert!(t.rearrange("a (b c) -> a b c", &[("b", 4)]).is_err());
+}
@@ -0,0 +1,82 @@
+//! Tests for `Tensor::repeat` operation.
+
+use xnn::{Context, Tensor};
+
+#[test]
+fn test_repeat() {
+    let ctx = Context::try_default().unwrap();
+    let t = Tensor::<u32>::from_shape_slice(&ctx, &[2, 2], &[1, 2, 3, 4]).unwrap();
+
+    let r = t.repeat(&[2, 3]).unwrap();
+    assert_eq!(r.dimensions(), &[4, 6]);
+    assert_eq!(
+        r.to_vec().unwrap(),
+        vec![
+            1, 2, 1, 2, 1, 2, 3, 4, 3, 4, 3, 4, 1, 2, 1, 2, 1, 2, 3, 4, 3, 4, 3, 4
+        ]
+    );
+}
+
+#[test]
+fn test_repeat_leading_axes() {
+    let ctx = Context::try_default().unwrap();
+    let t = Tensor::<f32>::from_slice(&ctx, &[1.0, 2.0]).unwrap();
+
+    let r = t.repeat(&[2, 1, 2]).unwrap();
+    assert_eq!(r.dimensions(), &[2, 1, 4]);
+    assert_eq!(
+        r.to_vec().unwrap(),
+        vec![1.0, 2.0, 1.0, 2.0, 1.0, 2.0, 1.0, 2.0]
+    );
+}
+
+#[test]
+fn test_repeat_strided() {
+    let ctx = Context::try_default().unwrap();
+    let t = Tensor::<i32>::from_shape_slice(&ctx, &[2, 3], &[1, 2, 3, 4, 5, 6])
+        .unwrap()
+        .transpose(0, 1)
+        .unwrap();
+
+    let r = t.repeat(&[1, 2]).unwrap();
+    assert_eq!(r.dimensions(), &[3, 4]);
+    assert_eq!(
+        r.to_vec().unwrap(),
+        vec![1, 4, 1, 4, 2, 5, 2, 5, 3, 6, 3, 6]
+    );
+
+    let w = Tensor::<f32>::constant(&ctx, &[4, 1], &[1.0]).unwrap();
+    let x = Tensor::<f32>::from_slice(&ctx, &[1.0, 2.0])
+        .unwrap()
+        .reshape(&[1, 2])
+        .unwrap()
+        .repeat(&[3, 2])
+        .unwrap();
+    assert_eq!(
+        x.matmul(&w, false, false).unwrap().to_vec().unwrap(),
+        vec![6.0, 6.0, 6.0]
+    );
+}
+
+#[test]
+fn test_repeat_zero() {
+    let ctx = Context::try_default().unwrap();
+    let t = Tensor::<f32>::constant(&ctx, &[2, 3], &[1.0]).unwrap();
+
+    let r = t.repeat(&[1, 0]).unwrap();
+    assert_eq!(r.dimensions(), &[2, 0]);
+    assert!(r.to_vec().unwrap().is_empty());
+
+    let r = t.repeat(&[0, 2, 1]).unwrap();
+    assert_eq!(r.dimensions(), &[0, 4, 3]);
+    assert!(r.to_vec().unwrap().is_empty());
+}
+
+#[test]
+fn test_repeat_invalid() {
+    let ctx = Context::try_default().unwrap();
+    let t = Tensor::<f32>::constant(&ctx, &[2, 3], &[0.0]).unwrap();
+    assert!(t.repeat(&[2]).is_err());
+    assert!(t.repeat(&[usize::MAX, 1]).is_err());
+    assert!(t.repeat(&[usize::MAX / 2, usize::MAX / 4]).is_err());
+}
@@ -0,0 +1,78 @@
+//! Tests for `Tensor::reshape` operation.
+
+use xnn::{Context, INFER, Tensor};
+
+#[test]
+fn test_reshape() {
+    let ctx = Context::try_default().unwrap();
+    let t =
+        Tensor::<f32>::from_shape_slice(&ctx, &[2, 3], &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]).unwrap();
+    let r = t.reshape(&[3, 2]).unwrap();
+    assert_eq!(r.dimensions(), &[3, 2]);
+    assert_eq!(r.to_vec().unwrap(), vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+
+    let r = t.reshape(&[6]).unwrap().reshape(&[1, 2, 1, 3]).unwrap();
+    assert_eq!(r.dimensions(), &[1, 2, 1, 3]);
+}
+
+#[test]
+fn test_reshape_then_compute() {
+    let ctx = Context::try_default().unwrap();
+    let t = Tensor::<i32>::from_shape_slice(&ctx, &[4], &[1, 2, 3, 4]).unwrap();
+    let r = t.reshape(&[2, 2]).unwrap();
+    let sum = r.sum_reduce(&[1], false).unwrap();
+    assert_eq!(sum.dimensions(), &[2, 1]);
+    assert_eq!(sum.to_vec().unwrap(), vec![3, 7]);
+}
+
+#[test]
+fn test_reshape_scalar() {
+    let ctx = Context::try_default().unwrap();
+    let t = Tensor::<f32>::from_slice(&ctx, &[5.0]).unwrap();
+    let r = t.reshape(&[]).unwrap();
+    assert_eq!(r.dimensions(), &[] as &[usize]);
+    assert_eq!(r.reshape(&[1, 1]).unwrap().to_vec().unwrap(), vec![5.0]);
+}
+
+#[test]
+fn test_reshape_invalid() {
+    let ctx = Context::try_default().unwrap();
+    let t = Tensor::<f32>::from_shape_slice(&ctx, &[2, 3], &[0.0; 6]).unwrap();
+    assert!(t.reshape(&[4]).is_err());
+    assert!(t.reshape(&[6, 0]).is_err());
+}
+
+#[test]
+fn test_reshape_infer() {
+    let ctx = Context::try_default().unwrap();
+    let data: Vec<f32> = (0..24u8).map(f32::from).collect();
+    let t = Tensor::<f32>::from_shape_slice(&ctx, &[2, 3, 4], &data).unwrap();
+
+    let r = t.reshape(&[INFER, 4]).unwrap();
+    assert_eq!(r.dimensions(), &[6, 4]);
+    assert_eq!(r.to_vec().unwrap(), data);
+
+    assert_eq!(t.reshape(&[2, INFER]).unwrap().dimensions(), &[2, 12]);
+    assert_eq!(t.reshape(&[INFER]).unwrap().dimensions(), &[24]);
+    assert_eq!(t.view(&[4, INFER, 3]).unwrap().dimensions(), &[4, 2, 3]);
+
+    let p = t.permute(&[2, 0, 1]).unwrap().reshape(&[INFER, 6]).unwrap();
+    assert_eq!(p.dimensions(), &[4, 6]);
+}
+
+#[test]
+fn test_reshape_infer_empty() {
+    let ctx = Context::try_default().unwrap();
+    let t = Tensor::<f32>::constant(&ctx, &[0, 8], &[0.0]).unwrap();
+    assert_eq!(t.reshape(&[INFER, 4]).unwrap().dimensions(), &[0, 4]);
+    assert!(t.reshape(&[0, INFER]).is_err());
+}
+
+#[test]
+fn test_reshape_infer_invalid() {
+    let ctx = Context::try_default().unwrap();
+    let t = Tensor::<f32>::from_shape_slice(&ctx, &[2, 3], &[0.0; 6]).unwrap();
+    assert!(t.reshape(&[INFER, 4]).is_err());
+    assert!(t.reshape(&[INFER, INFER]).is_err());
+    assert!(t.reshape(&[INFER, usize::MAX / 2, 4]).is_err());
+}
@@ -0,0 +1,44 @@
+//! Tests for `Tensor::shard` operation.
+
+use xnn::{Context, Tensor};
+
+#[test]
+fn test_shard_fused_qkv() {
+    let ctx = Context::try_default().unwrap();
+    let data: Vec<f32> = (0..12u8).map(f32::from).collect();
+    let t = Tensor::<f32>::from_shape_slice(&ctx, &[6, 2], &data).unwrap();
+    let parts = t.shard(0, 3).unwrap();
+    assert_eq!(parts.len(), 3);
+    for (i, part) in parts.iter().enumerate() {
+        assert_eq!(part.dimensions(), &[2, 2]);
+        assert_eq!(part.to_vec().unwrap(), data[i * 4..(i + 1) * 4]);
+    }
+}
+
+#[test]
+fn test_shard_inner_axis() {
+    let ctx = Context::try_default().unwrap();
+    let t = Tensor::<i32>::from_shape_slice(&ctx, &[2, 4], &[1, 2, 3, 4, 5, 6, 7, 8]).unwrap();
+    let parts = t.shard(1, 2).unwrap();
+    assert_eq!(parts[0].dimensions(), &[2, 2]);
+    assert_eq!(parts[0].to_vec().unwrap(), vec![1, 2, 5, 6]);
+    assert_eq!(parts[1].to_vec().unwrap(), vec![3, 4, 7, 8]);
+}
+
+#[test]
+fn test_shard_bool() {
+    let ctx = Context::try_default().unwrap();
+    let t = Tensor::<bool>::from_slice(&ctx, &[true, false, false, true]).unwrap();
+    let parts = t.shard(0, 4).unwrap();
+    let values: Vec<bool> = parts.iter().map(|p| p.to_vec().unwrap()[0]).collect();
+    assert_eq!(values, vec![true, false, false, true]);
+}
+
+#[test]
+fn test_shard_invalid() {
+    let ctx = Context::try_default().unwrap();
+    let t = Tensor::<f32>::from_shape_slice(&ctx, &[3, 2], &[0.0; 6]).unwrap();
+    assert!(t.shard(0, 2).is_err());
+    assert!(t.shard(0, 0).is_err());
+    assert!(t.shard(2, 1).is_err());
+}
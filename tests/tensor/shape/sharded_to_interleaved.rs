@@ -0,0 +1,36 @@
+//! Tests for `Tensor::sharded_to_interleaved` operation.
+
+use xnn::{Context, Tensor};
+
+#[test]
+fn test_sharded_to_interleaved_rows() {
+    let ctx = Context::try_default().unwrap();
+    let data = [0, 1, 2, 3, 10, 11, 12, 13, 20, 21, 22, 23];
+    let t = Tensor::<i32>::from_shape_slice(&ctx, &[6, 2], &data).unwrap();
+    let result = t.sharded_to_interleaved(0, 3, 1).unwrap();
+    assert_eq!(result.dimensions(), &[6, 2]);
+    assert_eq!(
+        result.to_vec().unwrap(),
+        vec![0, 1, 10, 11, 20, 21, 2, 3, 12, 13, 22, 23]
+    );
+}
+
+#[test]
+fn test_sharded_to_interleaved_blocks() {
+    let ctx = Context::try_default().unwrap();
+    let data: Vec<u32> = (0..12).collect();
+    let t = Tensor::<u32>::from_slice(&ctx, &data).unwrap();
+    let result = t.sharded_to_interleaved(0, 2, 3).unwrap();
+    assert_eq!(
+        result.to_vec().unwrap(),
+        vec![0, 1, 2, 6, 7, 8, 3, 4, 5, 9, 10, 11]
+    );
+}
+
+#[test]
+fn test_sharded_to_interleaved_invalid() {
+    let ctx = Context::try_default().unwrap();
+    let t = Tensor::<f32>::from_slice(&ctx, &[0.0; 6]).unwrap();
+    assert!(t.sharded_to_interleaved(0, 4, 1).is_err());
+    assert!(t.sharded_to_interleaved(1, 1, 1).is_err());
+}
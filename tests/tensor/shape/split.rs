@@ -0,0 +1,70 @@
+//! Tests for `Tensor::split` and `Tensor::chunk` operations.
+
+use xnn::{Context, Tensor};
+
+#[test]
+fn test_split() {
+    let ctx = Context::try_default().unwrap();
+    let data: Vec<u32> = (0..12).collect();
+    let t = Tensor::<u32>::from_shape_slice(&ctx, &[2, 6], &data).unwrap();
+
+    let parts = t.split(&[1, 3, 2], 1).unwrap();
+    assert_eq!(parts.len(), 3);
+    assert_eq!(parts[0].dimensions(), &[2, 1]);
+    assert_eq!(parts[0].to_vec().unwrap(), vec![0, 6]);
+    assert_eq!(parts[1].dimensions(), &[2, 3]);
+    assert_eq!(parts[1].to_vec().unwrap(), vec![1, 2, 3, 7, 8, 9]);
+    assert_eq!(parts[2].to_vec().unwrap(), vec![4, 5, 10, 11]);
+
+    let rows = t.split(&[1, 1], 0).unwrap();
+    assert_eq!(rows[1].to_vec().unwrap(), vec![6, 7, 8, 9, 10, 11]);
+}
+
+#[test]
+fn test_split_heads() {
+    let ctx = Context::try_default().unwrap();
+    let data: Vec<f32> = (0..8u8).map(f32::from).collect();
+    let qkv = Tensor::<f32>::from_shape_slice(&ctx, &[2, 4], &data).unwrap();
+
+    let heads = qkv.chunk(2, 1).unwrap();
+    let w = Tensor::<f32>::constant(&ctx, &[2, 1], &[1.0]).unwrap();
+    assert_eq!(
+        heads[1].matmul(&w, false, false).unwrap().to_vec().unwrap(),
+        vec![5.0, 13.0]
+    );
+}
+
+#[test]
+fn test_chunk() {
+    let ctx = Context::try_default().unwrap();
+    let data: Vec<u32> = (0..5).collect();
+    let t = Tensor::<u32>::from_slice(&ctx, &data).unwrap();
+
+    let parts = t.chunk(2, 0).unwrap();
+    assert_eq!(parts.len(), 2);
+    assert_eq!(parts[0].to_vec().unwrap(), vec![0, 1, 2]);
+    assert_eq!(parts[1].to_vec().unwrap(), vec![3, 4]);
+
+    let parts = t.chunk(5, 0).unwrap();
+    assert_eq!(parts.len(), 5);
+    assert_eq!(parts[4].to_vec().unwrap(), vec![4]);
+
+    let parts = t.chunk(4, 0).unwrap();
+    assert_eq!(parts.len(), 3);
+    assert_eq!(parts[2].to_vec().unwrap(), vec![4]);
+
+    let parts = t.chunk(8, 0).unwrap();
+    assert_eq!(parts.len(), 5);
+}
+
+#[test]
+fn test_split_invalid() {
+    let ctx = Context::try_default().unwrap();
+    let t = Tensor::<f32>::constant(&ctx, &[4, 3], &[0.0]).unwrap();
+    assert!(t.split(&[2, 2], 2).is_err());
+    assert!(t.split(&[2, 1], 0).is_err());
+    assert!(t.split(&[4, 1], 0).is_err());
+    assert!(t.split(&[usize::MAX, 5], 0).is_err());
+    assert!(t.chunk(0, 0).is_err());
+    assert!(t.chunk(2, 2).is_err());
+}
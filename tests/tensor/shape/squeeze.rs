@@ -0,0 +1,53 @@
+//! Tests for `Tensor::squeeze` and `Tensor::squeeze_dim` operations.
+
+use xnn::{Context, Tensor};
+
+#[test]
+fn test_squeeze() {
+    let ctx = Context::try_default().unwrap();
+    let t = Tensor::<u32>::from_shape_slice(&ctx, &[1, 3, 1, 2], &[0, 1, 2, 3, 4, 5]).unwrap();
+
+    let s = t.squeeze();
+    assert_eq!(s.dimensions(), &[3, 2]);
+    assert_eq!(s.to_vec().unwrap(), vec![0, 1, 2, 3, 4, 5]);
+
+    let s = t.squeeze_dim(2).unwrap();
+    assert_eq!(s.dimensions(), &[1, 3, 2]);
+    assert_eq!(s.to_vec().unwrap(), vec![0, 1, 2, 3, 4, 5]);
+}
+
+#[test]
+fn test_squeeze_to_scalar() {
+    let ctx = Context::try_default().unwrap();
+    let t = Tensor::<f32>::from_shape_slice(&ctx, &[1, 1], &[3.0]).unwrap();
+    let s = t.squeeze();
+    assert_eq!(s.dimensions(), &[] as &[usize]);
+    assert_eq!(s.to_vec().unwrap(), vec![3.0]);
+}
+
+#[test]
+fn test_squeeze_reduced() {
+    let ctx = Context::try_default().unwrap();
+    let t =
+        Tensor::<f32>::from_shape_slice(&ctx, &[2, 3], &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]).unwrap();
+    let sum = t.sum_reduce(&[1], false).unwrap().squeeze_dim(1).unwrap();
+    assert_eq!(sum.dimensions(), &[2]);
+    assert_eq!(sum.to_vec().unwrap(), vec![6.0, 15.0]);
+}
+
+#[test]
+fn test_squeeze_strided() {
+    let ctx = Context::try_default().unwrap();
+    let t = Tensor::<u32>::from_shape_slice(&ctx, &[2, 1, 3], &[0, 1, 2, 3, 4, 5]).unwrap();
+    let s = t.permute(&[2, 1, 0]).unwrap().squeeze();
+    assert_eq!(s.dimensions(), &[3, 2]);
+    assert_eq!(s.to_vec().unwrap(), vec![0, 3, 1, 4, 2, 5]);
+}
+
+#[test]
+fn test_squeeze_dim_invalid() {
+    let ctx = Context::try_default().unwrap();
+    let t = Tensor::<f32>::constant(&ctx, &[1, 3], &[0.0]).unwrap();
+    assert!(t.squeeze_dim(1).is_err());
+    assert!(t.squeeze_dim(2).is_err());
+}
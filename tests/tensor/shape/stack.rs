@@ -0,0 +1,71 @@
+//! Tests for `Tensor::stack` operation.
+
+use xnn::{Context, Tensor};
+
+#[test]
+fn test_stack_axis_0() {
+    let ctx = Context::try_default().unwrap();
+    let a = Tensor::<u32>::from_shape_slice(&ctx, &[2, 2], &[0, 1, 2, 3]).unwrap();
+    let b = Tensor::<u32>::from_shape_slice(&ctx, &[2, 2], &[4, 5, 6, 7]).unwrap();
+    let c = Tensor::<u32>::from_shape_slice(&ctx, &[2, 2], &[8, 9, 10, 11]).unwrap();
+
+    let y = Tensor::stack(&[&a, &b, &c], 0).unwrap();
+    assert_eq!(y.dimensions(), &[3, 2, 2]);
+    assert_eq!(y.to_vec().unwrap(), (0..12).collect::<Vec<_>>());
+}
+
+#[test]
+fn test_stack_inner_axes() {
+    let ctx = Context::try_default().unwrap();
+    let a = Tensor::<u32>::from_shape_slice(&ctx, &[2, 3], &[0, 1, 2, 3, 4, 5]).unwrap();
+    let b = Tensor::<u32>::from_shape_slice(&ctx, &[2, 3], &[6, 7, 8, 9, 10, 11]).unwrap();
+
+    let y = Tensor::stack(&[&a, &b], 1).unwrap();
+    assert_eq!(y.dimensions(), &[2, 2, 3]);
+    assert_eq!(
+        y.to_vec().unwrap(),
+        vec![0, 1, 2, 6, 7, 8, 3, 4, 5, 9, 10, 11]
+    );
+
+    let y = Tensor::stack(&[&a, &b], 2).unwrap();
+    assert_eq!(y.dimensions(), &[2, 3, 2]);
+    assert_eq!(
+        y.to_vec().unwrap(),
+        vec![0, 6, 1, 7, 2, 8, 3, 9, 4, 10, 5, 11]
+    );
+}
+
+#[test]
+fn test_stack_views_and_bool() {
+    let ctx = Context::try_default().unwrap();
+    let data: Vec<f32> = (0..6u8).map(f32::from).collect();
+    let t = Tensor::<f32>::from_shape_slice(&ctx, &[2, 3], &data).unwrap();
+    let transposed = t.transpose(0, 1).unwrap();
+    let rows = Tensor::<f32>::from_shape_slice(&ctx, &[3, 2], &data).unwrap();
+
+    let y = Tensor::stack(&[&transposed, &rows], 0).unwrap();
+    assert_eq!(
+        y.to_vec().unwrap(),
+        vec![0.0, 3.0, 1.0, 4.0, 2.0, 5.0, 0.0, 1.0, 2.0, 3.0, 4.0, 5.0]
+    );
+
+    let mask = Tensor::<bool>::from_slice(&ctx, &[true, false, true]).unwrap();
+    let other = Tensor::<bool>::from_slice(&ctx, &[false, false, true]).unwrap();
+    let y = Tensor::stack(&[&mask, &other], 0).unwrap();
+    assert_eq!(
+        y.to_vec().unwrap(),
+        vec![true, false, true, false, false, true]
+    );
+}
+
+#[test]
+fn test_stack_invalid() {
+    let ctx = Context::try_default().unwrap();
+    let a = Tensor::<f32>::constant(&ctx, &[2, 3], &[0.0]).unwrap();
+    let b = Tensor::<f32>::constant(&ctx, &[3, 2], &[0.0]).unwrap();
+
+    assert!(Tensor::<f32>::stack(&[], 0).is_err());
+    assert!(Tensor::stack(&[&a, &b], 0).is_err());
+    assert!(Tensor::stack(&[&a, &a], 3).is_err());
+    assert!(Tensor::stack(&[&a, &a], 2).is_ok());
+}
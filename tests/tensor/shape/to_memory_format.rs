@@ -0,0 +1,50 @@
+//! Tests for `Tensor::to_memory_format` operation.
+
+use xnn::{Context, MemoryFormat, Tensor};
+
+#[test]
+fn test_to_memory_format() {
+    let ctx = Context::try_default().unwrap();
+    let data: Vec<u32> = (0..24).collect();
+    let t = Tensor::<u32>::from_shape_slice(&ctx, &[2, 3, 2, 2], &data).unwrap();
+    assert_eq!(t.memory_format(), MemoryFormat::ChannelsFirst);
+
+    let nhwc = t.to_memory_format(MemoryFormat::ChannelsLast).unwrap();
+    assert_eq!(nhwc.dimensions(), &[2, 3, 2, 2]);
+    assert_eq!(nhwc.memory_format(), MemoryFormat::ChannelsLast);
+    assert_eq!(nhwc.to_vec().unwrap(), data);
+
+    // The buffer holds the channels of each pixel contiguously.
+    let physical = nhwc.permute(&[0, 2, 3, 1]).unwrap();
+    assert_eq!(physical.to_vec().unwrap()[..6], [0, 4, 8, 1, 5, 9]);
+
+    let nchw = nhwc.to_memory_format(MemoryFormat::ChannelsFirst).unwrap();
+    assert_eq!(nchw.memory_format(), MemoryFormat::ChannelsFirst);
+    assert_eq!(nchw.to_vec().unwrap(), data);
+}
+
+#[test]
+fn test_to_memory_format_propagates() {
+    let ctx = Context::try_default().unwrap();
+    let data: Vec<f32> = (0..16u8).map(f32::from).collect();
+    let t = Tensor::<f32>::from_shape_slice(&ctx, &[1, 4, 2, 2], &data)
+        .unwrap()
+        .to_memory_format(MemoryFormat::ChannelsLast)
+        .unwrap();
+
+    let y = t.neg().unwrap();
+    assert_eq!(y.memory_format(), MemoryFormat::ChannelsLast);
+    let expected: Vec<f32> = data.iter().map(|x| -x).collect();
+    assert_eq!(y.to_vec().unwrap(), expected);
+
+    let same = t.to_memory_format(MemoryFormat::ChannelsLast).unwrap();
+    assert_eq!(same.to_vec().unwrap(), data);
+}
+
+#[test]
+fn test_to_memory_format_invalid() {
+    let ctx = Context::try_default().unwrap();
+    let t = Tensor::<f32>::constant(&ctx, &[2, 3, 4], &[0.0]).unwrap();
+    assert_eq!(t.memory_format(), MemoryFormat::ChannelsFirst);
+    assert!(t.to_memory_format(MemoryFormat::ChannelsLast).is_err());
+}
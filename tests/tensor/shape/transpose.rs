@@ -0,0 +1,120 @@
+//! Tests for `Tensor::transpose` operation.
+
+use xnn::{Context, Tensor};
+
+#[test]
+fn test_transpose() {
+    let ctx = Context::try_default().unwrap();
+    let t = Tensor::<u32>::from_shape_slice(&ctx, &[2, 3], &[0, 1, 2, 3, 4, 5]).unwrap();
+    let v = t.transpose(0, 1).unwrap();
+    assert_eq!(v.dimensions(), &[3, 2]);
+    assert_eq!(v.to_vec().unwrap(), vec![0, 3, 1, 4, 2, 5]);
+    assert_eq!(
+        v.transpose(1, 0).unwrap().to_vec().unwrap(),
+        t.to_vec().unwrap()
+    );
+}
+
+#[test]
+fn test_transpose_elementwise() {
+    let ctx = Context::try_default().unwrap();
+    let a =
+        Tensor::<f32>::from_shape_slice(&ctx, &[2, 3], &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]).unwrap();
+    let b = Tensor::<f32>::from_shape_slice(&ctx, &[3, 2], &[10.0, 20.0, 30.0, 40.0, 50.0, 60.0])
+        .unwrap();
+    let at = a.transpose(0, 1).unwrap();
+
+    let sum = at.add(&b).unwrap();
+    assert_eq!(sum.dimensions(), &[3, 2]);
+    assert_eq!(
+        sum.to_vec().unwrap(),
+        vec![11.0, 24.0, 32.0, 45.0, 53.0, 66.0]
+    );
+
+    let neg = at.neg().unwrap();
+    assert_eq!(neg.dimensions(), &[3, 2]);
+    assert_eq!(
+        neg.to_vec().unwrap(),
+        vec![-1.0, -4.0, -2.0, -5.0, -3.0, -6.0]
+    );
+
+    let relu = at.sub(&b).unwrap().relu().unwrap();
+    assert_eq!(relu.to_vec().unwrap(), vec![0.0; 6]);
+}
+
+#[test]
+fn test_transpose_matmul() {
+    let ctx = Context::try_default().unwrap();
+    let a =
+        Tensor::<f32>::from_shape_slice(&ctx, &[2, 3], &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]).unwrap();
+    let b = Tensor::<f32>::from_shape_slice(&ctx, &[2, 2], &[1.0, 0.0, 2.0, 1.0]).unwrap();
+
+    let expected = a.matmul(&b, true, false).unwrap().to_vec().unwrap();
+    let at = a.transpose(0, 1).unwrap();
+    assert_eq!(
+        at.matmul(&b, false, false).unwrap().to_vec().unwrap(),
+        expected
+    );
+
+    let expected = b.matmul(&a, true, false).unwrap().to_vec().unwrap();
+    let bt = b.transpose(1, 0).unwrap();
+    assert_eq!(
+        bt.matmul(&a, false, false).unwrap().to_vec().unwrap(),
+        expected
+    );
+    assert_eq!(
+        b.matmul(&at, false, true).unwrap().to_vec().unwrap(),
+        b.matmul(&a, false, false).unwrap().to_vec().unwrap()
+    );
+}
+
+#[test]
+fn test_transpose_reduce_and_softmax() {
+    let ctx = Context::try_default().unwrap();
+    let t =
+        Tensor::<f32>::from_shape_slice(&ctx, &[2, 3], &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]).unwrap();
+    let v = t.transpose(0, 1).unwrap();
+
+    let sum = v.sum_reduce(&[1], false).unwrap();
+    assert_eq!(sum.dimensions(), &[3, 1]);
+    assert_eq!(sum.to_vec().unwrap(), vec![5.0, 7.0, 9.0]);
+
+    let softmax = v.softmax().unwrap().transpose(0, 1).unwrap();
+    let reference = Tensor::<f32>::from_shape_slice(&ctx, &[3, 2], &[1.0, 4.0, 2.0, 5.0, 3.0, 6.0])
+        .unwrap()
+        .softmax()
+        .unwrap()
+        .transpose(0, 1)
+        .unwrap();
+    assert_eq!(softmax.dimensions(), &[2, 3]);
+    crate::assert_vec_relative_eq(
+        &softmax.to_vec().unwrap(),
+        &reference.to_vec().unwrap(),
+        1e-6,
+    );
+}
+
+#[test]
+fn test_transpose_reshape_copies() {
+    let ctx = Context::try_default().unwrap();
+    let t = Tensor::<u32>::from_shape_slice(&ctx, &[2, 3], &[0, 1, 2, 3, 4, 5]).unwrap();
+    let r = t.transpose(0, 1).unwrap().reshape(&[6]).unwrap();
+    assert_eq!(r.to_vec().unwrap(), vec![0, 3, 1, 4, 2, 5]);
+    assert!(t.transpose(0, 1).unwrap().view(&[6]).is_err());
+}
+
+#[test]
+fn test_transpose_in_place_write() {
+    let ctx = Context::try_default().unwrap();
+    let t = Tensor::<f32>::constant(&ctx, &[2, 3], &[0.0]).unwrap();
+    let mut v = t.transpose(0, 1).unwrap();
+    assert!(v.write_slice(0, &[1.0]).is_err());
+}
+
+#[test]
+fn test_transpose_invalid() {
+    let ctx = Context::try_default().unwrap();
+    let t = Tensor::<f32>::constant(&ctx, &[2, 3], &[0.0]).unwrap();
+    assert!(t.transpose(0, 2).is_err());
+    assert!(t.transpose(2, 0).is_err());
+}
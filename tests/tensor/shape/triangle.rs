@@ -0,0 +1,97 @@
+//! Tests for `Tensor::tril`, `Tensor::triu` and `Tensor::causal_mask` operations.
+
+use xnn::{Context, Tensor};
+
+#[test]
+fn test_tril() {
+    let ctx = Context::try_default().unwrap();
+    let data: Vec<u32> = (1..=9).collect();
+    let t = Tensor::<u32>::from_shape_slice(&ctx, &[3, 3], &data).unwrap();
+
+    assert_eq!(
+        t.tril(0).unwrap().to_vec().unwrap(),
+        vec![1, 0, 0, 4, 5, 0, 7, 8, 9]
+    );
+    assert_eq!(
+        t.tril(1).unwrap().to_vec().unwrap(),
+        vec![1, 2, 0, 4, 5, 6, 7, 8, 9]
+    );
+    assert_eq!(
+        t.tril(-1).unwrap().to_vec().unwrap(),
+        vec![0, 0, 0, 4, 0, 0, 7, 8, 0]
+    );
+}
+
+#[test]
+fn test_triu() {
+    let ctx = Context::try_default().unwrap();
+    let data: Vec<u32> = (1..=9).collect();
+    let t = Tensor::<u32>::from_shape_slice(&ctx, &[3, 3], &data).unwrap();
+
+    assert_eq!(
+        t.triu(0).unwrap().to_vec().unwrap(),
+        vec![1, 2, 3, 0, 5, 6, 0, 0, 9]
+    );
+    assert_eq!(
+        t.triu(1).unwrap().to_vec().unwrap(),
+        vec![0, 2, 3, 0, 0, 6, 0, 0, 0]
+    );
+    assert_eq!(
+        t.triu(-1).unwrap().to_vec().unwrap(),
+        vec![1, 2, 3, 4, 5, 6, 0, 8, 9]
+    );
+}
+
+#[test]
+fn test_triangle_batched_rectangular() {
+    let ctx = Context::try_default().unwrap();
+    let t = Tensor::<f32>::constant(&ctx, &[2, 2, 3], &[1.0]).unwrap();
+
+    let lower = t.tril(0).unwrap();
+    assert_eq!(lower.dimensions(), &[2, 2, 3]);
+    assert_eq!(
+        lower.to_vec().unwrap(),
+        vec![1.0, 0.0, 0.0, 1.0, 1.0, 0.0, 1.0, 0.0, 0.0, 1.0, 1.0, 0.0]
+    );
+    assert_eq!(
+        t.triu(0).unwrap().to_vec().unwrap(),
+        vec![1.0, 1.0, 1.0, 0.0, 1.0, 1.0, 1.0, 1.0, 1.0, 0.0, 1.0, 1.0]
+    );
+}
+
+#[test]
+fn test_triangle_strided() {
+    let ctx = Context::try_default().unwrap();
+    let data: Vec<u32> = (1..=4).collect();
+    let t = Tensor::<u32>::from_shape_slice(&ctx, &[2, 2], &data)
+        .unwrap()
+        .transpose(0, 1)
+        .unwrap();
+
+    assert_eq!(t.tril(0).unwrap().to_vec().unwrap(), vec![1, 0, 2, 4]);
+}
+
+#[test]
+fn test_triangle_invalid() {
+    let ctx = Context::try_default().unwrap();
+    let t = Tensor::<f32>::from_slice(&ctx, &[1.0, 2.0]).unwrap();
+    assert!(t.tril(0).is_err());
+    assert!(t.triu(0).is_err());
+}
+
+#[test]
+fn test_causal_mask() {
+    let ctx = Context::try_default().unwrap();
+    let mask = Tensor::causal_mask(&ctx, 3).unwrap();
+
+    assert_eq!(mask.dimensions(), &[3, 3]);
+    assert_eq!(
+        mask.to_vec().unwrap(),
+        vec![true, false, false, true, true, false, true, true, true]
+    );
+
+    let scores = Tensor::<f32>::constant(&ctx, &[3, 3], &[0.0]).unwrap();
+    let attention = scores.masked_softmax(&mask).unwrap().to_vec().unwrap();
+    assert_eq!(attention[..3], [1.0, 0.0, 0.0]);
+    assert_eq!(attention[3..6], [0.5, 0.5, 0.0]);
+}
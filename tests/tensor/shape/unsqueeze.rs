@@ -0,0 +1,46 @@
+//! Tests for `Tensor::unsqueeze` operation.
+
+use xnn::{Context, Tensor};
+
+#[test]
+fn test_unsqueeze() {
+    let ctx = Context::try_default().unwrap();
+    let t = Tensor::<u32>::from_shape_slice(&ctx, &[2, 3], &[0, 1, 2, 3, 4, 5]).unwrap();
+
+    for (axis, dims) in [(0, [1, 2, 3]), (1, [2, 1, 3]), (2, [2, 3, 1])] {
+        let u = t.unsqueeze(axis).unwrap();
+        assert_eq!(u.dimensions(), &dims);
+        assert_eq!(u.to_vec().unwrap(), vec![0, 1, 2, 3, 4, 5]);
+    }
+}
+
+#[test]
+fn test_unsqueeze_broadcast() {
+    let ctx = Context::try_default().unwrap();
+    let x =
+        Tensor::<f32>::from_shape_slice(&ctx, &[2, 3], &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]).unwrap();
+    let bias = Tensor::<f32>::from_slice(&ctx, &[10.0, 20.0]).unwrap();
+
+    let y = x.add(&bias.unsqueeze(1).unwrap()).unwrap();
+    assert_eq!(y.dimensions(), &[2, 3]);
+    assert_eq!(
+        y.to_vec().unwrap(),
+        vec![11.0, 12.0, 13.0, 24.0, 25.0, 26.0]
+    );
+}
+
+#[test]
+fn test_unsqueeze_shares_buffer() {
+    let ctx = Context::try_default().unwrap();
+    let t = Tensor::<f32>::constant(&ctx, &[3], &[0.0]).unwrap();
+    let mut u = t.unsqueeze(0).unwrap();
+    u.write_slice(1, &[5.0]).unwrap();
+    assert_eq!(t.to_vec().unwrap(), vec![0.0, 5.0, 0.0]);
+}
+
+#[test]
+fn test_unsqueeze_invalid() {
+    let ctx = Context::try_default().unwrap();
+    let t = Tensor::<f32>::constant(&ctx, &[2, 3], &[0.0]).unwrap();
+    assert!(t.unsqueeze(3).is_err());
+}
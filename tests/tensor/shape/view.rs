@@ -0,0 +1,29 @@
+//! Tests for `Tensor::view` operation.
+
+use xnn::{Context, Tensor};
+
+#[test]
+fn test_view() {
+    let ctx = Context::try_default().unwrap();
+    let t = Tensor::<u32>::from_shape_slice(&ctx, &[2, 2, 2], &[0, 1, 2, 3, 4, 5, 6, 7]).unwrap();
+    let v = t.view(&[4, 2]).unwrap();
+    assert_eq!(v.dimensions(), &[4, 2]);
+    assert_eq!(v.to_vec().unwrap(), vec![0, 1, 2, 3, 4, 5, 6, 7]);
+}
+
+#[test]
+fn test_view_shares_buffer() {
+    let ctx = Context::try_default().unwrap();
+    let t = Tensor::<f32>::constant(&ctx, &[2, 3], &[0.0]).unwrap();
+    let mut v = t.view(&[6]).unwrap();
+    v.write_slice(4, &[7.0]).unwrap();
+    assert_eq!(t.to_vec().unwrap(), vec![0.0, 0.0, 0.0, 0.0, 7.0, 0.0]);
+}
+
+#[test]
+fn test_view_invalid() {
+    let ctx = Context::try_default().unwrap();
+    let t = Tensor::<f32>::constant(&ctx, &[2, 3], &[0.0]).unwrap();
+    assert!(t.view(&[5]).is_err());
+    assert!(t.view(&[0]).is_err());
+}
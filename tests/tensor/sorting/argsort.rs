@@ -0,0 +1,67 @@
+//! Tests for `Tensor::argsort` operation.
+
+use xnn::{Context, Tensor};
+
+#[test]
+fn test_argsort_ascending() {
+    let ctx = Context::try_default().unwrap();
+    let t = Tensor::<f32>::from_slice(&ctx, &[3.0, -1.0, 2.0, 0.5, 7.0]).unwrap();
+    let result = t.argsort(false, true).unwrap();
+    assert_eq!(result.dimensions(), &[5]);
+    assert_eq!(result.to_vec().unwrap(), vec![1, 3, 2, 0, 4]);
+}
+
+#[test]
+fn test_argsort_descending_rows() {
+    let ctx = Context::try_default().unwrap();
+    let t = Tensor::<i32>::from_shape_slice(&ctx, &[2, 3], &[1, 3, 2, -5, -7, 0]).unwrap();
+    let result = t.argsort(true, true).unwrap();
+    assert_eq!(result.dimensions(), &[2, 3]);
+    assert_eq!(result.to_vec().unwrap(), vec![1, 2, 0, 2, 0, 1]);
+}
+
+#[test]
+fn test_argsort_stable_ties() {
+    let ctx = Context::try_default().unwrap();
+    let data = [2u32, 1, 2, 1, 0, 2, 1, 0, 2];
+    let t = Tensor::<u32>::from_slice(&ctx, &data).unwrap();
+
+    let ascending = t.argsort(false, true).unwrap();
+    assert_eq!(ascending.to_vec().unwrap(), vec![4, 7, 1, 3, 6, 0, 2, 5, 8]);
+
+    let descending = t.argsort(true, true).unwrap();
+    assert_eq!(
+        descending.to_vec().unwrap(),
+        vec![0, 2, 5, 8, 1, 3, 6, 4, 7]
+    );
+}
+
+#[test]
+fn test_argsort_stable_long_rows() {
+    let ctx = Context::try_default().unwrap();
+    let data: Vec<u32> = (0..1500u32).map(|i| (i * 7919) % 13).collect();
+    let t = Tensor::<u32>::from_slice(&ctx, &data).unwrap();
+    let result = t.argsort(false, true).unwrap();
+
+    let mut expected: Vec<u32> = (0..1500).collect();
+    expected.sort_by_key(|&i| data[i as usize]);
+    assert_eq!(result.to_vec().unwrap(), expected);
+}
+
+#[test]
+fn test_argsort_unstable_is_permutation() {
+    let ctx = Context::try_default().unwrap();
+    let t = Tensor::<f32>::from_slice(&ctx, &[1.0, 0.0, 1.0, 0.0, 1.0]).unwrap();
+    let mut result = t.argsort(false, false).unwrap().to_vec().unwrap();
+    let (low, high) = result.split_at_mut(2);
+    low.sort_unstable();
+    high.sort_unstable();
+    assert_eq!(result, vec![1, 3, 0, 2, 4]);
+}
+
+#[test]
+fn test_argsort_scalar() {
+    let ctx = Context::try_default().unwrap();
+    let t = Tensor::<f32>::from_shape_slice(&ctx, &[], &[1.0]).unwrap();
+    assert!(t.argsort(false, true).is_err());
+}
@@ -0,0 +1,5 @@
+//! Sorting operation tests.
+
+mod argsort;
+mod sort;
+mod topk;
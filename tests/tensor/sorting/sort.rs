@@ -0,0 +1,35 @@
+//! Tests for `Tensor::sort` operation.
+
+use xnn::{Context, Tensor};
+
+#[test]
+fn test_sort_ascending() {
+    let ctx = Context::try_default().unwrap();
+    let t =
+        Tensor::<f32>::from_shape_slice(&ctx, &[2, 3], &[3.0, 1.0, 2.0, 0.0, -4.0, 9.0]).unwrap();
+    let (values, indices) = t.sort(false, true).unwrap();
+    assert_eq!(values.dimensions(), &[2, 3]);
+    assert_eq!(
+        values.to_vec().unwrap(),
+        vec![1.0, 2.0, 3.0, -4.0, 0.0, 9.0]
+    );
+    assert_eq!(indices.to_vec().unwrap(), vec![1, 2, 0, 1, 0, 2]);
+}
+
+#[test]
+fn test_sort_descending() {
+    let ctx = Context::try_default().unwrap();
+    let t = Tensor::<i32>::from_slice(&ctx, &[4, -2, 4, 8, 0]).unwrap();
+    let (values, indices) = t.sort(true, true).unwrap();
+    assert_eq!(values.to_vec().unwrap(), vec![8, 4, 4, 0, -2]);
+    assert_eq!(indices.to_vec().unwrap(), vec![3, 0, 2, 4, 1]);
+}
+
+#[test]
+fn test_sort_single_element_rows() {
+    let ctx = Context::try_default().unwrap();
+    let t = Tensor::<u32>::from_shape_slice(&ctx, &[3, 1], &[5, 3, 4]).unwrap();
+    let (values, indices) = t.sort(false, true).unwrap();
+    assert_eq!(values.to_vec().unwrap(), vec![5, 3, 4]);
+    assert_eq!(indices.to_vec().unwrap(), vec![0, 0, 0]);
+}
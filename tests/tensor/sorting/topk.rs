@@ -0,0 +1,32 @@
+//! Tests for `Tensor::topk` operation.
+
+use xnn::{Context, Tensor};
+
+#[test]
+fn test_topk_basic() {
+    let ctx = Context::try_default().unwrap();
+    let t = Tensor::<f32>::from_slice(&ctx, &[0.1, 0.9, 0.3, 0.7, 0.5]).unwrap();
+    let (values, indices) = t.topk(3, true).unwrap();
+    assert_eq!(values.dimensions(), &[3]);
+    assert_eq!(values.to_vec().unwrap(), vec![0.9, 0.7, 0.5]);
+    assert_eq!(indices.to_vec().unwrap(), vec![1, 3, 4]);
+}
+
+#[test]
+fn test_topk_stable_ties_at_cutoff() {
+    let ctx = Context::try_default().unwrap();
+    let data = [1, 5, 3, 5, 3, 3, 0, 3];
+    let t = Tensor::<i32>::from_shape_slice(&ctx, &[2, 4], &data).unwrap();
+    let (values, indices) = t.topk(2, true).unwrap();
+    assert_eq!(values.dimensions(), &[2, 2]);
+    assert_eq!(values.to_vec().unwrap(), vec![5, 5, 3, 3]);
+    assert_eq!(indices.to_vec().unwrap(), vec![1, 3, 0, 1]);
+}
+
+#[test]
+fn test_topk_invalid_k() {
+    let ctx = Context::try_default().unwrap();
+    let t = Tensor::<f32>::from_slice(&ctx, &[1.0, 2.0]).unwrap();
+    assert!(t.topk(0, true).is_err());
+    assert!(t.topk(3, true).is_err());
+}
@@ -0,0 +1,113 @@
+//! Tests for GPU-resident stop flags.
+
+use xnn::{Context, Tensor};
+
+#[test]
+fn test_stop_flag() {
+    let ctx = Context::try_default().unwrap();
+    let a = Tensor::<f32>::from_slice(&ctx, &[1.0, 2.0, 3.0]).unwrap();
+    let tokens = Tensor::<u32>::from_slice(&ctx, &[5, 9, 2]).unwrap();
+    let stop = Tensor::<u32>::from_slice(&ctx, &[9]).unwrap();
+
+    let mut flag = Tensor::<bool>::from_slice(&ctx, &[false]).unwrap();
+    ctx.set_stop_flag(Some(&flag)).unwrap();
+
+    assert_eq!(a.add(&a).unwrap().to_vec().unwrap(), vec![2.0, 4.0, 6.0]);
+
+    flag.set_if_any_(
+        &tokens
+            .eq(&Tensor::<u32>::from_slice(&ctx, &[7]).unwrap())
+            .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(flag.to_vec().unwrap(), vec![false]);
+    assert_eq!(a.neg().unwrap().to_vec().unwrap(), vec![-1.0, -2.0, -3.0]);
+
+    flag.set_if_any_(&tokens.eq(&stop).unwrap()).unwrap();
+    assert_eq!(flag.to_vec().unwrap(), vec![true]);
+    assert_eq!(a.add(&a).unwrap().to_vec().unwrap(), vec![0.0; 3]);
+
+    flag.write_slice(0, &[false]).unwrap();
+    assert_eq!(a.add(&a).unwrap().to_vec().unwrap(), vec![2.0, 4.0, 6.0]);
+
+    flag.write_slice(0, &[true]).unwrap();
+    ctx.set_stop_flag(None).unwrap();
+    assert_eq!(a.add(&a).unwrap().to_vec().unwrap(), vec![2.0, 4.0, 6.0]);
+}
+
+#[test]
+fn test_stop_flag_indirect() {
+    let ctx = Context::try_default().unwrap();
+    let t = Tensor::<u32>::from_slice(&ctx, &[4, 5, 6]).unwrap();
+    let indices = Tensor::<u32>::from_slice(&ctx, &[2, 0]).unwrap();
+    let count = Tensor::<u32>::from_slice(&ctx, &[2]).unwrap();
+
+    let flag = Tensor::<bool>::from_slice(&ctx, &[false]).unwrap();
+    ctx.set_stop_flag(Some(&flag)).unwrap();
+    assert_eq!(
+        t.take_rows(&indices, &count).unwrap().to_vec().unwrap(),
+        vec![6, 4]
+    );
+
+    let mut flag = flag;
+    flag.write_slice(0, &[true]).unwrap();
+    assert_eq!(
+        t.take_rows(&indices, &count).unwrap().to_vec().unwrap(),
+        vec![0, 0]
+    );
+}
+
+#[test]
+fn test_stop_flag_strided_readback() {
+    let ctx = Context::try_default().unwrap();
+    let t =
+        Tensor::<f32>::from_shape_slice(&ctx, &[2, 3], &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]).unwrap();
+
+    let flag = Tensor::<bool>::from_slice(&ctx, &[true]).unwrap();
+    ctx.set_stop_flag(Some(&flag)).unwrap();
+
+    let transposed = t.transpose(0, 1).unwrap();
+    assert_eq!(
+        transposed.to_vec().unwrap(),
+        vec![1.0, 4.0, 2.0, 5.0, 3.0, 6.0]
+    );
+    assert_eq!(
+        transposed.contiguous().unwrap().to_vec().unwrap(),
+        vec![1.0, 4.0, 2.0, 5.0, 3.0, 6.0]
+    );
+
+    let narrowed = t.narrow(1, 1, 2).unwrap();
+    assert_eq!(narrowed.to_vec().unwrap(), vec![2.0, 3.0, 5.0, 6.0]);
+    assert_eq!(t.read_region(&[0, 1], &[2, 1]).unwrap(), vec![2.0, 5.0]);
+
+    assert_eq!(t.neg().unwrap().to_vec().unwrap(), vec![0.0; 6]);
+    ctx.set_stop_flag(None).unwrap();
+}
+
+#[test]
+fn test_set_if_any_aliased() {
+    let ctx = Context::try_default().unwrap();
+    let mut flag = Tensor::<bool>::from_slice(&ctx, &[true]).unwrap();
+    let condition = flag.reshape(&[1]).unwrap();
+    flag.set_if_any_(&condition).unwrap();
+    assert_eq!(flag.to_vec().unwrap(), vec![true]);
+
+    flag.write_slice(0, &[false]).unwrap();
+    let condition = flag.reshape(&[1, 1]).unwrap();
+    flag.set_if_any_(&condition).unwrap();
+    assert_eq!(flag.to_vec().unwrap(), vec![false]);
+}
+
+#[test]
+fn test_stop_flag_invalid() {
+    let ctx = Context::try_default().unwrap();
+    let mut wide = Tensor::<bool>::from_slice(&ctx, &[false, false]).unwrap();
+    assert!(ctx.set_stop_flag(Some(&wide)).is_err());
+
+    let condition = Tensor::<bool>::from_slice(&ctx, &[true]).unwrap();
+    assert!(wide.set_if_any_(&condition).is_err());
+
+    let mut row = wide.narrow(0, 1, 1).unwrap();
+    assert!(ctx.set_stop_flag(Some(&row)).is_err());
+    assert!(row.set_if_any_(&condition).is_err());
+}
@@ -0,0 +1,44 @@
+//! Tests for `Tensor::write_slice` operation.
+
+use xnn::{Context, Df64, Tensor};
+
+#[test]
+fn test_write_slice_chunks() {
+    let ctx = Context::try_default().unwrap();
+    let mut t = Tensor::<f32>::constant(&ctx, &[2, 3], &[0.0]).unwrap();
+    t.write_slice(0, &[1.0, 2.0]).unwrap();
+    t.write_slice(2, &[3.0, 4.0, 5.0]).unwrap();
+    t.write_slice(5, &[6.0]).unwrap();
+    assert_eq!(t.to_vec().unwrap(), vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+}
+
+#[test]
+fn test_write_slice_after_compute() {
+    let ctx = Context::try_default().unwrap();
+    let mut t = Tensor::<i32>::constant(&ctx, &[4], &[7]).unwrap();
+    t.write_slice(1, &[-1, -2]).unwrap();
+    let doubled = t.add(&t).unwrap();
+    assert_eq!(doubled.to_vec().unwrap(), vec![14, -2, -4, 14]);
+}
+
+#[test]
+fn test_write_slice_bool_and_df64() {
+    let ctx = Context::try_default().unwrap();
+    let mut mask = Tensor::<bool>::constant(&ctx, &[3], &[false]).unwrap();
+    mask.write_slice(2, &[true]).unwrap();
+    assert_eq!(mask.to_vec().unwrap(), vec![false, false, true]);
+
+    let mut t = Tensor::<Df64>::constant(&ctx, &[2], &[Df64::from(0.0)]).unwrap();
+    t.write_slice(1, &[Df64::from(2.5)]).unwrap();
+    let values: Vec<f64> = t.to_vec().unwrap().into_iter().map(f64::from).collect();
+    assert_eq!(values, vec![0.0, 2.5]);
+}
+
+#[test]
+fn test_write_slice_out_of_bounds() {
+    let ctx = Context::try_default().unwrap();
+    let mut t = Tensor::<f32>::constant(&ctx, &[4], &[0.0]).unwrap();
+    assert!(t.write_slice(3, &[1.0, 2.0]).is_err());
+    assert!(t.write_slice(usize::MAX, &[1.0]).is_err());
+    assert!(t.write_slice(4, &[]).is_ok());
+}
@@ -0,0 +1,121 @@
+//! Tests for `DilatedLayer`.
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use xnn::wavenet::DilatedLayer;
+use xnn::{Context, Tensor};
+
+#[test]
+fn test_dilated_layer_matches_cpu() {
+    let ctx = Context::try_default().unwrap();
+    let mut rng = StdRng::seed_from_u64(43);
+    let (k, c, h, s, l, dilation) = (2, 3, 4, 2, 12, 2);
+
+    let mut random =
+        |len: usize| -> Vec<f32> { (0..len).map(|_| rng.random_range(-1.0..1.0)).collect() };
+    let x = random(c * l);
+    let filter = random(k * c * h);
+    let gate = random(k * c * h);
+    let residual = random(h * c);
+    let skip = random(h * s);
+
+    let tensor = |dims: &[usize], data: &[f32]| Tensor::from_shape_slice(&ctx, dims, data).unwrap();
+    let layer = DilatedLayer::new(
+        tensor(&[k, c, h], &filter),
+        tensor(&[k, c, h], &gate),
+        tensor(&[h, c], &residual),
+        tensor(&[h, s], &skip),
+        dilation,
+    )
+    .unwrap();
+
+    assert_eq!(layer.lookback(), 2);
+    assert_eq!(layer.channels(), c);
+    assert_eq!(layer.skip_channels(), s);
+    assert_eq!(layer.dilation(), dilation);
+
+    let (out, skip_out) = layer.forward(&tensor(&[1, c, l], &x)).unwrap();
+    let (expected_out, expected_skip) = crate::cpu_layer(
+        &x,
+        &filter,
+        &gate,
+        &residual,
+        &skip,
+        [k, c, h, s],
+        l,
+        dilation,
+    );
+
+    assert_eq!(out.dimensions(), &[1, c, l]);
+    assert_eq!(skip_out.dimensions(), &[1, s, l]);
+    crate::assert_close(&out.to_vec().unwrap(), &expected_out);
+    crate::assert_close(&skip_out.to_vec().unwrap(), &expected_skip);
+}
+
+#[test]
+fn test_dilated_layer_invalid() {
+    let ctx = Context::try_default().unwrap();
+    let zeros = |dims: &[usize]| Tensor::<f32>::constant(&ctx, dims, &[0.0]).unwrap();
+
+    assert!(
+        DilatedLayer::new(
+            zeros(&[2, 3, 4]),
+            zeros(&[2, 3, 4]),
+            zeros(&[4, 3]),
+            zeros(&[4, 2]),
+            0
+        )
+        .is_err()
+    );
+    assert!(
+        DilatedLayer::new(
+            zeros(&[2, 3, 4]),
+            zeros(&[3, 3, 4]),
+            zeros(&[4, 3]),
+            zeros(&[4, 2]),
+            1
+        )
+        .is_err()
+    );
+    assert!(
+        DilatedLayer::new(
+            zeros(&[2, 3, 4]),
+            zeros(&[2, 3, 4]),
+            zeros(&[4, 2]),
+            zeros(&[4, 2]),
+            1
+        )
+        .is_err()
+    );
+    assert!(
+        DilatedLayer::new(
+            zeros(&[2, 3, 4]),
+            zeros(&[2, 3, 4]),
+            zeros(&[4, 3]),
+            zeros(&[3, 2]),
+            1
+        )
+        .is_err()
+    );
+    assert!(
+        DilatedLayer::new(
+            zeros(&[3, 4]),
+            zeros(&[3, 4]),
+            zeros(&[4, 3]),
+            zeros(&[4, 2]),
+            1
+        )
+        .is_err()
+    );
+
+    let layer = DilatedLayer::new(
+        zeros(&[2, 3, 4]),
+        zeros(&[2, 3, 4]),
+        zeros(&[4, 3]),
+        zeros(&[4, 2]),
+        1,
+    )
+    .unwrap();
+    assert!(layer.forward(&zeros(&[1, 2, 8])).is_err());
+    assert!(layer.forward(&zeros(&[3, 8])).is_err());
+}
@@ -0,0 +1,84 @@
+//! Tests for `DilatedStack`.
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use xnn::wavenet::{DilatedLayer, DilatedStack};
+use xnn::{Context, Tensor};
+
+#[test]
+fn test_dilated_stack_matches_cpu() {
+    let ctx = Context::try_default().unwrap();
+    let mut rng = StdRng::seed_from_u64(44);
+    let (k, c, h, s, l) = (2, 2, 3, 2, 16);
+
+    let mut random =
+        |len: usize| -> Vec<f32> { (0..len).map(|_| rng.random_range(-1.0..1.0)).collect() };
+    let tensor = |dims: &[usize], data: &[f32]| Tensor::from_shape_slice(&ctx, dims, data).unwrap();
+
+    let x = random(c * l);
+    let mut layers = Vec::new();
+    let mut expected_out = x.clone();
+    let mut expected_skip = vec![0.0; s * l];
+
+    for dilation in [1, 2, 4] {
+        let (filter, gate) = (random(k * c * h), random(k * c * h));
+        let (residual, skip) = (random(h * c), random(h * s));
+
+        let (out, skip_out) = crate::cpu_layer(
+            &expected_out,
+            &filter,
+            &gate,
+            &residual,
+            &skip,
+            [k, c, h, s],
+            l,
+            dilation,
+        );
+        expected_out = out;
+        for (sum, value) in expected_skip.iter_mut().zip(skip_out) {
+            *sum += value;
+        }
+
+        layers.push(
+            DilatedLayer::new(
+                tensor(&[k, c, h], &filter),
+                tensor(&[k, c, h], &gate),
+                tensor(&[h, c], &residual),
+                tensor(&[h, s], &skip),
+                dilation,
+            )
+            .unwrap(),
+        );
+    }
+
+    let stack = DilatedStack::new(layers).unwrap();
+    assert_eq!(stack.layers().len(), 3);
+    assert_eq!(stack.receptive_field(), 8);
+
+    let (out, skips) = stack.forward(&tensor(&[1, c, l], &x)).unwrap();
+    assert_eq!(out.dimensions(), &[1, c, l]);
+    assert_eq!(skips.dimensions(), &[1, s, l]);
+    crate::assert_close(&out.to_vec().unwrap(), &expected_out);
+    crate::assert_close(&skips.to_vec().unwrap(), &expected_skip);
+}
+
+#[test]
+fn test_dilated_stack_invalid() {
+    let ctx = Context::try_default().unwrap();
+    let zeros = |dims: &[usize]| Tensor::<f32>::constant(&ctx, dims, &[0.0]).unwrap();
+    let layer = |c: usize, s: usize| {
+        DilatedLayer::new(
+            zeros(&[2, c, 4]),
+            zeros(&[2, c, 4]),
+            zeros(&[4, c]),
+            zeros(&[4, s]),
+            1,
+        )
+        .unwrap()
+    };
+
+    assert!(DilatedStack::new(Vec::new()).is_err());
+    assert!(DilatedStack::new(vec![layer(3, 2), layer(2, 2)]).is_err());
+    assert!(DilatedStack::new(vec![layer(3, 2), layer(3, 1)]).is_err());
+    assert!(DilatedStack::new(vec![layer(3, 2), layer(3, 2)]).is_ok());
+}
@@ -0,0 +1,62 @@
+//! Dilated convolution stack integration tests.
+
+mod dilated_layer;
+mod dilated_stack;
+
+/// CPU reference of a dilated layer over `[c, l]` `x`, returning the `[c, l]` residual and
+/// `[s, l]` skip outputs.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn cpu_layer(
+    x: &[f32],
+    filter: &[f32],
+    gate: &[f32],
+    residual: &[f32],
+    skip: &[f32],
+    [k, c, h, s]: [usize; 4],
+    l: usize,
+    dilation: usize,
+) -> (Vec<f32>, Vec<f32>) {
+    let causal = |weight: &[f32], o: usize, t: usize| {
+        let mut acc = 0.0;
+        for j in 0..k {
+            let Some(src) = (t + j * dilation).checked_sub(dilation * (k - 1)) else {
+                continue;
+            };
+            for i in 0..c {
+                acc += x[i * l + src] * weight[(j * c + i) * h + o];
+            }
+        }
+        acc
+    };
+
+    let mut z = vec![0.0; h * l];
+    for o in 0..h {
+        for t in 0..l {
+            let g = 1.0 / (1.0 + (-causal(gate, o, t)).exp());
+            z[o * l + t] = causal(filter, o, t).tanh() * g;
+        }
+    }
+
+    let project = |weight: &[f32], out: usize| {
+        let mut y = vec![0.0; out * l];
+        for o in 0..out {
+            for t in 0..l {
+                y[o * l + t] = (0..h).map(|i| z[i * l + t] * weight[i * out + o]).sum();
+            }
+        }
+        y
+    };
+
+    let mut res = project(residual, c);
+    for (r, x) in res.iter_mut().zip(x) {
+        *r += x;
+    }
+    (res, project(skip, s))
+}
+
+pub(crate) fn assert_close(actual: &[f32], expected: &[f32]) {
+    assert_eq!(actual.len(), expected.len(), "length mismatch");
+    for (a, e) in actual.iter().zip(expected) {
+        approx::assert_relative_eq!(a, e, epsilon = 1e-4);
+    }
+}